@@ -0,0 +1,120 @@
+//! A contract exercising all the builtin types and the nested generic
+//! combinations, the canonical coverage artifact of the plugins.
+//!
+//! Its ABI is in `crates/parser/test_data/kitchen_sink.abi.json`, to be regenerated
+//! with `make generate_artifacts` when this contract changes.
+//!
+//! NOT COVERED: the fixed-size arrays and `u512`, which are not
+//! in the ABIs of the Cairo version of these contracts.
+use starknet::ContractAddress;
+
+#[derive(Serde, Drop)]
+struct Item {
+    id: u64,
+    owner: ContractAddress,
+    tags: Span<felt252>,
+}
+
+#[derive(Serde, Drop)]
+struct Wrapper<T> {
+    inner: T,
+    count: u32,
+}
+
+#[derive(Serde, Drop)]
+enum Kind {
+    Empty,
+    Amount: u256,
+    Pair: (i8, i128),
+    Items: Array<Item>,
+}
+
+#[starknet::contract]
+mod kitchen_sink {
+    use starknet::{ClassHash, ContractAddress, EthAddress};
+    use super::{Item, Wrapper, Kind};
+
+    #[storage]
+    struct Storage {}
+
+    #[event]
+    #[derive(Drop, starknet::Event)]
+    enum Event {
+        Created: Created,
+        Nested: NestedEvent,
+        #[flat]
+        Flat: FlatEvent,
+    }
+
+    #[derive(Drop, starknet::Event)]
+    struct Created {
+        #[key]
+        id: u64,
+        kind: Kind,
+    }
+
+    // Three levels of events: `Event::Nested(NestedEvent::Deep(DeepEvent::Leaf(..)))`.
+    #[derive(Drop, starknet::Event)]
+    enum NestedEvent {
+        Deep: DeepEvent,
+        Leaf: Leaf,
+    }
+
+    #[derive(Drop, starknet::Event)]
+    enum DeepEvent {
+        Leaf: Leaf,
+    }
+
+    #[derive(Drop, starknet::Event)]
+    struct Leaf {
+        #[key]
+        owner: ContractAddress,
+        value: Option<u128>,
+    }
+
+    #[derive(Drop, starknet::Event)]
+    enum FlatEvent {
+        Flattened: Flattened,
+    }
+
+    #[derive(Drop, starknet::Event)]
+    struct Flattened {
+        class_hash: ClassHash,
+    }
+
+    #[external(v0)]
+    fn nested(
+        self: @ContractState, value: Option<Result<Array<Item>, felt252>>
+    ) -> Option<Result<Array<Item>, felt252>> {
+        value
+    }
+
+    #[external(v0)]
+    fn non_zero(self: @ContractState, value: NonZero<felt252>) -> NonZero<felt252> {
+        value
+    }
+
+    #[external(v0)]
+    fn builtins(
+        self: @ContractState,
+        class_hash: ClassHash,
+        address: ContractAddress,
+        eth_address: EthAddress,
+        flag: bool,
+        text: ByteArray,
+        word: bytes31,
+        amount: u256,
+        delta: i64,
+    ) -> (Wrapper<Wrapper<Kind>>, Span<(u8, Option<Kind>)>) {
+        let wrapper = Wrapper { inner: Wrapper { inner: Kind::Empty, count: 1 }, count: 2 };
+        (wrapper, array![(1, Option::Some(Kind::Amount(amount))), (2, Option::None)].span())
+    }
+
+    #[external(v0)]
+    fn emit_all(ref self: ContractState, owner: ContractAddress) {
+        self.emit(Created { id: 1, kind: Kind::Pair((-1, 1)) });
+        self.emit(NestedEvent::Deep(DeepEvent::Leaf(Leaf { owner, value: Option::Some(1) })));
+        self.emit(NestedEvent::Leaf(Leaf { owner, value: Option::None }));
+        self.emit(FlatEvent::Flattened(Flattened { class_hash: 0x1.try_into().unwrap() }));
+    }
+}
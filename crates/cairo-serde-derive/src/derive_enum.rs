@@ -1,14 +1,18 @@
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
-use syn::{DataEnum, Ident, Type, Variant};
+use syn::{DataEnum, Generics, Ident, Type, Variant};
 use unzip_n::unzip_n;
 
-pub fn derive_enum(ident: Ident, data: DataEnum) -> TokenStream {
+use crate::CairoField;
+
+pub fn derive_enum(ident: Ident, generics: &Generics, data: DataEnum) -> syn::Result<TokenStream> {
+    let (impl_line, rust_type) = crate::impl_line_and_rust_type(&ident, generics);
+
     let matches = &data
         .variants
         .iter()
         .map(|v| derive_enum_matches(&ident, v))
-        .collect::<Vec<_>>();
+        .collect::<syn::Result<Vec<_>>>()?;
 
     unzip_n!(3);
     let (serialized_size, serialize, deserialize) = data
@@ -16,7 +20,7 @@ pub fn derive_enum(ident: Ident, data: DataEnum) -> TokenStream {
         .iter()
         .enumerate()
         .map(|(i, v)| derive_enum_variant(&ident, i, v))
-        .collect::<Vec<_>>()
+        .collect::<syn::Result<Vec<_>>>()?
         .into_iter()
         .unzip_n_vec();
 
@@ -32,6 +36,12 @@ pub fn derive_enum(ident: Ident, data: DataEnum) -> TokenStream {
 
     let cairo_serialize = quote! {
         fn cairo_serialize(rust: &Self::RustType) -> Vec<::starknet::core::types::Felt> {
+            let mut result = Vec::new();
+            <Self as ::cainome_cairo_serde::CairoSerde>::cairo_serialize_to(rust, &mut result);
+            result
+        }
+
+        fn cairo_serialize_to(rust: &Self::RustType, out: &mut Vec<::starknet::core::types::Felt>) {
             match rust {
                 #(
                     #matches => #serialize,
@@ -48,10 +58,17 @@ pub fn derive_enum(ident: Ident, data: DataEnum) -> TokenStream {
         .collect::<Vec<_>>();
     let cairo_deserialize = quote! {
         fn cairo_deserialize(felt: &[::starknet::core::types::Felt], offset: usize) -> Result<Self::RustType, ::cainome_cairo_serde::Error> {
+            <Self as ::cainome_cairo_serde::CairoSerde>::cairo_deserialize_with_size(felt, offset)
+                .map(|(value, _)| value)
+        }
+
+        fn cairo_deserialize_with_size(felt: &[::starknet::core::types::Felt], offset: usize) -> Result<(Self::RustType, usize), ::cainome_cairo_serde::Error> {
             let offset = offset + 1;
             #(
                 if felt[offset - 1] == ::starknet::core::types::Felt::from(#deserialize_matches) {
-                    return Ok(#deserialize);
+                    // +1 for the variant index.
+                    let (value, size) = #deserialize;
+                    return Ok((value, size + 1));
                 }
             )*
             Err(::cainome_cairo_serde::Error::Deserialize("Invalid variant Id".to_string()))
@@ -62,8 +79,8 @@ pub fn derive_enum(ident: Ident, data: DataEnum) -> TokenStream {
     // Any of the members of the composite type can have a dynamic size.
     // This is why we return `None` for the `SERIALIZED_SIZE` constant.
     let output = quote! {
-        impl ::cainome_cairo_serde::CairoSerde for #ident {
-            type RustType = Self;
+        #impl_line {
+            type RustType = #rust_type;
 
             const SERIALIZED_SIZE: Option<usize> = None;
 
@@ -72,36 +89,55 @@ pub fn derive_enum(ident: Ident, data: DataEnum) -> TokenStream {
             #cairo_deserialize
         }
     };
-    output
+    Ok(output)
 }
 
-fn derive_enum_matches(ident: &Ident, variant: &Variant) -> TokenStream {
+fn derive_enum_matches(ident: &Ident, variant: &Variant) -> syn::Result<TokenStream> {
     let variant_ident = variant.ident.clone();
-    let (fields, _) = fields_idents_and_types(&variant.fields);
-    match &variant.fields {
+    // The skipped fields are not bound.
+    let patterns = cairo_fields(&variant.fields)?
+        .into_iter()
+        .map(|f| match (&variant.fields, f.skip) {
+            (syn::Fields::Named(_), true) => {
+                let field = f.accessor;
+                quote!(#field: _)
+            }
+            (_, true) => quote!(_),
+            (_, false) => f.accessor,
+        })
+        .collect::<Vec<_>>();
+
+    Ok(match &variant.fields {
         syn::Fields::Named(_) => quote! {
-            #ident::#variant_ident { #(#fields,)* }
+            #ident::#variant_ident { #(#patterns,)* }
         },
         syn::Fields::Unnamed(_) => quote! {
-            #ident::#variant_ident(#(#fields,)*)
+            #ident::#variant_ident(#(#patterns,)*)
         },
         syn::Fields::Unit => quote! {
             #ident::#variant_ident
         },
-    }
+    })
 }
 
 fn derive_enum_variant(
     ident: &Ident,
     index: usize,
     variant: &Variant,
-) -> (TokenStream, TokenStream, TokenStream) {
-    let (fields, types) = fields_idents_and_types(&variant.fields);
-    (
+) -> syn::Result<(TokenStream, TokenStream, TokenStream)> {
+    let cairo_fields = cairo_fields(&variant.fields)?;
+    let serialized = cairo_fields.iter().filter(|f| !f.skip).collect::<Vec<_>>();
+    let fields = serialized
+        .iter()
+        .map(|f| f.accessor.clone())
+        .collect::<Vec<_>>();
+    let types = serialized.iter().map(|f| f.ty.clone()).collect::<Vec<_>>();
+
+    Ok((
         derive_variant_cairo_serialized_size(&fields, &types),
         derive_variant_cairo_serialize(index, &fields, &types),
-        derive_variant_cairo_deserialize(ident, variant, &fields, &types),
-    )
+        derive_variant_cairo_deserialize(ident, variant, &cairo_fields),
+    ))
 }
 
 fn derive_variant_cairo_serialized_size(fields: &[TokenStream], types: &[Type]) -> TokenStream {
@@ -123,12 +159,10 @@ fn derive_variant_cairo_serialize(
     let index = syn::LitInt::new(&index.to_string(), Span::call_site());
     quote! {
         {
-            let mut result = Vec::new();
-            result.push(::starknet::core::types::Felt::from(#index));
+            out.push(::starknet::core::types::Felt::from(#index));
             #(
-                result.extend(<#types as ::cainome_cairo_serde::CairoSerde>::cairo_serialize(&#fields));
+                <#types as ::cainome_cairo_serde::CairoSerde>::cairo_serialize_to(&#fields, out);
             )*
-            result
         }
     }
 }
@@ -136,63 +170,71 @@ fn derive_variant_cairo_serialize(
 fn derive_variant_cairo_deserialize(
     ident: &Ident,
     variant: &Variant,
-    fields: &[TokenStream],
-    types: &[Type],
+    cairo_fields: &[CairoField],
 ) -> TokenStream {
     let variant_ident = &variant.ident;
+    let ident_str = ident.to_string();
+    let variant_str = variant_ident.to_string();
+
+    let values = cairo_fields
+        .iter()
+        .map(|f| {
+            if f.skip {
+                return quote!(::core::default::Default::default());
+            }
+
+            let ty = &f.ty;
+            let field_name = &f.name;
+
+            quote! {
+                {
+                    let (value, size) = <#ty as ::cainome_cairo_serde::CairoSerde>::cairo_deserialize_with_size(felt, current_offset)
+                        .map_err(|e| e.in_field(#variant_str, #field_name).in_variant(#ident_str, #variant_str))?;
+                    current_offset += size;
+                    value
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let fields = cairo_fields.iter().map(|f| &f.accessor);
 
     match &variant.fields {
         syn::Fields::Named(_) => quote! {
             {
+                #[allow(unused_mut)]
                 let mut current_offset = offset;
-                #ident::#variant_ident {
+                let value = #ident::#variant_ident {
                     #(
-                        #fields: {
-                            let value = <#types as ::cainome_cairo_serde::CairoSerde>::cairo_deserialize(felt, current_offset)?;
-                            current_offset += <#types as ::cainome_cairo_serde::CairoSerde>::cairo_serialized_size(&value);
-                            value
-                        },
+                        #fields: #values,
                     )*
-                }
+                };
+                (value, current_offset - offset)
             }
         },
         syn::Fields::Unnamed(_) => quote! {
             {
+                #[allow(unused_mut)]
                 let mut current_offset = offset;
-                #ident::#variant_ident (
+                let value = #ident::#variant_ident (
                     #(
-                        {
-                            let value = <#types as ::cainome_cairo_serde::CairoSerde>::cairo_deserialize(felt, current_offset)?;
-                            current_offset += <#types as ::cainome_cairo_serde::CairoSerde>::cairo_serialized_size(&value);
-                            value
-                        },
+                        #values,
                     )*
-                )
+                );
+                (value, current_offset - offset)
             }
         },
-        syn::Fields::Unit => quote! { #ident::#variant_ident},
+        syn::Fields::Unit => quote! { (#ident::#variant_ident, 0) },
     }
 }
 
-fn fields_idents_and_types(fields: &syn::Fields) -> (Vec<TokenStream>, Vec<Type>) {
+fn cairo_fields(fields: &syn::Fields) -> syn::Result<Vec<CairoField>> {
     fields
         .iter()
-        .cloned()
         .enumerate()
-        .map(field_ident_and_type)
-        .unzip()
-}
-
-fn field_ident_and_type((i, field): (usize, syn::Field)) -> (TokenStream, Type) {
-    (
-        field
-            .ident
-            .clone()
-            .map(|ident| quote! { #ident })
-            .unwrap_or({
-                let i = syn::Ident::new(&format!("__self_{}", i), Span::call_site());
-                quote! { #i }
-            }),
-        field.ty,
-    )
+        .map(|(i, field)| {
+            let binding = syn::Ident::new(&format!("__self_{}", i), Span::call_site());
+            CairoField::new(i, field, quote! { #binding })
+        })
+        .collect()
 }
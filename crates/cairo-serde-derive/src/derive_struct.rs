@@ -1,9 +1,36 @@
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::{DataStruct, Ident, Type};
+use syn::{Attribute, DataStruct, Fields, Generics, Ident, Type};
 
-pub fn derive_struct(ident: Ident, data: DataStruct) -> TokenStream {
-    let (fields, types) = fields_accessors_and_types(&data.fields);
+use crate::CairoField;
+
+pub fn derive_struct(
+    ident: Ident,
+    generics: &Generics,
+    data: DataStruct,
+    attrs: &[Attribute],
+) -> syn::Result<TokenStream> {
+    let cairo_fields = cairo_fields(&data.fields)?;
+    let (impl_line, rust_type) = crate::impl_line_and_rust_type(&ident, generics);
+
+    let (skipped, serialized): (Vec<&CairoField>, Vec<&CairoField>) =
+        cairo_fields.iter().partition(|f| f.skip);
+    let skipped = skipped.iter().map(|f| &f.accessor).collect::<Vec<_>>();
+
+    if is_transparent(attrs, &data.fields, serialized.len())? {
+        return Ok(derive_transparent(
+            &ident,
+            impl_line,
+            rust_type,
+            serialized[0],
+            &skipped,
+        ));
+    }
+
+    let ident_str = ident.to_string();
+    let fields = serialized.iter().map(|f| &f.accessor).collect::<Vec<_>>();
+    let types = serialized.iter().map(|f| &f.ty).collect::<Vec<_>>();
+    let field_names = serialized.iter().map(|f| &f.name).collect::<Vec<_>>();
 
     let cairo_serialized_size = quote! {
         fn cairo_serialized_size(rust: &Self::RustType) -> usize {
@@ -17,25 +44,42 @@ pub fn derive_struct(ident: Ident, data: DataStruct) -> TokenStream {
     let cairo_serialize = quote! {
         fn cairo_serialize(rust: &Self::RustType) -> Vec<::starknet::core::types::Felt> {
             let mut result = Vec::new();
+            <Self as ::cainome_cairo_serde::CairoSerde>::cairo_serialize_to(rust, &mut result);
+            result
+        }
+
+        #[allow(unused_variables)]
+        fn cairo_serialize_to(rust: &Self::RustType, out: &mut Vec<::starknet::core::types::Felt>) {
             #(
-                result.extend(<#types as ::cainome_cairo_serde::CairoSerde>::cairo_serialize(&rust.#fields));
+                <#types as ::cainome_cairo_serde::CairoSerde>::cairo_serialize_to(&rust.#fields, out);
             )*
-            result
         }
     };
 
     let cairo_deserialize = quote! {
         fn cairo_deserialize(felt: &[::starknet::core::types::Felt], offset: usize) -> Result<Self::RustType, ::cainome_cairo_serde::Error> {
+            <Self as ::cainome_cairo_serde::CairoSerde>::cairo_deserialize_with_size(felt, offset)
+                .map(|(value, _)| value)
+        }
+
+        #[allow(unused_variables)]
+        fn cairo_deserialize_with_size(felt: &[::starknet::core::types::Felt], offset: usize) -> Result<(Self::RustType, usize), ::cainome_cairo_serde::Error> {
+            #[allow(unused_mut)]
             let mut current_offset = offset;
-            Ok(Self {
+            let value = #ident {
                 #(
                     #fields: {
-                        let value = <#types as ::cainome_cairo_serde::CairoSerde>::cairo_deserialize(felt, current_offset)?;
-                        current_offset += <#types as ::cainome_cairo_serde::CairoSerde>::cairo_serialized_size(&value);
+                        let (value, size) = <#types as ::cainome_cairo_serde::CairoSerde>::cairo_deserialize_with_size(felt, current_offset)
+                            .map_err(|e| e.in_field(#ident_str, #field_names))?;
+                        current_offset += size;
                         value
                     },
                 )*
-            })
+                #(
+                    #skipped: ::core::default::Default::default(),
+                )*
+            };
+            Ok((value, current_offset - offset))
         }
     };
 
@@ -43,8 +87,8 @@ pub fn derive_struct(ident: Ident, data: DataStruct) -> TokenStream {
     // Any of the members of the composite type can have a dynamic size.
     // This is why we return `None` for the `SERIALIZED_SIZE` constant.
     let output = quote! {
-        impl ::cainome_cairo_serde::CairoSerde for #ident {
-            type RustType = Self;
+        #impl_line {
+            type RustType = #rust_type;
 
             const SERIALIZED_SIZE: Option<usize> = None;
 
@@ -53,28 +97,103 @@ pub fn derive_struct(ident: Ident, data: DataStruct) -> TokenStream {
             #cairo_deserialize
         }
     };
-    output
+    Ok(output)
+}
+
+/// Derives the implementation of a transparent struct, which is (de)serialized as its only
+/// serialized field, the skipped fields being defaulted.
+fn derive_transparent(
+    ident: &Ident,
+    impl_line: TokenStream,
+    rust_type: Type,
+    field: &CairoField,
+    skipped: &[&TokenStream],
+) -> TokenStream {
+    let ty = &field.ty;
+    let field = &field.accessor;
+
+    quote! {
+        #impl_line {
+            type RustType = #rust_type;
+
+            const SERIALIZED_SIZE: Option<usize> =
+                <#ty as ::cainome_cairo_serde::CairoSerde>::SERIALIZED_SIZE;
+
+            #[inline]
+            fn cairo_serialized_size(rust: &Self::RustType) -> usize {
+                <#ty as ::cainome_cairo_serde::CairoSerde>::cairo_serialized_size(&rust.#field)
+            }
+
+            #[inline]
+            fn cairo_serialize(rust: &Self::RustType) -> Vec<::starknet::core::types::Felt> {
+                <#ty as ::cainome_cairo_serde::CairoSerde>::cairo_serialize(&rust.#field)
+            }
+
+            #[inline]
+            fn cairo_serialize_to(rust: &Self::RustType, out: &mut Vec<::starknet::core::types::Felt>) {
+                <#ty as ::cainome_cairo_serde::CairoSerde>::cairo_serialize_to(&rust.#field, out)
+            }
+
+            #[inline]
+            fn cairo_deserialize(felt: &[::starknet::core::types::Felt], offset: usize) -> Result<Self::RustType, ::cainome_cairo_serde::Error> {
+                Ok(#ident {
+                    #field: <#ty as ::cainome_cairo_serde::CairoSerde>::cairo_deserialize(felt, offset)?,
+                    #(
+                        #skipped: ::core::default::Default::default(),
+                    )*
+                })
+            }
+
+            #[inline]
+            fn cairo_deserialize_with_size(felt: &[::starknet::core::types::Felt], offset: usize) -> Result<(Self::RustType, usize), ::cainome_cairo_serde::Error> {
+                let (value, size) = <#ty as ::cainome_cairo_serde::CairoSerde>::cairo_deserialize_with_size(felt, offset)?;
+                Ok((#ident { #field: value, #(#skipped: ::core::default::Default::default(),)* }, size))
+            }
+        }
+    }
 }
 
-fn fields_accessors_and_types(fields: &syn::Fields) -> (Vec<TokenStream>, Vec<Type>) {
+/// Returns whether the struct is a newtype, or is marked `#[repr(transparent)]`.
+///
+/// A `#[repr(transparent)]` struct may have skipped fields (like a `PhantomData`),
+/// but a single serialized field.
+fn is_transparent(
+    attrs: &[Attribute],
+    fields: &Fields,
+    serialized_len: usize,
+) -> syn::Result<bool> {
+    let mut repr_transparent = None;
+
+    for attr in attrs.iter().filter(|a| a.path().is_ident("repr")) {
+        // Other representations (like `C`) are ignored.
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("transparent") {
+                repr_transparent = Some(attr);
+            }
+            Ok(())
+        });
+    }
+
+    if let Some(attr) = repr_transparent {
+        if serialized_len != 1 {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "a `#[repr(transparent)]` struct must have a single serialized field to derive CairoSerde",
+            ));
+        }
+    }
+
+    Ok(repr_transparent.is_some()
+        || (serialized_len == 1 && matches!(fields, Fields::Unnamed(f) if f.unnamed.len() == 1)))
+}
+
+fn cairo_fields(fields: &Fields) -> syn::Result<Vec<CairoField>> {
     fields
         .iter()
-        .cloned()
         .enumerate()
-        .map(field_accessor_and_type)
-        .unzip()
-}
-
-fn field_accessor_and_type((i, field): (usize, syn::Field)) -> (TokenStream, Type) {
-    (
-        field
-            .ident
-            .clone()
-            .map(|ident| quote! { #ident })
-            .unwrap_or({
-                let i = syn::Index::from(i);
-                quote! { #i }
-            }),
-        field.ty,
-    )
+        .map(|(i, field)| {
+            let index = syn::Index::from(i);
+            CairoField::new(i, field, quote! { #index })
+        })
+        .collect()
 }
@@ -1,18 +1,290 @@
 use proc_macro::{self};
-use syn::{parse_macro_input, Data, DeriveInput};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input, Data, DeriveInput, Field, GenericParam, Generics, Ident, LitStr,
+    PredicateType, Type, WherePredicate,
+};
 
 mod derive_enum;
 mod derive_struct;
 
-#[proc_macro_derive(CairoSerde)]
+/// Derives `CairoSerde` for a struct or an enum.
+///
+/// The type used to (de)serialize a field can be overridden with
+/// `#[cairo_serde(with = SomeType)]`, where `SomeType` implements `CairoSerde`
+/// with the field type as `RustType` (like a `SerdeAdapter`).
+///
+/// A field marked `#[cairo_serde(skip)]` is not serialized, and is defaulted with
+/// `Default::default()` when deserializing. `#[cairo_serde(rename = "name")]` changes
+/// the name of a field in the paths of the deserialization errors.
+///
+/// A newtype (a tuple struct with a single field), or a struct with `#[repr(transparent)]`,
+/// is transparent: it is (de)serialized exactly as its field, with the same `SERIALIZED_SIZE`.
+///
+/// A generic type `Pair<T>` implements `CairoSerde` for any `T: CairoSerde<RustType = RT>`,
+/// with `Pair<RT>` as `RustType`, like the generic types of the bindings.
+#[proc_macro_derive(CairoSerde, attributes(cairo_serde))]
 pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    let DeriveInput { ident, data, .. } = parse_macro_input!(input);
+    derive_cairo_serde(parse_macro_input!(input))
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Derives `CairoSerde` for the given type, or returns the error of an unsupported type
+/// or of an invalid attribute, reported at its location.
+fn derive_cairo_serde(input: DeriveInput) -> syn::Result<TokenStream> {
+    let DeriveInput {
+        ident,
+        data,
+        attrs,
+        generics,
+        ..
+    } = input;
+
+    match data {
+        Data::Struct(data) => derive_struct::derive_struct(ident, &generics, data, &attrs),
+        Data::Enum(data) => derive_enum::derive_enum(ident, &generics, data),
+        Data::Union(data) => Err(syn::Error::new_spanned(
+            data.union_token,
+            "unions are not supported by the CairoSerde derive",
+        )),
+    }
+}
+
+/// The `#[cairo_serde(...)]` attributes of a field.
+#[derive(Default)]
+pub(crate) struct FieldAttrs {
+    /// The type implementing `CairoSerde` for the field, `with = SomeType`.
+    pub with: Option<Type>,
+    /// Whether the field is skipped, `skip`.
+    pub skip: bool,
+    /// The name of the field in the error paths, `rename = "name"`.
+    pub rename: Option<String>,
+}
+
+/// Parses the `#[cairo_serde(...)]` attributes of the given field.
+pub(crate) fn field_attrs(field: &Field) -> syn::Result<FieldAttrs> {
+    let mut attrs = FieldAttrs::default();
+
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|a| a.path().is_ident("cairo_serde"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("with") {
+                attrs.with = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("skip") {
+                attrs.skip = true;
+                Ok(())
+            } else if meta.path.is_ident("rename") {
+                let name: LitStr = meta.value()?.parse()?;
+                attrs.rename = Some(name.value());
+                Ok(())
+            } else {
+                Err(meta.error(
+                    "unsupported cairo_serde attribute, expected `with`, `skip` or `rename`",
+                ))
+            }
+        })?;
+    }
+
+    if attrs.skip && attrs.with.is_some() {
+        return Err(syn::Error::new_spanned(
+            field,
+            "a skipped field can't have a `with` cairo_serde attribute",
+        ));
+    }
 
-    let output = match data {
-        Data::Struct(data) => derive_struct::derive_struct(ident, data),
-        Data::Enum(data) => derive_enum::derive_enum(ident, data),
-        Data::Union(_) => panic!("Unions are not supported for the cairo_serde_derive!"),
+    Ok(attrs)
+}
+
+/// A field of a struct or of an enum variant.
+pub(crate) struct CairoField {
+    /// The accessor of the field: its name, its index or its binding.
+    pub accessor: TokenStream,
+    /// The type implementing `CairoSerde` for the field.
+    pub ty: Type,
+    /// The name of the field in the error paths.
+    pub name: String,
+    /// Whether the field is skipped: it's not serialized, and is defaulted
+    /// when deserializing.
+    pub skip: bool,
+}
+
+impl CairoField {
+    /// Builds the field at the given index, accessed with the given accessor
+    /// if the field is unnamed.
+    pub(crate) fn new(
+        index: usize,
+        field: &Field,
+        unnamed_accessor: TokenStream,
+    ) -> syn::Result<Self> {
+        let attrs = field_attrs(field)?;
+
+        let accessor = field
+            .ident
+            .as_ref()
+            .map(|ident| quote!(#ident))
+            .unwrap_or(unnamed_accessor);
+
+        // The unnamed fields are reported with their index.
+        let name = attrs.rename.unwrap_or_else(|| {
+            field
+                .ident
+                .as_ref()
+                .map_or(index.to_string(), |ident| ident.to_string())
+        });
+
+        Ok(Self {
+            accessor,
+            ty: attrs.with.unwrap_or_else(|| field.ty.clone()),
+            name,
+            skip: attrs.skip,
+        })
+    }
+}
+
+/// Returns the first line of the `CairoSerde` implementation and the `RustType`
+/// of a (possibly generic) type.
+///
+/// For each type parameter `T`, a parameter `RT` is added for its `RustType`,
+/// with the same bounds: `impl<T, RT> CairoSerde for Pair<T> where T: CairoSerde<RustType = RT>`,
+/// with `type RustType = Pair<RT>`.
+pub(crate) fn impl_line_and_rust_type(ident: &Ident, generics: &Generics) -> (TokenStream, Type) {
+    let mut impl_params = vec![];
+    let mut rust_args = vec![];
+    let mut predicates = vec![];
+
+    for param in &generics.params {
+        match param {
+            GenericParam::Lifetime(l) => {
+                impl_params.push(quote!(#l));
+                let lifetime = &l.lifetime;
+                rust_args.push(quote!(#lifetime));
+            }
+            GenericParam::Type(t) => {
+                let ty = &t.ident;
+                let rust_ty = format_ident!("R{}", ty);
+                let bounds = &t.bounds;
+                let colon = t.colon_token;
+
+                impl_params.push(quote!(#ty #colon #bounds));
+                impl_params.push(quote!(#rust_ty #colon #bounds));
+                rust_args.push(quote!(#rust_ty));
+                predicates
+                    .push(quote!(#ty: ::cainome_cairo_serde::CairoSerde<RustType = #rust_ty>));
+            }
+            GenericParam::Const(c) => {
+                let name = &c.ident;
+                let ty = &c.ty;
+                impl_params.push(quote!(const #name: #ty));
+                rust_args.push(quote!(#name));
+            }
+        }
+    }
+
+    let (_, ty_generics, where_clause) = generics.split_for_impl();
+    let mut where_predicates = vec![];
+
+    for predicate in where_clause.iter().flat_map(|w| w.predicates.iter()) {
+        where_predicates.push(quote!(#predicate));
+
+        // The bounds of a parameter also apply to its `RustType`.
+        if let WherePredicate::Type(PredicateType {
+            bounded_ty, bounds, ..
+        }) = predicate
+        {
+            let is_param = generics.type_params().any(|t| {
+                matches!(bounded_ty, Type::Path(p) if p.qself.is_none() && p.path.is_ident(&t.ident))
+            });
+
+            if is_param {
+                let rust_ty = format_ident!("R{}", quote!(#bounded_ty).to_string());
+                where_predicates.push(quote!(#rust_ty: #bounds));
+            }
+        }
+    }
+
+    where_predicates.extend(predicates.iter().cloned());
+
+    let impl_line = quote! {
+        impl<#(#impl_params),*> ::cainome_cairo_serde::CairoSerde for #ident #ty_generics
+        where
+            #(#where_predicates,)*
+    };
+
+    let rust_type = if predicates.is_empty() {
+        syn::parse_quote!(Self)
+    } else {
+        syn::parse_quote!(#ident<#(#rust_args),*>)
     };
 
-    output.into()
+    (impl_line, rust_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn derive_error(input: DeriveInput) -> String {
+        derive_cairo_serde(input)
+            .map(|_| ())
+            .unwrap_err()
+            .to_string()
+    }
+
+    #[test]
+    fn test_derive_errors() {
+        assert_eq!(
+            derive_error(syn::parse_quote! {
+                struct Example {
+                    #[cairo_serde(unknown)]
+                    x: Felt,
+                }
+            }),
+            "unsupported cairo_serde attribute, expected `with`, `skip` or `rename`"
+        );
+        assert_eq!(
+            derive_error(syn::parse_quote! {
+                enum Example {
+                    A(#[cairo_serde(skip, with = Other)] Felt),
+                }
+            }),
+            "a skipped field can't have a `with` cairo_serde attribute"
+        );
+        assert_eq!(
+            derive_error(syn::parse_quote! {
+                #[repr(transparent)]
+                struct Example {
+                    x: Felt,
+                    y: Felt,
+                }
+            }),
+            "a `#[repr(transparent)]` struct must have a single serialized field to derive CairoSerde"
+        );
+        assert_eq!(
+            derive_error(syn::parse_quote! {
+                union Example {
+                    x: u64,
+                }
+            }),
+            "unions are not supported by the CairoSerde derive"
+        );
+    }
+
+    #[test]
+    fn test_derive() {
+        assert!(derive_cairo_serde(syn::parse_quote! {
+            struct Example {
+                #[cairo_serde(skip)]
+                x: Felt,
+                #[cairo_serde(rename = "why", with = Other)]
+                y: Felt,
+            }
+        })
+        .is_ok());
+    }
 }
@@ -0,0 +1,101 @@
+//! Executors used by the blocking facades of the generated bindings.
+//!
+//! The bindings are async, the blocking facades rely on an executor
+//! implementing [`BlockOn`] to run the futures to completion.
+//! With the `tokio` feature, the tokio runtime and its handles can be used.
+use std::future::Future;
+
+/// An executor able to run a future to completion, blocking the current thread.
+pub trait BlockOn {
+    /// Runs the future to completion and returns its output.
+    ///
+    /// # Arguments
+    ///
+    /// * `future` - The future to run.
+    fn block_on<F: Future>(&self, future: F) -> F::Output;
+}
+
+impl<E: BlockOn + ?Sized> BlockOn for &E {
+    fn block_on<F: Future>(&self, future: F) -> F::Output {
+        (**self).block_on(future)
+    }
+}
+
+impl<E: BlockOn + ?Sized> BlockOn for std::sync::Arc<E> {
+    fn block_on<F: Future>(&self, future: F) -> F::Output {
+        (**self).block_on(future)
+    }
+}
+
+/// Note that blocking on a handle panics if called from an async context.
+#[cfg(feature = "tokio")]
+impl BlockOn for tokio::runtime::Handle {
+    fn block_on<F: Future>(&self, future: F) -> F::Output {
+        tokio::runtime::Handle::block_on(self, future)
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl BlockOn for tokio::runtime::Runtime {
+    fn block_on<F: Future>(&self, future: F) -> F::Output {
+        tokio::runtime::Runtime::block_on(self, future)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+    /// Polls the future until it's ready, only suitable for futures never pending.
+    struct Spin;
+
+    impl BlockOn for Spin {
+        fn block_on<F: Future>(&self, future: F) -> F::Output {
+            fn noop_raw_waker() -> RawWaker {
+                fn clone(_: *const ()) -> RawWaker {
+                    noop_raw_waker()
+                }
+                fn noop(_: *const ()) {}
+
+                static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+                RawWaker::new(std::ptr::null(), &VTABLE)
+            }
+
+            // Safe as the vtable functions don't use the data pointer.
+            let waker = unsafe { Waker::from_raw(noop_raw_waker()) };
+            let mut cx = Context::from_waker(&waker);
+            let mut future = std::pin::pin!(future);
+
+            loop {
+                if let Poll::Ready(out) = future.as_mut().poll(&mut cx) {
+                    return out;
+                }
+            }
+        }
+    }
+
+    fn run<E: BlockOn>(executor: E) -> u32 {
+        executor.block_on(async { 1 + 1 })
+    }
+
+    #[test]
+    fn test_block_on() {
+        assert_eq!(run(Spin), 2);
+        assert_eq!(run(&Spin), 2);
+        assert_eq!(run(Arc::new(Spin)), 2);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn test_block_on_tokio() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+
+        assert_eq!(run(runtime.handle().clone()), 2);
+        assert_eq!(run(runtime), 2);
+    }
+}
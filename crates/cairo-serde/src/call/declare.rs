@@ -0,0 +1,37 @@
+//! Declarations of the classes embedded into the bindings.
+//!
+//! The generated contracts with `include_artifact` embed their Sierra class, and have
+//! a `declare` function declaring it with the hash of its compiled class:
+//!
+//! ```ignore
+//! let declaration = MyContract::declare(&account).await?;
+//! let deployment = MyContract::deploy(&account, declaration.class_hash, salt, &owner).await?;
+//! ```
+use starknet::core::types::contract::SierraClass;
+use starknet::core::types::{Felt, FlattenedSierraClass};
+
+use crate::{Error, Result};
+
+/// A declaration of a class.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Declaration {
+    /// Hash of the declared class, once the transaction is accepted.
+    pub class_hash: Felt,
+    /// Hash of the transaction declaring the class.
+    pub transaction_hash: Felt,
+}
+
+/// Parses a Sierra class, and flattens its ABI as expected by the declare transactions.
+///
+/// # Arguments
+///
+/// * `sierra_json` - The JSON of the Sierra class.
+#[doc(hidden)]
+pub fn flatten_sierra_class(sierra_json: &str) -> Result<FlattenedSierraClass> {
+    let class = serde_json::from_str::<SierraClass>(sierra_json)
+        .map_err(|e| Error::Deserialize(format!("Sierra class: {}", e)))?;
+
+    class
+        .flatten()
+        .map_err(|e| Error::Serialize(format!("Sierra class ABI: {}", e)))
+}
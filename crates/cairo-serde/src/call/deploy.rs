@@ -0,0 +1,24 @@
+//! Deployments of the contracts with a constructor.
+//!
+//! The generated contracts with a constructor have a `deploy` function, deploying
+//! the contract through the Universal Deployer Contract (UDC), and a `deploy_with_deployer`
+//! function for the networks where the deployer is at an other address:
+//!
+//! ```ignore
+//! let deployment = MyContract::deploy(&account, class_hash, salt, &owner).await?;
+//! let contract = MyContract::new(deployment.address, account);
+//! ```
+use starknet::core::types::Felt;
+
+/// Address of the Universal Deployer Contract, the same on the public networks.
+pub const UDC_ADDRESS: Felt =
+    Felt::from_hex_unchecked("0x041a78e741e5af2fec34b695679bc6891742439f7afb8484ecd7766661ad02bf");
+
+/// A deployment sent through a deployer contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Deployment {
+    /// Address of the deployed contract, once the transaction is accepted.
+    pub address: Felt,
+    /// Hash of the transaction deploying the contract.
+    pub transaction_hash: Felt,
+}
@@ -0,0 +1,138 @@
+//! Calls to the entrypoints which are not in any ABI.
+//!
+//! The bindings are generated for the entrypoints of the ABIs. To call another
+//! entrypoint (like one of an upgraded contract), a [`DynCall`] is built from
+//! its selector, the arguments being serialized with their `CairoSerde`
+//! implementation:
+//!
+//! ```ignore
+//! use cainome::cairo_serde::{call::DynCall, selector, U256};
+//!
+//! let call = DynCall::new(token_address, selector!("transfer"))
+//!     .arg(&recipient)
+//!     .arg(&U256::from_bytes_be(&amount));
+//!
+//! account.execute_v3(vec![call.into()]).send().await?;
+//! ```
+use starknet::core::types::{Call, Felt, FunctionCall};
+
+use crate::call::FCall;
+use crate::CairoSerde;
+
+/// A call to an entrypoint given by its selector, with its calldata serialized
+/// argument by argument.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DynCall {
+    /// The address of the called contract.
+    pub to: Felt,
+    /// The selector of the entrypoint.
+    pub selector: Felt,
+    /// The serialized arguments.
+    pub calldata: Vec<Felt>,
+}
+
+impl DynCall {
+    /// Creates a call without arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `to` - The address of the called contract.
+    /// * `selector` - The selector of the entrypoint, usually `selector!("name")`.
+    pub fn new(to: Felt, selector: Felt) -> Self {
+        Self {
+            to,
+            selector,
+            calldata: vec![],
+        }
+    }
+
+    /// Appends an argument, serialized with its `CairoSerde` implementation.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value of the argument.
+    pub fn arg<T>(mut self, value: &T) -> Self
+    where
+        T: CairoSerde<RustType = T>,
+    {
+        T::cairo_serialize_to(value, &mut self.calldata);
+        self
+    }
+
+    /// Appends an argument already serialized.
+    ///
+    /// # Arguments
+    ///
+    /// * `felts` - The serialized argument.
+    pub fn raw_arg(mut self, felts: &[Felt]) -> Self {
+        self.calldata.extend_from_slice(felts);
+        self
+    }
+
+    /// Returns the call of a view, returning a `T` once called.
+    ///
+    /// # Arguments
+    ///
+    /// * `provider` - The provider used to call the view.
+    pub fn view<P, T>(self, provider: &P) -> FCall<'_, P, T>
+    where
+        P: starknet::providers::Provider + Sync,
+        T: CairoSerde<RustType = T>,
+    {
+        FCall::new(self.into(), provider)
+    }
+}
+
+impl From<DynCall> for Call {
+    fn from(call: DynCall) -> Self {
+        Call {
+            to: call.to,
+            selector: call.selector,
+            calldata: call.calldata,
+        }
+    }
+}
+
+impl From<DynCall> for FunctionCall {
+    fn from(call: DynCall) -> Self {
+        FunctionCall {
+            contract_address: call.to,
+            entry_point_selector: call.selector,
+            calldata: call.calldata,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{selector, ContractAddress, U256};
+    use starknet::core::utils::get_selector_from_name;
+
+    #[test]
+    fn test_dyn_call() {
+        let amount = U256 { low: 10, high: 0 };
+
+        let call: Call = DynCall::new(Felt::ONE, selector!("transfer"))
+            .arg(&ContractAddress(Felt::TWO))
+            .arg(&amount)
+            .raw_arg(&[Felt::THREE])
+            .arg(&vec![1_u8, 2])
+            .into();
+
+        assert_eq!(call.to, Felt::ONE);
+        assert_eq!(call.selector, get_selector_from_name("transfer").unwrap());
+        assert_eq!(
+            call.calldata,
+            vec![
+                Felt::TWO,
+                Felt::from(10_u8),
+                Felt::ZERO,
+                Felt::THREE,
+                Felt::TWO,
+                Felt::ONE,
+                Felt::TWO,
+            ]
+        );
+    }
+}
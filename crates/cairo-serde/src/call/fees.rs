@@ -0,0 +1,190 @@
+//! Fee settings of the V3 transactions sent by the externals.
+//!
+//! The externals generated with `execution_version("v3")` return an `ExecutionV3`,
+//! whose fees are configured with [`FeeSettingsV3`]:
+//!
+//! ```ignore
+//! use cainome::cairo_serde::call::{ExecutionV3Fees, FeeSettingsV3};
+//!
+//! let settings = FeeSettingsV3::new()
+//!     .l1_gas(20_000, 100_000_000_000_000)
+//!     .nonce(nonce);
+//!
+//! contract.transfer(&to, &amount).fee_settings(&settings)?.send().await?;
+//! ```
+//!
+//! The transactions are signed by `starknet-rs`, which only supports the L1 gas for now:
+//! the L2 gas, the L1 data gas and the tip are signed as zero. Non-zero bounds for
+//! them are rejected, instead of being silently dropped.
+use starknet::accounts::ExecutionV3;
+use starknet::core::types::Felt;
+
+use crate::paymaster::Invoke;
+use crate::{Error, Result};
+
+/// The bounds of a resource paid by a V3 transaction.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ResourceBounds {
+    /// The max amount of the resource consumed by the transaction.
+    pub max_amount: u64,
+    /// The max price paid per unit of the resource, in FRI.
+    pub max_price_per_unit: u128,
+}
+
+impl ResourceBounds {
+    /// Returns true if the bounds are zero, which is how `starknet-rs` signs the
+    /// resources it doesn't support.
+    fn is_zero(&self) -> bool {
+        self.max_amount == 0 && self.max_price_per_unit == 0
+    }
+}
+
+/// The fee settings of a V3 transaction, the ones not given being estimated
+/// (or fetched for the nonce) when the transaction is sent.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FeeSettingsV3 {
+    /// The nonce of the transaction.
+    pub nonce: Option<Felt>,
+    /// The bounds of the L1 gas.
+    pub l1_gas: Option<ResourceBounds>,
+    /// The bounds of the L2 gas, only zero is supported.
+    pub l2_gas: Option<ResourceBounds>,
+    /// The bounds of the L1 data gas, only zero is supported.
+    pub l1_data_gas: Option<ResourceBounds>,
+    /// The tip of the transaction, only zero is supported.
+    pub tip: u64,
+}
+
+impl FeeSettingsV3 {
+    /// Creates settings estimating all the fees.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the nonce of the transaction.
+    pub fn nonce(self, nonce: Felt) -> Self {
+        Self {
+            nonce: Some(nonce),
+            ..self
+        }
+    }
+
+    /// Sets the bounds of the L1 gas.
+    pub fn l1_gas(self, max_amount: u64, max_price_per_unit: u128) -> Self {
+        Self {
+            l1_gas: Some(ResourceBounds {
+                max_amount,
+                max_price_per_unit,
+            }),
+            ..self
+        }
+    }
+
+    /// Sets the bounds of the L2 gas.
+    pub fn l2_gas(self, max_amount: u64, max_price_per_unit: u128) -> Self {
+        Self {
+            l2_gas: Some(ResourceBounds {
+                max_amount,
+                max_price_per_unit,
+            }),
+            ..self
+        }
+    }
+
+    /// Sets the bounds of the L1 data gas.
+    pub fn l1_data_gas(self, max_amount: u64, max_price_per_unit: u128) -> Self {
+        Self {
+            l1_data_gas: Some(ResourceBounds {
+                max_amount,
+                max_price_per_unit,
+            }),
+            ..self
+        }
+    }
+
+    /// Sets the tip of the transaction.
+    pub fn tip(self, tip: u64) -> Self {
+        Self { tip, ..self }
+    }
+
+    /// Checks that the settings can be signed by `starknet-rs`.
+    pub fn check(&self) -> Result<()> {
+        for (name, bounds) in [("L2 gas", self.l2_gas), ("L1 data gas", self.l1_data_gas)] {
+            if bounds.is_some_and(|b| !b.is_zero()) {
+                return Err(Error::FeeSettings(format!(
+                    "non-zero {name} bounds are not supported by the signed transactions"
+                )));
+            }
+        }
+
+        if self.tip != 0 {
+            return Err(Error::FeeSettings(
+                "a non-zero tip is not supported by the signed transactions".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Applies [`FeeSettingsV3`] to the executions of V3 transactions.
+pub trait ExecutionV3Fees: Sized {
+    /// Returns the execution with the fee settings, or an error if they can't be signed.
+    fn fee_settings(self, settings: &FeeSettingsV3) -> Result<Self>;
+}
+
+impl<'a, A> ExecutionV3Fees for ExecutionV3<'a, A> {
+    fn fee_settings(self, settings: &FeeSettingsV3) -> Result<Self> {
+        settings.check()?;
+        Ok(apply(self, settings))
+    }
+}
+
+impl<'a, 'e, A, EA> ExecutionV3Fees for Invoke<'a, A, ExecutionV3<'e, EA>> {
+    fn fee_settings(self, settings: &FeeSettingsV3) -> Result<Self> {
+        settings.check()?;
+        Ok(self.map_execution(|e| apply(e, settings)))
+    }
+}
+
+/// Applies the settings, already checked, to the execution.
+fn apply<'a, A>(mut execution: ExecutionV3<'a, A>, settings: &FeeSettingsV3) -> ExecutionV3<'a, A> {
+    if let Some(nonce) = settings.nonce {
+        execution = execution.nonce(nonce);
+    }
+
+    if let Some(l1_gas) = settings.l1_gas {
+        execution = execution
+            .gas(l1_gas.max_amount)
+            .gas_price(l1_gas.max_price_per_unit);
+    }
+
+    execution
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fee_settings_check() {
+        let settings = FeeSettingsV3::new()
+            .l1_gas(20_000, 100)
+            .l2_gas(0, 0)
+            .nonce(Felt::THREE);
+
+        assert_eq!(
+            settings.l1_gas,
+            Some(ResourceBounds {
+                max_amount: 20_000,
+                max_price_per_unit: 100
+            })
+        );
+        assert_eq!(settings.nonce, Some(Felt::THREE));
+        assert!(settings.check().is_ok());
+
+        assert!(settings.l2_gas(1, 1).check().is_err());
+        assert!(settings.l1_data_gas(128, 1).check().is_err());
+        assert!(settings.tip(1).check().is_err());
+    }
+}
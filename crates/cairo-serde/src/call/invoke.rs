@@ -0,0 +1,205 @@
+//! Invocations of the externals, sent and decoded.
+//!
+//! The externals of the generated contracts return the execution of `starknet-rs`,
+//! whose `send()` only returns the hash of the transaction. The `send_and_decode`
+//! method of a generated contract sends the execution, waits for the receipt of the
+//! transaction, and returns an [`InvokeResult`] with the events of the contract decoded:
+//!
+//! ```ignore
+//! let result = contract.send_and_decode(contract.transfer(&to, &amount)).await?;
+//!
+//! println!("fee paid: {}", result.actual_fee().amount);
+//! for event in result.events {
+//!     // ...
+//! }
+//! ```
+use async_trait::async_trait;
+use futures_timer::Delay;
+use starknet::accounts::{ConnectedAccount, ExecutionV1, ExecutionV3};
+use starknet::core::types::{
+    ExecutionResult, FeePayment, Felt, StarknetError, TransactionReceipt,
+    TransactionReceiptWithBlockInfo,
+};
+use starknet::providers::{Provider, ProviderError};
+use std::time::Duration;
+
+use crate::paymaster::Invoke;
+use crate::{Error, Result};
+
+/// The interval between two polls of the receipt of a transaction.
+pub const DEFAULT_POLLING_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The number of polls before giving up on the receipt of a transaction.
+pub const DEFAULT_POLLING_ATTEMPTS: usize = 60;
+
+/// An execution that can be sent, returning the hash of the transaction.
+///
+/// Implemented for the executions returned by the externals of the generated contracts.
+#[async_trait]
+pub trait SendInvoke {
+    /// Sends the execution, and returns the hash of the transaction.
+    async fn send_invoke(&self) -> Result<Felt>;
+}
+
+#[async_trait]
+impl<'a, A> SendInvoke for ExecutionV1<'a, A>
+where
+    A: ConnectedAccount + Sync,
+{
+    async fn send_invoke(&self) -> Result<Felt> {
+        self.send()
+            .await
+            .map(|r| r.transaction_hash)
+            .map_err(Error::from_account)
+    }
+}
+
+#[async_trait]
+impl<'a, A> SendInvoke for ExecutionV3<'a, A>
+where
+    A: ConnectedAccount + Sync,
+{
+    async fn send_invoke(&self) -> Result<Felt> {
+        self.send()
+            .await
+            .map(|r| r.transaction_hash)
+            .map_err(Error::from_account)
+    }
+}
+
+#[async_trait]
+impl<'a, A, E> SendInvoke for Invoke<'a, A, E>
+where
+    A: Sync,
+    E: SendInvoke + Sync,
+{
+    async fn send_invoke(&self) -> Result<Felt> {
+        (**self).send_invoke().await
+    }
+}
+
+/// The result of an invocation, with the events of the invoked contract decoded.
+#[derive(Debug, Clone)]
+pub struct InvokeResult<E> {
+    /// The hash of the transaction.
+    pub transaction_hash: Felt,
+    /// The receipt of the transaction.
+    pub receipt: TransactionReceiptWithBlockInfo,
+    /// The events emitted by the invoked contract, decoded.
+    pub events: Vec<E>,
+}
+
+impl<E> InvokeResult<E> {
+    /// Returns the fee paid for the transaction.
+    pub fn actual_fee(&self) -> &FeePayment {
+        match &self.receipt.receipt {
+            TransactionReceipt::Invoke(r) => &r.actual_fee,
+            TransactionReceipt::L1Handler(r) => &r.actual_fee,
+            TransactionReceipt::Declare(r) => &r.actual_fee,
+            TransactionReceipt::Deploy(r) => &r.actual_fee,
+            TransactionReceipt::DeployAccount(r) => &r.actual_fee,
+        }
+    }
+
+    /// Returns the revert reason, if the transaction was reverted.
+    pub fn revert_reason(&self) -> Option<&str> {
+        match self.receipt.receipt.execution_result() {
+            ExecutionResult::Succeeded => None,
+            ExecutionResult::Reverted { reason } => Some(reason),
+        }
+    }
+}
+
+/// Polls the receipt of a transaction until it is known by the provider.
+///
+/// The polls are spaced by the timers of `futures-timer`, independent of the async runtime.
+///
+/// # Arguments
+///
+/// * `provider` - The provider to poll.
+/// * `transaction_hash` - The hash of the transaction.
+/// * `interval` - The interval between two polls.
+/// * `attempts` - The number of polls before giving up.
+pub async fn wait_for_receipt<P>(
+    provider: &P,
+    transaction_hash: Felt,
+    interval: Duration,
+    attempts: usize,
+) -> Result<TransactionReceiptWithBlockInfo>
+where
+    P: Provider + Sync,
+{
+    for attempt in 0..attempts.max(1) {
+        if attempt > 0 {
+            Delay::new(interval).await;
+        }
+
+        match provider.get_transaction_receipt(transaction_hash).await {
+            Ok(receipt) => return Ok(receipt),
+            Err(ProviderError::StarknetError(StarknetError::TransactionHashNotFound)) => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    Err(Error::Provider(ProviderError::StarknetError(
+        StarknetError::TransactionHashNotFound,
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starknet::core::types::{
+        ComputationResources, DataAvailabilityResources, DataResources, ExecutionResources,
+        InvokeTransactionReceipt, PriceUnit, ReceiptBlock, TransactionFinalityStatus,
+    };
+
+    #[test]
+    fn test_invoke_result() {
+        let receipt = InvokeTransactionReceipt {
+            transaction_hash: Felt::ONE,
+            actual_fee: FeePayment {
+                amount: Felt::from(1000),
+                unit: PriceUnit::Fri,
+            },
+            finality_status: TransactionFinalityStatus::AcceptedOnL2,
+            messages_sent: vec![],
+            events: vec![],
+            execution_resources: ExecutionResources {
+                computation_resources: ComputationResources {
+                    steps: 0,
+                    memory_holes: None,
+                    range_check_builtin_applications: None,
+                    pedersen_builtin_applications: None,
+                    poseidon_builtin_applications: None,
+                    ec_op_builtin_applications: None,
+                    ecdsa_builtin_applications: None,
+                    bitwise_builtin_applications: None,
+                    keccak_builtin_applications: None,
+                    segment_arena_builtin: None,
+                },
+                data_resources: DataResources {
+                    data_availability: DataAvailabilityResources {
+                        l1_gas: 0,
+                        l1_data_gas: 0,
+                    },
+                },
+            },
+            execution_result: ExecutionResult::Reverted {
+                reason: "out of gas".to_string(),
+            },
+        };
+
+        let result = InvokeResult::<()> {
+            transaction_hash: Felt::ONE,
+            receipt: TransactionReceiptWithBlockInfo {
+                receipt: TransactionReceipt::Invoke(receipt),
+                block: ReceiptBlock::Pending,
+            },
+            events: vec![],
+        };
+
+        assert_eq!(result.actual_fee().amount, Felt::from(1000));
+        assert_eq!(result.revert_reason(), Some("out of gas"));
+    }
+}
@@ -0,0 +1,319 @@
+//! This file must be in the proc_macro2 crate that must be reworked.
+pub mod declare;
+pub mod deploy;
+pub mod dyn_call;
+pub mod fees;
+pub mod invoke;
+pub mod multi_call;
+pub mod outside_execution;
+pub use declare::Declaration;
+pub use deploy::{Deployment, UDC_ADDRESS};
+pub use dyn_call::DynCall;
+pub use fees::{ExecutionV3Fees, FeeSettingsV3, ResourceBounds};
+use futures_util::stream::{self, StreamExt};
+pub use invoke::{InvokeResult, SendInvoke};
+pub use multi_call::MultiCall;
+use starknet::core::types::{BlockId, BlockTag, Felt, FunctionCall};
+use std::future::Future;
+use std::marker::PhantomData;
+
+use crate::{CairoSerde, Error, Result as CairoResult};
+
+#[derive(Debug)]
+pub struct FCall<'p, P, T> {
+    pub call_raw: FunctionCall,
+    pub block_id: BlockId,
+    provider: &'p P,
+    rust_type: PhantomData<T>,
+}
+
+impl<'p, P, T> FCall<'p, P, T>
+where
+    P: starknet::providers::Provider + Sync,
+    T: CairoSerde<RustType = T>,
+{
+    pub fn new(call_raw: FunctionCall, provider: &'p P) -> Self {
+        Self {
+            call_raw,
+            block_id: BlockId::Tag(BlockTag::Pending),
+            provider,
+            rust_type: PhantomData,
+        }
+    }
+
+    pub fn provider(self) -> &'p P {
+        self.provider
+    }
+
+    pub fn block_id(self, block_id: BlockId) -> Self {
+        Self { block_id, ..self }
+    }
+
+    /// Calls the view at the pending block, which includes the pending transactions
+    /// (the optimistic state). This is the default.
+    pub fn pending(self) -> Self {
+        self.block_id(BlockId::Tag(BlockTag::Pending))
+    }
+
+    /// Calls the view at the latest accepted block.
+    pub fn latest(self) -> Self {
+        self.block_id(BlockId::Tag(BlockTag::Latest))
+    }
+
+    /// Calls the view at the block of the given number.
+    pub fn block_number(self, block_number: u64) -> Self {
+        self.block_id(BlockId::Number(block_number))
+    }
+
+    /// Calls the view at the block of the given hash.
+    pub fn block_hash(self, block_hash: Felt) -> Self {
+        self.block_id(BlockId::Hash(block_hash))
+    }
+
+    pub async fn call(self) -> CairoResult<T> {
+        let r = self
+            .provider
+            .call(self.call_raw, self.block_id)
+            .await
+            .map_err(Error::from)?;
+
+        T::cairo_deserialize(&r, 0)
+    }
+
+    pub async fn raw_call(self) -> CairoResult<Vec<starknet::core::types::Felt>> {
+        self.provider
+            .call(self.call_raw, self.block_id)
+            .await
+            .map_err(Error::from)
+    }
+
+    /// Converts the call into a [`FCallOwned`], which owns a clone of the provider.
+    ///
+    /// Using a cheap to clone provider like `Arc<P>`, the returned call
+    /// doesn't borrow the contract or the reader anymore, and can be
+    /// moved into a spawned task.
+    pub fn into_owned(self) -> FCallOwned<P, T>
+    where
+        P: Clone,
+    {
+        FCallOwned {
+            call_raw: self.call_raw,
+            block_id: self.block_id,
+            provider: self.provider.clone(),
+            rust_type: PhantomData,
+        }
+    }
+}
+
+/// Same as [`FCall`], but owning the provider instead of borrowing it.
+#[derive(Debug)]
+pub struct FCallOwned<P, T> {
+    pub call_raw: FunctionCall,
+    pub block_id: BlockId,
+    provider: P,
+    rust_type: PhantomData<T>,
+}
+
+impl<P, T> FCallOwned<P, T>
+where
+    P: starknet::providers::Provider + Sync,
+    T: CairoSerde<RustType = T>,
+{
+    pub fn new(call_raw: FunctionCall, provider: P) -> Self {
+        Self {
+            call_raw,
+            block_id: BlockId::Tag(BlockTag::Pending),
+            provider,
+            rust_type: PhantomData,
+        }
+    }
+
+    pub fn provider(self) -> P {
+        self.provider
+    }
+
+    pub fn block_id(self, block_id: BlockId) -> Self {
+        Self { block_id, ..self }
+    }
+
+    /// Calls the view at the pending block, which includes the pending transactions
+    /// (the optimistic state). This is the default.
+    pub fn pending(self) -> Self {
+        self.block_id(BlockId::Tag(BlockTag::Pending))
+    }
+
+    /// Calls the view at the latest accepted block.
+    pub fn latest(self) -> Self {
+        self.block_id(BlockId::Tag(BlockTag::Latest))
+    }
+
+    /// Calls the view at the block of the given number.
+    pub fn block_number(self, block_number: u64) -> Self {
+        self.block_id(BlockId::Number(block_number))
+    }
+
+    /// Calls the view at the block of the given hash.
+    pub fn block_hash(self, block_hash: Felt) -> Self {
+        self.block_id(BlockId::Hash(block_hash))
+    }
+
+    pub async fn call(self) -> CairoResult<T> {
+        FCall::<P, T>::new(self.call_raw, &self.provider)
+            .block_id(self.block_id)
+            .call()
+            .await
+    }
+
+    pub async fn raw_call(self) -> CairoResult<Vec<Felt>> {
+        FCall::<P, T>::new(self.call_raw, &self.provider)
+            .block_id(self.block_id)
+            .raw_call()
+            .await
+    }
+}
+
+/// A constant declared with the bindings, whose value differs from the value
+/// returned by its view on the deployed contract.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConstMismatch {
+    /// The name of the constant.
+    pub name: &'static str,
+    /// The view returning the value of the constant.
+    pub view: &'static str,
+    /// The declared value (`Debug` format).
+    pub declared: String,
+    /// The value returned by the view (`Debug` format).
+    pub on_chain: String,
+}
+
+impl std::fmt::Display for ConstMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} is declared as {}, but `{}` returns {}",
+            self.name, self.declared, self.view, self.on_chain
+        )
+    }
+}
+
+/// Calls the views concurrently, with at most `concurrency` calls in flight,
+/// and returns their results in the order of the calls.
+///
+/// Unlike joining all the calls at once, the number of concurrent requests is bounded,
+/// to not be rate limited by the public RPC endpoints. A `concurrency` of `0` is
+/// considered as `1`.
+///
+/// # Arguments
+///
+/// * `calls` - The calls to the views, which can target different contracts.
+/// * `concurrency` - The maximum number of calls in flight.
+pub async fn batch<P, T>(calls: Vec<FCall<'_, P, T>>, concurrency: usize) -> Vec<CairoResult<T>>
+where
+    P: starknet::providers::Provider + Sync,
+    T: CairoSerde<RustType = T>,
+{
+    buffered(calls.into_iter().map(FCall::call), concurrency).await
+}
+
+/// Drives the futures with at most `concurrency` of them in flight, keeping their order.
+async fn buffered<F: Future>(
+    futures: impl IntoIterator<Item = F>,
+    concurrency: usize,
+) -> Vec<F::Output> {
+    stream::iter(futures)
+        .buffered(concurrency.max(1))
+        .collect()
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starknet::providers::{jsonrpc::HttpTransport, JsonRpcClient, Url};
+    use std::future::Future;
+    use std::sync::Arc;
+
+    fn assert_send_static<F: Future + Send + 'static>(_f: F) {}
+
+    #[test]
+    fn test_owned_call_is_send_and_static() {
+        let provider = Arc::new(JsonRpcClient::new(HttpTransport::new(
+            Url::parse("http://localhost:5050").unwrap(),
+        )));
+
+        let call = FunctionCall {
+            contract_address: Felt::ONE,
+            entry_point_selector: Felt::TWO,
+            calldata: vec![],
+        };
+
+        let fcall = FCall::<_, Felt>::new(call, &provider)
+            .block_id(BlockId::Tag(BlockTag::Latest))
+            .into_owned();
+
+        assert_eq!(fcall.block_id, BlockId::Tag(BlockTag::Latest));
+
+        // The futures are never polled, only their bounds are checked.
+        assert_send_static(fcall.call());
+    }
+
+    #[test]
+    fn test_call_block_shortcuts() {
+        let provider = Arc::new(JsonRpcClient::new(HttpTransport::new(
+            Url::parse("http://localhost:5050").unwrap(),
+        )));
+
+        let call = FunctionCall {
+            contract_address: Felt::ONE,
+            entry_point_selector: Felt::TWO,
+            calldata: vec![],
+        };
+
+        let fcall = FCall::<_, Felt>::new(call, &provider);
+        assert_eq!(fcall.block_id, BlockId::Tag(BlockTag::Pending));
+
+        let fcall = fcall.latest();
+        assert_eq!(fcall.block_id, BlockId::Tag(BlockTag::Latest));
+
+        let fcall = fcall.block_number(42);
+        assert_eq!(fcall.block_id, BlockId::Number(42));
+
+        let fcall = fcall.block_hash(Felt::THREE).pending();
+        assert_eq!(fcall.block_id, BlockId::Tag(BlockTag::Pending));
+
+        let fcall = fcall.into_owned().block_hash(Felt::THREE);
+        assert_eq!(fcall.block_id, BlockId::Hash(Felt::THREE));
+    }
+
+    #[tokio::test]
+    async fn test_buffered_is_bounded_and_ordered() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let in_flight = AtomicUsize::new(0);
+        let max_in_flight = AtomicUsize::new(0);
+
+        // The first futures take longer to complete than the next ones.
+        let futures = (0..10).map(|i| {
+            let (in_flight, max_in_flight) = (&in_flight, &max_in_flight);
+            async move {
+                let n = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                max_in_flight.fetch_max(n, Ordering::SeqCst);
+
+                for _ in 0..(10 - i) {
+                    tokio::task::yield_now().await;
+                }
+
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                i
+            }
+        });
+
+        assert_eq!(buffered(futures, 3).await, (0..10).collect::<Vec<_>>());
+        assert_eq!(max_in_flight.load(Ordering::SeqCst), 3);
+
+        assert_eq!(
+            buffered((1..3).map(|i| async move { i }), 0).await,
+            vec![1, 2]
+        );
+    }
+}
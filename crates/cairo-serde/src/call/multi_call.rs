@@ -0,0 +1,123 @@
+//! Calls sent in one transaction.
+//!
+//! The `<function>_getcall()` methods of the generated contracts return the
+//! [`Call`] of an external without invoking it. A [`MultiCall`] aggregates
+//! such calls (possibly of several contracts), to be invoked in one transaction
+//! with the `execute_batch` method of a generated contract:
+//!
+//! ```ignore
+//! use cainome::cairo_serde::call::MultiCall;
+//!
+//! let calls = MultiCall::new()
+//!     .call(token.approve_getcall(&spender, &amount))
+//!     .call(vault.deposit_getcall(&amount));
+//!
+//! vault.execute_batch(calls).send().await?;
+//! ```
+use starknet::core::types::Call;
+
+/// A list of calls, invoked in one transaction.
+#[derive(Debug, Clone, Default)]
+pub struct MultiCall {
+    calls: Vec<Call>,
+}
+
+impl MultiCall {
+    /// Creates an empty list of calls.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a call, like the one returned by a `<function>_getcall()` method
+    /// or a [`DynCall`](crate::call::DynCall).
+    ///
+    /// # Arguments
+    ///
+    /// * `call` - The call to append.
+    pub fn call(mut self, call: impl Into<Call>) -> Self {
+        self.calls.push(call.into());
+        self
+    }
+
+    /// Appends a call, see [`MultiCall::call`].
+    ///
+    /// # Arguments
+    ///
+    /// * `call` - The call to append.
+    pub fn push(&mut self, call: impl Into<Call>) {
+        self.calls.push(call.into());
+    }
+
+    /// Returns the calls, in the order they are invoked.
+    pub fn calls(&self) -> &[Call] {
+        &self.calls
+    }
+
+    /// Returns the number of calls.
+    pub fn len(&self) -> usize {
+        self.calls.len()
+    }
+
+    /// Returns true if there is no call.
+    pub fn is_empty(&self) -> bool {
+        self.calls.is_empty()
+    }
+
+    /// Returns the calls, in the order they are invoked.
+    pub fn into_calls(self) -> Vec<Call> {
+        self.calls
+    }
+}
+
+impl From<MultiCall> for Vec<Call> {
+    fn from(multi_call: MultiCall) -> Self {
+        multi_call.calls
+    }
+}
+
+impl<C: Into<Call>> FromIterator<C> for MultiCall {
+    fn from_iter<I: IntoIterator<Item = C>>(iter: I) -> Self {
+        Self {
+            calls: iter.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl<C: Into<Call>> Extend<C> for MultiCall {
+    fn extend<I: IntoIterator<Item = C>>(&mut self, iter: I) {
+        self.calls.extend(iter.into_iter().map(Into::into));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::call::DynCall;
+    use starknet::core::types::Felt;
+
+    #[test]
+    fn test_multi_call() {
+        let approve = Call {
+            to: Felt::ONE,
+            selector: Felt::TWO,
+            calldata: vec![Felt::THREE],
+        };
+
+        let mut calls = MultiCall::new()
+            .call(approve)
+            .call(DynCall::new(Felt::TWO, Felt::THREE));
+        calls.push(DynCall::new(Felt::THREE, Felt::ONE).raw_arg(&[Felt::TWO]));
+        calls.extend(vec![DynCall::new(Felt::ONE, Felt::ONE)]);
+
+        assert_eq!(calls.len(), 4);
+
+        let calls: Vec<Call> = calls.into();
+        let tos: Vec<Felt> = calls.iter().map(|c| c.to).collect();
+        assert_eq!(tos, vec![Felt::ONE, Felt::TWO, Felt::THREE, Felt::ONE]);
+        assert_eq!(calls[0].calldata, vec![Felt::THREE]);
+        assert_eq!(calls[2].calldata, vec![Felt::TWO]);
+
+        let calls: MultiCall = calls.into_iter().collect();
+        assert!(!calls.is_empty());
+    }
+}
@@ -0,0 +1,363 @@
+//! Outside execution (SNIP-9) of calls, signed by an account and sent by a relayer.
+//!
+//! An account supporting SNIP-9 executes the calls of an [`OutsideExecution`] when its
+//! `execute_from_outside_v2` entrypoint is invoked with the signature of the account.
+//! The calls are the ones returned by the `_getcall()` methods of the generated contracts:
+//!
+//! ```ignore
+//! let execution = OutsideExecution::new(nonce, vec![game.move_player_getcall(&direction)])
+//!     .execute_before(now + 600);
+//!
+//! let signature = execution.sign(&signer, chain_id, player_address).await?;
+//! let call = execution.execute_from_outside_call(player_address, signature);
+//! // `call` is sent by the relayer, which pays the fees.
+//! ```
+//!
+//! The message signed by the account is the SNIP-12 (revision 1) typed data of the execution.
+use starknet::core::types::{Call, Felt};
+use starknet::core::utils::starknet_keccak;
+use starknet::macros::{selector, short_string};
+use starknet::signers::Signer;
+use starknet_types_core::hash::{Poseidon, StarkHash};
+
+use crate::{cairo_serde_struct_body, CairoSerde, Result};
+
+/// Selector of the `execute_from_outside_v2` entrypoint of the accounts.
+pub const EXECUTE_FROM_OUTSIDE_V2_SELECTOR: Felt = selector!("execute_from_outside_v2");
+
+/// The caller of an execution which can be sent by anyone, the short string `ANY_CALLER`.
+pub const ANY_CALLER: Felt = short_string!("ANY_CALLER");
+
+/// Type of the SNIP-12 domain, revision 1.
+const STARKNET_DOMAIN_TYPE: &str = r#""StarknetDomain"("name":"shortstring","version":"shortstring","chainId":"shortstring","revision":"shortstring")"#;
+
+/// Type of the calls of an outside execution.
+const CALL_TYPE: &str =
+    r#""Call"("To":"ContractAddress","Selector":"selector","Calldata":"felt*")"#;
+
+/// Type of an outside execution, without the types it references.
+const OUTSIDE_EXECUTION_TYPE: &str = r#""OutsideExecution"("Caller":"ContractAddress","Nonce":"felt","Execute After":"u128","Execute Before":"u128","Calls":"Call*")"#;
+
+/// Name of the SNIP-12 domain of the outside executions, the short string `Account.execute_from_outside`.
+const DOMAIN_NAME: &str = "Account.execute_from_outside";
+
+/// Version of the SNIP-12 domain of the outside executions (`execute_from_outside_v2`).
+const DOMAIN_VERSION: u8 = 2;
+
+/// Calls executed by an account on behalf of a caller, in a time window (SNIP-9).
+#[derive(Debug, Clone)]
+pub struct OutsideExecution {
+    /// The only address allowed to send the execution, or [`ANY_CALLER`].
+    pub caller: Felt,
+    /// A nonce, which can't be reused by the account.
+    pub nonce: Felt,
+    /// The execution is only valid after this timestamp (in seconds).
+    pub execute_after: u64,
+    /// The execution is only valid before this timestamp (in seconds).
+    pub execute_before: u64,
+    /// The calls executed by the account.
+    pub calls: Vec<Call>,
+}
+
+impl CairoSerde for OutsideExecution {
+    type RustType = Self;
+
+    cairo_serde_struct_body!(OutsideExecution {
+        caller: Felt,
+        nonce: Felt,
+        execute_after: u64,
+        execute_before: u64,
+        calls: Vec<Call>,
+    });
+}
+
+/// `core::starknet::account::Call`, serialized as its `to`, `selector` and `calldata`.
+impl CairoSerde for Call {
+    type RustType = Self;
+
+    cairo_serde_struct_body!(Call {
+        to: Felt,
+        selector: Felt,
+        calldata: Vec<Felt>,
+    });
+}
+
+impl OutsideExecution {
+    /// Creates an execution of the `calls`, sent by any caller at any time.
+    ///
+    /// The time window should be restricted with [`Self::execute_before`], to not
+    /// leave a signed execution valid forever.
+    pub fn new(nonce: Felt, calls: Vec<Call>) -> Self {
+        Self {
+            caller: ANY_CALLER,
+            nonce,
+            execute_after: 0,
+            execute_before: u64::MAX,
+            calls,
+        }
+    }
+
+    /// Restricts the execution to be sent by `caller` (like the relayer).
+    pub fn caller(self, caller: Felt) -> Self {
+        Self { caller, ..self }
+    }
+
+    /// Sets the timestamp after which the execution is valid.
+    pub fn execute_after(self, execute_after: u64) -> Self {
+        Self {
+            execute_after,
+            ..self
+        }
+    }
+
+    /// Sets the timestamp before which the execution is valid.
+    pub fn execute_before(self, execute_before: u64) -> Self {
+        Self {
+            execute_before,
+            ..self
+        }
+    }
+
+    /// Adds a call to the execution.
+    pub fn call(mut self, call: Call) -> Self {
+        self.calls.push(call);
+        self
+    }
+
+    /// Returns the SNIP-12 hash of the execution, without its domain.
+    pub fn struct_hash(&self) -> Felt {
+        let calls: Vec<Felt> = self
+            .calls
+            .iter()
+            .map(|c| {
+                Poseidon::hash_array(&[
+                    starknet_keccak(CALL_TYPE.as_bytes()),
+                    c.to,
+                    c.selector,
+                    Poseidon::hash_array(&c.calldata),
+                ])
+            })
+            .collect();
+
+        let type_hash = starknet_keccak(format!("{OUTSIDE_EXECUTION_TYPE}{CALL_TYPE}").as_bytes());
+
+        Poseidon::hash_array(&[
+            type_hash,
+            self.caller,
+            self.nonce,
+            Felt::from(self.execute_after),
+            Felt::from(self.execute_before),
+            Poseidon::hash_array(&calls),
+        ])
+    }
+
+    /// Returns the SNIP-12 hash of the message signed by the account.
+    ///
+    /// # Arguments
+    ///
+    /// * `chain_id` - The chain of the account.
+    /// * `account_address` - The address of the account executing the calls.
+    pub fn message_hash(&self, chain_id: Felt, account_address: Felt) -> Felt {
+        let domain_hash = Poseidon::hash_array(&[
+            starknet_keccak(STARKNET_DOMAIN_TYPE.as_bytes()),
+            Felt::from_bytes_be_slice(DOMAIN_NAME.as_bytes()),
+            Felt::from(DOMAIN_VERSION),
+            chain_id,
+            Felt::ONE,
+        ]);
+
+        Poseidon::hash_array(&[
+            Felt::from_bytes_be_slice(b"StarkNet Message"),
+            domain_hash,
+            account_address,
+            self.struct_hash(),
+        ])
+    }
+
+    /// Signs the message of the execution with the `signer` of the account, as the
+    /// `[r, s]` signature expected by the accounts with a single Stark key.
+    ///
+    /// # Arguments
+    ///
+    /// * `signer` - The signer of the account.
+    /// * `chain_id` - The chain of the account.
+    /// * `account_address` - The address of the account executing the calls.
+    pub async fn sign<S>(
+        &self,
+        signer: &S,
+        chain_id: Felt,
+        account_address: Felt,
+    ) -> std::result::Result<Vec<Felt>, S::SignError>
+    where
+        S: Signer + Sync,
+    {
+        let signature = signer
+            .sign_hash(&self.message_hash(chain_id, account_address))
+            .await?;
+
+        Ok(vec![signature.r, signature.s])
+    }
+
+    /// Returns the call of the `execute_from_outside_v2` entrypoint of the account,
+    /// to be sent by the caller.
+    ///
+    /// # Arguments
+    ///
+    /// * `account_address` - The address of the account executing the calls.
+    /// * `signature` - The signature of the message by the account.
+    pub fn execute_from_outside_call(&self, account_address: Felt, signature: Vec<Felt>) -> Call {
+        let mut calldata = Self::cairo_serialize(self);
+        calldata.extend(Vec::<Felt>::cairo_serialize(&signature));
+
+        Call {
+            to: account_address,
+            selector: EXECUTE_FROM_OUTSIDE_V2_SELECTOR,
+            calldata,
+        }
+    }
+
+    /// Deserializes the execution from the calldata of `execute_from_outside_v2`.
+    pub fn from_calldata(calldata: &[Felt]) -> Result<(Self, Vec<Felt>)> {
+        let execution = Self::cairo_deserialize(calldata, 0)?;
+        let signature =
+            Vec::<Felt>::cairo_deserialize(calldata, Self::cairo_serialized_size(&execution))?;
+
+        Ok((execution, signature))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starknet::core::crypto::ecdsa_verify;
+    use starknet::core::utils::{cairo_short_string_to_felt, get_selector_from_name};
+    use starknet::signers::{LocalWallet, SigningKey};
+
+    fn execution() -> OutsideExecution {
+        OutsideExecution::new(
+            Felt::from(7),
+            vec![Call {
+                to: Felt::from(0x123),
+                selector: get_selector_from_name("transfer").unwrap(),
+                calldata: vec![Felt::ONE, Felt::TWO],
+            }],
+        )
+        .execute_after(10)
+        .execute_before(20)
+    }
+
+    #[test]
+    fn test_constants() {
+        assert_eq!(
+            EXECUTE_FROM_OUTSIDE_V2_SELECTOR,
+            get_selector_from_name("execute_from_outside_v2").unwrap()
+        );
+        assert_eq!(
+            ANY_CALLER,
+            cairo_short_string_to_felt("ANY_CALLER").unwrap()
+        );
+        assert_eq!(
+            Felt::from_bytes_be_slice(DOMAIN_NAME.as_bytes()),
+            cairo_short_string_to_felt(DOMAIN_NAME).unwrap()
+        );
+
+        // The type hashes of the OpenZeppelin and Argent accounts.
+        assert_eq!(
+            starknet_keccak(STARKNET_DOMAIN_TYPE.as_bytes()),
+            Felt::from_hex_unchecked(
+                "0x1ff2f602e42168014d405a94f75e8a93d640751d71d16311266e140d8b0a210"
+            )
+        );
+        assert_eq!(
+            starknet_keccak(CALL_TYPE.as_bytes()),
+            Felt::from_hex_unchecked(
+                "0x3635c7f2a7ba93844c0d064e18e487f35ab90f7c39d00f186a781fc3f0c2ca9"
+            )
+        );
+        assert_eq!(
+            starknet_keccak(format!("{OUTSIDE_EXECUTION_TYPE}{CALL_TYPE}").as_bytes()),
+            Felt::from_hex_unchecked(
+                "0x312b56c05a7965066ddbda31c016d8d05afc305071c0ca3cdc2192c3c2f1f0f"
+            )
+        );
+    }
+
+    #[test]
+    fn test_hashes() {
+        let execution = execution();
+        let call = &execution.calls[0];
+
+        let call_hash = Poseidon::hash_array(&[
+            starknet_keccak(CALL_TYPE.as_bytes()),
+            call.to,
+            call.selector,
+            Poseidon::hash_array(&[Felt::ONE, Felt::TWO]),
+        ]);
+        let struct_hash = Poseidon::hash_array(&[
+            Felt::from_hex_unchecked(
+                "0x312b56c05a7965066ddbda31c016d8d05afc305071c0ca3cdc2192c3c2f1f0f",
+            ),
+            ANY_CALLER,
+            Felt::from(7),
+            Felt::from(10),
+            Felt::from(20),
+            Poseidon::hash_array(&[call_hash]),
+        ]);
+        assert_eq!(execution.struct_hash(), struct_hash);
+
+        // The message depends on the chain and the account.
+        let hash = execution.message_hash(Felt::from(0x534e), Felt::from(0xacc));
+        assert_ne!(
+            hash,
+            execution.message_hash(Felt::from(0x534f), Felt::from(0xacc))
+        );
+        assert_ne!(
+            hash,
+            execution.message_hash(Felt::from(0x534e), Felt::from(0xacd))
+        );
+        assert_ne!(
+            hash,
+            execution
+                .clone()
+                .caller(Felt::ONE)
+                .message_hash(Felt::from(0x534e), Felt::from(0xacc))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sign_and_call() {
+        let key = SigningKey::from_secret_scalar(Felt::from(0x1234));
+        let signer = LocalWallet::from(key.clone());
+        let (chain_id, account) = (Felt::from(0x534e), Felt::from(0xacc));
+
+        let execution = execution();
+        let signature = execution.sign(&signer, chain_id, account).await.unwrap();
+
+        assert!(ecdsa_verify(
+            &key.verifying_key().scalar(),
+            &execution.message_hash(chain_id, account),
+            &starknet::core::crypto::Signature {
+                r: signature[0],
+                s: signature[1],
+            }
+        )
+        .unwrap());
+
+        let call = execution.execute_from_outside_call(account, signature.clone());
+        assert_eq!(call.to, account);
+        assert_eq!(
+            call.calldata[..5],
+            [
+                ANY_CALLER,
+                Felt::from(7),
+                Felt::from(10),
+                Felt::from(20),
+                Felt::ONE
+            ]
+        );
+
+        let (decoded, decoded_signature) = OutsideExecution::from_calldata(&call.calldata).unwrap();
+        assert_eq!(decoded.struct_hash(), execution.struct_hash());
+        assert_eq!(decoded_signature, signature);
+    }
+}
@@ -1,6 +1,10 @@
 use super::CairoSerde;
 
-use starknet::{core::types::Felt, providers::ProviderError};
+use starknet::{
+    accounts::AccountError,
+    core::types::{Felt, StarknetError},
+    providers::ProviderError,
+};
 
 /// Cairo types result.
 pub type Result<T> = core::result::Result<T, Error>;
@@ -14,12 +18,198 @@ pub enum Error {
     Serialize(String),
     #[error("Error during deserialization {0:?}.")]
     Deserialize(String),
+    /// An error of the provider which is not classified as a [`CallError`].
     #[error("Provider errror {0:?}.")]
-    Provider(#[from] ProviderError),
+    Provider(ProviderError),
     #[error("Bytes31 out of range.")]
     Bytes31OutOfRange,
     #[error("NonZero that is zero")]
     ZeroedNonZero,
+    /// The account failed to send a transaction.
+    #[error("Account error {0:?}.")]
+    Account(String),
+    /// The fee settings of a transaction can't be signed.
+    #[error("Unsupported fee settings: {0}")]
+    FeeSettings(String),
+    /// A call to a contract, or a transaction sent to it, failed for a known cause.
+    #[error("Call error: {0}")]
+    Call(#[from] CallError),
+    /// An error that occurred while deserializing a member of a type,
+    /// with the path to this member (`MyStruct.orders[3].price`).
+    #[error("{}{path}: {source}", .ty.as_deref().unwrap_or(""))]
+    Context {
+        /// The name of the outermost type, if any.
+        ty: Option<String>,
+        /// The path to the member, from the outermost type.
+        path: String,
+        /// The error of the member.
+        source: Box<Error>,
+    },
+}
+
+/// The cause of a failed call to a contract, or of a failed transaction sent to it,
+/// classified from the error of the provider.
+#[derive(Debug, thiserror::Error)]
+pub enum CallError {
+    /// No contract is deployed at the called address.
+    #[error("Contract not found.")]
+    ContractNotFound,
+    /// The contract has no entrypoint with the called selector (the execution trace).
+    #[error("Entrypoint not found: {0}")]
+    EntrypointNotFound(String),
+    /// The account rejected the transaction during its validation.
+    #[error("Validation failure: {0}")]
+    ValidationFailure(String),
+    /// The account can't pay the maximum fee of the transaction.
+    #[error("Insufficient funds to pay the fee.")]
+    InsufficientFunds,
+    /// The execution of the contract failed (the execution trace).
+    #[error("Execution failed: {0}")]
+    Reverted(String),
+}
+
+/// The markers of a missing entrypoint in an execution trace: the message of the sequencer
+/// for the Cairo 0 contracts, and the error of the Cairo 1 contracts (also as a felt).
+const ENTRYPOINT_NOT_FOUND_MARKERS: [&str; 3] = [
+    "ENTRYPOINT_NOT_FOUND",
+    "0x454e545259504f494e545f4e4f545f464f554e44",
+    "not found in contract",
+];
+
+impl CallError {
+    /// Classifies an execution trace, which may be a missing entrypoint.
+    fn from_trace(trace: String) -> Self {
+        if ENTRYPOINT_NOT_FOUND_MARKERS
+            .iter()
+            .any(|m| trace.contains(m))
+        {
+            CallError::EntrypointNotFound(trace)
+        } else {
+            CallError::Reverted(trace)
+        }
+    }
+}
+
+/// Classifies an error of the provider, the other errors being returned as is.
+impl TryFrom<ProviderError> for CallError {
+    type Error = ProviderError;
+
+    fn try_from(e: ProviderError) -> core::result::Result<Self, ProviderError> {
+        match e {
+            ProviderError::StarknetError(StarknetError::ContractNotFound) => {
+                Ok(CallError::ContractNotFound)
+            }
+            ProviderError::StarknetError(StarknetError::ValidationFailure(reason)) => {
+                Ok(CallError::ValidationFailure(reason))
+            }
+            ProviderError::StarknetError(
+                StarknetError::InsufficientAccountBalance | StarknetError::InsufficientMaxFee,
+            ) => Ok(CallError::InsufficientFunds),
+            ProviderError::StarknetError(StarknetError::ContractError(data)) => {
+                Ok(CallError::from_trace(data.revert_error))
+            }
+            ProviderError::StarknetError(StarknetError::TransactionExecutionError(data)) => {
+                Ok(CallError::from_trace(data.execution_error))
+            }
+            e => Err(e),
+        }
+    }
+}
+
+/// The errors of the provider with a known cause are an [`Error::Call`],
+/// the other ones an [`Error::Provider`].
+impl From<ProviderError> for Error {
+    fn from(e: ProviderError) -> Self {
+        match CallError::try_from(e) {
+            Ok(e) => Error::Call(e),
+            Err(e) => Error::Provider(e),
+        }
+    }
+}
+
+impl Error {
+    /// Converts the error of an account sending a transaction, the errors of the provider
+    /// being classified as a [`CallError`] when their cause is known.
+    ///
+    /// # Arguments
+    ///
+    /// * `e` - The error of the account.
+    pub fn from_account<S: std::fmt::Display>(e: AccountError<S>) -> Self {
+        match e {
+            AccountError::Provider(e) => e.into(),
+            e => Error::Account(e.to_string()),
+        }
+    }
+
+    /// Returns the cause of the failed call, if the error is a [`CallError`].
+    pub fn call_error(&self) -> Option<&CallError> {
+        match self.root_cause() {
+            Error::Call(e) => Some(e),
+            _ => None,
+        }
+    }
+
+    /// Adds the field of a struct to the path of the error.
+    ///
+    /// # Arguments
+    ///
+    /// * `ty` - The name of the struct.
+    /// * `field` - The name of the field being deserialized.
+    pub fn in_field(self, ty: &str, field: &str) -> Self {
+        self.with_context(Some(ty), format!(".{}", field.trim_start_matches("r#")))
+    }
+
+    /// Adds the variant of an enum to the path of the error.
+    ///
+    /// # Arguments
+    ///
+    /// * `ty` - The name of the enum.
+    /// * `variant` - The name of the variant being deserialized.
+    pub fn in_variant(self, ty: &str, variant: &str) -> Self {
+        self.with_context(Some(ty), format!("::{}", variant))
+    }
+
+    /// Adds the index of an array element to the path of the error.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the element being deserialized.
+    pub fn in_index(self, index: usize) -> Self {
+        self.with_context(None, format!("[{}]", index))
+    }
+
+    /// Returns the path to the member that failed to deserialize, if any.
+    pub fn path(&self) -> Option<String> {
+        match self {
+            Error::Context { ty, path, .. } => {
+                Some(format!("{}{}", ty.as_deref().unwrap_or(""), path))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the error without its context.
+    pub fn root_cause(&self) -> &Error {
+        match self {
+            Error::Context { source, .. } => source.root_cause(),
+            e => e,
+        }
+    }
+
+    fn with_context(self, ty: Option<&str>, segment: String) -> Self {
+        // The type of an inner context is the type of the member,
+        // which is already named by the new segment.
+        let (path, source) = match self {
+            Error::Context { path, source, .. } => (segment + &path, source),
+            e => (segment, Box::new(e)),
+        };
+
+        Error::Context {
+            ty: ty.map(String::from),
+            path,
+            source,
+        }
+    }
 }
 
 impl CairoSerde for Error {
@@ -35,3 +225,94 @@ impl CairoSerde for Error {
         ))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_context() {
+        let e = Error::Deserialize("Buffer too short".to_string())
+            .in_field("Order", "price")
+            .in_index(3)
+            .in_field("MyStruct", "orders");
+
+        assert_eq!(e.path().unwrap(), "MyStruct.orders[3].price");
+        assert!(matches!(e.root_cause(), Error::Deserialize(_)));
+        assert_eq!(
+            e.to_string(),
+            "MyStruct.orders[3].price: Error during deserialization \"Buffer too short\"."
+        );
+
+        let e = Error::ZeroedNonZero
+            .in_field("Point", "r#type")
+            .in_variant("Shape", "Point");
+        assert_eq!(e.path().unwrap(), "Shape::Point.type");
+
+        assert!(Error::ZeroedNonZero.path().is_none());
+    }
+
+    #[test]
+    fn test_call_error() {
+        use starknet::core::types::{ContractErrorData, TransactionExecutionErrorData};
+
+        let call_error =
+            |e: StarknetError| CallError::try_from(ProviderError::StarknetError(e)).unwrap();
+
+        assert!(matches!(
+            call_error(StarknetError::ContractNotFound),
+            CallError::ContractNotFound
+        ));
+        assert!(matches!(
+            call_error(StarknetError::InsufficientAccountBalance),
+            CallError::InsufficientFunds
+        ));
+        assert!(matches!(
+            call_error(StarknetError::InsufficientMaxFee),
+            CallError::InsufficientFunds
+        ));
+        assert!(matches!(
+            call_error(StarknetError::ValidationFailure("invalid signature".to_string())),
+            CallError::ValidationFailure(r) if r == "invalid signature"
+        ));
+        assert!(matches!(
+            call_error(StarknetError::ContractError(ContractErrorData {
+                revert_error: "Error in the called contract: 0x454e545259504f494e545f4e4f545f464f554e44 ('ENTRYPOINT_NOT_FOUND')".to_string(),
+            })),
+            CallError::EntrypointNotFound(_)
+        ));
+        assert!(matches!(
+            call_error(StarknetError::TransactionExecutionError(
+                TransactionExecutionErrorData {
+                    transaction_index: 0,
+                    execution_error: "Entry point EntryPointSelector(0x12) not found in contract."
+                        .to_string(),
+                }
+            )),
+            CallError::EntrypointNotFound(_)
+        ));
+        assert!(matches!(
+            call_error(StarknetError::ContractError(ContractErrorData {
+                revert_error: "Execution failed: 'Insufficient balance'".to_string(),
+            })),
+            CallError::Reverted(_)
+        ));
+        assert!(matches!(
+            CallError::try_from(ProviderError::RateLimited),
+            Err(ProviderError::RateLimited)
+        ));
+        assert!(matches!(
+            Error::from(ProviderError::RateLimited),
+            Error::Provider(ProviderError::RateLimited)
+        ));
+
+        let e = Error::from_account(AccountError::<String>::Provider(
+            ProviderError::StarknetError(StarknetError::ContractNotFound),
+        ));
+        assert!(matches!(e.call_error(), Some(CallError::ContractNotFound)));
+
+        let e = Error::from_account(AccountError::<String>::FeeOutOfRange);
+        assert!(matches!(e, Error::Account(_)));
+        assert!(e.call_error().is_none());
+    }
+}
@@ -0,0 +1,489 @@
+//! Small generic helpers used by the generated code.
+//!
+//! Instead of inlining the (de)serialization of every field and variant,
+//! the generated `CairoSerde` implementations call those helpers, which
+//! greatly reduces the size of the token streams for large ABIs.
+//!
+//! Those are internals of the generated code, hidden from the documentation.
+use ::starknet::core::types::{Event, Felt, TransactionReceipt};
+
+use crate::{CairoSerde, Error, Result};
+
+/// Expands to the items of a `CairoSerde` implementation for a struct,
+/// where the fields are (de)serialized in the given order.
+///
+/// The `RustType` must be provided by the implementation, and the struct
+/// must be constructible with the given name and fields.
+/// The `SERIALIZED_SIZE` is `None` unless given with `size = ...`.
+///
+/// ```ignore
+/// impl CairoSerde for MyStruct {
+///     type RustType = Self;
+///
+///     cairo_serde_struct_body!(MyStruct { a: Felt, b: Vec<u8> });
+/// }
+///
+/// impl CairoSerde for Point {
+///     type RustType = Self;
+///
+///     cairo_serde_struct_body!(Point { x: Felt, y: Felt }, size = Some(2));
+/// }
+/// ```
+#[doc(hidden)]
+#[macro_export]
+macro_rules! cairo_serde_struct_body {
+    ($name:ident { $($field:ident : $ty:ty),* $(,)? }) => {
+        $crate::cairo_serde_struct_body!(
+            $name { $($field: $ty),* },
+            size = ::std::option::Option::None
+        );
+    };
+    ($name:ident { $($field:ident : $ty:ty),* $(,)? }, size = $size:expr) => {
+        const SERIALIZED_SIZE: ::std::option::Option<usize> = $size;
+
+        #[inline]
+        fn cairo_serialized_size(__rust: &Self::RustType) -> usize {
+            0 $(+ <$ty as $crate::CairoSerde>::cairo_serialized_size(&__rust.$field))*
+        }
+
+        fn cairo_serialize(__rust: &Self::RustType) -> ::std::vec::Vec<starknet::core::types::Felt> {
+            let mut __out = ::std::vec::Vec::new();
+            <Self as $crate::CairoSerde>::cairo_serialize_to(__rust, &mut __out);
+            __out
+        }
+
+        #[allow(unused_variables)]
+        fn cairo_serialize_to(
+            __rust: &Self::RustType,
+            __out: &mut ::std::vec::Vec<starknet::core::types::Felt>,
+        ) {
+            $($crate::helpers::serialize_field::<$ty>(&__rust.$field, __out);)*
+        }
+
+        fn cairo_deserialize(
+            __felts: &[starknet::core::types::Felt],
+            __offset: usize,
+        ) -> $crate::Result<Self::RustType> {
+            <Self as $crate::CairoSerde>::cairo_deserialize_with_size(__felts, __offset)
+                .map(|(__rust, _)| __rust)
+        }
+
+        fn cairo_deserialize_with_size(
+            __felts: &[starknet::core::types::Felt],
+            __start: usize,
+        ) -> $crate::Result<(Self::RustType, usize)> {
+            #[allow(unused_mut)]
+            let mut __offset = __start;
+            // Fields initializers are evaluated in order,
+            // which ensures the offset is correctly advanced.
+            let __rust = $name {
+                $($field: $crate::helpers::deserialize_field::<$ty>(__felts, &mut __offset)
+                    .map_err(|e| e.in_field(stringify!($name), stringify!($field)))?),*
+            };
+            ::std::result::Result::Ok((__rust, __offset - __start))
+        }
+    };
+}
+
+/// Expands to the items of a `CairoSerde` implementation for an enum.
+///
+/// The variants without value (unit) and the variants with a value are given
+/// in two separate lists, each variant being associated with its index.
+/// The `SERIALIZED_SIZE` is `None` unless given with `size = ...`.
+///
+/// ```ignore
+/// impl CairoSerde for MyEnum {
+///     type RustType = Self;
+///
+///     cairo_serde_enum_body!(MyEnum {
+///         unit: [0 => A],
+///         value: [1 => B(Felt), 2 => C((u8, u8))],
+///     });
+/// }
+/// ```
+#[doc(hidden)]
+#[macro_export]
+macro_rules! cairo_serde_enum_body {
+    (
+        $name:ident {
+            unit: [$($unit_index:literal => $unit:ident),* $(,)?],
+            value: [$($value_index:literal => $variant:ident($ty:ty)),* $(,)?] $(,)?
+        }
+    ) => {
+        $crate::cairo_serde_enum_body!(
+            $name {
+                unit: [$($unit_index => $unit),*],
+                value: [$($value_index => $variant($ty)),*],
+            },
+            size = ::std::option::Option::None
+        );
+    };
+    (
+        $name:ident {
+            unit: [$($unit_index:literal => $unit:ident),* $(,)?],
+            value: [$($value_index:literal => $variant:ident($ty:ty)),* $(,)?] $(,)?
+        },
+        size = $size:expr
+    ) => {
+        const SERIALIZED_SIZE: ::std::option::Option<usize> = $size;
+
+        #[inline]
+        fn cairo_serialized_size(__rust: &Self::RustType) -> usize {
+            match __rust {
+                $($name::$unit => 1,)*
+                // +1 for the variant index.
+                $($name::$variant(__v) => <$ty as $crate::CairoSerde>::cairo_serialized_size(__v) + 1,)*
+                #[allow(unreachable_patterns)]
+                _ => 0,
+            }
+        }
+
+        fn cairo_serialize(__rust: &Self::RustType) -> ::std::vec::Vec<starknet::core::types::Felt> {
+            let mut __out = ::std::vec::Vec::new();
+            <Self as $crate::CairoSerde>::cairo_serialize_to(__rust, &mut __out);
+            __out
+        }
+
+        fn cairo_serialize_to(
+            __rust: &Self::RustType,
+            __out: &mut ::std::vec::Vec<starknet::core::types::Felt>,
+        ) {
+            match __rust {
+                $($name::$unit => $crate::helpers::serialize_variant_to::<()>($unit_index, &(), __out),)*
+                $($name::$variant(__v) => $crate::helpers::serialize_variant_to::<$ty>($value_index, __v, __out),)*
+                #[allow(unreachable_patterns)]
+                _ => {}
+            }
+        }
+
+        fn cairo_deserialize(
+            __felts: &[starknet::core::types::Felt],
+            __offset: usize,
+        ) -> $crate::Result<Self::RustType> {
+            <Self as $crate::CairoSerde>::cairo_deserialize_with_size(__felts, __offset)
+                .map(|(__rust, _)| __rust)
+        }
+
+        fn cairo_deserialize_with_size(
+            __felts: &[starknet::core::types::Felt],
+            __offset: usize,
+        ) -> $crate::Result<(Self::RustType, usize)> {
+            match $crate::helpers::deserialize_variant_index(__felts, __offset, stringify!($name))? {
+                $($unit_index => ::std::result::Result::Ok(($name::$unit, 1)),)*
+                // +1 for the variant index.
+                $($value_index => <$ty as $crate::CairoSerde>::cairo_deserialize_with_size(__felts, __offset + 1)
+                    .map(|(__v, __size)| ($name::$variant(__v), __size + 1))
+                    .map_err(|e| e.in_variant(stringify!($name), stringify!($variant))),)*
+                _ => ::std::result::Result::Err($crate::Error::Deserialize(format!(
+                    "Index not handle for enum {}",
+                    stringify!($name)
+                ))),
+            }
+        }
+    };
+}
+
+/// Serializes `rust` as a `T` at the end of `out`.
+///
+/// # Arguments
+///
+/// * `rust` - The value to serialize.
+/// * `out` - The buffer to serialize into.
+#[inline]
+pub fn serialize_field<T: CairoSerde + ?Sized>(rust: &T::RustType, out: &mut Vec<Felt>) {
+    T::cairo_serialize_to(rust, out);
+}
+
+/// Deserializes a `T` at the given offset, and moves the offset
+/// right after the deserialized value.
+///
+/// # Arguments
+///
+/// * `felts` - The buffer to deserialize from.
+/// * `offset` - The offset of the value, updated after deserialization.
+#[inline]
+pub fn deserialize_field<T: CairoSerde + ?Sized>(
+    felts: &[Felt],
+    offset: &mut usize,
+) -> Result<T::RustType> {
+    let (value, size) = T::cairo_deserialize_with_size(felts, *offset)?;
+    *offset += size;
+    Ok(value)
+}
+
+/// Serializes an enum variant: the variant index followed by the variant's value as a `T`.
+///
+/// # Arguments
+///
+/// * `index` - The variant index.
+/// * `rust` - The value of the variant.
+#[inline]
+pub fn serialize_variant<T: CairoSerde + ?Sized>(index: usize, rust: &T::RustType) -> Vec<Felt> {
+    let mut out = vec![];
+    serialize_variant_to::<T>(index, rust, &mut out);
+    out
+}
+
+/// Serializes an enum variant at the end of `out`: the variant index followed by
+/// the variant's value as a `T`.
+///
+/// # Arguments
+///
+/// * `index` - The variant index.
+/// * `rust` - The value of the variant.
+/// * `out` - The buffer to serialize into.
+#[inline]
+pub fn serialize_variant_to<T: CairoSerde + ?Sized>(
+    index: usize,
+    rust: &T::RustType,
+    out: &mut Vec<Felt>,
+) {
+    out.push(Felt::from(index));
+    serialize_field::<T>(rust, out);
+}
+
+/// Reads the index of an enum variant at the given offset.
+///
+/// # Arguments
+///
+/// * `felts` - The buffer to deserialize from.
+/// * `offset` - The offset of the variant index.
+/// * `enum_name` - The name of the enum, used for error reporting.
+#[inline]
+pub fn deserialize_variant_index(felts: &[Felt], offset: usize, enum_name: &str) -> Result<usize> {
+    let index = felts.get(offset).ok_or_else(|| {
+        Error::Deserialize(format!(
+            "Buffer too short to deserialize the variant index of enum {}",
+            enum_name
+        ))
+    })?;
+
+    let bytes = index.to_bytes_be();
+    if bytes[..24].iter().any(|b| *b != 0) {
+        return Err(Error::Deserialize(format!(
+            "Index {:#x} not handle for enum {}",
+            index, enum_name
+        )));
+    }
+
+    // Safe to unwrap since the slice is exactly 8 bytes long.
+    Ok(u64::from_be_bytes(bytes[24..].try_into().unwrap()) as usize)
+}
+
+/// Returns the events emitted during the transaction of the given receipt.
+///
+/// # Arguments
+///
+/// * `receipt` - The transaction receipt.
+#[inline]
+pub fn receipt_events(receipt: &TransactionReceipt) -> &[Event] {
+    match receipt {
+        TransactionReceipt::Invoke(r) => &r.events,
+        TransactionReceipt::L1Handler(r) => &r.events,
+        TransactionReceipt::Declare(r) => &r.events,
+        TransactionReceipt::Deploy(r) => &r.events,
+        TransactionReceipt::DeployAccount(r) => &r.events,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::U256;
+
+    #[test]
+    fn test_serialize_deserialize_fields() {
+        let mut out = vec![];
+        serialize_field::<Felt>(&Felt::ONE, &mut out);
+        serialize_field::<Vec<u8>>(&vec![2, 3], &mut out);
+        serialize_field::<U256>(&U256 { low: 4, high: 5 }, &mut out);
+
+        assert_eq!(out.len(), 6);
+
+        let mut offset = 0;
+        assert_eq!(
+            deserialize_field::<Felt>(&out, &mut offset).unwrap(),
+            Felt::ONE
+        );
+        assert_eq!(offset, 1);
+        assert_eq!(
+            deserialize_field::<Vec<u8>>(&out, &mut offset).unwrap(),
+            vec![2, 3]
+        );
+        assert_eq!(offset, 4);
+        assert_eq!(
+            deserialize_field::<U256>(&out, &mut offset).unwrap(),
+            U256 { low: 4, high: 5 }
+        );
+        assert_eq!(offset, 6);
+        assert!(deserialize_field::<Felt>(&out, &mut offset).is_err());
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct MyStruct {
+        a: Felt,
+        r#type: Vec<u8>,
+        c: (u32, U256),
+    }
+
+    impl CairoSerde for MyStruct {
+        type RustType = Self;
+
+        cairo_serde_struct_body!(MyStruct { a: Felt, r#type: Vec<u8>, c: (u32, U256) });
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Empty {}
+
+    impl CairoSerde for Empty {
+        type RustType = Self;
+
+        cairo_serde_struct_body!(Empty {});
+    }
+
+    #[test]
+    fn test_struct_body() {
+        let s = MyStruct {
+            a: Felt::ONE,
+            r#type: vec![2],
+            c: (3, U256 { low: 4, high: 5 }),
+        };
+
+        let felts = MyStruct::cairo_serialize(&s);
+        assert_eq!(MyStruct::cairo_serialized_size(&s), 6);
+        assert_eq!(
+            felts,
+            vec![
+                Felt::ONE,
+                Felt::ONE,
+                Felt::TWO,
+                Felt::THREE,
+                Felt::from(4),
+                Felt::from(5)
+            ]
+        );
+        assert_eq!(MyStruct::cairo_deserialize(&felts, 0).unwrap(), s);
+        assert_eq!(
+            MyStruct::cairo_deserialize(&felts[..5], 0)
+                .unwrap_err()
+                .path()
+                .unwrap(),
+            "MyStruct.c"
+        );
+
+        assert_eq!(Empty::cairo_serialized_size(&Empty {}), 0);
+        assert!(Empty::cairo_serialize(&Empty {}).is_empty());
+        assert_eq!(Empty::cairo_deserialize(&[], 0).unwrap(), Empty {});
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Point {
+        x: Felt,
+        y: U256,
+    }
+
+    impl CairoSerde for Point {
+        type RustType = Self;
+
+        cairo_serde_struct_body!(Point { x: Felt, y: U256 }, size = Some(3));
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum Side {
+        Buy,
+        Sell,
+    }
+
+    impl CairoSerde for Side {
+        type RustType = Self;
+
+        cairo_serde_enum_body!(
+            Side {
+                unit: [0 => Buy, 1 => Sell],
+                value: [],
+            },
+            size = Some(1)
+        );
+    }
+
+    #[test]
+    fn test_body_with_size() {
+        assert_eq!(MyStruct::SERIALIZED_SIZE, None);
+        assert_eq!(MyEnum::SERIALIZED_SIZE, None);
+
+        let p = Point {
+            x: Felt::ONE,
+            y: U256 { low: 2, high: 3 },
+        };
+        assert_eq!(Point::SERIALIZED_SIZE, Some(3));
+        assert_eq!(Point::cairo_serialize(&p).len(), 3);
+        assert_eq!(Point::cairo_serialized_size(&p), 3);
+
+        assert_eq!(Side::SERIALIZED_SIZE, Some(1));
+        assert_eq!(Side::cairo_serialize(&Side::Sell), vec![Felt::ONE]);
+        assert_eq!(
+            Side::cairo_deserialize(&[Felt::ONE], 0).unwrap(),
+            Side::Sell
+        );
+        assert_eq!(Side::cairo_serialized_size(&Side::Buy), 1);
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum MyEnum {
+        A,
+        B(Felt),
+        C((u8, Vec<u8>)),
+    }
+
+    impl CairoSerde for MyEnum {
+        type RustType = Self;
+
+        cairo_serde_enum_body!(MyEnum {
+            unit: [0 => A],
+            value: [1 => B(Felt), 2 => C((u8, Vec<u8>))],
+        });
+    }
+
+    #[test]
+    fn test_enum_body() {
+        let a = MyEnum::A;
+        assert_eq!(MyEnum::cairo_serialized_size(&a), 1);
+        assert_eq!(MyEnum::cairo_serialize(&a), vec![Felt::ZERO]);
+        assert_eq!(MyEnum::cairo_deserialize(&[Felt::ZERO], 0).unwrap(), a);
+
+        let b = MyEnum::B(Felt::THREE);
+        let felts = MyEnum::cairo_serialize(&b);
+        assert_eq!(MyEnum::cairo_serialized_size(&b), 2);
+        assert_eq!(felts, vec![Felt::ONE, Felt::THREE]);
+        assert_eq!(MyEnum::cairo_deserialize(&felts, 0).unwrap(), b);
+
+        let c = MyEnum::C((1, vec![2]));
+        let felts = MyEnum::cairo_serialize(&c);
+        assert_eq!(MyEnum::cairo_serialized_size(&c), 4);
+        assert_eq!(felts, vec![Felt::TWO, Felt::ONE, Felt::ONE, Felt::TWO]);
+        assert_eq!(MyEnum::cairo_deserialize(&felts, 0).unwrap(), c);
+
+        assert_eq!(
+            MyEnum::cairo_deserialize(&[Felt::TWO, Felt::ONE], 0)
+                .unwrap_err()
+                .path()
+                .unwrap(),
+            "MyEnum::C"
+        );
+        assert!(MyEnum::cairo_deserialize(&[Felt::THREE], 0).is_err());
+        assert!(MyEnum::cairo_deserialize(&[], 0).is_err());
+    }
+
+    #[test]
+    fn test_variant() {
+        let felts = serialize_variant::<(Felt, u32)>(2, &(Felt::THREE, 4));
+        assert_eq!(felts, vec![Felt::TWO, Felt::THREE, Felt::from(4)]);
+
+        let felts = serialize_variant::<()>(1, &());
+        assert_eq!(felts, vec![Felt::ONE]);
+
+        assert_eq!(deserialize_variant_index(&felts, 0, "MyEnum").unwrap(), 1);
+        assert!(deserialize_variant_index(&felts, 1, "MyEnum").is_err());
+        assert!(deserialize_variant_index(&[Felt::MAX], 0, "MyEnum").is_err());
+    }
+}
@@ -7,16 +7,41 @@
 //! types from Cairo (integers, felt etc...).
 //!
 mod error;
-pub use error::{Error, Result};
+pub use error::{CallError, Error, Result};
 
+pub mod blocking;
 pub mod call;
+#[doc(hidden)]
+pub mod helpers;
+#[cfg(feature = "test-support")]
+pub mod mock;
+pub mod paymaster;
+#[cfg(feature = "runtime")]
+pub mod runtime;
+#[cfg(any(feature = "schemars", feature = "utoipa"))]
+pub mod schema;
+#[cfg(feature = "const-selector")]
+pub mod selector;
+pub mod serde_bytes;
 pub mod serde_hex;
 pub mod types;
 
+/// Computes the selector of an entrypoint from its name at compile time: `selector!("transfer")`.
+pub use ::starknet::macros::selector;
+
+#[cfg(any(feature = "schemars", feature = "utoipa"))]
+pub use schema::FeltSchema;
+pub use serde_bytes::*;
 pub use serde_hex::*;
+pub use types::adapter::*;
 pub use types::array_legacy::*;
 pub use types::byte_array::*;
+pub use types::bytes31::*;
+pub use types::execution_info::*;
 pub use types::non_zero::*;
+#[cfg(feature = "secret")]
+pub use types::secret::*;
+pub use types::span::*;
 pub use types::starknet::*;
 pub use types::u256::*;
 pub use types::*;
@@ -48,9 +73,35 @@ pub trait CairoSerde {
     /// Serializes the given type into a Felt sequence.
     fn cairo_serialize(rust: &Self::RustType) -> Vec<Felt>;
 
-    /// TODO: add `serialize_to(rust: &Self::RustType, out: &mut Vec<Felt>)`.
-    /// for large buffers optimization.
+    /// Serializes the given type at the end of `out`.
+    ///
+    /// Building the calldata of a large payload with a single buffer avoids the
+    /// allocation of a `Vec` for every nested value. The default implementation
+    /// extends `out` with [`CairoSerde::cairo_serialize`], the containers and the
+    /// composite types override it to serialize their members into `out` directly.
+    #[inline]
+    fn cairo_serialize_to(rust: &Self::RustType, out: &mut Vec<Felt>) {
+        out.extend(Self::cairo_serialize(rust));
+    }
 
     /// Deserializes an array of felts into the given type.
     fn cairo_deserialize(felts: &[Felt], offset: usize) -> Result<Self::RustType>;
+
+    /// Deserializes an array of felts into the given type, and returns the number
+    /// of felts consumed.
+    ///
+    /// The containers and the composite types advance their offset with the size returned
+    /// for their members, instead of walking the deserialized members again with
+    /// [`CairoSerde::cairo_serialized_size`]. The default implementation deserializes with
+    /// [`CairoSerde::cairo_deserialize`] and computes the size of the value, which is cheap
+    /// for the types of static size.
+    #[inline]
+    fn cairo_deserialize_with_size(
+        felts: &[Felt],
+        offset: usize,
+    ) -> Result<(Self::RustType, usize)> {
+        let value = Self::cairo_deserialize(felts, offset)?;
+        let size = Self::cairo_serialized_size(&value);
+        Ok((value, size))
+    }
 }
@@ -0,0 +1,507 @@
+//! An in-memory provider, to unit test the readers of the generated contracts
+//! without a node or a devnet.
+//!
+//! Only available with the `test-support` feature. The [`MockProvider`] answers the calls
+//! to the views with the return data programmed for their selector and calldata:
+//!
+//! ```ignore
+//! let provider = MockProvider::new().with_call(
+//!     selector!("balance_of"),
+//!     vec![owner.0],
+//!     vec![Felt::from(1000), Felt::ZERO],
+//! );
+//!
+//! let reader = MyContractReader::new(address, &provider);
+//! assert_eq!(reader.balance_of(&owner).call().await?, U256 { low: 1000, high: 0 });
+//! ```
+//!
+//! The other methods of the [`Provider`] fail with [`MockProviderError::Unsupported`].
+use async_trait::async_trait;
+use starknet::core::types::{
+    BlockHashAndNumber, BlockId, BroadcastedDeclareTransaction,
+    BroadcastedDeployAccountTransaction, BroadcastedInvokeTransaction, BroadcastedTransaction,
+    ContractClass, ContractErrorData, DeclareTransactionResult, DeployAccountTransactionResult,
+    EventFilter, EventsPage, FeeEstimate, Felt, FunctionCall, InvokeTransactionResult,
+    MaybePendingBlockWithReceipts, MaybePendingBlockWithTxHashes, MaybePendingBlockWithTxs,
+    MaybePendingStateUpdate, MsgFromL1, SimulatedTransaction, SimulationFlag,
+    SimulationFlagForEstimateFee, StarknetError, SyncStatusType, Transaction,
+    TransactionReceiptWithBlockInfo, TransactionStatus, TransactionTrace, TransactionTraceWithHash,
+};
+use starknet::providers::jsonrpc::JsonRpcClientError;
+use starknet::providers::{Provider, ProviderError, ProviderRequestData, ProviderResponseData};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// The error of a request the [`MockProvider`] can't answer.
+///
+/// As `starknet-rs` only boxes the errors of its own providers into [`ProviderError::Other`],
+/// it is wrapped into a `JsonRpcClientError::TransportError`, with the same message.
+#[derive(Debug, thiserror::Error)]
+pub enum MockProviderError {
+    /// No response is programmed for the call.
+    #[error("MockProvider: no response for the call of selector {selector:#x} with calldata {calldata:?}")]
+    UnexpectedCall {
+        /// The selector of the call.
+        selector: Felt,
+        /// The calldata of the call.
+        calldata: Vec<Felt>,
+    },
+    /// The method of the provider is not supported.
+    #[error("MockProvider: `{0}` is not supported")]
+    Unsupported(&'static str),
+}
+
+impl From<MockProviderError> for ProviderError {
+    fn from(e: MockProviderError) -> Self {
+        ProviderError::Other(Box::new(JsonRpcClientError::TransportError(e)))
+    }
+}
+
+/// The programmed response of a call.
+#[derive(Debug, Clone)]
+enum MockResponse {
+    Retdata(Vec<Felt>),
+    Revert(String),
+}
+
+/// A provider answering the calls from a map of `(selector, calldata)` to return data.
+///
+/// The calls are answered whatever the called contract and the block, and are recorded
+/// to be checked with [`MockProvider::requests`].
+#[derive(Debug, Default)]
+pub struct MockProvider {
+    chain_id: Felt,
+    block_number: u64,
+    responses: HashMap<(Felt, Vec<Felt>), MockResponse>,
+    requests: Mutex<Vec<FunctionCall>>,
+}
+
+impl MockProvider {
+    /// Creates a provider without any programmed call.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Programs the return data of the calls of the selector with the calldata.
+    pub fn with_call(mut self, selector: Felt, calldata: Vec<Felt>, retdata: Vec<Felt>) -> Self {
+        self.responses
+            .insert((selector, calldata), MockResponse::Retdata(retdata));
+        self
+    }
+
+    /// Programs the calls of the selector with the calldata to fail,
+    /// like a contract reverting with the given error.
+    pub fn with_call_error(
+        mut self,
+        selector: Felt,
+        calldata: Vec<Felt>,
+        revert_error: &str,
+    ) -> Self {
+        self.responses.insert(
+            (selector, calldata),
+            MockResponse::Revert(revert_error.to_string()),
+        );
+        self
+    }
+
+    /// Sets the chain id returned by the provider, zero by default.
+    pub fn with_chain_id(self, chain_id: Felt) -> Self {
+        Self { chain_id, ..self }
+    }
+
+    /// Sets the number of the latest block returned by the provider, zero by default.
+    pub fn with_block_number(self, block_number: u64) -> Self {
+        Self {
+            block_number,
+            ..self
+        }
+    }
+
+    /// Returns the calls received by the provider, in order.
+    pub fn requests(&self) -> Vec<FunctionCall> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+fn unsupported(method: &'static str) -> ProviderError {
+    MockProviderError::Unsupported(method).into()
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl Provider for MockProvider {
+    async fn spec_version(&self) -> Result<String, ProviderError> {
+        Err(unsupported("spec_version"))
+    }
+
+    async fn get_block_with_tx_hashes<B>(
+        &self,
+        _block_id: B,
+    ) -> Result<MaybePendingBlockWithTxHashes, ProviderError>
+    where
+        B: AsRef<BlockId> + Send + Sync,
+    {
+        Err(unsupported("get_block_with_tx_hashes"))
+    }
+
+    async fn get_block_with_txs<B>(
+        &self,
+        _block_id: B,
+    ) -> Result<MaybePendingBlockWithTxs, ProviderError>
+    where
+        B: AsRef<BlockId> + Send + Sync,
+    {
+        Err(unsupported("get_block_with_txs"))
+    }
+
+    async fn get_block_with_receipts<B>(
+        &self,
+        _block_id: B,
+    ) -> Result<MaybePendingBlockWithReceipts, ProviderError>
+    where
+        B: AsRef<BlockId> + Send + Sync,
+    {
+        Err(unsupported("get_block_with_receipts"))
+    }
+
+    async fn get_state_update<B>(
+        &self,
+        _block_id: B,
+    ) -> Result<MaybePendingStateUpdate, ProviderError>
+    where
+        B: AsRef<BlockId> + Send + Sync,
+    {
+        Err(unsupported("get_state_update"))
+    }
+
+    async fn get_storage_at<A, K, B>(
+        &self,
+        _contract_address: A,
+        _key: K,
+        _block_id: B,
+    ) -> Result<Felt, ProviderError>
+    where
+        A: AsRef<Felt> + Send + Sync,
+        K: AsRef<Felt> + Send + Sync,
+        B: AsRef<BlockId> + Send + Sync,
+    {
+        Err(unsupported("get_storage_at"))
+    }
+
+    async fn get_transaction_status<H>(
+        &self,
+        _transaction_hash: H,
+    ) -> Result<TransactionStatus, ProviderError>
+    where
+        H: AsRef<Felt> + Send + Sync,
+    {
+        Err(unsupported("get_transaction_status"))
+    }
+
+    async fn get_transaction_by_hash<H>(
+        &self,
+        _transaction_hash: H,
+    ) -> Result<Transaction, ProviderError>
+    where
+        H: AsRef<Felt> + Send + Sync,
+    {
+        Err(unsupported("get_transaction_by_hash"))
+    }
+
+    async fn get_transaction_by_block_id_and_index<B>(
+        &self,
+        _block_id: B,
+        _index: u64,
+    ) -> Result<Transaction, ProviderError>
+    where
+        B: AsRef<BlockId> + Send + Sync,
+    {
+        Err(unsupported("get_transaction_by_block_id_and_index"))
+    }
+
+    async fn get_transaction_receipt<H>(
+        &self,
+        _transaction_hash: H,
+    ) -> Result<TransactionReceiptWithBlockInfo, ProviderError>
+    where
+        H: AsRef<Felt> + Send + Sync,
+    {
+        Err(unsupported("get_transaction_receipt"))
+    }
+
+    async fn get_class<B, H>(
+        &self,
+        _block_id: B,
+        _class_hash: H,
+    ) -> Result<ContractClass, ProviderError>
+    where
+        B: AsRef<BlockId> + Send + Sync,
+        H: AsRef<Felt> + Send + Sync,
+    {
+        Err(unsupported("get_class"))
+    }
+
+    async fn get_class_hash_at<B, A>(
+        &self,
+        _block_id: B,
+        _contract_address: A,
+    ) -> Result<Felt, ProviderError>
+    where
+        B: AsRef<BlockId> + Send + Sync,
+        A: AsRef<Felt> + Send + Sync,
+    {
+        Err(unsupported("get_class_hash_at"))
+    }
+
+    async fn get_class_at<B, A>(
+        &self,
+        _block_id: B,
+        _contract_address: A,
+    ) -> Result<ContractClass, ProviderError>
+    where
+        B: AsRef<BlockId> + Send + Sync,
+        A: AsRef<Felt> + Send + Sync,
+    {
+        Err(unsupported("get_class_at"))
+    }
+
+    async fn get_block_transaction_count<B>(&self, _block_id: B) -> Result<u64, ProviderError>
+    where
+        B: AsRef<BlockId> + Send + Sync,
+    {
+        Err(unsupported("get_block_transaction_count"))
+    }
+
+    async fn call<R, B>(&self, request: R, _block_id: B) -> Result<Vec<Felt>, ProviderError>
+    where
+        R: AsRef<FunctionCall> + Send + Sync,
+        B: AsRef<BlockId> + Send + Sync,
+    {
+        let request = request.as_ref();
+        self.requests.lock().unwrap().push(request.clone());
+
+        let key = (request.entry_point_selector, request.calldata.clone());
+
+        match self.responses.get(&key) {
+            Some(MockResponse::Retdata(retdata)) => Ok(retdata.clone()),
+            Some(MockResponse::Revert(revert_error)) => Err(ProviderError::StarknetError(
+                StarknetError::ContractError(ContractErrorData {
+                    revert_error: revert_error.clone(),
+                }),
+            )),
+            None => Err(MockProviderError::UnexpectedCall {
+                selector: key.0,
+                calldata: key.1,
+            }
+            .into()),
+        }
+    }
+
+    async fn estimate_fee<R, S, B>(
+        &self,
+        _request: R,
+        _simulation_flags: S,
+        _block_id: B,
+    ) -> Result<Vec<FeeEstimate>, ProviderError>
+    where
+        R: AsRef<[BroadcastedTransaction]> + Send + Sync,
+        S: AsRef<[SimulationFlagForEstimateFee]> + Send + Sync,
+        B: AsRef<BlockId> + Send + Sync,
+    {
+        Err(unsupported("estimate_fee"))
+    }
+
+    async fn estimate_message_fee<M, B>(
+        &self,
+        _message: M,
+        _block_id: B,
+    ) -> Result<FeeEstimate, ProviderError>
+    where
+        M: AsRef<MsgFromL1> + Send + Sync,
+        B: AsRef<BlockId> + Send + Sync,
+    {
+        Err(unsupported("estimate_message_fee"))
+    }
+
+    async fn block_number(&self) -> Result<u64, ProviderError> {
+        Ok(self.block_number)
+    }
+
+    async fn block_hash_and_number(&self) -> Result<BlockHashAndNumber, ProviderError> {
+        Err(unsupported("block_hash_and_number"))
+    }
+
+    async fn chain_id(&self) -> Result<Felt, ProviderError> {
+        Ok(self.chain_id)
+    }
+
+    async fn syncing(&self) -> Result<SyncStatusType, ProviderError> {
+        Err(unsupported("syncing"))
+    }
+
+    async fn get_events(
+        &self,
+        _filter: EventFilter,
+        _continuation_token: Option<String>,
+        _chunk_size: u64,
+    ) -> Result<EventsPage, ProviderError> {
+        Err(unsupported("get_events"))
+    }
+
+    async fn get_nonce<B, A>(
+        &self,
+        _block_id: B,
+        _contract_address: A,
+    ) -> Result<Felt, ProviderError>
+    where
+        B: AsRef<BlockId> + Send + Sync,
+        A: AsRef<Felt> + Send + Sync,
+    {
+        Err(unsupported("get_nonce"))
+    }
+
+    async fn add_invoke_transaction<I>(
+        &self,
+        _invoke_transaction: I,
+    ) -> Result<InvokeTransactionResult, ProviderError>
+    where
+        I: AsRef<BroadcastedInvokeTransaction> + Send + Sync,
+    {
+        Err(unsupported("add_invoke_transaction"))
+    }
+
+    async fn add_declare_transaction<D>(
+        &self,
+        _declare_transaction: D,
+    ) -> Result<DeclareTransactionResult, ProviderError>
+    where
+        D: AsRef<BroadcastedDeclareTransaction> + Send + Sync,
+    {
+        Err(unsupported("add_declare_transaction"))
+    }
+
+    async fn add_deploy_account_transaction<D>(
+        &self,
+        _deploy_account_transaction: D,
+    ) -> Result<DeployAccountTransactionResult, ProviderError>
+    where
+        D: AsRef<BroadcastedDeployAccountTransaction> + Send + Sync,
+    {
+        Err(unsupported("add_deploy_account_transaction"))
+    }
+
+    async fn trace_transaction<H>(
+        &self,
+        _transaction_hash: H,
+    ) -> Result<TransactionTrace, ProviderError>
+    where
+        H: AsRef<Felt> + Send + Sync,
+    {
+        Err(unsupported("trace_transaction"))
+    }
+
+    async fn simulate_transactions<B, T, S>(
+        &self,
+        _block_id: B,
+        _transactions: T,
+        _simulation_flags: S,
+    ) -> Result<Vec<SimulatedTransaction>, ProviderError>
+    where
+        B: AsRef<BlockId> + Send + Sync,
+        T: AsRef<[BroadcastedTransaction]> + Send + Sync,
+        S: AsRef<[SimulationFlag]> + Send + Sync,
+    {
+        Err(unsupported("simulate_transactions"))
+    }
+
+    async fn trace_block_transactions<B>(
+        &self,
+        _block_id: B,
+    ) -> Result<Vec<TransactionTraceWithHash>, ProviderError>
+    where
+        B: AsRef<BlockId> + Send + Sync,
+    {
+        Err(unsupported("trace_block_transactions"))
+    }
+
+    async fn batch_requests<R>(
+        &self,
+        _requests: R,
+    ) -> Result<Vec<ProviderResponseData>, ProviderError>
+    where
+        R: AsRef<[ProviderRequestData]> + Send + Sync,
+    {
+        Err(unsupported("batch_requests"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::call::FCall;
+    use crate::{CallError, Error, U256};
+    use starknet::macros::selector;
+
+    fn balance_of(provider: &MockProvider, owner: Felt) -> FCall<'_, MockProvider, U256> {
+        FCall::new(
+            FunctionCall {
+                contract_address: Felt::ONE,
+                entry_point_selector: selector!("balance_of"),
+                calldata: vec![owner],
+            },
+            provider,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_mock_provider_call() {
+        let provider = MockProvider::new()
+            .with_call(
+                selector!("balance_of"),
+                vec![Felt::TWO],
+                vec![Felt::from(1000), Felt::ZERO],
+            )
+            .with_call_error(
+                selector!("balance_of"),
+                vec![Felt::THREE],
+                "u256_sub Overflow",
+            )
+            .with_chain_id(Felt::from(0x534e5f5345504f4c4941_u128));
+
+        assert_eq!(
+            balance_of(&provider, Felt::TWO).call().await.unwrap(),
+            U256 { low: 1000, high: 0 }
+        );
+
+        assert!(matches!(
+            balance_of(&provider, Felt::THREE).call().await,
+            Err(Error::Call(CallError::Reverted(e))) if e == "u256_sub Overflow"
+        ));
+
+        assert!(matches!(
+            balance_of(&provider, Felt::ZERO).call().await,
+            Err(Error::Provider(ProviderError::Other(e)))
+                if e.to_string().starts_with("MockProvider: no response for the call")
+        ));
+
+        assert_eq!(provider.requests().len(), 3);
+        assert_eq!(provider.requests()[0].calldata, vec![Felt::TWO]);
+        assert_eq!(
+            provider.chain_id().await.unwrap(),
+            Felt::from(0x534e5f5345504f4c4941_u128)
+        );
+        assert!(provider
+            .get_events(
+                EventFilter {
+                    from_block: None,
+                    to_block: None,
+                    address: None,
+                    keys: None,
+                },
+                None,
+                10
+            )
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,434 @@
+//! Sponsored (gasless) execution of the externals, through a paymaster.
+//!
+//! With the `paymaster` option of `abigen!`, the externals return an [`Invoke`] instead of the
+//! execution of `starknet-rs`. The execution is still reachable (`send()`, `estimate_fee()`...
+//! are available on the `Invoke`), and the calls can be routed through a [`Paymaster`] instead.
+//!
+//! [`RelayerPaymaster`] is a relayer account sending the calls as a SNIP-9 outside execution
+//! signed by the account, the relayer paying the fees:
+//!
+//! ```ignore
+//! let paymaster = RelayerPaymaster::new(relayer_account);
+//!
+//! let tx_hash = contract
+//!     .move_player(&direction)
+//!     .sponsored(&paymaster)
+//!     .sign(&player_signer, nonce, now + 600)
+//!     .await?
+//!     .send()
+//!     .await?;
+//! ```
+//!
+//! Other flows (like a sponsor endpoint) implement [`Paymaster`], using the calls of the
+//! [`SponsoredExecution`] or its signed outside execution.
+use async_trait::async_trait;
+use starknet::accounts::{Account, ConnectedAccount};
+use starknet::core::types::{Call, Felt};
+use starknet::signers::Signer;
+use std::ops::Deref;
+
+use crate::call::outside_execution::{OutsideExecution, ANY_CALLER};
+use crate::Error;
+
+/// Paymaster result.
+pub type PaymasterResult<T> = core::result::Result<T, PaymasterError>;
+
+/// A paymaster error.
+#[derive(Debug, thiserror::Error)]
+pub enum PaymasterError {
+    /// The paymaster refused to sponsor the execution.
+    #[error("Execution not sponsored: {0}")]
+    Rejected(String),
+    /// The paymaster could not be reached, or failed to execute the calls.
+    #[error("Paymaster error: {0}")]
+    Other(#[from] Box<dyn std::error::Error + Send + Sync>),
+}
+
+/// An execution whose fees are paid by a sponsor.
+#[derive(Debug, Clone)]
+pub struct SponsoredExecution {
+    /// The account executing the calls.
+    pub account_address: Felt,
+    /// The chain the account is on.
+    pub chain_id: Felt,
+    /// The calls to be executed by the account.
+    pub calls: Vec<Call>,
+    /// The outside execution of the calls signed by the account, if signed
+    /// with [`Sponsored::sign`].
+    pub signed: Option<SignedOutsideExecution>,
+}
+
+/// An outside execution (SNIP-9) with the signature of the account executing it.
+#[derive(Debug, Clone)]
+pub struct SignedOutsideExecution {
+    /// The outside execution of the calls.
+    pub execution: OutsideExecution,
+    /// The signature of the execution by the account.
+    pub signature: Vec<Felt>,
+}
+
+/// A flow executing calls on behalf of an account, the fees being paid by a sponsor.
+#[async_trait]
+pub trait Paymaster {
+    /// Executes the calls, and returns the hash of the transaction.
+    async fn execute(&self, execution: &SponsoredExecution) -> PaymasterResult<Felt>;
+
+    /// Returns the caller allowed to send the outside executions signed for the paymaster,
+    /// [`ANY_CALLER`] by default.
+    fn caller(&self) -> Felt {
+        ANY_CALLER
+    }
+}
+
+#[async_trait]
+impl<T> Paymaster for &T
+where
+    T: Paymaster + Sync + ?Sized,
+{
+    async fn execute(&self, execution: &SponsoredExecution) -> PaymasterResult<Felt> {
+        (**self).execute(execution).await
+    }
+
+    fn caller(&self) -> Felt {
+        (**self).caller()
+    }
+}
+
+#[async_trait]
+impl<T> Paymaster for std::sync::Arc<T>
+where
+    T: Paymaster + Send + Sync + ?Sized,
+{
+    async fn execute(&self, execution: &SponsoredExecution) -> PaymasterResult<Felt> {
+        (**self).execute(execution).await
+    }
+
+    fn caller(&self) -> Felt {
+        (**self).caller()
+    }
+}
+
+/// The invocation of externals, executed by the account (the execution `E` of `starknet-rs`)
+/// or sponsored by a paymaster.
+///
+/// The methods of the execution taking it by reference (`send()`, `estimate_fee()`...) are
+/// available on the invocation, the ones taking it by value (`nonce()`...) with
+/// [`Invoke::map_execution`].
+#[derive(Debug)]
+pub struct Invoke<'a, A, E> {
+    account: &'a A,
+    calls: Vec<Call>,
+    execution: E,
+}
+
+impl<'a, A, E> Invoke<'a, A, E> {
+    /// Creates the invocation of the `calls`, `execution` being their execution by the `account`.
+    pub fn new(account: &'a A, calls: Vec<Call>, execution: E) -> Self {
+        Self {
+            account,
+            calls,
+            execution,
+        }
+    }
+
+    /// Returns the invoked calls.
+    pub fn calls(&self) -> &[Call] {
+        &self.calls
+    }
+
+    /// Returns the execution by the account.
+    pub fn into_execution(self) -> E {
+        self.execution
+    }
+
+    /// Configures the execution by the account, like `map_execution(|e| e.nonce(nonce))`.
+    pub fn map_execution(self, f: impl FnOnce(E) -> E) -> Self {
+        Self {
+            execution: f(self.execution),
+            ..self
+        }
+    }
+
+    /// Routes the execution through the `paymaster`, which pays its fees.
+    pub fn sponsored<P>(self, paymaster: &'a P) -> Sponsored<'a, A, P> {
+        Sponsored {
+            account: self.account,
+            calls: self.calls,
+            paymaster,
+            signed: None,
+        }
+    }
+}
+
+impl<'a, A, E> Deref for Invoke<'a, A, E> {
+    type Target = E;
+
+    fn deref(&self) -> &E {
+        &self.execution
+    }
+}
+
+/// The invocation of externals, sponsored by a paymaster.
+#[derive(Debug)]
+pub struct Sponsored<'a, A, P> {
+    account: &'a A,
+    calls: Vec<Call>,
+    paymaster: &'a P,
+    signed: Option<SignedOutsideExecution>,
+}
+
+impl<'a, A, P> Sponsored<'a, A, P>
+where
+    A: Account + Sync,
+    P: Paymaster + Sync,
+{
+    /// Signs the calls as an outside execution of the account, sent by the caller of the
+    /// paymaster, as required by the relayers like [`RelayerPaymaster`].
+    ///
+    /// # Arguments
+    ///
+    /// * `signer` - The signer of the account.
+    /// * `nonce` - The nonce of the outside execution, which can't be reused by the account.
+    /// * `execute_before` - The timestamp (in seconds) before which the execution is valid.
+    pub async fn sign<S>(
+        self,
+        signer: &S,
+        nonce: Felt,
+        execute_before: u64,
+    ) -> PaymasterResult<Self>
+    where
+        S: Signer + Sync,
+        S::SignError: Send + Sync + 'static,
+    {
+        let execution = OutsideExecution::new(nonce, self.calls.clone())
+            .caller(self.paymaster.caller())
+            .execute_before(execute_before);
+
+        let signature = execution
+            .sign(signer, self.account.chain_id(), self.account.address())
+            .await
+            .map_err(|e| PaymasterError::Other(Box::new(e)))?;
+
+        Ok(Self {
+            signed: Some(SignedOutsideExecution {
+                execution,
+                signature,
+            }),
+            ..self
+        })
+    }
+
+    /// Returns the execution given to the paymaster.
+    pub fn execution(&self) -> SponsoredExecution {
+        SponsoredExecution {
+            account_address: self.account.address(),
+            chain_id: self.account.chain_id(),
+            calls: self.calls.clone(),
+            signed: self.signed.clone(),
+        }
+    }
+
+    /// Sends the calls to the paymaster, and returns the hash of the transaction.
+    pub async fn send(&self) -> PaymasterResult<Felt> {
+        self.paymaster.execute(&self.execution()).await
+    }
+}
+
+/// A relayer account sending the outside executions signed by the accounts,
+/// and paying their fees.
+///
+/// The executions must be signed with [`Sponsored::sign`], the relayer being their only caller.
+#[derive(Debug)]
+pub struct RelayerPaymaster<R> {
+    relayer: R,
+}
+
+impl<R> RelayerPaymaster<R>
+where
+    R: ConnectedAccount + Sync,
+{
+    /// Creates a paymaster sending the executions from the `relayer` account.
+    pub fn new(relayer: R) -> Self {
+        Self { relayer }
+    }
+
+    /// Returns the `execute_from_outside_v2` call of the execution, sent by the relayer.
+    pub fn relayed_call(&self, execution: &SponsoredExecution) -> PaymasterResult<Call> {
+        let Some(signed) = &execution.signed else {
+            return Err(PaymasterError::Rejected(
+                "the outside execution is not signed by the account".to_string(),
+            ));
+        };
+
+        if signed.execution.caller != self.relayer.address() {
+            return Err(PaymasterError::Rejected(format!(
+                "the outside execution can't be sent by the relayer {:#x}",
+                self.relayer.address()
+            )));
+        }
+
+        Ok(signed
+            .execution
+            .execute_from_outside_call(execution.account_address, signed.signature.clone()))
+    }
+}
+
+#[async_trait]
+impl<R> Paymaster for RelayerPaymaster<R>
+where
+    R: ConnectedAccount + Sync + Send,
+{
+    async fn execute(&self, execution: &SponsoredExecution) -> PaymasterResult<Felt> {
+        let call = self.relayed_call(execution)?;
+
+        self.relayer
+            .execute_v3(vec![call])
+            .send()
+            .await
+            .map(|r| r.transaction_hash)
+            .map_err(|e| PaymasterError::Other(Box::new(Error::from_account(e))))
+    }
+
+    fn caller(&self) -> Felt {
+        self.relayer.address()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::call::outside_execution::EXECUTE_FROM_OUTSIDE_V2_SELECTOR;
+    use starknet::accounts::{ExecutionEncoding, SingleOwnerAccount};
+    use starknet::core::crypto::{ecdsa_verify, Signature};
+    use starknet::providers::{jsonrpc::HttpTransport, JsonRpcClient, Url};
+    use starknet::signers::{LocalWallet, SigningKey};
+    use std::sync::Mutex;
+
+    /// A sponsor recording the executions, and rejecting the empty ones.
+    #[derive(Default)]
+    struct Sponsor {
+        executions: Mutex<Vec<SponsoredExecution>>,
+    }
+
+    #[async_trait]
+    impl Paymaster for Sponsor {
+        async fn execute(&self, execution: &SponsoredExecution) -> PaymasterResult<Felt> {
+            if execution.calls.is_empty() {
+                return Err(PaymasterError::Rejected("no call".to_string()));
+            }
+
+            self.executions.lock().unwrap().push(execution.clone());
+            Ok(Felt::from(0xabc))
+        }
+    }
+
+    fn account() -> SingleOwnerAccount<JsonRpcClient<HttpTransport>, LocalWallet> {
+        account_at(Felt::from(0x123))
+    }
+
+    fn account_at(address: Felt) -> SingleOwnerAccount<JsonRpcClient<HttpTransport>, LocalWallet> {
+        SingleOwnerAccount::new(
+            JsonRpcClient::new(HttpTransport::new(
+                Url::parse("http://localhost:5050").unwrap(),
+            )),
+            LocalWallet::from(SigningKey::from_secret_scalar(Felt::ONE)),
+            address,
+            Felt::from(0x534e),
+            ExecutionEncoding::New,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_sponsored() {
+        let account = account();
+        let call = Call {
+            to: Felt::ONE,
+            selector: Felt::TWO,
+            calldata: vec![Felt::THREE],
+        };
+
+        let invoke = Invoke::new(&account, vec![call], ());
+        assert_eq!(invoke.calls()[0].calldata, vec![Felt::THREE]);
+
+        let sponsor = Sponsor::default();
+        let tx_hash = invoke.sponsored(&sponsor).send().await.unwrap();
+
+        assert_eq!(tx_hash, Felt::from(0xabc));
+
+        let execution = sponsor.executions.lock().unwrap()[0].clone();
+        assert_eq!(execution.account_address, Felt::from(0x123));
+        assert_eq!(execution.chain_id, Felt::from(0x534e));
+        assert_eq!(execution.calls[0].selector, Felt::TWO);
+
+        let rejected = Invoke::new(&account, vec![], ())
+            .sponsored(&sponsor)
+            .send()
+            .await;
+        assert!(matches!(rejected, Err(PaymasterError::Rejected(_))));
+    }
+
+    #[tokio::test]
+    async fn test_relayer() {
+        let account = account();
+        let key = SigningKey::from_secret_scalar(Felt::ONE);
+        let call = Call {
+            to: Felt::ONE,
+            selector: Felt::TWO,
+            calldata: vec![Felt::THREE],
+        };
+
+        let relayer = RelayerPaymaster::new(account_at(Felt::from(0x456)));
+        assert_eq!(Paymaster::caller(&relayer), Felt::from(0x456));
+
+        let unsigned = Invoke::new(&account, vec![call.clone()], ())
+            .sponsored(&relayer)
+            .execution();
+        assert!(matches!(
+            relayer.relayed_call(&unsigned),
+            Err(PaymasterError::Rejected(_))
+        ));
+
+        let execution = Invoke::new(&account, vec![call], ())
+            .sponsored(&relayer)
+            .sign(&LocalWallet::from(key.clone()), Felt::from(9), 100)
+            .await
+            .unwrap()
+            .execution();
+
+        let signed = execution.signed.clone().unwrap();
+        assert_eq!(signed.execution.caller, Felt::from(0x456));
+        assert_eq!(signed.execution.nonce, Felt::from(9));
+        assert_eq!(signed.execution.execute_before, 100);
+        assert!(ecdsa_verify(
+            &key.verifying_key().scalar(),
+            &signed
+                .execution
+                .message_hash(Felt::from(0x534e), Felt::from(0x123)),
+            &Signature {
+                r: signed.signature[0],
+                s: signed.signature[1],
+            }
+        )
+        .unwrap());
+
+        let relayed = relayer.relayed_call(&execution).unwrap();
+        assert_eq!(relayed.to, Felt::from(0x123));
+        assert_eq!(relayed.selector, EXECUTE_FROM_OUTSIDE_V2_SELECTOR);
+
+        // Signed for another relayer.
+        let other = RelayerPaymaster::new(account_at(Felt::from(0x789)));
+        assert!(matches!(
+            other.relayed_call(&execution),
+            Err(PaymasterError::Rejected(_))
+        ));
+    }
+
+    #[test]
+    fn test_map_execution() {
+        let account = account();
+        let invoke = Invoke::new(&account, vec![], 1).map_execution(|e| e + 1);
+
+        assert_eq!(*invoke, 2);
+        assert_eq!(invoke.into_execution(), 2);
+    }
+}
@@ -0,0 +1,922 @@
+//! Replay of the events emitted by a contract.
+//!
+//! The [`Backfiller`] pages through the events of a contract in a range of blocks,
+//! decodes them into the event enum generated for the contract (`<Contract>Event`),
+//! and gives them in order to a handler, along with:
+//!
+//! * [`BackfillEvent::Checkpoint`] once the events of a page are handled, to persist
+//!   the progress and resume from it later with [`Backfiller::with_checkpoint`],
+//!   or directly into a [`CheckpointStore`] with [`Backfiller::run_with_store`].
+//! * [`BackfillEvent::Reorg`] when the blocks already processed have been reorganized:
+//!   the events handled from the given block must be reverted, they are replayed from
+//!   the new chain.
+//!
+//! The reorgs are detected by checking, before each page, the hash of the last processed
+//! block. The hashes of the last processed blocks are tracked to find where the chains fork.
+//!
+//! Without checkpoints nor reorgs, [`stream_events`] pages through the events of a
+//! block range and yields them decoded as a `Stream`, which is what the `events()`
+//! method of the generated contracts and readers returns.
+//!
+//! ```ignore
+//! let mut backfiller = Backfiller::<_, MyContractEvent>::new(provider, address, 1_000);
+//!
+//! backfiller
+//!     .run(|e| async move {
+//!         match e {
+//!             BackfillEvent::Event(e) => index(e.event).await,
+//!             BackfillEvent::Reorg { from_block } => revert(from_block).await,
+//!             BackfillEvent::Checkpoint(c) => save(c).await,
+//!         }
+//!     })
+//!     .await?;
+//! ```
+use async_trait::async_trait;
+use futures_util::stream::{self, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use starknet::core::types::{
+    BlockId, EmittedEvent, EventFilter, EventsPage, Felt, MaybePendingBlockWithTxHashes,
+    StarknetError,
+};
+use starknet::providers::{Provider, ProviderError};
+use std::collections::VecDeque;
+use std::future::Future;
+use std::marker::PhantomData;
+
+use super::CheckpointStore;
+
+/// Default number of events requested per page.
+pub const DEFAULT_CHUNK_SIZE: u64 = 100;
+
+/// Default number of processed blocks whose hash is tracked to resolve the reorgs.
+pub const DEFAULT_TRACKED_BLOCKS: usize = 32;
+
+/// Error of a backfill.
+#[derive(Debug, thiserror::Error)]
+pub enum BackfillError {
+    #[error("Provider error {0:?}.")]
+    Provider(#[from] ProviderError),
+    /// An event of the contract could not be decoded into the event enum.
+    #[error("Event of the transaction {transaction_hash:#x} could not be decoded: {message}")]
+    Decode {
+        transaction_hash: Felt,
+        message: String,
+    },
+    /// The handler failed to handle an event.
+    #[error("Handler error: {0}")]
+    Handler(Box<dyn std::error::Error + Send + Sync>),
+    /// The checkpoint could not be loaded or saved.
+    #[error("Checkpoint store error: {0}")]
+    Store(Box<dyn std::error::Error + Send + Sync>),
+    /// The processed blocks were reorganized deeper than the tracked ones,
+    /// the fork point of the chains is unknown.
+    #[error("Block {block_number} was reorganized beyond the tracked blocks.")]
+    UnresolvedReorg { block_number: u64 },
+}
+
+/// The progress of a backfill, to resume it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    /// Number of the last processed block.
+    pub block_number: u64,
+    /// Hash of the last processed block, checked to detect the reorgs.
+    pub block_hash: Felt,
+    /// The query interrupted after the last processed block, if the events
+    /// of its range were not all handled.
+    pub continuation: Option<Continuation>,
+}
+
+/// A query of events interrupted between two pages.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Continuation {
+    /// First block of the query.
+    pub from_block: u64,
+    /// Last block of the query.
+    pub to_block: u64,
+    /// Continuation token of the next page.
+    pub token: String,
+}
+
+/// An event of the contract, decoded.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedEvent<E> {
+    pub event: E,
+    pub block_number: u64,
+    pub block_hash: Felt,
+    pub transaction_hash: Felt,
+}
+
+impl<E> DecodedEvent<E>
+where
+    E: for<'a> TryFrom<&'a EmittedEvent, Error = String>,
+{
+    /// Decodes an event, returns `None` for an event of the pending block.
+    fn decode(raw: &EmittedEvent) -> Option<Result<Self, BackfillError>> {
+        // Only the accepted blocks have a number and a hash.
+        let (Some(block_number), Some(block_hash)) = (raw.block_number, raw.block_hash) else {
+            return None;
+        };
+
+        Some(
+            E::try_from(raw)
+                .map(|event| DecodedEvent {
+                    event,
+                    block_number,
+                    block_hash,
+                    transaction_hash: raw.transaction_hash,
+                })
+                .map_err(|message| BackfillError::Decode {
+                    transaction_hash: raw.transaction_hash,
+                    message,
+                }),
+        )
+    }
+}
+
+/// What the handler of a backfill is given, in order.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BackfillEvent<E> {
+    /// An event of the contract.
+    Event(DecodedEvent<E>),
+    /// The blocks from `from_block` have been reorganized, the events handled
+    /// from this block must be reverted. The backfill resumes from it.
+    Reorg { from_block: u64 },
+    /// The events up to this checkpoint have been handled.
+    Checkpoint(Checkpoint),
+}
+
+/// Source of the events and the blocks of a backfill, implemented by all the providers.
+#[async_trait]
+pub trait EventSource {
+    /// Returns the number of the latest accepted block.
+    async fn latest_block_number(&self) -> Result<u64, ProviderError>;
+
+    /// Returns the hash of the block at `block_number`.
+    async fn block_hash(&self, block_number: u64) -> Result<Felt, ProviderError>;
+
+    /// Returns a page of the events matching the filter.
+    async fn events_page(
+        &self,
+        filter: EventFilter,
+        continuation_token: Option<String>,
+        chunk_size: u64,
+    ) -> Result<EventsPage, ProviderError>;
+}
+
+#[async_trait]
+impl<P> EventSource for P
+where
+    P: Provider + Sync,
+{
+    async fn latest_block_number(&self) -> Result<u64, ProviderError> {
+        self.block_number().await
+    }
+
+    async fn block_hash(&self, block_number: u64) -> Result<Felt, ProviderError> {
+        match self
+            .get_block_with_tx_hashes(BlockId::Number(block_number))
+            .await?
+        {
+            MaybePendingBlockWithTxHashes::Block(b) => Ok(b.block_hash),
+            MaybePendingBlockWithTxHashes::PendingBlock(_) => {
+                Err(ProviderError::StarknetError(StarknetError::BlockNotFound))
+            }
+        }
+    }
+
+    async fn events_page(
+        &self,
+        filter: EventFilter,
+        continuation_token: Option<String>,
+        chunk_size: u64,
+    ) -> Result<EventsPage, ProviderError> {
+        self.get_events(filter, continuation_token, chunk_size)
+            .await
+    }
+}
+
+/// Replays the events of a contract, see the [module documentation](self).
+///
+/// The events are decoded with the `TryFrom<&EmittedEvent>` implementation
+/// of the event enum generated for the contract.
+#[derive(Debug)]
+pub struct Backfiller<S, E> {
+    source: S,
+    address: Felt,
+    from_block: u64,
+    to_block: Option<u64>,
+    keys: Option<Vec<Vec<Felt>>>,
+    chunk_size: u64,
+    tracked_blocks: usize,
+    /// Number and hash of the last processed blocks, the last one first.
+    processed: VecDeque<(u64, Felt)>,
+    continuation: Option<Continuation>,
+    event: PhantomData<E>,
+}
+
+impl<S, E> Backfiller<S, E>
+where
+    S: EventSource,
+    E: for<'a> TryFrom<&'a EmittedEvent, Error = String>,
+{
+    /// Creates a backfill of the events of the contract at `address`, from the block `from_block`
+    /// up to the latest accepted block.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The provider to fetch the events and blocks from.
+    /// * `address` - The address of the contract.
+    /// * `from_block` - The first block to replay.
+    pub fn new(source: S, address: Felt, from_block: u64) -> Self {
+        Self {
+            source,
+            address,
+            from_block,
+            to_block: None,
+            keys: None,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            tracked_blocks: DEFAULT_TRACKED_BLOCKS,
+            processed: VecDeque::new(),
+            continuation: None,
+            event: PhantomData,
+        }
+    }
+
+    /// Stops the backfill at the block `to_block` (included) instead of the latest block.
+    pub fn with_to_block(self, to_block: u64) -> Self {
+        Self {
+            to_block: Some(to_block),
+            ..self
+        }
+    }
+
+    /// Only replays the events matching the keys filter, like `starknet_getEvents`.
+    pub fn with_keys(self, keys: Vec<Vec<Felt>>) -> Self {
+        Self {
+            keys: Some(keys),
+            ..self
+        }
+    }
+
+    /// Sets the number of events requested per page.
+    pub fn with_chunk_size(self, chunk_size: u64) -> Self {
+        Self { chunk_size, ..self }
+    }
+
+    /// Sets the number of processed blocks whose hash is tracked to find
+    /// the fork point of a reorg.
+    pub fn with_tracked_blocks(self, tracked_blocks: usize) -> Self {
+        Self {
+            tracked_blocks: tracked_blocks.max(1),
+            ..self
+        }
+    }
+
+    /// Resumes the backfill from a checkpoint given to the handler by a previous run.
+    pub fn with_checkpoint(mut self, checkpoint: Checkpoint) -> Self {
+        self.resume(checkpoint);
+        self
+    }
+
+    /// Returns the current progress of the backfill, if a block has been processed.
+    pub fn checkpoint(&self) -> Option<Checkpoint> {
+        self.processed
+            .front()
+            .map(|&(block_number, block_hash)| Checkpoint {
+                block_number,
+                block_hash,
+                continuation: self.continuation.clone(),
+            })
+    }
+
+    /// Replays the events until the last block of the backfill, giving them to the handler.
+    ///
+    /// Running it again once completed resumes from the last processed block,
+    /// to follow the new blocks.
+    ///
+    /// # Arguments
+    ///
+    /// * `handler` - The handler of the events, reorgs and checkpoints.
+    pub async fn run<F, Fut, HE>(&mut self, mut handler: F) -> Result<(), BackfillError>
+    where
+        F: FnMut(BackfillEvent<E>) -> Fut,
+        Fut: Future<Output = Result<(), HE>>,
+        HE: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        self.drive(|e| {
+            let fut = handler(e);
+            async move { fut.await.map_err(|e| BackfillError::Handler(e.into())) }
+        })
+        .await
+    }
+
+    /// Same as [`Backfiller::run`], the checkpoints being also saved into the store
+    /// once handled.
+    ///
+    /// If the backfill has no checkpoint yet, it resumes from the one of the store.
+    /// Otherwise the store is not loaded: the backfill resumes from its own progress,
+    /// like a backfill created [with a checkpoint](Backfiller::with_checkpoint).
+    ///
+    /// # Arguments
+    ///
+    /// * `store` - The store of the checkpoints.
+    /// * `handler` - The handler of the events, reorgs and checkpoints.
+    pub async fn run_with_store<C, F, Fut, HE>(
+        &mut self,
+        store: &C,
+        mut handler: F,
+    ) -> Result<(), BackfillError>
+    where
+        C: CheckpointStore,
+        F: FnMut(BackfillEvent<E>) -> Fut,
+        Fut: Future<Output = Result<(), HE>>,
+        HE: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        if self.processed.is_empty() {
+            if let Some(checkpoint) = store
+                .load()
+                .await
+                .map_err(|e| BackfillError::Store(e.into()))?
+            {
+                self.resume(checkpoint);
+            }
+        }
+
+        self.drive(|e| {
+            let checkpoint = match &e {
+                BackfillEvent::Checkpoint(c) => Some(c.clone()),
+                _ => None,
+            };
+            let fut = handler(e);
+
+            async move {
+                fut.await.map_err(|e| BackfillError::Handler(e.into()))?;
+
+                if let Some(checkpoint) = checkpoint {
+                    store
+                        .save(&checkpoint)
+                        .await
+                        .map_err(|e| BackfillError::Store(e.into()))?;
+                }
+
+                Ok(())
+            }
+        })
+        .await
+    }
+
+    async fn drive<F, Fut>(&mut self, mut handle: F) -> Result<(), BackfillError>
+    where
+        F: FnMut(BackfillEvent<E>) -> Fut,
+        Fut: Future<Output = Result<(), BackfillError>>,
+    {
+        loop {
+            if let Some(from_block) = self.resolve_reorg().await? {
+                handle(BackfillEvent::Reorg { from_block }).await?;
+            }
+
+            // The continuation is kept until its page is handled, to fetch the page
+            // again if the backfill fails before.
+            let (from_block, to_block, token) = match &self.continuation {
+                Some(c) => (c.from_block, c.to_block, Some(c.token.clone())),
+                None => {
+                    let from_block = self
+                        .processed
+                        .front()
+                        .map_or(self.from_block, |&(n, _)| n + 1);
+
+                    let latest = self.source.latest_block_number().await?;
+                    let to_block = self.to_block.map_or(latest, |t| t.min(latest));
+
+                    if from_block > to_block {
+                        return Ok(());
+                    }
+
+                    (from_block, to_block, None)
+                }
+            };
+
+            let filter = EventFilter {
+                from_block: Some(BlockId::Number(from_block)),
+                to_block: Some(BlockId::Number(to_block)),
+                address: Some(self.address),
+                keys: self.keys.clone(),
+            };
+
+            let page = self
+                .source
+                .events_page(filter, token, self.chunk_size)
+                .await?;
+
+            let mut last_block = None;
+
+            // Only the accepted blocks are in the range, never the pending one.
+            for event in page.events.iter().filter_map(DecodedEvent::decode) {
+                let event = event?;
+                last_block = Some((event.block_number, event.block_hash));
+
+                handle(BackfillEvent::Event(event)).await?;
+            }
+
+            self.continuation = match page.continuation_token {
+                Some(token) => Some(Continuation {
+                    from_block,
+                    to_block,
+                    token,
+                }),
+                None => {
+                    let block_hash = self.source.block_hash(to_block).await?;
+                    last_block = Some((to_block, block_hash));
+                    None
+                }
+            };
+
+            if let Some(block) = last_block {
+                self.track(block);
+            }
+
+            if let Some(checkpoint) = self.checkpoint() {
+                handle(BackfillEvent::Checkpoint(checkpoint)).await?;
+            }
+        }
+    }
+
+    fn resume(&mut self, checkpoint: Checkpoint) {
+        self.processed = VecDeque::from([(checkpoint.block_number, checkpoint.block_hash)]);
+        self.continuation = checkpoint.continuation;
+    }
+
+    /// Tracks a processed block.
+    fn track(&mut self, block: (u64, Felt)) {
+        if self.processed.front().map(|&(n, _)| n) == Some(block.0) {
+            self.processed.pop_front();
+        }
+
+        self.processed.push_front(block);
+        self.processed.truncate(self.tracked_blocks);
+    }
+
+    /// Checks the hash of the last processed block, and returns the first block
+    /// to replay if the chain has been reorganized.
+    async fn resolve_reorg(&mut self) -> Result<Option<u64>, BackfillError> {
+        let Some(&(block_number, block_hash)) = self.processed.front() else {
+            return Ok(None);
+        };
+
+        if self.source.block_hash(block_number).await? == block_hash {
+            return Ok(None);
+        }
+
+        // The interrupted query was on the previous chain.
+        self.continuation = None;
+        self.processed.pop_front();
+
+        while let Some(&(n, hash)) = self.processed.front() {
+            if self.source.block_hash(n).await? == hash {
+                return Ok(Some(n + 1));
+            }
+
+            self.processed.pop_front();
+        }
+
+        Err(BackfillError::UnresolvedReorg { block_number })
+    }
+}
+
+/// Pages through the events matching the filter, and yields them decoded into the
+/// event enum generated for the contract.
+///
+/// The events of the pending block are skipped, as they have no block number nor hash.
+/// An event which can't be decoded yields an error, and the stream goes on. A provider
+/// error ends the stream.
+///
+/// # Arguments
+///
+/// * `source` - The provider to fetch the events from.
+/// * `filter` - The block range, the address of the contract and the keys of the events.
+/// * `chunk_size` - The number of events requested per page.
+pub fn stream_events<'s, S, E>(
+    source: &'s S,
+    filter: EventFilter,
+    chunk_size: u64,
+) -> impl Stream<Item = Result<DecodedEvent<E>, BackfillError>> + 's
+where
+    S: EventSource + Sync,
+    E: for<'a> TryFrom<&'a EmittedEvent, Error = String> + 's,
+{
+    // The continuation token of the next page, `None` once the last page is fetched.
+    stream::unfold(Some(None), move |token| {
+        let filter = filter.clone();
+
+        async move {
+            let page = match source.events_page(filter, token?, chunk_size).await {
+                Ok(page) => page,
+                Err(e) => return Some((vec![Err(e.into())], None)),
+            };
+
+            let events = page
+                .events
+                .iter()
+                .filter_map(DecodedEvent::decode)
+                .collect();
+
+            Some((events, page.continuation_token.map(Some)))
+        }
+    })
+    .flat_map(stream::iter)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::MemoryCheckpointStore;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Transfer(Felt);
+
+    impl TryFrom<&EmittedEvent> for Transfer {
+        type Error = String;
+
+        fn try_from(event: &EmittedEvent) -> Result<Self, Self::Error> {
+            event
+                .data
+                .first()
+                .map(|v| Transfer(*v))
+                .ok_or_else(|| "Missing amount".to_string())
+        }
+    }
+
+    /// A chain with the hash and the events of each block, the continuation
+    /// token being the index of the next event in the range.
+    #[derive(Clone)]
+    struct Chain(Arc<Mutex<Vec<Block>>>);
+
+    /// The hash and the events amounts of a block.
+    type Block = (Felt, Vec<u64>);
+
+    impl Chain {
+        fn new(blocks: Vec<Vec<u64>>) -> Self {
+            Self(Arc::new(Mutex::new(
+                blocks
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, events)| (Felt::from(i as u64 + 1), events))
+                    .collect(),
+            )))
+        }
+
+        /// Replaces the blocks from `from_block`, the hashes depending on the events.
+        fn reorg(&self, from_block: usize, blocks: Vec<Vec<u64>>) {
+            let mut chain = self.0.lock().unwrap();
+            chain.truncate(from_block);
+
+            for events in blocks {
+                let hash = Felt::from((chain.len() as u64) << 32 | events.first().unwrap_or(&0));
+                chain.push((hash, events));
+            }
+        }
+    }
+
+    #[async_trait]
+    impl EventSource for Chain {
+        async fn latest_block_number(&self) -> Result<u64, ProviderError> {
+            Ok(self.0.lock().unwrap().len() as u64 - 1)
+        }
+
+        async fn block_hash(&self, block_number: u64) -> Result<Felt, ProviderError> {
+            self.0
+                .lock()
+                .unwrap()
+                .get(block_number as usize)
+                .map(|(hash, _)| *hash)
+                .ok_or(ProviderError::StarknetError(StarknetError::BlockNotFound))
+        }
+
+        async fn events_page(
+            &self,
+            filter: EventFilter,
+            continuation_token: Option<String>,
+            chunk_size: u64,
+        ) -> Result<EventsPage, ProviderError> {
+            let (Some(BlockId::Number(from)), Some(BlockId::Number(to))) =
+                (filter.from_block, filter.to_block)
+            else {
+                panic!("Block range expected");
+            };
+
+            let events: Vec<EmittedEvent> = self.0.lock().unwrap()[from as usize..=to as usize]
+                .iter()
+                .enumerate()
+                .flat_map(|(i, (hash, amounts))| {
+                    amounts.iter().map(move |amount| EmittedEvent {
+                        from_address: filter.address.unwrap(),
+                        keys: vec![Felt::ONE],
+                        data: vec![Felt::from(*amount)],
+                        block_hash: Some(*hash),
+                        block_number: Some(from + i as u64),
+                        transaction_hash: Felt::from(*amount),
+                    })
+                })
+                .collect();
+
+            let start: usize = continuation_token.map_or(0, |t| t.parse().unwrap());
+            let end = (start + chunk_size as usize).min(events.len());
+
+            Ok(EventsPage {
+                events: events[start..end].to_vec(),
+                continuation_token: (end < events.len()).then(|| end.to_string()),
+            })
+        }
+    }
+
+    async fn run(
+        backfiller: &mut Backfiller<Chain, Transfer>,
+    ) -> Result<Vec<BackfillEvent<Transfer>>, BackfillError> {
+        let mut handled = vec![];
+
+        backfiller
+            .run(|e| {
+                handled.push(e);
+                async { Ok::<_, String>(()) }
+            })
+            .await?;
+
+        Ok(handled)
+    }
+
+    fn amounts(handled: &[BackfillEvent<Transfer>]) -> Vec<u64> {
+        handled
+            .iter()
+            .filter_map(|e| match e {
+                BackfillEvent::Event(e) => Some(e.event.0.to_biguint().try_into().unwrap()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn checkpoints(handled: &[BackfillEvent<Transfer>]) -> Vec<Checkpoint> {
+        handled
+            .iter()
+            .filter_map(|e| match e {
+                BackfillEvent::Checkpoint(c) => Some(c.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_stream_events() {
+        let chain = Chain::new(vec![vec![1], vec![], vec![2, 3, 4], vec![5]]);
+        let filter = EventFilter {
+            from_block: Some(BlockId::Number(1)),
+            to_block: Some(BlockId::Number(2)),
+            address: Some(Felt::TWO),
+            keys: None,
+        };
+
+        let events: Vec<DecodedEvent<Transfer>> = stream_events(&chain, filter, 2)
+            .map(Result::unwrap)
+            .collect()
+            .await;
+
+        assert_eq!(
+            events
+                .iter()
+                .map(|e| (e.event.clone(), e.block_number))
+                .collect::<Vec<_>>(),
+            vec![
+                (Transfer(Felt::from(2)), 2),
+                (Transfer(Felt::from(3)), 2),
+                (Transfer(Felt::from(4)), 2),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_backfill_pages() {
+        let chain = Chain::new(vec![vec![1], vec![], vec![2, 3, 4], vec![5]]);
+        let mut backfiller = Backfiller::new(chain.clone(), Felt::TWO, 1).with_chunk_size(2);
+
+        let handled = run(&mut backfiller).await.unwrap();
+        assert_eq!(amounts(&handled), vec![2, 3, 4, 5]);
+
+        let checkpoints = checkpoints(&handled);
+        assert_eq!(
+            checkpoints[0],
+            Checkpoint {
+                block_number: 2,
+                block_hash: Felt::from(3),
+                continuation: Some(Continuation {
+                    from_block: 1,
+                    to_block: 3,
+                    token: "2".to_string(),
+                }),
+            }
+        );
+        assert_eq!(
+            checkpoints.last().unwrap(),
+            &Checkpoint {
+                block_number: 3,
+                block_hash: Felt::from(4),
+                continuation: None,
+            }
+        );
+
+        // Nothing new to replay.
+        assert!(run(&mut backfiller).await.unwrap().is_empty());
+
+        // The new blocks are followed.
+        chain.reorg(4, vec![vec![6]]);
+        assert_eq!(amounts(&run(&mut backfiller).await.unwrap()), vec![6]);
+    }
+
+    #[tokio::test]
+    async fn test_backfill_store() {
+        let chain = Chain::new(vec![vec![1, 2, 3], vec![4]]);
+        let store = MemoryCheckpointStore::new();
+
+        let mut backfiller =
+            Backfiller::<_, Transfer>::new(chain.clone(), Felt::TWO, 0).with_chunk_size(2);
+        let res = backfiller
+            .run_with_store(&store, |e| async move {
+                match e {
+                    BackfillEvent::Event(e) if e.event.0 == Felt::THREE => Err("Crashed"),
+                    _ => Ok(()),
+                }
+            })
+            .await;
+        assert!(matches!(res, Err(BackfillError::Handler(_))));
+
+        // Restarted from the checkpoint of the first page.
+        let checkpoint = store.checkpoint().unwrap();
+        assert_eq!(checkpoint.block_number, 0);
+        assert!(checkpoint.continuation.is_some());
+
+        let mut handled = vec![];
+        let mut backfiller = Backfiller::<_, Transfer>::new(chain, Felt::TWO, 0);
+        backfiller
+            .run_with_store(&store, |e| {
+                handled.push(e);
+                async { Ok::<_, String>(()) }
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(amounts(&handled), vec![3, 4]);
+        assert_eq!(store.checkpoint(), backfiller.checkpoint());
+    }
+
+    #[tokio::test]
+    async fn test_backfill_page_error() {
+        /// A chain failing to return its second page once.
+        struct Flaky {
+            chain: Chain,
+            pages: Mutex<usize>,
+        }
+
+        #[async_trait]
+        impl EventSource for Flaky {
+            async fn latest_block_number(&self) -> Result<u64, ProviderError> {
+                self.chain.latest_block_number().await
+            }
+
+            async fn block_hash(&self, block_number: u64) -> Result<Felt, ProviderError> {
+                self.chain.block_hash(block_number).await
+            }
+
+            async fn events_page(
+                &self,
+                filter: EventFilter,
+                continuation_token: Option<String>,
+                chunk_size: u64,
+            ) -> Result<EventsPage, ProviderError> {
+                let page = {
+                    let mut pages = self.pages.lock().unwrap();
+                    *pages += 1;
+                    *pages
+                };
+
+                if page == 2 {
+                    return Err(ProviderError::RateLimited);
+                }
+
+                self.chain
+                    .events_page(filter, continuation_token, chunk_size)
+                    .await
+            }
+        }
+
+        let chain = Chain::new(vec![vec![1, 2, 3], vec![4]]);
+        let source = Flaky {
+            chain,
+            pages: Mutex::new(0),
+        };
+        let mut backfiller =
+            Backfiller::<_, Transfer>::new(source, Felt::TWO, 0).with_chunk_size(2);
+
+        let mut handled = vec![];
+        let res = backfiller
+            .run(|e| {
+                handled.push(e);
+                async { Ok::<_, String>(()) }
+            })
+            .await;
+        assert!(matches!(
+            res,
+            Err(BackfillError::Provider(ProviderError::RateLimited))
+        ));
+
+        // Retried from the page which failed.
+        backfiller
+            .run(|e| {
+                handled.push(e);
+                async { Ok::<_, String>(()) }
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(amounts(&handled), vec![1, 2, 3, 4]);
+    }
+
+    #[tokio::test]
+    async fn test_backfill_to_block() {
+        let chain = Chain::new(vec![vec![1], vec![2], vec![3]]);
+        let mut backfiller = Backfiller::new(chain.clone(), Felt::TWO, 0).with_to_block(1);
+
+        assert_eq!(amounts(&run(&mut backfiller).await.unwrap()), vec![1, 2]);
+        assert_eq!(backfiller.checkpoint().unwrap().block_number, 1);
+    }
+
+    #[tokio::test]
+    async fn test_backfill_resume() {
+        let chain = Chain::new(vec![vec![1, 2, 3], vec![4]]);
+        let mut backfiller =
+            Backfiller::<_, Transfer>::new(chain.clone(), Felt::TWO, 0).with_chunk_size(2);
+
+        let mut first = None;
+        let _ = backfiller
+            .run(|e| {
+                // Interrupted after the first page.
+                let res = match e {
+                    BackfillEvent::Checkpoint(c) => Err(first.insert(c).clone()),
+                    _ => Ok(()),
+                };
+                async move { res.map_err(|c| format!("Stopped at {}", c.block_number)) }
+            })
+            .await;
+
+        let checkpoint = first.unwrap();
+        assert!(checkpoint.continuation.is_some());
+
+        let mut backfiller =
+            Backfiller::new(chain.clone(), Felt::TWO, 0).with_checkpoint(checkpoint);
+        assert_eq!(amounts(&run(&mut backfiller).await.unwrap()), vec![3, 4]);
+    }
+
+    #[tokio::test]
+    async fn test_backfill_reorg() {
+        let chain = Chain::new(vec![vec![1], vec![2], vec![3]]);
+        let mut backfiller = Backfiller::new(chain.clone(), Felt::TWO, 0).with_to_block(0);
+        run(&mut backfiller).await.unwrap();
+
+        let mut backfiller = backfiller.with_to_block(2);
+        assert_eq!(amounts(&run(&mut backfiller).await.unwrap()), vec![2, 3]);
+
+        chain.reorg(1, vec![vec![20], vec![30]]);
+
+        let handled = run(&mut backfiller).await.unwrap();
+        assert_eq!(handled[0], BackfillEvent::Reorg { from_block: 1 });
+        assert_eq!(amounts(&handled), vec![20, 30]);
+
+        // The fork point is further than the tracked blocks.
+        let mut backfiller = Backfiller::<_, Transfer>::new(chain.clone(), Felt::TWO, 0);
+        run(&mut backfiller).await.unwrap();
+        chain.reorg(0, vec![vec![100], vec![200], vec![300]]);
+
+        assert!(matches!(
+            run(&mut backfiller).await,
+            Err(BackfillError::UnresolvedReorg { block_number: 2 })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_backfill_decode_error() {
+        let chain = Chain::new(vec![vec![1]]);
+
+        struct Never;
+
+        impl TryFrom<&EmittedEvent> for Never {
+            type Error = String;
+
+            fn try_from(_: &EmittedEvent) -> Result<Self, Self::Error> {
+                Err("Unknown event".to_string())
+            }
+        }
+
+        let mut backfiller = Backfiller::<_, Never>::new(chain, Felt::TWO, 0);
+        let res = backfiller.run(|_| async { Ok::<_, String>(()) }).await;
+
+        assert!(matches!(res, Err(BackfillError::Decode { .. })));
+    }
+}
@@ -0,0 +1,187 @@
+//! Persistence of the checkpoints of a backfill.
+//!
+//! A [`CheckpointStore`] holds the last checkpoint of a backfill (the last processed block
+//! and the continuation token of the interrupted query), for a long running indexer
+//! to resume where it stopped after a restart, instead of scanning the blocks again.
+use async_trait::async_trait;
+use camino::{Utf8Path, Utf8PathBuf};
+use std::sync::Mutex;
+
+use super::Checkpoint;
+
+/// Storage of the last checkpoint of a backfill.
+#[async_trait]
+pub trait CheckpointStore {
+    type Error: Into<Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Returns the last checkpoint saved, if any.
+    async fn load(&self) -> Result<Option<Checkpoint>, Self::Error>;
+
+    /// Saves the checkpoint, replacing the previous one.
+    async fn save(&self, checkpoint: &Checkpoint) -> Result<(), Self::Error>;
+}
+
+/// A store keeping the checkpoint in memory, for the tests or the short lived backfills.
+#[derive(Debug, Default)]
+pub struct MemoryCheckpointStore {
+    checkpoint: Mutex<Option<Checkpoint>>,
+}
+
+impl MemoryCheckpointStore {
+    /// Creates an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the checkpoint saved, if any.
+    pub fn checkpoint(&self) -> Option<Checkpoint> {
+        self.checkpoint.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl CheckpointStore for MemoryCheckpointStore {
+    type Error = std::convert::Infallible;
+
+    async fn load(&self) -> Result<Option<Checkpoint>, Self::Error> {
+        Ok(self.checkpoint())
+    }
+
+    async fn save(&self, checkpoint: &Checkpoint) -> Result<(), Self::Error> {
+        *self.checkpoint.lock().unwrap() = Some(checkpoint.clone());
+        Ok(())
+    }
+}
+
+/// A store keeping the checkpoint in a JSON file.
+///
+/// The checkpoint is written into a temporary file renamed over the previous one,
+/// so a crash while saving never leaves a partially written checkpoint.
+#[derive(Debug, Clone)]
+pub struct FileCheckpointStore {
+    path: Utf8PathBuf,
+}
+
+impl FileCheckpointStore {
+    /// Creates a store keeping the checkpoint at `path`. The file is created on the first save.
+    pub fn new(path: impl Into<Utf8PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Returns the path of the checkpoint file.
+    pub fn path(&self) -> &Utf8Path {
+        &self.path
+    }
+}
+
+/// Error of a [`FileCheckpointStore`].
+#[derive(Debug, thiserror::Error)]
+pub enum FileCheckpointStoreError {
+    #[error("IO error on the checkpoint file {path}: {source}")]
+    Io {
+        path: Utf8PathBuf,
+        source: std::io::Error,
+    },
+    #[error("Invalid checkpoint file {path}: {source}")]
+    Json {
+        path: Utf8PathBuf,
+        source: serde_json::Error,
+    },
+}
+
+#[async_trait]
+impl CheckpointStore for FileCheckpointStore {
+    type Error = FileCheckpointStoreError;
+
+    async fn load(&self) -> Result<Option<Checkpoint>, Self::Error> {
+        let content = match std::fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(source) => {
+                return Err(FileCheckpointStoreError::Io {
+                    path: self.path.clone(),
+                    source,
+                })
+            }
+        };
+
+        serde_json::from_str(&content)
+            .map(Some)
+            .map_err(|source| FileCheckpointStoreError::Json {
+                path: self.path.clone(),
+                source,
+            })
+    }
+
+    async fn save(&self, checkpoint: &Checkpoint) -> Result<(), Self::Error> {
+        let content = serde_json::to_string_pretty(checkpoint).map_err(|source| {
+            FileCheckpointStoreError::Json {
+                path: self.path.clone(),
+                source,
+            }
+        })?;
+
+        let tmp = self.path.with_extension("tmp");
+        let io_error = |source| FileCheckpointStoreError::Io {
+            path: self.path.clone(),
+            source,
+        };
+
+        std::fs::write(&tmp, content).map_err(io_error)?;
+        std::fs::rename(&tmp, &self.path).map_err(io_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::Continuation;
+    use starknet::core::types::Felt;
+
+    fn checkpoint() -> Checkpoint {
+        Checkpoint {
+            block_number: 12,
+            block_hash: Felt::from(0x1234),
+            continuation: Some(Continuation {
+                from_block: 10,
+                to_block: 20,
+                token: "12-3".to_string(),
+            }),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_memory_store() {
+        let store = MemoryCheckpointStore::new();
+        assert_eq!(store.load().await.unwrap(), None);
+
+        store.save(&checkpoint()).await.unwrap();
+        assert_eq!(store.load().await.unwrap(), Some(checkpoint()));
+    }
+
+    #[tokio::test]
+    async fn test_file_store() {
+        let dir =
+            Utf8PathBuf::from_path_buf(std::env::temp_dir().join("cainome-checkpoint")).unwrap();
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let store = FileCheckpointStore::new(dir.join("checkpoint.json"));
+        let _ = std::fs::remove_file(store.path());
+        assert_eq!(store.load().await.unwrap(), None);
+
+        store.save(&checkpoint()).await.unwrap();
+        let mut checkpoint = checkpoint();
+        checkpoint.continuation = None;
+        store.save(&checkpoint).await.unwrap();
+
+        // Reloaded like after a restart.
+        let store = FileCheckpointStore::new(dir.join("checkpoint.json"));
+        assert_eq!(store.load().await.unwrap(), Some(checkpoint));
+
+        std::fs::write(store.path(), "{").unwrap();
+        assert!(matches!(
+            store.load().await,
+            Err(FileCheckpointStoreError::Json { .. })
+        ));
+    }
+}
@@ -0,0 +1,9 @@
+//! Runtime utilities for the applications built on the generated bindings,
+//! like the indexers replaying the events of a contract.
+mod backfill;
+mod checkpoint;
+
+pub use backfill::*;
+pub use checkpoint::*;
+/// The trait of the event streams, to consume them with `futures::StreamExt`.
+pub use futures_util::stream::Stream;
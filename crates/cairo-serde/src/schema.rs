@@ -0,0 +1,267 @@
+//! Schemas of the types of this crate, for the OpenAPI and JSON schema documentations.
+//!
+//! With the `schemars` feature, the types implement `schemars::JsonSchema`, and with the
+//! `utoipa` feature, `utoipa::ToSchema`. The schemas describe the serde representation
+//! of the types: the felts (and the types serialized as felts) are `0x` prefixed hex strings,
+//! and the `U256` is a decimal string.
+//!
+//! The `Felt` of `starknet-rs` doesn't implement those traits. [`FeltSchema`] stands for it,
+//! for instance with `#[schemars(with = "FeltSchema")]` on a member of type `Felt`.
+//! The bindings generated with the `JsonSchema` or `ToSchema` derives document their
+//! felt members as strings on their own.
+#[cfg(feature = "schemars")]
+use schemars::{
+    gen::SchemaGenerator,
+    schema::{InstanceType, Metadata, Schema, SchemaObject, StringValidation},
+    JsonSchema,
+};
+#[cfg(feature = "utoipa")]
+use utoipa::{
+    openapi::{ArrayBuilder, ObjectBuilder, RefOr, Schema as OpenApiSchema, SchemaType},
+    ToSchema,
+};
+
+use crate::{
+    ByteArray, Bytes31, CairoArrayLegacy, CairoSpan, ClassHash, ContractAddress, EthAddress,
+    NonZero, Zeroable, U256,
+};
+
+const FELT_PATTERN: &str = "^0x[0-9a-fA-F]{1,64}$";
+const U256_PATTERN: &str = "^[0-9]{1,78}$";
+
+/// The schema of a `Felt`, serialized as a `0x` prefixed hex string, named `Felt`.
+pub struct FeltSchema;
+
+#[cfg(feature = "schemars")]
+fn string_schema(description: &str, pattern: &str) -> Schema {
+    SchemaObject {
+        instance_type: Some(InstanceType::String.into()),
+        metadata: Some(Box::new(Metadata {
+            description: Some(description.to_string()),
+            ..Default::default()
+        })),
+        string: Some(Box::new(StringValidation {
+            pattern: Some(pattern.to_string()),
+            ..Default::default()
+        })),
+        ..Default::default()
+    }
+    .into()
+}
+
+#[cfg(feature = "utoipa")]
+fn openapi_string_schema(description: &str, pattern: &str) -> RefOr<OpenApiSchema> {
+    ObjectBuilder::new()
+        .schema_type(SchemaType::String)
+        .description(Some(description))
+        .pattern(Some(pattern))
+        .into()
+}
+
+/// Implements the schemas of the types serialized as strings.
+macro_rules! impl_string_schema {
+    ($($ty:ident as $name:literal => ($description:expr, $pattern:expr)),* $(,)?) => {
+        $(
+            #[cfg(feature = "schemars")]
+            impl JsonSchema for $ty {
+                fn schema_name() -> String {
+                    $name.to_string()
+                }
+
+                fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
+                    string_schema($description, $pattern)
+                }
+            }
+
+            #[cfg(feature = "utoipa")]
+            impl<'s> ToSchema<'s> for $ty {
+                fn schema() -> (&'s str, RefOr<OpenApiSchema>) {
+                    ($name, openapi_string_schema($description, $pattern))
+                }
+            }
+        )*
+    };
+}
+
+impl_string_schema!(
+    FeltSchema as "Felt" => (
+        "A felt, as a 0x prefixed hex string.",
+        FELT_PATTERN
+    ),
+    ContractAddress as "ContractAddress" => (
+        "A contract address, as a 0x prefixed hex string.",
+        FELT_PATTERN
+    ),
+    ClassHash as "ClassHash" => (
+        "A class hash, as a 0x prefixed hex string.",
+        FELT_PATTERN
+    ),
+    EthAddress as "EthAddress" => (
+        "An Ethereum address, as a 0x prefixed hex string.",
+        FELT_PATTERN
+    ),
+    Bytes31 as "Bytes31" => (
+        "31 bytes, as a 0x prefixed hex string.",
+        FELT_PATTERN
+    ),
+    U256 as "U256" => (
+        "A 256 bits unsigned integer, as a decimal string.",
+        U256_PATTERN
+    ),
+);
+
+/// The `ByteArray` is serialized as its Cairo representation.
+#[cfg(feature = "schemars")]
+impl JsonSchema for ByteArray {
+    fn schema_name() -> String {
+        "ByteArray".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        let mut schema = SchemaObject {
+            instance_type: Some(InstanceType::Object.into()),
+            ..Default::default()
+        };
+
+        let object = schema.object();
+        object
+            .properties
+            .insert("data".to_string(), gen.subschema_for::<Vec<Bytes31>>());
+        object.properties.insert(
+            "pending_word".to_string(),
+            gen.subschema_for::<FeltSchema>(),
+        );
+        object
+            .properties
+            .insert("pending_word_len".to_string(), gen.subschema_for::<usize>());
+        object.required = ["data", "pending_word", "pending_word_len"]
+            .iter()
+            .map(|p| p.to_string())
+            .collect();
+
+        schema.into()
+    }
+}
+
+#[cfg(feature = "utoipa")]
+impl<'s> ToSchema<'s> for ByteArray {
+    fn schema() -> (&'s str, RefOr<OpenApiSchema>) {
+        let schema = ObjectBuilder::new()
+            .property("data", ArrayBuilder::new().items(Bytes31::schema().1))
+            .required("data")
+            .property("pending_word", FeltSchema::schema().1)
+            .required("pending_word")
+            .property(
+                "pending_word_len",
+                ObjectBuilder::new()
+                    .schema_type(SchemaType::Integer)
+                    .minimum(Some(0.0)),
+            )
+            .required("pending_word_len");
+
+        ("ByteArray", schema.into())
+    }
+}
+
+/// The `NonZero` is serialized as the inner value.
+#[cfg(feature = "schemars")]
+impl<T: Zeroable + JsonSchema> JsonSchema for NonZero<T> {
+    fn is_referenceable() -> bool {
+        false
+    }
+
+    fn schema_name() -> String {
+        format!("NonZero_{}", T::schema_name())
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        gen.subschema_for::<T>()
+    }
+}
+
+#[cfg(feature = "utoipa")]
+impl<'s, T: Zeroable + ToSchema<'s>> ToSchema<'s> for NonZero<T> {
+    fn schema() -> (&'s str, RefOr<OpenApiSchema>) {
+        T::schema()
+    }
+}
+
+/// Implements the schemas of the arrays, serialized as sequences.
+macro_rules! impl_array_schema {
+    ($($ty:ident),* $(,)?) => {
+        $(
+            #[cfg(feature = "schemars")]
+            impl<T: JsonSchema> JsonSchema for $ty<T> {
+                fn is_referenceable() -> bool {
+                    false
+                }
+
+                fn schema_name() -> String {
+                    Vec::<T>::schema_name()
+                }
+
+                fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+                    Vec::<T>::json_schema(gen)
+                }
+            }
+
+            #[cfg(feature = "utoipa")]
+            impl<'s, T: ToSchema<'s>> ToSchema<'s> for $ty<T> {
+                fn schema() -> (&'s str, RefOr<OpenApiSchema>) {
+                    (
+                        stringify!($ty),
+                        ArrayBuilder::new().items(T::schema().1).into(),
+                    )
+                }
+            }
+        )*
+    };
+}
+
+impl_array_schema!(CairoSpan, CairoArrayLegacy);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn test_json_schema() {
+        let schema = serde_json::to_value(schemars::schema_for!(U256)).unwrap();
+        assert_eq!(schema["type"], "string");
+        assert_eq!(schema["pattern"], U256_PATTERN);
+
+        // The `NonZero` is transparent, a member refers to the inner type.
+        let mut gen = SchemaGenerator::default();
+        let schema = serde_json::to_value(gen.subschema_for::<NonZero<ContractAddress>>()).unwrap();
+        assert_eq!(schema["$ref"], "#/definitions/ContractAddress");
+
+        let schema = serde_json::to_value(schemars::schema_for!(ByteArray)).unwrap();
+        assert_eq!(schema["properties"]["data"]["type"], "array");
+        assert_eq!(
+            schema["properties"]["pending_word"]["$ref"],
+            "#/definitions/Felt"
+        );
+        assert_eq!(schema["required"].as_array().unwrap().len(), 3);
+
+        let schema = serde_json::to_value(schemars::schema_for!(CairoSpan<u8>)).unwrap();
+        assert_eq!(schema["type"], "array");
+    }
+
+    #[cfg(feature = "utoipa")]
+    #[test]
+    fn test_to_schema() {
+        let (name, schema) = <ClassHash as ToSchema>::schema();
+        let schema = serde_json::to_value(schema).unwrap();
+        assert_eq!(name, "ClassHash");
+        assert_eq!(schema["type"], "string");
+        assert_eq!(schema["pattern"], FELT_PATTERN);
+
+        let schema = serde_json::to_value(<ByteArray as ToSchema>::schema().1).unwrap();
+        assert_eq!(schema["properties"]["data"]["items"]["type"], "string");
+        assert_eq!(schema["properties"]["pending_word_len"]["type"], "integer");
+
+        let schema = serde_json::to_value(<CairoSpan<U256> as ToSchema>::schema().1).unwrap();
+        assert_eq!(schema["items"]["pattern"], U256_PATTERN);
+    }
+}
@@ -0,0 +1,314 @@
+//! Selectors computed in const contexts.
+//!
+//! The generated bindings embed the selectors precomputed at codegen time. For the
+//! entrypoints only known by the user crate (like the ones called dynamically), this
+//! module computes the selectors with `const fn`, so they can still be constants:
+//!
+//! ```ignore
+//! const UPGRADE_SELECTOR: Felt = cainome::cairo_serde::selector::function_selector("upgrade");
+//! ```
+//!
+//! The Keccak hash is implemented with the const features stable on Rust 1.63
+//! (loops and `str::from_utf8`), which doesn't raise the minimum Rust version of the crate.
+//! Used outside a const context, those functions are slower than the ones of `starknet-rs`.
+use starknet::core::types::Felt;
+
+/// Computes the selector of a function (entrypoint) from its name, like
+/// [`starknet::core::utils::get_selector_from_name`].
+///
+/// The default entrypoints (`__default__` and `__l1_default__`) have a zero selector.
+///
+/// # Panics
+///
+/// If the name is not ASCII, which fails the compilation in a const context.
+///
+/// # Arguments
+///
+/// * `name` - The name of the function.
+pub const fn function_selector(name: &str) -> Felt {
+    let name = name.as_bytes();
+
+    if bytes_eq(name, b"__default__") || bytes_eq(name, b"__l1_default__") {
+        return Felt::ZERO;
+    }
+
+    if !is_ascii(name) {
+        panic!("Non ASCII function name");
+    }
+
+    starknet_keccak(name)
+}
+
+/// Computes the selector of an event from its name, which is the first key of the emitted event.
+///
+/// # Panics
+///
+/// If the name is not ASCII, which fails the compilation in a const context.
+///
+/// # Arguments
+///
+/// * `name` - The name of the event (or of the event enum variant).
+pub const fn event_selector(name: &str) -> Felt {
+    let name = name.as_bytes();
+
+    if !is_ascii(name) {
+        panic!("Non ASCII event name");
+    }
+
+    starknet_keccak(name)
+}
+
+/// Keccak256 of the data, truncated to 250 bits.
+const fn starknet_keccak(data: &[u8]) -> Felt {
+    let mut hash = keccak256(data);
+    hash[0] &= 0x03;
+
+    felt_from_bytes_be(&hash)
+}
+
+/// Converts big-endian bytes into a felt, through its hex representation
+/// which is the only const constructor from a canonical value.
+const fn felt_from_bytes_be(bytes: &[u8; 32]) -> Felt {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+    let mut hex = [b'0'; 66];
+    hex[1] = b'x';
+
+    let mut i = 0;
+    while i < 32 {
+        hex[2 + 2 * i] = DIGITS[(bytes[i] >> 4) as usize];
+        hex[3 + 2 * i] = DIGITS[(bytes[i] & 0x0f) as usize];
+        i += 1;
+    }
+
+    match core::str::from_utf8(&hex) {
+        Ok(hex) => Felt::from_hex_unchecked(hex),
+        Err(_) => panic!("Hex digits are ASCII"),
+    }
+}
+
+const fn bytes_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+
+    true
+}
+
+const fn is_ascii(bytes: &[u8]) -> bool {
+    let mut i = 0;
+    while i < bytes.len() {
+        if !bytes[i].is_ascii() {
+            return false;
+        }
+        i += 1;
+    }
+
+    true
+}
+
+/// Number of bytes absorbed per permutation by Keccak256.
+const RATE: usize = 136;
+
+const ROUND_CONSTANTS: [u64; 24] = [
+    0x0000000000000001,
+    0x0000000000008082,
+    0x800000000000808a,
+    0x8000000080008000,
+    0x000000000000808b,
+    0x0000000080000001,
+    0x8000000080008081,
+    0x8000000000008009,
+    0x000000000000008a,
+    0x0000000000000088,
+    0x0000000080008009,
+    0x000000008000000a,
+    0x000000008000808b,
+    0x800000000000008b,
+    0x8000000000008089,
+    0x8000000000008003,
+    0x8000000000008002,
+    0x8000000000000080,
+    0x000000000000800a,
+    0x800000008000000a,
+    0x8000000080008081,
+    0x8000000000008080,
+    0x0000000080000001,
+    0x8000000080008008,
+];
+
+const RHO: [u32; 24] = [
+    1, 3, 6, 10, 15, 21, 28, 36, 45, 55, 2, 14, 27, 41, 56, 8, 25, 43, 62, 18, 39, 61, 20, 44,
+];
+
+const PI: [usize; 24] = [
+    10, 7, 11, 17, 18, 3, 5, 16, 8, 21, 24, 4, 15, 23, 19, 13, 12, 2, 20, 14, 22, 9, 6, 1,
+];
+
+/// The Keccak-f[1600] permutation.
+const fn keccak_f(mut state: [u64; 25]) -> [u64; 25] {
+    let mut round = 0;
+    while round < 24 {
+        // Theta.
+        let mut columns = [0u64; 5];
+        let mut x = 0;
+        while x < 5 {
+            columns[x] = state[x] ^ state[x + 5] ^ state[x + 10] ^ state[x + 15] ^ state[x + 20];
+            x += 1;
+        }
+
+        x = 0;
+        while x < 5 {
+            let d = columns[(x + 4) % 5] ^ columns[(x + 1) % 5].rotate_left(1);
+            let mut y = 0;
+            while y < 25 {
+                state[y + x] ^= d;
+                y += 5;
+            }
+            x += 1;
+        }
+
+        // Rho and pi.
+        let mut last = state[1];
+        let mut i = 0;
+        while i < 24 {
+            let lane = state[PI[i]];
+            state[PI[i]] = last.rotate_left(RHO[i]);
+            last = lane;
+            i += 1;
+        }
+
+        // Chi.
+        let mut y = 0;
+        while y < 25 {
+            let row = [
+                state[y],
+                state[y + 1],
+                state[y + 2],
+                state[y + 3],
+                state[y + 4],
+            ];
+            let mut x = 0;
+            while x < 5 {
+                state[y + x] = row[x] ^ (!row[(x + 1) % 5] & row[(x + 2) % 5]);
+                x += 1;
+            }
+            y += 5;
+        }
+
+        // Iota.
+        state[0] ^= ROUND_CONSTANTS[round];
+        round += 1;
+    }
+
+    state
+}
+
+/// Absorbs a block of `RATE` bytes into the state.
+const fn absorb(mut state: [u64; 25], block: &[u8], offset: usize) -> [u64; 25] {
+    let mut lane = 0;
+    while lane < RATE / 8 {
+        let i = offset + lane * 8;
+        state[lane] ^= u64::from_le_bytes([
+            block[i],
+            block[i + 1],
+            block[i + 2],
+            block[i + 3],
+            block[i + 4],
+            block[i + 5],
+            block[i + 6],
+            block[i + 7],
+        ]);
+        lane += 1;
+    }
+
+    keccak_f(state)
+}
+
+/// The Keccak256 hash (with the original padding of Keccak, not the one of SHA3).
+const fn keccak256(data: &[u8]) -> [u8; 32] {
+    let mut state = [0u64; 25];
+
+    let mut offset = 0;
+    while offset + RATE <= data.len() {
+        state = absorb(state, data, offset);
+        offset += RATE;
+    }
+
+    let mut last = [0u8; RATE];
+    let mut i = 0;
+    while offset + i < data.len() {
+        last[i] = data[offset + i];
+        i += 1;
+    }
+    last[i] ^= 0x01;
+    last[RATE - 1] ^= 0x80;
+
+    state = absorb(state, &last, 0);
+
+    let mut hash = [0u8; 32];
+    let mut lane = 0;
+    while lane < 4 {
+        let bytes = state[lane].to_le_bytes();
+        let mut j = 0;
+        while j < 8 {
+            hash[lane * 8 + j] = bytes[j];
+            j += 1;
+        }
+        lane += 1;
+    }
+
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starknet::core::utils::{get_selector_from_name, starknet_keccak as snrs_keccak};
+
+    const TRANSFER: Felt = function_selector("transfer");
+
+    #[test]
+    fn test_function_selector() {
+        assert_eq!(
+            TRANSFER,
+            Felt::from_hex_unchecked(
+                "0x83afd3f4caedc6eebf44246fe54e38c95e3179a5ec9ea81740eca5b482d12e"
+            )
+        );
+        assert_eq!(function_selector("__default__"), Felt::ZERO);
+        assert_eq!(function_selector("__l1_default__"), Felt::ZERO);
+
+        // Around the rate of the hash, which is absorbed in several blocks.
+        for len in [0, 1, 31, 135, 136, 137, 271, 272, 300] {
+            let name = "a".repeat(len);
+            assert_eq!(
+                function_selector(&name),
+                get_selector_from_name(&name).unwrap(),
+                "Length {}",
+                len
+            );
+        }
+    }
+
+    #[test]
+    fn test_event_selector() {
+        for name in ["Transfer", "Approval", "OwnershipTransferred", ""] {
+            assert_eq!(event_selector(name), snrs_keccak(name.as_bytes()));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Non ASCII function name")]
+    fn test_function_selector_non_ascii() {
+        function_selector("transfér");
+    }
+}
@@ -0,0 +1,164 @@
+//! Serde helpers for the bytes (`Vec<u8>`, `CairoSpan<u8>` and `ByteArray`) serialized as strings.
+//!
+//! By default, the bytes are serialized as arrays of numbers (and the `ByteArray` as its
+//! Cairo representation). With those helpers, they are serialized as hex or base64 strings,
+//! which is what most JSON APIs expect. The Cairo serialization is not affected.
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+
+use crate::{ByteArray, CairoSpan};
+
+/// A type holding bytes, serialized as a string by the helpers of this module.
+pub trait SerdeBytes: Sized {
+    /// Returns the bytes of the value.
+    fn to_serde_bytes(&self) -> Vec<u8>;
+
+    /// Builds the value from its bytes.
+    fn from_serde_bytes(bytes: Vec<u8>) -> Self;
+}
+
+impl SerdeBytes for Vec<u8> {
+    fn to_serde_bytes(&self) -> Vec<u8> {
+        self.clone()
+    }
+
+    fn from_serde_bytes(bytes: Vec<u8>) -> Self {
+        bytes
+    }
+}
+
+impl SerdeBytes for CairoSpan<u8> {
+    fn to_serde_bytes(&self) -> Vec<u8> {
+        self.0.clone()
+    }
+
+    fn from_serde_bytes(bytes: Vec<u8>) -> Self {
+        CairoSpan(bytes)
+    }
+}
+
+impl SerdeBytes for ByteArray {
+    fn to_serde_bytes(&self) -> Vec<u8> {
+        self.to_bytes()
+    }
+
+    fn from_serde_bytes(bytes: Vec<u8>) -> Self {
+        ByteArray::from_bytes(&bytes)
+    }
+}
+
+/// Serialize bytes as a `0x` prefixed hex string.
+pub fn serialize_bytes_as_hex<S, T>(
+    value: &T,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+    T: SerdeBytes,
+{
+    serializer.serialize_str(&format!("0x{}", hex::encode(value.to_serde_bytes())))
+}
+
+/// Deserialize bytes from a hex string, with or without the `0x` prefix.
+pub fn deserialize_bytes_from_hex<'de, D, T>(deserializer: D) -> std::result::Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: SerdeBytes,
+{
+    let hex_string: String = serde::Deserialize::deserialize(deserializer)?;
+    let digits = hex_string
+        .strip_prefix("0x")
+        .or_else(|| hex_string.strip_prefix("0X"))
+        .unwrap_or(&hex_string);
+
+    hex::decode(digits)
+        .map(T::from_serde_bytes)
+        .map_err(serde::de::Error::custom)
+}
+
+/// Serialize bytes as a base64 string (standard alphabet, with padding).
+pub fn serialize_bytes_as_base64<S, T>(
+    value: &T,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+    T: SerdeBytes,
+{
+    serializer.serialize_str(&BASE64.encode(value.to_serde_bytes()))
+}
+
+/// Deserialize bytes from a base64 string (standard alphabet, with padding).
+pub fn deserialize_bytes_from_base64<'de, D, T>(deserializer: D) -> std::result::Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: SerdeBytes,
+{
+    let base64_string: String = serde::Deserialize::deserialize(deserializer)?;
+
+    BASE64
+        .decode(base64_string)
+        .map(T::from_serde_bytes)
+        .map_err(serde::de::Error::custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Blob {
+        #[serde(
+            serialize_with = "serialize_bytes_as_hex",
+            deserialize_with = "deserialize_bytes_from_hex"
+        )]
+        hex: Vec<u8>,
+        #[serde(
+            serialize_with = "serialize_bytes_as_base64",
+            deserialize_with = "deserialize_bytes_from_base64"
+        )]
+        base64: Vec<u8>,
+        #[serde(
+            serialize_with = "serialize_bytes_as_hex",
+            deserialize_with = "deserialize_bytes_from_hex"
+        )]
+        byte_array: ByteArray,
+    }
+
+    #[test]
+    fn test_bytes_serialization() {
+        // Longer than a word of `ByteArray`, and not valid UTF-8.
+        let mut raw = vec![0xff, 0x00];
+        raw.extend(0..40);
+
+        let blob = Blob {
+            hex: vec![0xca, 0xfe],
+            base64: b"cainome".to_vec(),
+            byte_array: ByteArray::from_bytes(&raw),
+        };
+
+        let json = serde_json::to_value(&blob).unwrap();
+        assert_eq!(json["hex"], "0xcafe");
+        assert_eq!(json["base64"], "Y2Fpbm9tZQ==");
+        assert_eq!(json["byte_array"], format!("0x{}", hex::encode(&raw)));
+
+        assert_eq!(serde_json::from_value::<Blob>(json).unwrap(), blob);
+    }
+
+    #[test]
+    fn test_bytes_deserialization() {
+        let blob: Blob =
+            serde_json::from_str(r#"{ "hex": "CAFE", "base64": "", "byte_array": "0x636169" }"#)
+                .unwrap();
+
+        assert_eq!(blob.hex, vec![0xca, 0xfe]);
+        assert!(blob.base64.is_empty());
+        assert_eq!(blob.byte_array, ByteArray::from_string("cai").unwrap());
+
+        assert!(serde_json::from_str::<Blob>(
+            r#"{ "hex": "0xcaf", "base64": "", "byte_array": "0x" }"#
+        )
+        .is_err());
+    }
+}
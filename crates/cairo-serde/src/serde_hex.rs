@@ -1,5 +1,7 @@
 use serde::ser::SerializeSeq;
+use starknet::core::types::Felt;
 use std::num::ParseIntError;
+use std::str::FromStr;
 
 pub trait FromStrHexOrDec: Sized {
     fn from_str_hex_or_dec(s: &str) -> Result<Self, ParseIntError>;
@@ -48,7 +50,7 @@ where
 
 /// Serialize a vector of values as a hex string.
 pub fn serialize_as_hex_vec<S, T>(
-    value: &Vec<T>,
+    value: &[T],
     serializer: S,
 ) -> std::result::Result<S::Ok, S::Error>
 where
@@ -96,6 +98,65 @@ where
     seq.end()
 }
 
+/// Serialize a value as a decimal string.
+pub fn serialize_as_dec<S, T>(value: &T, serializer: S) -> std::result::Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+    T: serde::Serialize + std::fmt::Display,
+{
+    serializer.serialize_str(&value.to_string())
+}
+
+/// Serialize a vector of values as decimal strings.
+pub fn serialize_as_dec_vec<S, T>(
+    value: &[T],
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+    T: serde::Serialize + std::fmt::Display,
+{
+    let mut seq = serializer.serialize_seq(Some(value.len()))?;
+    for v in value {
+        seq.serialize_element(&v.to_string())?;
+    }
+    seq.end()
+}
+
+/// Serialize a tuple of two values as decimal strings.
+pub fn serialize_as_dec_t2<S, T1, T2>(
+    value: &(T1, T2),
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+    T1: serde::Serialize + std::fmt::Display,
+    T2: serde::Serialize + std::fmt::Display,
+{
+    let mut seq = serializer.serialize_seq(Some(2))?;
+    seq.serialize_element(&value.0.to_string())?;
+    seq.serialize_element(&value.1.to_string())?;
+    seq.end()
+}
+
+/// Serialize a tuple of three values as decimal strings.
+pub fn serialize_as_dec_t3<S, T1, T2, T3>(
+    value: &(T1, T2, T3),
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+    T1: serde::Serialize + std::fmt::Display,
+    T2: serde::Serialize + std::fmt::Display,
+    T3: serde::Serialize + std::fmt::Display,
+{
+    let mut seq = serializer.serialize_seq(Some(3))?;
+    seq.serialize_element(&value.0.to_string())?;
+    seq.serialize_element(&value.1.to_string())?;
+    seq.serialize_element(&value.2.to_string())?;
+    seq.end()
+}
+
 /// Deserialize a single hex string into a value.
 pub fn deserialize_from_hex<'de, D, T>(deserializer: D) -> std::result::Result<T, D::Error>
 where
@@ -106,11 +167,12 @@ where
     T::from_str_hex_or_dec(&hex_string).map_err(serde::de::Error::custom)
 }
 
-/// Deserialize a vector of hex strings into values.
-pub fn deserialize_from_hex_vec<'de, D, T>(deserializer: D) -> std::result::Result<Vec<T>, D::Error>
+/// Deserialize a vector of hex strings into values, collected into a `Vec` or a `CairoSpan`.
+pub fn deserialize_from_hex_vec<'de, D, T, V>(deserializer: D) -> std::result::Result<V, D::Error>
 where
     D: serde::Deserializer<'de>,
     T: serde::Deserialize<'de> + FromStrHexOrDec,
+    V: FromIterator<T>,
 {
     let hex_strings: Vec<String> = serde::Deserialize::deserialize(deserializer)?;
     hex_strings
@@ -119,6 +181,45 @@ where
         .collect()
 }
 
+/// Deserialize a felt from a hex (`0x` prefixed) or a decimal string.
+///
+/// The default deserialization of `Felt` only accepts hex strings.
+pub fn deserialize_felt_from_hex_or_dec<'de, D>(
+    deserializer: D,
+) -> std::result::Result<Felt, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let s: String = serde::Deserialize::deserialize(deserializer)?;
+    felt_from_hex_or_dec(&s).map_err(serde::de::Error::custom)
+}
+
+/// Deserialize a vector of hex (`0x` prefixed) or decimal strings into felts,
+/// collected into a `Vec` or a `CairoSpan`.
+pub fn deserialize_felt_from_hex_or_dec_vec<'de, D, V>(
+    deserializer: D,
+) -> std::result::Result<V, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    V: FromIterator<Felt>,
+{
+    let strings: Vec<String> = serde::Deserialize::deserialize(deserializer)?;
+    strings
+        .iter()
+        .map(|s| felt_from_hex_or_dec(s).map_err(serde::de::Error::custom))
+        .collect()
+}
+
+fn felt_from_hex_or_dec(s: &str) -> std::result::Result<Felt, String> {
+    let felt = if let Some(hex) = s.strip_prefix("0X") {
+        Felt::from_hex(hex)
+    } else {
+        Felt::from_str(s)
+    };
+
+    felt.map_err(|_| format!("invalid felt `{s}`, expected a hex or a decimal string"))
+}
+
 /// Deserialize a string into a value, trying first to use `from_str` default.
 /// If it fails, tries to parse as a hex string.
 macro_rules! deserialize_hex {
@@ -178,3 +279,80 @@ where
     let v3 = deserialize_hex!(&hex_strings.2, T3)?;
     Ok((v1, v2, v3))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Decimals {
+        #[serde(
+            serialize_with = "serialize_as_dec",
+            deserialize_with = "deserialize_from_hex"
+        )]
+        amount: u128,
+        #[serde(
+            serialize_with = "serialize_as_dec",
+            deserialize_with = "deserialize_felt_from_hex_or_dec"
+        )]
+        felt: Felt,
+        #[serde(
+            serialize_with = "serialize_as_dec_vec",
+            deserialize_with = "deserialize_felt_from_hex_or_dec_vec"
+        )]
+        felts: Vec<Felt>,
+        #[serde(
+            serialize_with = "serialize_as_dec_t2",
+            deserialize_with = "deserialize_from_hex_t2"
+        )]
+        pair: (u64, Felt),
+    }
+
+    #[test]
+    fn test_decimal_serialization() {
+        let value = Decimals {
+            amount: 123,
+            felt: Felt::from(123_u32),
+            felts: vec![Felt::ONE, Felt::from(255_u32)],
+            pair: (7, Felt::TWO),
+        };
+
+        let json = serde_json::to_value(&value).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "amount": "123",
+                "felt": "123",
+                "felts": ["1", "255"],
+                "pair": ["7", "2"],
+            })
+        );
+
+        assert_eq!(serde_json::from_value::<Decimals>(json).unwrap(), value);
+    }
+
+    #[test]
+    fn test_deserialize_hex_or_dec() {
+        let json = serde_json::json!({
+            "amount": "0x7b",
+            "felt": "0x7b",
+            "felts": ["0x1", "255"],
+            "pair": ["0x7", "2"],
+        });
+
+        let value: Decimals = serde_json::from_value(json).unwrap();
+        assert_eq!(value.amount, 123);
+        assert_eq!(value.felt, Felt::from(123_u32));
+        assert_eq!(value.felts, vec![Felt::ONE, Felt::from(255_u32)]);
+        assert_eq!(value.pair, (7, Felt::TWO));
+
+        let invalid = serde_json::json!({
+            "amount": "1",
+            "felt": "0xzz",
+            "felts": [],
+            "pair": ["1", "1"],
+        });
+        assert!(serde_json::from_value::<Decimals>(invalid).is_err());
+    }
+}
@@ -0,0 +1,171 @@
+//! Adapters to (de)serialize a Rust type through a Cairo-serializable type.
+//!
+//! Implementing [`Adapter<T>`] for a Rust type `U` tells how `U` converts
+//! from / into the Rust type of `T`. [`SerdeAdapter<T, U>`] then implements
+//! `CairoSerde` with `U` as Rust type, and can be used wherever a field type
+//! is expected by the `CairoSerde` machinery:
+//!
+//! ```ignore
+//! #[derive(CairoSerde)]
+//! struct Lock {
+//!     owner: ContractAddress,
+//!     #[cairo_serde(with = SerdeAdapter<(u64, u64), Duration>)]
+//!     duration: Duration,
+//! }
+//!
+//! impl CairoSerde for Lock {
+//!     type RustType = Self;
+//!
+//!     cairo_serde_struct_body!(Lock {
+//!         owner: ContractAddress,
+//!         duration: SerdeAdapter<(u64, u64), Duration>,
+//!     });
+//! }
+//! ```
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use crate::{CairoSerde, Error, Result};
+use starknet::core::types::Felt;
+
+/// Conversion of a Rust type from / into the Rust type of the Cairo-serializable type `T`.
+pub trait Adapter<T: CairoSerde>: Sized {
+    /// Converts the value into the Rust type of `T`, to be serialized.
+    fn to_cairo(&self) -> T::RustType;
+
+    /// Converts back a deserialized value of `T`.
+    fn from_cairo(cairo: T::RustType) -> Result<Self>;
+}
+
+/// `CairoSerde` implementation for a Rust type `U`, serialized as `T`.
+///
+/// See [`Adapter`].
+pub struct SerdeAdapter<T, U>(PhantomData<(T, U)>);
+
+impl<T, U> CairoSerde for SerdeAdapter<T, U>
+where
+    T: CairoSerde,
+    U: Adapter<T>,
+{
+    type RustType = U;
+
+    const SERIALIZED_SIZE: Option<usize> = T::SERIALIZED_SIZE;
+    const DYNAMIC: bool = T::DYNAMIC;
+
+    #[inline]
+    fn cairo_serialized_size(rust: &Self::RustType) -> usize {
+        T::cairo_serialized_size(&rust.to_cairo())
+    }
+
+    fn cairo_serialize(rust: &Self::RustType) -> Vec<Felt> {
+        T::cairo_serialize(&rust.to_cairo())
+    }
+
+    #[inline]
+    fn cairo_serialize_to(rust: &Self::RustType, out: &mut Vec<Felt>) {
+        T::cairo_serialize_to(&rust.to_cairo(), out)
+    }
+
+    fn cairo_deserialize(felts: &[Felt], offset: usize) -> Result<Self::RustType> {
+        U::from_cairo(T::cairo_deserialize(felts, offset)?)
+    }
+
+    /// The size is the one of the Cairo value, without converting the adapted value back.
+    fn cairo_deserialize_with_size(
+        felts: &[Felt],
+        offset: usize,
+    ) -> Result<(Self::RustType, usize)> {
+        let (cairo, size) = T::cairo_deserialize_with_size(felts, offset)?;
+        Ok((U::from_cairo(cairo)?, size))
+    }
+}
+
+/// A `Duration` as a tuple of seconds and nanoseconds.
+impl Adapter<(u64, u64)> for Duration {
+    fn to_cairo(&self) -> (u64, u64) {
+        (self.as_secs(), self.subsec_nanos() as u64)
+    }
+
+    fn from_cairo((secs, nanos): (u64, u64)) -> Result<Self> {
+        let nanos = u32::try_from(nanos)
+            .ok()
+            .filter(|n| *n < 1_000_000_000)
+            .ok_or_else(|| {
+                Error::Deserialize(format!("Invalid nanoseconds for a duration: {}", nanos))
+            })?;
+
+        Ok(Duration::new(secs, nanos))
+    }
+}
+
+/// A `Duration` as a number of seconds, the sub-second part being truncated.
+impl Adapter<u64> for Duration {
+    fn to_cairo(&self) -> u64 {
+        self.as_secs()
+    }
+
+    fn from_cairo(secs: u64) -> Result<Self> {
+        Ok(Duration::from_secs(secs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ContractAddress;
+
+    type DurationTuple = SerdeAdapter<(u64, u64), Duration>;
+
+    #[test]
+    fn test_duration_tuple_adapter() {
+        let d = Duration::new(12, 345);
+        let felts = DurationTuple::cairo_serialize(&d);
+
+        assert_eq!(felts, vec![Felt::from(12), Felt::from(345)]);
+        assert_eq!(DurationTuple::cairo_serialized_size(&d), 2);
+        assert_eq!(DurationTuple::cairo_deserialize(&felts, 0).unwrap(), d);
+
+        let invalid = vec![Felt::ONE, Felt::from(1_000_000_000)];
+        assert!(DurationTuple::cairo_deserialize(&invalid, 0).is_err());
+    }
+
+    #[test]
+    fn test_duration_secs_adapter() {
+        let d = Duration::new(12, 345);
+        let felts = SerdeAdapter::<u64, Duration>::cairo_serialize(&d);
+
+        assert_eq!(felts, vec![Felt::from(12)]);
+        assert_eq!(
+            SerdeAdapter::<u64, Duration>::cairo_deserialize(&felts, 0).unwrap(),
+            Duration::from_secs(12)
+        );
+        assert_eq!(SerdeAdapter::<u64, Duration>::SERIALIZED_SIZE, Some(1));
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Lock {
+        owner: ContractAddress,
+        duration: Duration,
+    }
+
+    impl CairoSerde for Lock {
+        type RustType = Self;
+
+        crate::cairo_serde_struct_body!(Lock {
+            owner: ContractAddress,
+            duration: SerdeAdapter<(u64, u64), Duration>,
+        });
+    }
+
+    #[test]
+    fn test_adapter_as_struct_field() {
+        let lock = Lock {
+            owner: ContractAddress(Felt::ONE),
+            duration: Duration::new(2, 3),
+        };
+
+        let felts = Lock::cairo_serialize(&lock);
+        assert_eq!(felts, vec![Felt::ONE, Felt::TWO, Felt::THREE]);
+        assert_eq!(Lock::cairo_deserialize(&felts, 0).unwrap(), lock);
+    }
+}
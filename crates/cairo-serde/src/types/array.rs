@@ -1,5 +1,5 @@
 //! CairoSerde implementation for `Vec`.
-//! They are used for Array and Span cairo types.
+//! They are used for the Array cairo type, the Span being a `CairoSpan`.
 use crate::{CairoSerde, Error, Result};
 use starknet::core::types::Felt;
 
@@ -19,12 +19,24 @@ where
     }
 
     fn cairo_serialize(rust: &Self::RustType) -> Vec<Felt> {
-        let mut out: Vec<Felt> = vec![rust.len().into()];
-        rust.iter().for_each(|r| out.extend(T::cairo_serialize(r)));
+        let mut out: Vec<Felt> = vec![];
+        Self::cairo_serialize_to(rust, &mut out);
         out
     }
 
+    fn cairo_serialize_to(rust: &Self::RustType, out: &mut Vec<Felt>) {
+        out.push(rust.len().into());
+        rust.iter().for_each(|r| T::cairo_serialize_to(r, out));
+    }
+
     fn cairo_deserialize(felts: &[Felt], offset: usize) -> Result<Self::RustType> {
+        Self::cairo_deserialize_with_size(felts, offset).map(|(out, _)| out)
+    }
+
+    fn cairo_deserialize_with_size(
+        felts: &[Felt],
+        offset: usize,
+    ) -> Result<(Self::RustType, usize)> {
         if offset >= felts.len() {
             return Err(Error::Deserialize(format!(
                 "Buffer too short to deserialize an array: offset ({}) : buffer {:?}",
@@ -37,7 +49,13 @@ where
                 Error::Deserialize("First felt of an array must fit into usize".to_string())
             })?;
 
-        if offset + len >= felts.len() {
+        // Only statically sized elements can be checked upfront, dynamic
+        // elements are checked during their own deserialization.
+        let min_size = len
+            .saturating_mul(T::SERIALIZED_SIZE.unwrap_or(0))
+            .saturating_add(offset + 1);
+
+        if min_size > felts.len() {
             return Err(Error::Deserialize(format!(
                 "Buffer too short to deserialize an array of length {}: offset ({}) : buffer {:?}",
                 len, offset, felts,
@@ -45,19 +63,21 @@ where
         }
 
         let mut out: Vec<RT> = vec![];
-        let mut offset = offset + 1;
+        // The length is the first felt.
+        let mut size = 1;
 
         loop {
             if out.len() == len {
                 break;
             }
 
-            let rust: RT = T::cairo_deserialize(felts, offset)?;
-            offset += T::cairo_serialized_size(&rust);
+            let (rust, rust_size) = T::cairo_deserialize_with_size(felts, offset + size)
+                .map_err(|e| e.in_index(out.len()))?;
+            size += rust_size;
             out.push(rust);
         }
 
-        Ok(out)
+        Ok((out, size))
     }
 }
 
@@ -116,6 +136,15 @@ mod tests {
         assert_eq!(vals[1], vec![3]);
     }
 
+    #[test]
+    fn test_deserialize_array_nested_error_path() {
+        // The second inner array is too short.
+        let felts: Vec<Felt> = vec![Felt::TWO, Felt::ONE, Felt::ONE, Felt::TWO, Felt::ONE];
+
+        let err = Vec::<Vec<u32>>::cairo_deserialize(&felts, 0).unwrap_err();
+        assert_eq!(err.path().unwrap(), "[1]");
+    }
+
     #[test]
     fn test_serialize_array_tuple() {
         let v: Vec<(u32, Felt)> = vec![(12, Felt::TWO)];
@@ -3,6 +3,7 @@ use crate::{CairoSerde, Error, Result};
 use starknet::core::types::Felt;
 
 #[derive(Debug, Clone, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct CairoArrayLegacy<T>(pub Vec<T>);
 
 impl<T: std::clone::Clone> CairoArrayLegacy<T> {
@@ -42,21 +43,30 @@ where
 
     fn cairo_serialize(rust: &Self::RustType) -> Vec<Felt> {
         let mut out: Vec<Felt> = vec![];
-        rust.0
-            .iter()
-            .for_each(|r| out.extend(T::cairo_serialize(r)));
+        Self::cairo_serialize_to(rust, &mut out);
         out
     }
 
+    fn cairo_serialize_to(rust: &Self::RustType, out: &mut Vec<Felt>) {
+        rust.0.iter().for_each(|r| T::cairo_serialize_to(r, out));
+    }
+
     fn cairo_deserialize(felts: &[Felt], offset: usize) -> Result<Self::RustType> {
+        Self::cairo_deserialize_with_size(felts, offset).map(|(out, _)| out)
+    }
+
+    fn cairo_deserialize_with_size(
+        felts: &[Felt],
+        offset: usize,
+    ) -> Result<(Self::RustType, usize)> {
         if offset >= felts.len() {
             // As the length of cairo 0 arrays is not included in the serialized form of the array,
             // we don't have much choice here to return an empty array instead of an error.
-            return Ok(CairoArrayLegacy(vec![]));
+            return Ok((CairoArrayLegacy(vec![]), 0));
         }
 
         let mut out: Vec<RT> = vec![];
-        let mut offset = offset;
+        let mut size = 0;
         let len = felts[offset - 1];
 
         if Felt::from(offset) + len > Felt::from(felts.len()) {
@@ -71,12 +81,12 @@ where
                 break;
             }
 
-            let rust: RT = T::cairo_deserialize(felts, offset)?;
-            offset += T::cairo_serialized_size(&rust);
+            let (rust, rust_size) = T::cairo_deserialize_with_size(felts, offset + size)?;
+            size += rust_size;
             out.push(rust);
         }
 
-        Ok(CairoArrayLegacy(out))
+        Ok((CairoArrayLegacy(out), size))
     }
 }
 
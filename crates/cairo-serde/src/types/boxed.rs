@@ -0,0 +1,60 @@
+//! CairoSerde implementation for Box.
+//!
+//! A `Box<T>` is serialized as `T`.
+//!
+//! <https://github.com/starkware-libs/cairo/blob/main/corelib/src/box.cairo>
+use crate::{CairoSerde, Result};
+use starknet::core::types::Felt;
+
+impl<T, RT> CairoSerde for Box<T>
+where
+    T: CairoSerde<RustType = RT>,
+{
+    type RustType = Box<RT>;
+
+    const SERIALIZED_SIZE: Option<usize> = T::SERIALIZED_SIZE;
+    const DYNAMIC: bool = T::DYNAMIC;
+
+    #[inline]
+    fn cairo_serialized_size(rust: &Self::RustType) -> usize {
+        T::cairo_serialized_size(rust)
+    }
+
+    fn cairo_serialize(rust: &Self::RustType) -> Vec<Felt> {
+        T::cairo_serialize(rust)
+    }
+
+    #[inline]
+    fn cairo_serialize_to(rust: &Self::RustType, out: &mut Vec<Felt>) {
+        T::cairo_serialize_to(rust, out)
+    }
+
+    fn cairo_deserialize(felts: &[Felt], offset: usize) -> Result<Self::RustType> {
+        Ok(Box::new(T::cairo_deserialize(felts, offset)?))
+    }
+
+    #[inline]
+    fn cairo_deserialize_with_size(
+        felts: &[Felt],
+        offset: usize,
+    ) -> Result<(Self::RustType, usize)> {
+        T::cairo_deserialize_with_size(felts, offset).map(|(rust, size)| (Box::new(rust), size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::U256;
+
+    #[test]
+    fn test_box_cairo_serde() {
+        let b = Box::new(U256 { low: 1, high: 2 });
+        let felts = Box::<U256>::cairo_serialize(&b);
+
+        assert_eq!(felts, vec![Felt::ONE, Felt::TWO]);
+        assert_eq!(Box::<U256>::cairo_serialized_size(&b), 2);
+        assert_eq!(Box::<U256>::cairo_deserialize(&felts, 0).unwrap(), b);
+        assert!(Box::<U256>::cairo_deserialize(&felts, 1).is_err());
+    }
+}
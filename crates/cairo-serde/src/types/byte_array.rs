@@ -19,59 +19,10 @@ use starknet::core::types::Felt;
 use crate::error::{Error, Result as CainomeResult};
 use crate::CairoSerde;
 
-const MAX_WORD_LEN: usize = 31;
+// Kept here for backward compatibility, `Bytes31` used to be defined in this module.
+pub use super::bytes31::{Bytes31, BYTES31_LEN, BYTES31_MAX};
 
-pub const BYTES31_MAX: Felt = Felt::from_raw([
-    576460566199927480,
-    18446744073709514624,
-    20123647,
-    18446744062762287141,
-]);
-
-#[derive(
-    Debug, Clone, Eq, PartialEq, PartialOrd, Default, serde::Serialize, serde::Deserialize,
-)]
-pub struct Bytes31(Felt);
-
-impl Bytes31 {
-    pub fn new(felt: Felt) -> CainomeResult<Self> {
-        if felt > BYTES31_MAX {
-            Err(Error::Bytes31OutOfRange)
-        } else {
-            Ok(Self(felt))
-        }
-    }
-
-    pub fn felt(&self) -> Felt {
-        self.0
-    }
-}
-
-impl From<Bytes31> for Felt {
-    fn from(value: Bytes31) -> Self {
-        value.felt()
-    }
-}
-
-impl TryFrom<Felt> for Bytes31 {
-    type Error = Error;
-
-    fn try_from(value: Felt) -> Result<Self, Self::Error> {
-        Self::new(value)
-    }
-}
-
-impl CairoSerde for Bytes31 {
-    type RustType = Self;
-
-    fn cairo_serialize(rust: &Self::RustType) -> Vec<Felt> {
-        vec![rust.felt()]
-    }
-
-    fn cairo_deserialize(felts: &[Felt], offset: usize) -> CainomeResult<Self::RustType> {
-        Self::new(felts[offset])
-    }
-}
+const MAX_WORD_LEN: usize = BYTES31_LEN;
 
 #[derive(
     Debug, Clone, Eq, PartialEq, PartialOrd, Default, serde::Serialize, serde::Deserialize,
@@ -97,25 +48,46 @@ impl CairoSerde for ByteArray {
 
     fn cairo_serialize(rust: &Self::RustType) -> Vec<Felt> {
         let mut out: Vec<Felt> = vec![];
-        out.extend(Vec::<Bytes31>::cairo_serialize(&rust.data));
-        out.extend(Felt::cairo_serialize(&rust.pending_word));
-        out.extend(u32::cairo_serialize(&(rust.pending_word_len as u32)));
+        Self::cairo_serialize_to(rust, &mut out);
         out
     }
 
+    fn cairo_serialize_to(rust: &Self::RustType, out: &mut Vec<Felt>) {
+        Vec::<Bytes31>::cairo_serialize_to(&rust.data, out);
+        Felt::cairo_serialize_to(&rust.pending_word, out);
+        u32::cairo_serialize_to(&(rust.pending_word_len as u32), out);
+    }
+
     fn cairo_deserialize(felts: &[Felt], offset: usize) -> CainomeResult<Self::RustType> {
-        let mut offset = offset;
-        let data = Vec::<Bytes31>::cairo_deserialize(felts, offset)?;
-        offset += Vec::<Bytes31>::cairo_serialized_size(&data);
-        let pending_word = Felt::cairo_deserialize(felts, offset)?;
-        offset += Felt::cairo_serialized_size(&pending_word);
-        let pending_word_len = u32::cairo_deserialize(felts, offset)?;
-
-        Ok(ByteArray {
-            data,
-            pending_word,
-            pending_word_len: pending_word_len as usize,
-        })
+        Self::cairo_deserialize_with_size(felts, offset).map(|(out, _)| out)
+    }
+
+    fn cairo_deserialize_with_size(
+        felts: &[Felt],
+        offset: usize,
+    ) -> CainomeResult<(Self::RustType, usize)> {
+        let (data, data_size) = Vec::<Bytes31>::cairo_deserialize_with_size(felts, offset)?;
+        let pending_word = Felt::cairo_deserialize(felts, offset + data_size)?;
+        let pending_word_len = u32::cairo_deserialize(felts, offset + data_size + 1)?;
+
+        Ok((
+            ByteArray {
+                data,
+                pending_word,
+                pending_word_len: pending_word_len as usize,
+            },
+            data_size + 2,
+        ))
+    }
+}
+
+/// The `ByteArray` is built from an arbitrary string, which ensures the
+/// consistency of the pending word and its length.
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for ByteArray {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let s: &str = u.arbitrary()?;
+        ByteArray::from_string(s).map_err(|_| arbitrary::Error::IncorrectFormat)
     }
 }
 
@@ -128,7 +100,16 @@ impl ByteArray {
     ///
     /// * `string` - The always valid UTF-8 string to convert.
     pub fn from_string(string: &str) -> CainomeResult<Self> {
-        let bytes = string.as_bytes();
+        Ok(Self::from_bytes(string.as_bytes()))
+    }
+
+    /// Packs arbitrary bytes into a `ByteArray`, whatever their encoding.
+    /// Unlike [`ByteArray::from_string`], the bytes are not required to be UTF-8.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The bytes to pack.
+    pub fn from_bytes(bytes: &[u8]) -> Self {
         let chunks: Vec<_> = bytes.chunks(MAX_WORD_LEN).collect();
 
         let remainder = if bytes.len() % MAX_WORD_LEN != 0 {
@@ -157,15 +138,35 @@ impl ByteArray {
 
         let mut data = Vec::new();
         for chunk in full_chunks {
-            // Safe to unwrap as full chunks are 31 bytes long, always fit in a felt.
-            data.push(Bytes31::new(Felt::from_bytes_be_slice(chunk))?)
+            // Full chunks are always 31 bytes long.
+            let mut bytes = [0; BYTES31_LEN];
+            bytes.copy_from_slice(chunk);
+            data.push(Bytes31::from(bytes))
         }
 
-        Ok(Self {
+        Self {
             data,
             pending_word,
             pending_word_len,
-        })
+        }
+    }
+
+    /// Returns the bytes of the `ByteArray`, whatever their encoding.
+    /// Unlike [`ByteArray::to_string`], this never fails on non UTF-8 payloads.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+
+        for d in &self.data {
+            bytes.extend_from_slice(d.as_bytes());
+        }
+
+        if self.pending_word_len > 0 {
+            bytes.extend_from_slice(
+                &self.pending_word.to_bytes_be()[1 + MAX_WORD_LEN - self.pending_word_len..],
+            );
+        }
+
+        bytes
     }
 
     /// Converts `ByteArray` instance into a UTF-8 encoded string on success.
@@ -175,7 +176,7 @@ impl ByteArray {
 
         for d in &self.data {
             // Chunks are always 31 bytes long (MAX_WORD_LEN).
-            s.push_str(&felt_to_utf8(&d.felt(), MAX_WORD_LEN)?);
+            s.push_str(&String::from_utf8(d.as_bytes().to_vec())?);
         }
 
         if self.pending_word_len > 0 {
@@ -208,6 +209,24 @@ fn felt_to_utf8(felt: &Felt, len: usize) -> Result<String, FromUtf8Error> {
     String::from_utf8(buffer)
 }
 
+/// A `ByteArray` holding a binary blob rather than a string.
+///
+/// It is the same type, use [`ByteArray::from_bytes`] and [`ByteArray::to_bytes`]
+/// to convert it from/to the raw bytes.
+pub type CairoBytes = ByteArray;
+
+impl From<&[u8]> for ByteArray {
+    fn from(value: &[u8]) -> Self {
+        ByteArray::from_bytes(value)
+    }
+}
+
+impl From<Vec<u8>> for ByteArray {
+    fn from(value: Vec<u8>) -> Self {
+        ByteArray::from_bytes(&value)
+    }
+}
+
 impl TryFrom<String> for ByteArray {
     type Error = Error;
 
@@ -226,7 +245,8 @@ impl TryFrom<&str> for ByteArray {
 
 #[cfg(test)]
 mod tests {
-    use super::ByteArray;
+    use super::{ByteArray, CairoBytes};
+    use crate::CairoSerde;
     use starknet::core::types::Felt;
 
     #[test]
@@ -489,4 +509,48 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn test_bytes_non_utf8() {
+        // 31 invalid UTF-8 bytes in the data, and 4 in the pending word.
+        let raw: Vec<u8> = (0..35).map(|i| 0xff - i as u8).collect();
+
+        let b = ByteArray::from_bytes(&raw);
+        assert_eq!(b.data.len(), 1);
+        assert_eq!(b.pending_word_len, 4);
+        assert_eq!(b.pending_word, Felt::from_hex("0xe0dfdedd").unwrap());
+        assert!(b.to_string().is_err());
+        assert_eq!(b.to_bytes(), raw);
+
+        let felts = ByteArray::cairo_serialize(&b);
+        assert_eq!(
+            ByteArray::cairo_deserialize(&felts, 0).unwrap().to_bytes(),
+            raw
+        );
+    }
+
+    #[test]
+    fn test_bytes_leading_zeros() {
+        let raw = vec![0, 0, 1, 0];
+        let b: CairoBytes = raw.clone().into();
+
+        assert_eq!(b.pending_word, Felt::from(256));
+        assert_eq!(b.pending_word_len, 4);
+        assert_eq!(b.to_bytes(), raw);
+        assert_eq!(ByteArray::from_bytes(&[]), ByteArray::default());
+    }
+
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn test_arbitrary_byte_array() {
+        use arbitrary::{Arbitrary, Unstructured};
+
+        let raw: Vec<u8> = (0..200).map(|i| (i % 26) as u8 + b'a').collect();
+        let mut u = Unstructured::new(&raw);
+
+        let ba = ByteArray::arbitrary(&mut u).unwrap();
+        let s = ba.to_string().unwrap();
+
+        assert_eq!(ByteArray::from_string(&s).unwrap(), ba);
+    }
 }
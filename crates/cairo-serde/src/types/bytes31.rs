@@ -0,0 +1,242 @@
+//! Support for Cairo `bytes31` type.
+//! <https://github.com/starkware-libs/cairo/blob/a4de08fbd75fa1d58c69d054d6b3d99aaf318f90/corelib/src/bytes_31.cairo>
+//!
+//! A `bytes31` is a felt that is guaranteed to fit in 31 bytes.
+//! On the rust side, the bytes are stored in big-endian order,
+//! which is the order in which Cairo packs the bytes of a `ByteArray`.
+use std::{
+    fmt,
+    str::{self, FromStr, Utf8Error},
+};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use starknet::core::types::Felt;
+
+use crate::error::{Error, Result as CainomeResult};
+use crate::CairoSerde;
+
+/// The number of bytes of a `bytes31`.
+pub const BYTES31_LEN: usize = 31;
+
+/// The maximum value of a `bytes31` as a felt, which is `2^248 - 1`.
+pub const BYTES31_MAX: Felt = Felt::from_raw([
+    576460566199927480,
+    18446744073709514624,
+    20123647,
+    18446744062762287141,
+]);
+
+#[derive(Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Bytes31([u8; BYTES31_LEN]);
+
+impl Bytes31 {
+    /// Initializes a new `Bytes31` from a felt, failing if the felt
+    /// doesn't fit in 31 bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `felt` - The felt to convert.
+    pub fn new(felt: Felt) -> CainomeResult<Self> {
+        let bytes = felt.to_bytes_be();
+
+        if bytes[0] != 0 {
+            return Err(Error::Bytes31OutOfRange);
+        }
+
+        let mut out = [0; BYTES31_LEN];
+        out.copy_from_slice(&bytes[1..]);
+
+        Ok(Self(out))
+    }
+
+    /// Initializes a new `Bytes31` from big-endian bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The big-endian bytes.
+    pub const fn from_bytes(bytes: [u8; BYTES31_LEN]) -> Self {
+        Self(bytes)
+    }
+
+    /// Returns the big-endian bytes.
+    pub const fn as_bytes(&self) -> &[u8; BYTES31_LEN] {
+        &self.0
+    }
+
+    /// Returns the felt representation.
+    pub fn felt(&self) -> Felt {
+        Felt::from_bytes_be_slice(&self.0)
+    }
+
+    /// Decodes the bytes as an UTF-8 string, like a Cairo short string.
+    /// The leading null bytes are considered as padding and are ignored.
+    pub fn to_utf8(&self) -> Result<&str, Utf8Error> {
+        let start = self.0.iter().position(|b| *b != 0).unwrap_or(BYTES31_LEN);
+
+        str::from_utf8(&self.0[start..])
+    }
+}
+
+/// Displays the bytes as hex by default (e.g. `0x616263`), and as
+/// an UTF-8 string with the alternate flag (e.g. `abc` for `{:#}`).
+/// If the bytes are not a valid UTF-8 string, the hex representation is used.
+impl fmt::Display for Bytes31 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            if let Ok(s) = self.to_utf8() {
+                return f.write_str(s);
+            }
+        }
+
+        write!(f, "{:#x}", self.felt())
+    }
+}
+
+impl fmt::Debug for Bytes31 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Bytes31({:#x})", self.felt())
+    }
+}
+
+/// Parses a `Bytes31` from its hex or decimal felt representation.
+impl FromStr for Bytes31 {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let felt = if s.starts_with("0x") || s.starts_with("0X") {
+            Felt::from_hex(s)
+        } else {
+            Felt::from_dec_str(s)
+        }
+        .map_err(|e| Error::Deserialize(format!("Invalid bytes31 `{}`: {}", s, e)))?;
+
+        Self::new(felt)
+    }
+}
+
+impl From<[u8; BYTES31_LEN]> for Bytes31 {
+    fn from(value: [u8; BYTES31_LEN]) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Bytes31> for [u8; BYTES31_LEN] {
+    fn from(value: Bytes31) -> Self {
+        value.0
+    }
+}
+
+impl From<Bytes31> for Felt {
+    fn from(value: Bytes31) -> Self {
+        value.felt()
+    }
+}
+
+impl TryFrom<Felt> for Bytes31 {
+    type Error = Error;
+
+    fn try_from(value: Felt) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+/// Serialized as a felt, to remain compatible with the felt representation.
+impl Serialize for Bytes31 {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.felt().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Bytes31 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let felt = Felt::deserialize(deserializer)?;
+        Self::new(felt).map_err(serde::de::Error::custom)
+    }
+}
+
+impl CairoSerde for Bytes31 {
+    type RustType = Self;
+
+    fn cairo_serialize(rust: &Self::RustType) -> Vec<Felt> {
+        vec![rust.felt()]
+    }
+
+    fn cairo_deserialize(felts: &[Felt], offset: usize) -> CainomeResult<Self::RustType> {
+        let felt = felts.get(offset).ok_or_else(|| {
+            Error::Deserialize(format!(
+                "Buffer too short to deserialize a bytes31: offset ({}) : buffer {:?}",
+                offset, felts,
+            ))
+        })?;
+
+        Self::new(*felt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn abc() -> Bytes31 {
+        let mut bytes = [0; BYTES31_LEN];
+        bytes[28..].copy_from_slice(b"abc");
+        Bytes31::from(bytes)
+    }
+
+    #[test]
+    fn test_felt_conversions() {
+        let b = abc();
+        assert_eq!(b.felt(), Felt::from_hex("0x616263").unwrap());
+        assert_eq!(Bytes31::try_from(b.felt()).unwrap(), b);
+        assert_eq!(Felt::from(b), Felt::from_hex("0x616263").unwrap());
+
+        assert_eq!(Bytes31::new(BYTES31_MAX).unwrap().as_bytes(), &[0xff; 31]);
+        assert!(Bytes31::new(BYTES31_MAX + Felt::ONE).is_err());
+        assert!(Bytes31::new(Felt::MAX).is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        let b = abc();
+        assert_eq!(format!("{}", b), "0x616263");
+        assert_eq!(format!("{:#}", b), "abc");
+        assert_eq!(format!("{:?}", b), "Bytes31(0x616263)");
+        assert_eq!(b.to_utf8().unwrap(), "abc");
+
+        let invalid = Bytes31::from([0xff; BYTES31_LEN]);
+        assert!(invalid.to_utf8().is_err());
+        assert_eq!(format!("{:#}", invalid), format!("{:#x}", BYTES31_MAX));
+
+        assert_eq!(Bytes31::default().to_utf8().unwrap(), "");
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(Bytes31::from_str("0x616263").unwrap(), abc());
+        assert_eq!(Bytes31::from_str("6382179").unwrap(), abc());
+        assert!(Bytes31::from_str("abc").is_err());
+        assert!(Bytes31::from_str(&format!("{:#x}", Felt::MAX)).is_err());
+    }
+
+    #[test]
+    fn test_serde() {
+        let b = abc();
+        let json = serde_json::to_string(&b).unwrap();
+        assert_eq!(json, "\"0x616263\"");
+        assert_eq!(serde_json::from_str::<Bytes31>(&json).unwrap(), b);
+
+        let out_of_range = format!("\"{:#x}\"", Felt::MAX);
+        assert!(serde_json::from_str::<Bytes31>(&out_of_range).is_err());
+    }
+
+    #[test]
+    fn test_cairo_serde() {
+        let b = abc();
+        let felts = Bytes31::cairo_serialize(&b);
+        assert_eq!(felts, vec![Felt::from_hex("0x616263").unwrap()]);
+        assert_eq!(Bytes31::cairo_deserialize(&felts, 0).unwrap(), b);
+        assert!(Bytes31::cairo_deserialize(&felts, 1).is_err());
+        assert!(Bytes31::cairo_deserialize(&[Felt::MAX], 0).is_err());
+    }
+}
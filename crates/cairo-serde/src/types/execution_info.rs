@@ -0,0 +1,280 @@
+//! Support for the Cairo structs returned by `starknet::get_execution_info`.
+//! <https://github.com/starkware-libs/cairo/blob/main/corelib/src/starknet/info.cairo>
+//!
+//! In Cairo, `ExecutionInfo` boxes the block and transaction info, but a `Box<T>`
+//! is serialized as `T`. Hence, the Rust types directly contain the values.
+use serde::{Deserialize, Serialize};
+use starknet::core::types::Felt;
+
+use crate::types::starknet::ContractAddress;
+use crate::{cairo_serde_struct_body, CairoSerde};
+
+/// `core::starknet::info::BlockInfo`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct BlockInfo {
+    pub block_number: u64,
+    pub block_timestamp: u64,
+    pub sequencer_address: ContractAddress,
+}
+
+impl CairoSerde for BlockInfo {
+    type RustType = Self;
+
+    cairo_serde_struct_body!(BlockInfo {
+        block_number: u64,
+        block_timestamp: u64,
+        sequencer_address: ContractAddress,
+    });
+}
+
+/// `core::starknet::info::TxInfo`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct TxInfo {
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::FeltSchema"))]
+    #[cfg_attr(feature = "utoipa", schema(value_type = String))]
+    pub version: Felt,
+    pub account_contract_address: ContractAddress,
+    pub max_fee: u128,
+    #[cfg_attr(feature = "schemars", schemars(with = "Vec<crate::FeltSchema>"))]
+    #[cfg_attr(feature = "utoipa", schema(value_type = Vec<String>))]
+    pub signature: Vec<Felt>,
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::FeltSchema"))]
+    #[cfg_attr(feature = "utoipa", schema(value_type = String))]
+    pub transaction_hash: Felt,
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::FeltSchema"))]
+    #[cfg_attr(feature = "utoipa", schema(value_type = String))]
+    pub chain_id: Felt,
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::FeltSchema"))]
+    #[cfg_attr(feature = "utoipa", schema(value_type = String))]
+    pub nonce: Felt,
+}
+
+impl CairoSerde for TxInfo {
+    type RustType = Self;
+
+    cairo_serde_struct_body!(TxInfo {
+        version: Felt,
+        account_contract_address: ContractAddress,
+        max_fee: u128,
+        signature: Vec<Felt>,
+        transaction_hash: Felt,
+        chain_id: Felt,
+        nonce: Felt,
+    });
+}
+
+/// `core::starknet::info::ExecutionInfo`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ExecutionInfo {
+    pub block_info: BlockInfo,
+    pub tx_info: TxInfo,
+    pub caller_address: ContractAddress,
+    pub contract_address: ContractAddress,
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::FeltSchema"))]
+    #[cfg_attr(feature = "utoipa", schema(value_type = String))]
+    pub entry_point_selector: Felt,
+}
+
+impl CairoSerde for ExecutionInfo {
+    type RustType = Self;
+
+    cairo_serde_struct_body!(ExecutionInfo {
+        block_info: BlockInfo,
+        tx_info: TxInfo,
+        caller_address: ContractAddress,
+        contract_address: ContractAddress,
+        entry_point_selector: Felt,
+    });
+}
+
+/// `core::starknet::info::v2::ResourcesBounds`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ResourcesBounds {
+    /// The resource name, as a Cairo short string (e.g. `L1_GAS`).
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::FeltSchema"))]
+    #[cfg_attr(feature = "utoipa", schema(value_type = String))]
+    pub resource: Felt,
+    pub max_amount: u64,
+    pub max_price_per_unit: u128,
+}
+
+impl CairoSerde for ResourcesBounds {
+    type RustType = Self;
+
+    cairo_serde_struct_body!(ResourcesBounds {
+        resource: Felt,
+        max_amount: u64,
+        max_price_per_unit: u128,
+    });
+}
+
+/// `core::starknet::info::v2::TxInfo`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct TxInfoV2 {
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::FeltSchema"))]
+    #[cfg_attr(feature = "utoipa", schema(value_type = String))]
+    pub version: Felt,
+    pub account_contract_address: ContractAddress,
+    pub max_fee: u128,
+    #[cfg_attr(feature = "schemars", schemars(with = "Vec<crate::FeltSchema>"))]
+    #[cfg_attr(feature = "utoipa", schema(value_type = Vec<String>))]
+    pub signature: Vec<Felt>,
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::FeltSchema"))]
+    #[cfg_attr(feature = "utoipa", schema(value_type = String))]
+    pub transaction_hash: Felt,
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::FeltSchema"))]
+    #[cfg_attr(feature = "utoipa", schema(value_type = String))]
+    pub chain_id: Felt,
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::FeltSchema"))]
+    #[cfg_attr(feature = "utoipa", schema(value_type = String))]
+    pub nonce: Felt,
+    pub resource_bounds: Vec<ResourcesBounds>,
+    pub tip: u128,
+    #[cfg_attr(feature = "schemars", schemars(with = "Vec<crate::FeltSchema>"))]
+    #[cfg_attr(feature = "utoipa", schema(value_type = Vec<String>))]
+    pub paymaster_data: Vec<Felt>,
+    pub nonce_data_availability_mode: u32,
+    pub fee_data_availability_mode: u32,
+    #[cfg_attr(feature = "schemars", schemars(with = "Vec<crate::FeltSchema>"))]
+    #[cfg_attr(feature = "utoipa", schema(value_type = Vec<String>))]
+    pub account_deployment_data: Vec<Felt>,
+}
+
+impl CairoSerde for TxInfoV2 {
+    type RustType = Self;
+
+    cairo_serde_struct_body!(TxInfoV2 {
+        version: Felt,
+        account_contract_address: ContractAddress,
+        max_fee: u128,
+        signature: Vec<Felt>,
+        transaction_hash: Felt,
+        chain_id: Felt,
+        nonce: Felt,
+        resource_bounds: Vec<ResourcesBounds>,
+        tip: u128,
+        paymaster_data: Vec<Felt>,
+        nonce_data_availability_mode: u32,
+        fee_data_availability_mode: u32,
+        account_deployment_data: Vec<Felt>,
+    });
+}
+
+/// `core::starknet::info::v2::ExecutionInfo`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ExecutionInfoV2 {
+    pub block_info: BlockInfo,
+    pub tx_info: TxInfoV2,
+    pub caller_address: ContractAddress,
+    pub contract_address: ContractAddress,
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::FeltSchema"))]
+    #[cfg_attr(feature = "utoipa", schema(value_type = String))]
+    pub entry_point_selector: Felt,
+}
+
+impl CairoSerde for ExecutionInfoV2 {
+    type RustType = Self;
+
+    cairo_serde_struct_body!(ExecutionInfoV2 {
+        block_info: BlockInfo,
+        tx_info: TxInfoV2,
+        caller_address: ContractAddress,
+        contract_address: ContractAddress,
+        entry_point_selector: Felt,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block_info() -> BlockInfo {
+        BlockInfo {
+            block_number: 1,
+            block_timestamp: 2,
+            sequencer_address: ContractAddress(Felt::THREE),
+        }
+    }
+
+    #[test]
+    fn test_execution_info_cairo_serde() {
+        let info = ExecutionInfo {
+            block_info: block_info(),
+            tx_info: TxInfo {
+                version: Felt::ONE,
+                account_contract_address: ContractAddress(Felt::TWO),
+                max_fee: 3,
+                signature: vec![Felt::from(4), Felt::from(5)],
+                transaction_hash: Felt::from(6),
+                chain_id: Felt::from(7),
+                nonce: Felt::from(8),
+            },
+            caller_address: ContractAddress(Felt::from(9)),
+            contract_address: ContractAddress(Felt::from(10)),
+            entry_point_selector: Felt::from(11),
+        };
+
+        let felts = ExecutionInfo::cairo_serialize(&info);
+        let expected: Vec<Felt> = [1, 2, 3, 1, 2, 3, 2, 4, 5, 6, 7, 8, 9, 10, 11]
+            .into_iter()
+            .map(Felt::from)
+            .collect();
+
+        assert_eq!(felts, expected);
+        assert_eq!(ExecutionInfo::cairo_serialized_size(&info), felts.len());
+        assert_eq!(ExecutionInfo::cairo_deserialize(&felts, 0).unwrap(), info);
+        assert!(ExecutionInfo::cairo_deserialize(&felts[..14], 0).is_err());
+    }
+
+    #[test]
+    fn test_execution_info_v2_cairo_serde() {
+        let info = ExecutionInfoV2 {
+            block_info: block_info(),
+            tx_info: TxInfoV2 {
+                version: Felt::THREE,
+                account_contract_address: ContractAddress(Felt::TWO),
+                max_fee: 0,
+                signature: vec![],
+                transaction_hash: Felt::ONE,
+                chain_id: Felt::TWO,
+                nonce: Felt::THREE,
+                resource_bounds: vec![ResourcesBounds {
+                    resource: Felt::from_bytes_be_slice(b"L1_GAS"),
+                    max_amount: 4,
+                    max_price_per_unit: 5,
+                }],
+                tip: 6,
+                paymaster_data: vec![Felt::from(7)],
+                nonce_data_availability_mode: 0,
+                fee_data_availability_mode: 1,
+                account_deployment_data: vec![],
+            },
+            caller_address: ContractAddress(Felt::from(9)),
+            contract_address: ContractAddress(Felt::from(10)),
+            entry_point_selector: Felt::from(11),
+        };
+
+        let felts = ExecutionInfoV2::cairo_serialize(&info);
+
+        assert_eq!(felts.len(), 3 + 7 + 4 + 1 + 2 + 2 + 1 + 3);
+        assert_eq!(ExecutionInfoV2::cairo_deserialize(&felts, 0).unwrap(), info);
+    }
+}
@@ -0,0 +1,112 @@
+//! CairoSerde implementation for `IndexMap`, behind the `indexmap` feature.
+//!
+//! Cairo has no map type in the ABIs, an `IndexMap<K, V>` is serialized as an
+//! `Array<(K, V)>`, the pairs in their insertion order.
+use crate::{CairoSerde, Error, Result};
+use indexmap::IndexMap;
+use starknet::core::types::Felt;
+use std::hash::Hash;
+
+impl<K, V, RK, RV> CairoSerde for IndexMap<K, V>
+where
+    K: CairoSerde<RustType = RK>,
+    V: CairoSerde<RustType = RV>,
+    RK: Hash + Eq,
+{
+    type RustType = IndexMap<RK, RV>;
+
+    const SERIALIZED_SIZE: Option<usize> = None;
+
+    #[inline]
+    fn cairo_serialized_size(rust: &Self::RustType) -> usize {
+        // 1 + because the length is always the first felt.
+        1 + rust
+            .iter()
+            .map(|(k, v)| K::cairo_serialized_size(k) + V::cairo_serialized_size(v))
+            .sum::<usize>()
+    }
+
+    fn cairo_serialize(rust: &Self::RustType) -> Vec<Felt> {
+        let mut out: Vec<Felt> = vec![];
+        Self::cairo_serialize_to(rust, &mut out);
+        out
+    }
+
+    fn cairo_serialize_to(rust: &Self::RustType, out: &mut Vec<Felt>) {
+        out.push(rust.len().into());
+        rust.iter().for_each(|(k, v)| {
+            K::cairo_serialize_to(k, out);
+            V::cairo_serialize_to(v, out);
+        });
+    }
+
+    fn cairo_deserialize(felts: &[Felt], offset: usize) -> Result<Self::RustType> {
+        Self::cairo_deserialize_with_size(felts, offset).map(|(out, _)| out)
+    }
+
+    fn cairo_deserialize_with_size(
+        felts: &[Felt],
+        offset: usize,
+    ) -> Result<(Self::RustType, usize)> {
+        let (pairs, size) = Vec::<(K, V)>::cairo_deserialize_with_size(felts, offset)?;
+
+        let mut out = IndexMap::with_capacity(pairs.len());
+
+        for (index, (k, v)) in pairs.into_iter().enumerate() {
+            // A duplicate key would silently drop a value of the array.
+            if out.insert(k, v).is_some() {
+                return Err(
+                    Error::Deserialize("Duplicate key in a map".to_string()).in_index(index)
+                );
+            }
+        }
+
+        Ok((out, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialize_index_map() {
+        let mut m: IndexMap<Felt, u32> = IndexMap::new();
+        m.insert(Felt::THREE, 1);
+        m.insert(Felt::ONE, 2);
+
+        let felts = IndexMap::<Felt, u32>::cairo_serialize(&m);
+        assert_eq!(
+            felts,
+            vec![Felt::TWO, Felt::THREE, Felt::ONE, Felt::ONE, Felt::TWO]
+        );
+        assert_eq!(IndexMap::<Felt, u32>::cairo_serialized_size(&m), 5);
+    }
+
+    #[test]
+    fn test_deserialize_index_map_keeps_order() {
+        let felts = vec![
+            Felt::ZERO,
+            Felt::TWO,
+            Felt::from(9_u32),
+            Felt::ONE,
+            Felt::from(90_u32),
+            Felt::ONE,
+            Felt::ZERO,
+        ];
+
+        let (m, size) = IndexMap::<u8, Vec<u8>>::cairo_deserialize_with_size(&felts, 1).unwrap();
+        assert_eq!(size, 6);
+        assert_eq!(m.keys().copied().collect::<Vec<_>>(), vec![9, 1]);
+        assert_eq!(m[&9], vec![90]);
+        assert!(m[&1].is_empty());
+    }
+
+    #[test]
+    fn test_deserialize_index_map_duplicate_key() {
+        let felts = vec![Felt::TWO, Felt::ONE, Felt::TWO, Felt::ONE, Felt::THREE];
+
+        let e = IndexMap::<u8, u8>::cairo_deserialize(&felts, 0).unwrap_err();
+        assert_eq!(e.path().unwrap(), "[1]");
+    }
+}
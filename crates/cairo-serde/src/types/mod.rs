@@ -1,19 +1,28 @@
+pub mod adapter;
 pub mod array;
 pub mod array_legacy;
 pub mod boolean;
+pub mod boxed;
 pub mod byte_array;
+pub mod bytes31;
+pub mod execution_info;
 pub mod felt;
+#[cfg(feature = "indexmap")]
+pub mod index_map;
 pub mod integers;
 pub mod non_zero;
 pub mod option;
 pub mod result;
+#[cfg(feature = "secret")]
+pub mod secret;
+pub mod span;
 pub mod starknet;
 pub mod tuple;
 pub mod u256;
 
 #[cfg(test)]
 mod tests {
-    use crate::CairoSerde;
+    use crate::{ByteArray, CairoSerde, U256};
     use ::starknet::core::types::Felt;
 
     #[test]
@@ -59,6 +68,68 @@ mod tests {
         assert_eq!(felts[6], Felt::THREE);
     }
 
+    #[test]
+    fn test_serialize_to_single_buffer() {
+        let o = Some(u32::MAX);
+        let r: Result<Vec<(u8, Felt)>, Felt> = Ok(vec![(1, Felt::TWO), (3, Felt::from(4))]);
+        let a: Vec<Option<u64>> = vec![Some(1), None];
+
+        let mut expected = vec![];
+        expected.extend(Option::<u32>::cairo_serialize(&o));
+        expected.extend(Result::<Vec<(u8, Felt)>, Felt>::cairo_serialize(&r));
+        expected.extend(Vec::<Option<u64>>::cairo_serialize(&a));
+
+        let mut felts = vec![];
+        Option::<u32>::cairo_serialize_to(&o, &mut felts);
+        Result::<Vec<(u8, Felt)>, Felt>::cairo_serialize_to(&r, &mut felts);
+        Vec::<Option<u64>>::cairo_serialize_to(&a, &mut felts);
+
+        assert_eq!(felts, expected);
+        assert_eq!(
+            felts,
+            vec![
+                Felt::ZERO,
+                Felt::from(u32::MAX),
+                Felt::ZERO,
+                Felt::TWO,
+                Felt::ONE,
+                Felt::TWO,
+                Felt::THREE,
+                Felt::from(4),
+                Felt::TWO,
+                Felt::ZERO,
+                Felt::ONE,
+                Felt::ONE,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_deserialize_with_size() {
+        type Nested = (Option<Vec<u8>>, Result<ByteArray, Felt>, Vec<(U256, bool)>);
+
+        let value: Nested = (
+            Some(vec![1, 2]),
+            Ok(ByteArray::from_string("a string longer than thirty one bytes").unwrap()),
+            vec![
+                (U256::from_bytes_be(&[1; 32]), true),
+                (U256 { low: 0, high: 1 }, false),
+            ],
+        );
+
+        let mut felts = vec![Felt::MAX];
+        Nested::cairo_serialize_to(&value, &mut felts);
+
+        let (decoded, size) = Nested::cairo_deserialize_with_size(&felts, 1).unwrap();
+        assert_eq!(decoded, value);
+        assert_eq!(size, Nested::cairo_serialized_size(&value));
+        assert_eq!(size, felts.len() - 1);
+
+        let (none, size) = Option::<Vec<u8>>::cairo_deserialize_with_size(&[Felt::ONE], 0).unwrap();
+        assert_eq!(none, None);
+        assert_eq!(size, 1);
+    }
+
     #[test]
     fn test_deserialize_several_values() {
         let felts = vec![
@@ -117,4 +188,171 @@ mod tests {
         assert_eq!(a, vec![1, 2, 3]);
         assert_eq!(offset, felts.len());
     }
+
+    mod nested {
+        use crate::{cairo_serde_struct_body, ByteArray, CairoSerde, NonZero, U256};
+        use ::starknet::core::types::Felt;
+        use std::fmt::Debug;
+
+        #[derive(Debug, Clone, PartialEq)]
+        struct WithDynamicFields {
+            name: ByteArray,
+            values: Vec<u32>,
+            owner: Felt,
+        }
+
+        impl CairoSerde for WithDynamicFields {
+            type RustType = Self;
+
+            cairo_serde_struct_body!(WithDynamicFields {
+                name: ByteArray,
+                values: Vec<u32>,
+                owner: Felt,
+            });
+        }
+
+        /// Checks that the value is correctly serialized when surrounded by other values,
+        /// and that the serialized size matches the actual serialized length.
+        fn assert_roundtrip<T>(value: T::RustType)
+        where
+            T: CairoSerde,
+            T::RustType: Debug + PartialEq,
+        {
+            let felts = T::cairo_serialize(&value);
+            assert_eq!(T::cairo_serialized_size(&value), felts.len());
+
+            if let Some(size) = T::SERIALIZED_SIZE {
+                assert_eq!(size, felts.len());
+            }
+
+            let prefix = vec![Felt::from(0xdead_u32), Felt::from(0xbeef_u32)];
+            let suffix = vec![Felt::MAX, Felt::TWO];
+
+            let mut buffer = prefix.clone();
+            buffer.extend(felts.clone());
+            buffer.extend(suffix);
+
+            let deserialized = T::cairo_deserialize(&buffer, prefix.len()).unwrap();
+            assert_eq!(T::cairo_serialized_size(&deserialized), felts.len());
+            assert_eq!(deserialized, value);
+
+            let (_, size) = T::cairo_deserialize_with_size(&buffer, prefix.len()).unwrap();
+            assert_eq!(size, felts.len());
+
+            // Exactly sized buffer.
+            assert_eq!(T::cairo_deserialize(&felts, 0).unwrap(), value);
+
+            // Truncated buffer.
+            if !felts.is_empty() {
+                assert!(T::cairo_deserialize(&felts[..felts.len() - 1], 0).is_err());
+            }
+        }
+
+        fn byte_array(s: &str) -> ByteArray {
+            ByteArray::from_string(s).unwrap()
+        }
+
+        #[test]
+        fn test_array_of_arrays_of_arrays() {
+            assert_roundtrip::<Vec<Vec<Vec<u8>>>>(vec![]);
+            assert_roundtrip::<Vec<Vec<Vec<u8>>>>(vec![vec![]]);
+            assert_roundtrip::<Vec<Vec<Vec<u8>>>>(vec![vec![vec![]]]);
+            assert_roundtrip::<Vec<Vec<Vec<u8>>>>(vec![
+                vec![vec![1, 2], vec![], vec![3]],
+                vec![],
+                vec![vec![4, 5, 6]],
+            ]);
+        }
+
+        #[test]
+        fn test_array_of_arrays_of_byte_arrays() {
+            let long = "a string that is longer than thirty one bytes, spanning several words";
+
+            assert_roundtrip::<Vec<Vec<ByteArray>>>(vec![
+                vec![byte_array(""), byte_array("abc")],
+                vec![],
+                vec![byte_array(long), byte_array(&long[..31])],
+            ]);
+        }
+
+        #[test]
+        fn test_array_of_arrays_of_structs() {
+            let s1 = WithDynamicFields {
+                name: byte_array("first item of a nested array of structs"),
+                values: vec![1, 2, 3],
+                owner: Felt::ONE,
+            };
+
+            let s2 = WithDynamicFields {
+                name: byte_array(""),
+                values: vec![],
+                owner: Felt::TWO,
+            };
+
+            assert_roundtrip::<Vec<Vec<WithDynamicFields>>>(vec![
+                vec![s1.clone(), s2.clone()],
+                vec![],
+                vec![s2, s1],
+            ]);
+        }
+
+        #[test]
+        fn test_options_and_results_of_nested_arrays() {
+            assert_roundtrip::<Vec<Option<Vec<U256>>>>(vec![
+                Some(vec![U256 { low: 1, high: 2 }]),
+                None,
+                Some(vec![]),
+            ]);
+
+            assert_roundtrip::<Option<Vec<Vec<ByteArray>>>>(Some(vec![
+                vec![byte_array("abc")],
+                vec![],
+            ]));
+            assert_roundtrip::<Option<Vec<Vec<ByteArray>>>>(None);
+
+            assert_roundtrip::<Result<Vec<Vec<Felt>>, ByteArray>>(Ok(vec![
+                vec![Felt::ONE],
+                vec![],
+            ]));
+            assert_roundtrip::<Result<Vec<Vec<Felt>>, ByteArray>>(Err(byte_array("error")));
+        }
+
+        #[test]
+        fn test_tuples_of_nested_arrays() {
+            assert_roundtrip::<Vec<(Vec<u8>, ByteArray, Vec<Vec<u64>>)>>(vec![
+                (vec![1], byte_array("a"), vec![vec![2, 3], vec![]]),
+                (vec![], byte_array(""), vec![]),
+            ]);
+        }
+
+        #[test]
+        fn test_arrays_of_non_zero_and_units() {
+            assert_roundtrip::<Vec<Vec<NonZero<U256>>>>(vec![vec![NonZero::new(U256 {
+                low: 1,
+                high: 0,
+            })
+            .unwrap()]]);
+
+            assert_roundtrip::<Vec<Option<()>>>(vec![Some(()), None]);
+            assert_roundtrip::<Vec<()>>(vec![(), ()]);
+        }
+
+        #[test]
+        fn test_static_sizes() {
+            assert_eq!(<()>::SERIALIZED_SIZE, Some(0));
+            assert_eq!(Option::<u32>::SERIALIZED_SIZE, None);
+            assert_eq!(Result::<u32, u32>::SERIALIZED_SIZE, None);
+            assert_eq!(Vec::<u32>::SERIALIZED_SIZE, None);
+            assert_eq!(ByteArray::SERIALIZED_SIZE, None);
+            assert_eq!(U256::SERIALIZED_SIZE, Some(2));
+
+            let is_dynamic = [
+                Option::<u32>::DYNAMIC,
+                Result::<u32, u32>::DYNAMIC,
+                Vec::<u32>::DYNAMIC,
+                ByteArray::DYNAMIC,
+            ];
+            assert!(is_dynamic.iter().all(|d| *d));
+        }
+    }
 }
@@ -51,9 +51,33 @@ where
         T::cairo_serialize(&rust.0)
     }
 
+    #[inline]
+    fn cairo_serialize_to(rust: &Self::RustType, out: &mut Vec<Felt>) {
+        T::cairo_serialize_to(&rust.0, out)
+    }
+
     fn cairo_deserialize(felts: &[Felt], offset: usize) -> Result<Self::RustType> {
         NonZero::new(T::cairo_deserialize(felts, offset)?).ok_or(crate::Error::ZeroedNonZero)
     }
+
+    fn cairo_deserialize_with_size(
+        felts: &[Felt],
+        offset: usize,
+    ) -> Result<(Self::RustType, usize)> {
+        let (rust, size) = T::cairo_deserialize_with_size(felts, offset)?;
+        Ok((NonZero::new(rust).ok_or(crate::Error::ZeroedNonZero)?, size))
+    }
+}
+
+/// The zero values are rejected as not matching the expected format.
+#[cfg(feature = "arbitrary")]
+impl<'a, T> arbitrary::Arbitrary<'a> for NonZero<T>
+where
+    T: arbitrary::Arbitrary<'a> + Zeroable,
+{
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        NonZero::new(T::arbitrary(u)?).ok_or(arbitrary::Error::IncorrectFormat)
+    }
 }
 
 pub trait Zeroable {
@@ -140,4 +164,19 @@ mod tests {
         let is_dynamic = NonZero::<i8>::DYNAMIC;
         assert!(!is_dynamic);
     }
+
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn test_arbitrary_non_zero() {
+        use arbitrary::{Arbitrary, Unstructured};
+
+        let mut u = Unstructured::new(&[0; 16]);
+        assert!(NonZero::<u128>::arbitrary(&mut u).is_err());
+
+        let mut u = Unstructured::new(&[1; 16]);
+        assert!(!NonZero::<u128>::arbitrary(&mut u)
+            .unwrap()
+            .inner()
+            .is_zero());
+    }
 }
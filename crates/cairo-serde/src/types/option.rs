@@ -13,6 +13,8 @@ where
 {
     type RustType = Option<RT>;
 
+    const SERIALIZED_SIZE: Option<usize> = None;
+
     #[inline]
     fn cairo_serialized_size(rust: &Self::RustType) -> usize {
         match rust {
@@ -23,19 +25,28 @@ where
 
     fn cairo_serialize(rust: &Self::RustType) -> Vec<Felt> {
         let mut out = vec![];
+        Self::cairo_serialize_to(rust, &mut out);
+        out
+    }
 
+    fn cairo_serialize_to(rust: &Self::RustType, out: &mut Vec<Felt>) {
         match rust {
             Some(r) => {
                 out.push(Felt::ZERO);
-                out.extend(T::cairo_serialize(r));
+                T::cairo_serialize_to(r, out);
             }
             None => out.push(Felt::ONE),
         };
-
-        out
     }
 
     fn cairo_deserialize(felts: &[Felt], offset: usize) -> Result<Self::RustType> {
+        Self::cairo_deserialize_with_size(felts, offset).map(|(out, _)| out)
+    }
+
+    fn cairo_deserialize_with_size(
+        felts: &[Felt],
+        offset: usize,
+    ) -> Result<(Self::RustType, usize)> {
         if offset >= felts.len() {
             return Err(Error::Deserialize(format!(
                 "Buffer too short to deserialize an Option: offset ({}) : buffer {:?}",
@@ -47,9 +58,10 @@ where
 
         if idx == Felt::ZERO {
             // + 1 as the offset value is the index of the enum.
-            Ok(Option::Some(T::cairo_deserialize(felts, offset + 1)?))
+            let (rust, size) = T::cairo_deserialize_with_size(felts, offset + 1)?;
+            Ok((Option::Some(rust), size + 1))
         } else if idx == Felt::ONE {
-            Ok(Option::None)
+            Ok((Option::None, 1))
         } else {
             Err(Error::Deserialize(
                 "Option is expected 0 or 1 index only".to_string(),
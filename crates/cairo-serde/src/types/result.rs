@@ -11,6 +11,8 @@ where
 {
     type RustType = Result<RT, RE>;
 
+    const SERIALIZED_SIZE: Option<usize> = None;
+
     #[inline]
     fn cairo_serialized_size(rust: &Self::RustType) -> usize {
         match rust {
@@ -21,22 +23,31 @@ where
 
     fn cairo_serialize(rust: &Self::RustType) -> Vec<Felt> {
         let mut out = vec![];
+        Self::cairo_serialize_to(rust, &mut out);
+        out
+    }
 
+    fn cairo_serialize_to(rust: &Self::RustType, out: &mut Vec<Felt>) {
         match rust {
             Result::Ok(r) => {
                 out.push(Felt::ZERO);
-                out.extend(T::cairo_serialize(r));
+                T::cairo_serialize_to(r, out);
             }
             Result::Err(e) => {
                 out.push(Felt::ONE);
-                out.extend(E::cairo_serialize(e));
+                E::cairo_serialize_to(e, out);
             }
         };
-
-        out
     }
 
     fn cairo_deserialize(felts: &[Felt], offset: usize) -> CairoResult<Self::RustType> {
+        Self::cairo_deserialize_with_size(felts, offset).map(|(out, _)| out)
+    }
+
+    fn cairo_deserialize_with_size(
+        felts: &[Felt],
+        offset: usize,
+    ) -> CairoResult<(Self::RustType, usize)> {
         if offset >= felts.len() {
             return Err(CairoError::Deserialize(format!(
                 "Buffer too short to deserialize a Result: offset ({}) : buffer {:?}",
@@ -48,9 +59,11 @@ where
 
         if idx == Felt::ZERO {
             // + 1 as the offset value is the index of the enum.
-            CairoResult::Ok(Ok(T::cairo_deserialize(felts, offset + 1)?))
+            let (rust, size) = T::cairo_deserialize_with_size(felts, offset + 1)?;
+            CairoResult::Ok((Ok(rust), size + 1))
         } else if idx == Felt::ONE {
-            CairoResult::Ok(Err(E::cairo_deserialize(felts, offset + 1)?))
+            let (rust, size) = E::cairo_deserialize_with_size(felts, offset + 1)?;
+            CairoResult::Ok((Err(rust), size + 1))
         } else {
             Err(CairoError::Deserialize(
                 "Result is expected 0 or 1 index only".to_string(),
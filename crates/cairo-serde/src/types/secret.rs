@@ -0,0 +1,428 @@
+//! Secret-bearing values, like the signatures or the session tokens passed through the bindings.
+//!
+//! [`Secret<T>`] wraps a value which must not leak: its `Debug` is redacted, it is compared
+//! in constant time (with `subtle`) and it is zeroized when dropped (with `zeroize`). It is
+//! serialized like `T`, so it can be used as a field type of the types deriving `CairoSerde`:
+//!
+//! ```ignore
+//! #[derive(CairoSerde)]
+//! struct Session {
+//!     owner: ContractAddress,
+//!     token: Secret<Felt>,
+//!     // A field override, for a secret of a type serialized through an adapter.
+//!     #[cairo_serde(with = Secret<SerdeAdapter<Felt, SessionKey>>)]
+//!     key: Secret<SessionKey>,
+//! }
+//! ```
+//!
+//! The types generated by `abigen!` can't use `Secret` for their members: a secret is
+//! wrapped once passed to or returned by the bindings.
+//!
+//! Only available with the `secret` feature.
+use std::fmt;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use starknet::core::types::Felt;
+pub use subtle::Choice;
+
+use crate::{
+    ByteArray, Bytes31, CairoArrayLegacy, CairoSerde, CairoSpan, ClassHash, ContractAddress,
+    EthAddress, Result, SerdeAdapter, U256,
+};
+
+/// Compares two byte slices in constant time: the time only depends on their lengths.
+///
+/// # Arguments
+///
+/// * `a` - The first bytes.
+/// * `b` - The second bytes.
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    subtle::ConstantTimeEq::ct_eq(a, b).into()
+}
+
+/// Equality in constant time, which doesn't reveal where two values differ.
+///
+/// Unlike `subtle::ConstantTimeEq`, it is implemented for `Felt` and the types of the bindings.
+/// The lengths of the variable-length values (like `Vec<T>`) are not secret.
+pub trait ConstantTimeEq {
+    /// Returns whether the values are equal, in a time independent of their contents.
+    fn ct_eq(&self, other: &Self) -> Choice;
+}
+
+/// Erasure of a value in memory, which the compiler can't optimize out.
+///
+/// Unlike `zeroize::Zeroize`, it is implemented for `Felt` and the types of the bindings.
+pub trait Zeroize {
+    /// Overwrites the value with zeros.
+    fn zeroize(&mut self);
+}
+
+/// Zeroizes a value whose bytes are only plain data, and for which zeros are a valid value.
+fn zeroize_flat<T: Copy>(value: &mut T) {
+    // SAFETY: `value` is a valid and aligned reference, and `T` is `Copy`: it has no destructor
+    // and owns no memory. The callers only pass types for which zeros are a valid value.
+    unsafe { zeroize::zeroize_flat_type(value as *mut T) };
+}
+
+macro_rules! impl_secret_integers {
+    ($($ty:ty),*) => {
+        $(
+            impl ConstantTimeEq for $ty {
+                fn ct_eq(&self, other: &Self) -> Choice {
+                    subtle::ConstantTimeEq::ct_eq(self, other)
+                }
+            }
+
+            impl Zeroize for $ty {
+                fn zeroize(&mut self) {
+                    zeroize::Zeroize::zeroize(self);
+                }
+            }
+        )*
+    };
+}
+
+impl_secret_integers!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128);
+
+impl ConstantTimeEq for bool {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        (*self as u8).ct_eq(&(*other as u8))
+    }
+}
+
+impl Zeroize for bool {
+    fn zeroize(&mut self) {
+        zeroize::Zeroize::zeroize(self);
+    }
+}
+
+impl ConstantTimeEq for Felt {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        subtle::ConstantTimeEq::ct_eq(&self.to_bytes_be()[..], &other.to_bytes_be()[..])
+    }
+}
+
+/// `Felt` is only made of integer limbs, and its zeroed limbs are `Felt::ZERO`.
+impl Zeroize for Felt {
+    fn zeroize(&mut self) {
+        zeroize_flat(self);
+    }
+}
+
+impl<T: ConstantTimeEq> ConstantTimeEq for Vec<T> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        if self.len() != other.len() {
+            return Choice::from(0);
+        }
+
+        self.iter()
+            .zip(other)
+            .fold(Choice::from(1), |acc, (x, y)| acc & x.ct_eq(y))
+    }
+}
+
+/// Zeroizes the items and the whole capacity of the vector, which is then empty.
+///
+/// As with `zeroize`, the buffers left by the previous reallocations of the vector
+/// (when it grew) are not zeroized: reserve the capacity of a secret vector upfront.
+impl<T: Zeroize> Zeroize for Vec<T> {
+    fn zeroize(&mut self) {
+        for item in self.iter_mut() {
+            item.zeroize();
+        }
+
+        self.clear();
+        zeroize::Zeroize::zeroize(self.spare_capacity_mut());
+    }
+}
+
+macro_rules! impl_secret_arrays {
+    ($($ty:ident),*) => {
+        $(
+            impl<T: ConstantTimeEq> ConstantTimeEq for $ty<T> {
+                fn ct_eq(&self, other: &Self) -> Choice {
+                    self.0.ct_eq(&other.0)
+                }
+            }
+
+            impl<T: Zeroize> Zeroize for $ty<T> {
+                fn zeroize(&mut self) {
+                    self.0.zeroize();
+                }
+            }
+        )*
+    };
+}
+
+impl_secret_arrays!(CairoSpan, CairoArrayLegacy);
+
+/// The adapter only marks the serialization of a field, and holds no value.
+impl<T, U> Zeroize for SerdeAdapter<T, U> {
+    fn zeroize(&mut self) {}
+}
+
+impl ConstantTimeEq for Bytes31 {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.felt().ct_eq(&other.felt())
+    }
+}
+
+/// `Bytes31` is a byte array, and its zeroed bytes are `Bytes31::default()`.
+impl Zeroize for Bytes31 {
+    fn zeroize(&mut self) {
+        zeroize_flat(self);
+    }
+}
+
+impl ConstantTimeEq for ByteArray {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        let data = self.data.ct_eq(&other.data);
+        let pending_word = self.pending_word.ct_eq(&other.pending_word);
+        let pending_word_len = self.pending_word_len.ct_eq(&other.pending_word_len);
+
+        data & pending_word & pending_word_len
+    }
+}
+
+impl Zeroize for ByteArray {
+    fn zeroize(&mut self) {
+        self.data.zeroize();
+        self.pending_word.zeroize();
+        self.pending_word_len.zeroize();
+    }
+}
+
+impl ConstantTimeEq for U256 {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.low.ct_eq(&other.low) & self.high.ct_eq(&other.high)
+    }
+}
+
+impl Zeroize for U256 {
+    fn zeroize(&mut self) {
+        self.low.zeroize();
+        self.high.zeroize();
+    }
+}
+
+macro_rules! impl_secret_felt_newtypes {
+    ($($ty:ty),*) => {
+        $(
+            impl ConstantTimeEq for $ty {
+                fn ct_eq(&self, other: &Self) -> Choice {
+                    self.0.ct_eq(&other.0)
+                }
+            }
+
+            impl Zeroize for $ty {
+                fn zeroize(&mut self) {
+                    self.0.zeroize();
+                }
+            }
+        )*
+    };
+}
+
+impl_secret_felt_newtypes!(ContractAddress, ClassHash, EthAddress);
+
+/// A secret value: redacted `Debug`, constant-time equality and zeroized on drop.
+///
+/// It is serialized (with `CairoSerde` and serde) exactly like the wrapped value,
+/// the serialized values are not protected.
+pub struct Secret<T: Zeroize>(T);
+
+impl<T: Zeroize> Secret<T> {
+    /// Wraps a secret value.
+    pub fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    /// Returns the secret value, to be used without being copied around.
+    pub fn expose_secret(&self) -> &T {
+        &self.0
+    }
+
+    /// Returns the secret value mutably.
+    pub fn expose_secret_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T: Zeroize> Drop for Secret<T> {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl<T: Zeroize> From<T> for Secret<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T: Zeroize> fmt::Debug for Secret<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Secret([REDACTED])")
+    }
+}
+
+impl<T: Zeroize + Clone> Clone for Secret<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T: Zeroize + Default> Default for Secret<T> {
+    fn default() -> Self {
+        Self(T::default())
+    }
+}
+
+impl<T: Zeroize + ConstantTimeEq> PartialEq for Secret<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.ct_eq(&other.0).into()
+    }
+}
+
+impl<T: Zeroize + ConstantTimeEq> Eq for Secret<T> {}
+
+impl<T: Zeroize + Serialize> Serialize for Secret<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de, T: Zeroize + Deserialize<'de>> Deserialize<'de> for Secret<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        T::deserialize(deserializer).map(Self)
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a, T: Zeroize + arbitrary::Arbitrary<'a>> arbitrary::Arbitrary<'a> for Secret<T> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        T::arbitrary(u).map(Self)
+    }
+}
+
+impl<T, RT> CairoSerde for Secret<T>
+where
+    T: CairoSerde<RustType = RT> + Zeroize,
+    RT: Zeroize,
+{
+    type RustType = Secret<RT>;
+
+    const SERIALIZED_SIZE: Option<usize> = T::SERIALIZED_SIZE;
+    const DYNAMIC: bool = T::DYNAMIC;
+
+    #[inline]
+    fn cairo_serialized_size(rust: &Self::RustType) -> usize {
+        T::cairo_serialized_size(&rust.0)
+    }
+
+    fn cairo_serialize(rust: &Self::RustType) -> Vec<Felt> {
+        T::cairo_serialize(&rust.0)
+    }
+
+    #[inline]
+    fn cairo_serialize_to(rust: &Self::RustType, out: &mut Vec<Felt>) {
+        T::cairo_serialize_to(&rust.0, out)
+    }
+
+    fn cairo_deserialize(felts: &[Felt], offset: usize) -> Result<Self::RustType> {
+        T::cairo_deserialize(felts, offset).map(Secret)
+    }
+
+    fn cairo_deserialize_with_size(
+        felts: &[Felt],
+        offset: usize,
+    ) -> Result<(Self::RustType, usize)> {
+        T::cairo_deserialize_with_size(felts, offset).map(|(value, size)| (Secret(value), size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(b"token", b"token"));
+        assert!(!constant_time_eq(b"token", b"tokem"));
+        assert!(!constant_time_eq(b"token", b"tokens"));
+        assert!(constant_time_eq(b"", b""));
+
+        let eq = |a: Choice| bool::from(a);
+        assert!(eq(Felt::TWO.ct_eq(&Felt::TWO)));
+        assert!(!eq(Felt::TWO.ct_eq(&Felt::THREE)));
+        assert!(eq(true.ct_eq(&true)));
+        assert!(!eq(true.ct_eq(&false)));
+        assert!(eq(vec![1u64, 2].ct_eq(&vec![1, 2])));
+        assert!(!eq(vec![1u64, 2].ct_eq(&vec![1, 3])));
+        assert!(!eq(vec![1u64, 2].ct_eq(&vec![1])));
+        assert!(eq(U256 { low: 1, high: 2 }.ct_eq(&U256 { low: 1, high: 2 })));
+        assert!(!eq(
+            U256 { low: 1, high: 2 }.ct_eq(&U256 { low: 1, high: 3 })
+        ));
+
+        let a = ByteArray::from_string("session").unwrap();
+        assert!(eq(a.ct_eq(&ByteArray::from_string("session").unwrap())));
+        assert!(!eq(a.ct_eq(&ByteArray::from_string("sessioN").unwrap())));
+    }
+
+    #[test]
+    fn test_zeroize() {
+        let mut felt = Felt::from(42_u32);
+        felt.zeroize();
+        assert_eq!(felt, Felt::ZERO);
+
+        let mut felt = Felt::MAX;
+        felt.zeroize();
+        assert_eq!(felt, Felt::ZERO);
+
+        let mut felts = vec![Felt::ONE, Felt::TWO];
+        felts.zeroize();
+        assert!(felts.is_empty());
+
+        let mut bytes = ByteArray::from_string("a session token longer than 31 bytes").unwrap();
+        bytes.zeroize();
+        assert_eq!(bytes, ByteArray::default());
+    }
+
+    #[test]
+    fn test_secret() {
+        let secret = Secret::new(Felt::from(0x1234_u32));
+
+        assert_eq!(format!("{:?}", secret), "Secret([REDACTED])");
+        assert_eq!(secret, Secret::from(Felt::from(0x1234_u32)));
+        assert_ne!(secret, Secret::new(Felt::ONE));
+        assert_eq!(*secret.expose_secret(), Felt::from(0x1234_u32));
+
+        assert_eq!(
+            serde_json::to_string(&secret).unwrap(),
+            serde_json::to_string(&Felt::from(0x1234_u32)).unwrap()
+        );
+        let json = serde_json::to_string(&secret).unwrap();
+        assert_eq!(serde_json::from_str::<Secret<Felt>>(&json).unwrap(), secret);
+    }
+
+    #[test]
+    fn test_secret_cairo_serde() {
+        let secret = Secret::new(Felt::THREE);
+        let felts = Secret::<Felt>::cairo_serialize(&secret);
+        assert_eq!(felts, vec![Felt::THREE]);
+        assert_eq!(Secret::<Felt>::SERIALIZED_SIZE, Some(1));
+        assert_eq!(
+            Secret::<Felt>::cairo_deserialize(&felts, 0).unwrap(),
+            secret
+        );
+
+        let signature = Secret::new(CairoSpan(vec![Felt::ONE, Felt::TWO]));
+        let felts = Secret::<CairoSpan<Felt>>::cairo_serialize(&signature);
+        assert_eq!(felts, vec![Felt::TWO, Felt::ONE, Felt::TWO]);
+        assert_eq!(
+            Secret::<CairoSpan<Felt>>::cairo_deserialize_with_size(&felts, 0).unwrap(),
+            (signature, 3)
+        );
+    }
+}
@@ -0,0 +1,179 @@
+//! Dedicated struct for the cairo `Span<T>`, distinct from the `Array<T>` mapped to `Vec<T>`.
+//!
+//! Both are serialized the same way, the length followed by the elements.
+use std::ops::{Deref, DerefMut};
+
+use crate::{CairoSerde, Result};
+use starknet::core::types::Felt;
+
+/// A cairo `Span<T>`, which is a read-only view over an array.
+///
+/// The bindings take the `Span<T>` inputs of the functions as slices (`&[T]`),
+/// which are serialized with [`CairoSpan::cairo_serialize_slice_to`] without
+/// being copied into a `CairoSpan`.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Default,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[serde(transparent)]
+pub struct CairoSpan<T>(pub Vec<T>);
+
+impl<T> CairoSpan<T> {
+    pub fn into_inner(self) -> Vec<T> {
+        self.0
+    }
+}
+
+impl<T: Clone> CairoSpan<T> {
+    pub fn from_slice(slice: &[T]) -> Self {
+        Self(slice.to_vec())
+    }
+}
+
+impl<T> Deref for CairoSpan<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for CairoSpan<T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        &mut self.0
+    }
+}
+
+impl<T> From<Vec<T>> for CairoSpan<T> {
+    fn from(value: Vec<T>) -> Self {
+        Self(value)
+    }
+}
+
+impl<T> From<CairoSpan<T>> for Vec<T> {
+    fn from(value: CairoSpan<T>) -> Self {
+        value.0
+    }
+}
+
+impl<T> FromIterator<T> for CairoSpan<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl<T> IntoIterator for CairoSpan<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a CairoSpan<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl<T: PartialEq> PartialEq<Vec<T>> for CairoSpan<T> {
+    fn eq(&self, other: &Vec<T>) -> bool {
+        &self.0 == other
+    }
+}
+
+impl<T, RT> CairoSpan<T>
+where
+    T: CairoSerde<RustType = RT>,
+{
+    /// Serializes a slice as a `Span<T>` at the end of `out`.
+    ///
+    /// # Arguments
+    ///
+    /// * `rust` - The elements of the span.
+    /// * `out` - The buffer to serialize into.
+    pub fn cairo_serialize_slice_to(rust: &[RT], out: &mut Vec<Felt>) {
+        out.push(rust.len().into());
+        rust.iter().for_each(|r| T::cairo_serialize_to(r, out));
+    }
+}
+
+impl<T, RT> CairoSerde for CairoSpan<T>
+where
+    T: CairoSerde<RustType = RT>,
+{
+    type RustType = CairoSpan<RT>;
+
+    const SERIALIZED_SIZE: Option<usize> = None;
+
+    #[inline]
+    fn cairo_serialized_size(rust: &Self::RustType) -> usize {
+        Vec::<T>::cairo_serialized_size(&rust.0)
+    }
+
+    fn cairo_serialize(rust: &Self::RustType) -> Vec<Felt> {
+        let mut out: Vec<Felt> = vec![];
+        Self::cairo_serialize_to(rust, &mut out);
+        out
+    }
+
+    #[inline]
+    fn cairo_serialize_to(rust: &Self::RustType, out: &mut Vec<Felt>) {
+        Self::cairo_serialize_slice_to(&rust.0, out);
+    }
+
+    fn cairo_deserialize(felts: &[Felt], offset: usize) -> Result<Self::RustType> {
+        Vec::<T>::cairo_deserialize(felts, offset).map(CairoSpan)
+    }
+
+    #[inline]
+    fn cairo_deserialize_with_size(
+        felts: &[Felt],
+        offset: usize,
+    ) -> Result<(Self::RustType, usize)> {
+        Vec::<T>::cairo_deserialize_with_size(felts, offset)
+            .map(|(out, size)| (CairoSpan(out), size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::U256;
+
+    #[test]
+    fn test_serialize_span() {
+        let span = CairoSpan(vec![U256 { low: 1, high: 2 }, U256 { low: 3, high: 4 }]);
+        let felts = CairoSpan::<U256>::cairo_serialize(&span);
+
+        assert_eq!(felts, Vec::<U256>::cairo_serialize(&span.0));
+
+        let mut from_slice = vec![];
+        CairoSpan::<U256>::cairo_serialize_slice_to(&span, &mut from_slice);
+        assert_eq!(from_slice, felts);
+
+        assert_eq!(
+            CairoSpan::<U256>::cairo_deserialize_with_size(&felts, 0).unwrap(),
+            (span, 5)
+        );
+    }
+
+    #[test]
+    fn test_deserialize_span_too_short() {
+        let felts = vec![Felt::TWO, Felt::ONE];
+        assert!(CairoSpan::<Felt>::cairo_deserialize(&felts, 0).is_err());
+    }
+}
@@ -6,6 +6,7 @@ use starknet::core::types::Felt;
 
 /// ContractAddress.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ContractAddress(pub Felt);
 
 impl From<Felt> for ContractAddress {
@@ -41,6 +42,7 @@ impl CairoSerde for ContractAddress {
 
 /// ClassHash.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ClassHash(pub Felt);
 
 impl From<Felt> for ClassHash {
@@ -78,6 +80,15 @@ impl CairoSerde for ClassHash {
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, serde::Serialize, serde::Deserialize)]
 pub struct EthAddress(pub Felt);
 
+/// An Ethereum address is 20 bytes long, hence only the lower bytes of the felt are set.
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for EthAddress {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let bytes: [u8; 20] = u.arbitrary()?;
+        Ok(Self(Felt::from_bytes_be_slice(&bytes)))
+    }
+}
+
 impl From<Felt> for EthAddress {
     fn from(item: Felt) -> Self {
         Self(item)
@@ -175,4 +186,15 @@ mod tests {
         let eth_address = EthAddress::from(Felt::from(1_u32));
         assert_eq!(eth_address, EthAddress(Felt::from(1_u32)))
     }
+
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn test_arbitrary_eth_address() {
+        use arbitrary::{Arbitrary, Unstructured};
+
+        let mut u = Unstructured::new(&[0xff; 32]);
+        let address = EthAddress::arbitrary(&mut u).unwrap();
+
+        assert_eq!(address.0, Felt::from_bytes_be_slice(&[0xff; 20]));
+    }
 }
@@ -5,6 +5,8 @@ use starknet::core::types::Felt;
 impl CairoSerde for () {
     type RustType = Self;
 
+    const SERIALIZED_SIZE: Option<usize> = Some(0);
+
     #[inline]
     fn cairo_serialized_size(_rust: &Self::RustType) -> usize {
         0
@@ -14,6 +16,9 @@ impl CairoSerde for () {
         vec![]
     }
 
+    #[inline]
+    fn cairo_serialize_to(_rust: &Self::RustType, _out: &mut Vec<Felt>) {}
+
     fn cairo_deserialize(_felts: &[Felt], _offset: usize) -> Result<Self::RustType> {
         Ok(())
     }
@@ -41,24 +46,27 @@ macro_rules! impl_tuples {
 
             fn cairo_serialize(rust: &Self::RustType) -> Vec<Felt> {
                 let mut out: Vec<Felt> = vec![];
-
-                $( out.extend($ty::cairo_serialize(& rust.$no)); )*
-
+                Self::cairo_serialize_to(rust, &mut out);
                 out
             }
 
+            fn cairo_serialize_to(rust: &Self::RustType, out: &mut Vec<Felt>) {
+                $( $ty::cairo_serialize_to(& rust.$no, out); )*
+            }
+
             fn cairo_deserialize(felts: &[Felt], offset: usize) -> Result<Self::RustType> {
-                let mut offset = offset;
+                Self::cairo_deserialize_with_size(felts, offset).map(|(out, _)| out)
+            }
+
+            fn cairo_deserialize_with_size(felts: &[Felt], offset: usize) -> Result<(Self::RustType, usize)> {
+                let mut size = 0;
 
                 $(
-                    let $var : $rt = $ty::cairo_deserialize(felts, offset)?;
-                    offset += $ty::cairo_serialized_size(& $var);
+                    let $var : ($rt, usize) = $ty::cairo_deserialize_with_size(felts, offset + size)?;
+                    size += $var.1;
                 )*
 
-                // Remove warning.
-                let _offset = offset;
-
-                Ok(($( $var ),*))
+                Ok((($( $var.0 ),*), size))
             }
         }
     }
@@ -68,6 +76,17 @@ impl_tuples!(2, A:RA:r0:0, B:RB:r1:1);
 impl_tuples!(3, A:RA:r0:0, B:RB:r1:1, C:RC:r2:2);
 impl_tuples!(4, A:RA:r0:0, B:RB:r1:1, C:RC:r2:2, D:RD:r3:3);
 impl_tuples!(5, A:RA:r0:0, B:RB:r1:1, C:RC:r2:2, D:RD:r3:3, E:RE:r4:4);
+impl_tuples!(6, A:RA:r0:0, B:RB:r1:1, C:RC:r2:2, D:RD:r3:3, E:RE:r4:4, F:RF:r5:5);
+impl_tuples!(7, A:RA:r0:0, B:RB:r1:1, C:RC:r2:2, D:RD:r3:3, E:RE:r4:4, F:RF:r5:5, G:RG:r6:6);
+impl_tuples!(8, A:RA:r0:0, B:RB:r1:1, C:RC:r2:2, D:RD:r3:3, E:RE:r4:4, F:RF:r5:5, G:RG:r6:6, H:RH:r7:7);
+impl_tuples!(9, A:RA:r0:0, B:RB:r1:1, C:RC:r2:2, D:RD:r3:3, E:RE:r4:4, F:RF:r5:5, G:RG:r6:6, H:RH:r7:7, I:RI:r8:8);
+impl_tuples!(10, A:RA:r0:0, B:RB:r1:1, C:RC:r2:2, D:RD:r3:3, E:RE:r4:4, F:RF:r5:5, G:RG:r6:6, H:RH:r7:7, I:RI:r8:8, J:RJ:r9:9);
+impl_tuples!(11, A:RA:r0:0, B:RB:r1:1, C:RC:r2:2, D:RD:r3:3, E:RE:r4:4, F:RF:r5:5, G:RG:r6:6, H:RH:r7:7, I:RI:r8:8, J:RJ:r9:9, K:RK:r10:10);
+impl_tuples!(12, A:RA:r0:0, B:RB:r1:1, C:RC:r2:2, D:RD:r3:3, E:RE:r4:4, F:RF:r5:5, G:RG:r6:6, H:RH:r7:7, I:RI:r8:8, J:RJ:r9:9, K:RK:r10:10, L:RL:r11:11);
+impl_tuples!(13, A:RA:r0:0, B:RB:r1:1, C:RC:r2:2, D:RD:r3:3, E:RE:r4:4, F:RF:r5:5, G:RG:r6:6, H:RH:r7:7, I:RI:r8:8, J:RJ:r9:9, K:RK:r10:10, L:RL:r11:11, M:RM:r12:12);
+impl_tuples!(14, A:RA:r0:0, B:RB:r1:1, C:RC:r2:2, D:RD:r3:3, E:RE:r4:4, F:RF:r5:5, G:RG:r6:6, H:RH:r7:7, I:RI:r8:8, J:RJ:r9:9, K:RK:r10:10, L:RL:r11:11, M:RM:r12:12, N:RN:r13:13);
+impl_tuples!(15, A:RA:r0:0, B:RB:r1:1, C:RC:r2:2, D:RD:r3:3, E:RE:r4:4, F:RF:r5:5, G:RG:r6:6, H:RH:r7:7, I:RI:r8:8, J:RJ:r9:9, K:RK:r10:10, L:RL:r11:11, M:RM:r12:12, N:RN:r13:13, O:RO:r14:14);
+impl_tuples!(16, A:RA:r0:0, B:RB:r1:1, C:RC:r2:2, D:RD:r3:3, E:RE:r4:4, F:RF:r5:5, G:RG:r6:6, H:RH:r7:7, I:RI:r8:8, J:RJ:r9:9, K:RK:r10:10, L:RL:r11:11, M:RM:r12:12, N:RN:r13:13, O:RO:r14:14, P:RP:r15:15);
 
 #[cfg(test)]
 mod tests {
@@ -109,4 +128,64 @@ mod tests {
         assert_eq!(vals.0, vec![Felt::ONE]);
         assert_eq!(vals.1, 99_u32);
     }
+
+    #[test]
+    fn test_serialize_deserialize_tuple16() {
+        type T16 = (
+            Felt,
+            u8,
+            u16,
+            u32,
+            u64,
+            u128,
+            bool,
+            Vec<Felt>,
+            Felt,
+            u8,
+            u16,
+            u32,
+            u64,
+            u128,
+            bool,
+            (u8, u8),
+        );
+
+        let v: T16 = (
+            Felt::ONE,
+            2,
+            3,
+            4,
+            5,
+            6,
+            true,
+            vec![Felt::TWO, Felt::THREE],
+            Felt::from(7),
+            8,
+            9,
+            10,
+            11,
+            12,
+            false,
+            (13, 14),
+        );
+
+        let felts = T16::cairo_serialize(&v);
+        assert_eq!(felts.len(), 19);
+        assert_eq!(T16::cairo_serialized_size(&v), 19);
+        assert_eq!(felts[7], Felt::TWO);
+        assert_eq!(felts[18], Felt::from(14));
+
+        // Std traits like `PartialEq` are only implemented for tuples up to 12 elements.
+        let d = T16::cairo_deserialize(&felts, 0).unwrap();
+        assert_eq!(
+            (d.0, d.1, d.2, d.3, d.4, d.5, d.6),
+            (v.0, v.1, v.2, v.3, v.4, v.5, v.6)
+        );
+        assert_eq!(d.7, v.7);
+        assert_eq!(
+            (d.8, d.9, d.10, d.11, d.12, d.13, d.14, d.15),
+            (v.8, v.9, v.10, v.11, v.12, v.13, v.14, v.15)
+        );
+        assert!(T16::cairo_deserialize(&felts[..18], 0).is_err());
+    }
 }
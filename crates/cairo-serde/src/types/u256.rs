@@ -1,15 +1,16 @@
 use crate::CairoSerde;
-use num_bigint::{BigInt, BigUint, ParseBigIntError};
+use num_bigint::{BigUint, ParseBigIntError};
 use serde_with::{DeserializeAs, DisplayFromStr, SerializeAs};
 use starknet::core::types::Felt;
 use std::{
     cmp::Ordering,
     fmt::Display,
-    ops::{Add, BitOr, Sub},
+    ops::{Add, BitOr, Div, Mul, Rem, Sub},
     str::FromStr,
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct U256 {
     pub low: u128,
     pub high: u128,
@@ -17,22 +18,24 @@ pub struct U256 {
 
 impl PartialOrd for U256 {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(match self.high.cmp(&other.high) {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for U256 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match self.high.cmp(&other.high) {
             Ordering::Equal => self.low.cmp(&other.low),
             ordering => ordering,
-        })
+        }
     }
 }
 
+/// Wraps on overflow, see [`U256::checked_add`] to detect it.
 impl Add for U256 {
     type Output = Self;
-    fn add(mut self, other: Self) -> Self {
-        let (low, overflow_low) = self.low.overflowing_add(other.low);
-        if overflow_low {
-            self.high += 1;
-        }
-        let (high, _overflow_high) = self.high.overflowing_add(other.high);
-        U256 { low, high }
+    fn add(self, other: Self) -> Self {
+        self.wrapping_add(other)
     }
 }
 
@@ -59,6 +62,28 @@ impl Sub for U256 {
     }
 }
 
+impl Mul for U256 {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        self.checked_mul(other)
+            .expect("U256 multiplication overflow")
+    }
+}
+
+impl Div for U256 {
+    type Output = Self;
+    fn div(self, other: Self) -> Self {
+        self.checked_div(other).expect("U256 division by zero")
+    }
+}
+
+impl Rem for U256 {
+    type Output = Self;
+    fn rem(self, other: Self) -> Self {
+        self.checked_rem(other).expect("U256 division by zero")
+    }
+}
+
 impl BitOr for U256 {
     type Output = Self;
 
@@ -72,38 +97,90 @@ impl BitOr for U256 {
 
 impl Display for U256 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut num = BigUint::from(0u128);
-        num += BigUint::from(self.high);
-        num <<= 128;
-        num += BigUint::from(self.low);
-        write!(f, "{}", num)
+        write!(f, "{}", BigUint::from(*self))
     }
 }
 
+/// Parses a decimal string, the bits above the 256 first ones being ignored.
+/// See the `TryFrom<BigUint>` conversion to reject the values out of range.
 impl FromStr for U256 {
     type Err = ParseBigIntError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let num = BigInt::from_str(s)?;
-        let num_big_uint = num.to_biguint().unwrap();
-        let mask = (BigUint::from(1u128) << 128u32) - BigUint::from(1u128);
-        let b_low: BigUint = (num_big_uint.clone() >> 0) & mask.clone();
-        let b_high: BigUint = (num_big_uint.clone() >> 128) & mask.clone();
+        Ok(U256::from_biguint_wrapping(&BigUint::from_str(s)?))
+    }
+}
 
-        let mut low = 0;
-        let mut high = 0;
+impl From<U256> for BigUint {
+    fn from(value: U256) -> Self {
+        (BigUint::from(value.high) << 128) + BigUint::from(value.low)
+    }
+}
 
-        for (i, digit) in b_low.to_u64_digits().iter().take(2).enumerate() {
-            low |= (*digit as u128) << (i * 64);
+impl TryFrom<BigUint> for U256 {
+    type Error = ValueOutOfRangeError;
+    fn try_from(value: BigUint) -> Result<Self, Self::Error> {
+        if value.bits() > 256 {
+            return Err(ValueOutOfRangeError);
         }
 
-        for (i, digit) in b_high.to_u64_digits().iter().take(2).enumerate() {
-            high |= (*digit as u128) << (i * 64);
-        }
+        Ok(U256::from_biguint_wrapping(&value))
+    }
+}
 
-        Ok(U256 { low, high })
+#[cfg(feature = "primitive-types")]
+impl From<U256> for primitive_types::U256 {
+    fn from(value: U256) -> Self {
+        primitive_types::U256([
+            value.low as u64,
+            (value.low >> 64) as u64,
+            value.high as u64,
+            (value.high >> 64) as u64,
+        ])
+    }
+}
+
+#[cfg(feature = "primitive-types")]
+impl From<primitive_types::U256> for U256 {
+    fn from(value: primitive_types::U256) -> Self {
+        let [l0, l1, h0, h1] = value.0;
+
+        U256 {
+            low: ((l1 as u128) << 64) | l0 as u128,
+            high: ((h1 as u128) << 64) | h0 as u128,
+        }
     }
 }
 
+/// Conversion and comparison with the unsigned integers.
+macro_rules! impl_u256_from_uint {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for U256 {
+                fn from(value: $t) -> Self {
+                    U256 {
+                        low: value as u128,
+                        high: 0,
+                    }
+                }
+            }
+
+            impl PartialEq<$t> for U256 {
+                fn eq(&self, other: &$t) -> bool {
+                    *self == U256::from(*other)
+                }
+            }
+
+            impl PartialOrd<$t> for U256 {
+                fn partial_cmp(&self, other: &$t) -> Option<Ordering> {
+                    Some(self.cmp(&U256::from(*other)))
+                }
+            }
+        )*
+    };
+}
+
+impl_u256_from_uint!(u8, u16, u32, u64, u128, usize);
+
 impl serde::Serialize for U256 {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -197,6 +274,92 @@ impl U256 {
     }
 }
 
+/// Arithmetic, the `checked_*` methods returning `None` on overflow
+/// (or division by zero), the `wrapping_*` ones wrapping around 2^256.
+impl U256 {
+    pub const ZERO: U256 = U256 { low: 0, high: 0 };
+    pub const ONE: U256 = U256 { low: 1, high: 0 };
+    pub const MAX: U256 = U256 {
+        low: u128::MAX,
+        high: u128::MAX,
+    };
+
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        let (low, carry) = self.low.overflowing_add(other.low);
+        let high = self
+            .high
+            .checked_add(other.high)?
+            .checked_add(carry as u128)?;
+        Some(U256 { low, high })
+    }
+
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        let (low, borrow) = self.low.overflowing_sub(other.low);
+        let high = self
+            .high
+            .checked_sub(other.high)?
+            .checked_sub(borrow as u128)?;
+        Some(U256 { low, high })
+    }
+
+    pub fn checked_mul(self, other: Self) -> Option<Self> {
+        U256::try_from(BigUint::from(self) * BigUint::from(other)).ok()
+    }
+
+    pub fn checked_div(self, other: Self) -> Option<Self> {
+        if other == U256::ZERO {
+            return None;
+        }
+
+        Some(U256::from_biguint_wrapping(
+            &(BigUint::from(self) / BigUint::from(other)),
+        ))
+    }
+
+    pub fn checked_rem(self, other: Self) -> Option<Self> {
+        if other == U256::ZERO {
+            return None;
+        }
+
+        Some(U256::from_biguint_wrapping(
+            &(BigUint::from(self) % BigUint::from(other)),
+        ))
+    }
+
+    pub fn wrapping_add(self, other: Self) -> Self {
+        let (low, carry) = self.low.overflowing_add(other.low);
+        let high = self
+            .high
+            .wrapping_add(other.high)
+            .wrapping_add(carry as u128);
+        U256 { low, high }
+    }
+
+    pub fn wrapping_sub(self, other: Self) -> Self {
+        let (low, borrow) = self.low.overflowing_sub(other.low);
+        let high = self
+            .high
+            .wrapping_sub(other.high)
+            .wrapping_sub(borrow as u128);
+        U256 { low, high }
+    }
+
+    pub fn wrapping_mul(self, other: Self) -> Self {
+        U256::from_biguint_wrapping(&(BigUint::from(self) * BigUint::from(other)))
+    }
+
+    /// Returns the 256 lowest bits of the value.
+    fn from_biguint_wrapping(value: &BigUint) -> Self {
+        let digits = value.to_u64_digits();
+        let digit = |i: usize| digits.get(i).copied().unwrap_or(0) as u128;
+
+        U256 {
+            low: (digit(1) << 64) | digit(0),
+            high: (digit(3) << 64) | digit(2),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -466,4 +629,100 @@ mod tests {
         };
         assert!(u256_1 < u256_2);
     }
+
+    #[test]
+    fn test_checked_add_sub() {
+        let a = U256 {
+            low: u128::MAX,
+            high: 1,
+        };
+
+        assert_eq!(a.checked_add(U256::ONE), Some(U256 { low: 0, high: 2 }));
+        assert_eq!(U256::MAX.checked_add(U256::ONE), None);
+        assert_eq!(U256 { low: 0, high: 2 }.checked_sub(U256::ONE), Some(a));
+        assert_eq!(U256::ZERO.checked_sub(U256::ONE), None);
+    }
+
+    #[test]
+    fn test_wrapping_add_sub_mul() {
+        assert_eq!(U256::MAX.wrapping_add(U256::from(2_u8)), U256::ONE);
+        assert_eq!(U256::MAX + U256::ONE, U256::ZERO);
+        assert_eq!(U256::ZERO.wrapping_sub(U256::ONE), U256::MAX);
+        assert_eq!(
+            U256::MAX.wrapping_mul(U256::from(2_u8)),
+            U256::MAX - U256::ONE
+        );
+    }
+
+    #[test]
+    fn test_mul_div_rem() {
+        let a = U256::from(u128::MAX);
+        let b = U256::from(3_u8);
+
+        let product = a * b;
+        assert_eq!(
+            product,
+            U256 {
+                low: u128::MAX - 2,
+                high: 2,
+            }
+        );
+        assert_eq!(product / b, a);
+        assert_eq!(product % a, U256::ZERO);
+        assert_eq!(U256::from(10_u8) % b, 1_u8);
+
+        assert_eq!(U256::MAX.checked_mul(b), None);
+        assert_eq!(a.checked_div(U256::ZERO), None);
+        assert_eq!(a.checked_rem(U256::ZERO), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "U256 division by zero")]
+    fn test_div_by_zero() {
+        let _ = U256::ONE / U256::ZERO;
+    }
+
+    #[test]
+    fn test_compare_with_uint() {
+        assert_eq!(U256::from(12_u32), 12_u32);
+        assert!(U256::from(12_u64) > 11_u64);
+        assert!(U256 { low: 0, high: 1 } > u128::MAX);
+        assert!(U256::ZERO < 1_usize);
+    }
+
+    #[test]
+    fn test_biguint_conversions() {
+        let a = U256 { low: 7, high: 5 };
+        let big = BigUint::from(a);
+
+        assert_eq!(big, (BigUint::from(5_u8) << 128) + BigUint::from(7_u8));
+        assert_eq!(U256::try_from(big).unwrap(), a);
+        assert_eq!(U256::try_from(BigUint::from(U256::MAX)).unwrap(), U256::MAX);
+        assert!(U256::try_from(BigUint::from(1_u8) << 256_u32).is_err());
+    }
+
+    #[test]
+    fn test_from_str_invalid() {
+        assert_eq!(U256::from_str(&U256::MAX.to_string()).unwrap(), U256::MAX);
+        assert!(U256::from_str("-1").is_err());
+        assert!(U256::from_str("1a").is_err());
+        assert_eq!(
+            U256::from_str(&((BigUint::from(1_u8) << 256_u32) + 1_u8).to_string()).unwrap(),
+            U256::ONE
+        );
+    }
+
+    #[cfg(feature = "primitive-types")]
+    #[test]
+    fn test_primitive_types_conversions() {
+        let a = U256 {
+            low: (2 << 64) | 1,
+            high: (4 << 64) | 3,
+        };
+        let p = primitive_types::U256::from(a);
+
+        assert_eq!(p.0, [1, 2, 3, 4]);
+        assert_eq!(U256::from(p), a);
+        assert_eq!(p.to_string(), a.to_string());
+    }
 }
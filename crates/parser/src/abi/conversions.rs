@@ -48,7 +48,7 @@ impl TryFrom<&AbiStruct> for Token {
             if !c.generic_args.is_empty() {
                 let mut token = Token::Composite(c.clone());
                 for (g_name, g_token) in c.generic_args.iter() {
-                    token = token.resolve_generic(g_name, &g_token.type_path());
+                    token = token.resolve_generic(g_name, &g_token.type_path_with_generics());
                 }
 
                 return Ok(token);
@@ -85,7 +85,7 @@ impl TryFrom<&AbiEnum> for Token {
             if !c.generic_args.is_empty() {
                 let mut token = Token::Composite(c.clone());
                 for (g_name, g_token) in c.generic_args.iter() {
-                    token = token.resolve_generic(g_name, &g_token.type_path());
+                    token = token.resolve_generic(g_name, &g_token.type_path_with_generics());
                 }
 
                 return Ok(token);
@@ -123,7 +123,7 @@ impl TryFrom<&AbiEventStruct> for Token {
             if !c.generic_args.is_empty() {
                 let mut token = Token::Composite(c.clone());
                 for (g_name, g_token) in c.generic_args.iter() {
-                    token = token.resolve_generic(g_name, &g_token.type_path());
+                    token = token.resolve_generic(g_name, &g_token.type_path_with_generics());
                 }
 
                 return Ok(token);
@@ -161,7 +161,7 @@ impl TryFrom<&AbiEventEnum> for Token {
             if !c.generic_args.is_empty() {
                 let mut token = Token::Composite(c.clone());
                 for (g_name, g_token) in c.generic_args.iter() {
-                    token = token.resolve_generic(g_name, &g_token.type_path());
+                    token = token.resolve_generic(g_name, &g_token.type_path_with_generics());
                 }
 
                 return Ok(token);
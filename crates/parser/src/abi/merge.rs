@@ -0,0 +1,250 @@
+//! Merging of several ABIs into a single one.
+//!
+//! A typical use case is a proxy contract, where the bindings must expose
+//! the entrypoints of both the proxy and the implementation.
+use starknet::core::types::contract::{AbiEntry, AbiEvent, TypedAbiEvent};
+use std::collections::HashMap;
+
+use super::parser::AbiParser;
+
+/// An entry defined differently by two of the merged ABIs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AbiMergeConflict {
+    /// Kind of the entry (`struct`, `function`, ...).
+    pub kind: String,
+    /// Name of the entry. For an interface item, the name is prefixed by the interface name.
+    pub name: String,
+    /// Index of the ABI whose definition is kept.
+    pub kept: usize,
+    /// Index of the ABI whose definition is discarded.
+    pub discarded: usize,
+}
+
+/// The result of an ABI merge.
+#[derive(Debug, Clone)]
+pub struct MergedAbi {
+    /// The merged ABI entries, without duplicates.
+    pub entries: Vec<AbiEntry>,
+    /// The conflicts found during the merge.
+    pub conflicts: Vec<AbiMergeConflict>,
+}
+
+impl AbiParser {
+    /// Merges several ABIs into a single one.
+    ///
+    /// Entries are identified by their kind and name. An entry defined identically
+    /// in several ABIs is only kept once. Interfaces with the same name have their
+    /// items merged. When two definitions differ, the first one is kept and a
+    /// conflict is reported.
+    ///
+    /// # Arguments
+    ///
+    /// * `abis` - The ABIs to merge, in order of precedence.
+    pub fn merge_abis(abis: &[Vec<AbiEntry>]) -> MergedAbi {
+        // Merged entries, with the index of the ABI they come from.
+        let mut merged = vec![];
+        let mut conflicts = vec![];
+
+        for (source, entries) in abis.iter().enumerate() {
+            merged = merge_entries(merged, entries, source, None, &mut conflicts);
+        }
+
+        MergedAbi {
+            entries: merged.into_iter().map(|(e, _)| e).collect(),
+            conflicts,
+        }
+    }
+}
+
+/// Merges `entries` coming from the ABI `source` into the already merged entries.
+fn merge_entries(
+    mut merged: Vec<(AbiEntry, usize)>,
+    entries: &[AbiEntry],
+    source: usize,
+    interface: Option<&str>,
+    conflicts: &mut Vec<AbiMergeConflict>,
+) -> Vec<(AbiEntry, usize)> {
+    let mut index: HashMap<(&'static str, String), usize> = merged
+        .iter()
+        .enumerate()
+        .map(|(i, (e, _))| (entry_key(e), i))
+        .collect();
+
+    for entry in entries {
+        let key = entry_key(entry);
+
+        let Some(&i) = index.get(&key) else {
+            index.insert(key, merged.len());
+            merged.push((entry.clone(), source));
+            continue;
+        };
+
+        let (existing, kept) = &mut merged[i];
+
+        if let (AbiEntry::Interface(existing), AbiEntry::Interface(other)) = (&mut *existing, entry)
+        {
+            let items = existing.items.iter().map(|e| (e.clone(), *kept)).collect();
+            existing.items =
+                merge_entries(items, &other.items, source, Some(&other.name), conflicts)
+                    .into_iter()
+                    .map(|(e, _)| e)
+                    .collect();
+            continue;
+        }
+
+        if !same_entry(existing, entry) {
+            let name = match interface {
+                Some(interface) => format!("{}::{}", interface, key.1),
+                None => key.1,
+            };
+
+            conflicts.push(AbiMergeConflict {
+                kind: key.0.to_string(),
+                name,
+                kept: *kept,
+                discarded: source,
+            });
+        }
+    }
+
+    merged
+}
+
+/// Returns the kind and the name identifying the entry.
+pub(super) fn entry_key(entry: &AbiEntry) -> (&'static str, String) {
+    match entry {
+        AbiEntry::Function(f) => ("function", f.name.clone()),
+        AbiEntry::L1Handler(f) => ("l1_handler", f.name.clone()),
+        AbiEntry::Struct(s) => ("struct", s.name.clone()),
+        AbiEntry::Enum(e) => ("enum", e.name.clone()),
+        // A contract has at most one constructor, whatever its name.
+        AbiEntry::Constructor(_) => ("constructor", "constructor".to_string()),
+        AbiEntry::Impl(i) => ("impl", i.name.clone()),
+        AbiEntry::Interface(i) => ("interface", i.name.clone()),
+        AbiEntry::Event(AbiEvent::Typed(TypedAbiEvent::Struct(s))) => ("event", s.name.clone()),
+        AbiEntry::Event(AbiEvent::Typed(TypedAbiEvent::Enum(e))) => ("event", e.name.clone()),
+        AbiEntry::Event(AbiEvent::Untyped(e)) => ("event", e.name.clone()),
+    }
+}
+
+/// ABI entries don't implement `PartialEq`, their JSON representations are compared instead.
+fn same_entry(a: &AbiEntry, b: &AbiEntry) -> bool {
+    match (serde_json::to_value(a), serde_json::to_value(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries(abi: &str) -> Vec<AbiEntry> {
+        AbiParser::parse_abi_string(abi).unwrap()
+    }
+
+    fn names(entries: &[AbiEntry]) -> Vec<String> {
+        entries.iter().map(|e| entry_key(e).1).collect()
+    }
+
+    const PROXY: &str = r#"[
+        { "type": "struct", "name": "pkg::Info", "members": [{ "name": "a", "type": "core::felt252" }] },
+        { "type": "function", "name": "upgrade", "inputs": [], "outputs": [], "state_mutability": "external" },
+        { "type": "interface", "name": "pkg::IContract", "items": [
+            { "type": "function", "name": "get_info", "inputs": [], "outputs": [{ "type": "pkg::Info" }], "state_mutability": "view" }
+        ]},
+        { "type": "constructor", "name": "constructor", "inputs": [] }
+    ]"#;
+
+    const IMPL: &str = r#"[
+        { "type": "struct", "name": "pkg::Info", "members": [{ "name": "a", "type": "core::felt252" }] },
+        { "type": "struct", "name": "pkg::Other", "members": [] },
+        { "type": "interface", "name": "pkg::IContract", "items": [
+            { "type": "function", "name": "get_info", "inputs": [], "outputs": [{ "type": "pkg::Info" }], "state_mutability": "view" },
+            { "type": "function", "name": "set_info", "inputs": [{ "name": "info", "type": "pkg::Info" }], "outputs": [], "state_mutability": "external" }
+        ]},
+        { "type": "constructor", "name": "constructor", "inputs": [{ "name": "owner", "type": "core::felt252" }] }
+    ]"#;
+
+    #[test]
+    fn test_merge_dedup() {
+        let merged = AbiParser::merge_abis(&[entries(PROXY), entries(IMPL)]);
+
+        assert_eq!(
+            names(&merged.entries),
+            vec![
+                "pkg::Info",
+                "upgrade",
+                "pkg::IContract",
+                "constructor",
+                "pkg::Other"
+            ]
+        );
+
+        let AbiEntry::Interface(interface) = &merged.entries[2] else {
+            panic!("Interface expected");
+        };
+        assert_eq!(names(&interface.items), vec!["get_info", "set_info"]);
+
+        // Only the constructors differ.
+        assert_eq!(
+            merged.conflicts,
+            vec![AbiMergeConflict {
+                kind: "constructor".to_string(),
+                name: "constructor".to_string(),
+                kept: 0,
+                discarded: 1,
+            }]
+        );
+
+        let tokens = AbiParser::collect_tokens(&merged.entries, &HashMap::new()).unwrap();
+        assert_eq!(tokens.structs.len(), 2);
+        assert_eq!(tokens.functions.len(), 1);
+        assert_eq!(tokens.interfaces["IContract"].len(), 2);
+    }
+
+    #[test]
+    fn test_merge_conflicts() {
+        let other = r#"[
+            { "type": "struct", "name": "pkg::Info", "members": [{ "name": "b", "type": "core::felt252" }] },
+            { "type": "interface", "name": "pkg::IContract", "items": [
+                { "type": "function", "name": "get_info", "inputs": [], "outputs": [], "state_mutability": "view" }
+            ]}
+        ]"#;
+
+        let merged = AbiParser::merge_abis(&[entries(PROXY), entries(other)]);
+
+        assert_eq!(
+            merged.conflicts,
+            vec![
+                AbiMergeConflict {
+                    kind: "struct".to_string(),
+                    name: "pkg::Info".to_string(),
+                    kept: 0,
+                    discarded: 1,
+                },
+                AbiMergeConflict {
+                    kind: "function".to_string(),
+                    name: "pkg::IContract::get_info".to_string(),
+                    kept: 0,
+                    discarded: 1,
+                },
+            ]
+        );
+
+        // The first definition is kept.
+        let AbiEntry::Struct(info) = &merged.entries[0] else {
+            panic!("Struct expected");
+        };
+        assert_eq!(info.members[0].name, "a");
+    }
+
+    #[test]
+    fn test_merge_single() {
+        let abi = entries(PROXY);
+        let merged = AbiParser::merge_abis(&[abi.clone()]);
+
+        assert_eq!(names(&merged.entries), names(&abi));
+        assert!(merged.conflicts.is_empty());
+    }
+}
@@ -1,3 +1,5 @@
+pub mod merge;
+pub mod normalize;
 pub mod parser;
 pub mod parser_legacy;
 
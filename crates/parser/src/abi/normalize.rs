@@ -0,0 +1,97 @@
+//! Normalization of an ABI into a canonical form.
+//!
+//! The compiler may output the same ABI with entries in a different order,
+//! the canonical form can then be used to compare or hash ABIs.
+use starknet::core::types::contract::AbiEntry;
+
+use super::merge::{entry_key, MergedAbi};
+use super::parser::AbiParser;
+
+impl AbiParser {
+    /// Normalizes an ABI: duplicated entries are removed, and the entries
+    /// (and the items of the interfaces) are sorted by kind and name.
+    ///
+    /// The order of the struct members and enum variants is kept, as it's part of
+    /// the serialization format. Duplicated entries with different definitions are
+    /// reported as conflicts, the first definition being kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `entries` - The ABI entries to normalize.
+    pub fn normalize_abi(entries: &[AbiEntry]) -> MergedAbi {
+        let mut normalized = Self::merge_abis(&[entries.to_vec()]);
+        sort_entries(&mut normalized.entries);
+        normalized
+    }
+}
+
+/// Sorts the entries by kind and name, recursively into the interfaces.
+fn sort_entries(entries: &mut [AbiEntry]) {
+    for entry in entries.iter_mut() {
+        if let AbiEntry::Interface(interface) = entry {
+            sort_entries(&mut interface.items);
+        }
+    }
+
+    entries.sort_by_key(entry_key);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ABI: &str = r#"[
+        { "type": "function", "name": "b", "inputs": [], "outputs": [], "state_mutability": "view" },
+        { "type": "struct", "name": "pkg::S", "members": [
+            { "name": "z", "type": "core::felt252" },
+            { "name": "a", "type": "core::felt252" }
+        ]},
+        { "type": "interface", "name": "pkg::I", "items": [
+            { "type": "function", "name": "y", "inputs": [], "outputs": [], "state_mutability": "view" },
+            { "type": "function", "name": "x", "inputs": [], "outputs": [], "state_mutability": "view" }
+        ]},
+        { "type": "function", "name": "a", "inputs": [], "outputs": [], "state_mutability": "view" },
+        { "type": "function", "name": "b", "inputs": [], "outputs": [], "state_mutability": "view" }
+    ]"#;
+
+    fn names(entries: &[AbiEntry]) -> Vec<String> {
+        entries.iter().map(|e| entry_key(e).1).collect()
+    }
+
+    #[test]
+    fn test_normalize_abi() {
+        let entries = AbiParser::parse_abi_string(ABI).unwrap();
+        let normalized = AbiParser::normalize_abi(&entries);
+
+        assert!(normalized.conflicts.is_empty());
+        assert_eq!(
+            names(&normalized.entries),
+            vec!["a", "b", "pkg::I", "pkg::S"]
+        );
+
+        let AbiEntry::Interface(interface) = &normalized.entries[2] else {
+            panic!("Interface expected");
+        };
+        assert_eq!(names(&interface.items), vec!["x", "y"]);
+
+        // Members order is part of the serialization, and must be kept.
+        let AbiEntry::Struct(s) = &normalized.entries[3] else {
+            panic!("Struct expected");
+        };
+        assert_eq!(s.members[0].name, "z");
+    }
+
+    #[test]
+    fn test_normalize_abi_is_canonical() {
+        let mut entries = AbiParser::parse_abi_string(ABI).unwrap();
+        let normalized = AbiParser::normalize_abi(&entries);
+
+        entries.reverse();
+        let normalized_reversed = AbiParser::normalize_abi(&entries);
+
+        assert_eq!(
+            serde_json::to_string(&normalized.entries).unwrap(),
+            serde_json::to_string(&normalized_reversed.entries).unwrap()
+        );
+    }
+}
@@ -1,7 +1,12 @@
-use starknet::core::types::contract::{AbiEntry, AbiEvent, SierraClass, TypedAbiEvent};
-use std::collections::HashMap;
-
-use crate::tokens::{Array, Composite, CompositeType, CoreBasic, Function, Token};
+use starknet::core::types::contract::{
+    AbiEntry, AbiEvent, AbiFunction, SierraClass, TypedAbiEvent,
+};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+use crate::tokens::{
+    extract_type_path_with_depth, Array, Composite, CompositeType, CoreBasic, Function,
+    StateMutability, Token,
+};
 use crate::{CainomeResult, Error};
 
 #[derive(Debug, Clone, PartialEq, Default)]
@@ -12,8 +17,353 @@ pub struct TokenizedAbi {
     pub structs: Vec<Token>,
     /// Standalone functions in the contract ABI.
     pub functions: Vec<Token>,
-    /// Fully qualified interface name mapped to all the defined functions in it.
+    /// Interface short name (`IERC20`) mapped to all the defined functions in it.
+    /// The interfaces sharing the same short name are keyed by their fully qualified name.
     pub interfaces: HashMap<String, Vec<Token>>,
+    /// Fully qualified name of each interface, by its key in `interfaces`.
+    pub interface_paths: HashMap<String, String>,
+    /// Impls of the contract (like the `#[abi(embed_v0)]` ones) mapped to the key
+    /// in `interfaces` of the interface they implement.
+    pub impls: HashMap<String, String>,
+    /// L1 handlers of the contract, handling the messages sent from L1.
+    /// Their first input is the L1 address sending the message.
+    pub l1_handlers: Vec<Token>,
+    /// Constructor of the contract, if any, as an external function without output.
+    pub constructor: Option<Token>,
+}
+
+impl TokenizedAbi {
+    /// Returns the fully qualified name of an interface, from its key in `interfaces`.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key of the interface.
+    pub fn interface_path<'a>(&'a self, key: &'a str) -> &'a str {
+        self.interface_paths
+            .get(key)
+            .map(String::as_str)
+            .unwrap_or(key)
+    }
+
+    /// Returns the key in `interfaces` of an interface, from its fully qualified name.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The fully qualified name of the interface.
+    pub fn interface_key(&self, path: &str) -> Option<&str> {
+        self.interface_paths
+            .iter()
+            .find(|(_, p)| *p == path)
+            .map(|(k, _)| k.as_str())
+    }
+
+    /// Returns the event enum of the contract, which is the only event enum
+    /// not being a variant of an other event enum (like components events are).
+    ///
+    /// `None` is returned if the contract has no event, or if the root event is ambiguous.
+    pub fn root_event(&self) -> Option<&Composite> {
+        let events: Vec<&Composite> = self
+            .enums
+            .iter()
+            .filter_map(|t| t.to_composite().ok())
+            .filter(|c| c.is_event)
+            .collect();
+
+        let mut roots = events.iter().filter(|candidate| {
+            !events.iter().any(|e| {
+                e.inners
+                    .iter()
+                    .any(|i| i.token.type_path() == candidate.type_path)
+            })
+        });
+
+        match (roots.next(), roots.next()) {
+            (Some(root), None) => Some(root),
+            _ => None,
+        }
+    }
+
+    /// Returns the type paths of the structs and enums automatically renamed
+    /// because their name collides with the Rust prelude, with their new name.
+    pub fn prelude_renames(&self) -> Vec<(String, String)> {
+        let mut renames: Vec<(String, String)> = self
+            .structs
+            .iter()
+            .chain(self.enums.iter())
+            .filter_map(|t| t.to_composite().ok())
+            .filter(|c| c.alias.is_some() && c.alias == c.prelude_alias())
+            .map(|c| (c.type_path_no_generic(), c.type_name_or_alias()))
+            .collect();
+
+        renames.sort();
+        renames
+    }
+
+    /// Returns the type paths (without generic arguments) of the structs and enums
+    /// referenced by the ABI but not defined in it, sorted and without duplicates.
+    ///
+    /// Such types can't be expanded, which happens with ABIs produced by some
+    /// tooling omitting types. The Cairo builtins are never considered unresolved.
+    pub fn unresolved_types(&self) -> Vec<String> {
+        let defined: HashSet<String> = self
+            .structs
+            .iter()
+            .chain(self.enums.iter())
+            .map(|t| t.type_path())
+            .collect();
+
+        let mut unresolved = BTreeSet::new();
+
+        for t in self.structs.iter().chain(self.enums.iter()) {
+            // The builtins are not expanded, their members don't need to be resolved.
+            if let Token::Composite(c) = t {
+                if c.is_builtin() {
+                    continue;
+                }
+
+                for i in &c.inners {
+                    collect_unresolved(&i.token, &defined, &mut unresolved);
+                }
+            }
+        }
+
+        let functions = self
+            .functions
+            .iter()
+            .chain(self.interfaces.values().flatten())
+            .chain(self.l1_handlers.iter())
+            .chain(self.constructor.iter());
+
+        for f in functions {
+            collect_unresolved(f, &defined, &mut unresolved);
+        }
+
+        unresolved.into_iter().collect()
+    }
+
+    /// Returns the structs and enums of distinct type paths which have the same name
+    /// once converted to Pascal case (like `pkg::my_struct` and `other::MyStruct`),
+    /// sorted by name.
+    ///
+    /// Such types would be generated with the same name. The aliases are taken into account,
+    /// which allows to fix a collision by aliasing the colliding types.
+    pub fn name_collisions(&self) -> Vec<NameCollision> {
+        let mut names: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+
+        for t in self.structs.iter().chain(self.enums.iter()) {
+            if let Token::Composite(c) = t {
+                // The builtins are not expanded.
+                if c.is_builtin() {
+                    continue;
+                }
+
+                names
+                    .entry(c.type_name_or_alias())
+                    .or_default()
+                    .insert(c.type_path_no_generic());
+            }
+        }
+
+        names
+            .into_iter()
+            .filter(|(_, type_paths)| type_paths.len() > 1)
+            .map(|(name, type_paths)| NameCollision {
+                name,
+                type_paths: type_paths.into_iter().collect(),
+            })
+            .collect()
+    }
+}
+
+/// Structs or enums of distinct type paths generated with the same name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NameCollision {
+    /// The name of the generated type.
+    pub name: String,
+    /// The type paths (without generic arguments) of the colliding types, sorted.
+    pub type_paths: Vec<String>,
+}
+
+impl NameCollision {
+    /// Returns an alias for each colliding type, prefixing its name with the
+    /// fewest modules of its type path making the names unique.
+    ///
+    /// When the type paths only differ by the case of their names, the aliases are
+    /// suffixed by their position instead.
+    pub fn suggested_aliases(&self) -> Vec<(String, String)> {
+        let max_depth = self
+            .type_paths
+            .iter()
+            .map(|p| p.split("::").count() - 1)
+            .max()
+            .unwrap_or_default();
+
+        for depth in 1..=max_depth {
+            let aliases: Vec<String> = self
+                .type_paths
+                .iter()
+                .map(|p| extract_type_path_with_depth(p, depth))
+                .collect();
+
+            let unique: HashSet<&String> = aliases.iter().collect();
+            if unique.len() == aliases.len() {
+                return self.type_paths.iter().cloned().zip(aliases).collect();
+            }
+        }
+
+        self.type_paths
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (p.clone(), format!("{}{}", self.name, i + 1)))
+            .collect()
+    }
+}
+
+impl std::fmt::Display for NameCollision {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let suggestions: Vec<String> = self
+            .suggested_aliases()
+            .into_iter()
+            .map(|(type_path, alias)| format!("{type_path} => {alias}"))
+            .collect();
+
+        write!(
+            f,
+            "types {} are all named `{}`, give them distinct aliases (e.g. {})",
+            self.type_paths.join(", "),
+            self.name,
+            suggestions.join(", ")
+        )
+    }
+}
+
+/// Completes the user `type_aliases` with the aliases of the structs and enums
+/// whose name collides with the Rust prelude, and which are not already aliased.
+///
+/// # Arguments
+///
+/// * `tokens` - The structs and enums tokens, by type path.
+/// * `type_aliases` - The aliases given by the user.
+pub(crate) fn with_prelude_aliases(
+    tokens: &HashMap<String, Token>,
+    type_aliases: &HashMap<String, String>,
+) -> HashMap<String, String> {
+    let mut aliases = type_aliases.clone();
+
+    for t in tokens.values() {
+        if let Token::Composite(c) = t {
+            let type_path = c.type_path_no_generic();
+
+            if let (Some(alias), false) = (c.prelude_alias(), aliases.contains_key(&type_path)) {
+                aliases.insert(type_path, alias);
+            }
+        }
+    }
+
+    aliases
+}
+
+/// Collects the composites referenced by `token` which are not `defined`.
+fn collect_unresolved(token: &Token, defined: &HashSet<String>, unresolved: &mut BTreeSet<String>) {
+    match token {
+        Token::Array(a) => collect_unresolved(&a.inner, defined, unresolved),
+        Token::Tuple(t) => {
+            for i in &t.inners {
+                collect_unresolved(i, defined, unresolved);
+            }
+        }
+        Token::Composite(c) => {
+            let type_path = c.type_path_no_generic();
+
+            if !c.is_builtin() && !defined.contains(&type_path) {
+                unresolved.insert(type_path);
+            }
+
+            // The inners of the defined composites are checked from their definition.
+            for (_, g) in &c.generic_args {
+                collect_unresolved(g, defined, unresolved);
+            }
+        }
+        Token::Function(f) => {
+            let tokens = f
+                .inputs
+                .iter()
+                .map(|(_, t)| t)
+                .chain(f.outputs.iter())
+                .chain(f.named_outputs.iter().map(|(_, t)| t));
+
+            for t in tokens {
+                collect_unresolved(t, defined, unresolved);
+            }
+        }
+        Token::CoreBasic(_) | Token::GenericArg(_) => (),
+    }
+}
+
+/// The default max number of nested composites hydrated, see [`ParserOptions`].
+pub const DEFAULT_HYDRATION_MAX_DEPTH: usize = 32;
+
+/// Options of the parsing of an ABI into tokens.
+#[derive(Debug, Clone)]
+pub struct ParserOptions {
+    /// Max number of nested composites (structs and enums) hydrated with their definition.
+    /// The parsing fails if the ABI nests more composites, recursive types excepted.
+    pub hydration_max_depth: usize,
+    /// The naming of the structs and enums which are not aliased.
+    pub type_naming: TypeNaming,
+}
+
+impl Default for ParserOptions {
+    fn default() -> Self {
+        Self {
+            hydration_max_depth: DEFAULT_HYDRATION_MAX_DEPTH,
+            type_naming: TypeNaming::default(),
+        }
+    }
+}
+
+/// The naming of the structs and enums which are not aliased.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TypeNaming {
+    /// The last segment of the type path (`pkg::token::Transfer` is `Transfer`).
+    /// The types named the same are reported by [`TokenizedAbi::name_collisions`].
+    #[default]
+    LastSegment,
+    /// The last segment of the type path, except for the types named the same which are
+    /// aliased with the fewest modules making their names unique (`TokenTransfer` and
+    /// `NftTransfer`), as suggested by [`NameCollision::suggested_aliases`].
+    ShortestUnique,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseTypeNamingError {
+    invalid_value: String,
+}
+
+impl std::fmt::Display for ParseTypeNamingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Invalid type naming '{}'. Supported values are 'last_segment' or 'shortest_unique'.",
+            self.invalid_value
+        )
+    }
+}
+
+impl std::error::Error for ParseTypeNamingError {}
+
+impl std::str::FromStr for TypeNaming {
+    type Err = ParseTypeNamingError;
+
+    fn from_str(input: &str) -> Result<TypeNaming, Self::Err> {
+        match input {
+            "last_segment" => Ok(TypeNaming::LastSegment),
+            "shortest_unique" => Ok(TypeNaming::ShortestUnique),
+            _ => Err(ParseTypeNamingError {
+                invalid_value: input.to_string(),
+            }),
+        }
+    }
 }
 
 pub struct AbiParser {}
@@ -34,8 +384,7 @@ impl AbiParser {
         type_aliases: &HashMap<String, String>,
     ) -> CainomeResult<TokenizedAbi> {
         let abi_entries = Self::parse_abi_string(abi)?;
-        let tokenized_abi =
-            AbiParser::collect_tokens(&abi_entries, type_aliases).expect("failed tokens parsing");
+        let tokenized_abi = AbiParser::collect_tokens(&abi_entries, type_aliases)?;
 
         Ok(tokenized_abi)
     }
@@ -64,6 +413,39 @@ impl AbiParser {
         entries: &[AbiEntry],
         type_aliases: &HashMap<String, String>,
     ) -> CainomeResult<TokenizedAbi> {
+        Self::collect_tokens_with_options(entries, type_aliases, &ParserOptions::default())
+    }
+
+    /// Parse all tokens in the ABI, with the given options.
+    pub fn collect_tokens_with_options(
+        entries: &[AbiEntry],
+        type_aliases: &HashMap<String, String>,
+        options: &ParserOptions,
+    ) -> CainomeResult<TokenizedAbi> {
+        if options.type_naming == TypeNaming::ShortestUnique {
+            let options = ParserOptions {
+                type_naming: TypeNaming::LastSegment,
+                ..options.clone()
+            };
+
+            let tokens = Self::collect_tokens_with_options(entries, type_aliases, &options)?;
+            let collisions = tokens.name_collisions();
+
+            if collisions.is_empty() {
+                return Ok(tokens);
+            }
+
+            // The types aliased by the user keep their alias.
+            let mut type_aliases = type_aliases.clone();
+            for collision in collisions {
+                for (type_path, alias) in collision.suggested_aliases() {
+                    type_aliases.entry(type_path).or_insert(alias);
+                }
+            }
+
+            return Self::collect_tokens_with_options(entries, &type_aliases, &options);
+        }
+
         let mut token_candidates: HashMap<String, Vec<Token>> = HashMap::new();
 
         // Entry tokens are structs, enums and events (which are structs and enums).
@@ -71,7 +453,7 @@ impl AbiParser {
             Self::collect_entry_token(entry, &mut token_candidates)?;
         }
 
-        let tokens = Self::filter_struct_enum_tokens(token_candidates);
+        let tokens = Self::filter_struct_enum_tokens(token_candidates, options)?;
 
         let mut structs = vec![];
         let mut enums = vec![];
@@ -80,9 +462,11 @@ impl AbiParser {
         // To be optimized.
         let mut all_composites: HashMap<String, Composite> = HashMap::new();
 
+        let type_aliases = with_prelude_aliases(&tokens, type_aliases);
+
         // Apply type aliases only on structs and enums.
         for (_, mut t) in tokens {
-            for (type_path, alias) in type_aliases {
+            for (type_path, alias) in &type_aliases {
                 t.apply_alias(type_path, alias);
             }
 
@@ -99,6 +483,8 @@ impl AbiParser {
 
         let mut functions = vec![];
         let mut interfaces: HashMap<String, Vec<Token>> = HashMap::new();
+        let mut l1_handlers = vec![];
+        let mut constructor = None;
 
         for entry in entries {
             Self::collect_entry_function(
@@ -106,18 +492,89 @@ impl AbiParser {
                 &all_composites,
                 &mut functions,
                 &mut interfaces,
+                &mut l1_handlers,
+                &mut constructor,
                 None,
             )?;
         }
 
+        // The composites nested in generic arguments (like `Option<T>`) are
+        // not resolved from the known composites, and must also be aliased.
+        for f in functions
+            .iter_mut()
+            .chain(interfaces.values_mut().flatten())
+            .chain(l1_handlers.iter_mut())
+            .chain(constructor.iter_mut())
+        {
+            for (type_path, alias) in &type_aliases {
+                f.apply_alias(type_path, alias);
+            }
+        }
+
+        let (interfaces, interface_paths) = Self::interfaces_by_short_name(interfaces);
+
+        let impls = entries
+            .iter()
+            .filter_map(|e| match e {
+                AbiEntry::Impl(i) => interface_paths
+                    .iter()
+                    .find(|(_, path)| **path == i.interface_name)
+                    .map(|(key, _)| (i.name.clone(), key.clone())),
+                _ => None,
+            })
+            .collect();
+
         Ok(TokenizedAbi {
             enums,
             structs,
             functions,
             interfaces,
+            interface_paths,
+            impls,
+            l1_handlers,
+            constructor,
         })
     }
 
+    /// Keys the interfaces by their short name, or by their fully qualified name
+    /// if other interfaces have the same short name.
+    ///
+    /// Returns the interfaces, and the fully qualified name of each one by key.
+    ///
+    /// # Arguments
+    ///
+    /// * `interfaces` - The interfaces, by fully qualified name.
+    fn interfaces_by_short_name(
+        interfaces: HashMap<String, Vec<Token>>,
+    ) -> (HashMap<String, Vec<Token>>, HashMap<String, String>) {
+        let short_name = |path: &str| {
+            let path = path.split('<').next().unwrap_or(path);
+            path.rsplit("::").next().unwrap_or(path).to_string()
+        };
+
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for path in interfaces.keys() {
+            *counts.entry(short_name(path)).or_default() += 1;
+        }
+
+        let mut by_key = HashMap::new();
+        let mut paths = HashMap::new();
+
+        for (path, functions) in interfaces {
+            let name = short_name(&path);
+            let key = if counts[&name] == 1 {
+                name
+            } else {
+                path.clone()
+            };
+
+            by_key.insert(key.clone(), functions);
+            paths.insert(key, path);
+        }
+
+        (by_key, paths)
+    }
+
     /// Collects the function from the ABI entry.
     ///
     /// # Arguments
@@ -126,12 +583,16 @@ impl AbiParser {
     /// * `all_composites` - All known composites tokens.
     /// * `functions` - The list of functions already collected.
     /// * `interfaces` - The list of interfaces already collected.
+    /// * `l1_handlers` - The list of L1 handlers already collected.
+    /// * `constructor` - The constructor, once collected.
     /// * `interface_name` - The name of the interface (if any).
     fn collect_entry_function(
         entry: &AbiEntry,
         all_composites: &HashMap<String, Composite>,
         functions: &mut Vec<Token>,
         interfaces: &mut HashMap<String, Vec<Token>>,
+        l1_handlers: &mut Vec<Token>,
+        constructor: &mut Option<Token>,
         interface_name: Option<String>,
     ) -> CainomeResult<()> {
         /// Gets the existing token into known composite, if any.
@@ -155,21 +616,30 @@ impl AbiParser {
             }
         }
 
+        fn function_token(
+            f: &AbiFunction,
+            all_composites: &HashMap<String, Composite>,
+        ) -> CainomeResult<Function> {
+            let mut func = Function::new(&f.name, f.state_mutability.clone().into());
+
+            for i in &f.inputs {
+                let token = get_existing_token_or_parsed(&i.r#type, all_composites)?;
+                func.inputs.push((i.name.clone(), token));
+            }
+
+            for o in &f.outputs {
+                let token = get_existing_token_or_parsed(&o.r#type, all_composites)?;
+                func.outputs.push(token);
+            }
+
+            Ok(func)
+        }
+
         // TODO: optimize the search and data structures.
         // HashMap would be more appropriate than vec.
         match entry {
             AbiEntry::Function(f) => {
-                let mut func = Function::new(&f.name, f.state_mutability.clone().into());
-
-                for i in &f.inputs {
-                    let token = get_existing_token_or_parsed(&i.r#type, all_composites)?;
-                    func.inputs.push((i.name.clone(), token));
-                }
-
-                for o in &f.outputs {
-                    let token = get_existing_token_or_parsed(&o.r#type, all_composites)?;
-                    func.outputs.push(token);
-                }
+                let func = function_token(f, all_composites)?;
 
                 if let Some(name) = interface_name {
                     interfaces
@@ -187,10 +657,25 @@ impl AbiParser {
                         all_composites,
                         functions,
                         interfaces,
+                        l1_handlers,
+                        constructor,
                         Some(interface.name.clone()),
                     )?;
                 }
             }
+            AbiEntry::L1Handler(f) => {
+                l1_handlers.push(Token::Function(function_token(f, all_composites)?));
+            }
+            AbiEntry::Constructor(c) => {
+                let mut func = Function::new(&c.name, StateMutability::External);
+
+                for i in &c.inputs {
+                    let token = get_existing_token_or_parsed(&i.r#type, all_composites)?;
+                    func.inputs.push((i.name.clone(), token));
+                }
+
+                *constructor = Some(Token::Function(func));
+            }
             _ => (),
         }
 
@@ -295,7 +780,8 @@ impl AbiParser {
 
     fn filter_struct_enum_tokens(
         token_candidates: HashMap<String, Vec<Token>>,
-    ) -> HashMap<String, Token> {
+        options: &ParserOptions,
+    ) -> CainomeResult<HashMap<String, Token>> {
         let tokens_filtered = Self::filter_token_candidates(token_candidates);
 
         // Can be a very huge copy here. Need an other way to do that in the loop
@@ -304,7 +790,7 @@ impl AbiParser {
 
         // So now once it's filtered, we may actually iterate again on the tokens
         // to resolve all structs/enums inners that may reference existing types.
-        Self::hydrate_composites(tokens_filtered, filtered)
+        Self::hydrate_composites(tokens_filtered, filtered, options)
     }
 
     /// ABI is a flat list of tokens that represents any types declared in cairo code.
@@ -373,13 +859,16 @@ impl AbiParser {
     fn hydrate_composites(
         tokens_filtered: HashMap<String, Token>,
         filtered: HashMap<String, Token>,
-    ) -> HashMap<String, Token> {
+        options: &ParserOptions,
+    ) -> CainomeResult<HashMap<String, Token>> {
         tokens_filtered
             .into_iter()
-            .fold(HashMap::new(), |mut acc, (name, token)| {
-                acc.insert(name, Token::hydrate(token, &filtered, 10, 0));
-                acc
+            .map(|(name, token)| {
+                let token =
+                    Token::hydrate(token, &filtered, options.hydration_max_depth, &mut vec![])?;
+                Ok((name, token))
             })
+            .collect()
     }
 }
 
@@ -388,6 +877,273 @@ mod tests {
     use super::*;
     use crate::tokens::{CompositeInner, CompositeInnerKind, CompositeType};
 
+    #[test]
+    fn test_root_event() {
+        let abi = r#"[
+            { "type": "event", "name": "pkg::Transfer", "kind": "struct", "members": [
+                { "name": "from", "type": "core::felt252", "kind": "key" }
+            ]},
+            { "type": "event", "name": "pkg::component::Event", "kind": "enum", "variants": [
+                { "name": "Transfer", "type": "pkg::Transfer", "kind": "nested" }
+            ]},
+            { "type": "event", "name": "pkg::contract::Event", "kind": "enum", "variants": [
+                { "name": "Transfer", "type": "pkg::Transfer", "kind": "nested" },
+                { "name": "ComponentEvent", "type": "pkg::component::Event", "kind": "flat" }
+            ]}
+        ]"#;
+
+        let tokens = AbiParser::tokens_from_abi_string(abi, &HashMap::new()).unwrap();
+        assert_eq!(
+            tokens.root_event().unwrap().type_path,
+            "pkg::contract::Event"
+        );
+
+        assert!(TokenizedAbi::default().root_event().is_none());
+    }
+
+    #[test]
+    fn test_unresolved_types() {
+        let abi = r#"[
+            { "type": "struct", "name": "pkg::Defined", "members": [
+                { "name": "a", "type": "pkg::MissingInStruct" },
+                { "name": "b", "type": "core::integer::u256" },
+                { "name": "c", "type": "core::byte_array::ByteArray" }
+            ]},
+            { "type": "function", "name": "f", "inputs": [
+                { "name": "a", "type": "core::array::Span::<pkg::MissingInArray>" },
+                { "name": "b", "type": "core::option::Option::<(pkg::Defined, pkg::MissingInTuple)>" }
+            ], "outputs": [{ "type": "pkg::Defined" }], "state_mutability": "view" },
+            { "type": "interface", "name": "pkg::I", "items": [
+                { "type": "function", "name": "g", "inputs": [], "outputs": [{ "type": "pkg::MissingInArray" }], "state_mutability": "view" }
+            ]}
+        ]"#;
+
+        let tokens = AbiParser::tokens_from_abi_string(abi, &HashMap::new()).unwrap();
+        assert_eq!(
+            tokens.unresolved_types(),
+            vec![
+                "pkg::MissingInArray",
+                "pkg::MissingInStruct",
+                "pkg::MissingInTuple"
+            ]
+        );
+
+        for file in [
+            "cairo_ls_abi.json",
+            "dojo_starter-directions_available.abi.json",
+            "kitchen_sink.abi.json",
+            "struct_tuple.abi.json",
+        ] {
+            let abi = std::fs::read_to_string(format!("./test_data/{}", file)).unwrap();
+            let tokens = AbiParser::tokens_from_abi_string(&abi, &HashMap::new()).unwrap();
+            assert!(tokens.unresolved_types().is_empty(), "{}", file);
+        }
+    }
+
+    #[test]
+    fn test_name_collisions() {
+        let abi = r#"[
+            { "type": "struct", "name": "pkg::a::my_struct", "members": [] },
+            { "type": "struct", "name": "pkg::b::MyStruct", "members": [] },
+            { "type": "enum", "name": "pkg::a::Side", "variants": [] },
+            { "type": "struct", "name": "pkg::a::side", "members": [] },
+            { "type": "struct", "name": "pkg::Unique", "members": [] },
+            { "type": "function", "name": "f", "inputs": [
+                { "name": "a", "type": "pkg::a::my_struct" },
+                { "name": "b", "type": "pkg::b::MyStruct" },
+                { "name": "c", "type": "pkg::a::Side" },
+                { "name": "d", "type": "pkg::a::side" },
+                { "name": "e", "type": "pkg::Unique" }
+            ], "outputs": [], "state_mutability": "view" }
+        ]"#;
+
+        let tokens = AbiParser::tokens_from_abi_string(abi, &HashMap::new()).unwrap();
+        let collisions = tokens.name_collisions();
+
+        assert_eq!(
+            collisions,
+            vec![
+                NameCollision {
+                    name: "MyStruct".to_string(),
+                    type_paths: vec![
+                        "pkg::a::my_struct".to_string(),
+                        "pkg::b::MyStruct".to_string()
+                    ],
+                },
+                NameCollision {
+                    name: "Side".to_string(),
+                    type_paths: vec!["pkg::a::Side".to_string(), "pkg::a::side".to_string()],
+                },
+            ]
+        );
+
+        assert_eq!(
+            collisions[0].suggested_aliases(),
+            vec![
+                ("pkg::a::my_struct".to_string(), "AMyStruct".to_string()),
+                ("pkg::b::MyStruct".to_string(), "BMyStruct".to_string()),
+            ]
+        );
+        assert_eq!(
+            collisions[1].suggested_aliases(),
+            vec![
+                ("pkg::a::Side".to_string(), "Side1".to_string()),
+                ("pkg::a::side".to_string(), "Side2".to_string()),
+            ]
+        );
+        assert_eq!(
+            collisions[0].to_string(),
+            "types pkg::a::my_struct, pkg::b::MyStruct are all named `MyStruct`, give them distinct aliases (e.g. pkg::a::my_struct => AMyStruct, pkg::b::MyStruct => BMyStruct)"
+        );
+
+        // Aliasing the colliding types fixes the collisions.
+        let aliases = HashMap::from([
+            ("pkg::a::my_struct".to_string(), "AMyStruct".to_string()),
+            ("pkg::a::side".to_string(), "SideStruct".to_string()),
+        ]);
+        let tokens = AbiParser::tokens_from_abi_string(abi, &aliases).unwrap();
+        assert!(tokens.name_collisions().is_empty());
+    }
+
+    #[test]
+    fn test_execution_info_builtins() {
+        let abi = r#"[
+            { "type": "struct", "name": "core::starknet::info::v2::ExecutionInfo", "members": [
+                { "name": "block_info", "type": "core::box::Box::<core::starknet::info::BlockInfo>" },
+                { "name": "tx_info", "type": "core::box::Box::<core::starknet::info::v2::TxInfo>" }
+            ]},
+            { "type": "struct", "name": "pkg::Wrapper", "members": [
+                { "name": "info", "type": "core::box::Box::<core::starknet::info::v2::ExecutionInfo>" }
+            ]}
+        ]"#;
+
+        let tokens = AbiParser::tokens_from_abi_string(abi, &HashMap::new()).unwrap();
+        assert!(tokens.unresolved_types().is_empty());
+
+        let execution_info = tokens
+            .structs
+            .iter()
+            .find(|t| t.type_path() == "core::starknet::info::v2::ExecutionInfo")
+            .unwrap();
+        assert!(execution_info.to_composite().unwrap().is_builtin());
+
+        let wrapper = tokens
+            .structs
+            .iter()
+            .find(|t| t.type_path() == "pkg::Wrapper")
+            .unwrap()
+            .to_composite()
+            .unwrap();
+        let boxed = wrapper.inners[0].token.to_composite().unwrap();
+        assert!(boxed.is_builtin());
+        assert_eq!(boxed.type_name(), "Box");
+        assert_eq!(
+            boxed.generic_args[0].1.type_path(),
+            "core::starknet::info::v2::ExecutionInfo"
+        );
+    }
+
+    #[test]
+    fn test_prelude_renames() {
+        let abi = r#"[
+            { "type": "struct", "name": "pkg::Option", "members": [{ "name": "a", "type": "core::felt252" }] },
+            { "type": "enum", "name": "pkg::Result", "variants": [{ "name": "Ok", "type": "()" }] },
+            { "type": "struct", "name": "pkg::String", "members": [] },
+            { "type": "struct", "name": "pkg::Wrapper", "members": [
+                { "name": "a", "type": "pkg::Option" },
+                { "name": "b", "type": "core::option::Option::<core::felt252>" }
+            ]},
+            { "type": "function", "name": "f", "inputs": [{ "name": "r", "type": "pkg::Result" }], "outputs": [{ "type": "core::option::Option::<pkg::Option>" }], "state_mutability": "view" }
+        ]"#;
+
+        let aliases = HashMap::from([("pkg::String".to_string(), "MyString".to_string())]);
+        let tokens = AbiParser::tokens_from_abi_string(abi, &aliases).unwrap();
+
+        // User aliases have precedence.
+        assert_eq!(
+            tokens.prelude_renames(),
+            vec![
+                ("pkg::Option".to_string(), "CairoOption".to_string()),
+                ("pkg::Result".to_string(), "CairoResult".to_string()),
+            ]
+        );
+
+        let wrapper = tokens
+            .structs
+            .iter()
+            .find(|t| t.type_path() == "pkg::Wrapper")
+            .unwrap()
+            .to_composite()
+            .unwrap();
+        assert_eq!(
+            wrapper.inners[0]
+                .token
+                .to_composite()
+                .unwrap()
+                .type_name_or_alias(),
+            "CairoOption"
+        );
+        // The Cairo builtin is not renamed.
+        assert_eq!(
+            wrapper.inners[1]
+                .token
+                .to_composite()
+                .unwrap()
+                .type_name_or_alias(),
+            "Option"
+        );
+
+        let f = tokens.functions[0].to_function().unwrap();
+        assert_eq!(
+            f.inputs[0].1.to_composite().unwrap().type_name_or_alias(),
+            "CairoResult"
+        );
+        // Including in the generic arguments of the builtins.
+        assert_eq!(
+            f.outputs[0].to_composite().unwrap().generic_args[0]
+                .1
+                .to_composite()
+                .unwrap()
+                .type_name_or_alias(),
+            "CairoOption"
+        );
+    }
+
+    #[test]
+    fn test_const_generics() {
+        let abi = r#"[
+            { "type": "struct", "name": "pkg::Config::<3>", "members": [{ "name": "a", "type": "core::felt252" }] },
+            { "type": "struct", "name": "pkg::Config::<5>", "members": [{ "name": "a", "type": "core::integer::u8" }] },
+            { "type": "struct", "name": "pkg::Buffer::<core::felt252, 2>", "members": [{ "name": "a", "type": "core::felt252" }] },
+            { "type": "function", "name": "f", "inputs": [{ "name": "c", "type": "pkg::Config::<3>" }], "outputs": [], "state_mutability": "view" }
+        ]"#;
+
+        let tokens = AbiParser::tokens_from_abi_string(abi, &HashMap::new()).unwrap();
+
+        // Each instantiation is a distinct type.
+        let mut names: Vec<_> = tokens
+            .structs
+            .iter()
+            .map(|t| (t.type_path(), t.to_composite().unwrap().type_name()))
+            .collect();
+        names.sort();
+
+        assert_eq!(
+            names,
+            vec![
+                ("pkg::Buffer::<2>".to_string(), "Buffer2".to_string()),
+                ("pkg::Config::<3>".to_string(), "Config3".to_string()),
+                ("pkg::Config::<5>".to_string(), "Config5".to_string()),
+            ]
+        );
+
+        let f = tokens.functions[0].to_function().unwrap();
+        assert_eq!(
+            f.inputs[0].1.to_composite().unwrap().type_name_or_alias(),
+            "Config3"
+        );
+    }
+
     #[test]
     fn test_filter_token_candidates_single_inner() {
         let mut input: HashMap<String, Vec<Token>> = HashMap::new();
@@ -776,6 +1532,85 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_kitchen_sink_abi() {
+        let abi = AbiParser::tokens_from_abi_string(
+            include_str!("../../test_data/kitchen_sink.abi.json"),
+            &HashMap::new(),
+        )
+        .unwrap();
+
+        let function = |name: &str| {
+            abi.functions
+                .iter()
+                .map(|f| f.to_function().unwrap())
+                .find(|f| f.name == name)
+                .unwrap()
+        };
+
+        // `Option<Result<Array<Item>, felt252>>`, hydrated down to the struct.
+        let nested = function("nested");
+        let option = nested.inputs[0].1.to_composite().unwrap();
+        let result = option.generic_args[0].1.to_composite().unwrap();
+        match &result.generic_args[0].1 {
+            Token::Array(a) => {
+                let item = a.inner.to_composite().unwrap();
+                assert_eq!(item.type_path, "contracts::abicov::kitchen_sink::Item");
+                assert_eq!(item.inners.len(), 3);
+            }
+            _ => panic!("Expected array"),
+        }
+
+        let non_zero = function("non_zero").inputs[0].1.to_composite().unwrap();
+        assert_eq!(non_zero.type_name(), "NonZero");
+        assert_eq!(non_zero.generic_args[0].1.type_path(), "core::felt252");
+
+        // The wrapper of a wrapper is generic on the inner wrapper.
+        let builtins = function("builtins");
+        assert_eq!(builtins.inputs.len(), 8);
+        match &builtins.outputs[0] {
+            Token::Tuple(t) => {
+                let wrapper = t.inners[0].to_composite().unwrap();
+                assert!(wrapper.is_generic());
+                assert_eq!(
+                    wrapper.generic_args[0]
+                        .1
+                        .to_composite()
+                        .unwrap()
+                        .type_name(),
+                    "Wrapper"
+                );
+            }
+            _ => panic!("Expected tuple"),
+        }
+
+        // Instantiated with itself, the wrapper is still generic on its inner member.
+        let wrapper = abi
+            .structs
+            .iter()
+            .map(|s| s.to_composite().unwrap())
+            .find(|s| s.type_name() == "Wrapper")
+            .unwrap();
+        assert_eq!(wrapper.inners[0].token, Token::GenericArg("A".to_string()));
+
+        // Three levels of events, the flat one included.
+        let event = abi.root_event().unwrap();
+        assert_eq!(event.inners.len(), 3);
+        assert_eq!(event.inners[2].kind, CompositeInnerKind::Flat);
+
+        let nested_event = event.inners[1].token.to_composite().unwrap();
+        let deep_event = nested_event.inners[0].token.to_composite().unwrap();
+        assert!(deep_event.is_event);
+        assert_eq!(
+            deep_event.inners[0]
+                .token
+                .to_composite()
+                .unwrap()
+                .type_name(),
+            "Leaf"
+        );
+    }
+
     #[test]
     fn test_composite_generic_args_hydratation() {
         let mut input: HashMap<String, Vec<Token>> = HashMap::new();
@@ -977,7 +1812,8 @@ Composite {
             })],
         );
 
-        let filtered = AbiParser::filter_struct_enum_tokens(input);
+        let filtered =
+            AbiParser::filter_struct_enum_tokens(input, &ParserOptions::default()).unwrap();
         let tmv = filtered
             .get("tournament::ls15_components::models::tournament::TournamentModelValue")
             .unwrap()
@@ -1129,7 +1965,8 @@ Composite {
             })],
         );
 
-        let filtered = AbiParser::filter_struct_enum_tokens(input);
+        let filtered =
+            AbiParser::filter_struct_enum_tokens(input, &ParserOptions::default()).unwrap();
         fn check_token_inners(token: &Token) {
             // end of recursion, if token is composite and inners are empty, this means hydration
             // was not properly done.
@@ -1142,6 +1979,81 @@ Composite {
         filtered.iter().for_each(|(_, t)| check_token_inners(t));
     }
 
+    fn nested_structs_abi(count: usize) -> Vec<AbiEntry> {
+        let structs: Vec<String> = (0..count)
+            .map(|i| {
+                let member = if i + 1 < count {
+                    format!("pkg::S{}", i + 1)
+                } else {
+                    "core::felt252".to_string()
+                };
+
+                format!(
+                    r#"{{ "type": "struct", "name": "pkg::S{i}", "members": [
+                        {{ "name": "inner", "type": "{member}" }}
+                    ]}}"#
+                )
+            })
+            .collect();
+
+        AbiParser::parse_abi_string(&format!("[{}]", structs.join(","))).unwrap()
+    }
+
+    #[test]
+    fn test_hydration_max_depth() {
+        let entries = nested_structs_abi(4);
+
+        let options = ParserOptions {
+            hydration_max_depth: 4,
+            ..Default::default()
+        };
+        assert!(
+            AbiParser::collect_tokens_with_options(&entries, &HashMap::new(), &options).is_ok()
+        );
+
+        let options = ParserOptions {
+            hydration_max_depth: 3,
+            ..Default::default()
+        };
+        let err = AbiParser::collect_tokens_with_options(&entries, &HashMap::new(), &options)
+            .unwrap_err();
+
+        match &err {
+            Error::HydrationDepthExceeded { max_depth, chain } => {
+                assert_eq!(*max_depth, 3);
+                assert_eq!(chain, &["pkg::S0", "pkg::S1", "pkg::S2", "pkg::S3"]);
+            }
+            e => panic!("unexpected error {e}"),
+        }
+
+        assert_eq!(
+            err.to_string(),
+            "Max depth of 3 nested types exceeded while hydrating \
+             pkg::S0 -> pkg::S1 -> pkg::S2 -> pkg::S3"
+        );
+    }
+
+    #[test]
+    fn test_recursive_type_hydration() {
+        let abi = r#"[
+            { "type": "struct", "name": "pkg::Tree", "members": [
+                { "name": "value", "type": "core::felt252" },
+                { "name": "children", "type": "core::array::Array::<pkg::Tree>" }
+            ]}
+        ]"#;
+
+        let tokens = AbiParser::tokens_from_abi_string(abi, &HashMap::new()).unwrap();
+        let tree = tokens.structs[0].to_composite().unwrap();
+
+        let Token::Array(children) = &tree.inners[1].token else {
+            panic!("Expected array");
+        };
+        // The recursive occurrence is resolved to the definition, without being hydrated.
+        let child = children.inner.to_composite().unwrap();
+        assert_eq!(child.r#type, CompositeType::Struct);
+        assert_eq!(child.inners.len(), 2);
+    }
+
     #[test]
     fn test_collect_tokens() {
         let sierra_abi = include_str!("../../test_data/cairo_ls_abi.json");
@@ -1152,4 +2064,173 @@ Composite {
         assert_ne!(tokens.interfaces.len(), 0);
         assert_ne!(tokens.structs.len(), 0);
     }
+
+    #[test]
+    fn test_collect_l1_handlers() {
+        let abi = r#"[
+            { "type": "struct", "name": "pkg::Deposit", "members": [
+                { "name": "amount", "type": "core::integer::u256" }
+            ]},
+            {
+                "type": "l1_handler",
+                "name": "on_deposit",
+                "inputs": [
+                    { "name": "from_address", "type": "core::felt252" },
+                    { "name": "deposit", "type": "pkg::Deposit" }
+                ],
+                "outputs": [],
+                "state_mutability": "external"
+            }
+        ]"#;
+
+        let tokens = AbiParser::tokens_from_abi_string(abi, &HashMap::new()).unwrap();
+        assert!(tokens.functions.is_empty());
+        assert_eq!(tokens.l1_handlers.len(), 1);
+
+        let handler = tokens.l1_handlers[0].to_function().unwrap();
+        assert_eq!(handler.name, "on_deposit");
+        assert_eq!(handler.inputs[0].0, "from_address");
+
+        // The composites of the inputs are hydrated like for the functions.
+        let deposit = handler.inputs[1].1.to_composite().unwrap();
+        assert_eq!(deposit.r#type, CompositeType::Struct);
+        assert_eq!(deposit.inners.len(), 1);
+    }
+
+    #[test]
+    fn test_collect_constructor() {
+        let abi = r#"[
+            { "type": "struct", "name": "pkg::Config", "members": [
+                { "name": "fee", "type": "core::integer::u128" }
+            ]},
+            {
+                "type": "constructor",
+                "name": "constructor",
+                "inputs": [
+                    { "name": "owner", "type": "core::starknet::contract_address::ContractAddress" },
+                    { "name": "config", "type": "pkg::Config" }
+                ]
+            }
+        ]"#;
+
+        let tokens = AbiParser::tokens_from_abi_string(abi, &HashMap::new()).unwrap();
+        assert!(tokens.functions.is_empty());
+
+        let constructor = tokens.constructor.unwrap();
+        let constructor = constructor.to_function().unwrap();
+        assert_eq!(constructor.name, "constructor");
+        assert_eq!(constructor.state_mutability, StateMutability::External);
+        assert!(constructor.outputs.is_empty());
+        assert_eq!(constructor.inputs[0].0, "owner");
+
+        let config = constructor.inputs[1].1.to_composite().unwrap();
+        assert_eq!(config.inners.len(), 1);
+    }
+
+    #[test]
+    fn test_collect_interfaces_by_short_name() {
+        let abi = r#"[
+            { "type": "impl", "name": "ERC20Impl", "interface_name": "openzeppelin::token::erc20::interface::IERC20" },
+            { "type": "interface", "name": "openzeppelin::token::erc20::interface::IERC20", "items": [
+                { "type": "function", "name": "total_supply", "inputs": [], "outputs": [{ "type": "core::felt252" }], "state_mutability": "view" }
+            ]},
+            { "type": "impl", "name": "UpgradeableImpl", "interface_name": "pkg::upgrade::IUpgradeable" },
+            { "type": "interface", "name": "pkg::upgrade::IUpgradeable", "items": [
+                { "type": "function", "name": "upgrade", "inputs": [], "outputs": [], "state_mutability": "external" }
+            ]},
+            { "type": "interface", "name": "other::upgrade::IUpgradeable", "items": [
+                { "type": "function", "name": "upgrade_to", "inputs": [], "outputs": [], "state_mutability": "external" }
+            ]}
+        ]"#;
+
+        let tokens = AbiParser::tokens_from_abi_string(abi, &HashMap::new()).unwrap();
+
+        let mut keys: Vec<&String> = tokens.interfaces.keys().collect();
+        keys.sort();
+        assert_eq!(
+            keys,
+            vec![
+                "IERC20",
+                "other::upgrade::IUpgradeable",
+                "pkg::upgrade::IUpgradeable"
+            ]
+        );
+
+        assert_eq!(
+            tokens.interface_path("IERC20"),
+            "openzeppelin::token::erc20::interface::IERC20"
+        );
+        assert_eq!(
+            tokens.interface_key("openzeppelin::token::erc20::interface::IERC20"),
+            Some("IERC20")
+        );
+        assert_eq!(tokens.interface_key("pkg::IMissing"), None);
+
+        assert_eq!(tokens.impls["ERC20Impl"], "IERC20");
+        assert_eq!(
+            tokens.impls["UpgradeableImpl"],
+            "pkg::upgrade::IUpgradeable"
+        );
+    }
+
+    #[test]
+    fn test_type_naming_shortest_unique() {
+        let abi = r#"[
+            { "type": "struct", "name": "pkg::token::Transfer", "members": [] },
+            { "type": "struct", "name": "pkg::nft::Transfer", "members": [] },
+            { "type": "struct", "name": "pkg::nft::Approval", "members": [] },
+            { "type": "function", "name": "transfer", "inputs": [
+                { "name": "a", "type": "pkg::token::Transfer" },
+                { "name": "b", "type": "pkg::nft::Transfer" }
+            ], "outputs": [], "state_mutability": "external" }
+        ]"#;
+        let entries = AbiParser::parse_abi_string(abi).unwrap();
+
+        let names = |tokens: &TokenizedAbi| {
+            let mut names: Vec<String> = tokens
+                .structs
+                .iter()
+                .map(|t| t.to_composite().unwrap().type_name_or_alias())
+                .collect();
+            names.sort();
+            names
+        };
+
+        let tokens = AbiParser::collect_tokens(&entries, &HashMap::new()).unwrap();
+        assert_eq!(tokens.name_collisions().len(), 1);
+
+        let options = ParserOptions {
+            type_naming: TypeNaming::ShortestUnique,
+            ..Default::default()
+        };
+
+        let tokens =
+            AbiParser::collect_tokens_with_options(&entries, &HashMap::new(), &options).unwrap();
+        assert!(tokens.name_collisions().is_empty());
+        assert_eq!(
+            names(&tokens),
+            vec!["Approval", "NftTransfer", "TokenTransfer"]
+        );
+
+        let function = tokens.functions[0].to_function().unwrap();
+        assert_eq!(
+            function.inputs[0]
+                .1
+                .to_composite()
+                .unwrap()
+                .type_name_or_alias(),
+            "TokenTransfer"
+        );
+
+        // The aliases of the user are kept.
+        let aliases = HashMap::from([("pkg::nft::Transfer".to_string(), "NftMove".to_string())]);
+        let tokens = AbiParser::collect_tokens_with_options(&entries, &aliases, &options).unwrap();
+        assert_eq!(names(&tokens), vec!["Approval", "NftMove", "Transfer"]);
+
+        assert_eq!(
+            "shortest_unique".parse::<TypeNaming>(),
+            Ok(TypeNaming::ShortestUnique)
+        );
+        assert!("short".parse::<TypeNaming>().is_err());
+    }
 }
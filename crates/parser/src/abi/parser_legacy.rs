@@ -4,9 +4,15 @@ use starknet::core::types::contract::legacy::{
 use starknet::core::types::contract::StateMutability;
 use std::collections::HashMap;
 
-use crate::tokens::{Composite, CompositeType, CoreBasic, Function, Token};
+use super::parser::with_prelude_aliases;
+use crate::tokens::{
+    Composite, CompositeInner, CompositeInnerKind, CompositeType, CoreBasic, Function, Token,
+};
 use crate::{CainomeResult, Error, TokenizedAbi};
 
+/// The name of the enum grouping the events of a Cairo 0 contract.
+const LEGACY_EVENT_NAME: &str = "Event";
+
 pub struct AbiParserLegacy {}
 
 impl AbiParserLegacy {
@@ -21,8 +27,7 @@ impl AbiParserLegacy {
         type_aliases: &HashMap<String, String>,
     ) -> CainomeResult<TokenizedAbi> {
         let abi_entries = Self::parse_abi_string(abi)?;
-        let tokenized_abi =
-            Self::collect_tokens(&abi_entries, type_aliases).expect("failed tokens parsing");
+        let tokenized_abi = Self::collect_tokens(&abi_entries, type_aliases)?;
 
         Ok(tokenized_abi)
     }
@@ -56,9 +61,11 @@ impl AbiParserLegacy {
         // To be optimized.
         let mut all_composites: HashMap<String, Composite> = HashMap::new();
 
+        let type_aliases = with_prelude_aliases(&tokens, type_aliases);
+
         // Apply type aliases only on structs and enums.
         for (_, mut t) in tokens {
-            for (type_path, alias) in type_aliases {
+            for (type_path, alias) in &type_aliases {
                 t.apply_alias(type_path, alias);
             }
 
@@ -73,12 +80,38 @@ impl AbiParserLegacy {
             }
         }
 
+        if let Some(event) = Self::collect_events(entries, &all_composites)? {
+            enums.push(event);
+        }
+
         let mut functions = vec![];
 
         for entry in entries {
             Self::collect_entry_function(entry, &mut all_composites, &mut structs, &mut functions)?;
         }
 
+        let mut l1_handlers = vec![];
+
+        for entry in entries {
+            Self::collect_entry_l1_handler(entry, &all_composites, &mut l1_handlers)?;
+        }
+
+        let mut constructor = None;
+
+        for entry in entries {
+            Self::collect_entry_constructor(entry, &all_composites, &mut constructor)?;
+        }
+
+        for f in functions
+            .iter_mut()
+            .chain(l1_handlers.iter_mut())
+            .chain(constructor.iter_mut())
+        {
+            for (type_path, alias) in &type_aliases {
+                f.apply_alias(type_path, alias);
+            }
+        }
+
         let interfaces: HashMap<String, Vec<Token>> = HashMap::new();
 
         Ok(TokenizedAbi {
@@ -86,9 +119,107 @@ impl AbiParserLegacy {
             structs,
             functions,
             interfaces,
+            interface_paths: HashMap::new(),
+            impls: HashMap::new(),
+            l1_handlers,
+            constructor,
         })
     }
 
+    /// Collects the events of the ABI into an `Event` enum, with one variant per event
+    /// named after it, as Cairo 0 events have the selector of their name as the first key.
+    ///
+    /// Returns `None` if there is no event, or if a struct is already named `Event`.
+    ///
+    /// # Arguments
+    ///
+    /// * `entries` - The ABI entries.
+    /// * `all_composites` - All known composites tokens, including the events.
+    fn collect_events(
+        entries: &[RawLegacyAbiEntry],
+        all_composites: &HashMap<String, Composite>,
+    ) -> CainomeResult<Option<Token>> {
+        if all_composites.contains_key(LEGACY_EVENT_NAME) {
+            return Ok(None);
+        }
+
+        let mut event = Composite::parse(LEGACY_EVENT_NAME)?;
+        event.r#type = CompositeType::Enum;
+        event.is_event = true;
+
+        for entry in entries {
+            if let RawLegacyAbiEntry::Event(ev) = entry {
+                let token = get_existing_token_or_parsed(&ev.name, all_composites)?;
+                let name = token.to_composite()?.type_name();
+
+                event.inners.push(CompositeInner {
+                    index: event.inners.len(),
+                    name,
+                    kind: CompositeInnerKind::Nested,
+                    token,
+                });
+            }
+        }
+
+        if event.inners.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(Token::Composite(event)))
+        }
+    }
+
+    /// Collects the L1 handler from the ABI entry.
+    ///
+    /// # Arguments
+    ///
+    /// * `entry` - The ABI entry to collect the L1 handler from.
+    /// * `all_composites` - All known composites tokens.
+    /// * `l1_handlers` - The list of L1 handlers already collected.
+    fn collect_entry_l1_handler(
+        entry: &RawLegacyAbiEntry,
+        all_composites: &HashMap<String, Composite>,
+        l1_handlers: &mut Vec<Token>,
+    ) -> CainomeResult<()> {
+        if let RawLegacyAbiEntry::L1Handler(h) = entry {
+            let mut func = Function::new(&h.name, StateMutability::External.into());
+
+            for i in &h.inputs {
+                let token = get_existing_token_or_parsed(&i.r#type, all_composites)?;
+                func.inputs.push((i.name.clone(), token));
+            }
+
+            l1_handlers.push(Token::Function(func));
+        }
+
+        Ok(())
+    }
+
+    /// Collects the constructor from the ABI entry.
+    ///
+    /// # Arguments
+    ///
+    /// * `entry` - The ABI entry to collect the constructor from.
+    /// * `all_composites` - All known composites tokens.
+    /// * `constructor` - The constructor, once collected.
+    fn collect_entry_constructor(
+        entry: &RawLegacyAbiEntry,
+        all_composites: &HashMap<String, Composite>,
+        constructor: &mut Option<Token>,
+    ) -> CainomeResult<()> {
+        if let RawLegacyAbiEntry::Constructor(c) = entry {
+            let mut func = Function::new(&c.name, StateMutability::External.into());
+
+            for i in &c.inputs {
+                let token = get_existing_token_or_parsed(&i.r#type, all_composites)?;
+                func.inputs.push((i.name.clone(), token));
+            }
+
+            *constructor = Some(Token::Function(func));
+        }
+
+        Ok(())
+    }
+
     /// Collects the token from the ABI entry.
     ///
     /// # Arguments
@@ -133,27 +264,6 @@ impl AbiParserLegacy {
         structs: &mut Vec<Token>,
         functions: &mut Vec<Token>,
     ) -> CainomeResult<()> {
-        /// Gets the existing token into known composite, if any.
-        /// Otherwise, return the parsed token.
-        fn get_existing_token_or_parsed(
-            type_path: &str,
-            all_composites: &HashMap<String, Composite>,
-        ) -> CainomeResult<Token> {
-            let parsed_token = Token::parse(type_path)?;
-
-            // If the token is an known struct or enum, we look up
-            // in existing one to get full info from there as the parsing
-            // of composites is already done before functions.
-            if let Token::Composite(ref c) = parsed_token {
-                match all_composites.get(&c.type_path_no_generic()) {
-                    Some(e) => Ok(Token::Composite(e.clone())),
-                    None => Ok(parsed_token),
-                }
-            } else {
-                Ok(parsed_token)
-            }
-        }
-
         // TODO: optimize the search and data structures.
         // HashMap would be more appropriate than vec.
         if let RawLegacyAbiEntry::Function(f) = entry {
@@ -201,3 +311,79 @@ impl AbiParserLegacy {
         Ok(())
     }
 }
+
+/// Gets the existing token into known composite, if any.
+/// Otherwise, return the parsed token.
+fn get_existing_token_or_parsed(
+    type_path: &str,
+    all_composites: &HashMap<String, Composite>,
+) -> CainomeResult<Token> {
+    let parsed_token = Token::parse(type_path)?;
+
+    // If the token is an known struct or enum, we look up
+    // in existing one to get full info from there as the parsing
+    // of composites is already done before functions.
+    if let Token::Composite(ref c) = parsed_token {
+        match all_composites.get(&c.type_path_no_generic()) {
+            Some(e) => Ok(Token::Composite(e.clone())),
+            None => Ok(parsed_token),
+        }
+    } else {
+        Ok(parsed_token)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_legacy_events() {
+        let abi = r#"[
+            { "type": "struct", "name": "Uint256", "size": 2, "members": [
+                { "name": "low", "type": "felt", "offset": 0 },
+                { "name": "high", "type": "felt", "offset": 1 }
+            ]},
+            { "type": "event", "name": "Transfer", "keys": [], "data": [
+                { "name": "from_", "type": "felt" },
+                { "name": "value", "type": "Uint256" }
+            ]},
+            { "type": "event", "name": "Approval", "keys": [], "data": [
+                { "name": "owner", "type": "felt" }
+            ]}
+        ]"#;
+
+        let tokens = AbiParserLegacy::tokens_from_abi_string(abi, &HashMap::new()).unwrap();
+        let event = tokens.root_event().unwrap();
+
+        assert_eq!(event.type_path, "Event");
+        assert_eq!(event.r#type, CompositeType::Enum);
+
+        let variants: Vec<(usize, &str)> = event
+            .inners
+            .iter()
+            .map(|i| (i.index, i.name.as_str()))
+            .collect();
+        assert_eq!(variants, vec![(0, "Transfer"), (1, "Approval")]);
+        assert!(event
+            .inners
+            .iter()
+            .all(|i| i.kind == CompositeInnerKind::Nested));
+
+        let transfer = event.inners[0].token.to_composite().unwrap();
+        assert!(transfer.is_event);
+        assert_eq!(transfer.inners.len(), 2);
+    }
+
+    #[test]
+    fn test_legacy_no_events() {
+        let abi = r#"[
+            { "type": "function", "name": "get", "inputs": [], "outputs": [
+                { "name": "value", "type": "felt" }
+            ], "stateMutability": "view" }
+        ]"#;
+
+        let tokens = AbiParserLegacy::tokens_from_abi_string(abi, &HashMap::new()).unwrap();
+        assert!(tokens.enums.is_empty());
+    }
+}
@@ -11,6 +11,17 @@ pub enum Error {
     ConversionFailed(String),
     #[error("Parser error: {0}")]
     ParsingFailed(String),
+    #[error(
+        "Max depth of {max_depth} nested types exceeded while hydrating {}",
+        .chain.join(" -> ")
+    )]
+    HydrationDepthExceeded {
+        max_depth: usize,
+        /// The type paths of the nested types, outermost first.
+        chain: Vec<String>,
+    },
+    #[error("Felt layout error: {0}")]
+    FeltLayout(String),
     #[error(transparent)]
     IO(#[from] std::io::Error),
     #[error(transparent)]
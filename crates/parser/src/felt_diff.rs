@@ -0,0 +1,639 @@
+//! Felt by felt comparison of serialized values.
+//!
+//! To debug a serialization mismatch between two implementations (like a backend in
+//! another language and the Rust bindings), the felts of the expected and actual values
+//! are walked along the type, each felt being labelled with the path of the member it
+//! belongs to (`orders[1].price.low`). The lengths of the arrays and the indices of the
+//! variants are walked on each side, so the comparison goes on after a length or a
+//! variant which differs.
+//!
+//! The path of a member is made of the names of the struct members, the names of the
+//! variants, the indices of the tuple members (`.0`) and of the array elements (`[2]`).
+//! The felts prefixing the arrays are `.len`, the ones prefixing the variants `.variant`.
+//! The felts which can't be walked (after an invalid variant index, or beyond the values)
+//! are reported as `<trailing>`.
+use starknet::core::types::Felt;
+
+use crate::tokens::{Composite, CompositeType, Token};
+use crate::{CainomeResult, Error};
+
+/// The path of the felts which are not part of the walked value.
+pub const TRAILING_PATH: &str = "<trailing>";
+
+/// A felt of a buffer, with its offset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeltAt {
+    /// The offset of the felt in the buffer.
+    pub offset: usize,
+    /// The felt.
+    pub felt: Felt,
+}
+
+/// A felt of the expected and the actual values, `None` for the side where it is missing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeltEntry {
+    /// The path of the member the felt belongs to.
+    pub path: String,
+    /// The type path of the felt (the enum for a variant index).
+    pub type_path: String,
+    /// The felt of the expected value.
+    pub expected: Option<FeltAt>,
+    /// The felt of the actual value.
+    pub actual: Option<FeltAt>,
+}
+
+impl FeltEntry {
+    /// Returns true if the expected and actual felts differ, or one of them is missing.
+    pub fn is_mismatch(&self) -> bool {
+        self.expected.map(|f| f.felt) != self.actual.map(|f| f.felt)
+    }
+}
+
+/// Returns all the felts of the expected and actual values of the type, in order.
+///
+/// The composites must be hydrated (as returned by the parser).
+///
+/// # Arguments
+///
+/// * `token` - The type of the values.
+/// * `expected` - The felts of the expected value.
+/// * `actual` - The felts of the actual value.
+pub fn compare(token: &Token, expected: &[Felt], actual: &[Felt]) -> CainomeResult<Vec<FeltEntry>> {
+    compare_fields(&[(String::new(), token.clone())], expected, actual)
+}
+
+/// Returns all the felts of the expected and actual values of consecutive named fields,
+/// like the inputs of a function, in order. See [`compare`].
+///
+/// # Arguments
+///
+/// * `fields` - The names and types of the fields.
+/// * `expected` - The felts of the expected values.
+/// * `actual` - The felts of the actual values.
+pub fn compare_fields(
+    fields: &[(String, Token)],
+    expected: &[Felt],
+    actual: &[Felt],
+) -> CainomeResult<Vec<FeltEntry>> {
+    let mut walker = Walker {
+        expected: Cursor::new(expected),
+        actual: Cursor::new(actual),
+        entries: vec![],
+    };
+
+    for (name, token) in fields {
+        walker.walk(token, &[], name, Sides::BOTH)?;
+    }
+
+    walker.trailing();
+
+    Ok(walker.entries)
+}
+
+/// Returns the felts which differ between the expected and actual values of the type.
+/// See [`compare`].
+///
+/// # Arguments
+///
+/// * `token` - The type of the values.
+/// * `expected` - The felts of the expected value.
+/// * `actual` - The felts of the actual value.
+pub fn diff(token: &Token, expected: &[Felt], actual: &[Felt]) -> CainomeResult<Vec<FeltEntry>> {
+    Ok(compare(token, expected, actual)?
+        .into_iter()
+        .filter(FeltEntry::is_mismatch)
+        .collect())
+}
+
+/// A buffer being walked.
+struct Cursor<'f> {
+    felts: &'f [Felt],
+    offset: usize,
+    /// Set when the buffer can't be walked further, its remaining felts are trailing.
+    lost: bool,
+}
+
+impl<'f> Cursor<'f> {
+    fn new(felts: &'f [Felt]) -> Self {
+        Self {
+            felts,
+            offset: 0,
+            lost: false,
+        }
+    }
+
+    fn next(&mut self) -> Option<FeltAt> {
+        if self.lost {
+            return None;
+        }
+
+        let felt = *self.felts.get(self.offset)?;
+        let at = FeltAt {
+            offset: self.offset,
+            felt,
+        };
+
+        self.offset += 1;
+        Some(at)
+    }
+
+    fn remaining(&self) -> usize {
+        self.felts.len().saturating_sub(self.offset)
+    }
+}
+
+/// The sides walked by a member: both, or only one when the other has a different
+/// variant or a shorter array.
+#[derive(Debug, Clone, Copy)]
+struct Sides {
+    expected: bool,
+    actual: bool,
+}
+
+impl Sides {
+    const BOTH: Self = Self {
+        expected: true,
+        actual: true,
+    };
+}
+
+struct Walker<'f> {
+    expected: Cursor<'f>,
+    actual: Cursor<'f>,
+    entries: Vec<FeltEntry>,
+}
+
+impl<'f> Walker<'f> {
+    fn walk(
+        &mut self,
+        token: &Token,
+        generic_args: &[(String, Token)],
+        path: &str,
+        sides: Sides,
+    ) -> CainomeResult<()> {
+        if !sides.expected && !sides.actual {
+            return Ok(());
+        }
+
+        match token {
+            Token::CoreBasic(b) => {
+                if b.type_path != "()" {
+                    self.felt(path, &b.type_path, sides);
+                }
+                Ok(())
+            }
+            Token::Array(a) => {
+                if a.is_legacy {
+                    return Err(layout_error(
+                        path,
+                        "the length of a Cairo 0 array is a separate member",
+                    ));
+                }
+
+                let (expected, actual) = self.felt(&join(path, "len"), "core::integer::u32", sides);
+                let expected_len = self.length(expected, true);
+                let actual_len = self.length(actual, false);
+
+                for i in 0..expected_len.max(actual_len) {
+                    let element_sides = Sides {
+                        expected: sides.expected && i < expected_len,
+                        actual: sides.actual && i < actual_len,
+                    };
+                    self.walk(
+                        &a.inner,
+                        generic_args,
+                        &format!("{path}[{i}]"),
+                        element_sides,
+                    )?;
+                }
+
+                Ok(())
+            }
+            Token::Tuple(t) => {
+                for (i, inner) in t.inners.iter().enumerate() {
+                    self.walk(inner, generic_args, &join(path, &i.to_string()), sides)?;
+                }
+                Ok(())
+            }
+            Token::Composite(c) => self.walk_composite(c, path, sides),
+            Token::GenericArg(name) => {
+                let token = generic_args
+                    .iter()
+                    .find(|(n, _)| n == name)
+                    .map(|(_, t)| t)
+                    .ok_or_else(|| {
+                        layout_error(path, &format!("unresolved generic argument `{name}`"))
+                    })?;
+
+                self.walk(token, &[], path, sides)
+            }
+            Token::Function(_) => Err(layout_error(path, "a function is not a value")),
+        }
+    }
+
+    fn walk_composite(
+        &mut self,
+        composite: &Composite,
+        path: &str,
+        sides: Sides,
+    ) -> CainomeResult<()> {
+        let args = &composite.generic_args;
+        let type_path = composite.type_path_no_generic();
+
+        match type_path.as_str() {
+            "core::integer::u256" => {
+                self.felt(&join(path, "low"), "core::integer::u128", sides);
+                self.felt(&join(path, "high"), "core::integer::u128", sides);
+                Ok(())
+            }
+            "core::byte_array::ByteArray" => {
+                let data = Token::parse("core::array::Array::<core::bytes_31::bytes31>")?;
+                self.walk(&data, &[], &join(path, "data"), sides)?;
+                self.felt(&join(path, "pending_word"), "core::felt252", sides);
+                self.felt(&join(path, "pending_word_len"), "core::integer::u32", sides);
+                Ok(())
+            }
+            "core::starknet::eth_address::EthAddress"
+            | "core::internal::bounded_int::BoundedInt" => {
+                self.felt(path, &type_path, sides);
+                Ok(())
+            }
+            "core::zeroable::NonZero" | "core::r#box::Box" => {
+                self.walk(generic_arg(composite, 0, path)?, args, path, sides)
+            }
+            "core::option::Option" => {
+                let some = generic_arg(composite, 0, path)?.clone();
+                self.walk_enum(
+                    &type_path,
+                    &[("Some", &some), ("None", &unit())],
+                    args,
+                    path,
+                    sides,
+                )
+            }
+            "core::result::Result" => {
+                let ok = generic_arg(composite, 0, path)?.clone();
+                let err = generic_arg(composite, 1, path)?.clone();
+                self.walk_enum(&type_path, &[("Ok", &ok), ("Err", &err)], args, path, sides)
+            }
+            _ => match composite.r#type {
+                CompositeType::Struct => {
+                    for inner in &composite.inners {
+                        self.walk(&inner.token, args, &join(path, &inner.name), sides)?;
+                    }
+                    Ok(())
+                }
+                CompositeType::Enum => {
+                    let mut inners: Vec<_> = composite.inners.iter().collect();
+                    inners.sort_by_key(|i| i.index);
+
+                    let variants: Vec<(&str, &Token)> =
+                        inners.iter().map(|i| (i.name.as_str(), &i.token)).collect();
+
+                    self.walk_enum(&type_path, &variants, args, path, sides)
+                }
+                CompositeType::Unknown => Err(layout_error(
+                    path,
+                    &format!("type `{}` is not hydrated", composite.type_path),
+                )),
+            },
+        }
+    }
+
+    /// Walks the variant index, then the variant of each side.
+    fn walk_enum(
+        &mut self,
+        type_path: &str,
+        variants: &[(&str, &Token)],
+        generic_args: &[(String, Token)],
+        path: &str,
+        sides: Sides,
+    ) -> CainomeResult<()> {
+        let (expected, actual) = self.felt(&join(path, "variant"), type_path, sides);
+        let expected_index = self.variant(expected, variants.len(), true);
+        let actual_index = self.variant(actual, variants.len(), false);
+
+        if expected_index == actual_index {
+            if let Some(i) = expected_index {
+                let (name, token) = variants[i];
+                self.walk(token, generic_args, &join(path, name), sides)?;
+            }
+
+            return Ok(());
+        }
+
+        if let Some(i) = expected_index {
+            let (name, token) = variants[i];
+            let expected_only = Sides {
+                expected: true,
+                actual: false,
+            };
+            self.walk(token, generic_args, &join(path, name), expected_only)?;
+        }
+
+        if let Some(i) = actual_index {
+            let (name, token) = variants[i];
+            let actual_only = Sides {
+                expected: false,
+                actual: true,
+            };
+            self.walk(token, generic_args, &join(path, name), actual_only)?;
+        }
+
+        Ok(())
+    }
+
+    /// Reads a felt on the walked sides, and records it.
+    fn felt(
+        &mut self,
+        path: &str,
+        type_path: &str,
+        sides: Sides,
+    ) -> (Option<FeltAt>, Option<FeltAt>) {
+        let expected = sides.expected.then(|| self.expected.next()).flatten();
+        let actual = sides.actual.then(|| self.actual.next()).flatten();
+
+        self.entries.push(FeltEntry {
+            path: path.to_string(),
+            type_path: type_path.to_string(),
+            expected,
+            actual,
+        });
+
+        (expected, actual)
+    }
+
+    /// Returns the length of an array of a side, which is lost if the length
+    /// exceeds its remaining felts.
+    fn length(&mut self, felt: Option<FeltAt>, expected: bool) -> usize {
+        let Some(felt) = felt else {
+            return 0;
+        };
+
+        let cursor = self.cursor(expected);
+
+        match felt_to_usize(felt.felt) {
+            Some(len) if len <= cursor.remaining() => len,
+            _ => {
+                cursor.lost = true;
+                0
+            }
+        }
+    }
+
+    /// Returns the index of the variant of a side, which is lost if the index is invalid.
+    fn variant(&mut self, felt: Option<FeltAt>, count: usize, expected: bool) -> Option<usize> {
+        let felt = felt?;
+
+        match felt_to_usize(felt.felt) {
+            Some(index) if index < count => Some(index),
+            _ => {
+                self.cursor(expected).lost = true;
+                None
+            }
+        }
+    }
+
+    fn cursor(&mut self, expected: bool) -> &mut Cursor<'f> {
+        if expected {
+            &mut self.expected
+        } else {
+            &mut self.actual
+        }
+    }
+
+    /// Records the felts of each side which were not walked.
+    fn trailing(&mut self) {
+        let rest = |cursor: &Cursor<'_>| -> Vec<FeltAt> {
+            (cursor.offset..cursor.felts.len())
+                .map(|offset| FeltAt {
+                    offset,
+                    felt: cursor.felts[offset],
+                })
+                .collect()
+        };
+
+        let expected = rest(&self.expected);
+        let actual = rest(&self.actual);
+
+        for i in 0..expected.len().max(actual.len()) {
+            self.entries.push(FeltEntry {
+                path: TRAILING_PATH.to_string(),
+                type_path: "core::felt252".to_string(),
+                expected: expected.get(i).copied(),
+                actual: actual.get(i).copied(),
+            });
+        }
+    }
+}
+
+/// Returns the path of a member.
+fn join(path: &str, member: &str) -> String {
+    if path.is_empty() {
+        member.to_string()
+    } else {
+        format!("{path}.{member}")
+    }
+}
+
+fn felt_to_usize(felt: Felt) -> Option<usize> {
+    let bytes = felt.to_bytes_be();
+
+    if bytes[..24].iter().any(|b| *b != 0) {
+        return None;
+    }
+
+    usize::try_from(u64::from_be_bytes(bytes[24..].try_into().ok()?)).ok()
+}
+
+fn unit() -> Token {
+    Token::parse("()").expect("unit type path is valid")
+}
+
+/// Returns the `index`-th generic argument of a builtin composite.
+fn generic_arg<'c>(composite: &'c Composite, index: usize, path: &str) -> CainomeResult<&'c Token> {
+    composite
+        .generic_args
+        .get(index)
+        .map(|(_, t)| t)
+        .ok_or_else(|| {
+            layout_error(
+                path,
+                &format!("missing generic argument of `{}`", composite.type_path),
+            )
+        })
+}
+
+fn layout_error(path: &str, reason: &str) -> Error {
+    Error::FeltLayout(format!("`{path}`: {reason}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AbiParser;
+    use std::collections::HashMap;
+
+    const ABI: &str = r#"[
+        {
+            "type": "enum",
+            "name": "pkg::Side",
+            "variants": [
+                { "name": "Buy", "type": "()" },
+                { "name": "Sell", "type": "core::integer::u64" }
+            ]
+        },
+        {
+            "type": "struct",
+            "name": "pkg::Order",
+            "members": [
+                { "name": "id", "type": "core::felt252" },
+                { "name": "side", "type": "pkg::Side" },
+                { "name": "amounts", "type": "core::array::Array::<core::integer::u256>" },
+                { "name": "memo", "type": "core::option::Option::<core::felt252>" }
+            ]
+        }
+    ]"#;
+
+    fn order() -> Token {
+        let tokens = AbiParser::tokens_from_abi_string(ABI, &HashMap::new()).unwrap();
+
+        tokens
+            .structs
+            .into_iter()
+            .find(|t| t.type_path() == "pkg::Order")
+            .unwrap()
+    }
+
+    fn felts(values: &[u64]) -> Vec<Felt> {
+        values.iter().map(|v| Felt::from(*v)).collect()
+    }
+
+    fn paths(entries: &[FeltEntry]) -> Vec<&str> {
+        entries.iter().map(|e| e.path.as_str()).collect()
+    }
+
+    #[test]
+    fn test_compare_labels_felts() {
+        // id, side: Sell(7), amounts: [1, 2], memo: Some(9)
+        let value = felts(&[5, 1, 7, 2, 1, 0, 2, 0, 0, 9]);
+        let entries = compare(&order(), &value, &value).unwrap();
+
+        assert_eq!(
+            paths(&entries),
+            vec![
+                "id",
+                "side.variant",
+                "side.Sell",
+                "amounts.len",
+                "amounts[0].low",
+                "amounts[0].high",
+                "amounts[1].low",
+                "amounts[1].high",
+                "memo.variant",
+                "memo.Some",
+            ]
+        );
+        assert_eq!(entries[2].type_path, "core::integer::u64");
+        assert_eq!(
+            entries[9].expected,
+            Some(FeltAt {
+                offset: 9,
+                felt: Felt::from(9)
+            })
+        );
+        assert!(entries.iter().all(|e| !e.is_mismatch()));
+    }
+
+    #[test]
+    fn test_diff_value_mismatch() {
+        let expected = felts(&[5, 1, 7, 1, 1, 0, 1]);
+        let actual = felts(&[5, 1, 7, 1, 0, 1, 1]);
+
+        let diffs = diff(&order(), &expected, &actual).unwrap();
+
+        assert_eq!(paths(&diffs), vec!["amounts[0].low", "amounts[0].high"]);
+        assert_eq!(diffs[0].expected.unwrap().offset, 4);
+        assert_eq!(diffs[0].actual.unwrap().felt, Felt::ZERO);
+    }
+
+    #[test]
+    fn test_diff_structural_mismatch() {
+        // Buy with no amount, against Sell(7) with one amount: the members after
+        // the variant and the length are still compared.
+        let expected = felts(&[5, 0, 0, 1]);
+        let actual = felts(&[5, 1, 7, 1, 3, 0, 0, 4]);
+
+        let diffs = diff(&order(), &expected, &actual).unwrap();
+
+        assert_eq!(
+            paths(&diffs),
+            vec![
+                "side.variant",
+                "side.Sell",
+                "amounts.len",
+                "amounts[0].low",
+                "amounts[0].high",
+                "memo.variant",
+                "memo.Some",
+            ]
+        );
+        assert_eq!(diffs[1].expected, None);
+        assert_eq!(diffs[6].expected, None);
+        assert_eq!(
+            diffs[6].actual,
+            Some(FeltAt {
+                offset: 7,
+                felt: Felt::from(4)
+            })
+        );
+    }
+
+    #[test]
+    fn test_diff_invalid_variant_and_trailing() {
+        let expected = felts(&[5, 0, 0, 1]);
+        let actual = felts(&[5, 9, 0, 1, 8]);
+
+        let diffs = diff(&order(), &expected, &actual).unwrap();
+
+        assert_eq!(
+            paths(&diffs),
+            vec![
+                "side.variant",
+                "amounts.len",
+                "memo.variant",
+                TRAILING_PATH,
+                TRAILING_PATH,
+                TRAILING_PATH,
+            ]
+        );
+        assert_eq!(diffs[3].actual.unwrap().offset, 2);
+    }
+
+    #[test]
+    fn test_compare_fields_and_basics() {
+        let fields = vec![
+            (
+                "to".to_string(),
+                Token::parse("core::starknet::contract_address::ContractAddress").unwrap(),
+            ),
+            (
+                "amount".to_string(),
+                Token::parse("core::integer::u256").unwrap(),
+            ),
+        ];
+
+        let entries = compare_fields(&fields, &felts(&[1, 2, 0]), &felts(&[1, 2])).unwrap();
+
+        assert_eq!(paths(&entries), vec!["to", "amount.low", "amount.high"]);
+        assert!(entries[2].is_mismatch());
+        assert_eq!(entries[2].actual, None);
+
+        assert!(compare(
+            &Token::parse("pkg::Unknown").unwrap(),
+            &felts(&[1]),
+            &felts(&[1])
+        )
+        .is_err());
+    }
+}
@@ -2,7 +2,16 @@ mod error;
 pub use error::{CainomeResult, Error};
 
 mod abi;
-pub use crate::abi::parser::{AbiParser, TokenizedAbi};
+pub use crate::abi::merge::{AbiMergeConflict, MergedAbi};
+pub use crate::abi::parser::{
+    AbiParser, NameCollision, ParseTypeNamingError, ParserOptions, TokenizedAbi, TypeNaming,
+    DEFAULT_HYDRATION_MAX_DEPTH,
+};
 pub use crate::abi::parser_legacy::AbiParserLegacy;
 
+pub mod felt_diff;
+pub mod selectors;
+pub mod size;
+pub mod src5;
 pub mod tokens;
+pub mod type_mapping;
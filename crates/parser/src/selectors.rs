@@ -0,0 +1,66 @@
+//! Selectors of the functions and events of a contract.
+//!
+//! Those are the canonical implementations to be used by the plugins
+//! (and the tests) to compute selectors, which can then be embedded
+//! as constants in the generated code.
+use starknet::core::types::Felt;
+use starknet::core::utils::{get_selector_from_name, starknet_keccak};
+
+use crate::{CainomeResult, Error};
+
+/// Computes the selector of a function (entrypoint) from its name.
+///
+/// The default entrypoints (`__default__` and `__l1_default__`) have a zero selector.
+///
+/// # Arguments
+///
+/// * `name` - The name of the function, which must be ASCII.
+pub fn function_selector(name: &str) -> CainomeResult<Felt> {
+    get_selector_from_name(name)
+        .map_err(|_| Error::ConversionFailed(format!("Non ASCII function name `{}`", name)))
+}
+
+/// Computes the selector of an event from its name, which is
+/// the first key of the emitted event.
+///
+/// # Arguments
+///
+/// * `name` - The name of the event (or of the event enum variant), which must be ASCII.
+pub fn event_selector(name: &str) -> CainomeResult<Felt> {
+    if !name.is_ascii() {
+        return Err(Error::ConversionFailed(format!(
+            "Non ASCII event name `{}`",
+            name
+        )));
+    }
+
+    Ok(starknet_keccak(name.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_function_selector() {
+        assert_eq!(
+            function_selector("transfer").unwrap(),
+            Felt::from_hex_unchecked(
+                "0x83afd3f4caedc6eebf44246fe54e38c95e3179a5ec9ea81740eca5b482d12e"
+            )
+        );
+        assert_eq!(function_selector("__default__").unwrap(), Felt::ZERO);
+        assert!(function_selector("transfér").is_err());
+    }
+
+    #[test]
+    fn test_event_selector() {
+        assert_eq!(
+            event_selector("Transfer").unwrap(),
+            Felt::from_hex_unchecked(
+                "0x99cd8bde557814842a3121e8ddfd433a539b8c9f14bf31ebf108d12e6196e9"
+            )
+        );
+        assert!(event_selector("Événement").is_err());
+    }
+}
@@ -0,0 +1,161 @@
+//! Serialized sizes of the tokens.
+//!
+//! This is the canonical computation of the size (in felts) of the serialized
+//! values of a type, when it doesn't depend on the value. The plugins should
+//! use it to embed the sizes in the generated code (like the `SERIALIZED_SIZE`
+//! of the Rust bindings), for all the backends to agree on the sizes.
+use crate::tokens::{Composite, CompositeType, Token};
+
+/// Returns the size in felts of any serialized value of the token,
+/// `None` if the size depends on the value (like for arrays).
+///
+/// The composites must be hydrated (as returned by the parser), the unresolved
+/// and generic types have no static size.
+///
+/// # Arguments
+///
+/// * `token` - The token.
+pub fn static_size(token: &Token) -> Option<usize> {
+    match token {
+        Token::CoreBasic(b) => {
+            if b.type_path == "()" {
+                Some(0)
+            } else {
+                Some(1)
+            }
+        }
+        Token::Array(_) => None,
+        Token::Tuple(t) => t.inners.iter().map(static_size).sum(),
+        Token::Composite(c) => composite_static_size(c),
+        Token::GenericArg(_) | Token::Function(_) => None,
+    }
+}
+
+/// Returns the size in felts of any serialized value of the composite,
+/// see [`static_size`].
+///
+/// # Arguments
+///
+/// * `composite` - The composite.
+pub fn composite_static_size(composite: &Composite) -> Option<usize> {
+    if composite.is_builtin() {
+        return match composite.type_path_no_generic().as_str() {
+            "core::integer::u256" => Some(2),
+            "core::starknet::eth_address::EthAddress" => Some(1),
+            "core::internal::bounded_int::BoundedInt" => Some(1),
+            // Those wrappers are serialized as their inner value.
+            "core::r#box::Box" | "core::zeroable::NonZero" => composite
+                .generic_args
+                .first()
+                .and_then(|(_, inner)| static_size(inner)),
+            // The variants of `Option` and `Result` have different sizes in general.
+            _ => None,
+        };
+    }
+
+    if composite.is_generic() {
+        return None;
+    }
+
+    let mut sizes = composite.inners.iter().map(|i| static_size(&i.token));
+
+    match composite.r#type {
+        CompositeType::Struct => sizes.sum(),
+        // The variant index, followed by the value of the variant:
+        // the size is static only if all the variants have the same size.
+        CompositeType::Enum => {
+            let first = sizes.next().unwrap_or(Some(0))?;
+
+            if sizes.all(|s| s == Some(first)) {
+                Some(first + 1)
+            } else {
+                None
+            }
+        }
+        CompositeType::Unknown => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AbiParser;
+    use std::collections::HashMap;
+
+    fn sizes(abi: &str) -> HashMap<String, Option<usize>> {
+        let tokens = AbiParser::tokens_from_abi_string(abi, &HashMap::new()).unwrap();
+
+        tokens
+            .structs
+            .iter()
+            .chain(tokens.enums.iter())
+            .map(|t| {
+                let c = t.to_composite().unwrap();
+                (c.type_name_or_alias(), static_size(t))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_static_size_basics() {
+        let size = |type_path| static_size(&Token::parse(type_path).unwrap());
+
+        assert_eq!(size("core::felt252"), Some(1));
+        assert_eq!(size("core::integer::u8"), Some(1));
+        assert_eq!(size("()"), Some(0));
+        assert_eq!(size("core::integer::u256"), Some(2));
+        assert_eq!(size("(core::felt252, core::integer::u256)"), Some(3));
+        assert_eq!(size("core::array::Array::<core::felt252>"), None);
+        assert_eq!(size("core::array::Span::<core::felt252>"), None);
+        assert_eq!(size("core::byte_array::ByteArray"), None);
+        assert_eq!(size("core::option::Option::<core::felt252>"), None);
+        assert_eq!(
+            size("core::zeroable::NonZero::<core::integer::u256>"),
+            Some(2)
+        );
+        assert_eq!(
+            size("(core::felt252, core::array::Array::<core::felt252>)"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_static_size_composites() {
+        let sizes = sizes(
+            r#"[
+                { "type": "struct", "name": "pkg::Point", "members": [
+                    { "name": "x", "type": "core::felt252" },
+                    { "name": "y", "type": "core::integer::u256" }
+                ]},
+                { "type": "struct", "name": "pkg::Empty", "members": [] },
+                { "type": "struct", "name": "pkg::Path", "members": [
+                    { "name": "points", "type": "core::array::Array::<pkg::Point>" }
+                ]},
+                { "type": "struct", "name": "pkg::Segment", "members": [
+                    { "name": "from", "type": "pkg::Point" },
+                    { "name": "to", "type": "pkg::Point" }
+                ]},
+                { "type": "enum", "name": "pkg::Side", "variants": [
+                    { "name": "Buy", "type": "()" },
+                    { "name": "Sell", "type": "()" }
+                ]},
+                { "type": "enum", "name": "pkg::Shape", "variants": [
+                    { "name": "Dot", "type": "pkg::Point" },
+                    { "name": "Line", "type": "pkg::Segment" }
+                ]},
+                { "type": "enum", "name": "pkg::Move", "variants": [
+                    { "name": "Left", "type": "core::felt252" },
+                    { "name": "Right", "type": "core::integer::u64" }
+                ]}
+            ]"#,
+        );
+
+        assert_eq!(sizes["Point"], Some(3));
+        assert_eq!(sizes["Empty"], Some(0));
+        assert_eq!(sizes["Path"], None);
+        assert_eq!(sizes["Segment"], Some(6));
+        assert_eq!(sizes["Side"], Some(1));
+        assert_eq!(sizes["Shape"], None);
+        assert_eq!(sizes["Move"], Some(2));
+    }
+}
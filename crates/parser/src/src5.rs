@@ -0,0 +1,411 @@
+//! SRC5 (SNIP-5) interface identifiers.
+//!
+//! The id of an interface is the XOR of the extended selectors of its functions,
+//! which are the starknet keccak of the functions signatures, like
+//! `transfer(ContractAddress,(u128,u128))->E((),())`:
+//!
+//! * The `self` parameter is not part of the signature, and there is no
+//!   `->` part for a function without output.
+//! * The core types are represented by their name (`felt252`, `ContractAddress`, ...),
+//!   except `bool` which is the enum `E((),())`.
+//! * The structs are represented as the tuple of their members, and the enums as
+//!   `E(...)` with the types of their variants (`()` for a unit variant).
+//! * `Array<T>` is kept as is, and `Span<T>` is the struct `(@Array<T>)`.
+use starknet::core::types::Felt;
+use starknet::core::utils::starknet_keccak;
+
+use crate::tokens::{Composite, CompositeType, Function, Token};
+use crate::TokenizedAbi;
+
+/// Returns the signature of the function used to compute its extended selector.
+///
+/// # Arguments
+///
+/// * `func` - The function.
+/// * `abi` - The tokenized ABI defining the function, to resolve the composites.
+pub fn function_signature(func: &Function, abi: &TokenizedAbi) -> String {
+    let mut resolver = Resolver {
+        abi,
+        visiting: vec![],
+    };
+
+    let inputs: Vec<String> = func
+        .inputs
+        .iter()
+        .map(|(_, t)| resolver.type_signature(t, &[]))
+        .collect();
+
+    let output = match func.outputs.as_slice() {
+        [] => String::new(),
+        [output] => format!("->{}", resolver.type_signature(output, &[])),
+        outputs => format!("->{}", resolver.tuple_signature(outputs, &[])),
+    };
+
+    format!("{}({}){}", func.name, inputs.join(","), output)
+}
+
+/// Computes the extended selector of the function, the starknet keccak of its
+/// [`function_signature`].
+///
+/// # Arguments
+///
+/// * `func` - The function.
+/// * `abi` - The tokenized ABI defining the function.
+pub fn extended_function_selector(func: &Function, abi: &TokenizedAbi) -> Felt {
+    starknet_keccak(function_signature(func, abi).as_bytes())
+}
+
+/// Computes the SRC5 id of an interface, the XOR of the extended selectors of its functions.
+///
+/// # Arguments
+///
+/// * `functions` - The functions of the interface.
+/// * `abi` - The tokenized ABI defining the functions.
+pub fn interface_id<'a>(
+    functions: impl IntoIterator<Item = &'a Function>,
+    abi: &TokenizedAbi,
+) -> Felt {
+    let mut id = [0_u8; 32];
+
+    for func in functions {
+        let selector = extended_function_selector(func, abi).to_bytes_be();
+
+        for (byte, s) in id.iter_mut().zip(selector) {
+            *byte ^= s;
+        }
+    }
+
+    Felt::from_bytes_be(&id)
+}
+
+/// Returns the SRC5 ids of all the interfaces of the ABI, by fully qualified interface name.
+///
+/// # Arguments
+///
+/// * `abi` - The tokenized ABI.
+pub fn interface_ids(abi: &TokenizedAbi) -> Vec<(String, Felt)> {
+    let mut ids: Vec<(String, Felt)> = abi
+        .interfaces
+        .iter()
+        .map(|(key, functions)| {
+            let functions = functions.iter().filter_map(|f| f.to_function().ok());
+            (
+                abi.interface_path(key).to_string(),
+                interface_id(functions, abi),
+            )
+        })
+        .collect();
+
+    ids.sort_by(|a, b| a.0.cmp(&b.0));
+    ids
+}
+
+/// Resolves the representation of the types, looking up the composites
+/// which are not hydrated (like the ones nested in arrays) in the ABI.
+struct Resolver<'a> {
+    abi: &'a TokenizedAbi,
+    /// The composites being represented, to stop on recursive types.
+    visiting: Vec<String>,
+}
+
+impl Resolver<'_> {
+    /// Returns the representation of the type in a function signature.
+    ///
+    /// The generic arguments of the composite being represented are given
+    /// to resolve the generic members.
+    fn type_signature(&mut self, token: &Token, generic_args: &[(String, Token)]) -> String {
+        match token {
+            Token::CoreBasic(b) => match b.type_path.as_str() {
+                "core::bool" => "E((),())".to_string(),
+                path => last_segment(path).to_string(),
+            },
+            Token::Array(a) => {
+                let inner = self.type_signature(&a.inner, generic_args);
+
+                if a.type_path.starts_with("core::array::Span") {
+                    format!("(@Array<{inner}>)")
+                } else {
+                    format!("Array<{inner}>")
+                }
+            }
+            Token::Tuple(t) => self.tuple_signature(&t.inners, generic_args),
+            Token::Composite(c) => self.composite_signature(c),
+            Token::GenericArg(g) => match generic_args.iter().find(|(name, _)| name == g) {
+                Some((_, t)) => self.type_signature(t, &[]),
+                None => g.clone(),
+            },
+            Token::Function(_) => String::new(),
+        }
+    }
+
+    fn composite_signature(&mut self, composite: &Composite) -> String {
+        let definition = self.definition(composite);
+
+        // The composites not defined in the ABI (like `NonZero`), or the occurrences
+        // of a recursive type inside itself, have no members to represent.
+        let Some(definition) = definition.filter(|_| !self.visiting.contains(&composite.type_path))
+        else {
+            let name = last_segment(&composite.type_path_no_generic()).to_string();
+
+            if composite.generic_args.is_empty() {
+                return name;
+            }
+
+            let args: Vec<String> = composite
+                .generic_args
+                .iter()
+                .map(|(_, t)| self.type_signature(t, &[]))
+                .collect();
+
+            return format!("{}<{}>", name, args.join(","));
+        };
+
+        self.visiting.push(composite.type_path.clone());
+
+        let members: Vec<String> = definition
+            .inners
+            .iter()
+            .map(|i| self.type_signature(&i.token, &definition.generic_args))
+            .collect();
+
+        self.visiting.pop();
+
+        match definition.r#type {
+            CompositeType::Enum => format!("E({})", members.join(",")),
+            _ => format!("({})", members.join(",")),
+        }
+    }
+
+    /// Returns the definition of the composite: itself if hydrated,
+    /// otherwise the struct or enum of the ABI with the same path.
+    fn definition(&self, composite: &Composite) -> Option<Composite> {
+        if composite.r#type != CompositeType::Unknown {
+            return Some(composite.clone());
+        }
+
+        self.abi
+            .structs
+            .iter()
+            .chain(self.abi.enums.iter())
+            .filter_map(|t| t.to_composite().ok())
+            .find(|c| c.type_path == composite.type_path)
+            .cloned()
+    }
+
+    fn tuple_signature(&mut self, tokens: &[Token], generic_args: &[(String, Token)]) -> String {
+        let items: Vec<String> = tokens
+            .iter()
+            .map(|t| self.type_signature(t, generic_args))
+            .collect();
+
+        format!("({})", items.join(","))
+    }
+}
+
+fn last_segment(path: &str) -> &str {
+    path.rsplit("::").next().unwrap_or(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AbiParser;
+    use std::collections::HashMap;
+
+    const U256: &str = r#"{ "type": "struct", "name": "core::integer::u256", "members": [
+        { "name": "low", "type": "core::integer::u128" },
+        { "name": "high", "type": "core::integer::u128" }
+    ]}"#;
+
+    fn interface_functions(abi: &str, interface: &str) -> (TokenizedAbi, Vec<Function>) {
+        let tokens = AbiParser::tokens_from_abi_string(abi, &HashMap::new()).unwrap();
+
+        let key = tokens.interface_key(interface).unwrap();
+        let functions = tokens.interfaces[key]
+            .iter()
+            .map(|f| f.to_function().unwrap().clone())
+            .collect();
+
+        (tokens, functions)
+    }
+
+    fn function(name: &str, inputs: &str, outputs: &str) -> String {
+        format!(
+            r#"{{ "type": "function", "name": "{name}", "inputs": [{inputs}],
+                "outputs": [{outputs}], "state_mutability": "view" }}"#
+        )
+    }
+
+    #[test]
+    fn test_src5_interface_id() {
+        let abi = format!(
+            r#"[{{ "type": "interface", "name": "openzeppelin::introspection::interface::ISRC5",
+                "items": [{}] }}]"#,
+            function(
+                "supports_interface",
+                r#"{ "name": "interface_id", "type": "core::felt252" }"#,
+                r#"{ "type": "core::bool" }"#
+            )
+        );
+
+        let (tokens, functions) =
+            interface_functions(&abi, "openzeppelin::introspection::interface::ISRC5");
+
+        assert_eq!(
+            function_signature(&functions[0], &tokens),
+            "supports_interface(felt252)->E((),())"
+        );
+        let id = Felt::from_hex_unchecked(
+            "0x3f918d17e5ee77373b56385708f855659a07f75997f365cf87748628532a055",
+        );
+
+        assert_eq!(interface_id(&functions, &tokens), id);
+        assert_eq!(
+            interface_ids(&tokens),
+            vec![(
+                "openzeppelin::introspection::interface::ISRC5".to_string(),
+                id
+            )]
+        );
+    }
+
+    #[test]
+    fn test_erc721_interface_id() {
+        let address =
+            r#"{ "name": "a", "type": "core::starknet::contract_address::ContractAddress" }"#;
+        let token_id = r#"{ "name": "token_id", "type": "core::integer::u256" }"#;
+        let approved = r#"{ "name": "approved", "type": "core::bool" }"#;
+        let data = r#"{ "name": "data", "type": "core::array::Span::<core::felt252>" }"#;
+
+        let items = [
+            function(
+                "balance_of",
+                address,
+                r#"{ "type": "core::integer::u256" }"#,
+            ),
+            function(
+                "owner_of",
+                token_id,
+                r#"{ "type": "core::starknet::contract_address::ContractAddress" }"#,
+            ),
+            function(
+                "safe_transfer_from",
+                &format!("{address}, {address}, {token_id}, {data}"),
+                "",
+            ),
+            function(
+                "transfer_from",
+                &format!("{address}, {address}, {token_id}"),
+                "",
+            ),
+            function("approve", &format!("{address}, {token_id}"), ""),
+            function(
+                "set_approval_for_all",
+                &format!("{address}, {approved}"),
+                "",
+            ),
+            function(
+                "get_approved",
+                token_id,
+                r#"{ "type": "core::starknet::contract_address::ContractAddress" }"#,
+            ),
+            function(
+                "is_approved_for_all",
+                &format!("{address}, {address}"),
+                r#"{ "type": "core::bool" }"#,
+            ),
+        ];
+
+        let abi = format!(
+            r#"[{U256}, {{ "type": "interface", "name": "pkg::IERC721", "items": [{}] }}]"#,
+            items.join(",")
+        );
+
+        let (tokens, functions) = interface_functions(&abi, "pkg::IERC721");
+        let signatures: Vec<String> = functions
+            .iter()
+            .map(|f| function_signature(f, &tokens))
+            .collect();
+
+        assert!(signatures.contains(
+            &"safe_transfer_from(ContractAddress,ContractAddress,(u128,u128),(@Array<felt252>))"
+                .to_string()
+        ));
+        assert_eq!(
+            interface_id(&functions, &tokens),
+            Felt::from_hex_unchecked(
+                "0x33eb2f84c309543403fd69f0d0f363781ef06ef6faeb0131ff16ea3175bd943"
+            )
+        );
+    }
+
+    #[test]
+    fn test_src6_interface_id() {
+        let abi = format!(
+            r#"[
+                {{ "type": "struct", "name": "core::starknet::account::Call", "members": [
+                    {{ "name": "to", "type": "core::starknet::contract_address::ContractAddress" }},
+                    {{ "name": "selector", "type": "core::felt252" }},
+                    {{ "name": "calldata", "type": "core::array::Array::<core::felt252>" }}
+                ]}},
+                {{ "type": "interface", "name": "pkg::ISRC6", "items": [{}, {}, {}] }}
+            ]"#,
+            function(
+                "__execute__",
+                r#"{ "name": "calls", "type": "core::array::Array::<core::starknet::account::Call>" }"#,
+                r#"{ "type": "core::array::Array::<core::array::Span::<core::felt252>>" }"#
+            ),
+            function(
+                "__validate__",
+                r#"{ "name": "calls", "type": "core::array::Array::<core::starknet::account::Call>" }"#,
+                r#"{ "type": "core::felt252" }"#
+            ),
+            function(
+                "is_valid_signature",
+                r#"{ "name": "hash", "type": "core::felt252" },
+                   { "name": "signature", "type": "core::array::Array::<core::felt252>" }"#,
+                r#"{ "type": "core::felt252" }"#
+            ),
+        );
+
+        let (tokens, functions) = interface_functions(&abi, "pkg::ISRC6");
+
+        assert_eq!(
+            interface_id(&functions, &tokens),
+            Felt::from_hex_unchecked(
+                "0x2ceccef7f994940b3962a6c67e0ba4fcd37df7d131417c604f91e03caecc1cd"
+            )
+        );
+    }
+
+    #[test]
+    fn test_function_signature_enums_and_generics() {
+        let abi = format!(
+            r#"[
+                {{ "type": "enum", "name": "core::option::Option::<core::felt252>", "variants": [
+                    {{ "name": "Some", "type": "core::felt252" }},
+                    {{ "name": "None", "type": "()" }}
+                ]}},
+                {{ "type": "struct", "name": "pkg::Pair::<core::integer::u8>", "members": [
+                    {{ "name": "a", "type": "core::integer::u8" }},
+                    {{ "name": "b", "type": "core::felt252" }}
+                ]}},
+                {{ "type": "interface", "name": "pkg::IPairs", "items": [{}] }}
+            ]"#,
+            function(
+                "get",
+                r#"{ "name": "key", "type": "core::option::Option::<core::felt252>" },
+                   { "name": "pair", "type": "pkg::Pair::<core::integer::u8>" },
+                   { "name": "t", "type": "(core::integer::u8, ())" }"#,
+                ""
+            ),
+        );
+
+        let (tokens, functions) = interface_functions(&abi, "pkg::IPairs");
+
+        assert_eq!(
+            function_signature(&functions[0], &tokens),
+            "get(E(felt252,()),(u8,felt252),(u8,()))"
+        );
+    }
+}
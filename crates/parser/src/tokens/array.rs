@@ -51,6 +51,11 @@ impl Array {
         )))
     }
 
+    /// Returns whether the array is a `Span<T>`, and not an `Array<T>` (or a cairo 0 array).
+    pub fn is_span(&self) -> bool {
+        self.type_path.starts_with(CAIRO_CORE_SPAN_ARRAY[0])
+    }
+
     pub fn resolve_generic(&self, generic_name: &str, generic_type_path: &str) -> Token {
         if self.type_path == generic_type_path {
             Token::GenericArg(generic_name.to_string())
@@ -87,6 +92,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_is_span() {
+        assert!(Array::parse("core::array::Span::<core::felt252>")
+            .unwrap()
+            .is_span());
+        assert!(!Array::parse("core::array::Array::<core::felt252>")
+            .unwrap()
+            .is_span());
+        assert!(!Array::parse("core::felt*").unwrap().is_span());
+    }
+
     #[test]
     fn test_parse_no_inner_invalid() {
         assert!(Array::parse("core::array::Array").is_err());
@@ -1,4 +1,4 @@
-use super::constants::{CAIRO_COMPOSITE_BUILTINS, CAIRO_GENERIC_BUILTINS};
+use super::constants::{CAIRO_COMPOSITE_BUILTINS, CAIRO_GENERIC_BUILTINS, RUST_PRELUDE_NAMES};
 use super::genericity;
 use super::Token;
 
@@ -54,8 +54,30 @@ impl Composite {
         })
     }
 
+    /// Returns the type path without the generic type arguments.
+    ///
+    /// The const generic arguments are kept (e.g. `pkg::Buffer::<3>` for
+    /// `pkg::Buffer::<core::felt252, 3>`), since each set of constants
+    /// identifies a distinct type.
     pub fn type_path_no_generic(&self) -> String {
-        genericity::type_path_no_generic(&self.type_path)
+        let type_path = genericity::type_path_no_generic(&self.type_path);
+        let const_args = self.const_args();
+
+        if const_args.is_empty() {
+            type_path
+        } else {
+            format!("{}::<{}>", type_path, const_args.join(", "))
+        }
+    }
+
+    /// Returns the const generic arguments of the type, see [`genericity::extract_const_args`].
+    pub fn const_args(&self) -> Vec<String> {
+        // Most types have no generic arguments at all, which avoids parsing them.
+        if !self.type_path.contains('<') {
+            return vec![];
+        }
+
+        genericity::extract_const_args(&self.type_path)
     }
 
     pub fn is_generic(&self) -> bool {
@@ -84,7 +106,16 @@ impl Composite {
 
     pub fn type_name(&self) -> String {
         // TODO: need to opti that with regex?
-        extract_type_path_with_depth(&self.type_path_no_generic(), 0)
+        let type_name =
+            extract_type_path_with_depth(&genericity::type_path_no_generic(&self.type_path), 0);
+
+        // The constants are part of the name, to have a distinct type for each set of constants.
+        let const_args = self.const_args();
+        if const_args.is_empty() {
+            type_name
+        } else {
+            format!("{}{}", type_name, const_args.join("_"))
+        }
     }
 
     pub fn type_name_or_alias(&self) -> String {
@@ -95,15 +126,31 @@ impl Composite {
         }
     }
 
+    /// Returns the alias to use if the type name collides with a name of the Rust
+    /// prelude (like `Option` or `Result`), which would be shadowed by the generated type.
+    ///
+    /// The alias is the type name prefixed by `Cairo` (e.g. `CairoOption`).
+    pub fn prelude_alias(&self) -> Option<String> {
+        let type_name = self.type_name();
+
+        if !self.is_builtin() && RUST_PRELUDE_NAMES.contains(&type_name.as_str()) {
+            Some(format!("Cairo{}", type_name))
+        } else {
+            None
+        }
+    }
+
     pub fn apply_alias(&mut self, type_path: &str, alias: &str) {
         if self.type_path_no_generic() == type_path {
             self.alias = Some(alias.to_string());
         }
 
-        for ref mut i in &mut self.inners {
-            if let Token::Composite(ref mut c) = i.token {
-                c.apply_alias(type_path, alias);
-            }
+        for i in &mut self.inners {
+            i.token.apply_alias(type_path, alias);
+        }
+
+        for (_, g) in &mut self.generic_args {
+            g.apply_alias(type_path, alias);
         }
     }
 
@@ -149,7 +196,7 @@ pub fn snake_to_pascal_case(s: &str) -> String {
 
 /// Escapes Rust keywords that may be found into cairo code.
 pub fn escape_rust_keywords(s: &str) -> String {
-    let keywords = ["move", "type", "final"];
+    let keywords = ["move", "type", "final", "box"];
 
     let mut s = s.to_string();
 
@@ -22,15 +22,60 @@ pub const CAIRO_CORE_BASIC: [&str; 17] = [
 // to match array pattern.
 pub const CAIRO_CORE_SPAN_ARRAY: [&str; 2] = ["core::array::Span", "core::array::Array"];
 
-pub const CAIRO_GENERIC_BUILTINS: [&str; 4] = [
+pub const CAIRO_GENERIC_BUILTINS: [&str; 5] = [
     "core::option::Option",
+    // `box` being a Rust keyword, the type path is escaped.
+    "core::r#box::Box",
     "core::result::Result",
     "core::zeroable::NonZero",
     "core::internal::bounded_int::BoundedInt",
 ];
 
-pub const CAIRO_COMPOSITE_BUILTINS: [&str; 3] = [
+pub const CAIRO_COMPOSITE_BUILTINS: [&str; 9] = [
     "core::byte_array::ByteArray",
     "core::starknet::eth_address::EthAddress",
     "core::integer::u256",
+    "core::starknet::info::BlockInfo",
+    "core::starknet::info::TxInfo",
+    "core::starknet::info::ExecutionInfo",
+    "core::starknet::info::v2::ResourcesBounds",
+    "core::starknet::info::v2::TxInfo",
+    "core::starknet::info::v2::ExecutionInfo",
+];
+
+/// Names of the Rust prelude (and of the std types used by the generated code)
+/// that a generated type must not shadow.
+pub const RUST_PRELUDE_NAMES: [&str; 32] = [
+    "Option",
+    "Result",
+    "Box",
+    "String",
+    "Vec",
+    "ToString",
+    "ToOwned",
+    "Clone",
+    "Copy",
+    "Default",
+    "Drop",
+    "Eq",
+    "PartialEq",
+    "Ord",
+    "PartialOrd",
+    "Send",
+    "Sync",
+    "Sized",
+    "Unpin",
+    "Fn",
+    "FnMut",
+    "FnOnce",
+    "From",
+    "Into",
+    "TryFrom",
+    "TryInto",
+    "AsRef",
+    "AsMut",
+    "Iterator",
+    "IntoIterator",
+    "Extend",
+    "FromIterator",
 ];
@@ -37,16 +37,16 @@ impl Function {
     }
 
     pub fn apply_alias(&mut self, type_path: &str, alias: &str) {
-        for (_, ref mut t) in &mut self.inputs {
-            if let Token::Composite(ref mut c) = t {
-                c.apply_alias(type_path, alias);
-            }
+        for (_, t) in &mut self.inputs {
+            t.apply_alias(type_path, alias);
         }
 
-        for ref mut t in &mut self.outputs {
-            if let Token::Composite(ref mut c) = t {
-                c.apply_alias(type_path, alias);
-            }
+        for t in &mut self.outputs {
+            t.apply_alias(type_path, alias);
+        }
+
+        for (_, t) in &mut self.named_outputs {
+            t.apply_alias(type_path, alias);
         }
     }
 
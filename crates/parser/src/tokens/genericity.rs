@@ -1,4 +1,4 @@
-use syn::{GenericArgument, PathArguments, Type};
+use syn::{Expr, GenericArgument, Lit, PathArguments, Type, UnOp};
 
 use super::Token;
 use crate::CainomeResult;
@@ -18,7 +18,10 @@ pub fn extract_generics_args(type_path: &str) -> CainomeResult<Vec<(String, Toke
                 for arg in &args.args {
                     if let GenericArgument::Type(ty) = arg {
                         let arg_name = ((ascii + i as u8) as char).to_string();
-                        let arg_str = quote::quote!(#ty).to_string().replace(' ', "");
+                        // Inside a proc-macro, the compiler may also wrap long paths on several lines.
+                        let arg_str = quote::quote!(#ty)
+                            .to_string()
+                            .replace(char::is_whitespace, "");
                         generic_args.push((arg_name, Token::parse(&arg_str)?));
                         i += 1;
                     }
@@ -30,6 +33,44 @@ pub fn extract_generics_args(type_path: &str) -> CainomeResult<Vec<(String, Toke
     Ok(generic_args)
 }
 
+/// Extracts the const generic arguments of a type path, like `3` in
+/// `pkg::Buffer::<core::felt252, 3>`, as strings usable in an identifier.
+///
+/// Integers are given in decimal, and negative integers are prefixed by `Neg`.
+pub fn extract_const_args(type_path: &str) -> Vec<String> {
+    let Ok(Type::Path(p)) = syn::parse_str::<Type>(type_path) else {
+        return vec![];
+    };
+
+    let Some(PathArguments::AngleBracketed(args)) = p.path.segments.last().map(|s| &s.arguments)
+    else {
+        return vec![];
+    };
+
+    fn const_to_string(expr: &Expr) -> Option<String> {
+        match expr {
+            Expr::Lit(l) => match &l.lit {
+                Lit::Int(i) => Some(i.base10_digits().replace('-', "Neg")),
+                Lit::Bool(b) => Some(b.value.to_string()),
+                _ => None,
+            },
+            Expr::Unary(u) if matches!(u.op, UnOp::Neg(_)) => {
+                const_to_string(&u.expr).map(|s| format!("Neg{}", s))
+            }
+            Expr::Paren(p) => const_to_string(&p.expr),
+            _ => None,
+        }
+    }
+
+    args.args
+        .iter()
+        .filter_map(|arg| match arg {
+            GenericArgument::Const(expr) => const_to_string(expr),
+            _ => None,
+        })
+        .collect()
+}
+
 pub fn type_path_no_generic(type_path: &str) -> String {
     let frags: Vec<&str> = type_path.split('<').collect();
     frags
@@ -53,4 +94,23 @@ mod tests {
             "module::TypeName"
         );
     }
+
+    #[test]
+    fn test_extract_const_args() {
+        assert!(extract_const_args("module::TypeName").is_empty());
+        assert!(extract_const_args("module::TypeName::<core::felt252>").is_empty());
+        assert_eq!(extract_const_args("module::TypeName::<3>"), vec!["3"]);
+        assert_eq!(
+            extract_const_args("module::TypeName::<core::felt252, 0x10, -2, true>"),
+            vec!["16", "Neg2", "true"]
+        );
+    }
+
+    #[test]
+    fn test_extract_generics_args_with_consts() {
+        let args = extract_generics_args("module::TypeName::<core::felt252, 3>").unwrap();
+        assert_eq!(args.len(), 1);
+        assert_eq!(args[0].0, "A");
+        assert_eq!(args[0].1.type_path(), "core::felt252");
+    }
 }
@@ -5,15 +5,16 @@
 mod array;
 mod basic;
 mod composite;
-mod constants;
+pub(crate) mod constants;
 mod function;
-mod genericity;
+pub(crate) mod genericity;
 mod tuple;
 
 use std::collections::HashMap;
 
 pub use array::Array;
 pub use basic::CoreBasic;
+pub(crate) use composite::extract_type_path_with_depth;
 pub use composite::{Composite, CompositeInner, CompositeInnerKind, CompositeType};
 pub use function::{Function, FunctionOutputKind, StateMutability};
 pub use tuple::Tuple;
@@ -76,6 +77,15 @@ impl Token {
         }
     }
 
+    /// Returns the type path with the generic arguments of the composites
+    /// (`pkg::Wrapper::<core::felt252>`, where [`Token::type_path`] returns `pkg::Wrapper`).
+    pub fn type_path_with_generics(&self) -> String {
+        match self {
+            Token::Composite(t) => t.type_path.to_string(),
+            _ => self.type_path(),
+        }
+    }
+
     // TODO: we may remove these two functions...! And change types somewhere..
     pub fn to_composite(&self) -> CainomeResult<&Composite> {
         match self {
@@ -124,6 +134,49 @@ impl Token {
         }
     }
 
+    /// Recursively replaces the tuples of the given type path by `replacement`.
+    ///
+    /// The type paths are compared without their whitespaces, `(core::felt252, core::felt252)`
+    /// matching `(core::felt252,core::felt252)`.
+    ///
+    /// # Arguments
+    ///
+    /// * `tuple_type_path` - The type path of the tuples to replace.
+    /// * `replacement` - The token replacing the tuples.
+    pub fn replace_tuple(&mut self, tuple_type_path: &str, replacement: &Token) {
+        match self {
+            Token::Tuple(t) => {
+                if without_whitespaces(&t.type_path) == without_whitespaces(tuple_type_path) {
+                    *self = replacement.clone();
+                } else {
+                    for i in &mut t.inners {
+                        i.replace_tuple(tuple_type_path, replacement);
+                    }
+                }
+            }
+            Token::Array(t) => t.inner.replace_tuple(tuple_type_path, replacement),
+            Token::Composite(t) => {
+                for i in &mut t.inners {
+                    i.token.replace_tuple(tuple_type_path, replacement);
+                }
+
+                for (_, g) in &mut t.generic_args {
+                    g.replace_tuple(tuple_type_path, replacement);
+                }
+            }
+            Token::Function(t) => {
+                for (_, i) in t.inputs.iter_mut().chain(t.named_outputs.iter_mut()) {
+                    i.replace_tuple(tuple_type_path, replacement);
+                }
+
+                for o in &mut t.outputs {
+                    o.replace_tuple(tuple_type_path, replacement);
+                }
+            }
+            Token::CoreBasic(_) | Token::GenericArg(_) => (),
+        }
+    }
+
     /// Recursively hydrates nested tokens
     ///
     /// Once abi is parsed, a flat list of tokens defined in cairo code is generated from parsed
@@ -132,31 +185,31 @@ impl Token {
     /// Some tokens can have nested tokens that may not have inners defined inside thus leading to
     /// confusion while using tokens. i.e Enums does not have inner variants defined.
     ///
+    /// A recursive type (like a tree containing an array of its own type) is hydrated once:
+    /// its occurrences inside itself are left as they are defined.
+    ///
+    /// Fails if more than `recursion_max_depth` composites are nested, with the chain of
+    /// their type paths.
+    ///
     /// # Arguments
     ///
     /// * `token` - The token to hydrate.
     /// * `filtered` - A map of type path to token that have already been hydrated.
-    /// * `recursion_max_depth` - Max depth recursion for token to hydrate.
-    /// * `iteration_count` - Current iteration count.
+    /// * `recursion_max_depth` - Max number of nested composites to hydrate.
+    /// * `chain` - The type paths of the composites being hydrated, outermost first.
     ///
     pub fn hydrate(
         token: Self,
         filtered: &HashMap<String, Token>,
         recursion_max_depth: usize,
-        iteration_count: usize,
-    ) -> Self {
-        if recursion_max_depth < iteration_count {
-            return token;
-        }
-        match token {
+        chain: &mut Vec<String>,
+    ) -> CainomeResult<Self> {
+        let mut hydrate = |token| Self::hydrate(token, filtered, recursion_max_depth, chain);
+
+        Ok(match token {
             Token::CoreBasic(_) | Token::GenericArg(_) => token,
             Token::Array(arr) => Token::Array(Array {
-                inner: Box::new(Self::hydrate(
-                    *arr.inner,
-                    filtered,
-                    recursion_max_depth,
-                    iteration_count + 1,
-                )),
+                inner: Box::new(hydrate(*arr.inner)?),
                 type_path: arr.type_path,
                 is_legacy: arr.is_legacy,
             }),
@@ -164,103 +217,135 @@ impl Token {
                 inners: tup
                     .inners
                     .into_iter()
-                    .map(|inner| {
-                        Self::hydrate(inner, filtered, recursion_max_depth, iteration_count + 1)
-                    })
-                    .collect(),
+                    .map(&mut hydrate)
+                    .collect::<CainomeResult<_>>()?,
                 type_path: tup.type_path,
             }),
             Token::Composite(comp) => {
                 if comp.r#type == CompositeType::Unknown && !comp.is_builtin() {
-                    if let Some(hydrated) = filtered.get(&comp.type_path) {
-                        return Token::hydrate(
-                            hydrated.clone(),
-                            filtered,
-                            recursion_max_depth,
-                            iteration_count + 1,
-                        );
-                    } else {
-                        panic!("Composite {} not found in filtered tokens", comp.type_path);
-                    }
+                    return match filtered.get(&comp.type_path) {
+                        Some(hydrated) => hydrate(hydrated.clone()),
+                        // The type is not defined in the ABI, it's kept unresolved
+                        // to be reported by `TokenizedAbi::unresolved_types`.
+                        None => Ok(Token::Composite(comp)),
+                    };
                 }
-                Token::Composite(Composite {
-                    type_path: comp.type_path,
-                    inners: comp
-                        .inners
-                        .into_iter()
-                        .map(|i| CompositeInner {
-                            index: i.index,
-                            name: i.name,
-                            kind: i.kind,
-                            token: Self::hydrate(
-                                i.token,
-                                filtered,
-                                recursion_max_depth,
-                                iteration_count + 1,
-                            ),
-                        })
-                        .collect(),
-                    generic_args: comp
-                        .generic_args
-                        .into_iter()
-                        .map(|(name, token)| {
-                            (
-                                name,
-                                Self::hydrate(
-                                    token,
-                                    filtered,
-                                    recursion_max_depth,
-                                    iteration_count + 1,
-                                ),
-                            )
-                        })
-                        .collect(),
-                    r#type: comp.r#type,
-                    is_event: comp.is_event,
-                    alias: comp.alias,
-                })
+
+                // The type is being hydrated: it's recursive.
+                if chain.contains(&comp.type_path) {
+                    return Ok(Token::Composite(comp));
+                }
+
+                if chain.len() >= recursion_max_depth {
+                    let mut chain = chain.clone();
+                    chain.push(comp.type_path);
+
+                    return Err(Error::HydrationDepthExceeded {
+                        max_depth: recursion_max_depth,
+                        chain,
+                    });
+                }
+
+                chain.push(comp.type_path.clone());
+
+                let hydrated = Self::hydrate_composite(comp, filtered, recursion_max_depth, chain);
+
+                chain.pop();
+
+                Token::Composite(hydrated?)
             }
             Token::Function(func) => Token::Function(Function {
                 name: func.name,
                 inputs: func
                     .inputs
                     .into_iter()
-                    .map(|(name, token)| {
-                        (
-                            name,
-                            Self::hydrate(
-                                token,
-                                filtered,
-                                recursion_max_depth,
-                                iteration_count + 1,
-                            ),
-                        )
-                    })
-                    .collect(),
+                    .map(|(name, token)| Ok((name, hydrate(token)?)))
+                    .collect::<CainomeResult<_>>()?,
                 outputs: func
                     .outputs
                     .into_iter()
-                    .map(|token| {
-                        Self::hydrate(token, filtered, recursion_max_depth, iteration_count + 1)
-                    })
-                    .collect(),
+                    .map(&mut hydrate)
+                    .collect::<CainomeResult<_>>()?,
                 named_outputs: func
                     .named_outputs
                     .into_iter()
-                    .map(|(name, token)| {
-                        (
-                            name,
-                            Self::hydrate(
-                                token,
-                                filtered,
-                                recursion_max_depth,
-                                iteration_count + 1,
-                            ),
-                        )
-                    })
-                    .collect(),
+                    .map(|(name, token)| Ok((name, hydrate(token)?)))
+                    .collect::<CainomeResult<_>>()?,
                 state_mutability: func.state_mutability,
             }),
+        })
+    }
+
+    /// Hydrates the inners and the generic arguments of a composite.
+    fn hydrate_composite(
+        comp: Composite,
+        filtered: &HashMap<String, Token>,
+        recursion_max_depth: usize,
+        chain: &mut Vec<String>,
+    ) -> CainomeResult<Composite> {
+        let mut hydrate = |token| Self::hydrate(token, filtered, recursion_max_depth, chain);
+
+        Ok(Composite {
+            type_path: comp.type_path,
+            inners: comp
+                .inners
+                .into_iter()
+                .map(|i| {
+                    Ok(CompositeInner {
+                        index: i.index,
+                        name: i.name,
+                        kind: i.kind,
+                        token: hydrate(i.token)?,
+                    })
+                })
+                .collect::<CainomeResult<_>>()?,
+            generic_args: comp
+                .generic_args
+                .into_iter()
+                .map(|(name, token)| Ok((name, hydrate(token)?)))
+                .collect::<CainomeResult<_>>()?,
+            r#type: comp.r#type,
+            is_event: comp.is_event,
+            alias: comp.alias,
+        })
+    }
+}
+
+fn without_whitespaces(s: &str) -> String {
+    s.chars().filter(|c| !c.is_whitespace()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replace_tuple() {
+        let replacement = Token::Composite(Composite::parse("pkg::Vec3").unwrap());
+
+        let mut token =
+            Token::parse("core::array::Array::<(core::felt252,core::felt252,core::felt252)>")
+                .unwrap();
+        token.replace_tuple(
+            "(core::felt252, core::felt252, core::felt252)",
+            &replacement,
+        );
+
+        match token {
+            Token::Array(a) => assert_eq!(*a.inner, replacement),
+            _ => panic!("array expected"),
+        }
+
+        // The nested tuples are replaced, not the other ones.
+        let mut token = Token::parse("((core::felt252,core::felt252,core::felt252),u8)").unwrap();
+        token.replace_tuple("(core::felt252,core::felt252,core::felt252)", &replacement);
+
+        match token {
+            Token::Tuple(t) => {
+                assert_eq!(t.inners[0], replacement);
+                assert_eq!(t.inners[1].type_path(), "u8");
+            }
+            _ => panic!("tuple expected"),
         }
     }
 }
@@ -113,6 +113,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_long_tuple() {
+        let inners: Vec<String> = (0..16)
+            .map(|i| match i % 4 {
+                0 => "core::felt252".to_string(),
+                1 => "core::integer::u64".to_string(),
+                2 => "core::array::Span::<(core::felt252, core::bool)>".to_string(),
+                _ => format!("mod1::MyStruct{}", i),
+            })
+            .collect();
+        let type_path = format!("({})", inners.join(", "));
+
+        let t = Tuple::parse(&type_path).unwrap();
+        assert_eq!(t.type_path, type_path);
+        assert_eq!(t.inners.len(), 16);
+        assert!(matches!(t.inners[2], Token::Array(_)));
+        assert_eq!(t.inners[15].type_path(), "mod1::MyStruct15");
+
+        let token = Token::parse(&format!("core::array::Array::<{}>", type_path)).unwrap();
+        match token {
+            Token::Array(a) => match *a.inner {
+                // Nested type paths are normalized without spaces.
+                Token::Tuple(inner) => assert_eq!(inner.inners, t.inners),
+                _ => panic!("Expected tuple, got {:?}", a.inner),
+            },
+            _ => panic!("Expected array, got {:?}", token),
+        }
+    }
+
     #[test]
     fn test_parse_other_type_invalid() {
         assert!(Tuple::parse("module::module2::MyStuct").is_err());
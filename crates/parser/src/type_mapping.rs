@@ -0,0 +1,127 @@
+//! Mapping of the Cairo builtin types to the types of the backends.
+//!
+//! This is the canonical table of the builtin types, with a column for each
+//! backend. Adding a builtin type is adding a row to [`BUILTIN_TYPES`], which
+//! must cover all the builtins recognized by the parser.
+use crate::tokens::genericity;
+
+/// A Cairo builtin type, with the type of each backend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BuiltinType {
+    /// The Cairo type path, without the generic arguments.
+    pub cairo: &'static str,
+    /// The Rust type, where `{ccs}` is the path of `cainome_cairo_serde`
+    /// and `{snrs}` the path of the `starknet-rs` types. The generic
+    /// arguments are added by the backend.
+    pub rust: &'static str,
+}
+
+impl BuiltinType {
+    const fn new(cairo: &'static str, rust: &'static str) -> Self {
+        Self { cairo, rust }
+    }
+}
+
+/// The builtin types, the arrays and spans excepted.
+pub const BUILTIN_TYPES: [BuiltinType; 31] = [
+    // Basic types.
+    BuiltinType::new("felt", "{snrs}::Felt"),
+    BuiltinType::new("core::felt252", "{snrs}::Felt"),
+    BuiltinType::new("core::bool", "bool"),
+    BuiltinType::new("core::integer::u8", "u8"),
+    BuiltinType::new("core::integer::u16", "u16"),
+    BuiltinType::new("core::integer::u32", "u32"),
+    BuiltinType::new("core::integer::u64", "u64"),
+    BuiltinType::new("core::integer::u128", "u128"),
+    BuiltinType::new("core::integer::usize", "usize"),
+    BuiltinType::new("core::integer::i8", "i8"),
+    BuiltinType::new("core::integer::i16", "i16"),
+    BuiltinType::new("core::integer::i32", "i32"),
+    BuiltinType::new("core::integer::i64", "i64"),
+    BuiltinType::new("core::integer::i128", "i128"),
+    BuiltinType::new(
+        "core::starknet::contract_address::ContractAddress",
+        "{ccs}::ContractAddress",
+    ),
+    BuiltinType::new("core::starknet::class_hash::ClassHash", "{ccs}::ClassHash"),
+    BuiltinType::new("core::bytes_31::bytes31", "{ccs}::Bytes31"),
+    // Generic types.
+    BuiltinType::new("core::option::Option", "Option"),
+    BuiltinType::new("core::r#box::Box", "Box"),
+    BuiltinType::new("core::result::Result", "Result"),
+    BuiltinType::new("core::zeroable::NonZero", "{ccs}::NonZero"),
+    // <https://github.com/starkware-libs/cairo/blob/35b299291fd7819f75409fb303ece7d30e4adb19/corelib/src/internal/bounded_int.cairo#L5>
+    BuiltinType::new("core::internal::bounded_int::BoundedInt", "{snrs}::Felt"),
+    // Composite types.
+    BuiltinType::new("core::byte_array::ByteArray", "{ccs}::ByteArray"),
+    BuiltinType::new(
+        "core::starknet::eth_address::EthAddress",
+        "{ccs}::EthAddress",
+    ),
+    BuiltinType::new("core::integer::u256", "{ccs}::U256"),
+    BuiltinType::new("core::starknet::info::BlockInfo", "{ccs}::BlockInfo"),
+    BuiltinType::new("core::starknet::info::TxInfo", "{ccs}::TxInfo"),
+    BuiltinType::new(
+        "core::starknet::info::ExecutionInfo",
+        "{ccs}::ExecutionInfo",
+    ),
+    BuiltinType::new(
+        "core::starknet::info::v2::ResourcesBounds",
+        "{ccs}::ResourcesBounds",
+    ),
+    BuiltinType::new("core::starknet::info::v2::TxInfo", "{ccs}::TxInfoV2"),
+    BuiltinType::new(
+        "core::starknet::info::v2::ExecutionInfo",
+        "{ccs}::ExecutionInfoV2",
+    ),
+];
+
+/// Returns the builtin type of the given type path, if any.
+///
+/// # Arguments
+///
+/// * `type_path` - The Cairo type path, with or without the generic arguments.
+pub fn builtin_type(type_path: &str) -> Option<&'static BuiltinType> {
+    let type_path = genericity::type_path_no_generic(type_path);
+
+    BUILTIN_TYPES.iter().find(|b| b.cairo == type_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tokens::constants::{
+        CAIRO_COMPOSITE_BUILTINS, CAIRO_CORE_BASIC, CAIRO_GENERIC_BUILTINS,
+    };
+
+    #[test]
+    fn test_builtin_types_cover_the_parser_builtins() {
+        let builtins = CAIRO_CORE_BASIC
+            .iter()
+            .chain(CAIRO_GENERIC_BUILTINS.iter())
+            .chain(CAIRO_COMPOSITE_BUILTINS.iter());
+
+        for b in builtins {
+            assert!(builtin_type(b).is_some(), "{} has no row", b);
+        }
+
+        assert_eq!(
+            BUILTIN_TYPES.len(),
+            CAIRO_CORE_BASIC.len() + CAIRO_GENERIC_BUILTINS.len() + CAIRO_COMPOSITE_BUILTINS.len()
+        );
+    }
+
+    #[test]
+    fn test_builtin_type() {
+        assert_eq!(
+            builtin_type("core::option::Option::<core::felt252>").map(|b| b.rust),
+            Some("Option")
+        );
+        assert_eq!(
+            builtin_type("core::integer::u256").map(|b| b.rust),
+            Some("{ccs}::U256")
+        );
+        assert_eq!(builtin_type("pkg::U256"), None);
+        assert_eq!(builtin_type("core::array::Array::<core::felt252>"), None);
+    }
+}
@@ -0,0 +1,151 @@
+//! On-disk cache of the `abigen!` expansions.
+//!
+//! In a workspace, the same ABI is often used by several crates, each one
+//! reading, parsing and expanding it again. When the `CAINOME_ABIGEN_CACHE_DIR`
+//! environment variable is set, the expanded code is stored in this directory,
+//! keyed by a hash of the ABI, the version of cainome and the macro options.
+//!
+//! The cache is best effort: any error while reading or writing it
+//! falls back to the regular expansion.
+use cainome_rs::ExpandOptions;
+use proc_macro2::TokenStream as TokenStream2;
+use starknet::core::utils::starknet_keccak;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::PathBuf;
+
+/// The environment variable enabling the cache, with the path of the cache directory.
+pub(crate) const CACHE_DIR_ENV: &str = "CAINOME_ABIGEN_CACHE_DIR";
+
+#[derive(Debug, Clone)]
+pub(crate) struct AbigenCache {
+    dir: PathBuf,
+}
+
+impl AbigenCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Returns the cache configured with the environment, if any.
+    pub fn from_env() -> Option<Self> {
+        std::env::var_os(CACHE_DIR_ENV)
+            .filter(|d| !d.is_empty())
+            .map(Self::new)
+    }
+
+    /// Returns the cached expansion for the given key.
+    pub fn get(&self, key: &str) -> Option<TokenStream2> {
+        fs::read_to_string(self.entry_path(key)).ok()?.parse().ok()
+    }
+
+    /// Stores the expansion for the given key.
+    ///
+    /// The content is first written in a temporary file that is then renamed,
+    /// since several crates may be compiled concurrently.
+    pub fn put(&self, key: &str, tokens: &TokenStream2) {
+        if fs::create_dir_all(&self.dir).is_err() {
+            return;
+        }
+
+        let tmp = self.dir.join(format!("{}.{}.tmp", key, std::process::id()));
+
+        if fs::write(&tmp, tokens.to_string()).is_err()
+            || fs::rename(&tmp, self.entry_path(key)).is_err()
+        {
+            let _ = fs::remove_file(&tmp);
+        }
+    }
+
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.rs", key))
+    }
+}
+
+/// Computes the cache key of an expansion.
+///
+/// # Arguments
+///
+/// * `contract_name` - The name of the contract.
+/// * `abi_json` - The JSON of the ABI, as given to the macro.
+/// * `type_aliases` - The type aliases.
+/// * `options` - The expand options.
+pub(crate) fn cache_key(
+    contract_name: &str,
+    abi_json: &str,
+    type_aliases: &HashMap<String, String>,
+    options: &ExpandOptions,
+) -> String {
+    // The hash maps are sorted, for the key to be deterministic.
+    let type_aliases: BTreeMap<_, _> = type_aliases.iter().collect();
+    let type_derives: BTreeMap<_, _> = options.type_derives.iter().collect();
+    let options = ExpandOptions {
+        type_derives: HashMap::new(),
+        ..options.clone()
+    };
+
+    let mut data = format!(
+        "{}\n{}\n{:?}\n{:?}\n{:?}\n",
+        env!("CARGO_PKG_VERSION"),
+        contract_name,
+        type_aliases,
+        type_derives,
+        options
+    )
+    .into_bytes();
+    data.extend(abi_json.as_bytes());
+
+    format!("{:x}", starknet_keccak(&data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quote::quote;
+
+    #[test]
+    fn test_cache_key() {
+        let pairs: Vec<(String, String)> = (0..16)
+            .map(|i| (format!("pkg{}::A", i), format!("A{}", i)))
+            .collect();
+        let aliases: HashMap<_, _> = pairs.iter().cloned().collect();
+        let aliases_rev: HashMap<_, _> = pairs.iter().rev().cloned().collect();
+        let options = ExpandOptions::default();
+
+        let key = cache_key("MyContract", "[]", &aliases, &options);
+
+        // The insertion order of the aliases doesn't matter.
+        assert_eq!(key, cache_key("MyContract", "[]", &aliases_rev, &options));
+        assert_ne!(key, cache_key("Other", "[]", &aliases, &options));
+        assert_ne!(key, cache_key("MyContract", "[ ]", &aliases, &options));
+        assert_ne!(
+            key,
+            cache_key("MyContract", "[]", &HashMap::new(), &options)
+        );
+
+        let blocking = ExpandOptions {
+            blocking: true,
+            ..Default::default()
+        };
+        assert_ne!(key, cache_key("MyContract", "[]", &aliases, &blocking));
+    }
+
+    #[test]
+    fn test_cache_get_put() {
+        let dir = std::env::temp_dir().join(format!("cainome-cache-{}", std::process::id()));
+        let cache = AbigenCache::new(&dir);
+
+        assert!(cache.get("key").is_none());
+
+        let tokens = quote! {
+            pub struct MyStruct {
+                pub a: starknet::core::types::Felt,
+            }
+        };
+        cache.put("key", &tokens);
+
+        assert_eq!(cache.get("key").unwrap().to_string(), tokens.to_string());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
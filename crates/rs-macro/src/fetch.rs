@@ -0,0 +1,153 @@
+//! Fetching of the ABI from a URL at build time.
+//!
+//! As the network access during a build is not expected by default, it must be
+//! allowed with the `CAINOME_ABIGEN_ALLOW_NETWORK` environment variable.
+//! The fetched ABIs are stored locally, and then reused without any network
+//! access, in the `fetched` sub-directory of `CAINOME_ABIGEN_CACHE_DIR` if set,
+//! or in the temporary directory otherwise.
+use sha2::{Digest, Sha256};
+use starknet::core::utils::starknet_keccak;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::cache::CACHE_DIR_ENV;
+
+/// The environment variable allowing the network access, when set to `1` or `true`.
+pub(crate) const ALLOW_NETWORK_ENV: &str = "CAINOME_ABIGEN_ALLOW_NETWORK";
+
+/// Fetches the ABI from the given URL, with the configuration from the environment.
+///
+/// # Arguments
+///
+/// * `url` - The URL of the ABI.
+/// * `sha256` - The expected SHA-256 of the ABI, if any.
+pub(crate) fn fetch_abi_from_env(url: &str, sha256: Option<&str>) -> Result<String, String> {
+    let allow_network = std::env::var(ALLOW_NETWORK_ENV)
+        .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+        .unwrap_or(false);
+
+    let cache_dir = match std::env::var_os(CACHE_DIR_ENV).filter(|d| !d.is_empty()) {
+        Some(dir) => PathBuf::from(dir).join("fetched"),
+        None => std::env::temp_dir().join("cainome-abigen").join("fetched"),
+    };
+
+    fetch_abi(url, sha256, &cache_dir, allow_network)
+}
+
+/// Fetches the ABI from the given URL.
+///
+/// A locally stored copy of the ABI is used if any (and if it matches the expected hash),
+/// otherwise the ABI is fetched if the network access is allowed.
+///
+/// # Arguments
+///
+/// * `url` - The URL of the ABI.
+/// * `sha256` - The expected SHA-256 of the ABI, if any.
+/// * `cache_dir` - The directory where the fetched ABIs are stored.
+/// * `allow_network` - Whether the network access is allowed.
+pub(crate) fn fetch_abi(
+    url: &str,
+    sha256: Option<&str>,
+    cache_dir: &Path,
+    allow_network: bool,
+) -> Result<String, String> {
+    let cache_path = cache_dir.join(format!("{:x}.json", starknet_keccak(url.as_bytes())));
+
+    if let Ok(content) = fs::read_to_string(&cache_path) {
+        if sha256.map_or(true, |h| check_sha256(&content, h).is_ok()) {
+            return Ok(content);
+        }
+    }
+
+    if !allow_network {
+        return Err(format!(
+            "fetching the ABI from {} requires the network access, allowed by setting {}=1",
+            url, ALLOW_NETWORK_ENV
+        ));
+    }
+
+    let content = http_get(url)?;
+
+    if let Some(h) = sha256 {
+        check_sha256(&content, h)?;
+    }
+
+    // Storing the ABI is best effort, it will be fetched again otherwise.
+    if fs::create_dir_all(cache_dir).is_ok() {
+        let tmp = cache_path.with_extension(format!("{}.tmp", std::process::id()));
+
+        if fs::write(&tmp, &content).is_err() || fs::rename(&tmp, &cache_path).is_err() {
+            let _ = fs::remove_file(&tmp);
+        }
+    }
+
+    Ok(content)
+}
+
+/// Checks the SHA-256 of the content, given as a hex string (optionally prefixed by `0x`).
+pub(crate) fn check_sha256(content: &str, expected: &str) -> Result<(), String> {
+    let actual = format!("{:x}", Sha256::digest(content.as_bytes()));
+    let expected = expected.trim_start_matches("0x").to_lowercase();
+
+    if actual == expected {
+        Ok(())
+    } else {
+        Err(format!(
+            "ABI integrity check failed: expected sha256 {}, got {}",
+            expected, actual
+        ))
+    }
+}
+
+fn http_get(url: &str) -> Result<String, String> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| format!("failed to start the runtime to fetch the ABI: {}", e))?;
+
+    runtime
+        .block_on(async { reqwest::get(url).await?.error_for_status()?.text().await })
+        .map_err(|e| format!("failed to fetch the ABI from {}: {}", url, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ABI: &str = "[]";
+    // `printf '[]' | sha256sum`
+    const ABI_SHA256: &str = "4f53cda18c2baa0c0354bb5f9a3ecbe5ed12ab4d8e11ba873c2f11161202b945";
+
+    #[test]
+    fn test_check_sha256() {
+        assert!(check_sha256(ABI, ABI_SHA256).is_ok());
+        assert!(check_sha256(ABI, &format!("0x{}", ABI_SHA256.to_uppercase())).is_ok());
+        assert!(check_sha256("[ ]", ABI_SHA256).is_err());
+    }
+
+    #[test]
+    fn test_fetch_abi_from_local_copy() {
+        let dir = std::env::temp_dir().join(format!("cainome-fetch-{}", std::process::id()));
+        let url = "https://registry.example/abi.json";
+
+        // Nothing stored, and no network access.
+        let err = fetch_abi(url, None, &dir, false).unwrap_err();
+        assert!(err.contains(ALLOW_NETWORK_ENV));
+
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join(format!("{:x}.json", starknet_keccak(url.as_bytes()))),
+            ABI,
+        )
+        .unwrap();
+
+        assert_eq!(fetch_abi(url, None, &dir, false).unwrap(), ABI);
+        assert_eq!(fetch_abi(url, Some(ABI_SHA256), &dir, false).unwrap(), ABI);
+
+        // The stored copy doesn't match the hash, it must be fetched again.
+        let other_sha256 = format!("{:x}", Sha256::digest(b"[ ]"));
+        assert!(fetch_abi(url, Some(&other_sha256), &dir, false).is_err());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,135 @@
+//! Defines the arguments of the `abigen_group` attribute.
+//!
+//! The attribute is set on an inline module, with the options shared by the contracts
+//! of the group. Each contract is declared in the module with a `contract!` item,
+//! taking the arguments of `abigen!`:
+//!
+//! ```ignore
+//! #[abigen_group(derives(Debug, Clone), execution_version("V3"))]
+//! mod bindings {
+//!     contract!(Vault, "path/to/vault.json");
+//!     contract!(Router, "path/to/router.json", type_aliases { pkg::Route as RouterRoute; });
+//! }
+//! ```
+//!
+//! The options of a contract are parsed after the shared ones, so a contract overrides
+//! the shared options taking a single value, and extends the ones taking a list.
+use proc_macro2::{Span, TokenStream as TokenStream2, TokenTree};
+use quote::quote;
+use syn::{Item, ItemMod, Macro};
+
+use crate::macro_inputs::ContractAbi;
+
+/// The name of the module generating the types shared by the contracts of the group.
+pub(crate) const COMMON_MODULE: &str = "common";
+
+/// The inline module of a group of contracts.
+pub(crate) struct ContractGroup {
+    /// The module, without the `contract!` items.
+    pub module: ItemMod,
+    /// The options shared by the contracts, parsed for a placeholder contract.
+    pub shared: ContractAbi,
+    /// The contracts of the group, with the shared options.
+    pub contracts: Vec<ContractAbi>,
+}
+
+impl ContractGroup {
+    /// Parses the module of the group, and the options of the attribute.
+    ///
+    /// # Arguments
+    ///
+    /// * `attr` - The options shared by the contracts of the group.
+    /// * `item` - The module declaring the contracts of the group.
+    pub fn parse(attr: TokenStream2, item: TokenStream2) -> syn::Result<Self> {
+        let mut module = syn::parse2::<ItemMod>(item)?;
+
+        let shared = syn::parse2::<ContractAbi>(with_options(
+            quote!(__Shared, "[]"),
+            &attr,
+            &TokenStream2::new(),
+        ))?;
+
+        let Some((_, items)) = module.content.as_mut() else {
+            return Err(syn::Error::new_spanned(
+                &module,
+                "`abigen_group` expects an inline module declaring the contracts with `contract!`",
+            ));
+        };
+
+        let mut contracts = vec![];
+        let mut others = vec![];
+
+        for item in items.drain(..) {
+            match item {
+                Item::Macro(m) if m.mac.path.is_ident("contract") => {
+                    contracts.push(parse_contract(&m.mac, &attr)?);
+                }
+                other => others.push(other),
+            }
+        }
+        *items = others;
+
+        if contracts.is_empty() {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "`abigen_group` expects at least one `contract!` item",
+            ));
+        }
+
+        for contract in &contracts {
+            if contract.output_path.is_some() {
+                return Err(syn::Error::new(
+                    contract.name.span(),
+                    "`output_path` is not supported in a group of contracts",
+                ));
+            }
+        }
+
+        Ok(Self {
+            module,
+            shared,
+            contracts,
+        })
+    }
+}
+
+/// Parses a `contract!` item, with the options shared by the group.
+///
+/// The shared options are inserted after the name and the ABI source of the contract,
+/// before its own options.
+fn parse_contract(mac: &Macro, attr: &TokenStream2) -> syn::Result<ContractAbi> {
+    let mut head = TokenStream2::new();
+    let mut tail = TokenStream2::new();
+    let mut commas = 0;
+
+    for tt in mac.tokens.clone() {
+        if commas >= 2 {
+            tail.extend([tt]);
+            continue;
+        }
+
+        if matches!(&tt, TokenTree::Punct(p) if p.as_char() == ',') {
+            commas += 1;
+            if commas == 2 {
+                continue;
+            }
+        }
+
+        head.extend([tt]);
+    }
+
+    syn::parse2::<ContractAbi>(with_options(head, attr, &tail))
+}
+
+/// Appends the options to the name and the ABI source of a contract.
+fn with_options(head: TokenStream2, shared: &TokenStream2, own: &TokenStream2) -> TokenStream2 {
+    let mut tokens = head;
+
+    for options in [shared, own] {
+        if !options.is_empty() {
+            tokens.extend(quote!(, #options));
+        }
+    }
+
+    tokens
+}
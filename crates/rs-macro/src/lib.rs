@@ -1,13 +1,21 @@
-use cainome_parser::{AbiParser, AbiParserLegacy};
-use cainome_rs::{self};
+use cainome_parser::{AbiParser, AbiParserLegacy, ParserOptions, TokenizedAbi};
+use cainome_rs::{self, ExecutionVersion, ExpandOptions, SharedTypes};
+use convert_case::{Case, Casing};
 use proc_macro::TokenStream;
-use proc_macro_error::proc_macro_error;
-use quote::quote;
+use proc_macro2::TokenStream as TokenStream2;
+use proc_macro_error::{abort_call_site, proc_macro_error};
+use quote::{format_ident, quote};
 
+mod cache;
+#[cfg(feature = "fetch")]
+mod fetch;
+mod group;
 mod macro_inputs;
 mod macro_inputs_legacy;
 mod spanned;
 
+use crate::cache::AbigenCache;
+use crate::group::{ContractGroup, COMMON_MODULE};
 use crate::macro_inputs::ContractAbi;
 use crate::macro_inputs_legacy::ContractAbiLegacy;
 
@@ -23,23 +31,46 @@ pub fn abigen_legacy(input: TokenStream) -> TokenStream {
     abigen_internal_legacy(input)
 }
 
+#[proc_macro_error]
+#[proc_macro_attribute]
+pub fn abigen_group(attr: TokenStream, item: TokenStream) -> TokenStream {
+    abigen_group_internal(attr, item)
+}
+
 fn abigen_internal(input: TokenStream) -> TokenStream {
     let contract_abi = syn::parse_macro_input!(input as ContractAbi);
+    let contract_name = contract_abi.name.to_string();
 
-    let abi_entries = contract_abi.abi;
-    let contract_name = contract_abi.name;
-
-    let abi_tokens = AbiParser::collect_tokens(&abi_entries, &contract_abi.type_aliases)
-        .expect("failed tokens parsing");
+    let options = expand_options(&contract_abi);
 
-    let expanded = cainome_rs::abi_to_tokenstream(
-        &contract_name.to_string(),
-        &abi_tokens,
-        contract_abi.execution_version,
-        &contract_abi.derives,
-        &contract_abi.contract_derives,
+    let cache = AbigenCache::from_env();
+    let cache_key = cache::cache_key(
+        &contract_name,
+        &contract_abi.abi_json,
+        &contract_abi.type_aliases,
+        &options,
     );
 
+    let env_tracking = track_execution_version_env(&contract_abi);
+
+    let expanded = match cache.as_ref().and_then(|c| c.get(&cache_key)) {
+        Some(expanded) => expanded,
+        None => {
+            let abi_tokens = match contract_tokens(&contract_abi) {
+                Ok(tokens) => tokens,
+                Err(e) => return e.to_compile_error().into(),
+            };
+
+            let expanded = cainome_rs::abi_to_tokenstream(&contract_name, &abi_tokens, &options);
+
+            if let Some(cache) = &cache {
+                cache.put(&cache_key, &expanded);
+            }
+
+            expanded
+        }
+    };
+
     if let Some(out_path) = contract_abi.output_path {
         let content: String = expanded.to_string();
         match std::fs::write(out_path, content) {
@@ -47,10 +78,93 @@ fn abigen_internal(input: TokenStream) -> TokenStream {
             Err(e) => panic!("Failed to write to file: {}", e),
         }
 
-        quote!().into()
+        env_tracking.into()
     } else {
-        expanded.into()
+        quote!(#expanded #env_tracking).into()
+    }
+}
+
+fn abigen_group_internal(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let group = match ContractGroup::parse(attr.into(), item.into()) {
+        Ok(group) => group,
+        Err(e) => return e.to_compile_error().into(),
+    };
+
+    let mut contracts_tokens = vec![];
+    for contract_abi in &group.contracts {
+        match contract_tokens(contract_abi) {
+            Ok(tokens) => contracts_tokens.push(tokens),
+            Err(e) => return e.to_compile_error().into(),
+        }
+    }
+
+    let shared = SharedTypes::new(&contracts_tokens.iter().collect::<Vec<_>>());
+    abort_on_name_collisions(&shared.tokens);
+
+    let env_tracking = group
+        .contracts
+        .iter()
+        .find(|c| c.execution_version_from_env)
+        .map(track_execution_version_env);
+
+    let common = format_ident!("{}", COMMON_MODULE);
+    let common_types =
+        cainome_rs::shared_types_to_tokenstream(&shared, &expand_options(&group.shared));
+    let common_path = format!("super::{}", COMMON_MODULE);
+
+    let mut modules = vec![];
+    for (i, (contract_abi, abi_tokens)) in group.contracts.iter().zip(&contracts_tokens).enumerate()
+    {
+        let contract_name = contract_abi.name.to_string();
+        let module_name = contract_name.to_case(Case::Snake);
+
+        if module_name == COMMON_MODULE {
+            return syn::Error::new(
+                contract_abi.name.span(),
+                format!(
+                    "the module `{}` of the shared types conflicts with the contract `{}`",
+                    COMMON_MODULE, contract_name
+                ),
+            )
+            .to_compile_error()
+            .into();
+        }
+
+        let options = ExpandOptions {
+            shared_types: shared.for_contract(i, &common_path),
+            ..expand_options(contract_abi)
+        };
+        let expanded = cainome_rs::abi_to_tokenstream(&contract_name, abi_tokens, &options);
+        let module = format_ident!("{}", &module_name);
+
+        modules.push(quote! {
+            pub mod #module {
+                #expanded
+            }
+        });
+    }
+
+    let module = group.module;
+    let attrs = &module.attrs;
+    let vis = &module.vis;
+    let name = &module.ident;
+    let items = module.content.map(|(_, items)| items).unwrap_or_default();
+
+    quote! {
+        #(#attrs)*
+        #vis mod #name {
+            #(#items)*
+
+            pub mod #common {
+                #common_types
+            }
+
+            #(#modules)*
+
+            #env_tracking
+        }
     }
+    .into()
 }
 
 fn abigen_internal_legacy(input: TokenStream) -> TokenStream {
@@ -59,16 +173,39 @@ fn abigen_internal_legacy(input: TokenStream) -> TokenStream {
     let abi_entries = contract_abi.abi;
     let contract_name = contract_abi.name;
 
-    let abi_tokens = AbiParserLegacy::collect_tokens(&abi_entries, &contract_abi.type_aliases)
-        .expect("failed tokens parsing");
+    let abi_tokens = match AbiParserLegacy::collect_tokens(&abi_entries, &contract_abi.type_aliases)
+    {
+        Ok(tokens) => tokens,
+        Err(e) => abort_call_site!("failed tokens parsing: {}", e),
+    };
 
-    let expanded = cainome_rs::abi_to_tokenstream(
-        &contract_name.to_string(),
-        &abi_tokens,
-        cainome_rs::ExecutionVersion::V1,
-        &contract_abi.derives,
-        &contract_abi.contract_derives,
-    );
+    abort_on_name_collisions(&abi_tokens);
+
+    let options = ExpandOptions {
+        execution_version: ExecutionVersion::V1,
+        derives: contract_abi.derives,
+        contract_derives: contract_abi.contract_derives,
+        type_derives: contract_abi.type_derives,
+        blocking: contract_abi.blocking,
+        arbitrary: contract_abi.arbitrary,
+        params_struct_threshold: contract_abi.params_struct_threshold,
+        prelude: contract_abi.prelude,
+        roundtrip_tests: contract_abi.roundtrip_tests,
+        serde_radix: contract_abi.serde_radix,
+        serde_bytes: contract_abi.serde_bytes,
+        src5: contract_abi.src5,
+        paymaster: contract_abi.paymaster,
+        consts: contract_abi.consts.clone(),
+        tuple_structs: contract_abi.tuple_structs.clone(),
+        // The legacy ABIs don't have enums.
+        result_enums: vec![],
+        generation_mode: contract_abi.generation_mode,
+        artifact: None,
+        shared_types: Default::default(),
+    };
+
+    let expanded =
+        cainome_rs::abi_to_tokenstream(&contract_name.to_string(), &abi_tokens, &options);
 
     if let Some(out_path) = contract_abi.output_path {
         let content: String = expanded.to_string();
@@ -82,3 +219,113 @@ fn abigen_internal_legacy(input: TokenStream) -> TokenStream {
         expanded.into()
     }
 }
+
+/// Returns an item reading the environment variable of the default execution version with
+/// `option_env!` if the contract uses it, for cargo to track the variable: the crate is rebuilt,
+/// and the bindings expanded again, when it changes.
+fn track_execution_version_env(contract_abi: &ContractAbi) -> TokenStream2 {
+    if !contract_abi.execution_version_from_env {
+        return quote!();
+    }
+
+    let env_var = ExecutionVersion::ENV_VAR;
+
+    quote! {
+        const _: ::core::option::Option<&str> = ::core::option_env!(#env_var);
+    }
+}
+
+/// Returns the options of the expansion of the bindings of the contract.
+fn expand_options(contract_abi: &ContractAbi) -> ExpandOptions {
+    ExpandOptions {
+        execution_version: contract_abi.execution_version,
+        derives: contract_abi.derives.clone(),
+        contract_derives: contract_abi.contract_derives.clone(),
+        type_derives: contract_abi.type_derives.clone(),
+        blocking: contract_abi.blocking,
+        arbitrary: contract_abi.arbitrary,
+        params_struct_threshold: contract_abi.params_struct_threshold,
+        prelude: contract_abi.prelude,
+        roundtrip_tests: contract_abi.roundtrip_tests,
+        serde_radix: contract_abi.serde_radix,
+        serde_bytes: contract_abi.serde_bytes,
+        src5: contract_abi.src5,
+        paymaster: contract_abi.paymaster,
+        consts: contract_abi.consts.clone(),
+        tuple_structs: contract_abi.tuple_structs.clone(),
+        result_enums: contract_abi.result_enums.clone(),
+        generation_mode: contract_abi.generation_mode,
+        artifact: contract_abi.artifact.clone(),
+        shared_types: Default::default(),
+    }
+}
+
+/// Tokenizes the ABI of the contract, aborting the expansion if some types are
+/// not defined or would be generated with the same name.
+fn contract_tokens(contract_abi: &ContractAbi) -> syn::Result<TokenizedAbi> {
+    let abi_entries = contract_abi.abi_entries()?;
+
+    let options = ParserOptions {
+        type_naming: contract_abi.type_naming,
+        ..Default::default()
+    };
+
+    let abi_tokens = match AbiParser::collect_tokens_with_options(
+        &abi_entries,
+        &contract_abi.type_aliases,
+        &options,
+    ) {
+        Ok(tokens) => tokens,
+        Err(e) => abort_call_site!("failed tokens parsing: {}", e),
+    };
+
+    let unresolved = abi_tokens.unresolved_types();
+    if !unresolved.is_empty() {
+        abort_call_site!(
+            "types referenced but not defined in the ABI: {}",
+            unresolved.join(", ")
+        );
+    }
+
+    abort_on_name_collisions(&abi_tokens);
+
+    Ok(abi_tokens)
+}
+
+/// Aborts the expansion if some types of the ABI would be generated with the same name.
+fn abort_on_name_collisions(abi_tokens: &TokenizedAbi) {
+    let collisions = abi_tokens.name_collisions();
+
+    if !collisions.is_empty() {
+        abort_call_site!(
+            "{}. Use `type_aliases` to rename them, or `type_naming(\"shortest_unique\")`.",
+            collisions
+                .iter()
+                .map(|c| c.to_string())
+                .collect::<Vec<_>>()
+                .join("; ")
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_track_execution_version_env() {
+        let contract_abi = syn::parse2::<ContractAbi>(quote!(MyContract, "[]")).unwrap();
+        assert_eq!(
+            track_execution_version_env(&contract_abi).to_string(),
+            quote!(
+                const _: ::core::option::Option<&str> =
+                    ::core::option_env!("CAINOME_EXECUTION_VERSION");
+            )
+            .to_string()
+        );
+
+        let contract_abi =
+            syn::parse2::<ContractAbi>(quote!(MyContract, "[]", execution_version("v3"))).unwrap();
+        assert!(track_execution_version_env(&contract_abi).is_empty());
+    }
+}
@@ -9,13 +9,13 @@
 //! Loading from a file with only the ABI array.
 //! abigen!(ContractName, "path/to/abi.json"
 //!
-//! TODO: support the full artifact JSON to be able to
-//! deploy contracts from abigen.
+//! A Sierra class is also accepted, and with `include_artifact`
+//! it is embedded into the bindings to be declared.
+use proc_macro2::Span;
 use proc_macro_error::emit_error;
 use quote::ToTokens;
 use starknet::core::types::contract::{AbiEntry, SierraClass};
 use std::collections::{HashMap, HashSet};
-use std::fs::File;
 use std::path::Path;
 use std::str::FromStr;
 use syn::{
@@ -23,23 +23,52 @@ use syn::{
     ext::IdentExt,
     parenthesized,
     parse::{Parse, ParseStream, Result},
-    Ident, LitStr, Token, Type,
+    Expr, Ident, LitBool, LitInt, LitStr, Token, Type,
 };
 
+#[cfg(feature = "fetch")]
+use crate::fetch;
 use crate::spanned::Spanned;
-use cainome_rs::ExecutionVersion;
+use cainome_parser::TypeNaming;
+use cainome_rs::{
+    BytesEncoding, ContractConst, EmbeddedClass, ExecutionVersion, GenerationMode, ResultEnum,
+    SerdeRadix, TupleStruct,
+};
 
 const CARGO_MANIFEST_DIR: &str = "$CARGO_MANIFEST_DIR/";
 
 #[derive(Clone, Debug)]
 pub(crate) struct ContractAbi {
     pub name: Ident,
-    pub abi: Vec<AbiEntry>,
+    /// The JSON of the ABI, either an array of ABI entries or a Sierra class.
+    /// It is only parsed when needed, see [`ContractAbi::abi_entries`].
+    pub abi_json: String,
+    /// The span of the ABI argument, to report the parsing errors.
+    pub abi_span: Span,
     pub output_path: Option<String>,
     pub type_aliases: HashMap<String, String>,
     pub execution_version: ExecutionVersion,
+    /// Whether the execution version is not given, and is read from the environment variable
+    /// which must then be tracked by cargo.
+    pub execution_version_from_env: bool,
     pub derives: Vec<String>,
     pub contract_derives: Vec<String>,
+    pub type_derives: HashMap<String, Vec<String>>,
+    pub blocking: bool,
+    pub arbitrary: bool,
+    pub params_struct_threshold: Option<usize>,
+    pub prelude: bool,
+    pub roundtrip_tests: bool,
+    pub serde_radix: SerdeRadix,
+    pub serde_bytes: BytesEncoding,
+    pub src5: bool,
+    pub paymaster: bool,
+    pub consts: Vec<ContractConst>,
+    pub tuple_structs: Vec<TupleStruct>,
+    pub result_enums: Vec<ResultEnum>,
+    pub generation_mode: GenerationMode,
+    pub type_naming: TypeNaming,
+    pub artifact: Option<EmbeddedClass>,
 }
 
 impl Parse for ContractAbi {
@@ -47,53 +76,54 @@ impl Parse for ContractAbi {
         let name = input.parse::<Ident>()?;
         input.parse::<Token![,]>()?;
 
-        // ABI path or content.
-
-        // Path rooted to the Cargo.toml location if it's a file.
-        let abi_or_path = input.parse::<LitStr>()?;
-
-        #[allow(clippy::collapsible_else_if)]
-        let abi = if abi_or_path.value().ends_with(".json") {
-            let json_path = if abi_or_path.value().starts_with(CARGO_MANIFEST_DIR) {
-                let manifest_dir = env!("CARGO_MANIFEST_DIR");
-                let new_dir = Path::new(manifest_dir)
-                    .join(abi_or_path.value().trim_start_matches(CARGO_MANIFEST_DIR))
-                    .to_string_lossy()
-                    .to_string();
-
-                LitStr::new(&new_dir, proc_macro2::Span::call_site())
-            } else {
-                abi_or_path
-            };
-
-            // To prepare the declare and deploy features, we also
-            // accept a full Sierra artifact for the ABI.
-            // To support declare and deploy, the full class must be stored.
-            if let Ok(sierra) =
-                serde_json::from_reader::<_, SierraClass>(open_json_file(&json_path.value())?)
-            {
-                sierra.abi
-            } else {
-                serde_json::from_reader::<_, Vec<AbiEntry>>(open_json_file(&json_path.value())?)
-                    .map_err(|e| {
-                        syn::Error::new(json_path.span(), format!("JSON parse error: {}", e))
-                    })?
+        // ABI path, content or URL (`url = "https://..."`).
+        let abi_source = if input.peek(Ident) && input.peek2(Token![=]) {
+            let key = input.parse::<Ident>()?;
+            if key != "url" {
+                return Err(syn::Error::new(
+                    key.span(),
+                    format!("unexpected ABI source `{key}`, expected `url`"),
+                ));
             }
+            input.parse::<Token![=]>()?;
+
+            AbiSource::Url(input.parse::<LitStr>()?)
         } else {
-            if let Ok(sierra) = serde_json::from_str::<SierraClass>(&abi_or_path.value()) {
-                sierra.abi
-            } else {
-                serde_json::from_str::<Vec<AbiEntry>>(&abi_or_path.value()).map_err(|e| {
-                    syn::Error::new(abi_or_path.span(), format!("JSON parse error: {}", e))
-                })?
-            }
+            // Path rooted to the Cargo.toml location if it's a file.
+            AbiSource::PathOrContent(input.parse::<LitStr>()?)
         };
 
         let mut output_path: Option<String> = None;
-        let mut execution_version = ExecutionVersion::V1;
+        // The environment variable only provides the default, the parameter has precedence.
+        let mut execution_version = ExecutionVersion::from_env()
+            .map_err(|e| {
+                syn::Error::new(
+                    Span::call_site(),
+                    format!("{}: {}", ExecutionVersion::ENV_VAR, e),
+                )
+            })?
+            .unwrap_or_default();
+        let mut execution_version_from_env = true;
         let mut type_aliases = HashMap::new();
         let mut derives = Vec::new();
         let mut contract_derives = Vec::new();
+        let mut type_derives = HashMap::new();
+        let mut blocking = false;
+        let mut arbitrary = false;
+        let mut params_struct_threshold: Option<usize> = None;
+        let mut prelude = false;
+        let mut roundtrip_tests = false;
+        let mut serde_radix = SerdeRadix::Hex;
+        let mut serde_bytes = BytesEncoding::Array;
+        let mut src5 = false;
+        let mut paymaster = false;
+        let mut consts = Vec::new();
+        let mut tuple_structs = Vec::new();
+        let mut result_enums = Vec::new();
+        let mut generation_mode = GenerationMode::Full;
+        let mut type_naming = TypeNaming::LastSegment;
+        let mut sha256: Option<LitStr> = None;
+        let mut include_artifact: Option<Span> = None;
 
         loop {
             if input.parse::<Token![,]>().is_err() {
@@ -141,10 +171,15 @@ impl Parse for ContractAbi {
                 "execution_version" => {
                     let content;
                     parenthesized!(content in input);
-                    let ev = content.parse::<LitStr>()?.value();
-                    execution_version = ExecutionVersion::from_str(&ev).map_err(|e| {
-                        syn::Error::new(content.span(), format!("Invalid execution version: {}", e))
-                    })?;
+                    // Either a string (`"v3"`) or an identifier (`V3`).
+                    let ev = if content.peek(LitStr) {
+                        content.parse::<LitStr>()?.value()
+                    } else {
+                        Ident::parse_any(&content)?.to_string()
+                    };
+                    execution_version = ExecutionVersion::from_str(&ev)
+                        .map_err(|e| syn::Error::new(content.span(), e))?;
+                    execution_version_from_env = false;
                 }
                 "derives" => {
                     let content;
@@ -164,22 +199,299 @@ impl Parse for ContractAbi {
                         contract_derives.push(derive.to_token_stream().to_string());
                     }
                 }
+                "type_derives" => {
+                    let content;
+                    braced!(content in input);
+                    let parsed =
+                        content.parse_terminated(Spanned::<TypeDerives>::parse, Token![;])?;
+
+                    for type_derive in parsed {
+                        if type_derives.contains_key(&type_derive.pattern) {
+                            emit_error!(
+                                type_derive.span(),
+                                format!("{} duplicate type pattern", type_derive.pattern)
+                            );
+                        }
+
+                        let td = type_derive.into_inner();
+                        type_derives.insert(td.pattern, td.derives);
+                    }
+                }
+                "consts" => {
+                    let content;
+                    braced!(content in input);
+                    let parsed =
+                        content.parse_terminated(Spanned::<ConstDecl>::parse, Token![;])?;
+
+                    for decl in parsed {
+                        let span = decl.span();
+                        let ConstDecl(c) = decl.into_inner();
+
+                        if consts
+                            .iter()
+                            .any(|other: &ContractConst| other.name == c.name)
+                        {
+                            emit_error!(span, format!("{} duplicate constant", c.name));
+                        }
+
+                        consts.push(c);
+                    }
+                }
+                "tuple_structs" => {
+                    let content;
+                    braced!(content in input);
+                    let parsed =
+                        content.parse_terminated(Spanned::<TupleStructDecl>::parse, Token![;])?;
+
+                    for decl in parsed {
+                        let span = decl.span();
+                        let TupleStructDecl(t) = decl.into_inner();
+
+                        if tuple_structs
+                            .iter()
+                            .any(|other: &TupleStruct| other.name == t.name)
+                        {
+                            emit_error!(span, format!("{} duplicate tuple struct", t.name));
+                        }
+
+                        tuple_structs.push(t);
+                    }
+                }
+                "result_enums" => {
+                    let content;
+                    braced!(content in input);
+                    let parsed =
+                        content.parse_terminated(Spanned::<ResultEnumDecl>::parse, Token![;])?;
+
+                    for decl in parsed {
+                        let span = decl.span();
+                        let ResultEnumDecl(r) = decl.into_inner();
+
+                        if result_enums
+                            .iter()
+                            .any(|other: &ResultEnum| other.enum_path == r.enum_path)
+                        {
+                            emit_error!(span, format!("{} duplicate result enum", r.enum_path));
+                        }
+
+                        result_enums.push(r);
+                    }
+                }
+                "blocking" => blocking = true,
+                "arbitrary" => arbitrary = true,
+                "prelude" => prelude = true,
+                "roundtrip_tests" => roundtrip_tests = true,
+                "src5" => src5 = true,
+                "paymaster" => paymaster = true,
+                "serde_radix" => {
+                    let content;
+                    parenthesized!(content in input);
+                    let radix = content.parse::<LitStr>()?.value();
+                    serde_radix = SerdeRadix::from_str(&radix)
+                        .map_err(|e| syn::Error::new(content.span(), e))?;
+                }
+                "serde_bytes" => {
+                    let content;
+                    parenthesized!(content in input);
+                    let encoding = content.parse::<LitStr>()?.value();
+                    serde_bytes = BytesEncoding::from_str(&encoding)
+                        .map_err(|e| syn::Error::new(content.span(), e))?;
+                }
+                "generation_mode" => {
+                    let content;
+                    parenthesized!(content in input);
+                    let mode = content.parse::<LitStr>()?.value();
+                    generation_mode = GenerationMode::from_str(&mode)
+                        .map_err(|e| syn::Error::new(content.span(), e))?;
+                }
+                "type_naming" => {
+                    let content;
+                    parenthesized!(content in input);
+                    let naming = content.parse::<LitStr>()?.value();
+                    type_naming = TypeNaming::from_str(&naming)
+                        .map_err(|e| syn::Error::new(content.span(), e))?;
+                }
+                "params_struct_threshold" => {
+                    let content;
+                    parenthesized!(content in input);
+                    params_struct_threshold = Some(content.parse::<LitInt>()?.base10_parse()?);
+                }
+                "include_artifact" => {
+                    // Also accepted as `include_artifact = true`.
+                    let include = if input.peek(Token![=]) {
+                        input.parse::<Token![=]>()?;
+                        input.parse::<LitBool>()?.value()
+                    } else {
+                        true
+                    };
+
+                    include_artifact = include.then(|| name.span());
+                }
+                "sha256" => {
+                    let content;
+                    parenthesized!(content in input);
+                    sha256 = Some(content.parse::<LitStr>()?);
+                }
                 _ => emit_error!(name.span(), format!("unexpected named parameter `{name}`")),
             }
         }
 
+        let artifact = match include_artifact {
+            Some(span) => Some(abi_source.embedded_class(span)?),
+            None => None,
+        };
+
+        let (abi_json, abi_span) = abi_source.resolve(sha256.as_ref())?;
+
         Ok(ContractAbi {
             name,
-            abi,
+            abi_json,
+            abi_span,
             output_path,
             type_aliases,
             execution_version,
+            execution_version_from_env,
             derives,
             contract_derives,
+            type_derives,
+            blocking,
+            arbitrary,
+            params_struct_threshold,
+            prelude,
+            roundtrip_tests,
+            serde_radix,
+            serde_bytes,
+            src5,
+            paymaster,
+            consts,
+            tuple_structs,
+            result_enums,
+            generation_mode,
+            type_naming,
+            artifact,
         })
     }
 }
 
+/// The source of the ABI given to the macro.
+enum AbiSource {
+    /// The path of a JSON file, or the JSON content.
+    PathOrContent(LitStr),
+    /// The URL of a JSON file.
+    Url(LitStr),
+}
+
+impl AbiSource {
+    /// Returns the path of the JSON file, if the ABI is loaded from a file.
+    fn json_path(&self) -> Option<String> {
+        let AbiSource::PathOrContent(abi_or_path) = self else {
+            return None;
+        };
+
+        let value = abi_or_path.value();
+        if !value.ends_with(".json") {
+            return None;
+        }
+
+        if value.starts_with(CARGO_MANIFEST_DIR) {
+            let manifest_dir = env!("CARGO_MANIFEST_DIR");
+            Some(
+                Path::new(manifest_dir)
+                    .join(value.trim_start_matches(CARGO_MANIFEST_DIR))
+                    .to_string_lossy()
+                    .to_string(),
+            )
+        } else {
+            Some(value)
+        }
+    }
+
+    /// Returns the Sierra class of the source, to be embedded into the bindings.
+    ///
+    /// Only a Sierra class file can be embedded, with its compiled class next to it.
+    fn embedded_class(&self, span: Span) -> Result<EmbeddedClass> {
+        let Some(json_path) = self.json_path() else {
+            return Err(syn::Error::new(
+                span,
+                "`include_artifact` requires the path of a Sierra class file",
+            ));
+        };
+
+        EmbeddedClass::from_sierra_path(Path::new(&json_path)).map_err(|e| syn::Error::new(span, e))
+    }
+
+    /// Returns the JSON of the ABI, with the span of the source.
+    ///
+    /// If given, the SHA-256 of the JSON is checked.
+    fn resolve(self, sha256: Option<&LitStr>) -> Result<(String, Span)> {
+        let json_path = self.json_path();
+
+        match self {
+            AbiSource::PathOrContent(abi_or_path) => {
+                let abi_json = match json_path {
+                    Some(json_path) => read_json_file(&json_path)?,
+                    None => abi_or_path.value(),
+                };
+
+                if let Some(sha256) = sha256 {
+                    check_sha256(&abi_json, sha256)?;
+                }
+
+                Ok((abi_json, abi_or_path.span()))
+            }
+            AbiSource::Url(url) => Ok((fetch_abi(&url, sha256)?, url.span())),
+        }
+    }
+}
+
+#[cfg(feature = "fetch")]
+fn check_sha256(abi_json: &str, sha256: &LitStr) -> Result<()> {
+    fetch::check_sha256(abi_json, &sha256.value()).map_err(|e| syn::Error::new(sha256.span(), e))
+}
+
+#[cfg(feature = "fetch")]
+fn fetch_abi(url: &LitStr, sha256: Option<&LitStr>) -> Result<String> {
+    let sha256 = sha256.map(|h| h.value());
+    fetch::fetch_abi_from_env(&url.value(), sha256.as_deref())
+        .map_err(|e| syn::Error::new(url.span(), e))
+}
+
+#[cfg(not(feature = "fetch"))]
+fn check_sha256(_abi_json: &str, sha256: &LitStr) -> Result<()> {
+    Err(fetch_disabled(sha256.span(), "sha256"))
+}
+
+#[cfg(not(feature = "fetch"))]
+fn fetch_abi(url: &LitStr, _sha256: Option<&LitStr>) -> Result<String> {
+    Err(fetch_disabled(url.span(), "url"))
+}
+
+/// The error of a parameter requiring the `fetch` feature, enabled
+/// by the `abigen-url` feature of cainome.
+#[cfg(not(feature = "fetch"))]
+fn fetch_disabled(span: Span, parameter: &str) -> syn::Error {
+    syn::Error::new(
+        span,
+        format!("`{parameter}` requires the `abigen-url` feature of cainome"),
+    )
+}
+
+impl ContractAbi {
+    /// Parses the ABI entries from the JSON.
+    ///
+    /// To prepare the declare and deploy features, a full Sierra artifact
+    /// is also accepted for the ABI.
+    /// To support declare and deploy, the full class must be stored.
+    pub fn abi_entries(&self) -> Result<Vec<AbiEntry>> {
+        if let Ok(sierra) = serde_json::from_str::<SierraClass>(&self.abi_json) {
+            return Ok(sierra.abi);
+        }
+
+        serde_json::from_str::<Vec<AbiEntry>>(&self.abi_json)
+            .map_err(|e| syn::Error::new(self.abi_span, format!("JSON parse error: {}", e)))
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub(crate) struct TypeAlias {
     abi: String,
@@ -198,12 +510,125 @@ impl Parse for TypeAlias {
     }
 }
 
+/// Derives applied to the types matching a pattern:
+/// `"Order*" => (serde::Serialize, serde::Deserialize)`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct TypeDerives {
+    pub pattern: String,
+    pub derives: Vec<String>,
+}
+
+impl Parse for TypeDerives {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let pattern = input.parse::<LitStr>()?.value();
+
+        input.parse::<Token![=>]>()?;
+
+        let content;
+        parenthesized!(content in input);
+        let derives = content
+            .parse_terminated(Type::parse, Token![,])?
+            .iter()
+            .map(|d| d.to_token_stream().to_string())
+            .collect();
+
+        Ok(TypeDerives { pattern, derives })
+    }
+}
+
+/// A constant of the contract, with the view returning its value if it's not
+/// the name of the constant in snake case: `[view =>] NAME: Type = value`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct ConstDecl(pub ContractConst);
+
+impl Parse for ConstDecl {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let view = if input.peek(Ident::peek_any) && input.peek2(Token![=>]) {
+            let view = Ident::parse_any(input)?.to_string();
+            input.parse::<Token![=>]>()?;
+            Some(view)
+        } else {
+            None
+        };
+
+        let name = input.parse::<Ident>()?.to_string();
+        input.parse::<Token![:]>()?;
+        let ty = input.parse::<Type>()?.to_token_stream().to_string();
+        input.parse::<Token![=]>()?;
+        let value = input.parse::<Expr>()?.to_token_stream().to_string();
+
+        Ok(ConstDecl(ContractConst {
+            name,
+            ty,
+            value,
+            view,
+        }))
+    }
+}
+
+/// A fixed tuple generated as a named struct: `(T1, T2, ...) as Name { field1, field2, ... }`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct TupleStructDecl(pub TupleStruct);
+
+impl Parse for TupleStructDecl {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let tuple = input.parse::<Type>()?.to_token_stream().to_string();
+        input.parse::<Token![as]>()?;
+        let name = input.parse::<Ident>()?.to_string();
+
+        let content;
+        braced!(content in input);
+        let fields = content
+            .parse_terminated(Ident::parse, Token![,])?
+            .iter()
+            .map(|f| f.to_string())
+            .collect();
+
+        Ok(TupleStructDecl(TupleStruct {
+            tuple: tuple.replace(' ', ""),
+            name,
+            fields,
+        }))
+    }
+}
+
+/// An enum mapped to `Result`, declared as `pkg::Outcome as Result<Success, Failure>`
+/// with the names of the ok and err variants.
+pub(crate) struct ResultEnumDecl(pub ResultEnum);
+
+impl Parse for ResultEnumDecl {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let enum_path = input.parse::<Type>()?.to_token_stream().to_string();
+        input.parse::<Token![as]>()?;
+
+        let result = input.parse::<Ident>()?;
+        if result != "Result" {
+            return Err(syn::Error::new(
+                result.span(),
+                format!("expected `Result<Ok, Err>`, found `{result}`"),
+            ));
+        }
+
+        input.parse::<Token![<]>()?;
+        let ok = input.parse::<Ident>()?.to_string();
+        input.parse::<Token![,]>()?;
+        let err = input.parse::<Ident>()?.to_string();
+        input.parse::<Token![>]>()?;
+
+        Ok(ResultEnumDecl(ResultEnum {
+            enum_path: enum_path.replace(' ', ""),
+            ok,
+            err,
+        }))
+    }
+}
+
 fn sanitize_str(abi: &str) -> String {
     abi.trim().replace([' ', '\n', '\t'], "").to_string()
 }
 
-fn open_json_file(file_path: &str) -> Result<File> {
-    File::open(file_path).map_err(|e| {
+fn read_json_file(file_path: &str) -> Result<String> {
+    std::fs::read_to_string(file_path).map_err(|e| {
         syn::Error::new(
             str_to_litstr(file_path).span(),
             format!("JSON open file {} error: {}", file_path, e),
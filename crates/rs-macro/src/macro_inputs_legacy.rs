@@ -17,14 +17,18 @@ use starknet::core::types::contract::legacy::{LegacyContractClass, RawLegacyAbiE
 use std::collections::{HashMap, HashSet};
 use std::fs::File;
 use std::path::Path;
+use std::str::FromStr;
 use syn::{
     braced,
     ext::IdentExt,
     parenthesized,
     parse::{Parse, ParseStream, Result},
-    Ident, LitStr, Token, Type,
+    Ident, LitInt, LitStr, Token, Type,
 };
 
+use cainome_rs::{BytesEncoding, ContractConst, GenerationMode, SerdeRadix, TupleStruct};
+
+use crate::macro_inputs::{ConstDecl, TupleStructDecl, TypeDerives};
 use crate::spanned::Spanned;
 
 const CARGO_MANIFEST_DIR: &str = "$CARGO_MANIFEST_DIR/";
@@ -37,6 +41,19 @@ pub(crate) struct ContractAbiLegacy {
     pub type_aliases: HashMap<String, String>,
     pub derives: Vec<String>,
     pub contract_derives: Vec<String>,
+    pub type_derives: HashMap<String, Vec<String>>,
+    pub blocking: bool,
+    pub arbitrary: bool,
+    pub params_struct_threshold: Option<usize>,
+    pub prelude: bool,
+    pub roundtrip_tests: bool,
+    pub serde_radix: SerdeRadix,
+    pub serde_bytes: BytesEncoding,
+    pub src5: bool,
+    pub paymaster: bool,
+    pub consts: Vec<ContractConst>,
+    pub tuple_structs: Vec<TupleStruct>,
+    pub generation_mode: GenerationMode,
 }
 
 impl Parse for ContractAbiLegacy {
@@ -91,6 +108,19 @@ impl Parse for ContractAbiLegacy {
         let mut type_aliases = HashMap::new();
         let mut derives = Vec::new();
         let mut contract_derives = Vec::new();
+        let mut type_derives = HashMap::new();
+        let mut blocking = false;
+        let mut arbitrary = false;
+        let mut params_struct_threshold: Option<usize> = None;
+        let mut prelude = false;
+        let mut roundtrip_tests = false;
+        let mut serde_radix = SerdeRadix::Hex;
+        let mut serde_bytes = BytesEncoding::Array;
+        let mut src5 = false;
+        let mut paymaster = false;
+        let mut consts = Vec::new();
+        let mut tuple_structs = Vec::new();
+        let mut generation_mode = GenerationMode::Full;
 
         loop {
             if input.parse::<Token![,]>().is_err() {
@@ -153,6 +183,96 @@ impl Parse for ContractAbiLegacy {
                         contract_derives.push(derive.to_token_stream().to_string());
                     }
                 }
+                "type_derives" => {
+                    let content;
+                    braced!(content in input);
+                    let parsed =
+                        content.parse_terminated(Spanned::<TypeDerives>::parse, Token![;])?;
+
+                    for type_derive in parsed {
+                        if type_derives.contains_key(&type_derive.pattern) {
+                            emit_error!(
+                                type_derive.span(),
+                                format!("{} duplicate type pattern", type_derive.pattern)
+                            );
+                        }
+
+                        let td = type_derive.into_inner();
+                        type_derives.insert(td.pattern, td.derives);
+                    }
+                }
+                "consts" => {
+                    let content;
+                    braced!(content in input);
+                    let parsed =
+                        content.parse_terminated(Spanned::<ConstDecl>::parse, Token![;])?;
+
+                    for decl in parsed {
+                        let span = decl.span();
+                        let ConstDecl(c) = decl.into_inner();
+
+                        if consts
+                            .iter()
+                            .any(|other: &ContractConst| other.name == c.name)
+                        {
+                            emit_error!(span, format!("{} duplicate constant", c.name));
+                        }
+
+                        consts.push(c);
+                    }
+                }
+                "tuple_structs" => {
+                    let content;
+                    braced!(content in input);
+                    let parsed =
+                        content.parse_terminated(Spanned::<TupleStructDecl>::parse, Token![;])?;
+
+                    for decl in parsed {
+                        let span = decl.span();
+                        let TupleStructDecl(t) = decl.into_inner();
+
+                        if tuple_structs
+                            .iter()
+                            .any(|other: &TupleStruct| other.name == t.name)
+                        {
+                            emit_error!(span, format!("{} duplicate tuple struct", t.name));
+                        }
+
+                        tuple_structs.push(t);
+                    }
+                }
+                "blocking" => blocking = true,
+                "arbitrary" => arbitrary = true,
+                "prelude" => prelude = true,
+                "roundtrip_tests" => roundtrip_tests = true,
+                "src5" => src5 = true,
+                "paymaster" => paymaster = true,
+                "serde_radix" => {
+                    let content;
+                    parenthesized!(content in input);
+                    let radix = content.parse::<LitStr>()?.value();
+                    serde_radix = SerdeRadix::from_str(&radix)
+                        .map_err(|e| syn::Error::new(content.span(), e))?;
+                }
+                "serde_bytes" => {
+                    let content;
+                    parenthesized!(content in input);
+                    let encoding = content.parse::<LitStr>()?.value();
+                    serde_bytes = BytesEncoding::from_str(&encoding)
+                        .map_err(|e| syn::Error::new(content.span(), e))?;
+                }
+                "generation_mode" => {
+                    let content;
+                    parenthesized!(content in input);
+                    let mode = content.parse::<LitStr>()?.value();
+                    generation_mode = GenerationMode::from_str(&mode)
+                        .map_err(|e| syn::Error::new(content.span(), e))?;
+                }
+                "params_struct_threshold" => {
+                    let content;
+                    parenthesized!(content in input);
+                    params_struct_threshold = Some(content.parse::<LitInt>()?.base10_parse()?);
+                }
                 _ => emit_error!(name.span(), format!("unexpected named parameter `{name}`")),
             }
         }
@@ -164,6 +284,19 @@ impl Parse for ContractAbiLegacy {
             type_aliases,
             derives,
             contract_derives,
+            type_derives,
+            blocking,
+            arbitrary,
+            params_struct_threshold,
+            prelude,
+            roundtrip_tests,
+            serde_radix,
+            serde_bytes,
+            src5,
+            paymaster,
+            consts,
+            tuple_structs,
+            generation_mode,
         })
     }
 }
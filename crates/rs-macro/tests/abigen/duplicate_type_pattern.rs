@@ -0,0 +1,22 @@
+#![no_main]
+use cainome_rs_macro::abigen;
+
+abigen!(
+    MyStruct,
+    r#"[
+        {
+            "type": "struct",
+            "name": "package::market::Order",
+            "members": [
+              {
+                "name": "price",
+                "type": "core::felt252"
+              }
+            ]
+        }
+    ]"#,
+    type_derives {
+        "Order*" => (Debug);
+        "Order*" => (Clone);
+    }
+);
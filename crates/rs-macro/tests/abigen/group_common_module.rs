@@ -0,0 +1,19 @@
+use cainome::rs::abigen_group;
+
+#[abigen_group(derives(Debug))]
+mod bindings {
+    contract!(
+        Common,
+        r#"[
+            {
+                "type": "function",
+                "name": "get_count",
+                "inputs": [],
+                "outputs": [{ "type": "core::felt252" }],
+                "state_mutability": "view"
+            }
+        ]"#
+    );
+}
+
+fn main() {}
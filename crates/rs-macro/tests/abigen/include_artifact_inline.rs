@@ -0,0 +1,17 @@
+use cainome::rs::abigen;
+
+abigen!(
+    Counter,
+    r#"[
+        {
+            "type": "function",
+            "name": "get_count",
+            "inputs": [],
+            "outputs": [{ "type": "core::felt252" }],
+            "state_mutability": "view"
+        }
+    ]"#,
+    include_artifact
+);
+
+fn main() {}
@@ -0,0 +1,20 @@
+use cainome::rs::abigen;
+
+abigen!(
+    MyToken,
+    r#"[
+        {
+            "type": "function",
+            "name": "transfer",
+            "inputs": [{ "name": "amount", "type": "core::felt252" }],
+            "outputs": [],
+            "state_mutability": "external"
+        }
+    ]"#,
+    consts {
+        FEE_BPS: u16 = 30;
+        transfer => AMOUNT: u16 = 1;
+    }
+);
+
+fn main() {}
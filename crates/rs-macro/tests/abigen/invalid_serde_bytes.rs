@@ -0,0 +1,14 @@
+#![no_main]
+use cainome_rs_macro::abigen;
+
+abigen!(
+    MyContract,
+    r#"[
+        {
+            "type": "struct",
+            "name": "contracts::Order",
+            "members": [{ "name": "price", "type": "core::felt252" }]
+        }
+    ]"#,
+    serde_bytes("utf8")
+);
@@ -0,0 +1,20 @@
+#![no_main]
+use cainome_rs_macro::abigen;
+
+abigen!(
+    MyContract,
+    r#"[
+        { "type": "struct", "name": "contracts::a::my_struct", "members": [] },
+        { "type": "struct", "name": "contracts::b::MyStruct", "members": [] },
+        {
+            "type": "function",
+            "name": "f",
+            "inputs": [
+                { "name": "a", "type": "contracts::a::my_struct" },
+                { "name": "b", "type": "contracts::b::MyStruct" }
+            ],
+            "outputs": [],
+            "state_mutability": "view"
+        }
+    ]"#
+);
@@ -0,0 +1,27 @@
+use cainome::rs::abigen;
+
+abigen!(
+    Market,
+    r#"[
+        {
+            "type": "enum",
+            "name": "pkg::Outcome",
+            "variants": [
+                { "name": "Failure", "type": "core::felt252" },
+                { "name": "Success", "type": "core::integer::u64" }
+            ]
+        },
+        {
+            "type": "function",
+            "name": "settle",
+            "inputs": [],
+            "outputs": [{ "type": "pkg::Outcome" }],
+            "state_mutability": "view"
+        }
+    ]"#,
+    result_enums {
+        pkg::Outcome as Result<Success, Failure>;
+    }
+);
+
+fn main() {}
@@ -0,0 +1,18 @@
+#![no_main]
+use cainome_rs_macro::abigen;
+
+abigen!(
+    MyContract,
+    r#"[
+        {
+            "type": "struct",
+            "name": "contracts::MyStruct",
+            "members": [
+              {
+                "name": "a",
+                "type": "contracts::Missing"
+              }
+            ]
+        }
+    ]"#
+);
@@ -0,0 +1,35 @@
+//! With `include_artifact`, the Sierra class is embedded to be declared, then deployed.
+use cainome::cairo_serde::call::{Declaration, Deployment};
+use cainome::rs::abigen;
+use starknet::accounts::{ConnectedAccount, SingleOwnerAccount};
+use starknet::core::types::contract::SierraClass;
+use starknet::core::types::Felt;
+use starknet::providers::jsonrpc::HttpTransport;
+use starknet::providers::JsonRpcClient;
+use starknet::signers::LocalWallet;
+
+type Account = SingleOwnerAccount<JsonRpcClient<HttpTransport>, LocalWallet>;
+
+abigen!(
+    Counter,
+    "$CARGO_MANIFEST_DIR/../parser/test_data/counter.contract_class.json",
+    include_artifact = true
+);
+
+#[allow(dead_code)]
+async fn declare_and_deploy<A: ConnectedAccount + Sync>(
+    account: &A,
+) -> Result<Deployment, cainome::cairo_serde::Error> {
+    let declaration: Declaration = Counter::declare(account).await?;
+    Counter::deploy(account, declaration.class_hash, Felt::ZERO, &Felt::ONE).await
+}
+
+fn main() {
+    let _ = declare_and_deploy::<Account>;
+
+    let class = serde_json::from_str::<SierraClass>(Counter::<Account>::SIERRA_CLASS).unwrap();
+
+    assert_eq!(Counter::<Account>::class_hash(), class.class_hash().unwrap());
+    assert_eq!(Counter::<Account>::CLASS_HASH, class.class_hash().unwrap());
+    assert_ne!(Counter::<Account>::COMPILED_CLASS_HASH, Felt::ZERO);
+}
@@ -0,0 +1,43 @@
+//! Each instantiation of a type with const generic arguments
+//! is generated as a distinct type, named after its constants.
+use cainome::cairo_serde::CairoSerde;
+use cainome::rs::abigen;
+
+abigen!(
+    MyContract,
+    r#"[
+        {
+            "type": "struct",
+            "name": "contracts::Config::<3>",
+            "members": [
+                { "name": "value", "type": "core::felt252" }
+            ]
+        },
+        {
+            "type": "struct",
+            "name": "contracts::Config::<5>",
+            "members": [
+                { "name": "value", "type": "core::integer::u8" }
+            ]
+        },
+        {
+            "type": "function",
+            "name": "get",
+            "inputs": [{ "name": "config", "type": "contracts::Config::<3>" }],
+            "outputs": [{ "type": "contracts::Config::<5>" }],
+            "state_mutability": "view"
+        }
+    ]"#,
+    derives(Debug, PartialEq)
+);
+
+fn main() {
+    let config3 = Config3 { value: 1.into() };
+    let config5 = Config5 { value: 2 };
+
+    assert_eq!(Config3::cairo_serialize(&config3), vec![1.into()]);
+    assert_eq!(
+        Config5::cairo_deserialize(&Config5::cairo_serialize(&config5), 0).unwrap(),
+        config5
+    );
+}
@@ -0,0 +1,82 @@
+//! With `consts`, the declared constants are emitted in a `<contract>_consts` module, and the
+//! reader checks them against their views. The modules generated once per contract are prefixed
+//! by the name of the contract, so several contracts can be expanded in the same module.
+use cainome::cairo_serde::call::ConstMismatch;
+use cainome::cairo_serde::U256;
+use cainome::rs::abigen;
+use starknet::core::types::Felt;
+use starknet::providers::jsonrpc::HttpTransport;
+use starknet::providers::JsonRpcClient;
+use std::future::Future;
+
+abigen!(
+    MyToken,
+    r#"[
+        {
+            "type": "function",
+            "name": "fee_bps",
+            "inputs": [],
+            "outputs": [{ "type": "core::integer::u16" }],
+            "state_mutability": "view"
+        },
+        {
+            "type": "function",
+            "name": "get_max_supply",
+            "inputs": [],
+            "outputs": [{ "type": "core::integer::u256" }],
+            "state_mutability": "view"
+        },
+        {
+            "type": "function",
+            "name": "treasury",
+            "inputs": [],
+            "outputs": [{ "type": "core::felt252" }],
+            "state_mutability": "view"
+        }
+    ]"#,
+    consts {
+        FEE_BPS: u16 = 30;
+        get_max_supply => MAX_SUPPLY: U256 = U256 { low: 1_000_000, high: 0 };
+        TREASURY: Felt = Felt::from_hex_unchecked("0x1234");
+    },
+    prelude,
+    roundtrip_tests
+);
+
+abigen!(
+    MyVault,
+    r#"[
+        {
+            "type": "function",
+            "name": "fee_bps",
+            "inputs": [],
+            "outputs": [{ "type": "core::integer::u16" }],
+            "state_mutability": "view"
+        }
+    ]"#,
+    consts {
+        FEE_BPS: u16 = 50;
+    },
+    prelude,
+    roundtrip_tests
+);
+
+fn assert_send<F: Future + Send>(_f: F) {}
+
+#[allow(dead_code)]
+async fn mismatches(
+    reader: &MyTokenReader<JsonRpcClient<HttpTransport>>,
+) -> cainome::cairo_serde::Result<Vec<ConstMismatch>> {
+    assert_send(reader.check_consts());
+    reader.check_consts().await
+}
+
+fn main() {
+    assert_eq!(my_token_consts::FEE_BPS, 30);
+    assert_eq!(my_token_consts::MAX_SUPPLY.low, 1_000_000);
+    assert_eq!(my_token_consts::TREASURY, Felt::from(0x1234));
+    assert_eq!(my_vault_consts::FEE_BPS, 50);
+
+    let _ = my_token_prelude::MyTokenReader::<JsonRpcClient<HttpTransport>>::entrypoints;
+    let _ = my_vault_prelude::MyVaultReader::<JsonRpcClient<HttpTransport>>::entrypoints;
+}
@@ -0,0 +1,84 @@
+//! A contract with a constructor is deployed with the serialized inputs of the constructor.
+use cainome::cairo_serde::call::Deployment;
+use cainome::cairo_serde::ContractAddress;
+use cainome::rs::abigen;
+use starknet::accounts::{ConnectedAccount, SingleOwnerAccount};
+use starknet::core::types::Felt;
+use starknet::providers::jsonrpc::HttpTransport;
+use starknet::providers::JsonRpcClient;
+use starknet::signers::LocalWallet;
+
+type Account = SingleOwnerAccount<JsonRpcClient<HttpTransport>, LocalWallet>;
+
+abigen!(
+    Vault,
+    r#"[
+        {
+            "type": "struct",
+            "name": "pkg::Limits",
+            "members": [
+                { "name": "daily", "type": "core::felt252" },
+                { "name": "total", "type": "core::felt252" }
+            ]
+        },
+        {
+            "type": "constructor",
+            "name": "constructor",
+            "inputs": [
+                { "name": "owner", "type": "core::starknet::contract_address::ContractAddress" },
+                { "name": "limits", "type": "pkg::Limits" },
+                { "name": "salt", "type": "core::felt252" }
+            ]
+        },
+        {
+            "type": "function",
+            "name": "withdraw",
+            "inputs": [{ "name": "amount", "type": "core::felt252" }],
+            "outputs": [],
+            "state_mutability": "external"
+        }
+    ]"#,
+    execution_version("v3")
+);
+
+async fn deploy<A: ConnectedAccount + Sync>(
+    account: &A,
+) -> Result<Deployment, cainome::cairo_serde::Error> {
+    let limits = Limits { daily: Felt::ONE, total: Felt::TWO };
+
+    Vault::deploy(
+        account,
+        Felt::ONE,
+        Felt::TWO,
+        &ContractAddress(Felt::THREE),
+        &limits,
+        &Felt::ZERO,
+    )
+    .await?;
+    Vault::deploy_with_deployer(
+        account,
+        Felt::from(0x1234_u32),
+        Felt::ONE,
+        Felt::TWO,
+        &ContractAddress(Felt::THREE),
+        &limits,
+        &Felt::ZERO,
+    )
+    .await
+}
+
+fn main() {
+    let _ = deploy::<Account>;
+
+    let calldata = Vault::<Account>::constructor_calldata(
+        &ContractAddress(Felt::THREE),
+        &Limits { daily: Felt::ONE, total: Felt::TWO },
+        &Felt::ZERO,
+    );
+
+    // The `salt` input of the constructor is renamed, `salt` being the salt of the deployment.
+    assert_eq!(
+        calldata,
+        vec![Felt::THREE, Felt::ONE, Felt::TWO, Felt::ZERO]
+    );
+}
@@ -0,0 +1,148 @@
+//! The events of the contract can be decoded from a transaction receipt,
+//! ignoring the events emitted by other contracts, and visited. They can
+//! also be streamed from a block range, or decoded from the transaction
+//! of a sent execution.
+use cainome::cairo_serde::call::SendInvoke;
+use cainome::cairo_serde::runtime::{BackfillError, DecodedEvent, Stream};
+use cainome::rs::abigen;
+use starknet::core::types::{
+    BlockId, BlockTag, ComputationResources, DataAvailabilityResources, DataResources, Event as StarknetEvent,
+    ExecutionResources, ExecutionResult, FeePayment, Felt, InvokeTransactionReceipt, PriceUnit,
+    TransactionFinalityStatus, TransactionReceipt,
+};
+use starknet::accounts::ConnectedAccount;
+use starknet::core::utils::get_selector_from_name;
+use starknet::providers::jsonrpc::HttpTransport;
+use starknet::providers::{JsonRpcClient, Url};
+
+abigen!(
+    MyContract,
+    r#"[
+        {
+            "type": "event",
+            "name": "contracts::Transfer",
+            "kind": "struct",
+            "members": [
+                { "name": "from", "type": "core::felt252", "kind": "key" },
+                { "name": "amount", "type": "core::integer::u32", "kind": "data" }
+            ]
+        },
+        {
+            "type": "event",
+            "name": "contracts::Event",
+            "kind": "enum",
+            "variants": [
+                { "name": "Transfer", "type": "contracts::Transfer", "kind": "nested" }
+            ]
+        }
+    ]"#
+);
+
+#[derive(Default)]
+struct Total(u32);
+
+impl MyContractEventVisitor for Total {
+    fn on_transfer(&mut self, event: &Transfer) {
+        self.0 += event.amount;
+    }
+}
+
+fn receipt(events: Vec<StarknetEvent>) -> TransactionReceipt {
+    TransactionReceipt::Invoke(InvokeTransactionReceipt {
+        transaction_hash: Felt::ONE,
+        actual_fee: FeePayment {
+            amount: Felt::ZERO,
+            unit: PriceUnit::Fri,
+        },
+        finality_status: TransactionFinalityStatus::AcceptedOnL2,
+        messages_sent: vec![],
+        events,
+        execution_resources: ExecutionResources {
+            computation_resources: ComputationResources {
+                steps: 0,
+                memory_holes: None,
+                range_check_builtin_applications: None,
+                pedersen_builtin_applications: None,
+                poseidon_builtin_applications: None,
+                ec_op_builtin_applications: None,
+                ecdsa_builtin_applications: None,
+                bitwise_builtin_applications: None,
+                keccak_builtin_applications: None,
+                segment_arena_builtin: None,
+            },
+            data_resources: DataResources {
+                data_availability: DataAvailabilityResources {
+                    l1_gas: 0,
+                    l1_data_gas: 0,
+                },
+            },
+        },
+        execution_result: ExecutionResult::Succeeded,
+    })
+}
+
+fn assert_events_stream<S>(_s: S)
+where
+    S: Stream<Item = Result<DecodedEvent<MyContractEvent>, BackfillError>> + Send,
+{
+}
+
+// Never called, only the types are checked.
+#[allow(dead_code)]
+async fn send_and_decode<A: ConnectedAccount + Sync>(
+    contract: &MyContract<A>,
+    execution: impl SendInvoke,
+) -> (Felt, Vec<MyContractEvent>) {
+    let result = contract.send_and_decode(execution).await.unwrap();
+    (result.actual_fee().amount, result.events)
+}
+
+fn main() {
+    let address = Felt::from(0x1234);
+    let transfer = |from_address: Felt, amount: u32| StarknetEvent {
+        from_address,
+        keys: vec![get_selector_from_name("Transfer").unwrap(), Felt::TWO],
+        data: vec![Felt::from(amount)],
+    };
+
+    let receipt = receipt(vec![
+        transfer(address, 1),
+        transfer(Felt::from(0x5678), 2),
+        // Unknown selector, ignored.
+        StarknetEvent {
+            from_address: address,
+            keys: vec![Felt::THREE],
+            data: vec![],
+        },
+        transfer(address, 3),
+    ]);
+
+    let provider = JsonRpcClient::new(HttpTransport::new(
+        Url::parse("http://localhost:5050").unwrap(),
+    ));
+    let reader = MyContractReader::new(address, &provider);
+
+    let events: Vec<MyContractEvent> = reader.events_in_receipt(&receipt);
+    let amounts: Vec<u32> = events
+        .iter()
+        .map(|e| match e {
+            Event::Transfer(t) => t.amount,
+        })
+        .collect();
+
+    assert_eq!(amounts, vec![1, 3]);
+
+    let mut total = Total::default();
+    for event in &events {
+        event.accept(&mut total);
+    }
+
+    assert_eq!(total.0, 4);
+
+    // The stream is never polled, only its type is checked.
+    assert_events_stream(reader.events(
+        BlockId::Number(1),
+        BlockId::Tag(BlockTag::Latest),
+        Some(vec![vec![get_selector_from_name("Transfer").unwrap()]]),
+    ));
+}
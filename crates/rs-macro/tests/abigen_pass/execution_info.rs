@@ -0,0 +1,79 @@
+//! The execution info structs of the corelib are mapped to the cairo-serde types.
+use cainome::cairo_serde::call::FCall;
+use cainome::cairo_serde::{BlockInfo, CairoSerde, ContractAddress, ExecutionInfoV2};
+use cainome::rs::abigen;
+use starknet::core::types::Felt;
+use starknet::providers::jsonrpc::HttpTransport;
+use starknet::providers::{JsonRpcClient, Url};
+
+abigen!(
+    MyContract,
+    r#"[
+        {
+            "type": "struct",
+            "name": "core::starknet::info::BlockInfo",
+            "members": [
+                { "name": "block_number", "type": "core::integer::u64" },
+                { "name": "block_timestamp", "type": "core::integer::u64" },
+                { "name": "sequencer_address", "type": "core::starknet::contract_address::ContractAddress" }
+            ]
+        },
+        {
+            "type": "struct",
+            "name": "core::starknet::info::v2::ExecutionInfo",
+            "members": [
+                { "name": "block_info", "type": "core::box::Box::<core::starknet::info::BlockInfo>" },
+                { "name": "tx_info", "type": "core::box::Box::<core::starknet::info::v2::TxInfo>" },
+                { "name": "caller_address", "type": "core::starknet::contract_address::ContractAddress" },
+                { "name": "contract_address", "type": "core::starknet::contract_address::ContractAddress" },
+                { "name": "entry_point_selector", "type": "core::felt252" }
+            ]
+        },
+        {
+            "type": "struct",
+            "name": "contracts::Snapshot",
+            "members": [
+                { "name": "block", "type": "core::starknet::info::BlockInfo" },
+                { "name": "counter", "type": "core::box::Box::<core::integer::u32>" }
+            ]
+        },
+        {
+            "type": "function",
+            "name": "execution_info",
+            "inputs": [],
+            "outputs": [{ "type": "core::starknet::info::v2::ExecutionInfo" }],
+            "state_mutability": "view"
+        },
+        {
+            "type": "function",
+            "name": "snapshot",
+            "inputs": [],
+            "outputs": [{ "type": "contracts::Snapshot" }],
+            "state_mutability": "view"
+        }
+    ]"#,
+    derives(Debug, PartialEq)
+);
+
+fn main() {
+    let snapshot = Snapshot {
+        block: BlockInfo {
+            block_number: 1,
+            block_timestamp: 2,
+            sequencer_address: ContractAddress(Felt::THREE),
+        },
+        counter: Box::new(4),
+    };
+
+    let felts = Snapshot::cairo_serialize(&snapshot);
+    assert_eq!(felts.len(), 4);
+    assert_eq!(Snapshot::cairo_deserialize(&felts, 0).unwrap(), snapshot);
+
+    let provider = JsonRpcClient::new(HttpTransport::new(
+        Url::parse("http://localhost:5050").unwrap(),
+    ));
+    let reader = MyContractReader::new(Felt::ONE, &provider);
+
+    // The output is decoded as the cairo-serde type.
+    let _call: FCall<_, ExecutionInfoV2> = reader.execution_info();
+}
@@ -0,0 +1,47 @@
+//! The execution version is given as a string or as an identifier, case insensitive.
+use cainome::rs::abigen;
+use starknet::accounts::{ExecutionV1, ExecutionV3, SingleOwnerAccount};
+use starknet::core::types::Felt;
+use starknet::providers::jsonrpc::HttpTransport;
+use starknet::providers::JsonRpcClient;
+use starknet::signers::LocalWallet;
+
+mod v1 {
+    cainome::rs::abigen!(
+        MyContract,
+        r#"[
+            {
+                "type": "function",
+                "name": "set",
+                "inputs": [{ "name": "v", "type": "core::felt252" }],
+                "outputs": [],
+                "state_mutability": "external"
+            }
+        ]"#,
+        execution_version("V1")
+    );
+}
+
+abigen!(
+    MyContract,
+    r#"[
+        {
+            "type": "function",
+            "name": "set",
+            "inputs": [{ "name": "v", "type": "core::felt252" }],
+            "outputs": [],
+            "state_mutability": "external"
+        }
+    ]"#,
+    execution_version(v3)
+);
+
+type Account = SingleOwnerAccount<JsonRpcClient<HttpTransport>, LocalWallet>;
+
+#[allow(dead_code)]
+fn executions(v1: &v1::MyContract<Account>, v3: &MyContract<Account>) {
+    let _: ExecutionV1<'_, Account> = v1.set(&Felt::ONE);
+    let _: ExecutionV3<'_, Account> = v3.set(&Felt::ONE);
+}
+
+fn main() {}
@@ -0,0 +1,91 @@
+//! The `decode` generation mode only emits the types and the decoding of the events,
+//! and the `encode` mode the types and the encoding of the calls to the externals.
+use starknet::core::types::{Event as StarknetEvent, Felt};
+use starknet::core::utils::get_selector_from_name;
+
+mod indexer {
+    use cainome::rs::abigen;
+
+    abigen!(
+        Book,
+        r#"[
+            {
+                "type": "function",
+                "name": "cancel",
+                "inputs": [{ "name": "id", "type": "core::felt252" }],
+                "outputs": [],
+                "state_mutability": "external"
+            },
+            {
+                "type": "event",
+                "name": "pkg::Cancelled",
+                "kind": "struct",
+                "members": [{ "name": "id", "type": "core::felt252", "kind": "key" }]
+            },
+            {
+                "type": "event",
+                "name": "pkg::Event",
+                "kind": "enum",
+                "variants": [{ "name": "Cancelled", "type": "pkg::Cancelled", "kind": "nested" }]
+            }
+        ]"#,
+        derives(Debug, PartialEq),
+        generation_mode("decode"),
+        prelude
+    );
+}
+
+mod relayer {
+    use cainome::rs::abigen;
+
+    abigen!(
+        Book,
+        r#"[
+            {
+                "type": "function",
+                "name": "cancel",
+                "inputs": [{ "name": "id", "type": "core::felt252" }],
+                "outputs": [],
+                "state_mutability": "external"
+            },
+            {
+                "type": "event",
+                "name": "pkg::Cancelled",
+                "kind": "struct",
+                "members": [{ "name": "id", "type": "core::felt252", "kind": "key" }]
+            },
+            {
+                "type": "event",
+                "name": "pkg::Event",
+                "kind": "enum",
+                "variants": [{ "name": "Cancelled", "type": "pkg::Cancelled", "kind": "nested" }]
+            }
+        ]"#,
+        derives(Debug, PartialEq),
+        generation_mode("encode"),
+        prelude
+    );
+}
+
+fn main() {
+    let event = StarknetEvent {
+        from_address: Felt::ONE,
+        keys: vec![get_selector_from_name("Cancelled").unwrap(), Felt::TWO],
+        data: vec![],
+    };
+
+    assert_eq!(
+        indexer::book_prelude::BookEvent::try_from(&event).unwrap(),
+        indexer::Event::Cancelled(indexer::Cancelled { id: Felt::TWO })
+    );
+
+    let calls = relayer::book_prelude::BookCalls::new(Felt::ONE);
+    let call = calls.cancel_getcall(&Felt::TWO);
+
+    assert_eq!(call.to, Felt::ONE);
+    assert_eq!(call.selector, get_selector_from_name("cancel").unwrap());
+    assert_eq!(call.calldata, vec![Felt::TWO]);
+
+    // The types are still (de)serialized.
+    let _ = relayer::Cancelled { id: Felt::TWO };
+}
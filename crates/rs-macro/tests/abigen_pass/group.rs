@@ -0,0 +1,109 @@
+//! The types defined identically by the contracts of a group are generated once,
+//! in the `common` module, and re-exported by the modules of the contracts.
+use cainome::rs::abigen_group;
+use starknet::core::types::{EmittedEvent, Felt};
+
+#[abigen_group(derives(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize))]
+pub mod bindings {
+    /// The other items of the module are kept.
+    pub const VERSION: u32 = 1;
+
+    contract!(
+        Market,
+        r#"[
+            {
+                "type": "struct",
+                "name": "pkg::Order",
+                "members": [{ "name": "id", "type": "core::felt252" }]
+            },
+            {
+                "type": "struct",
+                "name": "pkg::Quote",
+                "members": [{ "name": "price", "type": "core::felt252" }]
+            },
+            {
+                "type": "event",
+                "name": "pkg::Placed",
+                "kind": "struct",
+                "members": [{ "name": "order", "type": "pkg::Order", "kind": "data" }]
+            },
+            {
+                "type": "event",
+                "name": "pkg::Event",
+                "kind": "enum",
+                "variants": [{ "name": "Placed", "type": "pkg::Placed", "kind": "nested" }]
+            },
+            {
+                "type": "function",
+                "name": "place",
+                "inputs": [
+                    { "name": "order", "type": "pkg::Order" },
+                    { "name": "quote", "type": "pkg::Quote" }
+                ],
+                "outputs": [],
+                "state_mutability": "external"
+            }
+        ]"#
+    );
+
+    contract!(
+        Router,
+        r#"[
+            {
+                "type": "struct",
+                "name": "pkg::Order",
+                "members": [{ "name": "id", "type": "core::felt252" }]
+            },
+            {
+                "type": "struct",
+                "name": "pkg::Quote",
+                "members": [{ "name": "price", "type": "core::integer::u64" }]
+            },
+            {
+                "type": "event",
+                "name": "pkg::Placed",
+                "kind": "struct",
+                "members": [{ "name": "order", "type": "pkg::Order", "kind": "data" }]
+            },
+            {
+                "type": "event",
+                "name": "pkg::Event",
+                "kind": "enum",
+                "variants": [{ "name": "Placed", "type": "pkg::Placed", "kind": "nested" }]
+            },
+            {
+                "type": "function",
+                "name": "route",
+                "inputs": [
+                    { "name": "order", "type": "pkg::Order" },
+                    { "name": "quote", "type": "pkg::Quote" }
+                ],
+                "outputs": [],
+                "state_mutability": "view"
+            }
+        ]"#,
+        derives(Eq)
+    );
+}
+
+use bindings::{common, market, router};
+
+fn same_order(order: market::Order) -> router::Order {
+    order
+}
+
+fn decode(event: &EmittedEvent) -> Result<common::Event, String> {
+    router::Event::try_from(event)
+}
+
+fn main() {
+    let order = common::Order { id: Felt::ONE };
+    assert_eq!(same_order(order.clone()), order);
+
+    // `Quote` differs, it is generated by each contract.
+    let _ = market::Quote { price: Felt::ONE };
+    let _ = router::Quote { price: 1 };
+
+    assert_eq!(bindings::VERSION, 1);
+    let _ = decode;
+}
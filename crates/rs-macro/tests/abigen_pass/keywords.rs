@@ -0,0 +1,59 @@
+//! The names of the ABI which are keywords of Rust are raw identifiers,
+//! including the name of the contract.
+use cainome::rs::abigen;
+use starknet::core::types::Felt;
+use starknet::providers::jsonrpc::HttpTransport;
+use starknet::providers::JsonRpcClient;
+
+abigen!(
+    r#match,
+    r#"[
+        {
+            "type": "struct",
+            "name": "pkg::Move",
+            "members": [
+                { "name": "async", "type": "core::felt252" },
+                { "name": "final", "type": "core::bool" }
+            ]
+        },
+        {
+            "type": "function",
+            "name": "move",
+            "inputs": [
+                { "name": "type", "type": "pkg::Move" },
+                { "name": "box", "type": "core::felt252" }
+            ],
+            "outputs": [],
+            "state_mutability": "external"
+        },
+        {
+            "type": "function",
+            "name": "yield",
+            "inputs": [],
+            "outputs": [{ "type": "core::felt252" }],
+            "state_mutability": "view"
+        }
+    ]"#,
+    prelude
+);
+
+type Reader = matchReader<JsonRpcClient<HttpTransport>>;
+
+fn main() {
+    let m = Move {
+        r#async: Felt::ONE,
+        r#final: true,
+    };
+
+    assert_eq!(Reader::entrypoints().len(), 2);
+
+    let _ = Reader::r#yield;
+    let _ = match_prelude::r#match::<
+        starknet::accounts::SingleOwnerAccount<
+            JsonRpcClient<HttpTransport>,
+            starknet::signers::LocalWallet,
+        >,
+    >::move_getcall;
+
+    assert!(m.r#final);
+}
@@ -0,0 +1,79 @@
+//! The bindings of the kitchen sink contract, covering all the builtin types
+//! and the nested generic combinations, compile and round-trip.
+use cainome::cairo_serde::{CairoSerde, ContractAddress, NonZero, U256};
+use cainome::rs::abigen;
+use starknet::core::types::{EmittedEvent, Felt};
+use starknet::core::utils::get_selector_from_name;
+
+abigen!(
+    KitchenSink,
+    "$CARGO_MANIFEST_DIR/../parser/test_data/kitchen_sink.abi.json",
+    derives(Debug, PartialEq, serde::Serialize, serde::Deserialize)
+);
+
+fn main() {
+    let items = vec![Item {
+        id: 1,
+        owner: ContractAddress(Felt::ONE),
+        tags: vec![Felt::TWO, Felt::THREE].into(),
+    }];
+
+    let nested = Some(Ok::<_, Felt>(items));
+    let felts = Option::<Result<Vec<Item>, Felt>>::cairo_serialize(&nested);
+    assert_eq!(
+        Option::<Result<Vec<Item>, Felt>>::cairo_deserialize(&felts, 0).unwrap(),
+        nested
+    );
+
+    let wrapper = Wrapper {
+        inner: Wrapper {
+            inner: Kind::Pair((-1, 1)),
+            count: 1,
+        },
+        count: 2,
+    };
+    let felts = Wrapper::<Wrapper<Kind>>::cairo_serialize(&wrapper);
+    assert_eq!(
+        Wrapper::<Wrapper<Kind>>::cairo_deserialize(&felts, 0).unwrap(),
+        wrapper
+    );
+
+    let kind = Kind::Amount(U256 { low: 1, high: 0 });
+    assert_eq!(Kind::cairo_serialize(&kind), vec![Felt::ONE, Felt::ONE, Felt::ZERO]);
+
+    // The variants are not in alphabetical order, the discriminants are the ABI indexes.
+    assert_eq!(Kind::cairo_serialize(&Kind::Empty), vec![Felt::ZERO]);
+    assert_eq!(
+        Kind::cairo_deserialize(&[Felt::THREE, Felt::ZERO], 0).unwrap(),
+        Kind::Items(vec![])
+    );
+    assert_eq!(
+        Kind::cairo_deserialize(&[Felt::TWO, Felt::ONE, Felt::TWO], 0).unwrap(),
+        Kind::Pair((1, 2))
+    );
+
+    let _ = NonZero::new(Felt::ONE).unwrap();
+
+    // Three levels of events, with a selector for each level.
+    let event = EmittedEvent {
+        from_address: Felt::ONE,
+        keys: vec![
+            get_selector_from_name("Nested").unwrap(),
+            get_selector_from_name("Deep").unwrap(),
+            get_selector_from_name("Leaf").unwrap(),
+            Felt::TWO,
+        ],
+        data: vec![Felt::ZERO, Felt::THREE],
+        block_hash: None,
+        block_number: None,
+        transaction_hash: Felt::ZERO,
+    };
+
+    assert_eq!(
+        Event::try_from(&event).unwrap(),
+        Event::Nested(NestedEvent::Deep(DeepEvent::Leaf(Leaf {
+            owner: ContractAddress(Felt::TWO),
+            value: Some(3),
+        })))
+    );
+}
@@ -0,0 +1,76 @@
+//! The messages to the L1 handlers can be built for the cross-layer tests.
+use cainome::cairo_serde::U256;
+use cainome::rs::abigen;
+use starknet::core::types::{EthAddress, Felt, MsgToL2};
+use starknet::core::utils::get_selector_from_name;
+
+abigen!(
+    MyContract,
+    r#"[
+        {
+            "type": "struct",
+            "name": "contracts::Deposit",
+            "members": [
+                { "name": "account", "type": "core::starknet::contract_address::ContractAddress" },
+                { "name": "amount", "type": "core::integer::u256" }
+            ]
+        },
+        {
+            "type": "l1_handler",
+            "name": "on_deposit",
+            "inputs": [
+                { "name": "from_address", "type": "core::felt252" },
+                { "name": "deposit", "type": "contracts::Deposit" },
+                { "name": "memo", "type": "core::felt252" }
+            ],
+            "outputs": [],
+            "state_mutability": "external"
+        },
+        {
+            "type": "l1_handler",
+            "name": "ping",
+            "inputs": [{ "name": "from_address", "type": "core::felt252" }],
+            "outputs": [],
+            "state_mutability": "external"
+        }
+    ]"#
+);
+
+fn main() {
+    assert_eq!(
+        MyContractL1Handlers::ON_DEPOSIT_SELECTOR,
+        get_selector_from_name("on_deposit").unwrap()
+    );
+
+    let deposit = Deposit {
+        account: Felt::from(0x12).into(),
+        amount: U256 { low: 5, high: 0 },
+    };
+
+    let payload = MyContractL1Handlers::on_deposit_payload(&deposit, &Felt::TWO);
+    assert_eq!(
+        payload,
+        vec![Felt::from(0x12), Felt::from(5), Felt::ZERO, Felt::TWO]
+    );
+
+    let from_address = EthAddress::from_hex("0x8453fc6cd1bcfe8d4dfc069c400b433054d47bdc").unwrap();
+
+    let message =
+        MyContractL1Handlers::on_deposit_message(from_address.clone(), Felt::THREE, 7, &deposit, &Felt::TWO);
+    assert_eq!(message.from_address, from_address);
+    assert_eq!(message.to_address, Felt::THREE);
+    assert_eq!(message.selector, get_selector_from_name("on_deposit").unwrap());
+    assert_eq!(message.payload, payload);
+    assert_eq!(message.nonce, 7);
+
+    let expected = MsgToL2 {
+        from_address,
+        to_address: Felt::THREE,
+        selector: get_selector_from_name("on_deposit").unwrap(),
+        payload,
+        nonce: 7,
+    };
+    assert_eq!(message.hash(), expected.hash());
+
+    assert!(MyContractL1Handlers::ping_payload().is_empty());
+}
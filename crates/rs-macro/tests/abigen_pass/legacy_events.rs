@@ -0,0 +1,82 @@
+//! The events of a Cairo 0 contract are grouped into an `Event` enum,
+//! decoded from the emitted events.
+use cainome::rs::abigen_legacy;
+use starknet::core::types::{EmittedEvent, Felt};
+use starknet::core::utils::get_selector_from_name;
+
+abigen_legacy!(
+    MyContract,
+    r#"[
+        {
+            "type": "struct",
+            "name": "Uint256",
+            "size": 2,
+            "members": [
+                { "name": "low", "type": "felt", "offset": 0 },
+                { "name": "high", "type": "felt", "offset": 1 }
+            ]
+        },
+        {
+            "type": "event",
+            "name": "Transfer",
+            "keys": [],
+            "data": [
+                { "name": "from_", "type": "felt" },
+                { "name": "to", "type": "felt" },
+                { "name": "value", "type": "Uint256" }
+            ]
+        },
+        {
+            "type": "event",
+            "name": "Approval",
+            "keys": [],
+            "data": [
+                { "name": "owner", "type": "felt" }
+            ]
+        }
+    ]"#,
+    derives(Debug, PartialEq)
+);
+
+fn emitted(keys: Vec<Felt>, data: Vec<Felt>) -> EmittedEvent {
+    EmittedEvent {
+        from_address: Felt::ONE,
+        keys,
+        data,
+        block_hash: None,
+        block_number: None,
+        transaction_hash: Felt::ZERO,
+    }
+}
+
+fn main() {
+    let transfer = emitted(
+        vec![get_selector_from_name("Transfer").unwrap()],
+        vec![Felt::ONE, Felt::TWO, Felt::THREE, Felt::ZERO],
+    );
+
+    assert_eq!(
+        Event::try_from(&transfer).unwrap(),
+        Event::Transfer(Transfer {
+            from_: Felt::ONE,
+            to: Felt::TWO,
+            value: Uint256 {
+                low: Felt::THREE,
+                high: Felt::ZERO,
+            },
+        })
+    );
+
+    let approval = emitted(
+        vec![get_selector_from_name("Approval").unwrap()],
+        vec![Felt::TWO],
+    );
+
+    assert_eq!(
+        Event::try_from(&approval).unwrap(),
+        Event::Approval(Approval { owner: Felt::TWO })
+    );
+
+    let unknown = emitted(vec![get_selector_from_name("Unknown").unwrap()], vec![]);
+    assert!(Event::try_from(&unknown).is_err());
+}
@@ -0,0 +1,62 @@
+//! Functions with many parameters also take them as a struct.
+use cainome::rs::abigen;
+use starknet::core::types::Felt;
+use starknet::providers::jsonrpc::HttpTransport;
+use starknet::providers::{JsonRpcClient, Url};
+
+abigen!(
+    MyContract,
+    r#"[
+        {
+            "type": "function",
+            "name": "create_order",
+            "inputs": [
+                { "name": "market", "type": "core::felt252" },
+                { "name": "price", "type": "core::integer::u128" },
+                { "name": "amount", "type": "core::integer::u128" },
+                { "name": "tags", "type": "core::array::Array::<core::felt252>" }
+            ],
+            "outputs": [],
+            "state_mutability": "external"
+        },
+        {
+            "type": "function",
+            "name": "quote",
+            "inputs": [
+                { "name": "market", "type": "core::felt252" },
+                { "name": "price", "type": "core::integer::u128" },
+                { "name": "amount", "type": "core::integer::u128" }
+            ],
+            "outputs": [{ "type": "core::integer::u128" }],
+            "state_mutability": "view"
+        },
+        {
+            "type": "function",
+            "name": "get_market",
+            "inputs": [{ "name": "market", "type": "core::felt252" }],
+            "outputs": [{ "type": "core::felt252" }],
+            "state_mutability": "view"
+        }
+    ]"#,
+    derives(Debug, Clone, Default),
+    params_struct_threshold(2)
+);
+
+fn main() {
+    let provider = JsonRpcClient::new(HttpTransport::new(
+        Url::parse("http://localhost:5050").unwrap(),
+    ));
+    let reader = MyContractReader::new(Felt::ONE, &provider);
+
+    let params = QuoteParams {
+        price: 10,
+        ..Default::default()
+    };
+    let _call = reader.quote_with(&params);
+
+    let _params = CreateOrderParams {
+        market: Felt::TWO,
+        tags: vec![Felt::ONE],
+        ..Default::default()
+    };
+}
@@ -0,0 +1,91 @@
+//! With the `paymaster` option, the externals return an `Invoke`, executed by the account
+//! or sponsored by a paymaster.
+use std::future::Future;
+
+use cainome::cairo_serde::paymaster::{
+    Invoke, Paymaster, PaymasterResult, RelayerPaymaster, Sponsored, SponsoredExecution,
+};
+use cainome::rs::abigen;
+use starknet::accounts::{ExecutionV3, SingleOwnerAccount};
+use starknet::core::types::Felt;
+use starknet::providers::jsonrpc::HttpTransport;
+use starknet::providers::JsonRpcClient;
+use starknet::signers::LocalWallet;
+
+abigen!(
+    MyContract,
+    r#"[
+        {
+            "type": "function",
+            "name": "move_player",
+            "inputs": [
+                { "name": "x", "type": "core::integer::u32" },
+                { "name": "y", "type": "core::integer::u32" }
+            ],
+            "outputs": [],
+            "state_mutability": "external"
+        }
+    ]"#,
+    execution_version("v3"),
+    params_struct_threshold(1),
+    blocking,
+    paymaster
+);
+
+type Provider = JsonRpcClient<HttpTransport>;
+type Account = SingleOwnerAccount<Provider, LocalWallet>;
+
+struct Sponsor;
+
+#[async_trait::async_trait]
+impl Paymaster for Sponsor {
+    async fn execute(&self, _execution: &SponsoredExecution) -> PaymasterResult<Felt> {
+        Ok(Felt::ZERO)
+    }
+}
+
+fn assert_send<F: Future + Send>(_f: F) {}
+
+#[allow(dead_code)]
+fn invocations(contract: &MyContract<Account>, sponsor: &Sponsor) {
+    let invoke: Invoke<'_, Account, ExecutionV3<'_, Account>> = contract.move_player(&1, &2);
+    assert_eq!(invoke.calls().len(), 1);
+
+    // Executed by the account.
+    assert_send(invoke.send());
+    assert_send(invoke.estimate_fee());
+    let _: ExecutionV3<'_, Account> = invoke.map_execution(|e| e.nonce(Felt::ONE)).into_execution();
+
+    // Sponsored by the paymaster.
+    let sponsored: Sponsored<'_, Account, Sponsor> = contract
+        .move_player_with(&MovePlayerParams { x: 1, y: 2 })
+        .sponsored(sponsor);
+    assert_send(sponsored.send());
+
+    assert_send(
+        contract
+            .invoke_raw(Felt::ONE, vec![Felt::TWO])
+            .sponsored(sponsor)
+            .send(),
+    );
+
+    let batch = contract.execute_batch(vec![contract.move_player_getcall(&1, &2)]);
+    assert_eq!(batch.calls().len(), 1);
+    assert_send(batch.sponsored(sponsor).send());
+}
+
+#[allow(dead_code)]
+fn relayed(contract: &MyContract<Account>, relayer: &RelayerPaymaster<Account>, signer: &LocalWallet) {
+    // Signed by the account as an outside execution, sent by the relayer.
+    assert_send(async move {
+        contract
+            .move_player(&1, &2)
+            .sponsored(relayer)
+            .sign(signer, Felt::ONE, 100)
+            .await?
+            .send()
+            .await
+    });
+}
+
+fn main() {}
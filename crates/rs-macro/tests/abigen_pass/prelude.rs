@@ -0,0 +1,81 @@
+//! The generated items are re-exported flatly by the `<contract>_prelude` module.
+mod bindings {
+    use cainome::rs::abigen;
+
+    abigen!(
+        MyContract,
+        r#"[
+            {
+                "type": "struct",
+                "name": "core::integer::u256",
+                "members": [
+                    { "name": "low", "type": "core::integer::u128" },
+                    { "name": "high", "type": "core::integer::u128" }
+                ]
+            },
+            {
+                "type": "struct",
+                "name": "contracts::Order",
+                "members": [{ "name": "price", "type": "core::felt252" }]
+            },
+            {
+                "type": "function",
+                "name": "place",
+                "inputs": [
+                    { "name": "order", "type": "contracts::Order" },
+                    { "name": "market", "type": "core::felt252" }
+                ],
+                "outputs": [],
+                "state_mutability": "external"
+            },
+            {
+                "type": "event",
+                "name": "contracts::Placed",
+                "kind": "struct",
+                "members": [{ "name": "price", "type": "core::felt252", "kind": "data" }]
+            },
+            {
+                "type": "event",
+                "name": "contracts::Event",
+                "kind": "enum",
+                "variants": [
+                    { "name": "Placed", "type": "contracts::Placed", "kind": "nested" }
+                ]
+            }
+        ]"#,
+        params_struct_threshold(1),
+        prelude
+    );
+}
+
+use bindings::my_contract_prelude::*;
+use starknet::core::types::Felt;
+use starknet::providers::jsonrpc::HttpTransport;
+use starknet::providers::{JsonRpcClient, Url};
+
+struct Count(usize);
+
+impl EventVisitor for Count {
+    fn on_placed(&mut self, _event: &Placed) {
+        self.0 += 1;
+    }
+}
+
+fn main() {
+    let provider = JsonRpcClient::new(HttpTransport::new(
+        Url::parse("http://localhost:5050").unwrap(),
+    ));
+    let _reader = MyContractReader::new(Felt::ONE, &provider);
+
+    let _params = PlaceParams {
+        order: Order { price: Felt::ONE },
+        market: Felt::TWO,
+    };
+
+    let mut count = Count(0);
+    let event: MyContractEvent = Event::Placed(Placed { price: Felt::ONE });
+    event.accept(&mut count);
+
+    fn _is_visitor<V: MyContractEventVisitor>() {}
+    _is_visitor::<Count>();
+}
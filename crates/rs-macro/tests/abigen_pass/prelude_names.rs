@@ -0,0 +1,39 @@
+//! Types named like the Rust prelude are renamed, and don't shadow
+//! the prelude used by the generated code.
+use cainome::rs::abigen;
+
+abigen!(
+    MyContract,
+    r#"[
+        {
+            "type": "struct",
+            "name": "contracts::Option",
+            "members": [
+                { "name": "value", "type": "core::option::Option::<core::felt252>" }
+            ]
+        },
+        {
+            "type": "enum",
+            "name": "contracts::Result",
+            "variants": [
+                { "name": "Ok", "type": "()" },
+                { "name": "Err", "type": "core::felt252" }
+            ]
+        },
+        {
+            "type": "function",
+            "name": "get",
+            "inputs": [{ "name": "option", "type": "contracts::Option" }],
+            "outputs": [{ "type": "core::result::Result::<contracts::Result, core::felt252>" }],
+            "state_mutability": "view"
+        }
+    ]"#
+);
+
+fn main() {
+    let option = CairoOption { value: Some(1.into()) };
+    let result: Result<CairoResult, starknet::core::types::Felt> = Ok(CairoResult::Ok);
+
+    assert_eq!(option.value, Some(1.into()));
+    assert!(result.is_ok());
+}
@@ -0,0 +1,73 @@
+//! An enum equivalent to `Result` is mapped to the Rust `Result`, and not generated.
+use cainome::cairo_serde::CairoSerde;
+use cainome::rs::abigen;
+use starknet::accounts::ConnectedAccount;
+use starknet::core::types::Felt;
+use starknet::providers::Provider;
+
+abigen!(
+    Market,
+    r#"[
+        {
+            "type": "enum",
+            "name": "pkg::Outcome::<core::integer::u64, pkg::Error>",
+            "variants": [
+                { "name": "Success", "type": "core::integer::u64" },
+                { "name": "Failure", "type": "pkg::Error" }
+            ]
+        },
+        {
+            "type": "enum",
+            "name": "pkg::Error",
+            "variants": [
+                { "name": "Expired", "type": "()" },
+                { "name": "Unknown", "type": "core::felt252" }
+            ]
+        },
+        {
+            "type": "struct",
+            "name": "pkg::Receipt",
+            "members": [
+                { "name": "outcome", "type": "pkg::Outcome::<core::integer::u64, pkg::Error>" }
+            ]
+        },
+        {
+            "type": "function",
+            "name": "settle",
+            "inputs": [{ "name": "id", "type": "core::felt252" }],
+            "outputs": [{ "type": "pkg::Outcome::<core::integer::u64, pkg::Error>" }],
+            "state_mutability": "view"
+        },
+        {
+            "type": "function",
+            "name": "record",
+            "inputs": [{ "name": "outcome", "type": "pkg::Outcome::<core::integer::u64, pkg::Error>" }],
+            "outputs": [],
+            "state_mutability": "external"
+        }
+    ]"#,
+    derives(Debug, PartialEq, serde::Serialize, serde::Deserialize),
+    result_enums {
+        pkg::Outcome as Result<Success, Failure>;
+    }
+);
+
+async fn settle<P: Provider + Sync>(reader: &MarketReader<P>) -> Result<u64, Error> {
+    reader.settle(&Felt::ONE).call().await.unwrap()
+}
+
+fn record<A: ConnectedAccount + Sync>(market: &Market<A>) {
+    let _call = market.record_getcall(&Ok(1));
+}
+
+fn main() {
+    let receipt = Receipt {
+        outcome: Err(Error::Unknown(Felt::ONE)),
+    };
+    let felts = Receipt::cairo_serialize(&receipt);
+    assert_eq!(felts, vec![Felt::ONE, Felt::ONE, Felt::ONE]);
+    assert_eq!(Receipt::cairo_deserialize(&felts, 0).unwrap(), receipt);
+
+    let _ = settle::<starknet::providers::AnyProvider>;
+    let _ = record::<starknet::accounts::SingleOwnerAccount<starknet::providers::AnyProvider, starknet::signers::LocalWallet>>;
+}
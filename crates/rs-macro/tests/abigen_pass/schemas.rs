@@ -0,0 +1,39 @@
+//! The `JsonSchema` and `ToSchema` derives are accepted, the felt members being
+//! documented as strings.
+use cainome::rs::abigen;
+use utoipa::ToSchema;
+
+abigen!(
+    KitchenSink,
+    "$CARGO_MANIFEST_DIR/../parser/test_data/kitchen_sink.abi.json",
+    derives(
+        Debug,
+        serde::Serialize,
+        serde::Deserialize,
+        schemars::JsonSchema,
+        utoipa::ToSchema
+    ),
+    serde_bytes("hex")
+);
+
+fn main() {
+    let schema = serde_json::to_value(schemars::schema_for!(Item)).unwrap();
+    assert_eq!(schema["properties"]["owner"]["type"], "string");
+    assert_eq!(schema["properties"]["tags"]["items"]["type"], "string");
+    assert_eq!(schema["properties"]["id"]["type"], "string");
+
+    let schema = serde_json::to_value(schemars::schema_for!(Leaf)).unwrap();
+    assert_eq!(schema["properties"]["owner"]["type"], "string");
+
+    let schema = serde_json::to_value(schemars::schema_for!(Wrapper<Kind>)).unwrap();
+    assert_eq!(schema["properties"]["count"]["type"], "integer");
+
+    let (name, schema) = <Item as ToSchema>::schema();
+    let schema = serde_json::to_value(schema).unwrap();
+    assert_eq!(name, "Item");
+    assert_eq!(schema["properties"]["owner"]["type"], "string");
+    assert_eq!(schema["properties"]["tags"]["items"]["type"], "string");
+
+    let schema = serde_json::to_value(<Kind as ToSchema>::schema().1).unwrap();
+    assert!(schema["oneOf"].is_array());
+}
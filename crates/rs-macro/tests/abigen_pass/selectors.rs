@@ -0,0 +1,93 @@
+//! The selectors are precomputed in the generated code, and exposed as constants
+//! of the contract and the reader.
+use cainome::rs::abigen;
+use starknet::core::types::Felt;
+use starknet::core::utils::get_selector_from_name;
+use starknet::providers::jsonrpc::HttpTransport;
+use starknet::providers::{JsonRpcClient, Url};
+
+abigen!(
+    MyContract,
+    r#"[
+        {
+            "type": "event",
+            "name": "contracts::Moved",
+            "kind": "struct",
+            "members": [
+                { "name": "player", "type": "core::felt252", "kind": "key" }
+            ]
+        },
+        {
+            "type": "event",
+            "name": "contracts::Event",
+            "kind": "enum",
+            "variants": [
+                { "name": "Moved", "type": "contracts::Moved", "kind": "nested" }
+            ]
+        },
+        {
+            "type": "function",
+            "name": "get_position",
+            "inputs": [],
+            "outputs": [{ "type": "core::felt252" }],
+            "state_mutability": "view"
+        },
+        {
+            "type": "function",
+            "name": "move_to",
+            "inputs": [{ "name": "x", "type": "core::felt252" }],
+            "outputs": [],
+            "state_mutability": "external"
+        }
+    ]"#
+);
+
+fn main() {
+    assert_eq!(
+        Moved::event_selector(),
+        get_selector_from_name("Moved").unwrap()
+    );
+
+    let event = starknet::core::types::EmittedEvent {
+        from_address: Felt::ONE,
+        keys: vec![get_selector_from_name("Moved").unwrap(), Felt::TWO],
+        data: vec![],
+        block_hash: None,
+        block_number: None,
+        transaction_hash: Felt::ZERO,
+    };
+    assert!(
+        matches!(Event::try_from(&event), Ok(Event::Moved(Moved { player })) if player == Felt::TWO)
+    );
+
+    let provider = JsonRpcClient::new(HttpTransport::new(
+        Url::parse("http://localhost:5050").unwrap(),
+    ));
+    let reader = MyContractReader::new(Felt::ONE, &provider);
+
+    assert_eq!(
+        reader.get_position().call_raw.entry_point_selector,
+        get_selector_from_name("get_position").unwrap()
+    );
+
+    type Reader<'p> = MyContractReader<&'p JsonRpcClient<HttpTransport>>;
+
+    assert_eq!(
+        Reader::SELECTOR_MOVE_TO,
+        get_selector_from_name("move_to").unwrap()
+    );
+    assert_eq!(
+        Reader::entrypoints(),
+        &[
+            ("get_position", Reader::SELECTOR_GET_POSITION),
+            ("move_to", Reader::SELECTOR_MOVE_TO),
+        ]
+    );
+
+    let selector = reader.get_position().call_raw.entry_point_selector;
+    let name = Reader::entrypoints()
+        .iter()
+        .find(|(_, s)| *s == selector)
+        .map(|(name, _)| *name);
+    assert_eq!(name, Some("get_position"));
+}
@@ -0,0 +1,99 @@
+//! The generated types, calls and futures must be usable across threads,
+//! for instance inside `tokio::spawn`, with the standard providers and accounts.
+use std::future::Future;
+use std::sync::Arc;
+
+use cainome::cairo_serde::call::MultiCall;
+use cainome::rs::abigen;
+use starknet::accounts::SingleOwnerAccount;
+use starknet::core::types::Felt;
+use starknet::providers::jsonrpc::HttpTransport;
+use starknet::providers::JsonRpcClient;
+use starknet::signers::LocalWallet;
+
+abigen!(
+    MyContract,
+    r#"[
+        {
+            "type": "struct",
+            "name": "contracts::MyStruct",
+            "members": [
+                { "name": "a", "type": "core::felt252" },
+                { "name": "b", "type": "core::byte_array::ByteArray" },
+                { "name": "c", "type": "core::array::Array::<core::integer::u32>" }
+            ]
+        },
+        {
+            "type": "enum",
+            "name": "contracts::MyEnum",
+            "variants": [
+                { "name": "One", "type": "()" },
+                { "name": "Two", "type": "contracts::MyStruct" }
+            ]
+        },
+        {
+            "type": "function",
+            "name": "get_struct",
+            "inputs": [{ "name": "e", "type": "contracts::MyEnum" }],
+            "outputs": [{ "type": "contracts::MyStruct" }],
+            "state_mutability": "view"
+        },
+        {
+            "type": "function",
+            "name": "set_struct",
+            "inputs": [{ "name": "s", "type": "contracts::MyStruct" }],
+            "outputs": [],
+            "state_mutability": "external"
+        }
+    ]"#,
+    blocking
+);
+
+type Provider = JsonRpcClient<HttpTransport>;
+type Account = SingleOwnerAccount<Provider, LocalWallet>;
+
+fn assert_send_sync<T: Send + Sync>() {}
+fn assert_send_value<T: Send>(_v: T) {}
+fn assert_send<F: Future + Send>(_f: F) {}
+fn assert_send_static<F: Future + Send + 'static>(_f: F) {}
+
+#[allow(dead_code)]
+fn futures_are_send(contract: &MyContract<Account>, reader: &MyContractReader<Provider>) {
+    assert_send(contract.get_struct(&MyEnum::One).call());
+    assert_send(reader.get_struct(&MyEnum::One).call());
+    assert_send(reader.get_struct(&MyEnum::One).raw_call());
+    assert_send(contract.call_raw(Felt::ONE, vec![Felt::TWO]));
+    assert_send(reader.call_raw(Felt::ONE, vec![Felt::TWO]));
+}
+
+#[allow(dead_code)]
+fn owned_futures_are_static(reader: &MyContractReader<Arc<Provider>>) {
+    assert_send_static(reader.get_struct(&MyEnum::One).into_owned().call());
+}
+
+#[allow(dead_code)]
+fn executions_are_send(contract: &MyContract<Account>) {
+    let s = MyStruct {
+        a: Felt::ONE,
+        b: Default::default(),
+        c: vec![],
+    };
+
+    assert_send_value(contract.set_struct_getcall(&s));
+    assert_send_value(contract.set_struct(&s));
+    assert_send(contract.set_struct(&s).send());
+    assert_send(contract.invoke_raw(Felt::ONE, vec![Felt::TWO]).send());
+
+    let calls = MultiCall::new()
+        .call(contract.set_struct_getcall(&s))
+        .call(contract.set_struct_getcall(&s));
+    assert_send(contract.execute_batch(calls).send());
+}
+
+fn main() {
+    assert_send_sync::<MyStruct>();
+    assert_send_sync::<MyEnum>();
+    assert_send_sync::<MyContract<Account>>();
+    assert_send_sync::<MyContractReader<Provider>>();
+    assert_send_sync::<MyContractReader<Arc<Provider>>>();
+}
@@ -0,0 +1,10 @@
+//! The SHA-256 of the ABI is checked when given.
+use cainome::rs::abigen;
+
+abigen!(
+    MyContract,
+    "[]",
+    sha256("0x4f53cda18c2baa0c0354bb5f9a3ecbe5ed12ab4d8e11ba873c2f11161202b945")
+);
+
+fn main() {}
@@ -0,0 +1,89 @@
+//! The spans are `CairoSpan`, and the span inputs are taken as slices.
+use cainome::cairo_serde::{CairoSerde, CairoSpan};
+use cainome::rs::abigen;
+use starknet::core::types::Felt;
+
+abigen!(
+    MyContract,
+    r#"[
+        {
+            "type": "struct",
+            "name": "contracts::Batch",
+            "members": [
+                { "name": "ids", "type": "core::array::Span::<core::felt252>" },
+                { "name": "amounts", "type": "core::array::Array::<core::integer::u32>" }
+            ]
+        },
+        {
+            "type": "function",
+            "name": "submit",
+            "inputs": [
+                { "name": "ids", "type": "core::array::Span::<core::felt252>" },
+                { "name": "batches", "type": "core::array::Span::<contracts::Batch>" },
+                { "name": "amounts", "type": "core::array::Array::<core::integer::u32>" }
+            ],
+            "outputs": [],
+            "state_mutability": "external"
+        },
+        {
+            "type": "function",
+            "name": "ids",
+            "inputs": [],
+            "outputs": [{ "type": "core::array::Span::<core::felt252>" }],
+            "state_mutability": "view"
+        },
+        {
+            "type": "l1_handler",
+            "name": "on_ids",
+            "inputs": [
+                { "name": "from_address", "type": "core::felt252" },
+                { "name": "ids", "type": "core::array::Span::<core::felt252>" }
+            ],
+            "outputs": [],
+            "state_mutability": "external"
+        }
+    ]"#,
+    derives(Debug, Clone, PartialEq)
+);
+
+fn submit_call<A: starknet::accounts::ConnectedAccount + Sync>(
+    contract: &MyContract<A>,
+    ids: &[Felt],
+    batches: &[Batch],
+) -> starknet::core::types::Call {
+    // Borrowed slices, and `&Vec` for the spans.
+    contract.submit_getcall(ids, batches, &vec![1])
+}
+
+fn ids_call<P: starknet::providers::Provider + Sync>(
+    reader: &MyContractReader<P>,
+) -> cainome::cairo_serde::call::FCall<P, CairoSpan<Felt>> {
+    reader.ids()
+}
+
+fn main() {
+    let _ = submit_call::<starknet::accounts::SingleOwnerAccount<
+        starknet::providers::AnyProvider,
+        starknet::signers::LocalWallet,
+    >>;
+    let _ = ids_call::<starknet::providers::AnyProvider>;
+
+    let batch = Batch {
+        ids: CairoSpan(vec![Felt::ONE, Felt::TWO]),
+        amounts: vec![3],
+    };
+
+    let felts = Batch::cairo_serialize(&batch);
+    assert_eq!(
+        felts,
+        vec![Felt::TWO, Felt::ONE, Felt::TWO, Felt::ONE, Felt::THREE]
+    );
+    assert_eq!(Batch::cairo_deserialize(&felts, 0).unwrap(), batch);
+
+    let ids = [Felt::ONE, Felt::TWO];
+    assert_eq!(
+        MyContractL1Handlers::on_ids_payload(&ids[..1]),
+        vec![Felt::ONE, Felt::ONE]
+    );
+    assert_eq!(batch.ids, vec![Felt::ONE, Felt::TWO]);
+}
@@ -0,0 +1,64 @@
+//! The SRC5 ids of the interfaces are precomputed, with methods probing for them.
+use cainome::rs::abigen;
+use starknet::core::types::Felt;
+use starknet::core::utils::get_selector_from_name;
+use starknet::providers::jsonrpc::HttpTransport;
+use starknet::providers::{JsonRpcClient, Url};
+
+abigen!(
+    MyContract,
+    r#"[
+        {
+            "type": "interface",
+            "name": "openzeppelin::introspection::interface::ISRC5",
+            "items": [
+                {
+                    "type": "function",
+                    "name": "supports_interface",
+                    "inputs": [{ "name": "interface_id", "type": "core::felt252" }],
+                    "outputs": [{ "type": "core::bool" }],
+                    "state_mutability": "view"
+                }
+            ]
+        },
+        {
+            "type": "interface",
+            "name": "contracts::IMover",
+            "items": [
+                {
+                    "type": "function",
+                    "name": "move_to",
+                    "inputs": [{ "name": "x", "type": "core::felt252" }],
+                    "outputs": [],
+                    "state_mutability": "external"
+                }
+            ]
+        }
+    ]"#,
+    src5
+);
+
+fn main() {
+    assert_eq!(
+        MY_CONTRACT_ISRC5_INTERFACE_ID,
+        Felt::from_hex_unchecked(
+            "0x3f918d17e5ee77373b56385708f855659a07f75997f365cf87748628532a055"
+        )
+    );
+
+    let provider = JsonRpcClient::new(HttpTransport::new(
+        Url::parse("http://localhost:5050").unwrap(),
+    ));
+    let reader = MyContractReader::new(Felt::ONE, &provider);
+
+    let probe = reader.supports_i_mover();
+    assert_eq!(
+        probe.call_raw.entry_point_selector,
+        get_selector_from_name("supports_interface").unwrap()
+    );
+    assert_eq!(probe.call_raw.calldata, vec![MY_CONTRACT_I_MOVER_INTERFACE_ID]);
+
+    // The entrypoint of the ABI is still generated.
+    let _ = reader.supports_interface(&MY_CONTRACT_ISRC5_INTERFACE_ID);
+    let _ = reader.supports_isrc5();
+}
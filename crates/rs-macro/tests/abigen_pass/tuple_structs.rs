@@ -0,0 +1,51 @@
+//! With `tuple_structs`, the fixed tuples are generated as named structs, serialized like the tuples.
+use cainome::cairo_serde::CairoSerde;
+use cainome::rs::abigen;
+use starknet::core::types::Felt;
+
+abigen!(
+    MyGame,
+    r#"[
+        {
+            "type": "struct",
+            "name": "game::Player",
+            "members": [
+                { "name": "position", "type": "(core::felt252, core::felt252, core::felt252)" },
+                { "name": "speed", "type": "(core::integer::u8, core::integer::u8)" }
+            ]
+        },
+        {
+            "type": "function",
+            "name": "move_to",
+            "inputs": [
+                { "name": "path", "type": "core::array::Array::<(core::felt252, core::felt252, core::felt252)>" }
+            ],
+            "outputs": [{ "type": "game::Player" }],
+            "state_mutability": "external"
+        }
+    ]"#,
+    tuple_structs {
+        (core::felt252, core::felt252, core::felt252) as Vec3 { x, y, z };
+    }
+);
+
+fn main() {
+    let position = Vec3 {
+        x: Felt::ONE,
+        y: Felt::TWO,
+        z: Felt::THREE,
+    };
+
+    let tuple = (Felt::ONE, Felt::TWO, Felt::THREE);
+    assert_eq!(
+        Vec3::cairo_serialize(&position),
+        <(Felt, Felt, Felt)>::cairo_serialize(&tuple)
+    );
+
+    // The tuples which are not mapped are left as tuples.
+    let player = Player {
+        position,
+        speed: (1, 2),
+    };
+    assert_eq!(Player::cairo_serialized_size(&player), 5);
+}
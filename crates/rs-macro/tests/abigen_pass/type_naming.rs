@@ -0,0 +1,40 @@
+//! With `type_naming("shortest_unique")`, the types named the same are prefixed by their modules.
+use cainome::cairo_serde::CairoSerde;
+use cainome::rs::abigen;
+use starknet::core::types::Felt;
+
+abigen!(
+    MyMarket,
+    r#"[
+        {
+            "type": "struct",
+            "name": "market::token::Transfer",
+            "members": [{ "name": "amount", "type": "core::integer::u64" }]
+        },
+        {
+            "type": "struct",
+            "name": "market::nft::Transfer",
+            "members": [{ "name": "token_id", "type": "core::integer::u64" }]
+        },
+        {
+            "type": "function",
+            "name": "settle",
+            "inputs": [
+                { "name": "token", "type": "market::token::Transfer" },
+                { "name": "nft", "type": "market::nft::Transfer" }
+            ],
+            "outputs": [],
+            "state_mutability": "external"
+        }
+    ]"#,
+    type_naming("shortest_unique"),
+    derives(Debug, PartialEq, serde::Serialize, serde::Deserialize)
+);
+
+fn main() {
+    let token = TokenTransfer { amount: 3 };
+    let nft = NftTransfer { token_id: 7 };
+
+    assert_eq!(TokenTransfer::cairo_serialize(&token), vec![Felt::THREE]);
+    assert_eq!(NftTransfer::cairo_serialize(&nft), vec![Felt::from(7)]);
+}
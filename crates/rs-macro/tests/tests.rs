@@ -1,5 +1,153 @@
+use cainome_parser::{AbiParser, TokenizedAbi};
+use cainome_rs::{ExpandOptions, SharedTypes};
+use std::path::Path;
+
 #[test]
 fn test_compile_fail_abigen() {
     let cases = trybuild::TestCases::new();
     cases.compile_fail("tests/abigen/*.rs");
 }
+
+#[test]
+fn test_compile_pass_abigen() {
+    let cases = trybuild::TestCases::new();
+    cases.pass("tests/abigen_pass/*.rs");
+}
+
+/// Writes the bindings of the contracts and their examples, in the layout of the
+/// rust plugin of the CLI: the bindings in `dir`, the examples in `dir/examples`.
+fn write_examples(dir: &Path, contracts: &[(&str, &TokenizedAbi)], shared_types: bool) {
+    std::fs::create_dir_all(dir.join("examples")).unwrap();
+
+    let tokens: Vec<_> = contracts.iter().map(|(_, abi)| *abi).collect();
+    let shared = shared_types.then(|| SharedTypes::new(&tokens));
+    let sibling_modules: Vec<String> = shared.iter().map(|_| "types".to_string()).collect();
+
+    // The serde attributes of the members require the serde derives.
+    let options = ExpandOptions {
+        derives: ["Debug", "serde::Serialize", "serde::Deserialize"]
+            .map(String::from)
+            .to_vec(),
+        ..Default::default()
+    };
+
+    for (i, (name, abi)) in contracts.iter().enumerate() {
+        let options = ExpandOptions {
+            shared_types: shared
+                .as_ref()
+                .map(|s| s.for_contract(i, "super::types"))
+                .unwrap_or_default(),
+            ..options.clone()
+        };
+
+        let module = name.to_lowercase();
+        std::fs::write(
+            dir.join(format!("{module}.rs")),
+            cainome_rs::abi_to_tokenstream(name, abi, &options).to_string(),
+        )
+        .unwrap();
+
+        std::fs::write(
+            dir.join("examples").join(format!("{module}.rs")),
+            cainome_rs::abi_to_example_tokenstream(
+                name,
+                abi,
+                &format!("../{module}.rs"),
+                &sibling_modules,
+            )
+            .to_string(),
+        )
+        .unwrap();
+    }
+
+    if let Some(shared) = &shared {
+        std::fs::write(
+            dir.join("types.rs"),
+            cainome_rs::shared_types_to_tokenstream(shared, &options).to_string(),
+        )
+        .unwrap();
+    }
+}
+
+#[test]
+fn test_compile_pass_examples() {
+    let abi = std::fs::read_to_string(
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("../parser/test_data/kitchen_sink.abi.json"),
+    )
+    .unwrap();
+    let abi = AbiParser::tokens_from_abi_string(&abi, &Default::default()).unwrap();
+
+    // The functions with generic inputs are left out of the example.
+    let generic = AbiParser::tokens_from_abi_string(
+        r#"[
+            {
+                "type": "struct",
+                "name": "pkg::Wrapper::<core::felt252>",
+                "members": [{ "name": "inner", "type": "core::felt252" }]
+            },
+            {
+                "type": "function",
+                "name": "get",
+                "inputs": [{ "name": "w", "type": "pkg::Wrapper::<core::felt252>" }],
+                "outputs": [{ "type": "core::felt252" }],
+                "state_mutability": "view"
+            },
+            {
+                "type": "function",
+                "name": "set",
+                "inputs": [{ "name": "w", "type": "pkg::Wrapper::<core::felt252>" }],
+                "outputs": [],
+                "state_mutability": "external"
+            },
+            {
+                "type": "function",
+                "name": "touch",
+                "inputs": [],
+                "outputs": [],
+                "state_mutability": "external"
+            }
+        ]"#,
+        &Default::default(),
+    )
+    .unwrap();
+
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("examples");
+    write_examples(
+        &dir.join("own_types"),
+        &[("KitchenSink", &abi), ("Generic", &generic)],
+        false,
+    );
+    write_examples(
+        &dir.join("shared_types"),
+        &[("KitchenSink", &abi), ("OtherSink", &abi)],
+        true,
+    );
+
+    // The examples connect to a node, they are only compiled: each one is
+    // a module of a program doing nothing.
+    let mut programs = vec![];
+    for layout in ["own_types", "shared_types"] {
+        for example in std::fs::read_dir(dir.join(layout).join("examples")).unwrap() {
+            let example = example.unwrap().path();
+            let program = dir.join(format!(
+                "{layout}_{}",
+                example.file_name().unwrap().to_str().unwrap()
+            ));
+
+            std::fs::write(
+                &program,
+                format!(
+                    "#[path = {:?}]\n#[allow(dead_code)]\nmod example;\n\nfn main() {{}}\n",
+                    example
+                ),
+            )
+            .unwrap();
+            programs.push(program);
+        }
+    }
+
+    let cases = trybuild::TestCases::new();
+    for program in programs {
+        cases.pass(program);
+    }
+}
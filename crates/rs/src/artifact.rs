@@ -0,0 +1,99 @@
+//! Sierra classes embedded into the bindings.
+//!
+//! Test harnesses declare and deploy their contracts programmatically. Embedding the Sierra
+//! class into the bindings, with its class hash and the hash of its compiled class (CASM),
+//! lets the generated contract declare its own class without reading the artifacts at runtime.
+use starknet::core::types::contract::{CompiledClass, SierraClass};
+use starknet::core::types::Felt;
+use std::fs;
+use std::path::Path;
+
+/// The extension of the Sierra classes written by Scarb.
+const SIERRA_EXTENSION: &str = ".contract_class.json";
+/// The extension of the compiled classes written by Scarb, next to the Sierra classes.
+const CASM_EXTENSION: &str = ".compiled_contract_class.json";
+
+/// A Sierra class embedded into the bindings with `include_str!`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmbeddedClass {
+    /// The absolute path of the Sierra class, included by the generated code.
+    pub sierra_path: String,
+    /// The hash of the Sierra class.
+    pub class_hash: Felt,
+    /// The hash of the compiled class, declared along with the Sierra class.
+    pub compiled_class_hash: Felt,
+}
+
+impl EmbeddedClass {
+    /// Loads the Sierra class at the given path, and the compiled class written by Scarb
+    /// next to it (`<contract>.compiled_contract_class.json`) to compute its hash.
+    ///
+    /// # Arguments
+    ///
+    /// * `sierra_path` - The path of the Sierra class (`<contract>.contract_class.json`).
+    pub fn from_sierra_path(sierra_path: &Path) -> Result<Self, String> {
+        let sierra_path = sierra_path
+            .canonicalize()
+            .map_err(|e| format!("{}: {}", sierra_path.display(), e))?;
+        let sierra_path_str = sierra_path.to_string_lossy().to_string();
+
+        let Some(stem) = sierra_path_str.strip_suffix(SIERRA_EXTENSION) else {
+            return Err(format!(
+                "{}: the path of the Sierra class must end with `{}`",
+                sierra_path_str, SIERRA_EXTENSION
+            ));
+        };
+        let casm_path = format!("{}{}", stem, CASM_EXTENSION);
+
+        let sierra =
+            fs::read_to_string(&sierra_path).map_err(|e| format!("{}: {}", sierra_path_str, e))?;
+        let class_hash = serde_json::from_str::<SierraClass>(&sierra)
+            .map_err(|e| format!("{}: invalid Sierra class: {}", sierra_path_str, e))?
+            .class_hash()
+            .map_err(|e| format!("{}: {}", sierra_path_str, e))?;
+
+        let casm = fs::read_to_string(&casm_path).map_err(|e| {
+            format!(
+                "{}: {} (the compiled class is written with `casm = true` in the `starknet-contract` target of Scarb)",
+                casm_path, e
+            )
+        })?;
+        let compiled_class_hash = serde_json::from_str::<CompiledClass>(&casm)
+            .map_err(|e| format!("{}: invalid compiled class: {}", casm_path, e))?
+            .class_hash()
+            .map_err(|e| format!("{}: {}", casm_path, e))?;
+
+        Ok(Self {
+            sierra_path: sierra_path_str,
+            class_hash,
+            compiled_class_hash,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_sierra_path() {
+        let class = EmbeddedClass::from_sierra_path(Path::new(
+            "../parser/test_data/counter.contract_class.json",
+        ))
+        .unwrap();
+
+        assert!(Path::new(&class.sierra_path).is_absolute());
+        assert_ne!(class.class_hash, class.compiled_class_hash);
+
+        let err =
+            EmbeddedClass::from_sierra_path(Path::new("../parser/test_data/cairo_ls_abi.json"))
+                .unwrap_err();
+        assert!(err.ends_with("the path of the Sierra class must end with `.contract_class.json`"));
+
+        let err = EmbeddedClass::from_sierra_path(Path::new(
+            "../parser/test_data/missing.contract_class.json",
+        ))
+        .unwrap_err();
+        assert!(err.starts_with("../parser/test_data/missing.contract_class.json: "));
+    }
+}
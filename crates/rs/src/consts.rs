@@ -0,0 +1,195 @@
+//! Constants of a contract, declared with the bindings.
+//!
+//! Some contracts expose constants through views (`fee_bps()`, `max_supply()`...).
+//! Declaring them with the bindings keeps the magic numbers near the generated code,
+//! while the generated `check_consts()` method of the reader (and the generated test
+//! calling it) ensures they don't drift from the deployed contract.
+use cainome_parser::tokens::{Function, FunctionOutputKind, StateMutability};
+use cainome_parser::TokenizedAbi;
+use convert_case::{Case, Casing};
+use serde::{Deserialize, Serialize};
+
+/// A constant of the contract, emitted in the `<contract>_consts` module of the bindings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContractConst {
+    /// The name of the constant (`FEE_BPS`).
+    pub name: String,
+    /// The Rust type of the constant, which is the output type of the view (`u16`).
+    #[serde(rename = "type")]
+    pub ty: String,
+    /// The value of the constant, a Rust const expression (`30`).
+    pub value: String,
+    /// The view returning the value of the constant. If not given, the view is
+    /// the name of the constant in snake case (`fee_bps`).
+    #[serde(default)]
+    pub view: Option<String>,
+}
+
+impl ContractConst {
+    /// Returns the name of the view returning the value of the constant.
+    pub fn view_name(&self) -> String {
+        self.view
+            .clone()
+            .unwrap_or_else(|| self.name.to_case(Case::Snake))
+    }
+
+    /// Returns the view of the ABI returning the value of the constant.
+    ///
+    /// The view must take no input, and return a value.
+    ///
+    /// # Arguments
+    ///
+    /// * `abi_tokens` - The tokens of the contract ABI.
+    pub fn find_view<'a>(&self, abi_tokens: &'a TokenizedAbi) -> Result<&'a Function, String> {
+        let view_name = self.view_name();
+
+        let function = abi_tokens
+            .functions
+            .iter()
+            .chain(abi_tokens.interfaces.values().flatten())
+            .filter_map(|f| f.to_function().ok())
+            .find(|f| f.name == view_name)
+            .ok_or_else(|| {
+                format!(
+                    "constant `{}`: no function `{}` in the ABI",
+                    self.name, view_name
+                )
+            })?;
+
+        if function.state_mutability != StateMutability::View {
+            return Err(format!(
+                "constant `{}`: `{}` is not a view",
+                self.name, view_name
+            ));
+        }
+
+        if !function.inputs.is_empty() {
+            return Err(format!(
+                "constant `{}`: the view `{}` takes inputs",
+                self.name, view_name
+            ));
+        }
+
+        if matches!(function.get_output_kind(), FunctionOutputKind::NoOutput) {
+            return Err(format!(
+                "constant `{}`: the view `{}` has no output",
+                self.name, view_name
+            ));
+        }
+
+        Ok(function)
+    }
+}
+
+/// Checks the declared constants against the ABI, and returns the errors.
+///
+/// # Arguments
+///
+/// * `consts` - The declared constants.
+/// * `abi_tokens` - The tokens of the contract ABI.
+pub fn check_consts(consts: &[ContractConst], abi_tokens: &TokenizedAbi) -> Vec<String> {
+    let mut errors = vec![];
+
+    for (i, c) in consts.iter().enumerate() {
+        if consts[..i].iter().any(|other| other.name == c.name) {
+            errors.push(format!("constant `{}` declared twice", c.name));
+        }
+
+        if syn::parse_str::<syn::Ident>(&c.name).is_err() {
+            errors.push(format!("constant `{}`: invalid name", c.name));
+        }
+
+        if syn::parse_str::<syn::Type>(&c.ty).is_err() {
+            errors.push(format!("constant `{}`: invalid type `{}`", c.name, c.ty));
+        }
+
+        if syn::parse_str::<syn::Expr>(&c.value).is_err() {
+            errors.push(format!(
+                "constant `{}`: invalid value `{}`",
+                c.name, c.value
+            ));
+        }
+
+        if let Err(e) = c.find_view(abi_tokens) {
+            errors.push(e);
+        }
+    }
+
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cainome_parser::AbiParser;
+    use std::collections::HashMap;
+
+    const ABI: &str = r#"[
+        {
+            "type": "function",
+            "name": "fee_bps",
+            "inputs": [],
+            "outputs": [{ "type": "core::integer::u16" }],
+            "state_mutability": "view"
+        },
+        {
+            "type": "function",
+            "name": "balance_of",
+            "inputs": [{ "name": "account", "type": "core::felt252" }],
+            "outputs": [{ "type": "core::integer::u256" }],
+            "state_mutability": "view"
+        },
+        {
+            "type": "function",
+            "name": "pause",
+            "inputs": [],
+            "outputs": [],
+            "state_mutability": "external"
+        }
+    ]"#;
+
+    fn decl(name: &str, view: Option<&str>) -> ContractConst {
+        ContractConst {
+            name: name.to_string(),
+            ty: "u16".to_string(),
+            value: "30".to_string(),
+            view: view.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_check_consts() {
+        let tokens = AbiParser::tokens_from_abi_string(ABI, &HashMap::new()).unwrap();
+
+        assert!(check_consts(&[decl("FEE_BPS", None)], &tokens).is_empty());
+        assert!(check_consts(&[decl("FEE", Some("fee_bps"))], &tokens).is_empty());
+
+        assert_eq!(
+            check_consts(
+                &[
+                    decl("FEE_BPS", None),
+                    decl("FEE_BPS", None),
+                    decl("MAX", None),
+                    decl("BALANCE", Some("balance_of")),
+                    decl("PAUSED", Some("pause")),
+                ],
+                &tokens
+            ),
+            vec![
+                "constant `FEE_BPS` declared twice",
+                "constant `MAX`: no function `max` in the ABI",
+                "constant `BALANCE`: the view `balance_of` takes inputs",
+                "constant `PAUSED`: `pause` is not a view",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let c: ContractConst =
+            serde_json::from_str(r#"{ "name": "FEE_BPS", "type": "u16", "value": "30" }"#).unwrap();
+
+        assert_eq!(c, decl("FEE_BPS", None));
+        assert_eq!(c.view_name(), "fee_bps");
+    }
+}
@@ -0,0 +1,401 @@
+//! Coverage of the ABI entries by the generated bindings.
+//!
+//! Not every entry of an ABI ends up in the bindings: the builtins are mapped to the types
+//! of `cainome`, and the generation modes trim the entrypoints. The report lists each entry of the tokenized ABI
+//! with whether it was generated, or why it was skipped, for the teams auditing that their
+//! bindings are complete.
+use cainome_parser::tokens::{Function, StateMutability, Token};
+use cainome_parser::TokenizedAbi;
+use serde::Serialize;
+
+use crate::expand::utils;
+use crate::{ExpandOptions, GenerationMode};
+
+/// The kind of an ABI entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntryKind {
+    Struct,
+    Enum,
+    /// A struct or an enum emitted as an event.
+    Event,
+    View,
+    External,
+    L1Handler,
+    Constructor,
+}
+
+/// The reason an ABI entry is not generated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SkipReason {
+    /// The type is a builtin, mapped to a type of `cainome`.
+    Builtin,
+    /// The entrypoint is not generated in the generation mode.
+    GenerationMode,
+}
+
+/// Whether an ABI entry is generated.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum CoverageStatus {
+    Generated {
+        /// The name of the generated type or method.
+        rust_name: String,
+    },
+    Skipped {
+        reason: SkipReason,
+        /// A human readable explanation of the reason.
+        detail: String,
+    },
+}
+
+/// An ABI entry with its coverage.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CoverageEntry {
+    pub kind: EntryKind,
+    /// The type path of the types, the name of the entrypoints.
+    pub name: String,
+    /// The interface declaring the entrypoint, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interface: Option<String>,
+    #[serde(flatten)]
+    pub status: CoverageStatus,
+}
+
+/// The coverage of the ABI entries of a contract.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CoverageReport {
+    pub contract: String,
+    /// `full`, `decode` or `encode`.
+    pub generation_mode: &'static str,
+    pub generated: usize,
+    pub skipped: usize,
+    /// The types sorted by type path, then the entrypoints sorted by name.
+    pub entries: Vec<CoverageEntry>,
+}
+
+impl CoverageReport {
+    /// Builds the coverage report of the given ABI, expanded with the given options.
+    ///
+    /// The ABI is expected to be free of name collisions
+    /// (see [`TokenizedAbi::name_collisions`]), which fail the expansion of the bindings.
+    ///
+    /// # Arguments
+    ///
+    /// * `contract_name` - Name of the contract.
+    /// * `abi_tokens` - Tokenized ABI.
+    /// * `options` - Options used to expand the bindings.
+    pub fn new(contract_name: &str, abi_tokens: &TokenizedAbi, options: &ExpandOptions) -> Self {
+        let mode = options.generation_mode;
+        let mut entries = vec![];
+
+        let mut types: Vec<(EntryKind, &Token)> = abi_tokens
+            .structs
+            .iter()
+            .map(|t| (EntryKind::Struct, t))
+            .chain(abi_tokens.enums.iter().map(|t| (EntryKind::Enum, t)))
+            .collect();
+        types.sort_by_key(|(_, t)| t.type_path());
+
+        for (kind, t) in types {
+            let Ok(composite) = t.to_composite() else {
+                continue;
+            };
+
+            let kind = if composite.is_event {
+                EntryKind::Event
+            } else {
+                kind
+            };
+
+            let rust_name = composite.type_name_or_alias();
+
+            let status = if composite.is_builtin() {
+                skipped(
+                    SkipReason::Builtin,
+                    "builtin type, mapped to a type of `cainome`".to_string(),
+                )
+            } else {
+                CoverageStatus::Generated { rust_name }
+            };
+
+            entries.push(CoverageEntry {
+                kind,
+                name: composite.type_path.clone(),
+                interface: None,
+                status,
+            });
+        }
+
+        let mut functions: Vec<(Option<&str>, &Function)> = abi_tokens
+            .functions
+            .iter()
+            .map(|f| (None, f))
+            .chain(abi_tokens.interfaces.iter().flat_map(|(i, fs)| {
+                let path = abi_tokens.interface_path(i);
+                fs.iter().map(move |f| (Some(path), f))
+            }))
+            .filter_map(|(i, f)| f.to_function().ok().map(|f| (i, f)))
+            .collect();
+        functions.sort_by(|(ia, a), (ib, b)| a.name.cmp(&b.name).then(ia.cmp(ib)));
+
+        for (interface, f) in functions {
+            let (kind, generated) = match f.state_mutability {
+                StateMutability::View => (EntryKind::View, mode.has_contract()),
+                StateMutability::External => (EntryKind::External, mode.has_calldata_encoding()),
+            };
+
+            let rust_name = if mode.has_contract() {
+                utils::str_to_ident(&f.name).to_string()
+            } else {
+                format!("{}_getcall", f.name)
+            };
+
+            entries.push(CoverageEntry {
+                kind,
+                name: f.name.clone(),
+                interface: interface.map(String::from),
+                status: entrypoint_status(generated, rust_name, mode, kind),
+            });
+        }
+
+        for f in &abi_tokens.l1_handlers {
+            let Ok(f) = f.to_function() else {
+                continue;
+            };
+
+            entries.push(CoverageEntry {
+                kind: EntryKind::L1Handler,
+                name: f.name.clone(),
+                interface: None,
+                status: entrypoint_status(
+                    mode.has_calldata_encoding(),
+                    format!("{}_message", f.name),
+                    mode,
+                    EntryKind::L1Handler,
+                ),
+            });
+        }
+
+        if let Some(Ok(f)) = abi_tokens.constructor.as_ref().map(|c| c.to_function()) {
+            entries.push(CoverageEntry {
+                kind: EntryKind::Constructor,
+                name: f.name.clone(),
+                interface: None,
+                status: entrypoint_status(
+                    mode.has_contract(),
+                    "deploy".to_string(),
+                    mode,
+                    EntryKind::Constructor,
+                ),
+            });
+        }
+
+        let generated = entries
+            .iter()
+            .filter(|e| matches!(e.status, CoverageStatus::Generated { .. }))
+            .count();
+
+        Self {
+            contract: contract_name.to_string(),
+            generation_mode: mode.name(),
+            generated,
+            skipped: entries.len() - generated,
+            entries,
+        }
+    }
+
+    /// Returns the entries which are not generated.
+    pub fn skipped_entries(&self) -> impl Iterator<Item = &CoverageEntry> {
+        self.entries
+            .iter()
+            .filter(|e| matches!(e.status, CoverageStatus::Skipped { .. }))
+    }
+}
+
+fn skipped(reason: SkipReason, detail: String) -> CoverageStatus {
+    CoverageStatus::Skipped { reason, detail }
+}
+
+fn entrypoint_status(
+    generated: bool,
+    rust_name: String,
+    mode: GenerationMode,
+    kind: EntryKind,
+) -> CoverageStatus {
+    if generated {
+        return CoverageStatus::Generated { rust_name };
+    }
+
+    let kind = match kind {
+        EntryKind::View => "views",
+        EntryKind::L1Handler => "L1 handlers",
+        EntryKind::Constructor => "constructors",
+        _ => "externals",
+    };
+
+    skipped(
+        SkipReason::GenerationMode,
+        format!("{} are not generated in the `{}` mode", kind, mode.name()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cainome_parser::AbiParser;
+    use std::collections::HashMap;
+
+    const ABI: &str = r#"[
+        {
+            "type": "struct",
+            "name": "pkg::a::Order",
+            "members": [{ "name": "amount", "type": "core::integer::u256" }]
+        },
+        {
+            "type": "struct",
+            "name": "pkg::b::Quote",
+            "members": [{ "name": "price", "type": "core::felt252" }]
+        },
+        {
+            "type": "struct",
+            "name": "core::integer::u256",
+            "members": [
+                { "name": "low", "type": "core::integer::u128" },
+                { "name": "high", "type": "core::integer::u128" }
+            ]
+        },
+        {
+            "type": "interface",
+            "name": "pkg::IBook",
+            "items": [
+                {
+                    "type": "function",
+                    "name": "place",
+                    "inputs": [
+                        { "name": "a", "type": "pkg::a::Order" },
+                        { "name": "b", "type": "pkg::b::Quote" }
+                    ],
+                    "outputs": [],
+                    "state_mutability": "external"
+                },
+                {
+                    "type": "function",
+                    "name": "best",
+                    "inputs": [],
+                    "outputs": [{ "type": "core::felt252" }],
+                    "state_mutability": "view"
+                }
+            ]
+        },
+        {
+            "type": "l1_handler",
+            "name": "on_deposit",
+            "inputs": [{ "name": "from_address", "type": "core::felt252" }],
+            "outputs": [],
+            "state_mutability": "external"
+        },
+        {
+            "type": "constructor",
+            "name": "constructor",
+            "inputs": [{ "name": "owner", "type": "core::felt252" }]
+        }
+    ]"#;
+
+    fn report(generation_mode: GenerationMode) -> CoverageReport {
+        let tokens = AbiParser::tokens_from_abi_string(ABI, &HashMap::new()).unwrap();
+        let options = ExpandOptions {
+            generation_mode,
+            ..Default::default()
+        };
+
+        CoverageReport::new("Book", &tokens, &options)
+    }
+
+    fn reasons(report: &CoverageReport) -> Vec<(&str, Option<SkipReason>)> {
+        report
+            .entries
+            .iter()
+            .map(|e| {
+                let reason = match &e.status {
+                    CoverageStatus::Generated { .. } => None,
+                    CoverageStatus::Skipped { reason, .. } => Some(*reason),
+                };
+                (e.name.as_str(), reason)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_coverage_full() {
+        let report = report(GenerationMode::Full);
+
+        assert_eq!(
+            reasons(&report),
+            vec![
+                ("core::integer::u256", Some(SkipReason::Builtin)),
+                ("pkg::a::Order", None),
+                ("pkg::b::Quote", None),
+                ("best", None),
+                ("place", None),
+                ("on_deposit", None),
+                ("constructor", None),
+            ]
+        );
+        assert_eq!((report.generated, report.skipped), (6, 1));
+        assert_eq!(
+            report.entries[1].status,
+            CoverageStatus::Generated {
+                rust_name: "Order".to_string()
+            }
+        );
+
+        assert_eq!(report.entries[3].interface.as_deref(), Some("pkg::IBook"));
+        assert_eq!(report.entries[3].kind, EntryKind::View);
+        assert_eq!(
+            report.entries[6].status,
+            CoverageStatus::Generated {
+                rust_name: "deploy".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_coverage_generation_modes() {
+        let encode = report(GenerationMode::Encode);
+        let skipped: Vec<&str> = encode
+            .skipped_entries()
+            .filter(|e| e.kind != EntryKind::Struct)
+            .map(|e| e.name.as_str())
+            .collect();
+        assert_eq!(skipped, vec!["best", "constructor"]);
+        assert_eq!(
+            encode.entries[4].status,
+            CoverageStatus::Generated {
+                rust_name: "place_getcall".to_string()
+            }
+        );
+
+        let decode = report(GenerationMode::Decode);
+        let CoverageStatus::Skipped { reason, detail } = &decode.entries[5].status else {
+            panic!("skipped entry expected");
+        };
+        assert_eq!(*reason, SkipReason::GenerationMode);
+        assert_eq!(detail, "L1 handlers are not generated in the `decode` mode");
+    }
+
+    #[test]
+    fn test_coverage_json() {
+        let json = serde_json::to_value(report(GenerationMode::Full)).unwrap();
+
+        assert_eq!(json["generation_mode"], "full");
+        assert_eq!(json["entries"][0]["kind"], "struct");
+        assert_eq!(json["entries"][0]["status"], "skipped");
+        assert_eq!(json["entries"][0]["reason"], "builtin");
+        assert_eq!(json["entries"][3]["status"], "generated");
+        assert_eq!(json["entries"][3]["rust_name"], "best");
+        assert_eq!(json["entries"][5]["kind"], "l1_handler");
+        assert!(json["entries"][5].get("interface").is_none());
+    }
+}
@@ -1,7 +1,11 @@
 /// Execution version of Starknet transactions.
 
 /// The version of transaction to be executed.
-#[derive(Debug, Clone, Copy, Default)]
+///
+/// The enum is non exhaustive: new versions (or execution modes) may be added
+/// without breaking the parsing of the existing ones.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum ExecutionVersion {
     /// Execute the transaction using the `execute_v1` method, where fees are only payable in WEI.
     #[default]
@@ -10,6 +14,37 @@ pub enum ExecutionVersion {
     V3,
 }
 
+impl ExecutionVersion {
+    /// Environment variable providing the execution version used when none is given.
+    pub const ENV_VAR: &'static str = "CAINOME_EXECUTION_VERSION";
+
+    /// All the supported execution versions.
+    pub const ALL: &'static [ExecutionVersion] = &[ExecutionVersion::V1, ExecutionVersion::V3];
+
+    /// Returns the name of the version, as parsed by `from_str` (case insensitive).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExecutionVersion::V1 => "v1",
+            ExecutionVersion::V3 => "v3",
+        }
+    }
+
+    /// Reads the execution version from the `CAINOME_EXECUTION_VERSION` environment variable,
+    /// `None` if it's not set or empty.
+    pub fn from_env() -> Result<Option<Self>, ParseExecutionVersionError> {
+        match std::env::var(Self::ENV_VAR) {
+            Ok(value) if !value.trim().is_empty() => value.parse().map(Some),
+            _ => Ok(None),
+        }
+    }
+}
+
+impl std::fmt::Display for ExecutionVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub struct ParseExecutionVersionError {
     invalid_value: String,
@@ -17,10 +52,16 @@ pub struct ParseExecutionVersionError {
 
 impl std::fmt::Display for ParseExecutionVersionError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let supported: Vec<String> = ExecutionVersion::ALL
+            .iter()
+            .map(|v| format!("'{}'", v.as_str()))
+            .collect();
+
         write!(
             f,
-            "Invalid execution version '{}'. Supported values are 'v1', 'V1', 'v3', or 'V3'.",
-            self.invalid_value
+            "Invalid execution version '{}'. Supported values are {} (case insensitive).",
+            self.invalid_value,
+            supported.join(", ")
         )
     }
 }
@@ -31,12 +72,52 @@ impl std::str::FromStr for ExecutionVersion {
     type Err = ParseExecutionVersionError;
 
     fn from_str(input: &str) -> Result<ExecutionVersion, Self::Err> {
-        match input {
-            "v1" | "V1" => Ok(ExecutionVersion::V1),
-            "v3" | "V3" => Ok(ExecutionVersion::V3),
-            _ => Err(ParseExecutionVersionError {
+        ExecutionVersion::ALL
+            .iter()
+            .find(|v| v.as_str().eq_ignore_ascii_case(input.trim()))
+            .copied()
+            .ok_or_else(|| ParseExecutionVersionError {
                 invalid_value: input.to_string(),
-            }),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(ExecutionVersion::from_str("v1"), Ok(ExecutionVersion::V1));
+        assert_eq!(ExecutionVersion::from_str("V3"), Ok(ExecutionVersion::V3));
+        assert_eq!(ExecutionVersion::from_str(" v3 "), Ok(ExecutionVersion::V3));
+
+        for version in ExecutionVersion::ALL {
+            assert_eq!(
+                ExecutionVersion::from_str(&version.to_string()),
+                Ok(*version)
+            );
         }
+
+        assert_eq!(
+            ExecutionVersion::from_str("v2").unwrap_err().to_string(),
+            "Invalid execution version 'v2'. Supported values are 'v1', 'v3' (case insensitive)."
+        );
+    }
+
+    #[test]
+    fn test_from_env() {
+        std::env::set_var(ExecutionVersion::ENV_VAR, "V3");
+        assert_eq!(ExecutionVersion::from_env(), Ok(Some(ExecutionVersion::V3)));
+
+        std::env::set_var(ExecutionVersion::ENV_VAR, "");
+        assert_eq!(ExecutionVersion::from_env(), Ok(None));
+
+        std::env::set_var(ExecutionVersion::ENV_VAR, "v0");
+        assert!(ExecutionVersion::from_env().is_err());
+
+        std::env::remove_var(ExecutionVersion::ENV_VAR);
+        assert_eq!(ExecutionVersion::from_env(), Ok(None));
     }
 }
@@ -0,0 +1,82 @@
+//! # Declaration of the contract
+//!
+//! With an embedded Sierra class, the contract gets:
+//!
+//! * `SIERRA_CLASS`, the JSON of the class included at compile time.
+//! * `CLASS_HASH` and `COMPILED_CLASS_HASH`, computed when generating the bindings.
+//! * `class_hash`, returning the hash of the class.
+//! * `declare`, declaring the class with the account.
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::Ident;
+
+use crate::expand::utils;
+use crate::{EmbeddedClass, ExecutionVersion};
+
+pub struct CairoArtifact;
+
+impl CairoArtifact {
+    /// Expands the impl block of the contract declaring its embedded class.
+    ///
+    /// # Arguments
+    ///
+    /// * `contract_name` - The name of the contract.
+    /// * `class` - The Sierra class embedded into the bindings.
+    /// * `execution_version` - The version of the declaration transaction.
+    pub fn expand(
+        contract_name: &Ident,
+        class: &EmbeddedClass,
+        execution_version: ExecutionVersion,
+    ) -> TokenStream2 {
+        let snrs_types = utils::snrs_types();
+        let ccs = utils::cainome_cairo_serde();
+
+        let sierra_path = &class.sierra_path;
+        let class_hash = format!("{:#x}", class.class_hash);
+        let compiled_class_hash = format!("{:#x}", class.compiled_class_hash);
+
+        let declare_call = match execution_version {
+            ExecutionVersion::V1 => quote!(account.declare_v2(__class, Self::COMPILED_CLASS_HASH)),
+            ExecutionVersion::V3 => quote!(account.declare_v3(__class, Self::COMPILED_CLASS_HASH)),
+        };
+
+        quote! {
+            #[doc = "Declaration of the Sierra class of the contract, embedded into the bindings."]
+            impl<A: starknet::accounts::ConnectedAccount + Sync> #contract_name<A> {
+                /// The JSON of the Sierra class of the contract.
+                pub const SIERRA_CLASS: &'static str = include_str!(#sierra_path);
+                /// The hash of the Sierra class of the contract.
+                pub const CLASS_HASH: #snrs_types::Felt =
+                    #snrs_types::Felt::from_hex_unchecked(#class_hash);
+                /// The hash of the compiled class (CASM) of the contract.
+                pub const COMPILED_CLASS_HASH: #snrs_types::Felt =
+                    #snrs_types::Felt::from_hex_unchecked(#compiled_class_hash);
+
+                /// Returns the hash of the Sierra class of the contract.
+                pub fn class_hash() -> #snrs_types::Felt {
+                    Self::CLASS_HASH
+                }
+
+                /// Declares the Sierra class of the contract with the `account`.
+                ///
+                /// The declaration fails if the class is already declared.
+                pub async fn declare(
+                    account: &A,
+                ) -> Result<#ccs::call::Declaration, #ccs::Error> {
+                    let __class = std::sync::Arc::new(
+                        #ccs::call::declare::flatten_sierra_class(Self::SIERRA_CLASS)?,
+                    );
+                    let __result = #declare_call
+                        .send()
+                        .await
+                        .map_err(#ccs::Error::from_account)?;
+
+                    Ok(#ccs::call::Declaration {
+                        class_hash: __result.class_hash,
+                        transaction_hash: __result.transaction_hash,
+                    })
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,142 @@
+//! # Deployment of the contract
+//!
+//! The constructor of a contract is not an entrypoint, its inputs are given
+//! to the deployer contract along with the class hash and the salt.
+//! For the contracts with a constructor, the contract gets:
+//!
+//! * `constructor_calldata`, serializing the inputs of the constructor.
+//! * `deploy`, deploying the contract through the Universal Deployer Contract (UDC).
+//! * `deploy_with_deployer`, the same through a deployer at an other address.
+use cainome_parser::tokens::Function;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::Ident;
+
+use crate::expand::utils;
+use crate::ExecutionVersion;
+
+/// Names of the parameters of the deployment functions, prefixed with `constructor_`
+/// when used by an input of the constructor.
+const DEPLOY_PARAMS: [&str; 4] = ["account", "deployer_address", "class_hash", "salt"];
+
+pub struct CairoConstructor;
+
+impl CairoConstructor {
+    /// Expands the impl block of the contract deploying it with its constructor.
+    ///
+    /// # Arguments
+    ///
+    /// * `contract_name` - The name of the contract.
+    /// * `constructor` - The constructor of the contract.
+    /// * `execution_version` - The version of the deployment transaction.
+    pub fn expand(
+        contract_name: &Ident,
+        constructor: &Function,
+        execution_version: ExecutionVersion,
+    ) -> TokenStream2 {
+        let snrs_types = utils::snrs_types();
+        let ccs = utils::cainome_cairo_serde();
+
+        let calldata = utils::str_to_ident("__calldata");
+
+        let mut inputs = vec![];
+        let mut names = vec![];
+        let mut serializations = vec![];
+
+        for (name, token) in &constructor.inputs {
+            let name = if DEPLOY_PARAMS.contains(&name.as_str()) {
+                utils::str_to_ident(&format!("constructor_{}", name))
+            } else {
+                utils::str_to_ident(name)
+            };
+            let ty = utils::input_type(token);
+
+            inputs.push(quote!(#name: #ty));
+            serializations.push(utils::input_serialization(&name, token, &calldata));
+            names.push(name);
+        }
+
+        let deploy_call = match execution_version {
+            ExecutionVersion::V1 => quote!(__factory.deploy_v1(__calldata, salt, false)),
+            ExecutionVersion::V3 => quote!(__factory.deploy_v3(__calldata, salt, false)),
+        };
+
+        let constructor_calldata = if serializations.is_empty() {
+            quote!(vec![])
+        } else {
+            quote! {
+                use #ccs::CairoSerde;
+
+                let mut __calldata = vec![];
+                #(#serializations)*
+                __calldata
+            }
+        };
+
+        quote! {
+            #[doc = "Deployment of the contract, with the inputs of its constructor."]
+            impl<A: starknet::accounts::ConnectedAccount + Sync> #contract_name<A> {
+                /// Returns the calldata of the constructor, its inputs serialized.
+                #[allow(clippy::ptr_arg)]
+                #[allow(clippy::too_many_arguments)]
+                pub fn constructor_calldata(#(#inputs),*) -> Vec<#snrs_types::Felt> {
+                    #constructor_calldata
+                }
+
+                /// Deploys the contract of `class_hash` with the `account`, through the
+                /// Universal Deployer Contract (UDC). The address of the contract only depends
+                /// on the class hash, the salt and the inputs of the constructor.
+                ///
+                /// The returned deployment has the address of the contract, deployed once
+                /// the transaction is accepted.
+                #[allow(clippy::ptr_arg)]
+                #[allow(clippy::too_many_arguments)]
+                pub async fn deploy(
+                    account: &A,
+                    class_hash: #snrs_types::Felt,
+                    salt: #snrs_types::Felt,
+                    #(#inputs),*
+                ) -> Result<#ccs::call::Deployment, #ccs::Error> {
+                    Self::deploy_with_deployer(
+                        account,
+                        #ccs::call::UDC_ADDRESS,
+                        class_hash,
+                        salt,
+                        #(#names),*
+                    )
+                    .await
+                }
+
+                /// Deploys the contract like [`Self::deploy`], through the deployer
+                /// at `deployer_address` implementing the interface of the UDC.
+                #[allow(clippy::ptr_arg)]
+                #[allow(clippy::too_many_arguments)]
+                pub async fn deploy_with_deployer(
+                    account: &A,
+                    deployer_address: #snrs_types::Felt,
+                    class_hash: #snrs_types::Felt,
+                    salt: #snrs_types::Felt,
+                    #(#inputs),*
+                ) -> Result<#ccs::call::Deployment, #ccs::Error> {
+                    let __calldata = Self::constructor_calldata(#(#names),*);
+                    let __factory = starknet::contract::ContractFactory::new_with_udc(
+                        class_hash,
+                        account,
+                        deployer_address,
+                    );
+                    let __deployment = #deploy_call;
+
+                    let __result = __deployment
+                        .send()
+                        .await
+                        .map_err(#ccs::Error::from_account)?;
+
+                    Ok(#ccs::call::Deployment {
+                        address: __deployment.deployed_address(),
+                        transaction_hash: __result.transaction_hash,
+                    })
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,222 @@
+//! # Constants of the contract
+//!
+//! The constants declared with the bindings are emitted in a `<contract>_consts` module.
+//! The reader gets a `check_consts()` method comparing them with the values returned
+//! by their views, and an ignored test of a `<contract>_consts_tests` module calls it on
+//! a deployed contract.
+use convert_case::{Case, Casing};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::Ident;
+
+use crate::consts::ContractConst;
+use crate::expand::utils;
+
+/// Environment variable providing the URL of the node used by the generated test.
+const RPC_URL_ENV_VAR: &str = "CAINOME_CONSTS_RPC_URL";
+
+pub struct CairoConsts;
+
+impl CairoConsts {
+    /// Returns the name of the module of the constants, `<contract>_consts`.
+    ///
+    /// # Arguments
+    ///
+    /// * `contract_name` - The name of the contract.
+    pub fn module_name(contract_name: &Ident) -> Ident {
+        utils::str_to_ident(&utils::contract_item_name(
+            contract_name,
+            "consts",
+            Case::Snake,
+        ))
+    }
+
+    /// Expands the `<contract>_consts` module.
+    ///
+    /// # Arguments
+    ///
+    /// * `contract_name` - The name of the contract.
+    /// * `consts` - The declared constants, already checked against the ABI.
+    pub fn expand_module(contract_name: &Ident, consts: &[ContractConst]) -> TokenStream2 {
+        let module = Self::module_name(contract_name);
+        let items: Vec<TokenStream2> = consts
+            .iter()
+            .map(|c| {
+                let name = utils::str_to_ident(&c.name);
+                let ty = utils::str_to_type(&c.ty);
+                let value = syn::parse_str::<syn::Expr>(&c.value).expect("valid const value");
+                let doc = format!("Returned by the `{}` view of the contract.", c.view_name());
+
+                quote! {
+                    #[doc = #doc]
+                    pub const #name: #ty = #value;
+                }
+            })
+            .collect();
+
+        quote! {
+            /// Constants of the contract, declared with the bindings.
+            ///
+            /// They are checked against the deployed contract with `check_consts()` of the reader.
+            pub mod #module {
+                #[allow(unused_imports)]
+                use super::*;
+
+                #(#items)*
+            }
+        }
+    }
+
+    /// Expands the `check_consts()` method of the reader.
+    ///
+    /// # Arguments
+    ///
+    /// * `contract_name` - The name of the contract.
+    /// * `consts` - The declared constants, already checked against the ABI.
+    pub fn expand_check_method(contract_name: &Ident, consts: &[ContractConst]) -> TokenStream2 {
+        let ccs = utils::cainome_cairo_serde();
+        let module = Self::module_name(contract_name);
+
+        let checks: Vec<TokenStream2> = consts
+            .iter()
+            .map(|c| {
+                let name = utils::str_to_ident(&c.name);
+                let name_str = &c.name;
+                let view = c.view_name();
+                let view_ident = utils::str_to_ident(&view);
+
+                quote! {
+                    let __value = self.#view_ident().call().await?;
+                    if __value != #module::#name {
+                        __mismatches.push(#ccs::call::ConstMismatch {
+                            name: #name_str,
+                            view: #view,
+                            declared: format!("{:?}", #module::#name),
+                            on_chain: format!("{:?}", __value),
+                        });
+                    }
+                }
+            })
+            .collect();
+
+        let doc = format!(
+            "Reads the constants of the [`{}`] module from the contract, and returns \
+             the ones whose declared value differs from the value returned by their view.",
+            module
+        );
+
+        quote! {
+            #[doc = #doc]
+            pub async fn check_consts(&self) -> #ccs::Result<Vec<#ccs::call::ConstMismatch>> {
+                let mut __mismatches = vec![];
+                #(#checks)*
+                Ok(__mismatches)
+            }
+        }
+    }
+
+    /// Expands the `<contract>_consts_tests` module, checking the constants against the
+    /// contract deployed at the address given by `<CONTRACT>_ADDRESS`, on the node at
+    /// `CAINOME_CONSTS_RPC_URL`.
+    ///
+    /// The test is ignored, to be run with `cargo test -- --ignored`, and fails when the
+    /// variables are not set. It needs `tokio` (with the `macros` and `rt` features) in the
+    /// dev-dependencies of the crate including the bindings.
+    ///
+    /// # Arguments
+    ///
+    /// * `contract_name` - The name of the contract.
+    /// * `reader` - The name of the reader of the contract.
+    pub fn expand_tests(contract_name: &Ident, reader: &Ident) -> TokenStream2 {
+        let snrs_types = utils::snrs_types();
+        let contract_snake = contract_name.unraw().to_string().to_case(Case::Snake);
+        let address_env_var = format!("{}_ADDRESS", contract_snake.to_uppercase());
+        let test_name = utils::str_to_ident(&format!("check_{}_consts", contract_snake));
+        let module = utils::str_to_ident(&utils::contract_item_name(
+            contract_name,
+            "consts_tests",
+            Case::Snake,
+        ));
+        let ignored = format!(
+            "needs a deployed {} at `{}`, on the node at `{}`",
+            contract_name.unraw(),
+            address_env_var,
+            RPC_URL_ENV_VAR
+        );
+        let url_missing = format!("`{}` is not set", RPC_URL_ENV_VAR);
+        let address_missing = format!("`{}` is not set", address_env_var);
+
+        quote! {
+            #[cfg(test)]
+            mod #module {
+                use super::*;
+
+                #[tokio::test]
+                #[ignore = #ignored]
+                async fn #test_name() {
+                    let url = std::env::var(#RPC_URL_ENV_VAR).expect(#url_missing);
+                    let address = std::env::var(#address_env_var).expect(#address_missing);
+
+                    let provider = starknet::providers::jsonrpc::JsonRpcClient::new(
+                        starknet::providers::jsonrpc::HttpTransport::new(
+                            starknet::providers::Url::parse(&url).expect(#RPC_URL_ENV_VAR),
+                        ),
+                    );
+                    let address = #snrs_types::Felt::from_hex(&address).expect(#address_env_var);
+
+                    let mismatches = #reader::new(address, provider)
+                        .check_consts()
+                        .await
+                        .expect("failed to read the constants");
+
+                    assert!(
+                        mismatches.is_empty(),
+                        "{}",
+                        mismatches
+                            .iter()
+                            .map(|m| m.to_string())
+                            .collect::<Vec<_>>()
+                            .join("\n")
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn consts() -> Vec<ContractConst> {
+        vec![ContractConst {
+            name: "FEE_BPS".to_string(),
+            ty: "u16".to_string(),
+            value: "30".to_string(),
+            view: None,
+        }]
+    }
+
+    #[test]
+    fn test_expand() {
+        let contract_name = utils::str_to_ident("MyVault");
+
+        let module = CairoConsts::expand_module(&contract_name, &consts()).to_string();
+        assert!(module.contains("pub mod my_vault_consts"));
+        assert!(module.contains("pub const FEE_BPS : u16 = 30 ;"));
+
+        let method = CairoConsts::expand_check_method(&contract_name, &consts()).to_string();
+        assert!(method.contains("self . fee_bps () . call () . await ?"));
+        assert!(method.contains("__value != my_vault_consts :: FEE_BPS"));
+
+        let tests =
+            CairoConsts::expand_tests(&contract_name, &utils::str_to_ident("MyVaultReader"))
+                .to_string();
+        assert!(tests.contains("mod my_vault_consts_tests"));
+        assert!(tests.contains("# [ignore = "));
+        assert!(tests.contains("async fn check_my_vault_consts ()"));
+        assert!(tests.contains("\"MY_VAULT_ADDRESS\""));
+        assert!(!tests.contains("eprintln"));
+    }
+}
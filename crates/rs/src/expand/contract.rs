@@ -1,15 +1,38 @@
+use cainome_parser::tokens::{Composite, Function};
+use convert_case::{Case, Casing};
 use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
 use syn::Ident;
 
 use super::utils;
+use crate::ExecutionVersion;
 
 pub struct CairoContract;
 
 impl CairoContract {
-    pub fn expand(contract_name: Ident, contract_derives: &[String]) -> TokenStream2 {
+    /// Expands the contract and the reader structs, with their constructors and accessors.
+    ///
+    /// # Arguments
+    ///
+    /// * `contract_name` - Name of the contract.
+    /// * `contract_derives` - The derives of the structs.
+    /// * `summary` - The summary of the ABI, documenting the contract struct.
+    pub fn expand(
+        contract_name: Ident,
+        contract_derives: &[String],
+        summary: &str,
+    ) -> TokenStream2 {
         let reader = utils::str_to_ident(format!("{}Reader", contract_name).as_str());
 
+        let contract_doc = format!(
+            "Bindings of the `{}` contract, calling its views and invoking its externals with an account.",
+            contract_name
+        );
+        let reader_doc = format!(
+            "Reader of the `{}` contract, calling its views with a provider.",
+            contract_name
+        );
+
         let snrs_types = utils::snrs_types();
         let snrs_accounts = utils::snrs_accounts();
         let snrs_providers = utils::snrs_providers();
@@ -22,43 +45,62 @@ impl CairoContract {
 
         let q = quote! {
 
+            #[doc = #contract_doc]
+            ///
+            #[doc = #summary]
             #[derive(#(#internal_derives,)*)]
             pub struct #contract_name<A: #snrs_accounts::ConnectedAccount + Sync> {
+                /// Address of the contract.
                 pub address: #snrs_types::Felt,
+                /// Account sending the transactions.
                 pub account: A,
+                /// Block at which the views are called.
                 pub block_id: #snrs_types::BlockId,
             }
 
+            /// Construction and configuration.
             impl<A: #snrs_accounts::ConnectedAccount + Sync> #contract_name<A> {
+                /// Initializes the contract at the given address, calling the views at the pending block.
                 pub fn new(address: #snrs_types::Felt, account: A) -> Self {
                     Self { address, account, block_id: #snrs_types::BlockId::Tag(#snrs_types::BlockTag::Pending) }
                 }
 
+                /// Sets the address of the contract.
                 pub fn set_contract_address(&mut self, address: #snrs_types::Felt) {
                     self.address = address;
                 }
 
+                /// Returns the provider of the account.
                 pub fn provider(&self) -> &A::Provider {
                     self.account.provider()
                 }
 
+                /// Sets the block at which the views are called.
                 pub fn set_block(&mut self, block_id: #snrs_types::BlockId) {
                     self.block_id = block_id;
                 }
 
+                /// Returns the contract calling the views at the given block.
                 pub fn with_block(self, block_id: #snrs_types::BlockId) -> Self {
                     Self { block_id, ..self }
                 }
             }
 
+            #[doc = #reader_doc]
             #[derive(#(#internal_derives,)*)]
             pub struct #reader<P: #snrs_providers::Provider + Sync> {
+                /// Address of the contract.
                 pub address: #snrs_types::Felt,
+                /// Provider calling the views.
                 pub provider: P,
+                /// Block at which the views are called.
                 pub block_id: #snrs_types::BlockId,
             }
 
+            /// Construction and configuration.
             impl<P: #snrs_providers::Provider + Sync> #reader<P> {
+                /// Initializes the reader of the contract at the given address, calling the views
+                /// at the pending block.
                 pub fn new(
                     address: #snrs_types::Felt,
                     provider: P,
@@ -66,24 +108,446 @@ impl CairoContract {
                     Self { address, provider, block_id: #snrs_types::BlockId::Tag(#snrs_types::BlockTag::Pending) }
                 }
 
+                /// Sets the address of the contract.
                 pub fn set_contract_address(&mut self, address: #snrs_types::Felt) {
                     self.address = address;
                 }
 
+                /// Returns the provider of the reader.
                 pub fn provider(&self) -> &P {
                     &self.provider
                 }
 
+                /// Sets the block at which the views are called.
                 pub fn set_block(&mut self, block_id: #snrs_types::BlockId) {
                     self.block_id = block_id;
                 }
 
+                /// Returns the reader calling the views at the given block.
                 pub fn with_block(self, block_id: #snrs_types::BlockId) -> Self {
                     Self { block_id, ..self }
                 }
             }
+
+            impl<P: #snrs_providers::Provider + Send + Sync> #reader<std::sync::Arc<P>> {
+                /// Initializes a reader sharing the provider, which can be moved into
+                /// spawned tasks. Use `into_owned()` on the returned calls to not borrow the reader.
+                pub fn new_shared(
+                    address: #snrs_types::Felt,
+                    provider: std::sync::Arc<P>,
+                ) -> Self {
+                    Self::new(address, provider)
+                }
+            }
         };
 
         q
     }
+
+    /// Expands the methods to call or invoke any entrypoint by its selector, including
+    /// entrypoints missing from the ABI (like the ones of an upgraded contract).
+    ///
+    /// # Arguments
+    ///
+    /// * `is_for_reader` - Whether the methods are expanded for the reader (views only).
+    /// * `execution_version` - The version of transaction used by `invoke_raw`.
+    /// * `paymaster` - Whether `invoke_raw` can be sponsored by a paymaster.
+    pub fn expand_raw_methods(
+        is_for_reader: bool,
+        execution_version: ExecutionVersion,
+        paymaster: bool,
+    ) -> TokenStream2 {
+        let snrs_types = utils::snrs_types();
+        let ccs = utils::cainome_cairo_serde();
+
+        let call_raw = quote! {
+            /// Calls the entrypoint with the given selector and calldata at the current block,
+            /// and returns the raw output.
+            pub async fn call_raw(
+                &self,
+                selector: #snrs_types::Felt,
+                calldata: Vec<#snrs_types::Felt>,
+            ) -> #ccs::Result<Vec<#snrs_types::Felt>> {
+                let __call = #snrs_types::FunctionCall {
+                    contract_address: self.address,
+                    entry_point_selector: selector,
+                    calldata,
+                };
+
+                #ccs::call::FCall::<_, ()>::new(__call, self.provider())
+                    .block_id(self.block_id)
+                    .raw_call()
+                    .await
+            }
+        };
+
+        if is_for_reader {
+            return call_raw;
+        }
+
+        let exec_type = execution_version.get_invoke_type(paymaster);
+        let exec_call = execution_version.get_invoke_call(paymaster);
+        let batch_call = execution_version.get_batch_invoke_call(paymaster);
+
+        quote! {
+            #call_raw
+
+            /// Invokes the entrypoint with the given selector and calldata.
+            pub fn invoke_raw(
+                &self,
+                selector: #snrs_types::Felt,
+                calldata: Vec<#snrs_types::Felt>,
+            ) -> #exec_type {
+                let __call = #snrs_types::Call {
+                    to: self.address,
+                    selector,
+                    calldata,
+                };
+
+                #exec_call
+            }
+
+            /// Invokes the given calls in one transaction, like the ones returned by
+            /// the `<function>_getcall()` methods aggregated in a `MultiCall`.
+            pub fn execute_batch(
+                &self,
+                calls: impl Into<Vec<#snrs_types::Call>>,
+            ) -> #exec_type {
+                let __calls: Vec<#snrs_types::Call> = calls.into();
+
+                #batch_call
+            }
+        }
+    }
+
+    /// Expands the methods decoding the events of the contract, if the contract has
+    /// a root event. The `<ContractName>Event` alias (and the alias of its visitor) is
+    /// also expanded, unless it clashes with an existing type.
+    ///
+    /// Returns the alias declaration and the methods to be added to the contract
+    /// and the reader.
+    ///
+    /// # Arguments
+    ///
+    /// * `contract_name` - Name of the contract.
+    /// * `root_event` - The event enum of the contract, if any.
+    /// * `type_names` - The names of all the generated types.
+    pub fn expand_events_methods(
+        contract_name: &Ident,
+        root_event: Option<&Composite>,
+        type_names: &[String],
+    ) -> (TokenStream2, TokenStream2) {
+        let Some(root_event) = root_event else {
+            return (quote!(), quote!());
+        };
+
+        let snrs_types = utils::snrs_types();
+        let ccs = utils::cainome_cairo_serde();
+
+        let event_name = root_event.type_name_or_alias();
+        let event = utils::str_to_ident(&event_name);
+
+        let alias = match Self::event_alias_name(contract_name, Some(root_event), type_names) {
+            Some(alias_name) => {
+                let alias = utils::str_to_ident(&alias_name);
+                let visitor = utils::str_to_ident(&format!("{}Visitor", event_name));
+                let visitor_alias = utils::str_to_ident(&format!("{}Visitor", alias_name));
+
+                quote! {
+                    pub type #alias = #event;
+                    pub use self::#visitor as #visitor_alias;
+                }
+            }
+            None => quote!(),
+        };
+
+        let methods = quote! {
+            /// Returns the events emitted by this contract (at the configured address)
+            /// in the transaction of the given receipt.
+            /// The events that can't be decoded are ignored.
+            pub fn events_in_receipt(
+                &self,
+                receipt: &#snrs_types::TransactionReceipt,
+            ) -> Vec<#event> {
+                #ccs::helpers::receipt_events(receipt)
+                    .iter()
+                    .filter(|e| e.from_address == self.address)
+                    .filter_map(|e| #event::try_from(e).ok())
+                    .collect()
+            }
+
+            /// Sends the execution of externals of this contract, waits for the receipt of
+            /// the transaction, and returns it with the fee paid and the events emitted by
+            /// this contract (at the configured address) decoded.
+            pub async fn send_and_decode(
+                &self,
+                execution: impl #ccs::call::SendInvoke,
+            ) -> Result<#ccs::call::InvokeResult<#event>, #ccs::Error> {
+                let transaction_hash = execution.send_invoke().await?;
+                let receipt = #ccs::call::invoke::wait_for_receipt(
+                    self.provider(),
+                    transaction_hash,
+                    #ccs::call::invoke::DEFAULT_POLLING_INTERVAL,
+                    #ccs::call::invoke::DEFAULT_POLLING_ATTEMPTS,
+                )
+                .await?;
+                let events = self.events_in_receipt(&receipt.receipt);
+
+                Ok(#ccs::call::InvokeResult {
+                    transaction_hash,
+                    receipt,
+                    events,
+                })
+            }
+
+            /// Pages through the events emitted by this contract (at the configured address)
+            /// in the block range, only the ones matching the keys if given, and yields them decoded.
+            /// The events of the pending block are skipped.
+            pub fn events(
+                &self,
+                from_block: #snrs_types::BlockId,
+                to_block: #snrs_types::BlockId,
+                keys: Option<Vec<Vec<#snrs_types::Felt>>>,
+            ) -> impl #ccs::runtime::Stream<
+                Item = Result<#ccs::runtime::DecodedEvent<#event>, #ccs::runtime::BackfillError>,
+            > + '_ {
+                let __filter = #snrs_types::EventFilter {
+                    from_block: Some(from_block),
+                    to_block: Some(to_block),
+                    address: Some(self.address),
+                    keys,
+                };
+
+                #ccs::runtime::stream_events(self.provider(), __filter, #ccs::runtime::DEFAULT_CHUNK_SIZE)
+            }
+        };
+
+        (alias, methods)
+    }
+
+    /// Returns the name of the `<ContractName>Event` alias of the root event, if expanded.
+    ///
+    /// # Arguments
+    ///
+    /// * `contract_name` - Name of the contract.
+    /// * `root_event` - The event enum of the contract, if any.
+    /// * `type_names` - The names of all the generated types.
+    pub fn event_alias_name(
+        contract_name: &Ident,
+        root_event: Option<&Composite>,
+        type_names: &[String],
+    ) -> Option<String> {
+        let event_name = root_event?.type_name_or_alias();
+        let alias_name = format!("{}Event", contract_name);
+
+        if alias_name == event_name || type_names.contains(&alias_name) {
+            None
+        } else {
+            Some(alias_name)
+        }
+    }
+
+    /// Returns the name of the prelude module, `<contract>_prelude`.
+    ///
+    /// # Arguments
+    ///
+    /// * `contract_name` - Name of the contract.
+    pub fn prelude_name(contract_name: &Ident) -> Ident {
+        utils::str_to_ident(&utils::contract_item_name(
+            contract_name,
+            "prelude",
+            Case::Snake,
+        ))
+    }
+
+    /// Expands the `<contract>_prelude` module, re-exporting flatly the given items of the bindings.
+    ///
+    /// # Arguments
+    ///
+    /// * `contract_name` - Name of the contract.
+    /// * `items` - The names of the items to re-export (contract, reader, types, event visitors, ...).
+    /// * `blocking` - Whether the blocking facades are re-exported.
+    pub fn expand_prelude(contract_name: &Ident, items: &[String], blocking: bool) -> TokenStream2 {
+        let items = items.iter().map(|i| utils::str_to_ident(i));
+
+        let blocking = if blocking {
+            let contract_blocking = utils::str_to_ident(&format!("{}Blocking", contract_name));
+            let reader_blocking = utils::str_to_ident(&format!("{}ReaderBlocking", contract_name));
+
+            quote! {
+                #[cfg(feature = "blocking")]
+                pub use super::{#contract_blocking, #reader_blocking};
+            }
+        } else {
+            quote!()
+        };
+
+        let prelude = Self::prelude_name(contract_name);
+
+        quote! {
+            /// Flat re-exports of the contract, its types and its events.
+            pub mod #prelude {
+                pub use super::{#(#items),*};
+                #blocking
+            }
+        }
+    }
+
+    /// Expands the `SELECTOR_<NAME>` constants of the functions, and the `entrypoints()`
+    /// function listing their names and selectors.
+    ///
+    /// # Arguments
+    ///
+    /// * `functions` - The functions of the ABI, sorted by name.
+    pub fn expand_entrypoints(functions: &[&Function]) -> TokenStream2 {
+        let snrs_types = utils::snrs_types();
+
+        let mut consts = vec![];
+        let mut entries = vec![];
+
+        for f in functions {
+            let const_name =
+                utils::str_to_ident(&format!("SELECTOR_{}", f.name.to_case(Case::UpperSnake)));
+            let selector = utils::function_selector(&f.name);
+            let name = &f.name;
+            let doc = format!("Selector of the `{}` entrypoint.", name);
+
+            consts.push(quote! {
+                #[doc = #doc]
+                pub const #const_name: #snrs_types::Felt = #selector;
+            });
+            entries.push(quote!((#name, #selector)));
+        }
+
+        quote! {
+            #(#consts)*
+
+            /// Returns the names and the selectors of the entrypoints of the ABI,
+            /// sorted by name.
+            pub fn entrypoints() -> &'static [(&'static str, #snrs_types::Felt)] {
+                const ENTRYPOINTS: &[(&str, #snrs_types::Felt)] = &[#(#entries),*];
+                ENTRYPOINTS
+            }
+        }
+    }
+
+    /// Returns the name of the struct encoding the calls to the externals, generated
+    /// instead of the contract in the `encode` generation mode.
+    pub fn calls_struct_name(contract_name: &Ident) -> Ident {
+        utils::str_to_ident(&format!("{}Calls", contract_name))
+    }
+
+    /// Expands the struct encoding the calls to the externals of the contract at its address,
+    /// without account nor provider.
+    ///
+    /// # Arguments
+    ///
+    /// * `contract_name` - Name of the contract.
+    /// * `contract_derives` - The derives of the struct.
+    /// * `entrypoints` - The selectors of the entrypoints, see [`Self::expand_entrypoints`].
+    /// * `getcalls` - The `<function>_getcall` methods of the externals.
+    pub fn expand_calls(
+        contract_name: &Ident,
+        contract_derives: &[String],
+        entrypoints: &TokenStream2,
+        getcalls: &[TokenStream2],
+    ) -> TokenStream2 {
+        let calls = Self::calls_struct_name(contract_name);
+        let doc = format!(
+            "Encoding of the calls to the externals of the `{}` contract.",
+            contract_name
+        );
+
+        let snrs_types = utils::snrs_types();
+        let derives = contract_derives.iter().map(|d| utils::str_to_type(d));
+
+        quote! {
+            #[doc = #doc]
+            #[derive(#(#derives,)*)]
+            pub struct #calls {
+                /// Address of the contract.
+                pub address: #snrs_types::Felt,
+            }
+
+            impl #calls {
+                /// Initializes the calls to the contract at the given address.
+                pub fn new(address: #snrs_types::Felt) -> Self {
+                    Self { address }
+                }
+
+                #entrypoints
+
+                #(#getcalls)*
+            }
+        }
+    }
+
+    /// Expands the blocking facades of the contract and the reader, only available
+    /// with the `blocking` feature of the crate including the bindings.
+    ///
+    /// # Arguments
+    ///
+    /// * `contract_name` - Name of the contract.
+    /// * `views` - The blocking views of the contract.
+    /// * `reader_views` - The blocking views of the reader.
+    /// * `externals` - The blocking externals of the contract.
+    pub fn expand_blocking(
+        contract_name: &Ident,
+        views: &[TokenStream2],
+        reader_views: &[TokenStream2],
+        externals: &[TokenStream2],
+    ) -> TokenStream2 {
+        let reader = utils::str_to_ident(&format!("{}Reader", contract_name));
+        let contract_blocking = utils::str_to_ident(&format!("{}Blocking", contract_name));
+        let reader_blocking = utils::str_to_ident(&format!("{}ReaderBlocking", contract_name));
+
+        let snrs_accounts = utils::snrs_accounts();
+        let snrs_providers = utils::snrs_providers();
+        let ccs = utils::cainome_cairo_serde();
+
+        quote! {
+            /// Blocking facade of the contract, where each call
+            /// is run to completion by the executor.
+            #[cfg(feature = "blocking")]
+            pub struct #contract_blocking<A: #snrs_accounts::ConnectedAccount + Sync, E: #ccs::blocking::BlockOn> {
+                pub contract: #contract_name<A>,
+                pub executor: E,
+            }
+
+            #[cfg(feature = "blocking")]
+            impl<A: #snrs_accounts::ConnectedAccount + Sync> #contract_name<A> {
+                /// Wraps the contract into a blocking facade, using the given executor.
+                pub fn into_blocking<E: #ccs::blocking::BlockOn>(self, executor: E) -> #contract_blocking<A, E> {
+                    #contract_blocking { contract: self, executor }
+                }
+            }
+
+            #[cfg(feature = "blocking")]
+            impl<A: #snrs_accounts::ConnectedAccount + Sync, E: #ccs::blocking::BlockOn> #contract_blocking<A, E> {
+                #(#views)*
+                #(#externals)*
+            }
+
+            /// Blocking facade of the contract reader, where each call
+            /// is run to completion by the executor.
+            #[cfg(feature = "blocking")]
+            pub struct #reader_blocking<P: #snrs_providers::Provider + Sync, E: #ccs::blocking::BlockOn> {
+                pub reader: #reader<P>,
+                pub executor: E,
+            }
+
+            #[cfg(feature = "blocking")]
+            impl<P: #snrs_providers::Provider + Sync> #reader<P> {
+                /// Wraps the reader into a blocking facade, using the given executor.
+                pub fn into_blocking<E: #ccs::blocking::BlockOn>(self, executor: E) -> #reader_blocking<P, E> {
+                    #reader_blocking { reader: self, executor }
+                }
+            }
+
+            #[cfg(feature = "blocking")]
+            impl<P: #snrs_providers::Provider + Sync, E: #ccs::blocking::BlockOn> #reader_blocking<P, E> {
+                #(#reader_views)*
+            }
+        }
+    }
 }
@@ -0,0 +1,188 @@
+//! # Conversions between identical types
+//!
+//! When several contracts define the same type (same path and same members),
+//! separate Rust types are generated in the bindings of each contract.
+//! To exchange them, `From` implementations are generated from the types
+//! of the other bindings, going through their Cairo serialization which is
+//! identical by construction.
+use cainome_parser::tokens::{Composite, Token};
+use cainome_parser::TokenizedAbi;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use std::collections::{HashMap, HashSet};
+
+use crate::expand::utils;
+
+pub struct CairoConversion;
+
+impl CairoConversion {
+    /// Expands the `From` implementations converting the types of the other bindings
+    /// into the identical types of the bindings.
+    ///
+    /// # Arguments
+    ///
+    /// * `abi_tokens` - The tokens of the ABI the bindings are generated for.
+    /// * `other_abi_tokens` - The tokens of the ABI of the other bindings.
+    /// * `other_module` - The path of the module of the other bindings.
+    pub fn expand(
+        abi_tokens: &TokenizedAbi,
+        other_abi_tokens: &TokenizedAbi,
+        other_module: &str,
+    ) -> TokenStream2 {
+        let ccs = utils::cainome_cairo_serde();
+        let other_module = utils::str_to_type(other_module);
+
+        let impls = identical_composites(abi_tokens, other_abi_tokens)
+            .into_iter()
+            .map(|composite| {
+                let type_name = utils::str_to_ident(&composite.type_name_or_alias());
+
+                quote! {
+                    impl From<#other_module::#type_name> for #type_name {
+                        fn from(value: #other_module::#type_name) -> Self {
+                            use #ccs::CairoSerde;
+
+                            // Both types are identical, hence have the same serialization.
+                            let felts = <#other_module::#type_name>::cairo_serialize(&value);
+                            Self::cairo_deserialize(&felts, 0)
+                                .expect("identical types have the same serialization")
+                        }
+                    }
+                }
+            });
+
+        quote!(#(#impls)*)
+    }
+}
+
+/// Returns the structs and enums of `abi_tokens` that are defined identically in
+/// `other_abi_tokens`, including the types of their members.
+///
+/// The generic types are ignored, as they are not converted as a whole.
+fn identical_composites<'a>(
+    abi_tokens: &'a TokenizedAbi,
+    other_abi_tokens: &TokenizedAbi,
+) -> Vec<&'a Composite> {
+    let definitions = composite_definitions(abi_tokens);
+    let other_definitions = composite_definitions(other_abi_tokens);
+
+    let mut composites: Vec<&Composite> = definitions
+        .values()
+        .filter(|c| !c.is_builtin() && !c.is_generic())
+        .filter(|c| {
+            is_identical(
+                &c.type_path_no_generic(),
+                &definitions,
+                &other_definitions,
+                &mut HashSet::new(),
+            )
+        })
+        .copied()
+        .collect();
+
+    composites.sort_by_key(|c| c.type_name_or_alias());
+    composites
+}
+
+/// Returns the structs and enums of the ABI, by type path.
+pub(crate) fn composite_definitions(abi_tokens: &TokenizedAbi) -> HashMap<String, &Composite> {
+    abi_tokens
+        .structs
+        .iter()
+        .chain(abi_tokens.enums.iter())
+        .filter_map(|t| t.to_composite().ok())
+        .map(|c| (c.type_path_no_generic(), c))
+        .collect()
+}
+
+/// Returns whether the type is defined identically in both ABIs, including the
+/// types of its members.
+///
+/// `visited` contains the types being checked, to stop on recursive types.
+fn is_identical(
+    type_path: &str,
+    definitions: &HashMap<String, &Composite>,
+    other_definitions: &HashMap<String, &Composite>,
+    visited: &mut HashSet<String>,
+) -> bool {
+    if !visited.insert(type_path.to_string()) {
+        return true;
+    }
+
+    match (definitions.get(type_path), other_definitions.get(type_path)) {
+        (Some(c), Some(other)) => {
+            c == other
+                && c.inners.iter().all(|inner| {
+                    referenced_types(&inner.token)
+                        .iter()
+                        .all(|path| is_identical(path, definitions, other_definitions, visited))
+                })
+        }
+        // The builtins are not defined in the ABI.
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+/// Returns the path of the composite types referenced by the token.
+pub(crate) fn referenced_types(token: &Token) -> Vec<String> {
+    match token {
+        Token::Array(a) => referenced_types(&a.inner),
+        Token::Tuple(t) => t.inners.iter().flat_map(referenced_types).collect(),
+        Token::Composite(c) => {
+            let mut types = vec![c.type_path_no_generic()];
+            types.extend(c.generic_args.iter().flat_map(|(_, g)| referenced_types(g)));
+            types
+        }
+        Token::CoreBasic(_) | Token::GenericArg(_) | Token::Function(_) => vec![],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cainome_parser::AbiParser;
+
+    fn tokens(abi: &str) -> TokenizedAbi {
+        AbiParser::tokens_from_abi_string(abi, &HashMap::new()).unwrap()
+    }
+
+    const ORDER: &str = r#"
+        {
+            "type": "struct",
+            "name": "pkg::Order",
+            "members": [
+                { "name": "price", "type": "core::integer::u128" },
+                { "name": "side", "type": "pkg::Side" }
+            ]
+        }
+    "#;
+
+    fn side(variants: &str) -> String {
+        format!(
+            r#"{{ "type": "enum", "name": "pkg::Side", "variants": [{}] }}"#,
+            variants
+        )
+    }
+
+    #[test]
+    fn test_identical_composites() {
+        let buy_sell = side(r#"{ "name": "Buy", "type": "()" }, { "name": "Sell", "type": "()" }"#);
+        let buy_only = side(r#"{ "name": "Buy", "type": "()" }"#);
+
+        let a = tokens(&format!("[{}, {}]", ORDER, buy_sell));
+        let b = tokens(&format!("[{}, {}]", ORDER, buy_sell));
+        let c = tokens(&format!("[{}, {}]", ORDER, buy_only));
+
+        let names = |abi: &TokenizedAbi, other: &TokenizedAbi| {
+            identical_composites(abi, other)
+                .iter()
+                .map(|c| c.type_name_or_alias())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(names(&a, &b), vec!["Order", "Side"]);
+        // `Order` has the same members, but one of them is a different type.
+        assert!(names(&a, &c).is_empty());
+    }
+}
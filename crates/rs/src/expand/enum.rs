@@ -1,15 +1,22 @@
-use cainome_parser::tokens::{Composite, Token};
+use cainome_parser::tokens::Composite;
 use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
 use syn::Ident;
 
 use crate::expand::types::CairoToRust;
 use crate::expand::utils;
+use crate::{BytesEncoding, SerdeRadix};
 
 pub struct CairoEnum;
 
 impl CairoEnum {
-    pub fn expand_decl(composite: &Composite, derives: &[String]) -> TokenStream2 {
+    pub fn expand_decl(
+        composite: &Composite,
+        derives: &[String],
+        arbitrary: bool,
+        serde_radix: SerdeRadix,
+        serde_bytes: BytesEncoding,
+    ) -> TokenStream2 {
         if composite.is_builtin() {
             return quote!();
         }
@@ -22,12 +29,18 @@ impl CairoEnum {
             let name = utils::str_to_ident(&inner.name);
             let ty = utils::str_to_type(&inner.token.to_rust_type());
 
-            let serde = utils::serde_hex_derive(&inner.token.to_rust_type());
+            let serde = utils::serde_hex_derive(&inner.token.to_rust_type(), serde_radix);
+            let bytes = utils::serde_bytes_derive(&inner.token.to_rust_type(), serde_bytes);
+            let schema = utils::schema_derive(
+                &inner.token.to_rust_type(),
+                derives,
+                Some((serde_radix, serde_bytes)),
+            );
 
             if inner.token.type_name() == "()" {
                 variants.push(quote!(#serde #name));
             } else {
-                variants.push(quote!(#serde #name(#ty)));
+                variants.push(quote!(#serde #bytes #schema #name(#ty)));
             }
         }
 
@@ -37,6 +50,17 @@ impl CairoEnum {
             internal_derives.push(utils::str_to_type(d));
         }
 
+        let arbitrary_derive = if arbitrary {
+            utils::arbitrary_derive()
+        } else {
+            quote!()
+        };
+
+        let doc = format!(
+            "Binding of the Cairo enum `{}`.",
+            composite.type_path_no_generic()
+        );
+
         if composite.is_generic() {
             let gen_args: Vec<Ident> = composite
                 .generic_args
@@ -51,14 +75,18 @@ impl CairoEnum {
             // Those phantom fields are ignored by serde.
 
             quote! {
+                #[doc = #doc]
                 #[derive(#(#internal_derives,)*)]
+                #arbitrary_derive
                 pub enum #enum_name<#(#gen_args),*> {
                     #(#variants),*
                 }
             }
         } else {
             quote! {
+                #[doc = #doc]
                 #[derive(#(#internal_derives,)*)]
+                #arbitrary_derive
                 pub enum #enum_name {
                     #(#variants),*
                 }
@@ -74,64 +102,24 @@ impl CairoEnum {
         let name_str = &composite.type_name_or_alias();
         let enum_name = utils::str_to_ident(name_str);
 
-        let mut serialized_sizes: Vec<TokenStream2> = vec![];
-        let mut serializations: Vec<TokenStream2> = vec![];
-        let mut deserializations: Vec<TokenStream2> = vec![];
+        let ccs = utils::cainome_cairo_serde();
+
+        let mut units: Vec<TokenStream2> = vec![];
+        let mut values: Vec<TokenStream2> = vec![];
 
         for inner in &composite.inners {
             let variant_name = utils::str_to_ident(&inner.name);
-            let ty = utils::str_to_type(&inner.token.to_rust_type_path());
             let variant_index = inner.index;
 
-            // Tuples type used as rust type path must be surrounded
-            // by angle brackets.
-            let ty_punctuated = match inner.token {
-                Token::Tuple(_) => quote!(<#ty>),
-                _ => quote!(#ty),
-            };
-
             if inner.token.type_name() == "()" {
-                serializations.push(quote! {
-                    #enum_name::#variant_name => usize::cairo_serialize(&#variant_index)
-                });
-                deserializations.push(quote! {
-                    #variant_index => Ok(#enum_name::#variant_name)
-                });
-                serialized_sizes.push(quote! {
-                    #enum_name::#variant_name => 1
-                });
+                units.push(quote!(#variant_index => #variant_name));
             } else {
-                serializations.push(quote! {
-                    #enum_name::#variant_name(val) => {
-                        let mut temp = vec![];
-                        temp.extend(usize::cairo_serialize(&#variant_index));
-                        temp.extend(#ty_punctuated::cairo_serialize(val));
-                        temp
-                    }
-                });
-                deserializations.push(quote! {
-                    #variant_index => Ok(#enum_name::#variant_name(#ty_punctuated::cairo_deserialize(__felts, __offset + 1)?))
-                });
-                // +1 because we have to handle the variant index also.
-                serialized_sizes.push(quote! {
-                    #enum_name::#variant_name(val) => #ty_punctuated::cairo_serialized_size(val) + 1
-                })
+                let ty = utils::str_to_type(&inner.token.to_rust_type_path());
+                values.push(quote!(#variant_index => #variant_name(#ty)));
             }
         }
 
-        let ccs = utils::cainome_cairo_serde();
-
-        serialized_sizes.push(quote! {
-            _ => 0
-        });
-
-        serializations.push(quote! {
-            _ => vec![]
-        });
-
-        deserializations.push(quote! {
-            _ => return Err(#ccs::Error::Deserialize(format!("Index not handle for enum {}", #name_str)))
-        });
+        let size_arg = utils::serialized_size_arg(composite);
 
         let (impl_line, rust_type) = if composite.is_generic() {
             let gen_args: Vec<Ident> = composite
@@ -158,30 +146,10 @@ impl CairoEnum {
 
                 #rust_type
 
-                const SERIALIZED_SIZE: std::option::Option<usize> = std::option::Option::None;
-
-                #[inline]
-                fn cairo_serialized_size(__rust: &Self::RustType) -> usize {
-                    match __rust {
-                        #(#serialized_sizes),*
-                    }
-                }
-
-                fn cairo_serialize(__rust: &Self::RustType) -> Vec<starknet::core::types::Felt> {
-                    match __rust {
-                        #(#serializations),*
-                    }
-                }
-
-                fn cairo_deserialize(__felts: &[starknet::core::types::Felt], __offset: usize) -> #ccs::Result<Self::RustType> {
-                    let __f = __felts[__offset];
-                    let __index = u128::from_be_bytes(__f.to_bytes_be()[16..].try_into().unwrap());
-
-                    match __index as usize {
-                        #(#deserializations),*
-                    }
-
-                }
+                #ccs::cairo_serde_enum_body!(#enum_name {
+                    unit: [#(#units),*],
+                    value: [#(#values),*],
+                } #size_arg);
             }
         }
     }
@@ -1,4 +1,5 @@
 use cainome_parser::tokens::{Composite, CompositeInnerKind, Token};
+use convert_case::{Case, Casing};
 use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
 use syn::{LitStr, Type};
@@ -16,14 +17,18 @@ impl CairoEnumEvent {
         }
 
         let depth = 0;
-        let content = Self::expand_event_enum(composite, depth, enums, structs, None);
+        let content = Self::expand_event_enum(composite, depth, enums, structs, &[]);
 
         let event_name = utils::str_to_ident(&composite.type_name_or_alias());
 
         let snrs_types = utils::snrs_types();
         let ccs = utils::cainome_cairo_serde();
 
+        let visitor = Self::expand_visitor(composite);
+
         quote! {
+            #visitor
+
             impl TryFrom<&#snrs_types::EmittedEvent> for #event_name {
                 type Error = String;
 
@@ -58,25 +63,77 @@ impl CairoEnumEvent {
         }
     }
 
+    /// Expands the visitor trait of the event enum, with one method per variant,
+    /// and the `accept` method dispatching the event to the visitor.
+    ///
+    /// The methods have no default implementation, so adding a variant
+    /// to the event requires all the visitors to be updated.
+    pub fn expand_visitor(composite: &Composite) -> TokenStream2 {
+        let event_name_str = composite.type_name_or_alias();
+        let event_name = utils::str_to_ident(&event_name_str);
+        let visitor = utils::str_to_ident(&format!("{}Visitor", event_name_str));
+
+        let mut methods = vec![];
+        let mut arms = vec![];
+
+        for variant in &composite.inners {
+            let variant_ident = utils::str_to_ident(&variant.name);
+            let method = utils::str_to_ident(&format!("on_{}", variant.name.to_case(Case::Snake)));
+
+            if variant.token.type_name() == "()" {
+                methods.push(quote!(fn #method(&mut self);));
+                arms.push(quote!(#event_name::#variant_ident => visitor.#method()));
+            } else {
+                let ty = utils::str_to_type(&variant.token.to_rust_type());
+                methods.push(quote!(fn #method(&mut self, event: &#ty);));
+                arms.push(quote!(#event_name::#variant_ident(ref e) => visitor.#method(e)));
+            }
+        }
+
+        let doc = format!(
+            "Visitor of the `{}` variants, see `{}::accept`.",
+            event_name_str, event_name_str
+        );
+
+        quote! {
+            #[doc = #doc]
+            pub trait #visitor {
+                #(#methods)*
+            }
+
+            impl #event_name {
+                /// Calls the method of the visitor matching the variant of the event.
+                pub fn accept<V: #visitor + ?Sized>(&self, visitor: &mut V) {
+                    match *self {
+                        #(#arms,)*
+                    }
+                }
+            }
+        }
+    }
+
+    /// Expands the decoding of the variants of an event enum.
+    ///
+    /// The `outter_enums` are the variants wrapping this enum, from the root event
+    /// (`Event::Nested` then `NestedEvent::Deep` for `DeepEvent`), to wrap the decoded event.
     pub fn expand_event_enum(
         composite: &Composite,
         depth: usize,
         enums: &[Token],
         structs: &[Token],
-        outter_enum: Option<Type>,
+        outter_enums: &[Type],
     ) -> TokenStream2 {
         let mut variants = vec![];
 
         let event_name_str = composite.type_name_or_alias();
         let event_name = utils::str_to_ident(&composite.type_name_or_alias());
 
-        let snrs_utils = utils::snrs_utils();
-
         for variant in &composite.inners {
             let selector_key_offset = utils::str_to_litint(&depth.to_string());
 
             let variant_ident = utils::str_to_ident(&variant.name);
             let variant_name_str = utils::str_to_litstr(&variant.name);
+            let variant_selector = utils::event_selector(&variant.name);
 
             let variant_type_path = variant.token.type_path();
             let variant_type_name =
@@ -100,14 +157,13 @@ impl CairoEnumEvent {
             // If it's flat, the compiler enforces the type to be an enum.
             #[allow(clippy::collapsible_else_if)]
             let content = if is_flat {
-                // TODO: need recursion here...
                 let outter = utils::str_to_type(&format!("{}::{}", event_name_str, &variant.name));
                 Self::expand_event_enum(
                     variant_token.to_composite().unwrap(),
                     depth,
                     enums,
                     structs,
-                    Some(outter),
+                    &[outter_enums, &[outter]].concat(),
                 )
             } else {
                 if variant_is_enum {
@@ -119,12 +175,12 @@ impl CairoEnumEvent {
                         depth + 1,
                         enums,
                         structs,
-                        Some(outter),
+                        &[outter_enums, &[outter]].concat(),
                     );
 
                     quote! {
                         let selector = event.keys[#selector_key_offset];
-                        if selector == #snrs_utils::get_selector_from_name(#variant_name_str).unwrap_or_else(|_| panic!("Invalid selector for {}", #variant_name_str)) {
+                        if selector == #variant_selector {
                             #inner_content
                         }
                     }
@@ -134,23 +190,21 @@ impl CairoEnumEvent {
                         variant_name_str.clone(),
                     );
 
-                    let end_return = if let Some(ref o) = outter_enum {
-                        quote! {
-                            return Ok(#o(#event_name::#variant_ident(#variant_type_name {
-                                #(#names),*
-                            })))
-                        }
-                    } else {
+                    // Wrapped into the outter enums, the innermost first.
+                    let event = outter_enums.iter().rev().fold(
                         quote! {
-                            return Ok(#event_name::#variant_ident(#variant_type_name {
+                            #event_name::#variant_ident(#variant_type_name {
                                 #(#names),*
-                            }))
-                        }
-                    };
+                            })
+                        },
+                        |event, o| quote!(#o(#event)),
+                    );
+
+                    let end_return = quote!(return Ok(#event));
 
                     quote! {
                         let selector = event.keys[#selector_key_offset];
-                        if selector == #snrs_utils::get_selector_from_name(#variant_name_str).unwrap_or_else(|_| panic!("Invalid selector for {}", #variant_name_str)) {
+                        if selector == #variant_selector {
                             let mut key_offset = #selector_key_offset + 1;
                             let mut data_offset = 0;
 
@@ -195,20 +249,20 @@ impl CairoEnumEvent {
             match inner.kind {
                 CompositeInnerKind::Key => {
                     desers_tokens.push(quote! {
-                        let #name = match #ty_punctuated::cairo_deserialize(&event.keys, key_offset) {
+                        let (#name, size) = match #ty_punctuated::cairo_deserialize_with_size(&event.keys, key_offset) {
                             Ok(v) => v,
-                            Err(e) => return Err(format!("Could not deserialize field {} for {}: {:?}", #name_str, #variant_name, e)),
+                            Err(e) => return Err(format!("Could not deserialize field {} for {}: {}", #name_str, #variant_name, e)),
                         };
-                        key_offset += #ty_punctuated::cairo_serialized_size(&#name);
+                        key_offset += size;
                     });
                 }
                 CompositeInnerKind::Data => {
                     desers_tokens.push(quote! {
-                        let #name = match #ty_punctuated::cairo_deserialize(&event.data, data_offset) {
+                        let (#name, size) = match #ty_punctuated::cairo_deserialize_with_size(&event.data, data_offset) {
                             Ok(v) => v,
-                            Err(e) => return Err(format!("Could not deserialize field {} for {}: {:?}", #name_str, #variant_name, e)),
+                            Err(e) => return Err(format!("Could not deserialize field {} for {}: {}", #name_str, #variant_name, e)),
                         };
-                        data_offset += #ty_punctuated::cairo_serialized_size(&#name);
+                        data_offset += size;
                     });
                 }
                 _ => {}
@@ -0,0 +1,297 @@
+use cainome_parser::tokens::{Composite, CompositeType, Function, StateMutability, Token};
+use cainome_parser::TokenizedAbi;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+
+use crate::expand::r#struct::member_name;
+use crate::expand::types::CairoToRust;
+use crate::expand::utils;
+
+/// Maximum depth of nested types for which a placeholder value is built,
+/// to stop on recursive types.
+const MAX_PLACEHOLDER_DEPTH: usize = 8;
+
+pub struct CairoExample;
+
+impl CairoExample {
+    /// Expands a program interacting with the contract: each view is called with
+    /// a reader, and the first external is invoked with an account.
+    /// The inputs of the functions are placeholder values, the functions whose
+    /// placeholders can't be built (like recursive types) being left out.
+    ///
+    /// # Arguments
+    ///
+    /// * `contract_name` - The name of the contract.
+    /// * `abi_tokens` - The tokens of the contract ABI.
+    /// * `functions` - The functions of the contract, sorted by name.
+    /// * `bindings_path` - The path of the bindings file, relative to the example.
+    /// * `sibling_modules` - The modules referenced by the bindings as `super::<module>`,
+    ///   in the directory of the bindings file.
+    pub fn expand(
+        contract_name: &str,
+        abi_tokens: &TokenizedAbi,
+        functions: &[&Function],
+        bindings_path: &str,
+        sibling_modules: &[String],
+    ) -> TokenStream2 {
+        let contract = utils::str_to_ident(contract_name);
+        let reader = utils::str_to_ident(&format!("{}Reader", contract_name));
+        let bindings_mod = utils::str_to_ident(
+            bindings_path
+                .rsplit('/')
+                .next()
+                .unwrap_or(bindings_path)
+                .trim_end_matches(".rs"),
+        );
+
+        // The `super` of the bindings is the example, which declares their siblings.
+        let bindings_dir = bindings_path.rsplit_once('/').map_or("", |(dir, _)| dir);
+        let siblings = sibling_modules.iter().map(|module| {
+            let path = if bindings_dir.is_empty() {
+                format!("{module}.rs")
+            } else {
+                format!("{bindings_dir}/{module}.rs")
+            };
+            let path = utils::str_to_litstr(&path);
+            let module = utils::str_to_ident(module);
+
+            quote! {
+                #[path = #path]
+                #[allow(warnings)]
+                mod #module;
+            }
+        });
+
+        let bindings_path = utils::str_to_litstr(bindings_path);
+
+        let placeholders = Placeholders::new(abi_tokens);
+
+        let mut views = vec![];
+        for func in functions
+            .iter()
+            .filter(|f| f.state_mutability == StateMutability::View)
+        {
+            let name = utils::str_to_ident(&func.name);
+            let name_str = utils::str_to_litstr(&func.name);
+
+            let Some(inputs) = placeholders.inputs(func) else {
+                views.push(quote! {
+                    println!("{}: left out, its inputs can't be built with placeholder values", #name_str);
+                });
+                continue;
+            };
+
+            views.push(quote! {
+                match reader.#name(#(&#inputs),*).call().await {
+                    Ok(_) => println!("{}: ok", #name_str),
+                    Err(e) => println!("{}: {}", #name_str, e),
+                }
+            });
+        }
+
+        let external = functions
+            .iter()
+            .filter(|f| f.state_mutability == StateMutability::External)
+            .find_map(|func| Some((func, placeholders.inputs(func)?)))
+            .map(|(func, inputs)| {
+                let name = utils::str_to_ident(&func.name);
+                let name_str = utils::str_to_litstr(&func.name);
+
+                quote! {
+                    let signer = LocalWallet::from(SigningKey::from_secret_scalar(private_key));
+                    let chain_id = provider.chain_id().await.expect("Failed to fetch the chain id");
+                    let account = SingleOwnerAccount::new(
+                        provider.clone(),
+                        signer,
+                        account_address,
+                        chain_id,
+                        ExecutionEncoding::New,
+                    );
+
+                    // Externals are invoked with an account.
+                    let contract = #contract::new(contract_address, &account);
+
+                    match contract.#name(#(&#inputs),*).send().await {
+                        Ok(tx) => println!("{}: {:#x}", #name_str, tx.transaction_hash),
+                        Err(e) => println!("{}: {}", #name_str, e),
+                    }
+                }
+            })
+            .unwrap_or_default();
+
+        quote! {
+            // The bindings are compiled with the example, as a module.
+            #[path = #bindings_path]
+            #[allow(warnings)]
+            mod #bindings_mod;
+
+            #(#siblings)*
+
+            #[allow(unused_imports)]
+            use #bindings_mod::*;
+
+            #[allow(unused_imports)]
+            use starknet::accounts::{ExecutionEncoding, SingleOwnerAccount};
+            use starknet::core::types::Felt;
+            use starknet::providers::jsonrpc::HttpTransport;
+            #[allow(unused_imports)]
+            use starknet::providers::{JsonRpcClient, Provider, Url};
+            #[allow(unused_imports)]
+            use starknet::signers::{LocalWallet, SigningKey};
+            use std::sync::Arc;
+
+            #[allow(unused_variables)]
+            #[tokio::main]
+            async fn main() {
+                // Placeholders, to be replaced with the values of your deployment.
+                let rpc_url = Url::parse("http://0.0.0.0:5050").expect("Invalid RPC URL");
+                let contract_address = Felt::from_hex_unchecked("0x0");
+                let account_address = Felt::from_hex_unchecked("0x0");
+                let private_key = Felt::from_hex_unchecked("0x1");
+
+                let provider = Arc::new(JsonRpcClient::new(HttpTransport::new(rpc_url)));
+
+                // Views are called with a reader, which only requires a provider.
+                let reader = #reader::new(contract_address, &provider);
+
+                #(#views)*
+
+                #external
+            }
+        }
+    }
+}
+
+/// Builds the placeholder values of the function inputs, `None` being
+/// returned for the values that can't be built.
+pub(crate) struct Placeholders<'a> {
+    abi_tokens: &'a TokenizedAbi,
+}
+
+impl<'a> Placeholders<'a> {
+    pub(crate) fn new(abi_tokens: &'a TokenizedAbi) -> Self {
+        Self { abi_tokens }
+    }
+
+    fn inputs(&self, func: &Function) -> Option<Vec<TokenStream2>> {
+        func.inputs
+            .iter()
+            .map(|(_, token)| self.value(token, 0))
+            .collect()
+    }
+
+    fn value(&self, token: &Token, depth: usize) -> Option<TokenStream2> {
+        if depth > MAX_PLACEHOLDER_DEPTH {
+            return None;
+        }
+
+        let ccs = utils::cainome_cairo_serde();
+        let snrs_types = utils::snrs_types();
+
+        let value = match token {
+            Token::CoreBasic(b) => match b.type_name().as_str() {
+                "felt" | "felt252" => quote!(#snrs_types::Felt::ZERO),
+                "bool" => quote!(false),
+                "ContractAddress" => quote!(#ccs::ContractAddress(#snrs_types::Felt::ZERO)),
+                "ClassHash" => quote!(#ccs::ClassHash(#snrs_types::Felt::ZERO)),
+                "bytes31" => quote!(#ccs::Bytes31::new(#snrs_types::Felt::ZERO).unwrap()),
+                // Integers.
+                _ => quote!(0),
+            },
+            Token::Array(a) => {
+                if a.is_legacy {
+                    quote!(#ccs::CairoArrayLegacy(vec![]))
+                } else if a.is_span() && depth > 0 {
+                    // The `Span<T>` inputs are slices, given as `&vec![]`.
+                    quote!(#ccs::CairoSpan(vec![]))
+                } else {
+                    quote!(vec![])
+                }
+            }
+            Token::Tuple(t) => {
+                let inners = t
+                    .inners
+                    .iter()
+                    .map(|i| self.value(i, depth + 1))
+                    .collect::<Option<Vec<_>>>()?;
+                quote!((#(#inners,)*))
+            }
+            Token::Composite(c) => return self.composite_value(c, depth),
+            Token::GenericArg(_) | Token::Function(_) => return None,
+        };
+
+        Some(value)
+    }
+
+    pub(crate) fn composite_value(
+        &self,
+        composite: &Composite,
+        depth: usize,
+    ) -> Option<TokenStream2> {
+        let ccs = utils::cainome_cairo_serde();
+        let snrs_types = utils::snrs_types();
+
+        match composite.type_path_no_generic().as_str() {
+            "core::option::Option" => return Some(quote!(None)),
+            "core::result::Result" => {
+                let ok = self.value(&composite.generic_args.first()?.1, depth + 1)?;
+                return Some(quote!(Ok(#ok)));
+            }
+            "core::r#box::Box" => {
+                let inner = self.value(&composite.generic_args.first()?.1, depth + 1)?;
+                return Some(quote!(Box::new(#inner)));
+            }
+            "core::integer::u256" => return Some(quote!(#ccs::U256 { low: 0, high: 0 })),
+            "core::byte_array::ByteArray" => return Some(quote!(#ccs::ByteArray::default())),
+            "core::starknet::eth_address::EthAddress" => {
+                return Some(quote!(#ccs::EthAddress(#snrs_types::Felt::ZERO)))
+            }
+            "core::internal::bounded_int::BoundedInt" => {
+                return Some(quote!(#snrs_types::Felt::ZERO))
+            }
+            _ => {}
+        }
+
+        // The generic types would require the resolution of their arguments.
+        if composite.is_builtin() || composite.is_generic() {
+            return None;
+        }
+
+        // The tokens of the inputs don't include the members, which are
+        // only found in the definition of the type.
+        let definition = self
+            .abi_tokens
+            .structs
+            .iter()
+            .chain(self.abi_tokens.enums.iter())
+            .filter_map(|t| t.to_composite().ok())
+            .find(|d| d.type_path_no_generic() == composite.type_path_no_generic())?;
+
+        let name = utils::str_to_type(&Token::Composite(definition.clone()).to_rust_type_path());
+
+        match definition.r#type {
+            CompositeType::Struct => {
+                let mut fields = vec![];
+                for inner in &definition.inners {
+                    let field = member_name(&inner.name);
+                    let value = self.value(&inner.token, depth + 1)?;
+                    fields.push(quote!(#field: #value));
+                }
+
+                Some(quote!(#name { #(#fields),* }))
+            }
+            CompositeType::Enum => {
+                let variant = definition.inners.first()?;
+                let variant_name = utils::str_to_ident(&variant.name);
+
+                if variant.token.type_name() == "()" {
+                    Some(quote!(#name::#variant_name))
+                } else {
+                    let value = self.value(&variant.token, depth + 1)?;
+                    Some(quote!(#name::#variant_name(#value)))
+                }
+            }
+            CompositeType::Unknown => None,
+        }
+    }
+}
@@ -13,9 +13,13 @@
 //!
 //! * `FCall` - Struct for readonly functions.
 //! * `ExecutionV1` - Struct from starknet-rs for transaction based functions.
+//! * `Invoke` - Struct wrapping the `ExecutionV1` (or `ExecutionV3`) with the `paymaster` option,
+//!   to route the transaction through a paymaster.
 use cainome_parser::tokens::{Function, FunctionOutputKind, StateMutability, Token};
+use convert_case::{Case, Casing};
 use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
+use syn::Ident;
 
 use crate::expand::types::CairoToRust;
 use crate::expand::utils;
@@ -35,6 +39,51 @@ impl ExecutionVersion {
             ExecutionVersion::V3 => quote!(self.account.execute_v3(vec![__call])),
         }
     }
+
+    /// Returns the type returned by the externals, the execution being wrapped
+    /// into an `Invoke` which can be sponsored by a paymaster if `paymaster` is set.
+    pub(crate) fn get_invoke_type(&self, paymaster: bool) -> TokenStream2 {
+        let exec_type = utils::str_to_type(&self.get_type_str());
+
+        if paymaster {
+            let ccs = utils::cainome_cairo_serde();
+            quote!(#ccs::paymaster::Invoke<'_, A, #exec_type>)
+        } else {
+            quote!(#exec_type)
+        }
+    }
+
+    /// Returns the invocation of `__call` by the externals, see [`Self::get_invoke_type`].
+    pub(crate) fn get_invoke_call(&self, paymaster: bool) -> TokenStream2 {
+        let exec_call = self.get_call_str();
+
+        if paymaster {
+            let ccs = utils::cainome_cairo_serde();
+            quote! {
+                #ccs::paymaster::Invoke::new(&self.account, vec![__call.clone()], #exec_call)
+            }
+        } else {
+            exec_call
+        }
+    }
+
+    /// Returns the invocation of the `__calls` vector in one transaction,
+    /// see [`Self::get_invoke_call`].
+    pub(crate) fn get_batch_invoke_call(&self, paymaster: bool) -> TokenStream2 {
+        let exec_call = match self {
+            ExecutionVersion::V1 => quote!(self.account.execute_v1(__calls)),
+            ExecutionVersion::V3 => quote!(self.account.execute_v3(__calls)),
+        };
+
+        if paymaster {
+            let ccs = utils::cainome_cairo_serde();
+            quote! {
+                #ccs::paymaster::Invoke::new(&self.account, __calls.clone(), #exec_call)
+            }
+        } else {
+            exec_call
+        }
+    }
 }
 
 fn get_func_inputs(inputs: &[(String, Token)]) -> Vec<TokenStream2> {
@@ -42,8 +91,8 @@ fn get_func_inputs(inputs: &[(String, Token)]) -> Vec<TokenStream2> {
 
     for (name, token) in inputs {
         let name = utils::str_to_ident(name);
-        let ty = utils::str_to_type(&token.to_rust_type_path());
-        out.push(quote!(#name:&#ty));
+        let ty = utils::input_type(token);
+        out.push(quote!(#name: #ty));
     }
 
     out
@@ -56,24 +105,20 @@ impl CairoFunction {
         func: &Function,
         is_for_reader: bool,
         execution_version: ExecutionVersion,
+        paymaster: bool,
     ) -> TokenStream2 {
         let func_name = &func.name;
         let func_name_ident = utils::str_to_ident(func_name);
+        let selector = utils::function_selector(func_name);
 
-        let mut serializations: Vec<TokenStream2> = vec![];
-        for (name, token) in &func.inputs {
-            let name = utils::str_to_ident(name);
-            let ty = utils::str_to_type(&token.to_rust_type_path());
-
-            let ser = match token {
-                Token::Tuple(_) => quote! {
-                    __calldata.extend(<#ty>::cairo_serialize(#name));
-                },
-                _ => quote!(__calldata.extend(#ty::cairo_serialize(#name));),
-            };
-
-            serializations.push(ser);
-        }
+        let calldata = utils::str_to_ident("__calldata");
+        let serializations: Vec<TokenStream2> = func
+            .inputs
+            .iter()
+            .map(|(name, token)| {
+                utils::input_serialization(&utils::str_to_ident(name), token, &calldata)
+            })
+            .collect();
 
         let out_type = match func.get_output_kind() {
             FunctionOutputKind::NoOutput => quote!(()),
@@ -88,7 +133,6 @@ impl CairoFunction {
         };
 
         let inputs = get_func_inputs(&func.inputs);
-        let func_name_call = utils::str_to_ident(&format!("{}_getcall", func_name));
         let type_param = if is_for_reader {
             utils::str_to_type("P")
         } else {
@@ -98,30 +142,38 @@ impl CairoFunction {
         let ccs = utils::cainome_cairo_serde();
 
         match &func.state_mutability {
-            StateMutability::View => quote! {
-                #[allow(clippy::ptr_arg)]
-                #[allow(clippy::too_many_arguments)]
-                pub fn #func_name_ident(
-                    &self,
-                    #(#inputs),*
-                ) -> #ccs::call::FCall<#type_param, #out_type> {
-                    use #ccs::CairoSerde;
-
-                    let mut __calldata = vec![];
-                    #(#serializations)*
-
-                    let __call = starknet::core::types::FunctionCall {
-                        contract_address: self.address,
-                        entry_point_selector: starknet::macros::selector!(#func_name),
-                        calldata: __calldata,
-                    };
-
-                    #ccs::call::FCall::new(
-                        __call,
-                        self.provider(),
-                    )
+            StateMutability::View => {
+                let doc = format!(
+                    "Calls the `{}` view, with `call()` on the returned call.",
+                    func_name
+                );
+
+                quote! {
+                    #[doc = #doc]
+                    #[allow(clippy::ptr_arg)]
+                    #[allow(clippy::too_many_arguments)]
+                    pub fn #func_name_ident(
+                        &self,
+                        #(#inputs),*
+                    ) -> #ccs::call::FCall<#type_param, #out_type> {
+                        use #ccs::CairoSerde;
+
+                        let mut __calldata = vec![];
+                        #(#serializations)*
+
+                        let __call = starknet::core::types::FunctionCall {
+                            contract_address: self.address,
+                            entry_point_selector: #selector,
+                            calldata: __calldata,
+                        };
+
+                        #ccs::call::FCall::new(
+                            __call,
+                            self.provider(),
+                        )
+                    }
                 }
-            },
+            }
             StateMutability::External => {
                 // For now, ExecutionV1 can't return the list of calls.
                 // This would be helpful to easily access the calls
@@ -131,46 +183,269 @@ impl CairoFunction {
                 //
                 // TODO: if it's possible to do it with lifetime,
                 // this can be tried in an issue.
-                let exec_type = utils::str_to_type(&execution_version.get_type_str());
-                let exec_call = execution_version.get_call_str();
+                let exec_type = execution_version.get_invoke_type(paymaster);
+                let exec_call = execution_version.get_invoke_call(paymaster);
+
+                let getcall = Self::expand_getcall(func);
+                let doc = format!(
+                    "Invokes the `{}` external, with `send()` on the returned execution \
+                     (or `estimate_fee()` to only estimate its fee).",
+                    func_name
+                );
 
                 quote! {
+                    #getcall
+
+                    #[doc = #doc]
                     #[allow(clippy::ptr_arg)]
                     #[allow(clippy::too_many_arguments)]
-                    pub fn #func_name_call(
+                    pub fn #func_name_ident(
                         &self,
                         #(#inputs),*
-                    ) -> starknet::core::types::Call {
+                    ) -> #exec_type {
                         use #ccs::CairoSerde;
 
                         let mut __calldata = vec![];
                         #(#serializations)*
 
-                        starknet::core::types::Call {
+                        let __call = starknet::core::types::Call {
                             to: self.address,
-                            selector: starknet::macros::selector!(#func_name),
+                            selector: #selector,
                             calldata: __calldata,
-                        }
+                        };
+
+                        #exec_call
+                    }
+                }
+            }
+        }
+    }
+
+    /// Expands the `<function>_getcall` method of an external, returning its call
+    /// to the contract at `self.address`.
+    ///
+    /// # Arguments
+    ///
+    /// * `func` - The external to expand.
+    pub fn expand_getcall(func: &Function) -> TokenStream2 {
+        let func_name = &func.name;
+        let func_name_call = utils::str_to_ident(&format!("{}_getcall", func_name));
+        let selector = utils::function_selector(func_name);
+
+        let calldata = utils::str_to_ident("__calldata");
+        let serializations: Vec<TokenStream2> = func
+            .inputs
+            .iter()
+            .map(|(name, token)| {
+                utils::input_serialization(&utils::str_to_ident(name), token, &calldata)
+            })
+            .collect();
+
+        let inputs = get_func_inputs(&func.inputs);
+        let ccs = utils::cainome_cairo_serde();
+
+        let doc = format!(
+            "Returns the call to the `{}` external, to be sent along with other calls.",
+            func_name
+        );
+
+        quote! {
+            #[doc = #doc]
+            #[allow(clippy::ptr_arg)]
+            #[allow(clippy::too_many_arguments)]
+            pub fn #func_name_call(
+                &self,
+                #(#inputs),*
+            ) -> starknet::core::types::Call {
+                use #ccs::CairoSerde;
+
+                let mut __calldata = vec![];
+                #(#serializations)*
+
+                starknet::core::types::Call {
+                    to: self.address,
+                    selector: #selector,
+                    calldata: __calldata,
+                }
+            }
+        }
+    }
+
+    /// Returns the name of the struct grouping the parameters of the function.
+    pub fn params_struct_name(func: &Function) -> Ident {
+        utils::str_to_ident(&format!("{}Params", func.name.to_case(Case::Pascal)))
+    }
+
+    /// Expands the struct grouping the parameters of the function,
+    /// to be given to the `<function>_with` variant of the function.
+    ///
+    /// # Arguments
+    ///
+    /// * `func` - The function to expand the parameters of.
+    /// * `derives` - The derives of the struct.
+    pub fn expand_params_struct(func: &Function, derives: &[String]) -> TokenStream2 {
+        let struct_name = Self::params_struct_name(func);
+        let inputs = func.inputs.iter().map(|(name, token)| {
+            let name = utils::str_to_ident(name);
+            let ty = utils::str_to_type(&token.to_rust_type());
+            let schema = utils::schema_derive(&token.to_rust_type(), derives, None);
+            quote!(#schema pub #name: #ty)
+        });
+
+        let derives: Vec<_> = derives.iter().map(|d| utils::str_to_type(d)).collect();
+
+        let doc = format!("Parameters of the `{}` function.", func.name);
+
+        quote! {
+            #[doc = #doc]
+            #[derive(#(#derives,)*)]
+            pub struct #struct_name {
+                #(#inputs),*
+            }
+        }
+    }
+
+    /// Expands the `<function>_with` variant of the function, taking its parameters
+    /// as a struct (see [`CairoFunction::expand_params_struct`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `func` - The function to expand.
+    /// * `is_for_reader` - Whether the function is expanded for the reader.
+    /// * `execution_version` - The version of transaction to be executed.
+    /// * `paymaster` - Whether the externals can be sponsored by a paymaster.
+    pub fn expand_with(
+        func: &Function,
+        is_for_reader: bool,
+        execution_version: ExecutionVersion,
+        paymaster: bool,
+    ) -> TokenStream2 {
+        let func_name_ident = utils::str_to_ident(&func.name);
+        let func_name_with = utils::str_to_ident(&format!("{}_with", func.name));
+        let struct_name = Self::params_struct_name(func);
+        let input_names: Vec<_> = func
+            .inputs
+            .iter()
+            .map(|(name, _)| utils::str_to_ident(name))
+            .collect();
+
+        let out_type = match &func.state_mutability {
+            StateMutability::View => {
+                let out_type = match func.get_output_kind() {
+                    FunctionOutputKind::NoOutput => quote!(()),
+                    FunctionOutputKind::Cairo1 => {
+                        let out_type = utils::str_to_type(&func.outputs[0].to_rust_type_path());
+                        quote!(#out_type)
+                    }
+                    FunctionOutputKind::Cairo0 => {
+                        let out_type = utils::str_to_type(&func.get_cairo0_output_name());
+                        quote!(#out_type)
+                    }
+                };
+                let type_param = if is_for_reader {
+                    utils::str_to_type("P")
+                } else {
+                    utils::str_to_type("A::Provider")
+                };
+                let ccs = utils::cainome_cairo_serde();
+
+                quote!(#ccs::call::FCall<#type_param, #out_type>)
+            }
+            StateMutability::External => execution_version.get_invoke_type(paymaster),
+        };
+
+        let doc = format!(
+            "Same as [`Self::{}`], with the parameters grouped into a [`{}`].",
+            func.name, struct_name
+        );
+
+        quote! {
+            #[doc = #doc]
+            pub fn #func_name_with(&self, params: &#struct_name) -> #out_type {
+                self.#func_name_ident(#(&params.#input_names),*)
+            }
+        }
+    }
+
+    /// Expands the blocking variant of the function, to be added to a blocking facade
+    /// where `inner` is the wrapped contract (or reader) and `executor` a
+    /// `BlockOn` executor.
+    ///
+    /// Views return the decoded output, and externals the result of the sent transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `func` - The function to expand.
+    /// * `inner` - The name of the field of the facade wrapping the contract.
+    pub fn expand_blocking(func: &Function, inner: &str) -> TokenStream2 {
+        let func_name_ident = utils::str_to_ident(&func.name);
+        let inner = utils::str_to_ident(inner);
+
+        let inputs = get_func_inputs(&func.inputs);
+        let input_names: Vec<_> = func
+            .inputs
+            .iter()
+            .map(|(name, _)| utils::str_to_ident(name))
+            .collect();
+
+        let ccs = utils::cainome_cairo_serde();
+
+        match &func.state_mutability {
+            StateMutability::View => {
+                let out_type = match func.get_output_kind() {
+                    FunctionOutputKind::NoOutput => quote!(()),
+                    FunctionOutputKind::Cairo1 => {
+                        let out_type = utils::str_to_type(&func.outputs[0].to_rust_type_path());
+                        quote!(#out_type)
                     }
+                    FunctionOutputKind::Cairo0 => {
+                        let out_type = utils::str_to_type(&func.get_cairo0_output_name());
+                        quote!(#out_type)
+                    }
+                };
+
+                let doc = format!(
+                    "Calls the `{}` view, blocking until its output is decoded.",
+                    func.name
+                );
 
+                quote! {
+                    #[doc = #doc]
                     #[allow(clippy::ptr_arg)]
                     #[allow(clippy::too_many_arguments)]
                     pub fn #func_name_ident(
                         &self,
                         #(#inputs),*
-                    ) -> #exec_type {
-                        use #ccs::CairoSerde;
+                    ) -> #ccs::Result<#out_type> {
+                        use #ccs::blocking::BlockOn;
 
-                        let mut __calldata = vec![];
-                        #(#serializations)*
+                        self.executor.block_on(self.#inner.#func_name_ident(#(#input_names),*).call())
+                    }
+                }
+            }
+            StateMutability::External => {
+                let doc = format!(
+                    "Invokes the `{}` external, blocking until the transaction is sent.",
+                    func.name
+                );
 
-                        let __call = starknet::core::types::Call {
-                            to: self.address,
-                            selector: starknet::macros::selector!(#func_name),
-                            calldata: __calldata,
-                        };
+                let snrs_types = utils::snrs_types();
+                let snrs_accounts = utils::snrs_accounts();
 
-                        #exec_call
+                quote! {
+                    #[doc = #doc]
+                    #[allow(clippy::ptr_arg)]
+                    #[allow(clippy::too_many_arguments)]
+                    pub fn #func_name_ident(
+                        &self,
+                        #(#inputs),*
+                    ) -> Result<
+                        #snrs_types::InvokeTransactionResult,
+                        #snrs_accounts::AccountError<<A as #snrs_accounts::Account>::SignError>,
+                    > {
+                        use #ccs::blocking::BlockOn;
+
+                        self.executor.block_on(self.#inner.#func_name_ident(#(#input_names),*).send())
                     }
                 }
             }
@@ -0,0 +1,149 @@
+//! # L1 handlers messages
+//!
+//! The L1 handlers of a contract are invoked by the sequencer with the messages
+//! sent from L1 through the Starknet core contract. The first input of a handler
+//! is the L1 address sending the message, and the other inputs are the payload
+//! of the message.
+//!
+//! For the cross-layer tests, a `<Contract>L1Handlers` struct is generated with,
+//! for each L1 handler, the selector, the payload and the full message, whose
+//! hash is the one computed by the Starknet core contract.
+use cainome_parser::tokens::{Function, Token};
+use convert_case::{Case, Casing};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::Ident;
+
+use crate::expand::utils;
+
+pub struct CairoL1Handler;
+
+impl CairoL1Handler {
+    /// Returns the name of the struct holding the messages helpers of the contract.
+    pub fn struct_name(contract_name: &Ident) -> Ident {
+        utils::str_to_ident(&format!("{}L1Handlers", contract_name))
+    }
+
+    /// Expands the struct with the messages helpers of the L1 handlers, nothing
+    /// if the contract has no L1 handler.
+    ///
+    /// # Arguments
+    ///
+    /// * `contract_name` - The name of the contract.
+    /// * `l1_handlers` - The L1 handlers of the contract.
+    pub fn expand(contract_name: &Ident, l1_handlers: &[Token]) -> TokenStream2 {
+        let mut handlers: Vec<&Function> = l1_handlers
+            .iter()
+            .filter_map(|t| t.to_function().ok())
+            .collect();
+
+        if handlers.is_empty() {
+            return quote!();
+        }
+
+        handlers.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let struct_name = Self::struct_name(contract_name);
+        let doc = format!(
+            "Messages from L1 to the L1 handlers of the `{}` contract.",
+            contract_name
+        );
+
+        let items = handlers.iter().map(|h| Self::expand_handler(h));
+
+        quote! {
+            #[doc = #doc]
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+            pub struct #struct_name;
+
+            impl #struct_name {
+                #(#items)*
+            }
+        }
+    }
+
+    fn expand_handler(handler: &Function) -> TokenStream2 {
+        let snrs_types = utils::snrs_types();
+        let ccs = utils::cainome_cairo_serde();
+
+        let name = &handler.name;
+        let selector = utils::function_selector(name);
+        let selector_name =
+            utils::str_to_ident(&format!("{}_SELECTOR", name.to_case(Case::UpperSnake)));
+        let payload_fn = utils::str_to_ident(&format!("{}_payload", name));
+        let message_fn = utils::str_to_ident(&format!("{}_message", name));
+
+        // The first input is the L1 address sending the message, not part of the payload.
+        let payload_inputs = handler.inputs.iter().skip(1);
+
+        let mut inputs = vec![];
+        let mut names = vec![];
+        let mut serializations = vec![];
+
+        let buffer = utils::str_to_ident("__payload");
+
+        for (input_name, token) in payload_inputs {
+            let input_name = utils::str_to_ident(input_name);
+            let ty = utils::input_type(token);
+
+            inputs.push(quote!(#input_name: #ty));
+            names.push(quote!(#input_name));
+            serializations.push(utils::input_serialization(&input_name, token, &buffer));
+        }
+
+        let payload = if serializations.is_empty() {
+            quote!(vec![])
+        } else {
+            quote! {
+                use #ccs::CairoSerde;
+
+                let mut __payload = vec![];
+                #(#serializations)*
+                __payload
+            }
+        };
+
+        let selector_doc = format!("Selector of the `{}` L1 handler.", name);
+        let payload_doc = format!(
+            "Returns the payload of a message to the `{}` L1 handler: its inputs \
+             following the address of the sender, serialized.",
+            name
+        );
+        let message_doc = format!(
+            "Returns the message sent by `from_address` on L1 with the `nonce` of the core contract, \
+             to the `{}` L1 handler of the contract at `to_address`. \
+             Its hash (`MsgToL2::hash`) is the one computed by the Starknet core contract.",
+            name
+        );
+
+        quote! {
+            #[doc = #selector_doc]
+            pub const #selector_name: #snrs_types::Felt = #selector;
+
+            #[doc = #payload_doc]
+            #[allow(clippy::ptr_arg)]
+            #[allow(clippy::too_many_arguments)]
+            pub fn #payload_fn(#(#inputs),*) -> Vec<#snrs_types::Felt> {
+                #payload
+            }
+
+            #[doc = #message_doc]
+            #[allow(clippy::ptr_arg)]
+            #[allow(clippy::too_many_arguments)]
+            pub fn #message_fn(
+                from_address: #snrs_types::EthAddress,
+                to_address: #snrs_types::Felt,
+                nonce: u64,
+                #(#inputs),*
+            ) -> #snrs_types::MsgToL2 {
+                #snrs_types::MsgToL2 {
+                    from_address,
+                    to_address,
+                    selector: Self::#selector_name,
+                    payload: Self::#payload_fn(#(#names),*),
+                    nonce,
+                }
+            }
+        }
+    }
+}
@@ -1,13 +1,31 @@
+pub(crate) mod artifact;
+pub(crate) mod constructor;
+pub(crate) mod consts;
 pub(crate) mod contract;
+pub(crate) mod conversion;
 pub(crate) mod r#enum;
 pub(crate) mod event;
+pub(crate) mod example;
 pub(crate) mod function;
+pub(crate) mod l1_handler;
+pub(crate) mod roundtrip;
+pub(crate) mod shared;
+pub(crate) mod src5;
 pub(crate) mod r#struct;
-mod types;
+pub(crate) mod types;
 pub(crate) mod utils;
 
+pub use artifact::CairoArtifact;
+pub use constructor::CairoConstructor;
+pub use consts::CairoConsts;
 pub use contract::CairoContract;
+pub use conversion::CairoConversion;
 pub use event::CairoEnumEvent;
+pub use example::CairoExample;
 pub use function::CairoFunction;
+pub use l1_handler::CairoL1Handler;
 pub use r#enum::CairoEnum;
 pub use r#struct::CairoStruct;
+pub use roundtrip::CairoRoundtripTests;
+pub use shared::CairoSharedTypes;
+pub use src5::CairoSrc5;
@@ -0,0 +1,123 @@
+//! # Round-trip tests of the generated types
+//!
+//! A test is generated for each struct and enum of the ABI, serializing a sample
+//! value and deserializing it back. The sample values list all the members of the
+//! types, so the tests stop compiling when the bindings drift from the ABI.
+use cainome_parser::TokenizedAbi;
+use convert_case::{Case, Casing};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::Ident;
+
+use crate::expand::example::Placeholders;
+use crate::expand::utils;
+
+pub struct CairoRoundtripTests;
+
+impl CairoRoundtripTests {
+    /// Expands the `<contract>_roundtrip_tests` module, with a round-trip test per type.
+    ///
+    /// The generic types, and the types for which no sample value can be built,
+    /// are not tested.
+    ///
+    /// # Arguments
+    ///
+    /// * `contract_name` - The name of the contract.
+    /// * `abi_tokens` - The tokens of the contract ABI.
+    pub fn expand(contract_name: &Ident, abi_tokens: &TokenizedAbi) -> TokenStream2 {
+        let ccs = utils::cainome_cairo_serde();
+        let module = utils::str_to_ident(&utils::contract_item_name(
+            contract_name,
+            "roundtrip_tests",
+            Case::Snake,
+        ));
+
+        let mut composites: Vec<_> = abi_tokens
+            .structs
+            .iter()
+            .chain(abi_tokens.enums.iter())
+            .filter_map(|t| t.to_composite().ok())
+            .filter(|c| !c.is_builtin() && !c.is_generic())
+            .collect();
+        composites.sort_by_key(|c| c.type_name_or_alias());
+
+        let mut tests = vec![];
+
+        for composite in composites {
+            let Some(sample) = Placeholders::new(abi_tokens).composite_value(composite, 0) else {
+                continue;
+            };
+
+            let type_name = composite.type_name_or_alias();
+            let ty = utils::str_to_ident(&type_name);
+            let test_name =
+                utils::str_to_ident(&format!("roundtrip_{}", type_name.to_case(Case::Snake)));
+
+            tests.push(quote! {
+                #[test]
+                fn #test_name() {
+                    let value: #ty = #sample;
+
+                    let felts = #ty::cairo_serialize(&value);
+                    assert_eq!(felts.len(), #ty::cairo_serialized_size(&value));
+                    if let Some(size) = #ty::SERIALIZED_SIZE {
+                        assert_eq!(felts.len(), size);
+                    }
+
+                    let decoded = #ty::cairo_deserialize(&felts, 0).unwrap();
+                    assert_eq!(#ty::cairo_serialize(&decoded), felts);
+                }
+            });
+        }
+
+        quote! {
+            #[cfg(test)]
+            mod #module {
+                #[allow(unused_imports)]
+                use super::*;
+                #[allow(unused_imports)]
+                use #ccs::CairoSerde;
+
+                #(#tests)*
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cainome_parser::AbiParser;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_expand_skips_generic_types() {
+        let abi = r#"[
+            {
+                "type": "struct",
+                "name": "pkg::Order",
+                "members": [{ "name": "price", "type": "core::integer::u128" }]
+            },
+            {
+                "type": "struct",
+                "name": "pkg::Wrapper::<core::felt252>",
+                "members": [{ "name": "inner", "type": "core::felt252" }]
+            },
+            {
+                "type": "function",
+                "name": "get",
+                "inputs": [],
+                "outputs": [{ "type": "pkg::Wrapper::<core::felt252>" }],
+                "state_mutability": "view"
+            }
+        ]"#;
+
+        let tokens = AbiParser::tokens_from_abi_string(abi, &HashMap::new()).unwrap();
+        let expanded =
+            CairoRoundtripTests::expand(&utils::str_to_ident("MyBook"), &tokens).to_string();
+
+        assert!(expanded.contains("mod my_book_roundtrip_tests"));
+        assert!(expanded.contains("fn roundtrip_order ()"));
+        assert!(!expanded.contains("roundtrip_wrapper"));
+    }
+}
@@ -0,0 +1,45 @@
+//! # Types shared by a group of contracts
+//!
+//! The types defined identically by the contracts of a group are generated once in
+//! a common module. The bindings of each contract re-export them, with the visitor
+//! of the events when the events are decoded, so that the paths of the types are the
+//! same as for the types generated by the bindings.
+use cainome_parser::tokens::{Composite, CompositeType};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::Path;
+
+use crate::expand::utils;
+use crate::GenerationMode;
+
+pub struct CairoSharedTypes;
+
+impl CairoSharedTypes {
+    /// Expands the re-export of a shared type from the module generating it.
+    ///
+    /// # Arguments
+    ///
+    /// * `composite` - The shared type.
+    /// * `module` - The path of the module generating the shared type.
+    /// * `mode` - The parts of the bindings generated.
+    pub fn expand_reexport(
+        composite: &Composite,
+        module: &str,
+        mode: GenerationMode,
+    ) -> TokenStream2 {
+        let module: Path = syn::parse_str(module).expect("valid module path");
+        let type_name = composite.type_name_or_alias();
+        let name = utils::str_to_ident(&type_name);
+
+        // Only the event enums have a visitor.
+        if composite.is_event
+            && composite.r#type == CompositeType::Enum
+            && mode.has_events_decoding()
+        {
+            let visitor = utils::str_to_ident(&format!("{}Visitor", type_name));
+            quote!(pub use #module::{#name, #visitor};)
+        } else {
+            quote!(pub use #module::#name;)
+        }
+    }
+}
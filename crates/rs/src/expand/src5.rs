@@ -0,0 +1,206 @@
+//! # SRC5 interface detection
+//!
+//! The SRC5 ids of the interfaces of the ABI are precomputed as constants, and
+//! the contract and the reader get methods to probe a deployed contract for them
+//! with the `supports_interface` entrypoint of SRC5.
+use cainome_parser::{src5, TokenizedAbi};
+use convert_case::{Boundary, Case, Casing};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use starknet::core::types::Felt;
+use syn::Ident;
+
+use crate::expand::utils;
+
+pub struct CairoSrc5;
+
+impl CairoSrc5 {
+    /// Returns the name of the interface id constant and of the probing method of
+    /// each interface of the ABI, with its fully qualified name and its id.
+    ///
+    /// The constants are prefixed by the name of the contract (`MY_TOKEN_IERC20_INTERFACE_ID`).
+    /// The interfaces with the same name in different modules only keep the first one,
+    /// in the order of their fully qualified names.
+    ///
+    /// # Arguments
+    ///
+    /// * `contract_name` - The name of the contract.
+    /// * `abi_tokens` - The tokens of the contract ABI.
+    pub fn interfaces(contract_name: &Ident, abi_tokens: &TokenizedAbi) -> Vec<Src5Interface> {
+        let mut interfaces: Vec<Src5Interface> = vec![];
+
+        for (path, id) in src5::interface_ids(abi_tokens) {
+            let name = path.rsplit("::").next().unwrap_or(&path);
+            // `IERC20` is a single word, but `IERC721Metadata` two.
+            let to_case = |case| {
+                name.from_case(Case::Pascal)
+                    .without_boundaries(&[Boundary::UpperDigit, Boundary::LowerDigit])
+                    .to_case(case)
+            };
+
+            let const_name = utils::contract_item_name(
+                contract_name,
+                &format!("{}_INTERFACE_ID", to_case(Case::UpperSnake)),
+                Case::UpperSnake,
+            );
+
+            if interfaces.iter().any(|i| i.const_name == const_name) {
+                continue;
+            }
+
+            let method_name = format!("supports_{}", to_case(Case::Snake));
+
+            interfaces.push(Src5Interface {
+                path,
+                id,
+                const_name,
+                method_name,
+            });
+        }
+
+        interfaces
+    }
+
+    /// Expands the interface ids constants.
+    ///
+    /// # Arguments
+    ///
+    /// * `interfaces` - The interfaces of the ABI.
+    pub fn expand_consts(interfaces: &[Src5Interface]) -> TokenStream2 {
+        let snrs_types = utils::snrs_types();
+
+        let consts = interfaces.iter().map(|i| {
+            let const_name = utils::str_to_ident(&i.const_name);
+            let id = utils::felt_const(&i.id);
+            let doc = format!("SRC5 id of the `{}` interface.", i.path);
+
+            quote! {
+                #[doc = #doc]
+                pub const #const_name: #snrs_types::Felt = #id;
+            }
+        });
+
+        quote!(#(#consts)*)
+    }
+
+    /// Expands the methods probing the contract for the interfaces.
+    ///
+    /// # Arguments
+    ///
+    /// * `interfaces` - The interfaces of the ABI.
+    /// * `is_for_reader` - Whether the methods are expanded for the reader.
+    pub fn expand_methods(interfaces: &[Src5Interface], is_for_reader: bool) -> TokenStream2 {
+        let snrs_types = utils::snrs_types();
+        let ccs = utils::cainome_cairo_serde();
+        let selector = utils::function_selector("supports_interface");
+
+        let type_param = if is_for_reader {
+            utils::str_to_type("P")
+        } else {
+            utils::str_to_type("A::Provider")
+        };
+
+        let probes = interfaces.iter().map(|i| {
+            let const_name = utils::str_to_ident(&i.const_name);
+            let method_name = utils::str_to_ident(&i.method_name);
+            let doc = format!(
+                "Returns whether the contract supports the `{}` interface (SRC5).",
+                i.path
+            );
+
+            quote! {
+                #[doc = #doc]
+                pub fn #method_name(&self) -> #ccs::call::FCall<#type_param, bool> {
+                    self.src5_supports_interface(#const_name)
+                }
+            }
+        });
+
+        quote! {
+            /// Returns whether the contract supports the interface of the given id,
+            /// with the `supports_interface` entrypoint of SRC5.
+            pub fn src5_supports_interface(
+                &self,
+                interface_id: #snrs_types::Felt,
+            ) -> #ccs::call::FCall<#type_param, bool> {
+                let __call = #snrs_types::FunctionCall {
+                    contract_address: self.address,
+                    entry_point_selector: #selector,
+                    calldata: vec![interface_id],
+                };
+
+                #ccs::call::FCall::new(__call, self.provider())
+            }
+
+            #(#probes)*
+        }
+    }
+}
+
+/// An interface of the ABI, with the names of its generated items.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Src5Interface {
+    /// Fully qualified name of the interface.
+    pub path: String,
+    /// SRC5 id of the interface.
+    pub id: Felt,
+    /// Name of the constant holding the id.
+    pub const_name: String,
+    /// Name of the probing method.
+    pub method_name: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cainome_parser::AbiParser;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_interfaces_names() {
+        let interface = |name: &str| {
+            format!(
+                r#"{{ "type": "interface", "name": "{name}", "items": [
+                    {{ "type": "function", "name": "get", "inputs": [], "outputs": [],
+                       "state_mutability": "view" }}
+                ]}}"#
+            )
+        };
+
+        let abi = format!(
+            "[{}, {}, {}, {}]",
+            interface("pkg::erc20::IERC20"),
+            interface("pkg::erc721::IERC721Metadata"),
+            interface("pkg::IMyContract"),
+            interface("other::erc20::IERC20"),
+        );
+
+        let tokens = AbiParser::tokens_from_abi_string(&abi, &HashMap::new()).unwrap();
+        let names: Vec<(String, String, String)> =
+            CairoSrc5::interfaces(&utils::str_to_ident("MyToken"), &tokens)
+                .into_iter()
+                .map(|i| (i.path, i.const_name, i.method_name))
+                .collect();
+
+        assert_eq!(
+            names,
+            vec![
+                (
+                    "other::erc20::IERC20".to_string(),
+                    "MY_TOKEN_IERC20_INTERFACE_ID".to_string(),
+                    "supports_ierc20".to_string()
+                ),
+                (
+                    "pkg::IMyContract".to_string(),
+                    "MY_TOKEN_I_MY_CONTRACT_INTERFACE_ID".to_string(),
+                    "supports_i_my_contract".to_string()
+                ),
+                (
+                    "pkg::erc721::IERC721Metadata".to_string(),
+                    "MY_TOKEN_IERC721_METADATA_INTERFACE_ID".to_string(),
+                    "supports_ierc721_metadata".to_string()
+                ),
+            ]
+        );
+    }
+}
@@ -1,15 +1,22 @@
-use cainome_parser::tokens::{Composite, Token};
+use cainome_parser::tokens::Composite;
 use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
 use syn::Ident;
 
 use crate::expand::types::CairoToRust;
 use crate::expand::utils;
+use crate::{BytesEncoding, SerdeRadix};
 
 pub struct CairoStruct;
 
 impl CairoStruct {
-    pub fn expand_decl(composite: &Composite, derives: &[String]) -> TokenStream2 {
+    pub fn expand_decl(
+        composite: &Composite,
+        derives: &[String],
+        arbitrary: bool,
+        serde_radix: SerdeRadix,
+        serde_bytes: BytesEncoding,
+    ) -> TokenStream2 {
         if composite.is_builtin() {
             return quote!();
         }
@@ -18,23 +25,18 @@ impl CairoStruct {
 
         let mut members: Vec<TokenStream2> = vec![];
         for inner in &composite.inners {
-            let name = utils::str_to_ident(&inner.name);
+            let name = member_name(&inner.name);
             let ty = utils::str_to_type(&inner.token.to_rust_type());
 
-            let serde = utils::serde_hex_derive(&inner.token.to_rust_type());
-
-            // r#{name} is not a valid identifier, thus we can't create an ident.
-            // And with proc macro 2, we cannot do `quote!(r##name)`.
-            // TODO: this needs to be done more elegantly...
-            if &inner.name == "type" {
-                members.push(quote!(#serde pub r#type: #ty));
-            } else if &inner.name == "move" {
-                members.push(quote!(#serde pub r#move: #ty));
-            } else if &inner.name == "final" {
-                members.push(quote!(#serde pub r#final: #ty));
-            } else {
-                members.push(quote!(#serde pub #name: #ty));
-            }
+            let serde = utils::serde_hex_derive(&inner.token.to_rust_type(), serde_radix);
+            let bytes = utils::serde_bytes_derive(&inner.token.to_rust_type(), serde_bytes);
+            let schema = utils::schema_derive(
+                &inner.token.to_rust_type(),
+                derives,
+                Some((serde_radix, serde_bytes)),
+            );
+
+            members.push(quote!(#serde #bytes #schema pub #name: #ty));
         }
 
         let mut internal_derives = vec![];
@@ -43,6 +45,17 @@ impl CairoStruct {
             internal_derives.push(utils::str_to_type(d));
         }
 
+        let arbitrary_derive = if arbitrary {
+            utils::arbitrary_derive()
+        } else {
+            quote!()
+        };
+
+        let doc = format!(
+            "Binding of the Cairo struct `{}`.",
+            composite.type_path_no_generic()
+        );
+
         if composite.is_generic() {
             let gen_args: Vec<Ident> = composite
                 .generic_args
@@ -57,14 +70,18 @@ impl CairoStruct {
             // Those phantom fields are ignored by serde.
 
             quote! {
+                #[doc = #doc]
                 #[derive(#(#internal_derives,)*)]
+                #arbitrary_derive
                 pub struct #struct_name<#(#gen_args),*> {
                     #(#members),*
                 }
             }
         } else {
             quote! {
+                #[doc = #doc]
                 #[derive(#(#internal_derives,)*)]
+                #arbitrary_derive
                 pub struct #struct_name {
                     #(#members),*
                 }
@@ -80,90 +97,27 @@ impl CairoStruct {
         let struct_name = utils::str_to_ident(&composite.type_name_or_alias());
         let struct_name_str = utils::str_to_litstr(&composite.type_name_or_alias());
 
-        let mut sizes: Vec<TokenStream2> = vec![];
-        let mut sers: Vec<TokenStream2> = vec![];
-        let mut desers: Vec<TokenStream2> = vec![];
-        let mut names: Vec<TokenStream2> = vec![];
+        let ccs = utils::cainome_cairo_serde();
+
+        let mut fields: Vec<TokenStream2> = vec![];
 
         for inner in &composite.inners {
-            let name = utils::str_to_ident(&inner.name);
+            let name = member_name(&inner.name);
             let ty = utils::str_to_type(&inner.token.to_rust_type_path());
 
-            // Tuples type used as rust type path item path must be surrounded
-            // by angle brackets.
-            let ty_punctuated = match inner.token {
-                Token::Tuple(_) => quote!(<#ty>),
-                _ => quote!(#ty),
-            };
-
-            // r#{name} is not a valid identifier, thus we can't create an ident.
-            // And with proc macro 2, we cannot do `quote!(r##name)`.
-            // TODO: this needs to be done more elegantly...
-            if &inner.name == "type" {
-                names.push(quote!(r#type));
-
-                sizes.push(quote! {
-                    __size += #ty_punctuated::cairo_serialized_size(&__rust.r#type);
-                });
-
-                sers.push(quote!(__out.extend(#ty_punctuated::cairo_serialize(&__rust.r#type));));
-
-                desers.push(quote! {
-                    let r#type = #ty_punctuated::cairo_deserialize(__felts, __offset)?;
-                    __offset += #ty_punctuated::cairo_serialized_size(&r#type);
-                });
-            } else if &inner.name == "move" {
-                names.push(quote!(r#move));
-
-                sizes.push(quote! {
-                    __size += #ty_punctuated::cairo_serialized_size(&__rust.r#move);
-                });
-
-                sers.push(quote!(__out.extend(#ty_punctuated::cairo_serialize(&__rust.r#move));));
-
-                desers.push(quote! {
-                    let r#move = #ty_punctuated::cairo_deserialize(__felts, __offset)?;
-                    __offset += #ty_punctuated::cairo_serialized_size(&r#move);
-                });
-            } else if &inner.name == "final" {
-                names.push(quote!(r#final));
-
-                sizes.push(quote! {
-                    __size += #ty_punctuated::cairo_serialized_size(&__rust.r#final);
-                });
-
-                sers.push(quote!(__out.extend(#ty_punctuated::cairo_serialize(&__rust.r#final));));
-
-                desers.push(quote! {
-                    let r#final = #ty_punctuated::cairo_deserialize(__felts, __offset)?;
-                    __offset += #ty_punctuated::cairo_serialized_size(&r#final);
-                });
-            } else {
-                names.push(quote!(#name));
-
-                sizes.push(quote! {
-                    __size += #ty_punctuated::cairo_serialized_size(&__rust.#name);
-                });
-
-                sers.push(quote!(__out.extend(#ty_punctuated::cairo_serialize(&__rust.#name));));
-
-                desers.push(quote! {
-                    let #name = #ty_punctuated::cairo_deserialize(__felts, __offset)?;
-                    __offset += #ty_punctuated::cairo_serialized_size(&#name);
-                });
-            }
+            fields.push(quote!(#name: #ty));
         }
 
-        let ccs = utils::cainome_cairo_serde();
         let snrs_types = utils::snrs_types();
-        let snrs_utils = utils::snrs_utils();
+        let size_arg = utils::serialized_size_arg(composite);
 
         let event_impl = if composite.is_event {
+            let event_selector = utils::event_selector(&composite.type_name_or_alias());
+
             quote! {
                 impl #struct_name {
                     pub fn event_selector() -> #snrs_types::Felt {
-                        // Ok to unwrap since the event name comes from the ABI, which is already validated.
-                        #snrs_utils::get_selector_from_name(#struct_name_str).unwrap()
+                        #event_selector
                     }
 
                     pub fn event_name() -> &'static str {
@@ -200,31 +154,24 @@ impl CairoStruct {
 
                 #rust_type
 
-                const SERIALIZED_SIZE: std::option::Option<usize> = None;
-
-                #[inline]
-                fn cairo_serialized_size(__rust: &Self::RustType) -> usize {
-                    let mut __size = 0;
-                    #(#sizes)*
-                    __size
-                }
-
-                fn cairo_serialize(__rust: &Self::RustType) -> Vec<#snrs_types::Felt> {
-                    let mut __out: Vec<#snrs_types::Felt> = vec![];
-                    #(#sers)*
-                    __out
-                }
-
-                fn cairo_deserialize(__felts: &[#snrs_types::Felt], __offset: usize) -> #ccs::Result<Self::RustType> {
-                    let mut __offset = __offset;
-                    #(#desers)*
-                    Ok(#struct_name {
-                        #(#names),*
-                    })
-                }
+                #ccs::cairo_serde_struct_body!(#struct_name { #(#fields),* } #size_arg);
             }
 
             #event_impl
         }
     }
 }
+
+/// Returns the identifier of a struct member, using a raw identifier
+/// for the names that are rust keywords.
+pub(crate) fn member_name(name: &str) -> TokenStream2 {
+    match name {
+        "type" => quote!(r#type),
+        "move" => quote!(r#move),
+        "final" => quote!(r#final),
+        _ => {
+            let name = utils::str_to_ident(name);
+            quote!(#name)
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use cainome_parser::tokens::Token;
+use cainome_parser::type_mapping;
 
 use super::utils;
 
@@ -11,11 +12,14 @@ pub trait CairoToRust {
 impl CairoToRust for Token {
     fn to_rust_type(&self) -> String {
         match self {
-            Token::CoreBasic(t) => basic_types_to_rust(&t.type_name()),
+            Token::CoreBasic(t) => builtin_to_rust(&t.type_path).unwrap_or_else(|| t.type_name()),
             Token::Array(t) => {
                 if t.is_legacy {
                     let ccsp = utils::cainome_cairo_serde_path();
                     format!("{}::CairoArrayLegacy<{}>", ccsp, t.inner.to_rust_type())
+                } else if t.is_span() {
+                    let ccsp = utils::cainome_cairo_serde_path();
+                    format!("{}::CairoSpan<{}>", ccsp, t.inner.to_rust_type())
                 } else {
                     format!("Vec<{}>", t.inner.to_rust_type())
                 }
@@ -35,12 +39,7 @@ impl CairoToRust for Token {
                 s
             }
             Token::Composite(c) => {
-                let mut s = c.type_name_or_alias();
-
-                let (type_name, is_builtin) = builtin_composite_to_rust(&s);
-                if is_builtin {
-                    s = type_name;
-                }
+                let mut s = builtin_to_rust(&c.type_path).unwrap_or_else(|| c.type_name_or_alias());
 
                 if c.is_generic() {
                     s.push('<');
@@ -62,7 +61,7 @@ impl CairoToRust for Token {
 
     fn to_rust_type_path(&self) -> String {
         match self {
-            Token::CoreBasic(t) => basic_types_to_rust(&t.type_name()),
+            Token::CoreBasic(t) => builtin_to_rust(&t.type_path).unwrap_or_else(|| t.type_name()),
             Token::Array(t) => {
                 if t.is_legacy {
                     let ccsp = utils::cainome_cairo_serde_path();
@@ -71,6 +70,9 @@ impl CairoToRust for Token {
                         ccsp,
                         t.inner.to_rust_type_path()
                     )
+                } else if t.is_span() {
+                    let ccsp = utils::cainome_cairo_serde_path();
+                    format!("{}::CairoSpan::<{}>", ccsp, t.inner.to_rust_type_path())
                 } else {
                     format!("Vec::<{}>", t.inner.to_rust_type_path())
                 }
@@ -88,12 +90,7 @@ impl CairoToRust for Token {
                 s
             }
             Token::Composite(c) => {
-                let mut s = c.type_name_or_alias();
-
-                let (type_name, is_builtin) = builtin_composite_to_rust(&s);
-                if is_builtin {
-                    s = type_name;
-                }
+                let mut s = builtin_to_rust(&c.type_path).unwrap_or_else(|| c.type_name_or_alias());
 
                 if c.is_generic() {
                     s.push_str("::<");
@@ -114,35 +111,15 @@ impl CairoToRust for Token {
     }
 }
 
-fn basic_types_to_rust(type_name: &str) -> String {
+/// Maps the builtin types with the Rust column of the shared table,
+/// see [`cainome_parser::type_mapping`].
+fn builtin_to_rust(type_path: &str) -> Option<String> {
     let ccsp = utils::cainome_cairo_serde_path();
     let snrs_types = utils::starknet_rs_types_path();
 
-    match type_name {
-        "ClassHash" => format!("{ccsp}::ClassHash"),
-        "ContractAddress" => format!("{ccsp}::ContractAddress"),
-        "EthAddress" => format!("{ccsp}::EthAddress"),
-        "felt252" => format!("{snrs_types}::Felt"),
-        "felt" => format!("{snrs_types}::Felt"),
-        "bytes31" => format!("{ccsp}::Bytes31"),
-        "ByteArray" => format!("{ccsp}::ByteArray"),
-        "NonZero" => format!("{ccsp}::NonZero"),
-        "U256" => format!("{ccsp}::U256"),
-        _ => type_name.to_string(),
-    }
-}
-
-fn builtin_composite_to_rust(type_name: &str) -> (String, bool) {
-    let ccsp = utils::cainome_cairo_serde_path();
-    let snrs_types = utils::starknet_rs_types_path();
-
-    match type_name {
-        "EthAddress" => (format!("{ccsp}::EthAddress"), true),
-        "ByteArray" => (format!("{ccsp}::ByteArray"), true),
-        "NonZero" => (format!("{ccsp}::NonZero"), true),
-        "U256" => (format!("{ccsp}::U256"), true),
-        // <https://github.com/starkware-libs/cairo/blob/35b299291fd7819f75409fb303ece7d30e4adb19/corelib/src/internal/bounded_int.cairo#L5>
-        "BoundedInt" => (format!("{snrs_types}::Felt"), true),
-        _ => (type_name.to_string(), false),
-    }
+    type_mapping::builtin_type(type_path).map(|b| {
+        b.rust
+            .replace("{ccs}", &ccsp)
+            .replace("{snrs}", &snrs_types)
+    })
 }
@@ -1,10 +1,63 @@
 //! Utils function for expansion.
+use cainome_parser::tokens::{Composite, Token};
+use cainome_parser::{selectors, size};
+use convert_case::{Boundary, Case, Casing};
 use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
+use starknet::core::types::Felt;
 use syn::{Ident, LitInt, LitStr, Type};
 
+use super::types::CairoToRust;
+use crate::{BytesEncoding, SerdeRadix};
+
+/// The keywords of Rust, strict and reserved, which can't be used as identifiers.
+pub const RUST_KEYWORDS: [&str; 51] = [
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+    "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// The keywords which can't be raw identifiers.
+const NON_RAW_KEYWORDS: [&str; 5] = ["_", "crate", "self", "super", "Self"];
+
+/// Converts a name (from the ABI or given by the user) into an identifier.
+///
+/// * A character not allowed in an identifier is replaced by `_`.
+/// * A name starting with a digit is prefixed with `_`.
+/// * A keyword is a raw identifier (`r#type`), except the keywords which can't be raw
+///   (like `self`), suffixed with `_` like the contract names sanitized by the CLI.
+///
+/// A name starting with `r#` (like the name of a raw identifier formatted into an other
+/// name, as `r#matchReader`) is converted without it.
 pub fn str_to_ident(str_in: &str) -> Ident {
-    Ident::new(str_in, proc_macro2::Span::call_site())
+    let span = proc_macro2::Span::call_site();
+    let name = str_in.strip_prefix("r#").unwrap_or(str_in);
+
+    let mut name: String = name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        name.insert(0, '_');
+    }
+
+    if NON_RAW_KEYWORDS.contains(&name.as_str()) {
+        name.push('_');
+        Ident::new(&name, span)
+    } else if RUST_KEYWORDS.contains(&name.as_str()) {
+        Ident::new_raw(&name, span)
+    } else {
+        Ident::new(&name, span)
+    }
 }
 
 pub fn str_to_type(str_in: &str) -> Type {
@@ -23,10 +76,6 @@ pub fn snrs_types() -> Type {
     str_to_type("starknet::core::types")
 }
 
-pub fn snrs_utils() -> Type {
-    str_to_type("starknet::core::utils")
-}
-
 pub fn snrs_accounts() -> Type {
     str_to_type("starknet::accounts")
 }
@@ -49,6 +98,89 @@ pub fn starknet_rs_types_path() -> String {
     String::from("starknet::core::types")
 }
 
+/// Returns the type of a function input, taken by reference.
+///
+/// The `Span<T>` inputs are taken as slices, which allows to give a borrowed slice
+/// (or a `&Vec<T>`) without copying it.
+pub fn input_type(token: &Token) -> TokenStream2 {
+    match token {
+        Token::Array(a) if a.is_span() => {
+            let inner = str_to_type(&a.inner.to_rust_type());
+            quote!(&[#inner])
+        }
+        _ => {
+            let ty = str_to_type(&token.to_rust_type_path());
+            quote!(&#ty)
+        }
+    }
+}
+
+/// Returns the statement serializing the function input `name` at the end of `buffer`.
+pub fn input_serialization(name: &Ident, token: &Token, buffer: &Ident) -> TokenStream2 {
+    let ty = str_to_type(&token.to_rust_type_path());
+
+    match token {
+        Token::Array(a) if a.is_span() => {
+            quote!(#ty::cairo_serialize_slice_to(#name, &mut #buffer);)
+        }
+        // Tuples type used as rust type path item path must be surrounded
+        // by angle brackets.
+        Token::Tuple(_) => quote!(<#ty>::cairo_serialize_to(#name, &mut #buffer);),
+        _ => quote!(#ty::cairo_serialize_to(#name, &mut #buffer);),
+    }
+}
+
+/// Returns the name of an item generated once per contract (like the `consts` module),
+/// prefixed by the name of the contract: several contracts can then be expanded in the
+/// same module. The digits are kept with the letters (`ERC20_...` for `Erc20`).
+///
+/// # Arguments
+///
+/// * `contract_name` - The name of the contract.
+/// * `name` - The name of the item, in the `case` case.
+/// * `case` - The case of the item name, `Snake` or `UpperSnake`.
+pub fn contract_item_name(contract_name: &Ident, name: &str, case: Case) -> String {
+    let contract_name = contract_name.to_string();
+    let contract_name = contract_name.strip_prefix("r#").unwrap_or(&contract_name);
+
+    let contract_name = contract_name
+        .from_case(Case::Pascal)
+        .without_boundaries(&[Boundary::UpperDigit, Boundary::LowerDigit])
+        .to_case(case);
+
+    format!("{}_{}", contract_name, name)
+}
+
+/// Derive of `arbitrary::Arbitrary`, only enabled with the `arbitrary` feature
+/// of the crate including the bindings.
+pub fn arbitrary_derive() -> TokenStream2 {
+    quote!(#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))])
+}
+
+/// Expands a felt value as a constant expression.
+pub fn felt_const(felt: &Felt) -> TokenStream2 {
+    let snrs_types = snrs_types();
+    let hex = str_to_litstr(&format!("{:#x}", felt));
+
+    quote!(#snrs_types::Felt::from_hex_unchecked(#hex))
+}
+
+/// Expands the precomputed selector of a function.
+pub fn function_selector(name: &str) -> TokenStream2 {
+    let selector = selectors::function_selector(name)
+        .unwrap_or_else(|e| panic!("Invalid selector for function {}: {}", name, e));
+
+    felt_const(&selector)
+}
+
+/// Expands the precomputed selector of an event.
+pub fn event_selector(name: &str) -> TokenStream2 {
+    let selector = selectors::event_selector(name)
+        .unwrap_or_else(|e| panic!("Invalid selector for event {}: {}", name, e));
+
+    felt_const(&selector)
+}
+
 /// Expands the implementation line with generic types.
 pub fn impl_with_gen_args(entity_name: &Ident, gen_args: &Vec<Ident>) -> TokenStream2 {
     let gen_args_rust: Vec<Ident> = gen_args
@@ -89,6 +221,9 @@ enum SerdeHexType {
     Single,
     Tuple(usize),
     Vec,
+    /// Felts, only serialized with a custom radix in decimal.
+    Felt,
+    FeltVec,
 }
 
 impl SerdeHexType {
@@ -97,20 +232,43 @@ impl SerdeHexType {
     }
 }
 
-/// Serde derive for hex serialization of struct member or enum variant.
-/// In the case of tuples, all the elements will be serialized as hex.
-pub fn serde_hex_derive(ty: &str) -> TokenStream2 {
-    let serde_single = format!("{}::serialize_as_hex", cainome_cairo_serde_path());
-    let serde_vec = format!("{}::serialize_as_hex_vec", cainome_cairo_serde_path());
-    let serde_tuple_2 = format!("{}::serialize_as_hex_t2", cainome_cairo_serde_path());
-    let serde_tuple_3 = format!("{}::serialize_as_hex_t3", cainome_cairo_serde_path());
-
-    let deser_single = format!("{}::deserialize_from_hex", cainome_cairo_serde_path());
-    let deser_vec = format!("{}::deserialize_from_hex_vec", cainome_cairo_serde_path());
-    let deser_tuple_2 = format!("{}::deserialize_from_hex_t2", cainome_cairo_serde_path());
-    let deser_tuple_3 = format!("{}::deserialize_from_hex_t3", cainome_cairo_serde_path());
+/// Returns the `size = ...` argument of the `CairoSerde` body macros,
+/// empty if the serialized size of the composite is not static.
+pub fn serialized_size_arg(composite: &Composite) -> TokenStream2 {
+    match size::composite_static_size(composite) {
+        Some(size) => quote!(, size = ::std::option::Option::Some(#size)),
+        None => quote!(),
+    }
+}
 
-    let serde_hex = is_serde_hex_int(ty);
+/// Serde derive for hex (or decimal) serialization of struct member or enum variant.
+/// In the case of tuples, all the elements will be serialized with the radix.
+///
+/// With the decimal radix, the felts are also serialized as decimal strings. In all cases,
+/// the hex and decimal strings are accepted when deserializing.
+pub fn serde_hex_derive(ty: &str, radix: SerdeRadix) -> TokenStream2 {
+    let ccs = cainome_cairo_serde_path();
+    let ser = match radix {
+        SerdeRadix::Hex => "serialize_as_hex",
+        SerdeRadix::Decimal => "serialize_as_dec",
+    };
+
+    let serde_single = format!("{ccs}::{ser}");
+    let serde_vec = format!("{ccs}::{ser}_vec");
+    let serde_tuple_2 = format!("{ccs}::{ser}_t2");
+    let serde_tuple_3 = format!("{ccs}::{ser}_t3");
+
+    let deser_single = format!("{ccs}::deserialize_from_hex");
+    let deser_vec = format!("{ccs}::deserialize_from_hex_vec");
+    let deser_tuple_2 = format!("{ccs}::deserialize_from_hex_t2");
+    let deser_tuple_3 = format!("{ccs}::deserialize_from_hex_t3");
+    let deser_felt = format!("{ccs}::deserialize_felt_from_hex_or_dec");
+    let deser_felt_vec = format!("{ccs}::deserialize_felt_from_hex_or_dec_vec");
+
+    let serde_hex = match radix {
+        SerdeRadix::Hex => is_serde_hex_int(ty),
+        SerdeRadix::Decimal => is_serde_dec(ty),
+    };
 
     match serde_hex {
         SerdeHexType::None => quote!(),
@@ -126,10 +284,182 @@ pub fn serde_hex_derive(ty: &str) -> TokenStream2 {
         SerdeHexType::Vec => quote! {
             #[serde(serialize_with = #serde_vec, deserialize_with = #deser_vec)]
         },
-        _ => panic!("Unsupported type {} for serde_hex", ty),
+        SerdeHexType::Felt => quote! {
+            #[serde(serialize_with = #serde_single, deserialize_with = #deser_felt)]
+        },
+        SerdeHexType::FeltVec => quote! {
+            #[serde(serialize_with = #serde_vec, deserialize_with = #deser_felt_vec)]
+        },
+        // Wider tuples don't have hex serialization helpers, the default
+        // serde implementation of the tuple is used instead.
+        SerdeHexType::Tuple(_) => quote!(),
     }
 }
 
+/// Serde derive for the hex or base64 serialization of the bytes (`Vec<u8>`,
+/// `CairoSpan<u8>` and `ByteArray`) of a struct member or enum variant.
+pub fn serde_bytes_derive(ty: &str, encoding: BytesEncoding) -> TokenStream2 {
+    let ccs = cainome_cairo_serde_path();
+
+    if vec_inner_type(ty) != Some("u8") && ty != format!("{ccs}::ByteArray") {
+        return quote!();
+    }
+
+    let (ser, deser) = match encoding {
+        BytesEncoding::Array => return quote!(),
+        BytesEncoding::Hex => ("serialize_bytes_as_hex", "deserialize_bytes_from_hex"),
+        BytesEncoding::Base64 => ("serialize_bytes_as_base64", "deserialize_bytes_from_base64"),
+    };
+
+    let ser = format!("{ccs}::{ser}");
+    let deser = format!("{ccs}::{deser}");
+
+    quote! {
+        #[serde(serialize_with = #ser, deserialize_with = #deser)]
+    }
+}
+
+/// Schema derive (`schemars::JsonSchema` and `utoipa::ToSchema`) of a struct member
+/// or enum variant, empty if none of those derives is requested.
+///
+/// The member is documented with the type of its serialized value, where the felts
+/// (and the types serialized as felts) are strings. Hence, the `Felt` of
+/// `starknet-rs`, which doesn't implement the schema traits, is never referenced.
+///
+/// # Arguments
+///
+/// * `ty` - The rust type of the member.
+/// * `derives` - The derives of the type.
+/// * `serde` - The radix and the bytes encoding of the member, if the member
+///             is serialized with the helpers of [`serde_hex_derive`] and [`serde_bytes_derive`].
+pub fn schema_derive(
+    ty: &str,
+    derives: &[String],
+    serde: Option<(SerdeRadix, BytesEncoding)>,
+) -> TokenStream2 {
+    let schemars = has_derive(derives, "JsonSchema");
+    let utoipa = has_derive(derives, "ToSchema");
+
+    if !schemars && !utoipa {
+        return quote!();
+    }
+
+    let schema_ty = match serde {
+        Some((radix, encoding)) => serialized_schema_type(ty, radix, encoding),
+        None => schema_type(ty),
+    };
+
+    if schema_ty == ty {
+        return quote!();
+    }
+
+    let schemars_attr = if schemars {
+        quote!(#[schemars(with = #schema_ty)])
+    } else {
+        quote!()
+    };
+
+    let utoipa_attr = if utoipa {
+        let value_type = str_to_type(&schema_ty);
+        quote!(#[schema(value_type = #value_type)])
+    } else {
+        quote!()
+    };
+
+    quote!(#schemars_attr #utoipa_attr)
+}
+
+/// Returns true if the derive named `name` (whatever its path) is in the derives.
+fn has_derive(derives: &[String], name: &str) -> bool {
+    derives.iter().any(|d| {
+        let d = d.replace(char::is_whitespace, "");
+        d.rsplit("::").next() == Some(name)
+    })
+}
+
+/// Returns the schema type of a member serialized with the serde helpers,
+/// where the integers serialized with a radix and the encoded bytes are strings.
+fn serialized_schema_type(ty: &str, radix: SerdeRadix, encoding: BytesEncoding) -> String {
+    if !serde_bytes_derive(ty, encoding).is_empty() {
+        return "String".to_string();
+    }
+
+    let serde_hex = match radix {
+        SerdeRadix::Hex => is_serde_hex_int(ty),
+        SerdeRadix::Decimal => is_serde_dec(ty),
+    };
+
+    match serde_hex {
+        SerdeHexType::Single | SerdeHexType::Felt => "String".to_string(),
+        SerdeHexType::Vec | SerdeHexType::FeltVec => "Vec<String>".to_string(),
+        SerdeHexType::Tuple(n @ (2 | 3)) => format!("({})", vec!["String"; n].join(", ")),
+        SerdeHexType::Tuple(_) | SerdeHexType::None => schema_type(ty),
+    }
+}
+
+/// Returns the schema type of a type, where the felts and the types serialized
+/// as strings are replaced by `String`, and the cainome wrappers by the wrapped type.
+fn schema_type(ty: &str) -> String {
+    let ccs = cainome_cairo_serde_path();
+    let snrs = starknet_rs_types_path();
+
+    let mut schema_ty = strip_wrapper(ty, &format!("{ccs}::NonZero<"));
+
+    for string_ty in [
+        format!("{snrs}::Felt"),
+        format!("{ccs}::ContractAddress"),
+        format!("{ccs}::ClassHash"),
+        format!("{ccs}::EthAddress"),
+        format!("{ccs}::Bytes31"),
+        format!("{ccs}::U256"),
+    ] {
+        schema_ty = schema_ty.replace(&string_ty, "String");
+    }
+
+    for vec_ty in [
+        format!("{ccs}::CairoSpan<"),
+        format!("{ccs}::CairoArrayLegacy<"),
+    ] {
+        schema_ty = schema_ty.replace(&vec_ty, "Vec<");
+    }
+
+    schema_ty
+}
+
+/// Replaces all the `Wrapper<T>` of a type by `T`, `wrapper` being the path
+/// of the wrapper followed by `<`.
+fn strip_wrapper(ty: &str, wrapper: &str) -> String {
+    let mut ty = ty.to_string();
+
+    while let Some(start) = ty.find(wrapper) {
+        let inner_start = start + wrapper.len();
+        let mut depth = 1;
+        let mut end = inner_start;
+
+        for (i, c) in ty[inner_start..].char_indices() {
+            match c {
+                '<' => depth += 1,
+                '>' => depth -= 1,
+                _ => {}
+            }
+
+            if depth == 0 {
+                end = inner_start + i;
+                break;
+            }
+        }
+
+        ty = format!(
+            "{}{}{}",
+            &ty[..start],
+            &ty[inner_start..end],
+            &ty[end + 1..]
+        );
+    }
+
+    ty
+}
+
 /// To simplify the serde interop with client in javascript,
 /// we use the hex format for all the types greater than u32.
 /// IEEE 754 standard for floating-point arithmetic,
@@ -156,6 +486,30 @@ fn is_serde_hex_int(ty: &str) -> SerdeHexType {
     SerdeHexType::None
 }
 
+/// Returns how the type is serialized with the decimal radix: as the hex integers,
+/// plus the felts (alone, in vectors, or in tuples).
+fn is_serde_dec(ty: &str) -> SerdeHexType {
+    let felt = format!("{}::Felt", starknet_rs_types_path());
+
+    if ty == felt {
+        return SerdeHexType::Felt;
+    }
+
+    if vec_inner_type(ty) == Some(felt.as_str()) {
+        return SerdeHexType::FeltVec;
+    }
+
+    if ty.starts_with('(') && ty.ends_with(')') {
+        let elements: Vec<&str> = ty[1..ty.len() - 1].split(',').collect();
+
+        if elements.iter().any(|t| t.trim() == felt) {
+            return SerdeHexType::Tuple(elements.len());
+        }
+    }
+
+    is_serde_hex_int(ty)
+}
+
 /// Checks if the type is a tuple of integers that should be serialized as hex.
 fn is_serde_hex_tuple(ty: &str) -> SerdeHexType {
     if ty.starts_with('(') && ty.ends_with(')') {
@@ -177,9 +531,7 @@ fn is_serde_hex_tuple(ty: &str) -> SerdeHexType {
 
 /// Checks if the type is a vector of integers that should be serialized as hex.
 fn is_serde_hex_vec(ty: &str) -> SerdeHexType {
-    if ty.starts_with("Vec<") && ty.ends_with('>') {
-        let inner_type = &ty[4..ty.len() - 1];
-
+    if let Some(inner_type) = vec_inner_type(ty) {
         if !is_serde_hex_int(inner_type).is_none() {
             return SerdeHexType::Vec;
         } else {
@@ -190,10 +542,101 @@ fn is_serde_hex_vec(ty: &str) -> SerdeHexType {
     SerdeHexType::None
 }
 
+/// Returns the type of the elements of a `Vec` (array) or a `CairoSpan` (span).
+fn vec_inner_type(ty: &str) -> Option<&str> {
+    let span_prefix = format!("{}::CairoSpan<", cainome_cairo_serde_path());
+
+    ty.strip_prefix("Vec<")
+        .or_else(|| ty.strip_prefix(span_prefix.as_str()))
+        .and_then(|t| t.strip_suffix('>'))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_str_to_ident() {
+        assert_eq!(str_to_ident("my_contract").to_string(), "my_contract");
+        assert_eq!(str_to_ident("match").to_string(), "r#match");
+        assert_eq!(str_to_ident("r#match").to_string(), "r#match");
+        assert_eq!(str_to_ident("r#matchReader").to_string(), "matchReader");
+        assert_eq!(str_to_ident("self").to_string(), "self_");
+        assert_eq!(str_to_ident("Self").to_string(), "Self_");
+        assert_eq!(str_to_ident("1inch").to_string(), "_1inch");
+        assert_eq!(str_to_ident("my-contract.v2").to_string(), "my_contract_v2");
+        assert_eq!(str_to_ident("").to_string(), "__");
+    }
+
+    #[test]
+    fn test_function_selector() {
+        assert_eq!(
+            function_selector("transfer").to_string(),
+            quote!(starknet::core::types::Felt::from_hex_unchecked(
+                "0x83afd3f4caedc6eebf44246fe54e38c95e3179a5ec9ea81740eca5b482d12e"
+            ))
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn test_arbitrary_derive() {
+        assert_eq!(
+            arbitrary_derive().to_string(),
+            quote!(#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]).to_string()
+        );
+    }
+
+    #[test]
+    fn test_schema_derive() {
+        let derives = vec!["Debug".to_string(), "schemars :: JsonSchema".to_string()];
+        let felt = "starknet::core::types::Felt";
+
+        assert!(schema_derive(felt, &["Debug".to_string()], None).is_empty());
+        assert!(schema_derive("u32", &derives, None).is_empty());
+        assert_eq!(
+            schema_derive(felt, &derives, None).to_string(),
+            quote!(#[schemars(with = "String")]).to_string()
+        );
+        assert_eq!(
+            schema_derive(
+                "u64",
+                &["ToSchema".to_string()],
+                Some((SerdeRadix::Hex, BytesEncoding::Array))
+            )
+            .to_string(),
+            quote!(#[schema(value_type = String)]).to_string()
+        );
+    }
+
+    #[test]
+    fn test_schema_type() {
+        assert_eq!(
+            schema_type("Option<cainome::cairo_serde::NonZero<starknet::core::types::Felt>>"),
+            "Option<String>"
+        );
+        assert_eq!(
+            schema_type("cainome::cairo_serde::CairoSpan<(cainome::cairo_serde::U256, u8)>"),
+            "Vec<(String, u8)>"
+        );
+        assert_eq!(
+            schema_type("cainome::cairo_serde::ByteArray"),
+            "cainome::cairo_serde::ByteArray"
+        );
+        assert_eq!(
+            serialized_schema_type("(u64, u8)", SerdeRadix::Hex, BytesEncoding::Array),
+            "(String, String)"
+        );
+        assert_eq!(
+            serialized_schema_type("Vec<u8>", SerdeRadix::Hex, BytesEncoding::Base64),
+            "String"
+        );
+        assert_eq!(
+            serialized_schema_type("Vec<u8>", SerdeRadix::Hex, BytesEncoding::Array),
+            "Vec<u8>"
+        );
+    }
+
     #[test]
     fn test_is_serde_hex_int() {
         assert_eq!(is_serde_hex_int("u128"), SerdeHexType::Single);
@@ -218,6 +661,103 @@ mod tests {
         assert_eq!(is_serde_hex_tuple("(felt252, u32)"), SerdeHexType::None);
     }
 
+    #[test]
+    fn test_serde_hex_derive_wide_tuple() {
+        let ty = format!("({})", vec!["u64"; 16].join(", "));
+        assert_eq!(is_serde_hex_tuple(&ty), SerdeHexType::Tuple(16));
+        assert!(serde_hex_derive(&ty, SerdeRadix::Hex).is_empty());
+    }
+
+    #[test]
+    fn test_is_serde_dec() {
+        assert_eq!(
+            is_serde_dec("starknet::core::types::Felt"),
+            SerdeHexType::Felt
+        );
+        assert_eq!(
+            is_serde_dec("Vec<starknet::core::types::Felt>"),
+            SerdeHexType::FeltVec
+        );
+        assert_eq!(
+            is_serde_dec("(starknet::core::types::Felt, u32)"),
+            SerdeHexType::Tuple(2)
+        );
+        assert_eq!(is_serde_dec("u128"), SerdeHexType::Single);
+        assert_eq!(is_serde_dec("Vec<u64>"), SerdeHexType::Vec);
+        assert_eq!(
+            is_serde_dec("cainome::cairo_serde::CairoSpan<starknet::core::types::Felt>"),
+            SerdeHexType::FeltVec
+        );
+        assert_eq!(
+            is_serde_hex_int("cainome::cairo_serde::CairoSpan<u128>"),
+            SerdeHexType::Vec
+        );
+        assert_eq!(is_serde_dec("u32"), SerdeHexType::None);
+        assert_eq!(
+            is_serde_dec("Vec<Vec<starknet::core::types::Felt>>"),
+            SerdeHexType::None
+        );
+    }
+
+    #[test]
+    fn test_serde_hex_derive_radix() {
+        let felt = "starknet::core::types::Felt";
+
+        assert!(serde_hex_derive(felt, SerdeRadix::Hex).is_empty());
+        assert_eq!(
+            serde_hex_derive(felt, SerdeRadix::Decimal).to_string(),
+            quote! {
+                #[serde(
+                    serialize_with = "cainome::cairo_serde::serialize_as_dec",
+                    deserialize_with = "cainome::cairo_serde::deserialize_felt_from_hex_or_dec"
+                )]
+            }
+            .to_string()
+        );
+        assert_eq!(
+            serde_hex_derive("u128", SerdeRadix::Decimal).to_string(),
+            quote! {
+                #[serde(
+                    serialize_with = "cainome::cairo_serde::serialize_as_dec",
+                    deserialize_with = "cainome::cairo_serde::deserialize_from_hex"
+                )]
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn test_serde_bytes_derive() {
+        let byte_array = "cainome::cairo_serde::ByteArray";
+
+        assert!(serde_bytes_derive("Vec<u8>", BytesEncoding::Array).is_empty());
+        assert!(serde_bytes_derive("Vec<u16>", BytesEncoding::Hex).is_empty());
+        assert!(
+            !serde_bytes_derive("cainome::cairo_serde::CairoSpan<u8>", BytesEncoding::Hex)
+                .is_empty()
+        );
+        assert_eq!(
+            serde_bytes_derive("Vec<u8>", BytesEncoding::Hex).to_string(),
+            quote! {
+                #[serde(
+                    serialize_with = "cainome::cairo_serde::serialize_bytes_as_hex",
+                    deserialize_with = "cainome::cairo_serde::deserialize_bytes_from_hex"
+                )]
+            }
+            .to_string()
+        );
+        assert_eq!(
+            serde_bytes_derive(byte_array, BytesEncoding::Base64).to_string(),
+            quote! {
+                #[serde(
+                    serialize_with = "cainome::cairo_serde::serialize_bytes_as_base64",
+                    deserialize_with = "cainome::cairo_serde::deserialize_bytes_from_base64"
+                )]
+            }
+            .to_string()
+        );
+    }
+
     #[test]
     fn test_is_serde_hex_vec() {
         assert_eq!(is_serde_hex_vec("Vec<u128>"), SerdeHexType::Vec);
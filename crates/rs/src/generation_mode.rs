@@ -0,0 +1,77 @@
+/// Parts of the bindings to generate.
+
+/// The mode of generation of the bindings.
+///
+/// The types are always generated, with their `CairoSerde` implementations. The other modes
+/// trim the bindings for the consumers only decoding events (like indexers) or only encoding
+/// calldata (like relayers), which don't need the accounts and providers of `starknet-rs`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GenerationMode {
+    /// The contract and the reader, with the types and the events.
+    #[default]
+    Full,
+    /// The types and the decoding of the events, without the contract and the reader.
+    Decode,
+    /// The types and a `<Contract>Calls` struct encoding the calls to the externals,
+    /// without the contract, the reader, and the decoding of the events.
+    Encode,
+}
+
+impl GenerationMode {
+    /// Returns the name of the mode, as parsed (`full`, `decode` or `encode`).
+    pub fn name(&self) -> &'static str {
+        match self {
+            GenerationMode::Full => "full",
+            GenerationMode::Decode => "decode",
+            GenerationMode::Encode => "encode",
+        }
+    }
+
+    /// Returns whether the contract and the reader are generated.
+    pub fn has_contract(&self) -> bool {
+        *self == GenerationMode::Full
+    }
+
+    /// Returns whether the events are decoded from the emitted events.
+    pub fn has_events_decoding(&self) -> bool {
+        *self != GenerationMode::Encode
+    }
+
+    /// Returns whether the calls to the externals (and the messages to the L1 handlers)
+    /// are encoded.
+    pub fn has_calldata_encoding(&self) -> bool {
+        *self != GenerationMode::Decode
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseGenerationModeError {
+    invalid_value: String,
+}
+
+impl std::fmt::Display for ParseGenerationModeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Invalid generation mode '{}'. Supported values are 'full', 'decode' or 'encode'.",
+            self.invalid_value
+        )
+    }
+}
+
+impl std::error::Error for ParseGenerationModeError {}
+
+impl std::str::FromStr for GenerationMode {
+    type Err = ParseGenerationModeError;
+
+    fn from_str(input: &str) -> Result<GenerationMode, Self::Err> {
+        match input {
+            "full" => Ok(GenerationMode::Full),
+            "decode" => Ok(GenerationMode::Decode),
+            "encode" => Ok(GenerationMode::Encode),
+            _ => Err(ParseGenerationModeError {
+                invalid_value: input.to_string(),
+            }),
+        }
+    }
+}
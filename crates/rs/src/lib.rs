@@ -1,5 +1,5 @@
 use anyhow::Result;
-use cainome_parser::tokens::StateMutability;
+use cainome_parser::tokens::{Function, StateMutability, Token};
 use cainome_parser::{AbiParser, TokenizedAbi};
 use camino::Utf8PathBuf;
 use proc_macro2::TokenStream as TokenStream2;
@@ -9,12 +9,37 @@ use std::fmt;
 use std::fs;
 use std::io;
 
+mod artifact;
+mod consts;
+mod coverage;
 mod execution_version;
 mod expand;
+mod generation_mode;
+mod options;
+mod result_enums;
+mod serde_bytes;
+mod serde_radix;
+mod shared_types;
+mod tuple_structs;
+pub use artifact::EmbeddedClass;
+pub use consts::{check_consts, ContractConst};
+pub use coverage::{CoverageEntry, CoverageReport, CoverageStatus, EntryKind, SkipReason};
 pub use execution_version::{ExecutionVersion, ParseExecutionVersionError};
+pub use expand::utils::RUST_KEYWORDS;
+pub use generation_mode::{GenerationMode, ParseGenerationModeError};
+pub use options::ExpandOptions;
+pub use result_enums::{check_result_enums, ResultEnum};
+pub use serde_bytes::{BytesEncoding, ParseBytesEncodingError};
+pub use serde_radix::{ParseSerdeRadixError, SerdeRadix};
+pub use shared_types::SharedTypes;
+pub use tuple_structs::{check_tuple_structs, TupleStruct};
 
 use crate::expand::utils;
-use crate::expand::{CairoContract, CairoEnum, CairoEnumEvent, CairoFunction, CairoStruct};
+use crate::expand::{
+    CairoArtifact, CairoConstructor, CairoConsts, CairoContract, CairoConversion, CairoEnum,
+    CairoEnumEvent, CairoExample, CairoFunction, CairoL1Handler, CairoRoundtripTests,
+    CairoSharedTypes, CairoSrc5, CairoStruct,
+};
 
 ///Type-safe contract bindings generated by Abigen.
 #[derive(Clone)]
@@ -23,6 +48,8 @@ pub struct ContractBindings {
     pub name: String,
     /// Tokenized ABI written to a `[TokenStream2]`.
     pub tokens: TokenStream2,
+    /// Inner doc comments (`//!`) summarizing the ABI, written at the top of the file.
+    pub module_doc: String,
 }
 
 impl ContractBindings {
@@ -33,8 +60,8 @@ impl ContractBindings {
     /// * `file` - The path to the file to write the bindings to.
     pub fn write_to_file(&self, file: &str) -> io::Result<()> {
         let content = format!(
-            "// ****\n// Auto-generated by cainome do not edit.\n// ****\n\n#![allow(clippy::all)]\n#![allow(warnings)]\n\n{}",
-            self
+            "// ****\n// Auto-generated by cainome do not edit.\n// ****\n\n{}\n#![allow(clippy::all)]\n#![allow(warnings)]\n\n{}",
+            self.module_doc, self
         );
         fs::write(file, content)
     }
@@ -75,6 +102,34 @@ pub struct Abigen {
     pub derives: Vec<String>,
     /// Derives to be added to the generated contract.
     pub contract_derives: Vec<String>,
+    /// Derives to be added only to the generated types matching a pattern.
+    pub type_derives: HashMap<String, Vec<String>>,
+    /// Whether to generate the blocking facades, behind the `blocking` feature.
+    pub blocking: bool,
+    /// Whether to derive `arbitrary::Arbitrary` for the types, behind the `arbitrary` feature.
+    pub arbitrary: bool,
+    /// Number of parameters above which a function also takes its parameters as a struct.
+    pub params_struct_threshold: Option<usize>,
+    /// Whether to generate a `<contract>_prelude` module re-exporting the generated items.
+    pub prelude: bool,
+    /// Whether to generate the serialization round-trip tests of the types.
+    pub roundtrip_tests: bool,
+    /// The radix of the integers and felts serialized with serde.
+    pub serde_radix: SerdeRadix,
+    /// The encoding of the bytes serialized with serde.
+    pub serde_bytes: BytesEncoding,
+    /// Whether to generate the SRC5 interface ids and the methods probing for them.
+    pub src5: bool,
+    /// Whether the externals can be sponsored by a paymaster.
+    pub paymaster: bool,
+    /// Constants of the contract, checked against their views.
+    pub consts: Vec<ContractConst>,
+    /// Fixed tuples generated as named structs.
+    pub tuple_structs: Vec<TupleStruct>,
+    /// Enums of the contract mapped to `Result`.
+    pub result_enums: Vec<ResultEnum>,
+    /// The parts of the bindings to generate.
+    pub generation_mode: GenerationMode,
 }
 
 impl Abigen {
@@ -93,6 +148,20 @@ impl Abigen {
             execution_version: ExecutionVersion::V1,
             derives: vec![],
             contract_derives: vec![],
+            type_derives: HashMap::new(),
+            blocking: false,
+            arbitrary: false,
+            params_struct_threshold: None,
+            prelude: false,
+            roundtrip_tests: false,
+            serde_radix: SerdeRadix::Hex,
+            serde_bytes: BytesEncoding::Array,
+            src5: false,
+            paymaster: false,
+            consts: vec![],
+            tuple_structs: vec![],
+            result_enums: vec![],
+            generation_mode: GenerationMode::Full,
         }
     }
 
@@ -136,23 +205,205 @@ impl Abigen {
         self
     }
 
+    /// Sets the derives to be added only to the generated types matching a pattern.
+    ///
+    /// # Arguments
+    ///
+    /// * `type_derives` - Derives by type pattern, where `*` matches any sequence of
+    ///   characters. A pattern with `::` is matched against the full type path,
+    ///   otherwise against the generated type name.
+    pub fn with_type_derives(mut self, type_derives: HashMap<String, Vec<String>>) -> Self {
+        self.type_derives = type_derives;
+        self
+    }
+
+    /// Sets whether the blocking facades of the contract and the reader are generated.
+    /// The facades are only available with the `blocking` feature of the crate
+    /// including the bindings.
+    ///
+    /// # Arguments
+    ///
+    /// * `blocking` - Whether the blocking facades are generated.
+    pub fn with_blocking(mut self, blocking: bool) -> Self {
+        self.blocking = blocking;
+        self
+    }
+
+    /// Sets whether `arbitrary::Arbitrary` is derived for the generated types.
+    /// The derives are only available with the `arbitrary` feature of the crate
+    /// including the bindings, which must depend on `arbitrary` with the `derive` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `arbitrary` - Whether `Arbitrary` is derived.
+    pub fn with_arbitrary(mut self, arbitrary: bool) -> Self {
+        self.arbitrary = arbitrary;
+        self
+    }
+
+    /// Sets the number of parameters above which a function also gets a `<Function>Params`
+    /// struct, and a `<function>_with(&params)` variant taking it.
+    ///
+    /// # Arguments
+    ///
+    /// * `threshold` - The number of parameters, `None` to disable the params structs.
+    pub fn with_params_struct_threshold(mut self, threshold: Option<usize>) -> Self {
+        self.params_struct_threshold = threshold;
+        self
+    }
+
+    /// Sets if a `<contract>_prelude` module re-exporting flatly the contract, the reader,
+    /// the types and the events is generated.
+    ///
+    /// # Arguments
+    ///
+    /// * `prelude` - Whether the `<contract>_prelude` module is generated.
+    pub fn with_prelude(mut self, prelude: bool) -> Self {
+        self.prelude = prelude;
+        self
+    }
+
+    /// Sets if a `<contract>_roundtrip_tests` module is generated, testing the serialization
+    /// round-trip of each generated type with a sample value.
+    ///
+    /// # Arguments
+    ///
+    /// * `roundtrip_tests` - Whether the round-trip tests are generated.
+    pub fn with_roundtrip_tests(mut self, roundtrip_tests: bool) -> Self {
+        self.roundtrip_tests = roundtrip_tests;
+        self
+    }
+
+    /// Sets the radix of the integers wider than `u32` and the felts in the serde
+    /// implementations of the generated types. Both radixes are accepted when deserializing.
+    ///
+    /// # Arguments
+    ///
+    /// * `serde_radix` - The radix used to serialize.
+    pub fn with_serde_radix(mut self, serde_radix: SerdeRadix) -> Self {
+        self.serde_radix = serde_radix;
+        self
+    }
+
+    /// Sets the encoding of the bytes (`Vec<u8>` and `ByteArray` members) in the serde
+    /// implementations of the generated types.
+    ///
+    /// # Arguments
+    ///
+    /// * `serde_bytes` - The encoding used to serialize the bytes.
+    pub fn with_serde_bytes(mut self, serde_bytes: BytesEncoding) -> Self {
+        self.serde_bytes = serde_bytes;
+        self
+    }
+
+    /// Sets if the SRC5 ids of the interfaces of the ABI are generated as constants,
+    /// with the methods probing the contract for them (like `supports_ierc20()`).
+    ///
+    /// # Arguments
+    ///
+    /// * `src5` - Whether the SRC5 interface ids are generated.
+    pub fn with_src5(mut self, src5: bool) -> Self {
+        self.src5 = src5;
+        self
+    }
+
+    /// Sets if the externals return an `Invoke`, which can be sponsored by a paymaster
+    /// (`.sponsored(&paymaster)`) instead of being executed by the account.
+    ///
+    /// # Arguments
+    ///
+    /// * `paymaster` - Whether the externals can be sponsored by a paymaster.
+    pub fn with_paymaster(mut self, paymaster: bool) -> Self {
+        self.paymaster = paymaster;
+        self
+    }
+
+    /// Sets the constants of the contract, emitted in a `<contract>_consts` module and checked
+    /// against their views by the `check_consts()` method of the reader.
+    ///
+    /// # Arguments
+    ///
+    /// * `consts` - The constants of the contract.
+    pub fn with_consts(mut self, consts: Vec<ContractConst>) -> Self {
+        self.consts = consts;
+        self
+    }
+
+    /// Sets the fixed tuples generated as named structs, used in place of the tuples.
+    ///
+    /// # Arguments
+    ///
+    /// * `tuple_structs` - The tuples generated as named structs.
+    pub fn with_tuple_structs(mut self, tuple_structs: Vec<TupleStruct>) -> Self {
+        self.tuple_structs = tuple_structs;
+        self
+    }
+
+    /// Sets the enums of the contract mapped to `Result`, used in place of the enums.
+    ///
+    /// # Arguments
+    ///
+    /// * `result_enums` - The enums mapped to `Result`, with their ok and err variants.
+    pub fn with_result_enums(mut self, result_enums: Vec<ResultEnum>) -> Self {
+        self.result_enums = result_enums;
+        self
+    }
+
+    /// Sets the parts of the bindings to generate: everything (the default), only the types
+    /// with the decoding of the events, or only the types with the encoding of the calls.
+    ///
+    /// # Arguments
+    ///
+    /// * `generation_mode` - The parts of the bindings to generate.
+    pub fn with_generation_mode(mut self, generation_mode: GenerationMode) -> Self {
+        self.generation_mode = generation_mode;
+        self
+    }
+
+    /// Returns the expand options configured on this builder.
+    pub fn expand_options(&self) -> ExpandOptions {
+        ExpandOptions {
+            execution_version: self.execution_version,
+            derives: self.derives.clone(),
+            contract_derives: self.contract_derives.clone(),
+            type_derives: self.type_derives.clone(),
+            blocking: self.blocking,
+            arbitrary: self.arbitrary,
+            params_struct_threshold: self.params_struct_threshold,
+            prelude: self.prelude,
+            roundtrip_tests: self.roundtrip_tests,
+            serde_radix: self.serde_radix,
+            serde_bytes: self.serde_bytes,
+            src5: self.src5,
+            paymaster: self.paymaster,
+            consts: self.consts.clone(),
+            tuple_structs: self.tuple_structs.clone(),
+            result_enums: self.result_enums.clone(),
+            generation_mode: self.generation_mode,
+            // Only embedded by `abigen!`: the bindings written to a file would
+            // include the class from an absolute path of the machine generating them.
+            artifact: None,
+            shared_types: Default::default(),
+        }
+    }
+
     /// Generates the contract bindings.
     pub fn generate(&self) -> Result<ContractBindings> {
         let file_content = std::fs::read_to_string(&self.abi_source)?;
 
         match AbiParser::tokens_from_abi_string(&file_content, &self.types_aliases) {
             Ok(tokens) => {
-                let expanded = abi_to_tokenstream(
-                    &self.contract_name,
-                    &tokens,
-                    self.execution_version,
-                    &self.derives,
-                    &self.contract_derives,
-                );
+                let expanded =
+                    abi_to_tokenstream(&self.contract_name, &tokens, &self.expand_options());
 
                 Ok(ContractBindings {
                     name: self.contract_name.clone(),
                     tokens: expanded,
+                    module_doc: abi_to_module_doc(
+                        &self.contract_name,
+                        &tokens,
+                        self.generation_mode,
+                    ),
                 })
             }
             Err(e) => {
@@ -171,111 +422,356 @@ impl Abigen {
 ///
 /// * `contract_name` - Name of the contract.
 /// * `abi_tokens` - Tokenized ABI.
-/// * `execution_version` - The version of transaction to be executed.
-/// * `derives` - Derives to be added to the generated types.
-/// * `contract_derives` - Derives to be added to the generated contract.
+/// * `options` - Options to customize the generated bindings.
 pub fn abi_to_tokenstream(
     contract_name: &str,
     abi_tokens: &TokenizedAbi,
-    execution_version: ExecutionVersion,
-    derives: &[String],
-    contract_derives: &[String],
+    options: &ExpandOptions,
 ) -> TokenStream2 {
-    let contract_name = utils::str_to_ident(contract_name);
+    let with_tuple_structs;
+    let abi_tokens = if options.tuple_structs.is_empty() {
+        abi_tokens
+    } else {
+        let errors = check_tuple_structs(&options.tuple_structs, abi_tokens);
 
-    let mut tokens: Vec<TokenStream2> = vec![];
+        if !errors.is_empty() {
+            return quote! {
+                #(compile_error!(#errors);)*
+            };
+        }
 
-    tokens.push(CairoContract::expand(
-        contract_name.clone(),
-        contract_derives,
-    ));
+        with_tuple_structs = tuple_structs::apply_tuple_structs(abi_tokens, &options.tuple_structs);
+        &with_tuple_structs
+    };
 
-    let mut sorted_structs = abi_tokens.structs.clone();
-    sorted_structs.sort_by(|a, b| {
-        let a_name = a
-            .to_composite()
-            .expect("composite expected")
-            .type_name_or_alias();
-        let b_name = b
-            .to_composite()
-            .expect("composite expected")
-            .type_name_or_alias();
-        a_name.cmp(&b_name)
-    });
+    let with_result_enums;
+    let abi_tokens = if options.result_enums.is_empty() {
+        abi_tokens
+    } else {
+        let errors = check_result_enums(&options.result_enums, abi_tokens);
 
-    let mut sorted_enums = abi_tokens.enums.clone();
-    sorted_enums.sort_by(|a, b| {
-        let a_name = a
-            .to_composite()
-            .expect("composite expected")
-            .type_name_or_alias();
-        let b_name = b
-            .to_composite()
-            .expect("composite expected")
-            .type_name_or_alias();
-        a_name.cmp(&b_name)
-    });
+        if !errors.is_empty() {
+            return quote! {
+                #(compile_error!(#errors);)*
+            };
+        }
 
-    for s in &sorted_structs {
-        let s_composite = s.to_composite().expect("composite expected");
-        tokens.push(CairoStruct::expand_decl(s_composite, derives));
-        tokens.push(CairoStruct::expand_impl(s_composite));
-    }
+        with_result_enums = result_enums::apply_result_enums(abi_tokens, &options.result_enums);
+        &with_result_enums
+    };
 
-    for e in &sorted_enums {
-        let e_composite = e.to_composite().expect("composite expected");
-        tokens.push(CairoEnum::expand_decl(e_composite, derives));
-        tokens.push(CairoEnum::expand_impl(e_composite));
+    let execution_version = options.execution_version;
+    let paymaster = options.paymaster;
+    let mode = options.generation_mode;
+
+    let contract_name = utils::str_to_ident(contract_name);
+
+    let mut tokens: Vec<TokenStream2> = vec![];
 
-        tokens.push(CairoEnumEvent::expand(
-            e.to_composite().expect("composite expected"),
-            &abi_tokens.enums,
-            &abi_tokens.structs,
+    if mode.has_contract() {
+        tokens.push(CairoContract::expand(
+            contract_name.clone(),
+            &options.contract_derives,
+            &abi_summary(abi_tokens),
         ));
     }
 
+    let sorted_structs = sorted_composites(&abi_tokens.structs);
+    let sorted_enums = sorted_composites(&abi_tokens.enums);
+
+    tokens.extend(expand_types(
+        &sorted_structs,
+        &sorted_enums,
+        abi_tokens,
+        options,
+    ));
+
     let mut reader_views = vec![];
     let mut views = vec![];
     let mut externals = vec![];
 
-    // Interfaces are not yet reflected in the generated contract.
-    // Then, the standalone functions and functions from interfaces are put together.
-    let mut functions = abi_tokens.functions.clone();
-    for funcs in abi_tokens.interfaces.values() {
-        functions.extend(funcs.clone());
-    }
+    let mut blocking_reader_views = vec![];
+    let mut blocking_views = vec![];
+    let mut blocking_externals = vec![];
 
-    functions.sort_by(|a, b| {
-        let a_name = a.to_function().expect("function expected").name.to_string();
-        let b_name = b.to_function().expect("function expected").name.to_string();
-        a_name.cmp(&b_name)
-    });
+    let mut params_struct_names = vec![];
+    let mut getcalls = vec![];
+
+    for f in sorted_functions(abi_tokens) {
+        // Without the contract, only the calls to the externals may be encoded.
+        if !mode.has_contract() {
+            if mode.has_calldata_encoding() && f.state_mutability == StateMutability::External {
+                getcalls.push(CairoFunction::expand_getcall(f));
+            }
+
+            continue;
+        }
+
+        let has_params_struct = options.has_params_struct(f);
+        if has_params_struct {
+            tokens.push(CairoFunction::expand_params_struct(f, &options.derives));
+            params_struct_names.push(CairoFunction::params_struct_name(f).to_string());
+        }
 
-    for f in functions {
-        let f = f.to_function().expect("function expected");
         match f.state_mutability {
             StateMutability::View => {
-                reader_views.push(CairoFunction::expand(f, true, execution_version));
-                views.push(CairoFunction::expand(f, false, execution_version));
+                reader_views.push(CairoFunction::expand(f, true, execution_version, paymaster));
+                views.push(CairoFunction::expand(
+                    f,
+                    false,
+                    execution_version,
+                    paymaster,
+                ));
+
+                if has_params_struct {
+                    reader_views.push(CairoFunction::expand_with(
+                        f,
+                        true,
+                        execution_version,
+                        paymaster,
+                    ));
+                    views.push(CairoFunction::expand_with(
+                        f,
+                        false,
+                        execution_version,
+                        paymaster,
+                    ));
+                }
+
+                if options.blocking {
+                    blocking_reader_views.push(CairoFunction::expand_blocking(f, "reader"));
+                    blocking_views.push(CairoFunction::expand_blocking(f, "contract"));
+                }
             }
             StateMutability::External => {
-                externals.push(CairoFunction::expand(f, false, execution_version))
+                externals.push(CairoFunction::expand(
+                    f,
+                    false,
+                    execution_version,
+                    paymaster,
+                ));
+
+                if has_params_struct {
+                    externals.push(CairoFunction::expand_with(
+                        f,
+                        false,
+                        execution_version,
+                        paymaster,
+                    ));
+                }
+
+                if options.blocking {
+                    blocking_externals.push(CairoFunction::expand_blocking(f, "contract"));
+                }
             }
         }
     }
 
     let reader = utils::str_to_ident(format!("{}Reader", contract_name).as_str());
 
-    tokens.push(quote! {
-        impl<A: starknet::accounts::ConnectedAccount + Sync> #contract_name<A> {
-            #(#views)*
-            #(#externals)*
+    // The builtins (like `u256`) are not generated, but mapped to the types of `cainome`.
+    let type_names: Vec<String> = sorted_structs
+        .iter()
+        .chain(sorted_enums.iter())
+        .map(|t| t.to_composite().expect("composite expected"))
+        .filter(|c| !c.is_builtin())
+        .map(|c| c.type_name_or_alias())
+        .collect();
+
+    let (event_alias, events_methods) =
+        CairoContract::expand_events_methods(&contract_name, abi_tokens.root_event(), &type_names);
+
+    let entrypoints = CairoContract::expand_entrypoints(&sorted_functions(abi_tokens));
+
+    let raw_methods = CairoContract::expand_raw_methods(false, execution_version, paymaster);
+    let reader_raw_methods = CairoContract::expand_raw_methods(true, execution_version, paymaster);
+
+    let src5_interfaces = if options.src5 {
+        CairoSrc5::interfaces(&contract_name, abi_tokens)
+    } else {
+        vec![]
+    };
+
+    let (src5_consts, src5_methods, reader_src5_methods) = if options.src5 {
+        (
+            CairoSrc5::expand_consts(&src5_interfaces),
+            CairoSrc5::expand_methods(&src5_interfaces, false),
+            CairoSrc5::expand_methods(&src5_interfaces, true),
+        )
+    } else {
+        (quote!(), quote!(), quote!())
+    };
+
+    // The methods are grouped into documented impl blocks, which are the sections
+    // of the documentation of the contract and the reader.
+    let views_doc =
+        quote!(#[doc = "Views of the contract, called with `call()` on the returned calls."]);
+    let externals_doc = quote!(#[doc = "Externals of the contract, sent with `send()` on the returned executions."]);
+    let others_doc = quote!(#[doc = "Selectors of and raw access to the entrypoints, and decoding of the events."]);
+
+    let contract_impl =
+        quote!(impl<A: starknet::accounts::ConnectedAccount + Sync> #contract_name<A>);
+    let reader_impl = quote!(impl<P: starknet::providers::Provider + Sync> #reader<P>);
+
+    let contract_views = if views.is_empty() {
+        quote!()
+    } else {
+        quote!(#views_doc #contract_impl { #(#views)* })
+    };
+
+    let contract_externals = if externals.is_empty() {
+        quote!()
+    } else {
+        quote!(#externals_doc #contract_impl { #(#externals)* })
+    };
+
+    let contract_deploy = match abi_tokens.constructor.as_ref().map(|c| c.to_function()) {
+        Some(Ok(constructor)) => {
+            CairoConstructor::expand(&contract_name, constructor, execution_version)
+        }
+        _ => quote!(),
+    };
+
+    let contract_declare = match &options.artifact {
+        Some(class) => CairoArtifact::expand(&contract_name, class, execution_version),
+        None => quote!(),
+    };
+
+    let reader_views = if reader_views.is_empty() {
+        quote!()
+    } else {
+        quote!(#views_doc #reader_impl { #(#reader_views)* })
+    };
+
+    if mode.has_events_decoding() {
+        tokens.push(event_alias);
+    }
+
+    tokens.push(src5_consts);
+
+    if mode.has_contract() {
+        tokens.push(quote! {
+        #others_doc
+        #contract_impl {
+            #entrypoints
+            #raw_methods
+            #src5_methods
+            #events_methods
+        }
+
+        #contract_views
+        #contract_externals
+        #contract_deploy
+        #contract_declare
+
+        #others_doc
+        #reader_impl {
+            #entrypoints
+            #reader_raw_methods
+            #reader_src5_methods
+            #events_methods
         }
 
-        impl<P: starknet::providers::Provider + Sync> #reader<P> {
-            #(#reader_views)*
+        #reader_views
+        });
+    } else if mode.has_calldata_encoding() {
+        tokens.push(CairoContract::expand_calls(
+            &contract_name,
+            &options.contract_derives,
+            &entrypoints,
+            &getcalls,
+        ));
+    }
+
+    if !options.consts.is_empty() {
+        let errors = check_consts(&options.consts, abi_tokens);
+
+        if errors.is_empty() {
+            tokens.push(CairoConsts::expand_module(&contract_name, &options.consts));
+
+            // The constants are checked by the reader.
+            if mode.has_contract() {
+                let check_method =
+                    CairoConsts::expand_check_method(&contract_name, &options.consts);
+
+                tokens.push(quote! {
+                    #[doc = "Checks of the constants declared with the bindings."]
+                    #reader_impl {
+                        #check_method
+                    }
+                });
+                tokens.push(CairoConsts::expand_tests(&contract_name, &reader));
+            }
+        } else {
+            tokens.extend(errors.iter().map(|e| quote!(compile_error!(#e);)));
         }
-    });
+    }
+
+    let l1_handlers = if mode.has_calldata_encoding() {
+        CairoL1Handler::expand(&contract_name, &abi_tokens.l1_handlers)
+    } else {
+        quote!()
+    };
+    let has_l1_handlers = !l1_handlers.is_empty();
+    tokens.push(l1_handlers);
+
+    if options.blocking && mode.has_contract() {
+        tokens.push(CairoContract::expand_blocking(
+            &contract_name,
+            &blocking_views,
+            &blocking_reader_views,
+            &blocking_externals,
+        ));
+    }
+
+    if options.prelude {
+        let mut items = vec![];
+
+        if mode.has_contract() {
+            items.push(contract_name.to_string());
+            items.push(reader.to_string());
+        } else if mode.has_calldata_encoding() {
+            items.push(CairoContract::calls_struct_name(&contract_name).to_string());
+        }
+
+        items.extend(type_names.iter().cloned());
+
+        if mode.has_events_decoding() {
+            items.extend(
+                sorted_enums
+                    .iter()
+                    .map(|e| e.to_composite().expect("composite expected"))
+                    .filter(|e| e.is_event)
+                    .map(|e| format!("{}Visitor", e.type_name_or_alias())),
+            );
+
+            if let Some(alias_name) = CairoContract::event_alias_name(
+                &contract_name,
+                abi_tokens.root_event(),
+                &type_names,
+            ) {
+                items.push(format!("{}Visitor", alias_name));
+                items.push(alias_name);
+            }
+        }
+
+        items.extend(params_struct_names);
+        items.extend(src5_interfaces.into_iter().map(|i| i.const_name));
+
+        if has_l1_handlers {
+            items.push(CairoL1Handler::struct_name(&contract_name).to_string());
+        }
+
+        tokens.push(CairoContract::expand_prelude(
+            &contract_name,
+            &items,
+            options.blocking && mode.has_contract(),
+        ));
+    }
+
+    if options.roundtrip_tests {
+        tokens.push(CairoRoundtripTests::expand(&contract_name, abi_tokens));
+    }
 
     let expanded = quote! {
         #(#tokens)*
@@ -283,3 +779,509 @@ pub fn abi_to_tokenstream(
 
     expanded
 }
+
+/// Returns the name of the prelude module generated in the bindings of the contract
+/// with [`ExpandOptions::prelude`] (`my_contract_prelude` for `MyContract`).
+///
+/// # Arguments
+///
+/// * `contract_name` - Name of the contract.
+pub fn prelude_module_name(contract_name: &str) -> String {
+    CairoContract::prelude_name(&utils::str_to_ident(contract_name)).to_string()
+}
+
+/// Converts the types shared by a group of contracts into rust bindings, generated
+/// once in a module the bindings of the contracts re-export them from
+/// (see [`ExpandOptions::shared_types`]).
+///
+/// # Arguments
+///
+/// * `shared` - The types shared by the contracts of the group.
+/// * `options` - Options to customize the generated types.
+pub fn shared_types_to_tokenstream(shared: &SharedTypes, options: &ExpandOptions) -> TokenStream2 {
+    // The shared types referencing an enum mapped to `Result` reference it in all the contracts.
+    let shared_tokens = result_enums::apply_result_enums(&shared.tokens, &options.result_enums);
+
+    let sorted_structs = sorted_composites(&shared_tokens.structs);
+    let sorted_enums = sorted_composites(&shared_tokens.enums);
+
+    let tokens = expand_types(&sorted_structs, &sorted_enums, &shared_tokens, options);
+
+    quote! {
+        #(#tokens)*
+    }
+}
+
+/// Generates an example program interacting with the contract, to be compiled
+/// along the bindings generated by [`abi_to_tokenstream`].
+///
+/// Each view is called, and the first external is invoked, with placeholder values.
+///
+/// # Arguments
+///
+/// * `contract_name` - Name of the contract.
+/// * `abi_tokens` - Tokenized ABI.
+/// * `bindings_path` - Path of the bindings file, relative to the example file.
+/// * `sibling_modules` - Modules referenced by the bindings as `super::<module>` (like the
+///   shared types), in the directory of the bindings file.
+pub fn abi_to_example_tokenstream(
+    contract_name: &str,
+    abi_tokens: &TokenizedAbi,
+    bindings_path: &str,
+    sibling_modules: &[String],
+) -> TokenStream2 {
+    CairoExample::expand(
+        contract_name,
+        abi_tokens,
+        &sorted_functions(abi_tokens),
+        bindings_path,
+        sibling_modules,
+    )
+}
+
+/// Generates the `From` implementations converting the types of other bindings
+/// into the identical types (same path and same members) of the bindings.
+///
+/// # Arguments
+///
+/// * `abi_tokens` - Tokenized ABI of the bindings.
+/// * `other_abi_tokens` - Tokenized ABI of the other bindings.
+/// * `other_module` - Path of the module of the other bindings (like `super::other_contract`).
+pub fn identical_types_conversions_tokenstream(
+    abi_tokens: &TokenizedAbi,
+    other_abi_tokens: &TokenizedAbi,
+    other_module: &str,
+) -> TokenStream2 {
+    CairoConversion::expand(abi_tokens, other_abi_tokens, other_module)
+}
+
+/// Returns the inner doc comments (`//!`) of a module containing the bindings
+/// of a contract, summarizing its ABI.
+///
+/// # Arguments
+///
+/// * `contract_name` - Name of the contract.
+/// * `abi_tokens` - Tokenized ABI.
+/// * `mode` - The parts of the bindings generated.
+pub fn abi_to_module_doc(
+    contract_name: &str,
+    abi_tokens: &TokenizedAbi,
+    mode: GenerationMode,
+) -> String {
+    let mut lines = vec![
+        format!("Bindings of the `{}` contract.", contract_name),
+        String::new(),
+        abi_summary(abi_tokens),
+        String::new(),
+    ];
+
+    match mode {
+        GenerationMode::Full => {
+            lines.push(format!(
+                "- [`{0}`]: calls the views and invokes the externals with an account.",
+                contract_name
+            ));
+            lines.push(format!(
+                "- [`{0}Reader`]: calls the views with a provider.",
+                contract_name
+            ));
+        }
+        GenerationMode::Decode => {
+            lines.push("- The types, and the decoding of the events.".to_string());
+        }
+        GenerationMode::Encode => {
+            lines.push(format!(
+                "- [`{0}Calls`]: encodes the calls to the externals.",
+                contract_name
+            ));
+        }
+    }
+
+    if mode.has_calldata_encoding() && !abi_tokens.l1_handlers.is_empty() {
+        lines.push(format!(
+            "- [`{0}L1Handlers`]: builds the messages sent from L1 to the L1 handlers.",
+            contract_name
+        ));
+    }
+
+    let mut doc = String::new();
+    for line in lines {
+        if line.is_empty() {
+            doc.push_str("//!\n");
+        } else {
+            doc.push_str(&format!("//! {}\n", line));
+        }
+    }
+
+    doc
+}
+
+/// Returns a sentence summarizing the entrypoints and the types of the ABI.
+fn abi_summary(abi_tokens: &TokenizedAbi) -> String {
+    fn count(n: usize, what: &str) -> String {
+        format!("{} {}{}", n, what, if n == 1 { "" } else { "s" })
+    }
+
+    let functions = sorted_functions(abi_tokens);
+    let views = functions
+        .iter()
+        .filter(|f| f.state_mutability == StateMutability::View)
+        .count();
+
+    let mut counts = vec![
+        count(views, "view"),
+        count(functions.len() - views, "external"),
+        count(abi_tokens.structs.len() + abi_tokens.enums.len(), "type"),
+    ];
+
+    if !abi_tokens.l1_handlers.is_empty() {
+        counts.push(count(abi_tokens.l1_handlers.len(), "L1 handler"));
+    }
+
+    let mut summary = format!(
+        "Generated by cainome from an ABI with {}.",
+        counts.join(", ")
+    );
+
+    let mut interfaces: Vec<&str> = abi_tokens
+        .interfaces
+        .keys()
+        .map(|k| abi_tokens.interface_path(k))
+        .collect();
+    interfaces.sort();
+
+    if !interfaces.is_empty() {
+        let interfaces: Vec<String> = interfaces.iter().map(|i| format!("`{}`", i)).collect();
+        summary.push_str(&format!(" Interfaces: {}.", interfaces.join(", ")));
+    }
+
+    if let Some(event) = abi_tokens.root_event() {
+        summary.push_str(&format!(" Events: `{}`.", event.type_path));
+    }
+
+    summary
+}
+
+/// Returns all the functions of the ABI, sorted by name.
+/// Returns the composites sorted by the name of the generated type.
+fn sorted_composites(composites: &[Token]) -> Vec<Token> {
+    let mut sorted = composites.to_vec();
+    sorted.sort_by_key(|t| {
+        t.to_composite()
+            .expect("composite expected")
+            .type_name_or_alias()
+    });
+    sorted
+}
+
+/// Expands the declarations and the implementations of the structs and the enums,
+/// with the decoding of the events. The shared types are re-exported from their module.
+///
+/// # Arguments
+///
+/// * `structs` - The structs to expand.
+/// * `enums` - The enums to expand.
+/// * `abi_tokens` - Tokenized ABI, to resolve the nested events.
+/// * `options` - Options to customize the generated bindings.
+fn expand_types(
+    structs: &[Token],
+    enums: &[Token],
+    abi_tokens: &TokenizedAbi,
+    options: &ExpandOptions,
+) -> Vec<TokenStream2> {
+    let mode = options.generation_mode;
+    let mut tokens = vec![];
+
+    for s in structs {
+        let s_composite = s.to_composite().expect("composite expected");
+
+        if let Some(module) = options
+            .shared_types
+            .get(&s_composite.type_path_no_generic())
+        {
+            tokens.push(CairoSharedTypes::expand_reexport(s_composite, module, mode));
+            continue;
+        }
+
+        tokens.push(CairoStruct::expand_decl(
+            s_composite,
+            &options.derives_for(s_composite),
+            options.arbitrary,
+            options.serde_radix,
+            options.serde_bytes,
+        ));
+        tokens.push(CairoStruct::expand_impl(s_composite));
+    }
+
+    for e in enums {
+        let e_composite = e.to_composite().expect("composite expected");
+
+        if let Some(module) = options
+            .shared_types
+            .get(&e_composite.type_path_no_generic())
+        {
+            tokens.push(CairoSharedTypes::expand_reexport(e_composite, module, mode));
+            continue;
+        }
+
+        tokens.push(CairoEnum::expand_decl(
+            e_composite,
+            &options.derives_for(e_composite),
+            options.arbitrary,
+            options.serde_radix,
+            options.serde_bytes,
+        ));
+        tokens.push(CairoEnum::expand_impl(e_composite));
+
+        if mode.has_events_decoding() {
+            tokens.push(CairoEnumEvent::expand(
+                e_composite,
+                &abi_tokens.enums,
+                &abi_tokens.structs,
+            ));
+        }
+    }
+
+    tokens
+}
+
+fn sorted_functions(abi_tokens: &TokenizedAbi) -> Vec<&Function> {
+    // Interfaces are not yet reflected in the generated contract.
+    // Then, the standalone functions and functions from interfaces are put together.
+    let mut functions: Vec<&Function> = abi_tokens
+        .functions
+        .iter()
+        .chain(abi_tokens.interfaces.values().flatten())
+        .map(|f| f.to_function().expect("function expected"))
+        .collect();
+
+    functions.sort_by(|a, b| a.name.cmp(&b.name));
+
+    functions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_abi_to_module_doc() {
+        let abi = r#"[
+            {
+                "type": "struct",
+                "name": "pkg::Order",
+                "members": [{ "name": "price", "type": "core::integer::u128" }]
+            },
+            {
+                "type": "interface",
+                "name": "pkg::IBook",
+                "items": [
+                    {
+                        "type": "function",
+                        "name": "order",
+                        "inputs": [],
+                        "outputs": [{ "type": "pkg::Order" }],
+                        "state_mutability": "view"
+                    },
+                    {
+                        "type": "function",
+                        "name": "cancel",
+                        "inputs": [],
+                        "outputs": [],
+                        "state_mutability": "external"
+                    }
+                ]
+            }
+        ]"#;
+        let abi_tokens = AbiParser::tokens_from_abi_string(abi, &HashMap::new()).unwrap();
+
+        assert_eq!(
+            abi_to_module_doc("Book", &abi_tokens, GenerationMode::Full),
+            "//! Bindings of the `Book` contract.\n\
+             //!\n\
+             //! Generated by cainome from an ABI with 1 view, 1 external, 1 type. Interfaces: `pkg::IBook`.\n\
+             //!\n\
+             //! - [`Book`]: calls the views and invokes the externals with an account.\n\
+             //! - [`BookReader`]: calls the views with a provider.\n"
+        );
+    }
+
+    #[test]
+    fn test_generation_modes() {
+        let abi = r#"[
+            {
+                "type": "function",
+                "name": "cancel",
+                "inputs": [{ "name": "id", "type": "core::felt252" }],
+                "outputs": [],
+                "state_mutability": "external"
+            },
+            {
+                "type": "function",
+                "name": "price",
+                "inputs": [],
+                "outputs": [{ "type": "core::felt252" }],
+                "state_mutability": "view"
+            },
+            {
+                "type": "event",
+                "name": "pkg::Cancelled",
+                "kind": "struct",
+                "members": [{ "name": "id", "type": "core::felt252", "kind": "key" }]
+            },
+            {
+                "type": "event",
+                "name": "pkg::Event",
+                "kind": "enum",
+                "variants": [{ "name": "Cancelled", "type": "pkg::Cancelled", "kind": "nested" }]
+            }
+        ]"#;
+        let abi_tokens = AbiParser::tokens_from_abi_string(abi, &HashMap::new()).unwrap();
+
+        let expand = |generation_mode| {
+            let options = ExpandOptions {
+                generation_mode,
+                prelude: true,
+                ..Default::default()
+            };
+            abi_to_tokenstream("Book", &abi_tokens, &options).to_string()
+        };
+
+        let full = expand(GenerationMode::Full);
+        assert!(full.contains("pub struct Book < A"));
+        assert!(full.contains("pub struct BookReader < P"));
+        assert!(full.contains("TryFrom < & starknet :: core :: types :: Event > for Event"));
+
+        let decode = expand(GenerationMode::Decode);
+        assert!(!decode.contains("pub struct Book"));
+        assert!(!decode.contains("cancel_getcall"));
+        assert!(decode.contains("pub struct Cancelled"));
+        assert!(decode.contains("TryFrom < & starknet :: core :: types :: Event > for Event"));
+        assert!(decode.contains("pub type BookEvent = Event ;"));
+        assert!(decode.contains("pub use super :: { Cancelled , Event , EventVisitor"));
+
+        let encode = expand(GenerationMode::Encode);
+        assert!(!encode.contains("pub struct Book <"));
+        assert!(!encode.contains("TryFrom"));
+        assert!(encode.contains("pub struct BookCalls"));
+        assert!(encode.contains("pub fn cancel_getcall"));
+        assert!(encode.contains("pub const SELECTOR_CANCEL"));
+        assert!(encode.contains("pub fn entrypoints ()"));
+        assert!(!encode.contains("pub fn price"));
+        assert!(encode.contains("pub use super :: { BookCalls , Cancelled , Event }"));
+
+        assert!(
+            abi_to_module_doc("Book", &abi_tokens, GenerationMode::Encode)
+                .ends_with("//! - [`BookCalls`]: encodes the calls to the externals.\n")
+        );
+    }
+
+    #[test]
+    fn test_constructor_deploy() {
+        let abi = r#"[
+            {
+                "type": "constructor",
+                "name": "constructor",
+                "inputs": [
+                    { "name": "owner", "type": "core::starknet::contract_address::ContractAddress" },
+                    { "name": "salt", "type": "core::felt252" }
+                ]
+            }
+        ]"#;
+        let abi_tokens = AbiParser::tokens_from_abi_string(abi, &HashMap::new()).unwrap();
+
+        let expand = |generation_mode| {
+            let options = ExpandOptions {
+                generation_mode,
+                ..Default::default()
+            };
+            abi_to_tokenstream("Vault", &abi_tokens, &options).to_string()
+        };
+
+        let full = expand(GenerationMode::Full);
+        assert!(full.contains("pub fn constructor_calldata (owner : & cainome :: cairo_serde :: ContractAddress , constructor_salt : & starknet :: core :: types :: Felt)"));
+        assert!(full.contains("pub async fn deploy (account : & A , class_hash : starknet :: core :: types :: Felt , salt : starknet :: core :: types :: Felt , owner"));
+        assert!(full.contains("pub async fn deploy_with_deployer"));
+        assert!(full.contains("__factory . deploy_v1 (__calldata , salt , false)"));
+
+        // The deployment needs an account.
+        assert!(!expand(GenerationMode::Encode).contains("deploy"));
+    }
+
+    #[test]
+    fn test_embedded_class_declare() {
+        let abi_tokens = AbiParser::tokens_from_abi_string("[]", &HashMap::new()).unwrap();
+        let class = EmbeddedClass::from_sierra_path(std::path::Path::new(
+            "../parser/test_data/counter.contract_class.json",
+        ))
+        .unwrap();
+
+        let expand = |artifact| {
+            let options = ExpandOptions {
+                execution_version: ExecutionVersion::V3,
+                artifact,
+                ..Default::default()
+            };
+            abi_to_tokenstream("Counter", &abi_tokens, &options).to_string()
+        };
+
+        let with_artifact = expand(Some(class.clone()));
+        assert!(with_artifact.contains(&format!(
+            "pub const SIERRA_CLASS : & 'static str = include_str ! ({:?})",
+            class.sierra_path
+        )));
+        assert!(with_artifact.contains(&format!(
+            "Felt :: from_hex_unchecked (\"{:#x}\")",
+            class.compiled_class_hash
+        )));
+        assert!(with_artifact.contains("pub fn class_hash ()"));
+        assert!(with_artifact.contains("pub async fn declare (account : & A ,)"));
+        assert!(
+            with_artifact.contains("account . declare_v3 (__class , Self :: COMPILED_CLASS_HASH)")
+        );
+
+        assert!(!expand(None).contains("declare"));
+    }
+
+    #[test]
+    fn test_shared_types_reexport() {
+        let abi = r#"[
+            {
+                "type": "struct",
+                "name": "pkg::Order",
+                "members": [{ "name": "id", "type": "core::felt252" }]
+            },
+            {
+                "type": "event",
+                "name": "pkg::Event",
+                "kind": "enum",
+                "variants": []
+            }
+        ]"#;
+        let a = AbiParser::tokens_from_abi_string(abi, &HashMap::new()).unwrap();
+        let b = AbiParser::tokens_from_abi_string(abi, &HashMap::new()).unwrap();
+        let shared = SharedTypes::new(&[&a, &b]);
+
+        let options = ExpandOptions {
+            shared_types: shared.for_contract(0, "super::common"),
+            ..Default::default()
+        };
+        let bindings = abi_to_tokenstream("A", &a, &options).to_string();
+        assert!(bindings.contains("pub use super :: common :: Order ;"));
+        assert!(bindings.contains("pub use super :: common :: { Event , EventVisitor } ;"));
+        assert!(!bindings.contains("pub struct Order"));
+
+        let common = shared_types_to_tokenstream(&shared, &ExpandOptions::default()).to_string();
+        assert!(common.contains("pub struct Order"));
+        assert!(common.contains("pub enum Event"));
+        assert!(common.contains("pub trait EventVisitor"));
+    }
+
+    #[test]
+    fn test_parse_generation_mode() {
+        assert_eq!("decode".parse(), Ok(GenerationMode::Decode));
+        assert_eq!("encode".parse(), Ok(GenerationMode::Encode));
+        assert_eq!("full".parse(), Ok(GenerationMode::Full));
+        assert!("types".parse::<GenerationMode>().is_err());
+    }
+}
@@ -0,0 +1,234 @@
+//! Options to customize the expansion of the rust bindings.
+use cainome_parser::tokens::{Composite, Function};
+use std::collections::{BTreeMap, HashMap};
+
+use crate::{
+    BytesEncoding, ContractConst, EmbeddedClass, ExecutionVersion, GenerationMode, ResultEnum,
+    SerdeRadix, TupleStruct,
+};
+
+/// Options used to expand a tokenized ABI into rust bindings.
+#[derive(Debug, Clone, Default)]
+pub struct ExpandOptions {
+    /// The version of transaction to be executed.
+    pub execution_version: ExecutionVersion,
+    /// Derives to be added to all the generated types.
+    pub derives: Vec<String>,
+    /// Derives to be added to the generated contract.
+    pub contract_derives: Vec<String>,
+    /// Derives to be added only to the generated types matching a pattern.
+    ///
+    /// In the patterns, `*` matches any sequence of characters. A pattern containing `::`
+    /// is matched against the full type path (without generic arguments), otherwise it is
+    /// matched against the name of the generated type (which is the alias if any).
+    pub type_derives: HashMap<String, Vec<String>>,
+    /// Whether to generate the blocking facades of the contract and the reader,
+    /// only available with the `blocking` feature of the crate including the bindings.
+    pub blocking: bool,
+    /// Whether to derive `arbitrary::Arbitrary` for the generated types,
+    /// only available with the `arbitrary` feature of the crate including the bindings.
+    pub arbitrary: bool,
+    /// Functions with more parameters than this threshold also get a `<Function>Params`
+    /// struct and a `<function>_with(&params)` variant taking it.
+    /// The struct derives the common derives of the types.
+    pub params_struct_threshold: Option<usize>,
+    /// Whether to generate a `<contract>_prelude` module re-exporting flatly the contract,
+    /// the reader, the types and the events of the bindings.
+    pub prelude: bool,
+    /// Whether to generate a `<contract>_roundtrip_tests` module, testing the serialization
+    /// round-trip of each generated type with a sample value.
+    pub roundtrip_tests: bool,
+    /// The radix of the integers wider than `u32` and the felts in the serde
+    /// implementations of the generated types.
+    pub serde_radix: SerdeRadix,
+    /// The encoding of the `Vec<u8>` and `ByteArray` members in the serde
+    /// implementations of the generated types.
+    pub serde_bytes: BytesEncoding,
+    /// Whether to generate the SRC5 ids of the interfaces of the ABI, and the methods
+    /// probing the contract for them.
+    pub src5: bool,
+    /// Whether the externals return an `Invoke`, which can be sponsored by a paymaster
+    /// (`.sponsored(&paymaster)`) instead of being executed by the account.
+    pub paymaster: bool,
+    /// Constants of the contract, emitted in a `<contract>_consts` module and checked against
+    /// their views by the `check_consts()` method of the reader.
+    pub consts: Vec<ContractConst>,
+    /// Fixed tuples generated as named structs, used everywhere in place of the tuples.
+    pub tuple_structs: Vec<TupleStruct>,
+    /// Enums of the contract mapped to `Result`, used everywhere in place of the enums
+    /// which are not generated.
+    pub result_enums: Vec<ResultEnum>,
+    /// The parts of the bindings to generate: everything, or only the types with the decoding
+    /// of the events or the encoding of the calls.
+    pub generation_mode: GenerationMode,
+    /// The Sierra class embedded into the bindings, declared by the `declare()` function
+    /// of the contract.
+    pub artifact: Option<EmbeddedClass>,
+    /// Types generated by an other module, like the common module of a group of contracts,
+    /// re-exported instead of being generated: the type paths (without generic arguments)
+    /// mapped to the path of the module generating them.
+    pub shared_types: BTreeMap<String, String>,
+}
+
+impl ExpandOptions {
+    /// Returns whether the parameters of the function are grouped into a struct.
+    ///
+    /// # Arguments
+    ///
+    /// * `func` - The function.
+    pub fn has_params_struct(&self, func: &Function) -> bool {
+        self.params_struct_threshold
+            .is_some_and(|threshold| func.inputs.len() > threshold)
+    }
+
+    /// Returns the derives to be added to the given composite.
+    ///
+    /// The common derives come first, followed by the derives of all the matching patterns,
+    /// in the lexicographic order of the patterns. Duplicates are removed.
+    ///
+    /// # Arguments
+    ///
+    /// * `composite` - The composite to get the derives for.
+    pub fn derives_for(&self, composite: &Composite) -> Vec<String> {
+        let type_path = composite.type_path_no_generic();
+        let type_name = composite.type_name_or_alias();
+
+        let mut patterns: Vec<&String> = self.type_derives.keys().collect();
+        patterns.sort();
+
+        let mut derives = self.derives.clone();
+
+        for pattern in patterns {
+            let candidate = if pattern.contains("::") {
+                &type_path
+            } else {
+                &type_name
+            };
+
+            if !glob_match(pattern, candidate) {
+                continue;
+            }
+
+            for derive in &self.type_derives[pattern] {
+                if !derives.contains(derive) {
+                    derives.push(derive.clone());
+                }
+            }
+        }
+
+        derives
+    }
+}
+
+/// Matches `value` against `pattern`, where `*` matches any sequence of characters.
+fn glob_match(pattern: &str, value: &str) -> bool {
+    let mut parts = pattern.split('*');
+
+    // There is always at least one part, even for an empty pattern.
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = value.strip_prefix(first) else {
+        return false;
+    };
+
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middles)) = parts.split_last() else {
+        // No wildcard in the pattern.
+        return rest.is_empty();
+    };
+
+    for part in middles {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+
+    rest.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cainome_parser::tokens::{StateMutability, Token};
+
+    fn composite(type_path: &str, alias: Option<&str>) -> Composite {
+        let mut c = Token::parse(type_path)
+            .unwrap()
+            .to_composite()
+            .unwrap()
+            .clone();
+        c.alias = alias.map(|a| a.to_string());
+        c
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("Order", "Order"));
+        assert!(!glob_match("Order", "OrderBook"));
+        assert!(glob_match("Order*", "OrderBook"));
+        assert!(glob_match("Order*", "Order"));
+        assert!(!glob_match("Order*", "MyOrder"));
+        assert!(glob_match("*Order", "MyOrder"));
+        assert!(glob_match("*", "Anything"));
+        assert!(glob_match("pkg::*::Order*", "pkg::market::OrderBook"));
+        assert!(!glob_match("pkg::*::Order*", "other::market::OrderBook"));
+        assert!(glob_match("A*B*C", "AxxBxxC"));
+        assert!(!glob_match("A*B*C", "AxxCxxB"));
+        assert!(!glob_match("AB*BA", "ABA"));
+    }
+
+    #[test]
+    fn test_derives_for() {
+        let options = ExpandOptions {
+            derives: vec!["Debug".to_string()],
+            type_derives: HashMap::from([
+                (
+                    "Order*".to_string(),
+                    vec!["serde::Serialize".to_string(), "Debug".to_string()],
+                ),
+                (
+                    "pkg::positions::*".to_string(),
+                    vec!["Copy".to_string(), "Clone".to_string()],
+                ),
+            ]),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            options.derives_for(&composite("pkg::market::OrderBook", None)),
+            vec!["Debug", "serde::Serialize"]
+        );
+        assert_eq!(
+            options.derives_for(&composite("pkg::positions::Position", None)),
+            vec!["Debug", "Copy", "Clone"]
+        );
+        assert_eq!(
+            options.derives_for(&composite("pkg::market::Book", Some("OrderBook2"))),
+            vec!["Debug", "serde::Serialize"]
+        );
+        assert_eq!(
+            options.derives_for(&composite("pkg::market::Book", None)),
+            vec!["Debug"]
+        );
+    }
+
+    #[test]
+    fn test_has_params_struct() {
+        let mut func = Function::new("transfer", StateMutability::External);
+        for name in ["a", "b", "c"] {
+            func.inputs
+                .push((name.to_string(), Token::parse("core::felt252").unwrap()));
+        }
+
+        assert!(!ExpandOptions::default().has_params_struct(&func));
+
+        let options = ExpandOptions {
+            params_struct_threshold: Some(2),
+            ..Default::default()
+        };
+        assert!(options.has_params_struct(&func));
+
+        func.inputs.pop();
+        assert!(!options.has_params_struct(&func));
+    }
+}
@@ -0,0 +1,312 @@
+//! Enums of the contract mapped to `Result`.
+//!
+//! Some contracts define their own two-variant enums equivalent to `Result`, like
+//! `Outcome { Success: T, Failure: E }`. Mapping such an enum to `Result<T, E>` uses it
+//! everywhere the enum was, and the enum is not generated. The variants are serialized
+//! like the ones of `Result`, hence the ok variant must be the first one of the enum.
+use cainome_parser::tokens::{Composite, CompositeType, Token};
+use cainome_parser::TokenizedAbi;
+use serde::{Deserialize, Serialize};
+
+/// The type path of the Cairo `Result`, mapped to the Rust `Result`.
+const RESULT_TYPE_PATH: &str = "core::result::Result";
+
+/// An enum of the contract mapped to `Result`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResultEnum {
+    /// The Cairo type path of the enum, without its generic arguments (`pkg::Outcome`).
+    pub enum_path: String,
+    /// The name of the variant mapped to `Ok` (`Success`).
+    pub ok: String,
+    /// The name of the variant mapped to `Err` (`Failure`).
+    pub err: String,
+}
+
+/// Checks the enums mapped to `Result` against the ABI, and returns the errors.
+///
+/// An enum not defined by the ABI is ignored, since the same enums may be mapped
+/// for several contracts.
+///
+/// # Arguments
+///
+/// * `result_enums` - The enums mapped to `Result`.
+/// * `abi_tokens` - The tokens of the contract ABI.
+pub fn check_result_enums(result_enums: &[ResultEnum], abi_tokens: &TokenizedAbi) -> Vec<String> {
+    let mut errors = vec![];
+
+    for (i, r) in result_enums.iter().enumerate() {
+        if result_enums[..i]
+            .iter()
+            .any(|other| other.enum_path == r.enum_path)
+        {
+            errors.push(format!("result enum `{}` declared twice", r.enum_path));
+        }
+
+        if r.ok == r.err {
+            errors.push(format!(
+                "result enum `{}`: the ok and err variants are both `{}`",
+                r.enum_path, r.ok
+            ));
+        }
+
+        let Some(definition) = find_enum(abi_tokens, &r.enum_path) else {
+            continue;
+        };
+
+        if definition.r#type != CompositeType::Enum {
+            errors.push(format!("result enum `{}`: not an enum", r.enum_path));
+            continue;
+        }
+
+        if definition.is_event {
+            errors.push(format!(
+                "result enum `{}`: an event can't be mapped to `Result`",
+                r.enum_path
+            ));
+        }
+
+        let names: Vec<&str> = definition.inners.iter().map(|v| v.name.as_str()).collect();
+        if names != [r.ok.as_str(), r.err.as_str()] {
+            errors.push(format!(
+                "result enum `{}`: expected the variants `{}` and `{}` in this order (serialized as `Ok` and `Err`), found {}",
+                r.enum_path,
+                r.ok,
+                r.err,
+                names
+                    .iter()
+                    .map(|n| format!("`{}`", n))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+    }
+
+    errors
+}
+
+/// Returns the tokens of the ABI where the enums are replaced by `Result`,
+/// and removed from the enums to generate.
+///
+/// # Arguments
+///
+/// * `abi_tokens` - The tokens of the contract ABI.
+/// * `result_enums` - The enums mapped to `Result`, already checked.
+pub(crate) fn apply_result_enums(
+    abi_tokens: &TokenizedAbi,
+    result_enums: &[ResultEnum],
+) -> TokenizedAbi {
+    let mut abi_tokens = abi_tokens.clone();
+
+    // The definitions, with the generic arguments as `Token::GenericArg`.
+    let definitions: Vec<Composite> = result_enums
+        .iter()
+        .filter_map(|r| find_enum(&abi_tokens, &r.enum_path).cloned())
+        .collect();
+
+    abi_tokens.enums.retain(|t| {
+        !definitions
+            .iter()
+            .any(|d| d.type_path_no_generic() == t.type_path())
+    });
+
+    let tokens = abi_tokens
+        .structs
+        .iter_mut()
+        .chain(abi_tokens.enums.iter_mut())
+        .chain(abi_tokens.functions.iter_mut())
+        .chain(abi_tokens.interfaces.values_mut().flatten())
+        .chain(abi_tokens.l1_handlers.iter_mut())
+        .chain(abi_tokens.constructor.iter_mut());
+
+    for token in tokens {
+        replace_enums(token, &definitions);
+    }
+
+    abi_tokens
+}
+
+fn find_enum<'a>(abi_tokens: &'a TokenizedAbi, enum_path: &str) -> Option<&'a Composite> {
+    abi_tokens
+        .enums
+        .iter()
+        .chain(abi_tokens.structs.iter())
+        .filter_map(|t| t.to_composite().ok())
+        .find(|c| c.type_path_no_generic() == enum_path)
+}
+
+/// Recursively replaces the references to the enums by `Result`.
+fn replace_enums(token: &mut Token, definitions: &[Composite]) {
+    match token {
+        Token::Composite(c) => {
+            for i in &mut c.inners {
+                replace_enums(&mut i.token, definitions);
+            }
+
+            for (_, g) in &mut c.generic_args {
+                replace_enums(g, definitions);
+            }
+
+            let type_path = c.type_path_no_generic();
+            if let Some(definition) = definitions
+                .iter()
+                .find(|d| d.type_path_no_generic() == type_path)
+            {
+                *token = to_result(definition, &c.generic_args);
+            }
+        }
+        Token::Array(a) => replace_enums(&mut a.inner, definitions),
+        Token::Tuple(t) => {
+            for i in &mut t.inners {
+                replace_enums(i, definitions);
+            }
+        }
+        Token::Function(f) => {
+            for (_, i) in f.inputs.iter_mut().chain(f.named_outputs.iter_mut()) {
+                replace_enums(i, definitions);
+            }
+
+            for o in &mut f.outputs {
+                replace_enums(o, definitions);
+            }
+        }
+        Token::CoreBasic(_) | Token::GenericArg(_) => (),
+    }
+}
+
+/// Returns the `Result` of the ok and err variants of the enum, with the generic
+/// arguments of the reference to the enum.
+fn to_result(definition: &Composite, generic_args: &[(String, Token)]) -> Token {
+    let variant = |index: usize| {
+        let mut token = definition.inners[index].token.clone();
+        resolve_generic_args(&mut token, generic_args);
+        token
+    };
+
+    let (ok, err) = (variant(0), variant(1));
+
+    Token::Composite(Composite {
+        type_path: format!(
+            "{}::<{}, {}>",
+            RESULT_TYPE_PATH,
+            ok.type_path_with_generics(),
+            err.type_path_with_generics()
+        ),
+        inners: vec![],
+        generic_args: vec![("A".to_string(), ok), ("B".to_string(), err)],
+        r#type: CompositeType::Enum,
+        is_event: false,
+        alias: None,
+    })
+}
+
+/// Replaces the generic arguments of the definition of the enum by the given ones.
+fn resolve_generic_args(token: &mut Token, generic_args: &[(String, Token)]) {
+    match token {
+        Token::GenericArg(name) => {
+            if let Some((_, arg)) = generic_args.iter().find(|(n, _)| n == name) {
+                *token = arg.clone();
+            }
+        }
+        Token::Array(a) => resolve_generic_args(&mut a.inner, generic_args),
+        Token::Tuple(t) => {
+            for i in &mut t.inners {
+                resolve_generic_args(i, generic_args);
+            }
+        }
+        Token::Composite(c) => {
+            for (_, g) in &mut c.generic_args {
+                resolve_generic_args(g, generic_args);
+            }
+        }
+        Token::CoreBasic(_) | Token::Function(_) => (),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cainome_parser::AbiParser;
+    use std::collections::HashMap;
+
+    use crate::expand::types::CairoToRust;
+
+    const ABI: &str = r#"[
+        {
+            "type": "enum",
+            "name": "pkg::Outcome::<core::integer::u64, pkg::Error>",
+            "variants": [
+                { "name": "Success", "type": "core::integer::u64" },
+                { "name": "Failure", "type": "pkg::Error" }
+            ]
+        },
+        {
+            "type": "enum",
+            "name": "pkg::Error",
+            "variants": [
+                { "name": "Expired", "type": "()" },
+                { "name": "Unknown", "type": "core::felt252" }
+            ]
+        },
+        {
+            "type": "struct",
+            "name": "pkg::Receipt",
+            "members": [
+                { "name": "outcome", "type": "pkg::Outcome::<core::integer::u64, pkg::Error>" }
+            ]
+        },
+        {
+            "type": "function",
+            "name": "settle",
+            "inputs": [{ "name": "id", "type": "core::felt252" }],
+            "outputs": [{ "type": "pkg::Outcome::<core::integer::u64, pkg::Error>" }],
+            "state_mutability": "external"
+        }
+    ]"#;
+
+    fn outcome() -> ResultEnum {
+        ResultEnum {
+            enum_path: "pkg::Outcome".to_string(),
+            ok: "Success".to_string(),
+            err: "Failure".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_check_result_enums() {
+        let tokens = AbiParser::tokens_from_abi_string(ABI, &HashMap::new()).unwrap();
+
+        assert!(check_result_enums(&[outcome()], &tokens).is_empty());
+
+        let reversed = ResultEnum {
+            enum_path: "pkg::Error".to_string(),
+            ok: "Unknown".to_string(),
+            err: "Expired".to_string(),
+        };
+        let missing = ResultEnum {
+            enum_path: "pkg::Missing".to_string(),
+            ..outcome()
+        };
+
+        assert_eq!(
+            check_result_enums(&[outcome(), outcome(), reversed, missing], &tokens),
+            vec![
+                "result enum `pkg::Outcome` declared twice",
+                "result enum `pkg::Error`: expected the variants `Unknown` and `Expired` in this order (serialized as `Ok` and `Err`), found `Expired`, `Unknown`",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_apply_result_enums() {
+        let tokens = AbiParser::tokens_from_abi_string(ABI, &HashMap::new()).unwrap();
+        let tokens = apply_result_enums(&tokens, &[outcome()]);
+
+        assert!(!tokens.enums.iter().any(|t| t.type_path() == "pkg::Outcome"));
+
+        let receipt = tokens.structs[0].to_composite().unwrap();
+        assert_eq!(receipt.inners[0].token.to_rust_type(), "Result<u64,Error>");
+
+        let settle = tokens.functions[0].to_function().unwrap();
+        assert_eq!(settle.outputs[0].to_rust_type(), "Result<u64,Error>");
+    }
+}
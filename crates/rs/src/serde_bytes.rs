@@ -0,0 +1,47 @@
+/// Encoding of the bytes serialized with serde.
+
+/// The encoding of the bytes (`Vec<u8>` and `ByteArray` members) in the serde
+/// implementations of the generated types. The Cairo serialization is not affected.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BytesEncoding {
+    /// The default serde implementations: the `Vec<u8>` are arrays of numbers,
+    /// and the `ByteArray` are their Cairo representation.
+    #[default]
+    Array,
+    /// The bytes are serialized as `0x` prefixed hex strings.
+    Hex,
+    /// The bytes are serialized as base64 strings (standard alphabet, with padding).
+    Base64,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseBytesEncodingError {
+    invalid_value: String,
+}
+
+impl std::fmt::Display for ParseBytesEncodingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Invalid bytes encoding '{}'. Supported values are 'array', 'hex' or 'base64'.",
+            self.invalid_value
+        )
+    }
+}
+
+impl std::error::Error for ParseBytesEncodingError {}
+
+impl std::str::FromStr for BytesEncoding {
+    type Err = ParseBytesEncodingError;
+
+    fn from_str(input: &str) -> Result<BytesEncoding, Self::Err> {
+        match input {
+            "array" => Ok(BytesEncoding::Array),
+            "hex" => Ok(BytesEncoding::Hex),
+            "base64" => Ok(BytesEncoding::Base64),
+            _ => Err(ParseBytesEncodingError {
+                invalid_value: input.to_string(),
+            }),
+        }
+    }
+}
@@ -0,0 +1,47 @@
+/// Radix of the integers and felts serialized with serde.
+
+/// The radix used by the serde implementations of the generated types.
+///
+/// With both radixes, the hex (`0x` prefixed) and the decimal strings are accepted
+/// when deserializing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SerdeRadix {
+    /// The integers wider than `u32` are serialized as hex strings, the felts use
+    /// their default serialization (hex strings).
+    #[default]
+    Hex,
+    /// The integers wider than `u32` and the felts are serialized as decimal strings,
+    /// as expected by some JSON APIs (like indexers).
+    Decimal,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseSerdeRadixError {
+    invalid_value: String,
+}
+
+impl std::fmt::Display for ParseSerdeRadixError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Invalid serde radix '{}'. Supported values are 'hex', 'dec' or 'decimal'.",
+            self.invalid_value
+        )
+    }
+}
+
+impl std::error::Error for ParseSerdeRadixError {}
+
+impl std::str::FromStr for SerdeRadix {
+    type Err = ParseSerdeRadixError;
+
+    fn from_str(input: &str) -> Result<SerdeRadix, Self::Err> {
+        match input {
+            "hex" => Ok(SerdeRadix::Hex),
+            "dec" | "decimal" => Ok(SerdeRadix::Decimal),
+            _ => Err(ParseSerdeRadixError {
+                invalid_value: input.to_string(),
+            }),
+        }
+    }
+}
@@ -0,0 +1,245 @@
+//! Types shared by a group of contracts.
+//!
+//! Large projects bind many contracts defining the same types (the types of a common
+//! package, or the events of a component). Instead of generating them in the bindings
+//! of each contract, the types defined identically by several contracts of a group are
+//! generated once, and re-exported by the bindings of these contracts.
+use cainome_parser::tokens::{Composite, CompositeType, Token};
+use cainome_parser::TokenizedAbi;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use crate::expand::conversion::{composite_definitions, referenced_types};
+
+/// The types defined identically by several contracts of a group.
+#[derive(Debug, Clone, Default)]
+pub struct SharedTypes {
+    /// The structs and enums generated once for the group.
+    pub tokens: TokenizedAbi,
+    /// For each contract of the group, the type paths (without generic arguments)
+    /// of its types which are shared.
+    pub by_contract: Vec<BTreeSet<String>>,
+}
+
+impl SharedTypes {
+    /// Returns the types defined identically (same path and same members) by at least
+    /// two of the contracts, including the types of their members.
+    ///
+    /// The builtins are not generated, and the generic types are never shared.
+    ///
+    /// # Arguments
+    ///
+    /// * `abis` - The tokenized ABIs of the contracts of the group.
+    pub fn new(abis: &[&TokenizedAbi]) -> Self {
+        let definitions: Vec<HashMap<String, &Composite>> =
+            abis.iter().map(|abi| composite_definitions(abi)).collect();
+
+        // The definition of a type is the first one also found in an other contract.
+        let mut references: BTreeMap<String, &Composite> = BTreeMap::new();
+
+        for (i, defs) in definitions.iter().enumerate() {
+            for (type_path, composite) in defs {
+                if composite.is_builtin()
+                    || composite.is_generic()
+                    || references.contains_key(type_path)
+                {
+                    continue;
+                }
+
+                let found_elsewhere = definitions
+                    .iter()
+                    .enumerate()
+                    .any(|(j, other)| j != i && other.get(type_path) == Some(composite));
+
+                if found_elsewhere {
+                    references.insert(type_path.clone(), composite);
+                }
+            }
+        }
+
+        let mut by_contract: Vec<BTreeSet<String>> = definitions
+            .iter()
+            .map(|defs| {
+                references
+                    .iter()
+                    .filter(|(type_path, c)| defs.get(*type_path) == Some(*c))
+                    .map(|(type_path, _)| type_path.clone())
+                    .collect()
+            })
+            .collect();
+
+        // A type is only shared by a contract if the types of its members are too,
+        // and a type is only shared if at least two contracts share it.
+        loop {
+            let mut changed = false;
+
+            for (shared, defs) in by_contract.iter_mut().zip(&definitions) {
+                let before = shared.len();
+                let snapshot = shared.clone();
+
+                shared.retain(|type_path| {
+                    defs[type_path].inners.iter().all(|inner| {
+                        referenced_types(&inner.token)
+                            .iter()
+                            .all(|r| !defs.contains_key(r) || snapshot.contains(r))
+                    })
+                });
+
+                changed |= shared.len() != before;
+            }
+
+            for type_path in references.keys() {
+                let count = by_contract.iter().filter(|s| s.contains(type_path)).count();
+
+                if count == 1 {
+                    for shared in by_contract.iter_mut() {
+                        shared.remove(type_path);
+                    }
+                    changed = true;
+                }
+            }
+
+            if !changed {
+                break;
+            }
+        }
+
+        let mut tokens = TokenizedAbi::default();
+
+        for (type_path, composite) in references {
+            if !by_contract.iter().any(|s| s.contains(&type_path)) {
+                continue;
+            }
+
+            let token = Token::Composite(composite.clone());
+            if composite.r#type == CompositeType::Enum {
+                tokens.enums.push(token);
+            } else {
+                tokens.structs.push(token);
+            }
+        }
+
+        Self {
+            tokens,
+            by_contract,
+        }
+    }
+
+    /// Returns the shared types of the contract at the given index in the group,
+    /// with the path of the module generating them, as expected by
+    /// [`crate::ExpandOptions::shared_types`].
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the contract in the group.
+    /// * `module` - The path of the module generating the shared types (like `super::common`).
+    pub fn for_contract(&self, index: usize, module: &str) -> BTreeMap<String, String> {
+        self.by_contract
+            .get(index)
+            .map(|shared| {
+                shared
+                    .iter()
+                    .map(|type_path| (type_path.clone(), module.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cainome_parser::AbiParser;
+
+    fn tokens(abi: &str) -> TokenizedAbi {
+        AbiParser::tokens_from_abi_string(abi, &HashMap::new()).unwrap()
+    }
+
+    fn abi(price_type: &str) -> String {
+        format!(
+            r#"[
+                {{
+                    "type": "struct",
+                    "name": "pkg::Price",
+                    "members": [{{ "name": "value", "type": "{price_type}" }}]
+                }},
+                {{
+                    "type": "struct",
+                    "name": "pkg::Order",
+                    "members": [
+                        {{ "name": "price", "type": "pkg::Price" }},
+                        {{ "name": "amount", "type": "core::integer::u256" }}
+                    ]
+                }},
+                {{
+                    "type": "struct",
+                    "name": "pkg::Fill",
+                    "members": [{{ "name": "id", "type": "core::felt252" }}]
+                }},
+                {{
+                    "type": "function",
+                    "name": "place",
+                    "inputs": [
+                        {{ "name": "order", "type": "pkg::Order" }},
+                        {{ "name": "fill", "type": "pkg::Fill" }}
+                    ],
+                    "outputs": [],
+                    "state_mutability": "external"
+                }}
+            ]"#
+        )
+    }
+
+    #[test]
+    fn test_shared_types() {
+        let a = tokens(&abi("core::felt252"));
+        let b = tokens(&abi("core::felt252"));
+        let c = tokens(&abi("core::integer::u128"));
+
+        let shared = SharedTypes::new(&[&a, &b, &c]);
+
+        let names: Vec<String> = shared
+            .tokens
+            .structs
+            .iter()
+            .map(|t| t.type_path())
+            .collect();
+        assert_eq!(names, vec!["pkg::Fill", "pkg::Order", "pkg::Price"]);
+
+        let all = BTreeSet::from([
+            "pkg::Fill".to_string(),
+            "pkg::Order".to_string(),
+            "pkg::Price".to_string(),
+        ]);
+        assert_eq!(shared.by_contract[0], all);
+        assert_eq!(shared.by_contract[1], all);
+        // `Price` differs, hence `Order` referencing it too.
+        assert_eq!(
+            shared.by_contract[2],
+            BTreeSet::from(["pkg::Fill".to_string()])
+        );
+
+        assert_eq!(
+            shared.for_contract(2, "super::common"),
+            BTreeMap::from([("pkg::Fill".to_string(), "super::common".to_string())])
+        );
+    }
+
+    #[test]
+    fn test_shared_types_single_contract() {
+        let a = tokens(&abi("core::felt252"));
+        let c = tokens(&abi("core::integer::u128"));
+
+        // `Fill` is shared, but `Order` is not: its `Price` differs.
+        let shared = SharedTypes::new(&[&a, &c]);
+        assert_eq!(
+            shared.by_contract,
+            vec![
+                BTreeSet::from(["pkg::Fill".to_string()]),
+                BTreeSet::from(["pkg::Fill".to_string()])
+            ]
+        );
+        assert_eq!(shared.tokens.structs.len(), 1);
+
+        assert!(SharedTypes::new(&[&a]).tokens.structs.is_empty());
+    }
+}
@@ -0,0 +1,282 @@
+//! Fixed tuples generated as named structs.
+//!
+//! Some ABIs use fixed tuples heavily, like `(felt252, felt252, felt252)` for the vectors
+//! of the game math. Mapping such a tuple to a named struct (`Vec3 { x, y, z }`) generates
+//! the struct, used everywhere the tuple was. The struct is serialized like the tuple.
+use cainome_parser::tokens::{Composite, CompositeInner, CompositeInnerKind, CompositeType, Token};
+use cainome_parser::TokenizedAbi;
+use serde::{Deserialize, Serialize};
+
+/// The module of the type paths of the generated structs, which are not part of the ABI.
+const TUPLE_STRUCTS_MODULE: &str = "cainome::tuple_structs";
+
+/// A fixed tuple generated as a named struct.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TupleStruct {
+    /// The Cairo type path of the tuple (`(core::felt252, core::felt252, core::felt252)`).
+    pub tuple: String,
+    /// The name of the generated struct (`Vec3`).
+    pub name: String,
+    /// The names of the fields, one for each element of the tuple (`["x", "y", "z"]`).
+    pub fields: Vec<String>,
+}
+
+impl TupleStruct {
+    /// Returns the struct token replacing the tuple.
+    fn to_token(&self) -> Result<Token, String> {
+        let tuple = match Token::parse(&self.tuple) {
+            Ok(Token::Tuple(t)) => t,
+            _ => {
+                return Err(format!(
+                    "tuple struct `{}`: `{}` is not a tuple",
+                    self.name, self.tuple
+                ))
+            }
+        };
+
+        if tuple.inners.len() != self.fields.len() {
+            return Err(format!(
+                "tuple struct `{}`: {} fields given for a tuple of {} elements",
+                self.name,
+                self.fields.len(),
+                tuple.inners.len()
+            ));
+        }
+
+        let inners = tuple
+            .inners
+            .into_iter()
+            .zip(&self.fields)
+            .enumerate()
+            .map(|(index, (token, name))| CompositeInner {
+                index,
+                name: name.clone(),
+                kind: CompositeInnerKind::NotUsed,
+                token,
+            })
+            .collect();
+
+        Ok(Token::Composite(Composite {
+            type_path: format!("{}::{}", TUPLE_STRUCTS_MODULE, self.name),
+            inners,
+            generic_args: vec![],
+            r#type: CompositeType::Struct,
+            is_event: false,
+            alias: Some(self.name.clone()),
+        }))
+    }
+}
+
+/// Checks the tuple structs against the ABI, and returns the errors.
+///
+/// # Arguments
+///
+/// * `tuple_structs` - The tuples generated as named structs.
+/// * `abi_tokens` - The tokens of the contract ABI.
+pub fn check_tuple_structs(
+    tuple_structs: &[TupleStruct],
+    abi_tokens: &TokenizedAbi,
+) -> Vec<String> {
+    let mut errors = vec![];
+
+    for (i, t) in tuple_structs.iter().enumerate() {
+        let previous = &tuple_structs[..i];
+
+        if previous.iter().any(|other| other.name == t.name) {
+            errors.push(format!("tuple struct `{}` declared twice", t.name));
+        }
+
+        if previous
+            .iter()
+            .any(|other| without_whitespaces(&other.tuple) == without_whitespaces(&t.tuple))
+        {
+            errors.push(format!(
+                "tuple struct `{}`: `{}` already mapped to a struct",
+                t.name, t.tuple
+            ));
+        }
+
+        if syn::parse_str::<syn::Ident>(&t.name).is_err() {
+            errors.push(format!("tuple struct `{}`: invalid name", t.name));
+        }
+
+        for (j, field) in t.fields.iter().enumerate() {
+            if syn::parse_str::<syn::Ident>(field).is_err() {
+                errors.push(format!(
+                    "tuple struct `{}`: invalid field name `{}`",
+                    t.name, field
+                ));
+            }
+
+            if t.fields[..j].contains(field) {
+                errors.push(format!(
+                    "tuple struct `{}`: field `{}` declared twice",
+                    t.name, field
+                ));
+            }
+        }
+
+        let collides = abi_tokens
+            .structs
+            .iter()
+            .chain(abi_tokens.enums.iter())
+            .filter_map(|t| t.to_composite().ok())
+            .any(|c| !c.is_builtin() && c.type_name_or_alias() == t.name);
+
+        if collides {
+            errors.push(format!(
+                "tuple struct `{}`: a type of the ABI has the same name",
+                t.name
+            ));
+        }
+
+        if let Err(e) = t.to_token() {
+            errors.push(e);
+        }
+    }
+
+    errors
+}
+
+/// Returns the tokens of the ABI where the tuples are replaced by their structs,
+/// which are added to the structs of the ABI.
+///
+/// # Arguments
+///
+/// * `abi_tokens` - The tokens of the contract ABI.
+/// * `tuple_structs` - The tuples generated as named structs, already checked.
+pub(crate) fn apply_tuple_structs(
+    abi_tokens: &TokenizedAbi,
+    tuple_structs: &[TupleStruct],
+) -> TokenizedAbi {
+    let mut abi_tokens = abi_tokens.clone();
+
+    let replacements: Vec<(&str, Token)> = tuple_structs
+        .iter()
+        .map(|t| (t.tuple.as_str(), t.to_token().expect("valid tuple struct")))
+        .collect();
+
+    let mut structs: Vec<Token> = replacements.iter().map(|(_, s)| s.clone()).collect();
+
+    let tokens = abi_tokens
+        .structs
+        .iter_mut()
+        .chain(abi_tokens.enums.iter_mut())
+        .chain(abi_tokens.functions.iter_mut())
+        .chain(abi_tokens.interfaces.values_mut().flatten())
+        .chain(abi_tokens.l1_handlers.iter_mut())
+        .chain(abi_tokens.constructor.iter_mut())
+        // The tuples nested in the tuple structs are also replaced.
+        .chain(structs.iter_mut());
+
+    for token in tokens {
+        for (tuple, replacement) in &replacements {
+            token.replace_tuple(tuple, replacement);
+        }
+    }
+
+    abi_tokens.structs.extend(structs);
+    abi_tokens
+}
+
+fn without_whitespaces(s: &str) -> String {
+    s.chars().filter(|c| !c.is_whitespace()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cainome_parser::AbiParser;
+    use std::collections::HashMap;
+
+    const ABI: &str = r#"[
+        {
+            "type": "struct",
+            "name": "pkg::Player",
+            "members": [
+                { "name": "position", "type": "(core::felt252, core::felt252, core::felt252)" }
+            ]
+        },
+        {
+            "type": "function",
+            "name": "move_to",
+            "inputs": [
+                { "name": "path", "type": "core::array::Array::<(core::felt252, core::felt252, core::felt252)>" }
+            ],
+            "outputs": [{ "type": "pkg::Player" }],
+            "state_mutability": "external"
+        }
+    ]"#;
+
+    fn vec3() -> TupleStruct {
+        TupleStruct {
+            tuple: "(core::felt252, core::felt252, core::felt252)".to_string(),
+            name: "Vec3".to_string(),
+            fields: vec!["x".to_string(), "y".to_string(), "z".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_check_tuple_structs() {
+        let tokens = AbiParser::tokens_from_abi_string(ABI, &HashMap::new()).unwrap();
+
+        assert!(check_tuple_structs(&[vec3()], &tokens).is_empty());
+
+        let player = TupleStruct {
+            tuple: "(u8, u8)".to_string(),
+            name: "Player".to_string(),
+            fields: vec!["a".to_string()],
+        };
+
+        assert_eq!(
+            check_tuple_structs(&[vec3(), vec3(), player], &tokens),
+            vec![
+                "tuple struct `Vec3` declared twice",
+                "tuple struct `Vec3`: `(core::felt252, core::felt252, core::felt252)` already mapped to a struct",
+                "tuple struct `Player`: a type of the ABI has the same name",
+                "tuple struct `Player`: 1 fields given for a tuple of 2 elements",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_apply_tuple_structs() {
+        let tokens = AbiParser::tokens_from_abi_string(ABI, &HashMap::new()).unwrap();
+        let tokens = apply_tuple_structs(&tokens, &[vec3()]);
+
+        let vec3 = tokens
+            .structs
+            .iter()
+            .find(|t| t.type_path() == "cainome::tuple_structs::Vec3")
+            .unwrap()
+            .to_composite()
+            .unwrap();
+        assert_eq!(vec3.type_name_or_alias(), "Vec3");
+        assert_eq!(vec3.inners[2].name, "z");
+
+        let player = tokens
+            .structs
+            .iter()
+            .find(|t| t.type_path() == "pkg::Player")
+            .unwrap()
+            .to_composite()
+            .unwrap();
+        assert_eq!(
+            player.inners[0].token.type_path(),
+            "cainome::tuple_structs::Vec3"
+        );
+
+        let move_to = tokens.functions[0].to_function().unwrap();
+        match &move_to.inputs[0].1 {
+            Token::Array(a) => assert_eq!(a.inner.type_path(), "cainome::tuple_structs::Vec3"),
+            _ => panic!("array expected"),
+        }
+        // The output is the hydrated `Player`, also using the struct.
+        assert_eq!(
+            move_to.outputs[0].to_composite().unwrap().inners[0]
+                .token
+                .type_path(),
+            "cainome::tuple_structs::Vec3"
+        );
+    }
+}
@@ -1,31 +1,53 @@
 //! Cainome CLI arguments.
 //!
-use cainome_rs::ExecutionVersion;
+use cainome_rs::{BytesEncoding, ExecutionVersion, GenerationMode, SerdeRadix};
 use camino::Utf8PathBuf;
 use clap::{Args, Parser};
 use starknet::core::types::Felt;
+use std::collections::HashMap;
 use url::Url;
 
+use crate::commands::Commands;
 use crate::plugins::builtins::BuiltinPlugins;
-use crate::plugins::PluginManager;
+use crate::plugins::{parse_formatter, parse_plugin_out, PluginManager};
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 #[command(propagate_version = true)]
+#[command(args_conflicts_with_subcommands = true)]
+#[command(subcommand_negates_reqs = true)]
 pub struct CainomeArgs {
+    #[command(subcommand)]
+    pub command: Option<Commands>,
+
     #[arg(long)]
     #[arg(value_name = "OUTPUT_DIR")]
+    #[arg(required = true)]
     #[arg(help = "Directory where bindings files must be written.")]
-    pub output_dir: Utf8PathBuf,
+    pub output_dir: Option<Utf8PathBuf>,
 
     #[arg(long)]
     #[arg(value_name = "PATH")]
     #[arg(conflicts_with = "contract_address")]
     #[arg(
-        help = "Path where artifacts are located. Cainome will parse all the files that are a valid Sierra artifact."
+        help = "Path where artifacts are located. Cainome will parse all the files that are a valid Sierra artifact, or a Cairo 0 artifact (compiled contract or bare ABI)."
     )]
     pub artifacts_path: Option<Utf8PathBuf>,
 
+    #[arg(long)]
+    #[arg(value_name = "PATH")]
+    #[arg(conflicts_with_all = ["artifacts_path", "contract_address", "merge_abi"])]
+    #[arg(
+        help = "Path of the Scarb.toml of a Scarb workspace (or package). Cainome parses the contracts built by `scarb build` for all the packages of the workspace, named after their Cairo module."
+    )]
+    pub scarb_manifest: Option<Utf8PathBuf>,
+
+    #[arg(long)]
+    #[arg(value_name = "PROFILE")]
+    #[arg(requires = "scarb_manifest")]
+    #[arg(help = "Profile the Scarb contracts were built with, 'dev' by default.")]
+    pub scarb_profile: Option<String>,
+
     #[arg(long)]
     #[arg(value_name = "PATH")]
     #[arg(help = "Path of a JSON file defining Cainome parsing configuration.")]
@@ -39,19 +61,41 @@ pub struct CainomeArgs {
     #[arg(help = "Address of the contract to fetch the ABI from.")]
     pub contract_address: Option<Felt>,
 
+    #[arg(long)]
+    #[arg(value_name = "PATH")]
+    #[arg(conflicts_with_all = ["artifacts_path", "scarb_manifest", "contract_address", "merge_abi"])]
+    #[arg(
+        help = "Path of a manifest (JSON, or TOML with the `.toml` extension) listing the contracts to fetch from the chain, as `{ name, address, network }` entries. The network is the name of an entry of the `networks` of the manifest, an RPC url, or `--rpc-url` when not given. The contracts are fetched concurrently, at most 8 at a time."
+    )]
+    pub contracts_manifest: Option<Utf8PathBuf>,
+
     #[arg(long)]
     #[arg(value_name = "NAME")]
-    #[arg(requires = "contract_address")]
-    #[arg(requires = "rpc_url")]
-    #[arg(help = "Name of the contract.")]
+    #[arg(help = "Name of the contract, when fetched from the chain or merged from ABI files.")]
     pub contract_name: Option<String>,
 
     #[arg(long)]
-    #[arg(value_name = "URL")]
-    #[arg(requires = "contract_address")]
+    #[arg(value_name = "PATH")]
+    #[arg(conflicts_with_all = ["artifacts_path", "contract_address"])]
     #[arg(requires = "contract_name")]
-    #[arg(conflicts_with = "artifacts_path")]
-    #[arg(help = "The Starknet RPC provider to fetch the ABI from.")]
+    #[arg(
+        help = "Path of an ABI (or Sierra class) to merge into a single contract, can be repeated. Types defined in several files are de-duplicated, the first definition wins on conflict."
+    )]
+    pub merge_abi: Vec<Utf8PathBuf>,
+
+    #[arg(long)]
+    #[arg(value_name = "PATH")]
+    #[arg(
+        help = "Path of an ABI (or Sierra class) providing the structs and enums referenced but not defined in the parsed ABIs, can be repeated."
+    )]
+    pub extra_types: Vec<Utf8PathBuf>,
+
+    #[arg(long)]
+    #[arg(value_name = "URL")]
+    #[arg(conflicts_with_all = ["artifacts_path", "scarb_manifest", "merge_abi"])]
+    #[arg(
+        help = "The Starknet RPC provider to fetch the ABI from, the default network of the contracts of `--contracts-manifest`."
+    )]
     pub rpc_url: Option<Url>,
 
     #[command(flatten)]
@@ -60,8 +104,12 @@ pub struct CainomeArgs {
 
     #[arg(long)]
     #[arg(value_name = "EXECUTION_VERSION")]
-    #[arg(help = "The execution version to use. Supported values are 'v1', 'V1', 'v3', or 'V3'.")]
-    pub execution_version: ExecutionVersion,
+    #[arg(required = true)]
+    #[arg(env = "CAINOME_EXECUTION_VERSION")]
+    #[arg(
+        help = "The execution version to use. Supported values are 'v1' or 'v3' (case insensitive)."
+    )]
+    pub execution_version: Option<ExecutionVersion>,
 
     #[arg(long)]
     #[arg(value_name = "DERIVES")]
@@ -72,6 +120,95 @@ pub struct CainomeArgs {
     #[arg(value_name = "CONTRACT_DERIVES")]
     #[arg(help = "Derives to be added to the generated contract.")]
     pub contract_derives: Option<Vec<String>>,
+
+    #[arg(long)]
+    #[arg(
+        help = "Generate blocking facades of the contracts, available with the `blocking` feature of the crate including the bindings."
+    )]
+    pub blocking: bool,
+
+    #[arg(long)]
+    #[arg(
+        help = "Derive `arbitrary::Arbitrary` for the generated types, available with the `arbitrary` feature of the crate including the bindings."
+    )]
+    pub arbitrary: bool,
+
+    #[arg(long)]
+    #[arg(value_name = "COUNT")]
+    #[arg(
+        help = "Functions with more parameters than COUNT also get a `<Function>Params` struct and a `<function>_with(&params)` variant taking it."
+    )]
+    pub params_struct_threshold: Option<usize>,
+
+    #[arg(long)]
+    #[arg(
+        help = "Generate a `<contract>_prelude` module in the bindings of each contract, re-exporting flatly the contract, the reader, the types and the events. The rust plugin also writes a `prelude.rs` file re-exporting the preludes of all the contracts."
+    )]
+    pub prelude: bool,
+
+    #[arg(long)]
+    #[arg(
+        help = "Generate a `<contract>_roundtrip_tests` module in the bindings, testing the serialization round-trip of each generated type with a sample value."
+    )]
+    pub roundtrip_tests: bool,
+
+    #[arg(long)]
+    #[arg(value_name = "RADIX")]
+    #[arg(default_value = "hex")]
+    #[arg(
+        help = "Radix of the integers wider than u32 and the felts in the serde implementations of the generated types. Supported values are 'hex' or 'dec' (felts included), both are accepted when deserializing."
+    )]
+    pub serde_radix: SerdeRadix,
+
+    #[arg(long)]
+    #[arg(value_name = "ENCODING")]
+    #[arg(default_value = "array")]
+    #[arg(
+        help = "Encoding of the bytes (Vec<u8> and ByteArray members) in the serde implementations of the generated types. Supported values are 'array', 'hex' or 'base64'."
+    )]
+    pub serde_bytes: BytesEncoding,
+
+    #[arg(long)]
+    #[arg(
+        help = "Generate the SRC5 ids of the interfaces of the ABI as constants, and the methods probing the contract for them (like `supports_ierc20()`)."
+    )]
+    pub src5: bool,
+
+    #[arg(long)]
+    #[arg(
+        help = "Make the externals return an `Invoke`, which can be sponsored by a paymaster (`.sponsored(&paymaster)`) instead of being executed by the account."
+    )]
+    pub paymaster: bool,
+
+    #[arg(long)]
+    #[arg(value_name = "MODE")]
+    #[arg(default_value = "full")]
+    #[arg(
+        help = "Parts of the bindings generated by the rust plugin. Supported values are 'full' (the default), 'decode' (only the types and the decoding of the events) or 'encode' (only the types and the encoding of the calls to the externals, with a `<Contract>Calls` struct)."
+    )]
+    pub generation_mode: GenerationMode,
+
+    #[arg(long)]
+    #[arg(requires = "rust")]
+    #[arg(
+        help = "Also write an `examples/<contract>.rs` program into the output directory, calling each view and invoking one external of the contract with placeholder values (rust plugin)."
+    )]
+    pub emit_examples: bool,
+
+    #[arg(long)]
+    #[arg(requires = "rust")]
+    #[arg(
+        help = "Generate `From` implementations between the types defined identically (same path and same members) by several contracts. The bindings files are expected to be sibling modules (rust plugin)."
+    )]
+    pub identical_types_conversions: bool,
+
+    #[arg(long)]
+    #[arg(requires = "rust")]
+    #[arg(conflicts_with = "identical_types_conversions")]
+    #[arg(
+        help = "Generate the types defined identically (same path and same members) by several contracts once, in a `types.rs` module re-exported by the bindings of the contracts. The bindings files are expected to be sibling modules (rust plugin)."
+    )]
+    pub shared_types: bool,
 }
 
 #[derive(Debug, Args, Clone)]
@@ -79,6 +216,52 @@ pub struct PluginOptions {
     #[arg(long)]
     #[arg(help = "Generate bindings for rust (built-in).")]
     pub rust: bool,
+
+    #[arg(long)]
+    #[arg(value_name = "PATH")]
+    #[arg(requires = "rust")]
+    #[arg(
+        help = "Directory where the rust bindings must be written, instead of the output directory."
+    )]
+    pub rust_out: Option<Utf8PathBuf>,
+
+    #[arg(long)]
+    #[arg(
+        help = "Generate a report of the calldata sizes of the entrypoints (markdown and JSON), instead of bindings."
+    )]
+    pub calldata_report: bool,
+
+    #[arg(long)]
+    #[arg(
+        help = "Generate a manifest of the session policies of the externals (JSON), for the wallets with session keys, instead of bindings."
+    )]
+    pub session_policies: bool,
+
+    #[arg(long)]
+    #[arg(
+        help = "Generate a report of the coverage of the ABI entries by the rust bindings (JSON), listing the entries generated and the reason of the skipped ones, instead of bindings."
+    )]
+    pub coverage_report: bool,
+    // cainome:new-plugin:options
+    #[arg(long = "formatter")]
+    #[arg(value_name = "PLUGIN=COMMAND")]
+    #[arg(value_parser = parse_formatter)]
+    #[arg(
+        help = "Command formatting the files generated by a plugin (e.g. `rust=rustfmt --edition 2021`), can be repeated. An empty command disables the formatting for the plugin."
+    )]
+    pub formatters: Vec<(String, String)>,
+
+    #[arg(long = "plugin-out")]
+    #[arg(value_name = "PLUGIN=PATH")]
+    #[arg(value_parser = parse_plugin_out)]
+    #[arg(
+        help = "Directory where the files of a plugin must be written instead of the output directory (e.g. `rust=src/bindings`), can be repeated."
+    )]
+    pub plugin_outs: Vec<(String, Utf8PathBuf)>,
+
+    #[arg(long)]
+    #[arg(help = "Don't format the generated files.")]
+    pub no_format: bool,
     // TODO: For custom plugin, we can add a vector of strings,
     // where the user provides the name of the plugin.
     // Then cainome like protobuf will attempt to execute cainome_plugin_<NAME>.
@@ -94,9 +277,31 @@ impl From<PluginOptions> for PluginManager {
             builtin_plugins.push(BuiltinPlugins::Rust);
         }
 
+        if options.calldata_report {
+            builtin_plugins.push(BuiltinPlugins::CalldataReport);
+        }
+
+        if options.session_policies {
+            builtin_plugins.push(BuiltinPlugins::SessionPolicies);
+        }
+
+        if options.coverage_report {
+            builtin_plugins.push(BuiltinPlugins::CoverageReport);
+        }
+        // cainome:new-plugin:registrations
+
+        let mut output_dirs: HashMap<_, _> = options.plugin_outs.into_iter().collect();
+
+        if let Some(path) = options.rust_out {
+            output_dirs.insert("rust".to_string(), path);
+        }
+
         Self {
             builtin_plugins,
             plugins,
+            formatters: options.formatters.into_iter().collect(),
+            output_dirs,
+            format: !options.no_format,
         }
     }
 }
@@ -0,0 +1,878 @@
+//! Dynamic encoding of JSON values into calldata, and decoding of felts into
+//! JSON values, driven by the tokens of an ABI.
+//!
+//! The values are represented as:
+//!
+//! * felts, addresses and class hashes: hexadecimal (`0x` prefixed) or decimal strings.
+//!   Decoded as hexadecimal strings.
+//! * integers: numbers or decimal strings (hexadecimal strings are accepted for
+//!   the unsigned ones). `u128` and `i128` are decoded as decimal strings.
+//! * `u256`: like the unsigned integers, or `{ "low": .., "high": .. }`. Decoded as
+//!   a hexadecimal string.
+//! * `bool`: booleans, `ByteArray`: strings, `()`: `null`.
+//! * arrays, spans and tuples: arrays.
+//! * structs: objects with a field per member.
+//! * enums: the name of the variant for a unit variant, `{ "Variant": value }` otherwise.
+//!   `Option` is `null` or its value, `Result` is `{ "Ok": .. }` or `{ "Err": .. }`.
+use cainome_cairo_serde::{ByteArray, CairoSerde, U256};
+use cainome_parser::tokens::{Composite, CompositeType, Function, Token};
+use cainome_parser::TokenizedAbi;
+use serde_json::{json, Map, Value};
+use starknet::core::types::Felt;
+
+use crate::error::{CainomeCliResult, Error};
+
+/// Encoder and decoder of the values of the types of an ABI.
+#[derive(Debug)]
+pub struct Codec<'a> {
+    abi: &'a TokenizedAbi,
+}
+
+impl<'a> Codec<'a> {
+    pub fn new(abi: &'a TokenizedAbi) -> Self {
+        Self { abi }
+    }
+
+    /// Returns the function `name` of the ABI, searched in the interfaces too.
+    pub fn function(&self, name: &str) -> CainomeCliResult<&'a Function> {
+        let mut interfaces: Vec<_> = self.abi.interfaces.iter().collect();
+        interfaces.sort_by(|a, b| a.0.cmp(b.0));
+
+        self.abi
+            .functions
+            .iter()
+            .chain(interfaces.into_iter().flat_map(|(_, f)| f))
+            .filter_map(|t| t.to_function().ok())
+            .find(|f| f.name == name)
+            .ok_or_else(|| Error::Other(format!("Function `{name}` not found in the ABI")))
+    }
+
+    /// Encodes the arguments of the function into its calldata.
+    ///
+    /// # Arguments
+    ///
+    /// * `func` - The function.
+    /// * `args` - The values of the inputs of the function, in order.
+    pub fn encode_inputs(&self, func: &Function, args: &[Value]) -> CainomeCliResult<Vec<Felt>> {
+        if args.len() != func.inputs.len() {
+            return Err(Error::Other(format!(
+                "Function `{}` expects {} arguments ({}), got {}",
+                func.name,
+                func.inputs.len(),
+                func.inputs
+                    .iter()
+                    .map(|(name, _)| name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                args.len()
+            )));
+        }
+
+        let mut felts = vec![];
+
+        for ((name, token), value) in func.inputs.iter().zip(args) {
+            self.encode(token, value, &[], name, &mut felts)?;
+        }
+
+        Ok(felts)
+    }
+
+    /// Decodes the felts returned by the function.
+    ///
+    /// A single output is decoded as is, several outputs (only possible for Cairo 0)
+    /// as an object if they are named, an array otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `func` - The function.
+    /// * `felts` - The felts returned by the function.
+    pub fn decode_outputs(&self, func: &Function, felts: &[Felt]) -> CainomeCliResult<Value> {
+        let mut offset = 0;
+
+        let value = if !func.named_outputs.is_empty() {
+            let mut object = Map::new();
+            for (name, token) in &func.named_outputs {
+                object.insert(name.clone(), self.decode(token, &[], felts, &mut offset)?);
+            }
+            Value::Object(object)
+        } else {
+            match func.outputs.as_slice() {
+                [] => Value::Null,
+                [output] => self.decode(output, &[], felts, &mut offset)?,
+                outputs => Value::Array(
+                    outputs
+                        .iter()
+                        .map(|t| self.decode(t, &[], felts, &mut offset))
+                        .collect::<CainomeCliResult<_>>()?,
+                ),
+            }
+        };
+
+        if offset != felts.len() {
+            tracing::warn!(
+                "{} felts left after decoding the outputs of `{}`",
+                felts.len() - offset,
+                func.name
+            );
+        }
+
+        Ok(value)
+    }
+
+    /// Encodes the value of the type `token`, appending the felts to `felts`.
+    ///
+    /// `path` is the location of the value in the arguments, for the error messages.
+    fn encode(
+        &self,
+        token: &Token,
+        value: &Value,
+        generic_args: &[(String, Token)],
+        path: &str,
+        felts: &mut Vec<Felt>,
+    ) -> CainomeCliResult<()> {
+        match token {
+            Token::CoreBasic(b) => {
+                felts.extend(encode_basic(&b.type_path, value, path)?);
+            }
+            Token::Array(a) => {
+                let items = as_array(value, path)?;
+                felts.push(items.len().into());
+
+                for (i, item) in items.iter().enumerate() {
+                    self.encode(&a.inner, item, generic_args, &format!("{path}[{i}]"), felts)?;
+                }
+            }
+            Token::Tuple(t) => {
+                let items = as_array(value, path)?;
+                if items.len() != t.inners.len() {
+                    return Err(invalid(
+                        path,
+                        &format!("expected a tuple of {} items", t.inners.len()),
+                    ));
+                }
+
+                for (i, (inner, item)) in t.inners.iter().zip(items).enumerate() {
+                    self.encode(inner, item, generic_args, &format!("{path}.{i}"), felts)?;
+                }
+            }
+            Token::Composite(c) => self.encode_composite(c, value, path, felts)?,
+            Token::GenericArg(g) => {
+                let token = resolve_generic(g, generic_args, path)?;
+                self.encode(token, value, &[], path, felts)?;
+            }
+            Token::Function(_) => return Err(invalid(path, "functions can't be encoded")),
+        }
+
+        Ok(())
+    }
+
+    fn encode_composite(
+        &self,
+        composite: &Composite,
+        value: &Value,
+        path: &str,
+        felts: &mut Vec<Felt>,
+    ) -> CainomeCliResult<()> {
+        let args = &composite.generic_args;
+
+        match composite.type_path_no_generic().as_str() {
+            "core::integer::u256" => {
+                let u256 = parse_u256(value).ok_or_else(|| invalid(path, "expected a u256"))?;
+                felts.extend(U256::cairo_serialize(&u256));
+            }
+            "core::byte_array::ByteArray" => {
+                let string = value
+                    .as_str()
+                    .ok_or_else(|| invalid(path, "expected a string"))?;
+                let bytes = ByteArray::from_string(string)
+                    .map_err(|e| invalid(path, &format!("invalid byte array ({e:?})")))?;
+                felts.extend(ByteArray::cairo_serialize(&bytes));
+            }
+            "core::starknet::eth_address::EthAddress"
+            | "core::internal::bounded_int::BoundedInt" => {
+                felts.push(parse_felt(value).ok_or_else(|| invalid(path, "expected a felt"))?);
+            }
+            "core::option::Option" => match value {
+                Value::Null => felts.push(Felt::ONE),
+                value => {
+                    felts.push(Felt::ZERO);
+                    self.encode(generic_arg(composite, 0, path)?, value, args, path, felts)?;
+                }
+            },
+            "core::result::Result" => {
+                let (variant, value) = as_variant(value, path)?;
+                let (index, token) = match variant {
+                    "Ok" => (Felt::ZERO, generic_arg(composite, 0, path)?),
+                    "Err" => (Felt::ONE, generic_arg(composite, 1, path)?),
+                    _ => return Err(invalid(path, "expected `Ok` or `Err`")),
+                };
+                felts.push(index);
+                self.encode(token, value, args, &format!("{path}.{variant}"), felts)?;
+            }
+            "core::zeroable::NonZero" | "core::r#box::Box" => {
+                self.encode(generic_arg(composite, 0, path)?, value, args, path, felts)?;
+            }
+            _ => {
+                let definition = self.definition(composite, path)?;
+                let args = generic_args_of(composite, &definition);
+
+                match definition.r#type {
+                    CompositeType::Enum => {
+                        let (variant, value) = as_variant(value, path)?;
+                        let inner = definition
+                            .inners
+                            .iter()
+                            .find(|i| i.name == variant)
+                            .ok_or_else(|| {
+                                invalid(path, &format!("unknown variant `{variant}`"))
+                            })?;
+
+                        felts.push(inner.index.into());
+                        self.encode(
+                            &inner.token,
+                            value,
+                            &args,
+                            &format!("{path}.{variant}"),
+                            felts,
+                        )?;
+                    }
+                    _ => {
+                        let object = value
+                            .as_object()
+                            .ok_or_else(|| invalid(path, "expected an object"))?;
+
+                        for inner in &definition.inners {
+                            let member_path = format!("{path}.{}", inner.name);
+                            let value = object
+                                .get(&inner.name)
+                                .ok_or_else(|| invalid(&member_path, "missing member"))?;
+                            self.encode(&inner.token, value, &args, &member_path, felts)?;
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Decodes the value of the type `token` from the felts at `offset`, which is advanced.
+    fn decode(
+        &self,
+        token: &Token,
+        generic_args: &[(String, Token)],
+        felts: &[Felt],
+        offset: &mut usize,
+    ) -> CainomeCliResult<Value> {
+        match token {
+            Token::CoreBasic(b) => decode_basic(&b.type_path, felts, offset),
+            Token::Array(a) => {
+                let len = felt_to_usize(next_felt(felts, offset)?)?;
+
+                (0..len)
+                    .map(|_| self.decode(&a.inner, generic_args, felts, offset))
+                    .collect::<CainomeCliResult<_>>()
+                    .map(Value::Array)
+            }
+            Token::Tuple(t) => t
+                .inners
+                .iter()
+                .map(|inner| self.decode(inner, generic_args, felts, offset))
+                .collect::<CainomeCliResult<_>>()
+                .map(Value::Array),
+            Token::Composite(c) => self.decode_composite(c, felts, offset),
+            Token::GenericArg(g) => {
+                let token = resolve_generic(g, generic_args, "output")?;
+                self.decode(token, &[], felts, offset)
+            }
+            Token::Function(_) => Err(Error::Other("Functions can't be decoded".to_string())),
+        }
+    }
+
+    fn decode_composite(
+        &self,
+        composite: &Composite,
+        felts: &[Felt],
+        offset: &mut usize,
+    ) -> CainomeCliResult<Value> {
+        let args = &composite.generic_args;
+
+        let value = match composite.type_path_no_generic().as_str() {
+            "core::integer::u256" => {
+                let u256 = deserialize::<U256>(felts, offset)?;
+
+                if u256.high == 0 {
+                    Value::String(format!("{:#x}", u256.low))
+                } else {
+                    Value::String(format!("{:#x}{:032x}", u256.high, u256.low))
+                }
+            }
+            "core::byte_array::ByteArray" => {
+                let bytes = deserialize::<ByteArray>(felts, offset)?;
+                Value::String(
+                    bytes
+                        .to_string()
+                        .map_err(|e| Error::Other(format!("Byte array is not valid UTF-8: {e}")))?,
+                )
+            }
+            "core::starknet::eth_address::EthAddress"
+            | "core::internal::bounded_int::BoundedInt" => {
+                Value::String(format!("{:#x}", next_felt(felts, offset)?))
+            }
+            "core::option::Option" => {
+                if next_felt(felts, offset)? == Felt::ZERO {
+                    self.decode(generic_arg(composite, 0, "output")?, args, felts, offset)?
+                } else {
+                    Value::Null
+                }
+            }
+            "core::result::Result" => {
+                let (variant, token) = if next_felt(felts, offset)? == Felt::ZERO {
+                    ("Ok", generic_arg(composite, 0, "output")?)
+                } else {
+                    ("Err", generic_arg(composite, 1, "output")?)
+                };
+                json!({ variant: self.decode(token, args, felts, offset)? })
+            }
+            "core::zeroable::NonZero" | "core::r#box::Box" => {
+                self.decode(generic_arg(composite, 0, "output")?, args, felts, offset)?
+            }
+            _ => {
+                let definition = self.definition(composite, "output")?;
+                let args = generic_args_of(composite, &definition);
+
+                match definition.r#type {
+                    CompositeType::Enum => {
+                        let index = felt_to_usize(next_felt(felts, offset)?)?;
+                        let inner = definition
+                            .inners
+                            .iter()
+                            .find(|i| i.index == index)
+                            .ok_or_else(|| {
+                                Error::Other(format!(
+                                    "Invalid variant index {index} for `{}`",
+                                    composite.type_path
+                                ))
+                            })?;
+
+                        if is_unit(&inner.token) {
+                            Value::String(inner.name.clone())
+                        } else {
+                            json!({ &inner.name: self.decode(&inner.token, &args, felts, offset)? })
+                        }
+                    }
+                    _ => {
+                        let mut object = Map::new();
+                        for inner in &definition.inners {
+                            object.insert(
+                                inner.name.clone(),
+                                self.decode(&inner.token, &args, felts, offset)?,
+                            );
+                        }
+                        Value::Object(object)
+                    }
+                }
+            }
+        };
+
+        Ok(value)
+    }
+
+    /// Returns the definition of the composite: itself if hydrated,
+    /// otherwise the struct or enum of the ABI with the same path.
+    fn definition(&self, composite: &Composite, path: &str) -> CainomeCliResult<Composite> {
+        if composite.r#type != CompositeType::Unknown {
+            return Ok(composite.clone());
+        }
+
+        self.abi
+            .structs
+            .iter()
+            .chain(self.abi.enums.iter())
+            .filter_map(|t| t.to_composite().ok())
+            .find(|c| c.type_path == composite.type_path)
+            .cloned()
+            .ok_or_else(|| {
+                invalid(
+                    path,
+                    &format!("type `{}` is not defined in the ABI", composite.type_path),
+                )
+            })
+    }
+}
+
+/// Encodes the value of a core basic type.
+fn encode_basic(type_path: &str, value: &Value, path: &str) -> CainomeCliResult<Vec<Felt>> {
+    let felt = match type_path {
+        "()" => return Ok(vec![]),
+        "core::bool" => {
+            let b = value
+                .as_bool()
+                .ok_or_else(|| invalid(path, "expected a boolean"))?;
+            return Ok(bool::cairo_serialize(&b));
+        }
+        "core::integer::u8" => encode_unsigned(value, u8::MAX.into(), path)?,
+        "core::integer::u16" => encode_unsigned(value, u16::MAX.into(), path)?,
+        "core::integer::u32" | "core::integer::usize" => {
+            encode_unsigned(value, u32::MAX.into(), path)?
+        }
+        "core::integer::u64" => encode_unsigned(value, u64::MAX.into(), path)?,
+        "core::integer::u128" => encode_unsigned(value, u128::MAX, path)?,
+        "core::integer::i8" => encode_signed(value, i8::MIN.into(), i8::MAX.into(), path)?,
+        "core::integer::i16" => encode_signed(value, i16::MIN.into(), i16::MAX.into(), path)?,
+        "core::integer::i32" => encode_signed(value, i32::MIN.into(), i32::MAX.into(), path)?,
+        "core::integer::i64" => encode_signed(value, i64::MIN.into(), i64::MAX.into(), path)?,
+        "core::integer::i128" => encode_signed(value, i128::MIN, i128::MAX, path)?,
+        _ => parse_felt(value).ok_or_else(|| invalid(path, "expected a felt"))?,
+    };
+
+    Ok(vec![felt])
+}
+
+fn encode_unsigned(value: &Value, max: u128, path: &str) -> CainomeCliResult<Felt> {
+    let s = number_str(value).ok_or_else(|| invalid(path, "expected an integer"))?;
+
+    let n = match s.strip_prefix("0x") {
+        Some(hex) => u128::from_str_radix(hex, 16),
+        None => s.parse::<u128>(),
+    };
+
+    match n {
+        Ok(n) if n <= max => Ok(n.into()),
+        _ => Err(invalid(path, &format!("expected an integer in [0, {max}]"))),
+    }
+}
+
+fn encode_signed(value: &Value, min: i128, max: i128, path: &str) -> CainomeCliResult<Felt> {
+    let s = number_str(value).ok_or_else(|| invalid(path, "expected an integer"))?;
+
+    match s.parse::<i128>() {
+        Ok(n) if (min..=max).contains(&n) => Ok(n.into()),
+        _ => Err(invalid(
+            path,
+            &format!("expected an integer in [{min}, {max}]"),
+        )),
+    }
+}
+
+/// Decodes the value of a core basic type.
+fn decode_basic(type_path: &str, felts: &[Felt], offset: &mut usize) -> CainomeCliResult<Value> {
+    if type_path == "()" {
+        return Ok(Value::Null);
+    }
+
+    let felt = next_felt(felts, offset)?;
+
+    let value = match type_path {
+        "core::bool" => Value::Bool(felt == Felt::ONE),
+        "core::integer::u8"
+        | "core::integer::u16"
+        | "core::integer::u32"
+        | "core::integer::usize"
+        | "core::integer::u64" => json!(u64::try_from(felt_to_u128(felt)?).map_err(|_| {
+            Error::Other(format!("{felt:#x} is out of range for `{type_path}`"))
+        })?),
+        "core::integer::u128" => Value::String(felt_to_u128(felt)?.to_string()),
+        "core::integer::i8" | "core::integer::i16" | "core::integer::i32"
+        | "core::integer::i64" => json!(i64::try_from(felt_to_i128(felt)?).map_err(|_| {
+            Error::Other(format!("{felt:#x} is out of range for `{type_path}`"))
+        })?),
+        "core::integer::i128" => Value::String(felt_to_i128(felt)?.to_string()),
+        _ => Value::String(format!("{felt:#x}")),
+    };
+
+    Ok(value)
+}
+
+/// Returns the textual representation of a number, given as a JSON number or string.
+fn number_str(value: &Value) -> Option<String> {
+    match value {
+        Value::Number(n) => Some(n.to_string()),
+        Value::String(s) => Some(s.clone()),
+        _ => None,
+    }
+}
+
+/// Parses a felt, given as a JSON number or a hexadecimal or decimal string.
+fn parse_felt(value: &Value) -> Option<Felt> {
+    match value {
+        Value::Number(n) => n.as_u64().map(Felt::from),
+        Value::String(s) if s.starts_with("0x") => Felt::from_hex(s).ok(),
+        Value::String(s) => Felt::from_dec_str(s).ok(),
+        _ => None,
+    }
+}
+
+/// Parses a `u256`, given as a JSON number, a hexadecimal or decimal string,
+/// or an object with the `low` and `high` parts.
+fn parse_u256(value: &Value) -> Option<U256> {
+    if let Value::Object(object) = value {
+        let part = |name| number_str(object.get(name)?)?.parse::<u128>().ok();
+        return Some(U256 {
+            low: part("low")?,
+            high: part("high")?,
+        });
+    }
+
+    let s = number_str(value)?;
+
+    if let Some(hex) = s.strip_prefix("0x") {
+        if hex.is_empty() || hex.len() > 64 {
+            return None;
+        }
+
+        let hex = format!("{:0>64}", hex);
+        let mut bytes = [0_u8; 32];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(hex.get(i * 2..i * 2 + 2)?, 16).ok()?;
+        }
+
+        return Some(U256::from_bytes_be(&bytes));
+    }
+
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    // Bigger values are truncated by the parsing.
+    let u256 = s.parse::<U256>().ok()?;
+    let digits = match s.trim_start_matches('0') {
+        "" => "0",
+        digits => digits,
+    };
+
+    (u256.to_string() == digits).then_some(u256)
+}
+
+fn felt_to_u128(felt: Felt) -> CainomeCliResult<u128> {
+    let bytes = felt.to_bytes_be();
+
+    if bytes[..16].iter().any(|b| *b != 0) {
+        return Err(Error::Other(format!(
+            "{felt:#x} is out of range for an integer"
+        )));
+    }
+
+    Ok(u128::from_be_bytes(bytes[16..].try_into().unwrap()))
+}
+
+/// Returns the signed integer represented by the felt, the negative
+/// values being represented by their opposite modulo the prime.
+fn felt_to_i128(felt: Felt) -> CainomeCliResult<i128> {
+    let out_of_range = || Error::Other(format!("{felt:#x} is out of range for an integer"));
+
+    if let Ok(n) = felt_to_u128(felt) {
+        i128::try_from(n).map_err(|_| out_of_range())
+    } else {
+        let opposite = felt_to_u128(-felt).map_err(|_| out_of_range())?;
+        0_i128
+            .checked_sub_unsigned(opposite)
+            .ok_or_else(out_of_range)
+    }
+}
+
+fn felt_to_usize(felt: Felt) -> CainomeCliResult<usize> {
+    usize::try_from(felt_to_u128(felt)?)
+        .map_err(|_| Error::Other(format!("{felt:#x} is out of range for a length")))
+}
+
+/// Returns the felt at `offset`, which is advanced.
+fn next_felt(felts: &[Felt], offset: &mut usize) -> CainomeCliResult<Felt> {
+    let felt = felts.get(*offset).copied().ok_or_else(|| {
+        Error::Other(format!(
+            "Not enough felts to decode the outputs (offset {})",
+            offset
+        ))
+    })?;
+
+    *offset += 1;
+    Ok(felt)
+}
+
+/// Deserializes a value with its [`CairoSerde`] implementation, advancing `offset`.
+fn deserialize<T: CairoSerde<RustType = T>>(
+    felts: &[Felt],
+    offset: &mut usize,
+) -> CainomeCliResult<T> {
+    let (value, size) = T::cairo_deserialize_with_size(felts, *offset)
+        .map_err(|e| Error::Other(format!("Outputs could not be decoded: {e:?}")))?;
+
+    *offset += size;
+    Ok(value)
+}
+
+fn as_array<'v>(value: &'v Value, path: &str) -> CainomeCliResult<&'v Vec<Value>> {
+    value
+        .as_array()
+        .ok_or_else(|| invalid(path, "expected an array"))
+}
+
+/// Returns the name and the value of the variant of an enum, the value
+/// being `null` for a unit variant given by its name only.
+fn as_variant<'v>(value: &'v Value, path: &str) -> CainomeCliResult<(&'v str, &'v Value)> {
+    match value {
+        Value::String(name) => Ok((name, &Value::Null)),
+        Value::Object(object) if object.len() == 1 => {
+            let (name, value) = object.iter().next().unwrap();
+            Ok((name, value))
+        }
+        _ => Err(invalid(
+            path,
+            "expected a variant name or an object with a single variant",
+        )),
+    }
+}
+
+fn is_unit(token: &Token) -> bool {
+    matches!(token, Token::CoreBasic(b) if b.type_path == "()")
+}
+
+/// Returns the `index`-th generic argument of a builtin composite.
+fn generic_arg<'c>(
+    composite: &'c Composite,
+    index: usize,
+    path: &str,
+) -> CainomeCliResult<&'c Token> {
+    composite
+        .generic_args
+        .get(index)
+        .map(|(_, t)| t)
+        .ok_or_else(|| {
+            invalid(
+                path,
+                &format!("missing generic argument of `{}`", composite.type_path),
+            )
+        })
+}
+
+/// Returns the generic arguments resolving the members of the definition of a composite,
+/// the ones of its occurrence if any.
+fn generic_args_of(composite: &Composite, definition: &Composite) -> Vec<(String, Token)> {
+    if composite.generic_args.is_empty() {
+        definition.generic_args.clone()
+    } else {
+        composite.generic_args.clone()
+    }
+}
+
+fn resolve_generic<'t>(
+    name: &str,
+    generic_args: &'t [(String, Token)],
+    path: &str,
+) -> CainomeCliResult<&'t Token> {
+    generic_args
+        .iter()
+        .find(|(n, _)| n == name)
+        .map(|(_, t)| t)
+        .ok_or_else(|| invalid(path, &format!("unresolved generic argument `{name}`")))
+}
+
+fn invalid(path: &str, reason: &str) -> Error {
+    Error::Other(format!("Invalid value for `{path}`: {reason}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cainome_parser::AbiParser;
+    use std::collections::HashMap;
+
+    const ABI: &str = r#"[
+        {
+            "type": "struct",
+            "name": "pkg::Order",
+            "members": [
+                { "name": "price", "type": "core::integer::u256" },
+                { "name": "owner", "type": "core::starknet::contract_address::ContractAddress" },
+                { "name": "delta", "type": "core::integer::i64" }
+            ]
+        },
+        {
+            "type": "enum",
+            "name": "pkg::Side",
+            "variants": [
+                { "name": "Buy", "type": "()" },
+                { "name": "Sell", "type": "core::integer::u32" }
+            ]
+        },
+        {
+            "type": "struct",
+            "name": "pkg::Wrapper::<core::felt252>",
+            "members": [{ "name": "inner", "type": "core::felt252" }]
+        },
+        {
+            "type": "function",
+            "name": "place",
+            "inputs": [
+                { "name": "orders", "type": "core::array::Span::<pkg::Order>" },
+                { "name": "side", "type": "pkg::Side" },
+                { "name": "memo", "type": "core::option::Option::<core::byte_array::ByteArray>" },
+                { "name": "pair", "type": "(core::bool, core::integer::i128)" },
+                { "name": "wrapped", "type": "pkg::Wrapper::<core::felt252>" }
+            ],
+            "outputs": [
+                { "type": "core::result::Result::<pkg::Side, core::felt252>" }
+            ],
+            "state_mutability": "external"
+        }
+    ]"#;
+
+    fn tokens() -> TokenizedAbi {
+        AbiParser::tokens_from_abi_string(ABI, &HashMap::new()).unwrap()
+    }
+
+    #[test]
+    fn test_encode_inputs() {
+        let abi = tokens();
+        let codec = Codec::new(&abi);
+        let func = codec.function("place").unwrap();
+
+        let args = vec![
+            json!([{ "price": "0x100000000000000000000000000000002", "owner": "0x12", "delta": -3 }]),
+            json!({ "Sell": 7 }),
+            json!("ab"),
+            json!([true, "-5"]),
+            json!({ "inner": "10" }),
+        ];
+
+        let felts = codec.encode_inputs(func, &args).unwrap();
+
+        let expected: Vec<Felt> = vec![
+            // Span of one order.
+            Felt::ONE,
+            Felt::TWO,
+            Felt::ONE,
+            Felt::from(0x12),
+            Felt::from(-3_i64),
+            // Sell(7).
+            Felt::ONE,
+            Felt::from(7),
+            // Some("ab").
+            Felt::ZERO,
+            Felt::ZERO,
+            Felt::from(0x6162),
+            Felt::TWO,
+            // (true, -5).
+            Felt::ONE,
+            Felt::from(-5_i64),
+            // Wrapper.
+            Felt::from(10),
+        ];
+
+        assert_eq!(felts, expected);
+    }
+
+    #[test]
+    fn test_encode_inputs_errors() {
+        let abi = tokens();
+        let codec = Codec::new(&abi);
+        let func = codec.function("place").unwrap();
+
+        let valid = || {
+            vec![
+                json!([]),
+                json!("Buy"),
+                Value::Null,
+                json!([false, 0]),
+                json!({ "inner": "0x1" }),
+            ]
+        };
+
+        let error = |args: Vec<Value>| codec.encode_inputs(func, &args).unwrap_err().to_string();
+
+        assert!(codec.encode_inputs(func, &valid()).is_ok());
+        assert!(error(valid()[..4].to_vec()).contains("expects 5 arguments"));
+
+        let mut args = valid();
+        args[0] = json!([{ "price": 1, "owner": "0x1" }]);
+        assert!(error(args).contains("`orders[0].delta`: missing member"));
+
+        let mut args = valid();
+        args[1] = json!("Hold");
+        assert!(error(args).contains("unknown variant `Hold`"));
+
+        let mut args = valid();
+        args[3] = json!([false, "170141183460469231731687303715884105728"]);
+        assert!(error(args).contains("`pair.1`"));
+
+        assert!(Codec::new(&abi).function("cancel").is_err());
+    }
+
+    #[test]
+    fn test_decode_outputs() {
+        let abi = tokens();
+        let codec = Codec::new(&abi);
+        let func = codec.function("place").unwrap();
+
+        let decode = |felts: &[Felt]| codec.decode_outputs(func, felts).unwrap();
+
+        assert_eq!(decode(&[Felt::ZERO, Felt::ZERO]), json!({ "Ok": "Buy" }));
+        assert_eq!(
+            decode(&[Felt::ZERO, Felt::ONE, Felt::from(7)]),
+            json!({ "Ok": { "Sell": 7 } })
+        );
+        assert_eq!(
+            decode(&[Felt::ONE, Felt::from(0xff)]),
+            json!({ "Err": "0xff" })
+        );
+        assert!(codec.decode_outputs(func, &[Felt::ZERO]).is_err());
+    }
+
+    #[test]
+    fn test_values_roundtrip() {
+        let abi = tokens();
+        let codec = Codec::new(&abi);
+        let func = codec.function("place").unwrap();
+
+        let roundtrip = |token: &Token, value: Value| {
+            let mut felts = vec![];
+            codec
+                .encode(token, &value, &[], "value", &mut felts)
+                .unwrap();
+
+            let mut offset = 0;
+            let decoded = codec.decode(token, &[], &felts, &mut offset).unwrap();
+            assert_eq!(offset, felts.len());
+            decoded
+        };
+
+        let orders = json!([
+            { "price": "0x100000000000000000000000000000002", "owner": "0x12", "delta": -3 },
+            { "price": "0x0", "owner": "0x0", "delta": 9 }
+        ]);
+        assert_eq!(roundtrip(&func.inputs[0].1, orders.clone()), orders);
+
+        assert_eq!(roundtrip(&func.inputs[2].1, json!("memo")), json!("memo"));
+        assert_eq!(roundtrip(&func.inputs[2].1, Value::Null), Value::Null);
+
+        assert_eq!(
+            roundtrip(
+                &func.inputs[3].1,
+                json!([true, "-170141183460469231731687303715884105728"])
+            ),
+            json!([true, "-170141183460469231731687303715884105728"])
+        );
+    }
+
+    #[test]
+    fn test_parse_u256() {
+        let u256 = |low, high| Some(U256 { low, high });
+
+        assert_eq!(parse_u256(&json!(5)), u256(5, 0));
+        assert_eq!(parse_u256(&json!("0x5")), u256(5, 0));
+        assert_eq!(
+            parse_u256(&json!("340282366920938463463374607431768211457")),
+            u256(1, 1)
+        );
+        assert_eq!(parse_u256(&json!({ "low": 1, "high": "2" })), u256(1, 2));
+        assert_eq!(
+            parse_u256(&json!(format!("0x{}", "f".repeat(64)))),
+            u256(u128::MAX, u128::MAX)
+        );
+
+        assert_eq!(parse_u256(&json!(format!("0x1{}", "0".repeat(64)))), None);
+        assert_eq!(parse_u256(&json!("-1")), None);
+        assert_eq!(parse_u256(&json!("1".repeat(80))), None);
+        assert_eq!(parse_u256(&json!("0x")), None);
+    }
+}
@@ -0,0 +1,169 @@
+//! Call of a view function of a deployed contract.
+//!
+//! The arguments are encoded and the outputs decoded with the ABI of the contract,
+//! see the [`crate::codec`] module for the representation of the values.
+use cainome_parser::tokens::StateMutability;
+use cainome_parser::{selectors, AbiParser, TokenizedAbi};
+use camino::Utf8PathBuf;
+use clap::Args;
+use serde_json::Value;
+use starknet::core::types::{BlockId, BlockTag, Felt, FunctionCall};
+use starknet::providers::jsonrpc::HttpTransport;
+use starknet::providers::{JsonRpcClient, Provider};
+use url::Url;
+
+use crate::codec::Codec;
+use crate::error::{CainomeCliResult, Error};
+
+/// The function of a deployed contract to call or invoke, with its arguments.
+#[derive(Debug, Args)]
+pub struct FunctionArgs {
+    #[arg(long)]
+    #[arg(value_name = "PATH")]
+    #[arg(
+        help = "Path of the ABI of the contract, as a JSON array of ABI entries or a Sierra class."
+    )]
+    pub abi: Utf8PathBuf,
+
+    #[arg(long)]
+    #[arg(value_name = "ADDRESS")]
+    #[arg(help = "Address of the contract.")]
+    pub address: Felt,
+
+    #[arg(long)]
+    #[arg(value_name = "NAME")]
+    #[arg(help = "Name of the function.")]
+    pub function: String,
+
+    #[arg(long)]
+    #[arg(value_name = "ARG")]
+    #[arg(num_args = 1..)]
+    #[arg(allow_negative_numbers = true)]
+    #[arg(
+        help = "Arguments of the function, in order. Each argument is a JSON value \
+                  (like '{\"low\":1,\"high\":0}' or '[1,2]'), a plain string otherwise (like 0x1234)."
+    )]
+    pub args: Vec<String>,
+
+    #[arg(long)]
+    #[arg(value_name = "URL")]
+    #[arg(help = "The Starknet RPC provider.")]
+    pub rpc_url: Url,
+}
+
+impl FunctionArgs {
+    /// Loads and tokenizes the ABI of the contract.
+    pub fn load_abi(&self) -> CainomeCliResult<TokenizedAbi> {
+        let content = std::fs::read_to_string(&self.abi)?;
+
+        AbiParser::tokens_from_abi_string(&content, &Default::default())
+            .map_err(|e| Error::Other(format!("ABI file {} could not be parsed: {e:?}", self.abi)))
+    }
+
+    /// Returns the arguments of the function as JSON values.
+    pub fn values(&self) -> Vec<Value> {
+        self.args.iter().map(|a| parse_arg(a)).collect()
+    }
+
+    /// Returns the selector and the calldata of the function.
+    pub fn encode(&self, codec: &Codec<'_>) -> CainomeCliResult<(Felt, Vec<Felt>)> {
+        let func = codec.function(&self.function)?;
+        let calldata = codec.encode_inputs(func, &self.values())?;
+
+        Ok((selectors::function_selector(&func.name)?, calldata))
+    }
+
+    /// Returns the RPC provider.
+    pub fn provider(&self) -> JsonRpcClient<HttpTransport> {
+        JsonRpcClient::new(HttpTransport::new(self.rpc_url.clone()))
+    }
+}
+
+#[derive(Debug, Args)]
+pub struct CallArgs {
+    #[command(flatten)]
+    pub function: FunctionArgs,
+
+    #[arg(long)]
+    #[arg(help = "Outputs the returned felts instead of the decoded values.")]
+    pub raw: bool,
+}
+
+/// Calls the function described by `args`, and prints its decoded outputs.
+pub async fn run(args: CallArgs) -> CainomeCliResult<()> {
+    let abi = args.function.load_abi()?;
+    let codec = Codec::new(&abi);
+
+    let func = codec.function(&args.function.function)?;
+    if func.state_mutability == StateMutability::External {
+        tracing::warn!(
+            "`{}` is an external function, calling it doesn't change the state of the contract",
+            func.name
+        );
+    }
+
+    let (selector, calldata) = args.function.encode(&codec)?;
+
+    let felts = args
+        .function
+        .provider()
+        .call(
+            FunctionCall {
+                contract_address: args.function.address,
+                entry_point_selector: selector,
+                calldata,
+            },
+            BlockId::Tag(BlockTag::Pending),
+        )
+        .await?;
+
+    if args.raw {
+        for felt in felts {
+            println!("{felt:#x}");
+        }
+    } else {
+        let outputs = codec.decode_outputs(func, &felts)?;
+        println!("{}", serde_json::to_string_pretty(&outputs)?);
+    }
+
+    Ok(())
+}
+
+/// Parses an argument given on the command line: a JSON value if it is one,
+/// a string otherwise.
+///
+/// The numbers which are not integers fitting in 64 bits (like big decimal felts)
+/// are kept as strings, to not lose precision.
+fn parse_arg(arg: &str) -> Value {
+    match serde_json::from_str::<Value>(arg) {
+        Ok(Value::Number(n)) if n.is_f64() => Value::String(arg.to_string()),
+        Ok(value) => value,
+        Err(_) => Value::String(arg.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_arg() {
+        assert_eq!(parse_arg("0x1234"), json!("0x1234"));
+        assert_eq!(parse_arg("12"), json!(12));
+        assert_eq!(parse_arg("-12"), json!(-12));
+        assert_eq!(
+            parse_arg(
+                "3618502788666131213697322783095070105623107215331596699973092056135872020480"
+            ),
+            json!("3618502788666131213697322783095070105623107215331596699973092056135872020480")
+        );
+        assert_eq!(parse_arg("true"), json!(true));
+        assert_eq!(parse_arg("[1,\"0x2\"]"), json!([1, "0x2"]));
+        assert_eq!(
+            parse_arg("{\"low\":1,\"high\":0}"),
+            json!({ "low": 1, "high": 0 })
+        );
+        assert_eq!(parse_arg("hello world"), json!("hello world"));
+    }
+}
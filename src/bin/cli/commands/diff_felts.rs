@@ -0,0 +1,247 @@
+//! Felt by felt comparison of two serialized values, to debug serialization mismatches.
+//!
+//! The felts of the expected and actual values are labelled with the members of the type
+//! (or of the inputs of a function, for calldata), see [`cainome_parser::felt_diff`].
+//! Without actual felts, the expected ones are only labelled.
+use cainome_parser::felt_diff::{self, FeltAt, FeltEntry};
+use cainome_parser::tokens::Token;
+use cainome_parser::{AbiParser, TokenizedAbi};
+use camino::Utf8PathBuf;
+use clap::Args;
+use starknet::core::types::Felt;
+
+use crate::codec::Codec;
+use crate::error::{CainomeCliResult, Error};
+
+#[derive(Debug, Args)]
+pub struct DiffFeltsArgs {
+    #[arg(long)]
+    #[arg(value_name = "PATH")]
+    #[arg(
+        help = "Path of the ABI defining the types, as a JSON array of ABI entries or a Sierra class. Not needed for the core types."
+    )]
+    pub abi: Option<Utf8PathBuf>,
+
+    #[arg(long = "type")]
+    #[arg(value_name = "TYPE")]
+    #[arg(required_unless_present = "function")]
+    #[arg(conflicts_with = "function")]
+    #[arg(
+        help = "Type of the values, the path or the name of a struct or enum of the ABI, or a core type (like 'core::integer::u256')."
+    )]
+    pub type_path: Option<String>,
+
+    #[arg(long)]
+    #[arg(value_name = "NAME")]
+    #[arg(requires = "abi")]
+    #[arg(help = "Function of the ABI whose inputs are the values, to compare calldata.")]
+    pub function: Option<String>,
+
+    #[arg(long)]
+    #[arg(value_name = "FELTS")]
+    #[arg(num_args = 1..)]
+    #[arg(required = true)]
+    #[arg(
+        help = "Felts of the expected value, hexadecimal or decimal, separated by spaces or commas."
+    )]
+    pub expected: Vec<String>,
+
+    #[arg(long)]
+    #[arg(value_name = "FELTS")]
+    #[arg(num_args = 1..)]
+    #[arg(
+        help = "Felts of the actual value. If not given, the felts of the expected value are only labelled."
+    )]
+    pub actual: Option<Vec<String>>,
+
+    #[arg(long)]
+    #[arg(help = "Also outputs the felts which are the same in both values.")]
+    pub all: bool,
+}
+
+/// Compares the felts described by `args`, and prints the ones which differ.
+pub fn run(args: DiffFeltsArgs) -> CainomeCliResult<()> {
+    let abi = match &args.abi {
+        Some(path) => {
+            let content = std::fs::read_to_string(path)?;
+            AbiParser::tokens_from_abi_string(&content, &Default::default()).map_err(|e| {
+                Error::Other(format!("ABI file {} could not be parsed: {e:?}", path))
+            })?
+        }
+        None => TokenizedAbi::default(),
+    };
+
+    let fields = match (&args.type_path, &args.function) {
+        (_, Some(function)) => Codec::new(&abi).function(function)?.inputs.clone(),
+        (Some(type_path), None) => vec![(String::new(), find_type(&abi, type_path)?)],
+        (None, None) => return Err(Error::Other("Invalid arguments".to_string())),
+    };
+
+    let expected = parse_felts(&args.expected)?;
+
+    let out = match &args.actual {
+        Some(actual) => {
+            let actual = parse_felts(actual)?;
+            let entries = felt_diff::compare_fields(&fields, &expected, &actual)?;
+            let mismatches = entries.iter().filter(|e| e.is_mismatch()).count();
+
+            if mismatches == 0 {
+                tracing::info!("The {} felts are the same", expected.len());
+            } else {
+                tracing::warn!("{mismatches} felts differ");
+            }
+
+            format_entries(&entries, true, args.all)
+        }
+        None => format_entries(
+            &felt_diff::compare_fields(&fields, &expected, &expected)?,
+            false,
+            true,
+        ),
+    };
+
+    print!("{}", out);
+
+    Ok(())
+}
+
+/// Returns the struct or enum of the ABI with the given path or name,
+/// or the parsed type if it is not defined by the ABI.
+fn find_type(abi: &TokenizedAbi, type_path: &str) -> CainomeCliResult<Token> {
+    let composites = || {
+        abi.structs
+            .iter()
+            .chain(abi.enums.iter())
+            .filter_map(|t| t.to_composite().ok())
+    };
+
+    if let Some(c) = composites()
+        .find(|c| c.type_path == type_path)
+        .or_else(|| composites().find(|c| c.type_name_or_alias() == type_path))
+    {
+        return Ok(Token::Composite(c.clone()));
+    }
+
+    Ok(Token::parse(type_path)?)
+}
+
+/// Parses the felts given as arguments, each argument possibly holding
+/// several felts separated by spaces or commas.
+fn parse_felts(args: &[String]) -> CainomeCliResult<Vec<Felt>> {
+    args.iter()
+        .flat_map(|a| a.split(|c: char| c == ',' || c.is_whitespace()))
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            let invalid = || Error::Other(format!("Invalid felt `{s}`"));
+
+            // Checked first, as the parsing panics on some invalid digits.
+            match s.strip_prefix("0x") {
+                Some(hex) if !hex.is_empty() && hex.chars().all(|c| c.is_ascii_hexdigit()) => {
+                    Felt::from_hex(s).map_err(|_| invalid())
+                }
+                None if s.chars().all(|c| c.is_ascii_digit()) => {
+                    Felt::from_dec_str(s).map_err(|_| invalid())
+                }
+                _ => Err(invalid()),
+            }
+        })
+        .collect()
+}
+
+/// Returns a line per felt: its offset(s), path, type and value(s), the differing felts
+/// being marked with `!`.
+///
+/// # Arguments
+///
+/// * `entries` - The felts of the values.
+/// * `with_actual` - Whether the actual felts are output, or only the expected ones.
+/// * `all` - Whether the felts which are the same are output.
+fn format_entries(entries: &[FeltEntry], with_actual: bool, all: bool) -> String {
+    let felt = |f: Option<FeltAt>| f.map_or("-".to_string(), |f| format!("{:#x}", f.felt));
+    let offset = |f: Option<FeltAt>| f.map_or("-".to_string(), |f| f.offset.to_string());
+
+    let rows: Vec<Vec<String>> = entries
+        .iter()
+        .filter(|e| all || e.is_mismatch())
+        .map(|e| {
+            let path = if e.path.is_empty() {
+                "."
+            } else {
+                e.path.as_str()
+            };
+
+            let mut row = vec![
+                if with_actual && e.is_mismatch() {
+                    "!".to_string()
+                } else {
+                    " ".to_string()
+                },
+                offset(e.expected),
+            ];
+
+            if with_actual {
+                row.push(offset(e.actual));
+            }
+
+            row.extend([path.to_string(), e.type_path.clone(), felt(e.expected)]);
+
+            if with_actual {
+                row.push(felt(e.actual));
+            }
+
+            row
+        })
+        .collect();
+
+    let mut widths = vec![0; rows.first().map_or(0, Vec::len)];
+    for row in &rows {
+        for (w, cell) in widths.iter_mut().zip(row) {
+            *w = (*w).max(cell.len());
+        }
+    }
+
+    let mut out = String::new();
+    for row in rows {
+        let line: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, w)| format!("{cell:<w$}"))
+            .collect();
+        out.push_str(line.join("  ").trim_end());
+        out.push('\n');
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_felts() {
+        assert_eq!(
+            parse_felts(&["0x1,2".to_string(), "0x3 4".to_string()]).unwrap(),
+            vec![Felt::ONE, Felt::TWO, Felt::THREE, Felt::from(4)]
+        );
+        assert!(parse_felts(&["0xg".to_string()]).is_err());
+        assert!(parse_felts(&["1a".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_format_entries() {
+        let token = Token::parse("core::integer::u256").unwrap();
+        let entries =
+            felt_diff::compare(&token, &[Felt::ONE, Felt::ZERO], &[Felt::ONE, Felt::TWO]).unwrap();
+
+        assert_eq!(
+            format_entries(&entries, true, false),
+            "!  1  1  high  core::integer::u128  0x0  0x2\n"
+        );
+        assert_eq!(
+            format_entries(&entries, true, true),
+            "   0  0  low   core::integer::u128  0x1  0x1\n\
+             !  1  1  high  core::integer::u128  0x0  0x2\n"
+        );
+    }
+}
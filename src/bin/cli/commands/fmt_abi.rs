@@ -0,0 +1,80 @@
+//! Normalization of an ABI file.
+//!
+//! The canonical form of an ABI doesn't depend on the order in which the
+//! compiler outputs the entries, its hash can then be used for caching or diffing.
+use cainome_parser::AbiParser;
+use camino::Utf8PathBuf;
+use clap::Args;
+use starknet::core::utils::starknet_keccak;
+
+use crate::error::{CainomeCliResult, Error};
+
+#[derive(Debug, Args)]
+pub struct FmtAbiArgs {
+    #[arg(value_name = "PATH")]
+    #[arg(
+        help = "Path of the ABI to normalize, as a JSON array of ABI entries or a Sierra class."
+    )]
+    pub abi: Utf8PathBuf,
+
+    #[arg(long)]
+    #[arg(value_name = "PATH")]
+    #[arg(help = "File where the normalized ABI is written. Defaults to the standard output.")]
+    pub output: Option<Utf8PathBuf>,
+
+    #[arg(long)]
+    #[arg(help = "Outputs the Starknet keccak of the normalized ABI instead of the ABI itself.")]
+    pub hash: bool,
+}
+
+/// Normalizes the ABI described by `args`.
+pub fn run(args: FmtAbiArgs) -> CainomeCliResult<()> {
+    let content = std::fs::read_to_string(&args.abi)?;
+    let entries = AbiParser::parse_abi_string(&content)
+        .map_err(|e| Error::Other(format!("ABI file {} could not be parsed: {e:?}", args.abi)))?;
+
+    let normalized = AbiParser::normalize_abi(&entries);
+
+    for conflict in &normalized.conflicts {
+        tracing::warn!(
+            "Duplicated {} `{}` with different definitions, keeping the first one",
+            conflict.kind,
+            conflict.name,
+        );
+    }
+
+    let out = format_output(
+        &serde_json::to_string_pretty(&normalized.entries)?,
+        args.hash,
+    );
+
+    match args.output {
+        Some(path) => std::fs::write(path, out)?,
+        None => print!("{}", out),
+    }
+
+    Ok(())
+}
+
+/// Returns the output for the canonical JSON of the ABI, its hash if `hash` is set.
+fn format_output(canonical: &str, hash: bool) -> String {
+    if hash {
+        format!("{:#x}\n", starknet_keccak(canonical.as_bytes()))
+    } else {
+        format!("{}\n", canonical)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_output() {
+        assert_eq!(format_output("[]", false), "[]\n");
+        assert_eq!(
+            format_output("[]", true),
+            format!("{:#x}\n", starknet_keccak(b"[]"))
+        );
+    }
+}
@@ -0,0 +1,652 @@
+//! Scaffolding of a new project using the bindings of contracts.
+//!
+//! `cainome init my-project --template indexer --abi path/erc20.abi.json` creates
+//! a Cargo workspace with:
+//!
+//! * `bindings`: a library with the `abigen!` bindings of each ABI, copied into `bindings/abis`.
+//! * `app`: the `my-project` binary, a working example of the template using the bindings
+//!   of the first ABI.
+//!
+//! The templates are:
+//!
+//! * `indexer`: replays the events of the contract from a block.
+//! * `bot`: invokes an external of the contract at a regular interval.
+//! * `cli`: calls or invokes any entrypoint of the contract from the command line.
+use cainome_parser::tokens::StateMutability;
+use cainome_parser::{AbiParser, TokenizedAbi};
+use camino::Utf8PathBuf;
+use clap::Args;
+use convert_case::Case;
+
+use crate::error::{CainomeCliResult, Error};
+use crate::plugins::utils::sanitize_identifier;
+
+const WORKSPACE_MANIFEST: &str = r#"[workspace]
+resolver = "2"
+members = ["app", "bindings"]
+
+[workspace.dependencies]
+bindings = { path = "bindings" }
+cainome = { version = "__CAINOME_VERSION__", features = ["abigen-rs"] }
+futures-util = "0.3"
+serde = { version = "1.0", features = ["derive"] }
+starknet = "0.12"
+tokio = { version = "1", features = ["full"] }
+"#;
+
+const BINDINGS_MANIFEST: &str = r#"[package]
+name = "bindings"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+cainome.workspace = true
+serde.workspace = true
+starknet.workspace = true
+"#;
+
+const APP_MANIFEST: &str = r#"[package]
+name = "__PROJECT__"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+bindings.workspace = true
+cainome.workspace = true
+futures-util.workspace = true
+starknet.workspace = true
+tokio.workspace = true
+"#;
+
+const BINDINGS_LIB_HEADER: &str = r#"//! Bindings of the contracts, generated by cainome from the ABIs of the `abis` directory.
+//!
+//! An ABI is updated by replacing its file, the bindings being generated at compile time.
+//! The paths of the ABIs are relative to the root of the workspace, where cargo compiles the crates.
+"#;
+
+const BINDINGS_MODULE: &str = r#"
+pub mod __MODULE__ {
+    cainome::rs::abigen!(
+        __CONTRACT__,
+        "bindings/abis/__MODULE__.json",
+        derives(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize),
+        execution_version("v3"),
+        prelude
+    );
+}
+"#;
+
+const GITIGNORE: &str = "/target\n";
+
+/// Helpers shared by the examples, reading the configuration from the environment.
+const APP_ENV: &str = r#"
+/// Returns the value of the environment variable `name`.
+fn env(name: &str) -> Result<String, String> {
+    std::env::var(name).map_err(|_| format!("`{}` is not set", name))
+}
+
+/// Returns the provider of the `STARKNET_RPC_URL` node.
+fn provider() -> Result<JsonRpcClient<HttpTransport>, Box<dyn std::error::Error>> {
+    Ok(JsonRpcClient::new(HttpTransport::new(Url::parse(&env(
+        "STARKNET_RPC_URL",
+    )?)?)))
+}
+"#;
+
+const APP_ACCOUNT: &str = r#"
+type Account = SingleOwnerAccount<JsonRpcClient<HttpTransport>, LocalWallet>;
+
+/// Returns the account at `ACCOUNT_ADDRESS` signing with `PRIVATE_KEY`.
+async fn account() -> Result<Account, Box<dyn std::error::Error>> {
+    let provider = provider()?;
+    let chain_id = provider.chain_id().await?;
+    let signer = LocalWallet::from(SigningKey::from_secret_scalar(Felt::from_hex(&env(
+        "PRIVATE_KEY",
+    )?)?));
+    let address = Felt::from_hex(&env("ACCOUNT_ADDRESS")?)?;
+
+    Ok(SingleOwnerAccount::new(
+        provider,
+        signer,
+        address,
+        chain_id,
+        ExecutionEncoding::New,
+    ))
+}
+
+/// Returns the selector of the entrypoint named `name`.
+fn selector(name: &str) -> Result<Felt, String> {
+    __CONTRACT__::<Account>::entrypoints()
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, selector)| *selector)
+        .ok_or_else(|| format!("`{}` is not an entrypoint of __CONTRACT__", name))
+}
+
+/// Parses comma separated felts, like `0x1,2`.
+fn calldata(felts: &str) -> Result<Vec<Felt>, Box<dyn std::error::Error>> {
+    felts
+        .split(',')
+        .filter(|f| !f.trim().is_empty())
+        .map(|f| Ok(Felt::from_str(f.trim())?))
+        .collect()
+}
+"#;
+
+const INDEXER_MAIN: &str = r#"//! Indexer of the `__CONTRACT__` contract, replaying its events from a block.
+//!
+//! Configured with the `STARKNET_RPC_URL`, `CONTRACT_ADDRESS` and `FROM_BLOCK`
+//! (the first block by default) environment variables.
+use bindings::__MODULE__::__CONTRACT__Reader;
+use futures_util::StreamExt;
+use starknet::core::types::{BlockId, BlockTag, Felt};
+use starknet::providers::jsonrpc::HttpTransport;
+use starknet::providers::{JsonRpcClient, Url};
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let address = Felt::from_hex(&env("CONTRACT_ADDRESS")?)?;
+    let from_block = match std::env::var("FROM_BLOCK") {
+        Ok(block) => block.parse()?,
+        Err(_) => 0,
+    };
+
+    let reader = __CONTRACT__Reader::new(address, provider()?);
+
+    let mut events = Box::pin(reader.events(
+        BlockId::Number(from_block),
+        BlockId::Tag(BlockTag::Latest),
+        None,
+    ));
+
+    while let Some(event) = events.next().await {
+        let event = event?;
+        println!(
+            "block {} tx {:#x}: {:?}",
+            event.block_number, event.transaction_hash, event.event
+        );
+    }
+
+    Ok(())
+}
+"#;
+
+const BOT_MAIN: &str = r#"//! Bot of the `__CONTRACT__` contract, invoking an external at a regular interval.
+//!
+//! Configured with the `STARKNET_RPC_URL`, `CONTRACT_ADDRESS`, `ACCOUNT_ADDRESS` and `PRIVATE_KEY`
+//! environment variables, and:
+//!
+//! * `ENTRYPOINT`: the external to invoke (`__EXTERNAL__` by default).
+//! * `CALLDATA`: its calldata, as comma separated felts (empty by default).
+//! * `INTERVAL_SECS`: the interval between two invocations (60 by default).
+use bindings::__MODULE__::__CONTRACT__;
+use starknet::accounts::{ExecutionEncoding, SingleOwnerAccount};
+use starknet::core::types::Felt;
+use starknet::providers::jsonrpc::HttpTransport;
+use starknet::providers::{JsonRpcClient, Provider, Url};
+use starknet::signers::{LocalWallet, SigningKey};
+use std::str::FromStr;
+use std::time::Duration;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let address = Felt::from_hex(&env("CONTRACT_ADDRESS")?)?;
+    let entrypoint = env("ENTRYPOINT").unwrap_or_else(|_| "__EXTERNAL__".to_string());
+    let selector = selector(&entrypoint)?;
+    let calldata = calldata(&env("CALLDATA").unwrap_or_default())?;
+    let interval = match std::env::var("INTERVAL_SECS") {
+        Ok(secs) => Duration::from_secs(secs.parse()?),
+        Err(_) => Duration::from_secs(60),
+    };
+
+    let contract = __CONTRACT__::new(address, account().await?);
+
+    loop {
+        let execution = contract.invoke_raw(selector, calldata.clone());
+__SEND__
+        tokio::time::sleep(interval).await;
+    }
+}
+"#;
+
+/// Sending of the bot execution, waiting for the decoded events when the contract has some.
+const BOT_SEND_AND_DECODE: &str = r#"        let result = contract.send_and_decode(execution).await?;
+
+        println!("{} sent in tx {:#x}", entrypoint, result.transaction_hash);
+        for event in result.events {
+            println!("  {:?}", event);
+        }
+"#;
+
+const BOT_SEND: &str = r#"        let result = execution.send().await?;
+
+        println!("{} sent in tx {:#x}", entrypoint, result.transaction_hash);
+"#;
+
+const CLI_MAIN: &str = r#"//! Command line of the `__CONTRACT__` contract, calling or invoking its entrypoints:
+//!
+//! ```text
+//! __PROJECT__ call <ENTRYPOINT> [CALLDATA]
+//! __PROJECT__ invoke <ENTRYPOINT> [CALLDATA]
+//! ```
+//!
+//! The calldata is given as comma separated felts (like `0x1,2`). Configured with the
+//! `STARKNET_RPC_URL` and `CONTRACT_ADDRESS` environment variables, and the
+//! `ACCOUNT_ADDRESS` and `PRIVATE_KEY` ones to invoke.
+use bindings::__MODULE__::{__CONTRACT__Reader, __CONTRACT__};
+use starknet::accounts::{ExecutionEncoding, SingleOwnerAccount};
+use starknet::core::types::Felt;
+use starknet::providers::jsonrpc::HttpTransport;
+use starknet::providers::{JsonRpcClient, Provider, Url};
+use starknet::signers::{LocalWallet, SigningKey};
+use std::str::FromStr;
+
+const USAGE: &str = "usage: __PROJECT__ <call|invoke> <ENTRYPOINT> [CALLDATA]";
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (Some(command), Some(entrypoint)) = (args.first(), args.get(1)) else {
+        return Err(USAGE.into());
+    };
+
+    let address = Felt::from_hex(&env("CONTRACT_ADDRESS")?)?;
+    let selector = selector(entrypoint)?;
+    let calldata = calldata(args.get(2).map(String::as_str).unwrap_or_default())?;
+
+    match command.as_str() {
+        "call" => {
+            let reader = __CONTRACT__Reader::new(address, provider()?);
+
+            for felt in reader.call_raw(selector, calldata).await? {
+                println!("{:#x}", felt);
+            }
+        }
+        "invoke" => {
+            let contract = __CONTRACT__::new(address, account().await?);
+            let result = contract.invoke_raw(selector, calldata).send().await?;
+
+            println!("{:#x}", result.transaction_hash);
+        }
+        _ => return Err(USAGE.into()),
+    }
+
+    Ok(())
+}
+"#;
+
+const README: &str = r#"# __PROJECT__
+
+Generated by `cainome init` with the `__TEMPLATE__` template.
+
+* `bindings`: the bindings of the contracts, generated at compile time from the ABIs of `bindings/abis`.
+* `app`: the `__PROJECT__` binary, using the bindings of the `__CONTRACT__` contract.
+
+The configuration is read from the environment, see the documentation at the top of `app/src/main.rs`:
+
+```sh
+STARKNET_RPC_URL=http://localhost:5050 CONTRACT_ADDRESS=0x... cargo run
+```
+"#;
+
+/// The example program of the scaffolded project.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Template {
+    /// Replays the events of the contract.
+    Indexer,
+    /// Invokes an external of the contract in a loop.
+    Bot,
+    /// Calls or invokes the entrypoints of the contract from the command line.
+    Cli,
+}
+
+impl std::fmt::Display for Template {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Template::Indexer => write!(f, "indexer"),
+            Template::Bot => write!(f, "bot"),
+            Template::Cli => write!(f, "cli"),
+        }
+    }
+}
+
+impl std::str::FromStr for Template {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input {
+            "indexer" => Ok(Template::Indexer),
+            "bot" => Ok(Template::Bot),
+            "cli" => Ok(Template::Cli),
+            _ => Err(format!(
+                "Invalid template '{}'. Supported values are 'indexer', 'bot' or 'cli'.",
+                input
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Args)]
+pub struct InitArgs {
+    #[arg(value_name = "NAME")]
+    #[arg(help = "Name of the project, and of its directory (e.g. `my-indexer`).")]
+    pub name: String,
+
+    #[arg(long)]
+    #[arg(value_name = "TEMPLATE")]
+    #[arg(default_value = "cli")]
+    #[arg(
+        help = "Example program of the project. Supported values are 'indexer' \
+                  (replays the events of the contract), 'bot' (invokes an external at a regular interval) \
+                  or 'cli' (calls or invokes the entrypoints from the command line)."
+    )]
+    pub template: Template,
+
+    #[arg(long = "abi")]
+    #[arg(value_name = "PATH")]
+    #[arg(required = true)]
+    #[arg(
+        help = "ABI of a contract, as a JSON array of ABI entries or a Sierra class, \
+                  named after the contract (e.g. `erc20.abi.json`). The example uses the first one."
+    )]
+    pub abis: Vec<Utf8PathBuf>,
+
+    #[arg(long)]
+    #[arg(value_name = "PATH")]
+    #[arg(default_value = ".")]
+    #[arg(help = "Directory where the project directory is created.")]
+    pub path: Utf8PathBuf,
+}
+
+/// A contract of the project.
+struct InitContract {
+    /// Name of the contract in Pascal case, used for the bindings.
+    name: String,
+    /// Name of the module of the bindings, and of the ABI file.
+    module: String,
+    /// Content of the ABI file.
+    abi: String,
+    tokens: TokenizedAbi,
+}
+
+impl InitContract {
+    fn load(path: &Utf8PathBuf) -> CainomeCliResult<Self> {
+        let abi = std::fs::read_to_string(path)?;
+        let tokens = AbiParser::tokens_from_abi_string(&abi, &Default::default())
+            .map_err(|e| Error::Other(format!("ABI file {} could not be parsed: {e:?}", path)))?;
+
+        let file_name = path.file_name().unwrap_or_default();
+        let stem = file_name.split('.').next().unwrap_or_default();
+        let name = sanitize_identifier(stem, Case::Snake, Case::Pascal);
+        let module = sanitize_identifier(&name, Case::Pascal, Case::Snake);
+
+        Ok(Self {
+            name,
+            module,
+            abi,
+            tokens,
+        })
+    }
+
+    /// Returns the name of the first external, sorted by name.
+    fn first_external(&self) -> Option<String> {
+        let mut externals: Vec<String> = self
+            .tokens
+            .functions
+            .iter()
+            .chain(self.tokens.interfaces.values().flatten())
+            .filter_map(|t| t.to_function().ok())
+            .filter(|f| f.state_mutability == StateMutability::External)
+            .map(|f| f.name.clone())
+            .collect();
+
+        externals.sort();
+        externals.into_iter().next()
+    }
+}
+
+/// Scaffolds the project described by `args`.
+pub fn run(args: InitArgs) -> CainomeCliResult<()> {
+    validate_name(&args.name)?;
+
+    let contracts = args
+        .abis
+        .iter()
+        .map(InitContract::load)
+        .collect::<CainomeCliResult<Vec<_>>>()?;
+
+    let files = project_files(&args.name, args.template, &contracts)?;
+
+    let root = args.path.join(&args.name);
+    if root.exists() {
+        return Err(Error::Other(format!("`{}` already exists", root)));
+    }
+
+    for (path, content) in &files {
+        let path = root.join(path);
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        std::fs::write(&path, content)?;
+    }
+
+    tracing::info!(
+        "Project `{}` scaffolded at `{}` with the {} template",
+        args.name,
+        root,
+        args.template
+    );
+
+    Ok(())
+}
+
+/// Returns the files of the project, with their path relative to its root.
+fn project_files(
+    name: &str,
+    template: Template,
+    contracts: &[InitContract],
+) -> CainomeCliResult<Vec<(String, String)>> {
+    let Some(contract) = contracts.first() else {
+        return Err(Error::Other("At least one ABI is expected".to_string()));
+    };
+
+    if let Some(duplicate) = contracts
+        .iter()
+        .enumerate()
+        .find(|(i, c)| contracts[..*i].iter().any(|o| o.module == c.module))
+    {
+        return Err(Error::Other(format!(
+            "Several ABIs are named `{}`",
+            duplicate.1.module
+        )));
+    }
+
+    let has_events = contract.tokens.root_event().is_some();
+
+    let main = match template {
+        Template::Indexer => {
+            if !has_events {
+                return Err(Error::Other(format!(
+                    "The indexer template replays the events, and the `{}` contract has none",
+                    contract.name
+                )));
+            }
+
+            format!("{}{}", INDEXER_MAIN, APP_ENV)
+        }
+        Template::Bot => {
+            let send = if has_events {
+                BOT_SEND_AND_DECODE
+            } else {
+                BOT_SEND
+            };
+
+            format!("{}{}{}", BOT_MAIN, APP_ENV, APP_ACCOUNT)
+                .replace("__SEND__\n", send)
+                .replace(
+                    "__EXTERNAL__",
+                    &contract.first_external().unwrap_or_default(),
+                )
+        }
+        Template::Cli => format!("{}{}{}", CLI_MAIN, APP_ENV, APP_ACCOUNT),
+    };
+
+    let mut lib = BINDINGS_LIB_HEADER.to_string();
+    for c in contracts {
+        lib.push_str(
+            &BINDINGS_MODULE
+                .replace("__MODULE__", &c.module)
+                .replace("__CONTRACT__", &c.name),
+        );
+    }
+
+    let mut files = vec![
+        (
+            "Cargo.toml".to_string(),
+            WORKSPACE_MANIFEST.replace("__CAINOME_VERSION__", env!("CARGO_PKG_VERSION")),
+        ),
+        (".gitignore".to_string(), GITIGNORE.to_string()),
+        ("README.md".to_string(), README.to_string()),
+        ("app/Cargo.toml".to_string(), APP_MANIFEST.to_string()),
+        ("app/src/main.rs".to_string(), main),
+        (
+            "bindings/Cargo.toml".to_string(),
+            BINDINGS_MANIFEST.to_string(),
+        ),
+        ("bindings/src/lib.rs".to_string(), lib),
+    ];
+
+    for (_, content) in files.iter_mut() {
+        *content = content
+            .replace("__PROJECT__", name)
+            .replace("__TEMPLATE__", &template.to_string())
+            .replace("__MODULE__", &contract.module)
+            .replace("__CONTRACT__", &contract.name);
+    }
+
+    // The ABIs are copied as is.
+    files.extend(
+        contracts
+            .iter()
+            .map(|c| (format!("bindings/abis/{}.json", c.module), c.abi.clone())),
+    );
+
+    Ok(files)
+}
+
+/// Ensures the project name can be used as a Cargo package name.
+fn validate_name(name: &str) -> CainomeCliResult<()> {
+    let valid = name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-')
+        && name != "bindings";
+
+    if !valid {
+        return Err(Error::Other(format!(
+            "Invalid project name `{}`: expected a Cargo package name (like `my-indexer`)",
+            name
+        )));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ABI: &str = r#"[
+        {
+            "type": "function",
+            "name": "transfer",
+            "inputs": [{ "name": "amount", "type": "core::felt252" }],
+            "outputs": [],
+            "state_mutability": "external"
+        },
+        {
+            "type": "event",
+            "name": "pkg::Transfer",
+            "kind": "struct",
+            "members": [{ "name": "amount", "type": "core::felt252", "kind": "data" }]
+        },
+        {
+            "type": "event",
+            "name": "pkg::Event",
+            "kind": "enum",
+            "variants": [{ "name": "Transfer", "type": "pkg::Transfer", "kind": "nested" }]
+        }
+    ]"#;
+
+    fn contract(name: &str, abi: &str) -> InitContract {
+        InitContract {
+            name: sanitize_identifier(name, Case::Snake, Case::Pascal),
+            module: name.to_string(),
+            abi: abi.to_string(),
+            tokens: AbiParser::tokens_from_abi_string(abi, &Default::default()).unwrap(),
+        }
+    }
+
+    fn file<'a>(files: &'a [(String, String)], path: &str) -> &'a str {
+        &files.iter().find(|(p, _)| p == path).unwrap().1
+    }
+
+    #[test]
+    fn test_project_files() {
+        let contracts = [contract("my_token", ABI), contract("vault", "[]")];
+        let files = project_files("my-bot", Template::Bot, &contracts).unwrap();
+
+        let lib = file(&files, "bindings/src/lib.rs");
+        assert!(lib.contains("pub mod my_token {"));
+        assert!(lib.contains("        MyToken,\n        \"bindings/abis/my_token.json\","));
+        assert!(lib.contains("pub mod vault {"));
+        assert_eq!(file(&files, "bindings/abis/vault.json"), "[]");
+
+        let main = file(&files, "app/src/main.rs");
+        assert!(main.contains("use bindings::my_token::MyToken;"));
+        assert!(main.contains("unwrap_or_else(|_| \"transfer\".to_string())"));
+        assert!(main.contains("contract.send_and_decode(execution)"));
+        assert!(!main.contains("__"));
+
+        assert!(file(&files, "app/Cargo.toml").contains("name = \"my-bot\""));
+        assert!(file(&files, "Cargo.toml").contains(&format!(
+            "cainome = {{ version = \"{}\"",
+            env!("CARGO_PKG_VERSION")
+        )));
+    }
+
+    #[test]
+    fn test_project_files_without_events() {
+        let contracts = [contract("vault", "[]")];
+
+        assert!(project_files("my-indexer", Template::Indexer, &contracts).is_err());
+
+        let files = project_files("my-bot", Template::Bot, &contracts).unwrap();
+        assert!(file(&files, "app/src/main.rs").contains("execution.send().await?"));
+    }
+
+    #[test]
+    fn test_project_files_duplicated_names() {
+        let contracts = [contract("vault", "[]"), contract("vault", "[]")];
+        assert!(project_files("my-cli", Template::Cli, &contracts).is_err());
+    }
+
+    #[test]
+    fn test_parse_template() {
+        assert_eq!("indexer".parse(), Ok(Template::Indexer));
+        assert_eq!("bot".parse(), Ok(Template::Bot));
+        assert_eq!("cli".parse(), Ok(Template::Cli));
+        assert!("web".parse::<Template>().is_err());
+    }
+
+    #[test]
+    fn test_validate_name() {
+        assert!(validate_name("my-indexer").is_ok());
+        assert!(validate_name("bot_2").is_ok());
+        assert!(validate_name("My-Indexer").is_err());
+        assert!(validate_name("2bot").is_err());
+        assert!(validate_name("bindings").is_err());
+    }
+}
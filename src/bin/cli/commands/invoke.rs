@@ -0,0 +1,96 @@
+//! Invocation of an external function of a deployed contract.
+//!
+//! The transaction is sent from an account whose credentials are given as described
+//! in the [`crate::wallet`] module. The arguments are encoded like for the `call` command.
+use cainome_parser::tokens::StateMutability;
+use cainome_rs::ExecutionVersion;
+use clap::Args;
+use starknet::accounts::{Account, ExecutionEncoding, SingleOwnerAccount};
+use starknet::core::types::{BlockId, BlockTag, Call, Felt};
+use starknet::providers::Provider;
+
+use crate::codec::Codec;
+use crate::commands::call::FunctionArgs;
+use crate::error::{CainomeCliResult, Error};
+use crate::wallet::WalletArgs;
+
+#[derive(Debug, Args)]
+pub struct InvokeArgs {
+    #[command(flatten)]
+    pub function: FunctionArgs,
+
+    #[arg(long)]
+    #[arg(value_name = "ADDRESS")]
+    #[arg(help = "Address of the account sending the transaction.")]
+    pub account_address: Felt,
+
+    #[command(flatten)]
+    pub wallet: WalletArgs,
+
+    #[arg(long)]
+    #[arg(value_name = "EXECUTION_VERSION")]
+    #[arg(default_value = "v3")]
+    #[arg(env = "CAINOME_EXECUTION_VERSION")]
+    #[arg(
+        help = "The execution version of the transaction. Supported values are 'v1' or 'v3' (case insensitive)."
+    )]
+    pub execution_version: ExecutionVersion,
+}
+
+/// Invokes the function described by `args`, and prints the hash of the transaction.
+pub async fn run(args: InvokeArgs) -> CainomeCliResult<()> {
+    let abi = args.function.load_abi()?;
+    let codec = Codec::new(&abi);
+
+    let func = codec.function(&args.function.function)?;
+    if func.state_mutability == StateMutability::View {
+        tracing::warn!(
+            "`{}` is a view function, invoking it only costs fees",
+            func.name
+        );
+    }
+
+    let (selector, calldata) = args.function.encode(&codec)?;
+    let signer = args.wallet.signer()?;
+
+    let provider = args.function.provider();
+    let chain_id = provider.chain_id().await?;
+
+    let mut account = SingleOwnerAccount::new(
+        provider,
+        signer,
+        args.account_address,
+        chain_id,
+        ExecutionEncoding::New,
+    );
+    account.set_block_id(BlockId::Tag(BlockTag::Pending));
+
+    let calls = vec![Call {
+        to: args.function.address,
+        selector,
+        calldata,
+    }];
+
+    let transaction_hash = match args.execution_version {
+        ExecutionVersion::V1 => account
+            .execute_v1(calls)
+            .send()
+            .await
+            .map(|r| r.transaction_hash),
+        ExecutionVersion::V3 => account
+            .execute_v3(calls)
+            .send()
+            .await
+            .map(|r| r.transaction_hash),
+        version => {
+            return Err(Error::Other(format!(
+                "Execution version {version} is not supported to invoke a function"
+            )))
+        }
+    }
+    .map_err(|e| Error::Other(format!("Transaction could not be sent: {e}")))?;
+
+    println!("{transaction_hash:#x}");
+
+    Ok(())
+}
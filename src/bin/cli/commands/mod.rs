@@ -0,0 +1,56 @@
+//! Cainome CLI subcommands.
+//!
+//! Without any subcommand, cainome generates bindings using the
+//! top level arguments. Subcommands are used for any other tooling.
+use clap::Subcommand;
+
+use crate::error::CainomeCliResult;
+
+mod call;
+mod diff_felts;
+mod fmt_abi;
+mod init;
+mod invoke;
+mod new_plugin;
+pub use call::CallArgs;
+pub use diff_felts::DiffFeltsArgs;
+pub use fmt_abi::FmtAbiArgs;
+pub use init::InitArgs;
+pub use invoke::InvokeArgs;
+pub use new_plugin::NewPluginArgs;
+
+#[derive(Debug, Subcommand)]
+pub enum Commands {
+    #[command(about = "Scaffolds a new builtin plugin into the cainome sources.")]
+    NewPlugin(NewPluginArgs),
+    #[command(
+        about = "Scaffolds a Cargo workspace using the bindings of ABIs, with an example program."
+    )]
+    Init(InitArgs),
+    #[command(about = "Normalizes an ABI into a canonical form, to be hashed or diffed.")]
+    FmtAbi(FmtAbiArgs),
+    #[command(
+        about = "Compares two serialized values felt by felt, labelling the felts with their type."
+    )]
+    DiffFelts(DiffFeltsArgs),
+    #[command(
+        about = "Calls a view function of a deployed contract, decoding its outputs with the ABI."
+    )]
+    Call(CallArgs),
+    #[command(about = "Invokes an external function of a deployed contract from an account.")]
+    Invoke(InvokeArgs),
+}
+
+impl Commands {
+    /// Runs the subcommand.
+    pub async fn run(self) -> CainomeCliResult<()> {
+        match self {
+            Commands::NewPlugin(args) => new_plugin::run(args),
+            Commands::Init(args) => init::run(args),
+            Commands::FmtAbi(args) => fmt_abi::run(args),
+            Commands::DiffFelts(args) => diff_felts::run(args),
+            Commands::Call(args) => call::run(args).await,
+            Commands::Invoke(args) => invoke::run(args).await,
+        }
+    }
+}
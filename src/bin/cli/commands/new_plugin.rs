@@ -0,0 +1,288 @@
+//! Scaffolding of a new builtin plugin.
+//!
+//! The builtin plugins are rust modules compiled into the cainome binary.
+//! Adding one requires touching several files, which is automated here:
+//!
+//! * `src/bin/cli/plugins/builtins/<name>.rs`: the `BuiltinPlugin` skeleton and its tests.
+//! * `src/bin/cli/plugins/builtins/mod.rs`: module declaration and `BuiltinPlugins` variant.
+//! * `src/bin/cli/plugins/mod.rs`: registration into the `PluginManager`.
+//! * `src/bin/cli/args.rs`: the `--<name>` flag into the `PluginOptions`.
+//!
+//! The insertion points are identified by anchor comments in those files,
+//! which must not be removed.
+use camino::Utf8PathBuf;
+use clap::Args;
+use convert_case::{Case, Casing};
+
+use crate::error::{CainomeCliResult, Error};
+use crate::plugins::utils::RUST_KEYWORDS;
+
+const BUILTINS_DIR: &str = "src/bin/cli/plugins/builtins";
+const PLUGINS_MOD: &str = "src/bin/cli/plugins/mod.rs";
+const ARGS: &str = "src/bin/cli/args.rs";
+
+const ANCHOR_MODULES: &str = "// cainome:new-plugin:modules";
+const ANCHOR_VARIANTS: &str = "// cainome:new-plugin:variants";
+const ANCHOR_BUILDERS: &str = "// cainome:new-plugin:builders";
+const ANCHOR_OPTIONS: &str = "// cainome:new-plugin:options";
+const ANCHOR_REGISTRATIONS: &str = "// cainome:new-plugin:registrations";
+
+const PLUGIN_TEMPLATE: &str = r#"use async_trait::async_trait;
+use camino::Utf8PathBuf;
+use convert_case::Case;
+
+use crate::error::CainomeCliResult;
+use crate::plugins::builtins::BuiltinPlugin;
+use crate::plugins::utils::contract_identifier;
+use crate::plugins::PluginInput;
+
+pub struct __NAME__Plugin;
+
+impl __NAME__Plugin {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl BuiltinPlugin for __NAME__Plugin {
+    fn name(&self) -> &'static str {
+        "__SNAKE__"
+    }
+
+    // TODO: return the command formatting the generated files, if any.
+    fn formatter(&self) -> Option<&'static str> {
+        None
+    }
+
+    async fn generate_code(&self, input: &PluginInput) -> CainomeCliResult<Vec<Utf8PathBuf>> {
+        tracing::trace!("__NAME__ plugin requested");
+
+        let mut files = vec![];
+
+        for contract in &input.contracts {
+            let contract_name = contract_identifier(contract, Case::Snake);
+
+            // TODO: lower `contract.tokens` into the target language.
+            let generated = String::new();
+
+            let mut out_path = input.output_dir.clone();
+            out_path.push(format!("{}.__EXTENSION__", contract_name));
+
+            tracing::trace!("__NAME__ writing file {}", out_path);
+            std::fs::write(&out_path, generated)?;
+
+            files.push(out_path);
+        }
+
+        Ok(files)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugins::test_utils;
+
+    #[tokio::test]
+    async fn test_generate_code() {
+        let input = test_utils::plugin_input("__SNAKE__", &["my_contract"]);
+
+        let files = __NAME__Plugin::new().generate_code(&input).await.unwrap();
+
+        assert_eq!(files, vec![input.output_dir.join("my_contract.__EXTENSION__")]);
+        assert!(files[0].is_file());
+    }
+}
+"#;
+
+#[derive(Debug, Args)]
+pub struct NewPluginArgs {
+    #[arg(value_name = "NAME")]
+    #[arg(help = "Name of the plugin, in snake case (e.g. `typescript`).")]
+    pub name: String,
+
+    #[arg(long)]
+    #[arg(value_name = "EXTENSION")]
+    #[arg(help = "Extension of the files generated by the plugin. Defaults to the plugin name.")]
+    pub extension: Option<String>,
+
+    #[arg(long)]
+    #[arg(value_name = "PATH")]
+    #[arg(default_value = ".")]
+    #[arg(help = "Root of the cainome repository where the plugin is added.")]
+    pub repo_root: Utf8PathBuf,
+}
+
+/// Scaffolds the builtin plugin described by `args`.
+pub fn run(args: NewPluginArgs) -> CainomeCliResult<()> {
+    validate_name(&args.name)?;
+
+    let snake = args.name.clone();
+    let pascal = snake.to_case(Case::Pascal);
+    let extension = args.extension.unwrap_or_else(|| snake.clone());
+
+    let builtins_dir = args.repo_root.join(BUILTINS_DIR);
+    let builtins_mod = builtins_dir.join("mod.rs");
+
+    if !builtins_mod.is_file() {
+        return Err(Error::Other(format!(
+            "`{}` is not the root of a cainome repository",
+            args.repo_root
+        )));
+    }
+
+    let plugin_path = builtins_dir.join(format!("{}.rs", snake));
+    if plugin_path.exists() {
+        return Err(Error::Other(format!(
+            "Plugin `{}` already exists at `{}`",
+            snake, plugin_path
+        )));
+    }
+
+    // All the files are patched in memory first, to not leave
+    // the repository half modified if an anchor is missing.
+    let builtins = patch(
+        &builtins_mod,
+        &[
+            (
+                ANCHOR_MODULES,
+                format!("mod {snake};\npub use {snake}::{pascal}Plugin;"),
+            ),
+            (ANCHOR_VARIANTS, format!("{pascal},")),
+        ],
+    )?;
+
+    let plugins_mod_path = args.repo_root.join(PLUGINS_MOD);
+    let plugins = patch(
+        &plugins_mod_path,
+        &[(
+            ANCHOR_BUILDERS,
+            format!("BuiltinPlugins::{pascal} => Box::new(builtins::{pascal}Plugin::new()),"),
+        )],
+    )?;
+
+    let args_path = args.repo_root.join(ARGS);
+    let cli_args = patch(
+        &args_path,
+        &[
+            (
+                ANCHOR_OPTIONS,
+                format!(
+                    "\n#[arg(long)]\n#[arg(help = \"Generate bindings for {snake} (built-in).\")]\npub {snake}: bool,"
+                ),
+            ),
+            (
+                ANCHOR_REGISTRATIONS,
+                format!(
+                    "\nif options.{snake} {{\n    builtin_plugins.push(BuiltinPlugins::{pascal});\n}}"
+                ),
+            ),
+        ],
+    )?;
+
+    let plugin = PLUGIN_TEMPLATE
+        .replace("__NAME__", &pascal)
+        .replace("__SNAKE__", &snake)
+        .replace("__EXTENSION__", &extension);
+
+    std::fs::write(&plugin_path, plugin)?;
+    std::fs::write(&builtins_mod, builtins)?;
+    std::fs::write(&plugins_mod_path, plugins)?;
+    std::fs::write(&args_path, cli_args)?;
+
+    tracing::info!("Plugin `{}` scaffolded at `{}`", snake, plugin_path);
+    tracing::info!(
+        "Run `cargo test --features build-binary {}` to check the wiring.",
+        snake
+    );
+
+    Ok(())
+}
+
+/// Ensures the plugin name can be used as a module name and a CLI flag.
+fn validate_name(name: &str) -> CainomeCliResult<()> {
+    let valid = name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        && !RUST_KEYWORDS.contains(&name);
+
+    if !valid {
+        return Err(Error::Other(format!(
+            "Invalid plugin name `{}`: expected a snake case identifier",
+            name
+        )));
+    }
+
+    Ok(())
+}
+
+/// Reads the file at `path` and inserts each snippet before its anchor.
+fn patch(path: &Utf8PathBuf, insertions: &[(&str, String)]) -> CainomeCliResult<String> {
+    let mut content = std::fs::read_to_string(path)?;
+
+    for (anchor, snippet) in insertions {
+        content = insert_before_anchor(&content, anchor, snippet)
+            .ok_or_else(|| Error::Other(format!("Anchor `{}` not found in `{}`", anchor, path)))?;
+    }
+
+    Ok(content)
+}
+
+/// Inserts `snippet` before the line containing `anchor`, using the anchor indentation.
+/// Empty lines of the snippet are kept empty.
+fn insert_before_anchor(content: &str, anchor: &str, snippet: &str) -> Option<String> {
+    let anchor_start = content.find(anchor)?;
+    let line_start = content[..anchor_start].rfind('\n').map_or(0, |i| i + 1);
+    let indent = &content[line_start..anchor_start];
+
+    let mut indented = String::new();
+    for line in snippet.lines() {
+        if !line.is_empty() {
+            indented.push_str(indent);
+            indented.push_str(line);
+        }
+        indented.push('\n');
+    }
+
+    Some(format!(
+        "{}{}{}",
+        &content[..line_start],
+        indented,
+        &content[line_start..]
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_before_anchor() {
+        let content = "enum A {\n    X,\n    // anchor\n}\n";
+        let patched = insert_before_anchor(content, "// anchor", "Y,").unwrap();
+        assert_eq!(patched, "enum A {\n    X,\n    Y,\n    // anchor\n}\n");
+
+        let patched = insert_before_anchor(&patched, "// anchor", "\nZ,").unwrap();
+        assert_eq!(
+            patched,
+            "enum A {\n    X,\n    Y,\n\n    Z,\n    // anchor\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_insert_before_anchor_missing() {
+        assert!(insert_before_anchor("enum A {}", "// anchor", "Y,").is_none());
+    }
+
+    #[test]
+    fn test_validate_name() {
+        assert!(validate_name("typescript").is_ok());
+        assert!(validate_name("go_lang2").is_ok());
+        assert!(validate_name("TypeScript").is_err());
+        assert!(validate_name("2go").is_err());
+        assert!(validate_name("type-script").is_err());
+        assert!(validate_name("match").is_err());
+    }
+}
@@ -0,0 +1,190 @@
+//! Manifest of the contracts to fetch from the chain.
+//!
+//! The manifest is a JSON or TOML file (by its extension) listing the contracts, with the
+//! network they are deployed on. A network is the name of an entry of `networks`, or an RPC url:
+//!
+//! ```toml
+//! [networks]
+//! mainnet = "https://starknet-mainnet.example/rpc"
+//!
+//! [[contracts]]
+//! name = "Router"
+//! address = "0x1234"
+//! network = "mainnet"
+//! ```
+use camino::Utf8Path;
+use serde::Deserialize;
+use starknet::core::types::Felt;
+use std::collections::HashMap;
+use std::fs;
+use url::Url;
+
+use crate::error::{CainomeCliResult, Error};
+
+/// A contract listed in the manifest.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ManifestContract {
+    /// The name of the contract.
+    pub name: String,
+    /// The address of the contract.
+    pub address: Felt,
+    /// The name of a network of the manifest, or an RPC url.
+    /// The `--rpc-url` of the command is used when not given.
+    #[serde(default)]
+    pub network: Option<String>,
+}
+
+/// The manifest of the contracts to fetch from the chain.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ContractsManifest {
+    /// The RPC urls of the networks, by name.
+    #[serde(default)]
+    pub networks: HashMap<String, String>,
+    /// The contracts to fetch.
+    pub contracts: Vec<ManifestContract>,
+}
+
+impl ContractsManifest {
+    /// Reads a manifest, parsed as TOML if its extension is `toml`, as JSON otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the manifest.
+    pub fn from_file(path: &Utf8Path) -> CainomeCliResult<Self> {
+        let content = fs::read_to_string(path)?;
+
+        let manifest: Self = if path.extension() == Some("toml") {
+            toml::from_str(&content).map_err(|e| {
+                Error::Other(format!(
+                    "Contracts manifest {path} could not be parsed: {e}"
+                ))
+            })?
+        } else {
+            serde_json::from_str(&content)?
+        };
+
+        for (i, contract) in manifest.contracts.iter().enumerate() {
+            if manifest.contracts[..i]
+                .iter()
+                .any(|other| other.name == contract.name)
+            {
+                return Err(Error::Other(format!(
+                    "Contracts manifest {path} lists several contracts named {}",
+                    contract.name
+                )));
+            }
+        }
+
+        Ok(manifest)
+    }
+
+    /// Returns the RPC url of the network of a contract.
+    ///
+    /// # Arguments
+    ///
+    /// * `contract` - The contract of the manifest.
+    /// * `default_url` - The url used when the contract has no network.
+    pub fn rpc_url(
+        &self,
+        contract: &ManifestContract,
+        default_url: Option<&Url>,
+    ) -> CainomeCliResult<Url> {
+        let Some(network) = &contract.network else {
+            return default_url.cloned().ok_or_else(|| {
+                Error::Other(format!(
+                    "Contract {} has no network, and no `--rpc-url` is given",
+                    contract.name
+                ))
+            });
+        };
+
+        let url = self.networks.get(network).unwrap_or(network);
+
+        Url::parse(url).map_err(|_| {
+            Error::Other(format!(
+                "Contract {}: the network {network} is neither a network of the manifest nor an url",
+                contract.name
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugins::test_utils::temp_dir;
+    use camino::Utf8PathBuf;
+
+    fn write_manifest(file_name: &str, content: &str) -> Utf8PathBuf {
+        let path = temp_dir("manifest").join(file_name);
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_contracts_manifest() {
+        let toml_path = write_manifest(
+            "cainome-contracts-manifest.toml",
+            r#"
+            [networks]
+            mainnet = "https://mainnet.example/rpc"
+
+            [[contracts]]
+            name = "Router"
+            address = "0x1234"
+            network = "mainnet"
+
+            [[contracts]]
+            name = "Oracle"
+            address = "0x5678"
+            network = "https://sepolia.example/rpc"
+
+            [[contracts]]
+            name = "Vault"
+            address = "0x9abc"
+            "#,
+        );
+        let json_path = write_manifest(
+            "cainome-contracts-manifest.json",
+            r#"{ "contracts": [{ "name": "Router", "address": "0x1234", "network": "mainnet" }] }"#,
+        );
+
+        let manifest = ContractsManifest::from_file(&toml_path).unwrap();
+        let default_url = Url::parse("http://localhost:5050").unwrap();
+
+        assert_eq!(manifest.contracts.len(), 3);
+        assert_eq!(manifest.contracts[0].address, Felt::from(0x1234));
+
+        let urls: Vec<String> = manifest
+            .contracts
+            .iter()
+            .map(|c| manifest.rpc_url(c, Some(&default_url)).unwrap().to_string())
+            .collect();
+        assert_eq!(
+            urls,
+            vec![
+                "https://mainnet.example/rpc",
+                "https://sepolia.example/rpc",
+                "http://localhost:5050/",
+            ]
+        );
+        assert!(manifest.rpc_url(&manifest.contracts[2], None).is_err());
+
+        let manifest = ContractsManifest::from_file(&json_path).unwrap();
+        assert_eq!(manifest.contracts[0].name, "Router");
+        assert!(manifest.rpc_url(&manifest.contracts[0], None).is_err());
+    }
+
+    #[test]
+    fn test_contracts_manifest_duplicated_names() {
+        let path = write_manifest(
+            "cainome-contracts-manifest-duplicated.json",
+            r#"{ "contracts": [
+                { "name": "Router", "address": "0x1" },
+                { "name": "Router", "address": "0x2" }
+            ] }"#,
+        );
+
+        assert!(ContractsManifest::from_file(&path).is_err());
+    }
+}
@@ -1,32 +1,256 @@
-use cainome_parser::{AbiParser, TokenizedAbi};
+use cainome_parser::{AbiParser, AbiParserLegacy, ParserOptions, TokenizedAbi, TypeNaming};
+use cainome_rs::{ContractConst, ResultEnum, TupleStruct};
 use camino::Utf8PathBuf;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
 use url::Url;
 
+pub mod manifest;
+pub mod scarb;
+use manifest::ContractsManifest;
+use scarb::ScarbWorkspace;
+
+use starknet::core::utils::get_selector_from_name;
 use starknet::{
-    core::types::{BlockId, BlockTag, ContractClass, Felt},
+    core::types::{
+        contract::{
+            legacy::{RawLegacyAbiEntry, RawLegacyEntryPoints},
+            AbiEntry, SierraClass,
+        },
+        BlockId, BlockTag, CompressedLegacyContractClass, ContractClass, Felt,
+        FlattenedSierraClass, LegacyEntryPointsByType,
+    },
     providers::{jsonrpc::HttpTransport, AnyProvider, JsonRpcClient, Provider},
 };
+use starknet_types_core::hash::{Poseidon, StarkHash};
 
 use crate::error::{CainomeCliResult, Error};
 
-#[derive(Debug)]
+/// The maximum number of contracts of a manifest fetched at a time.
+const MAX_CONCURRENT_FETCHES: usize = 8;
+
+#[derive(Debug, Clone)]
 pub enum ContractOrigin {
     /// Contract's ABI was loaded from a local Sierra class file
     /// with the given file name.
     SierraClassFile(String),
     /// Contract's ABI was fetched from the given address.
     FetchedFromChain(Felt),
+    /// Contract's ABI was loaded from a local Cairo 0 file (full artifact or bare ABI)
+    /// with the given file name.
+    LegacyArtifactFile(String),
+    /// Contract's ABI was merged from the given ABI files.
+    MergedAbiFiles(Vec<String>),
+}
+
+/// Metadata of the artifact the contract was parsed from, for the plugins
+/// embedding it in their outputs.
+///
+/// Only the ABI is always known, the other fields are only available
+/// when the artifact is a Sierra class.
+#[derive(Debug, Clone, Default)]
+pub struct ContractArtifact {
+    /// Class hash of the Sierra class.
+    pub class_hash: Option<Felt>,
+    /// Poseidon hash of the Sierra program.
+    pub sierra_program_hash: Option<Felt>,
+    /// Version of the Sierra program (e.g. `1.6.0`).
+    pub sierra_version: Option<String>,
+    /// Version of the compiler which produced the Sierra program (e.g. `2.8.4`).
+    pub compiler_version: Option<String>,
+    /// Version of the contract class (e.g. `0.1.0`).
+    pub contract_class_version: Option<String>,
+    /// The original ABI, as a JSON string.
+    pub abi: String,
+}
+
+impl ContractArtifact {
+    /// Builds the metadata of a local Sierra class.
+    pub fn from_sierra_class(class: &SierraClass) -> Self {
+        Self {
+            // The class hash can't be computed if the ABI contains unknown entries.
+            class_hash: class.class_hash().ok(),
+            abi: serde_json::to_string(&class.abi).unwrap_or_default(),
+            ..Self::from_sierra_program(&class.sierra_program, &class.contract_class_version)
+        }
+    }
+
+    /// Builds the metadata of a Sierra class fetched from the chain.
+    pub fn from_flattened_sierra_class(class: &FlattenedSierraClass) -> Self {
+        Self {
+            class_hash: Some(class.class_hash()),
+            abi: class.abi.clone(),
+            ..Self::from_sierra_program(&class.sierra_program, &class.contract_class_version)
+        }
+    }
+
+    /// Builds the metadata of a bare ABI, which only contains the ABI.
+    pub fn from_abi(entries: &[AbiEntry]) -> Self {
+        Self {
+            abi: serde_json::to_string(entries).unwrap_or_default(),
+            ..Default::default()
+        }
+    }
+
+    /// Builds the metadata of a Cairo 0 ABI, which only contains the ABI.
+    pub fn from_legacy_abi(entries: &[RawLegacyAbiEntry]) -> Self {
+        Self {
+            abi: serde_json::to_string(entries).unwrap_or_default(),
+            ..Default::default()
+        }
+    }
+
+    fn from_sierra_program(program: &[Felt], contract_class_version: &str) -> Self {
+        // The program starts with the Sierra version and the compiler version,
+        // each being encoded as 3 felts (major, minor, patch).
+        let version = |felts: Option<&[Felt]>| {
+            let numbers = felts?
+                .iter()
+                .map(|f| {
+                    let bytes = f.to_bytes_be();
+                    let (high, low) = bytes.split_at(24);
+
+                    high.iter()
+                        .all(|b| *b == 0)
+                        .then(|| u64::from_be_bytes(low.try_into().unwrap()).to_string())
+                })
+                .collect::<Option<Vec<_>>>()?;
+
+            Some(numbers.join("."))
+        };
+
+        Self {
+            class_hash: None,
+            sierra_program_hash: Some(Poseidon::hash_array(program)),
+            sierra_version: version(program.get(0..3)),
+            compiler_version: version(program.get(3..6)),
+            contract_class_version: Some(contract_class_version.to_string()),
+            abi: String::new(),
+        }
+    }
+}
+
+/// A Cairo 0 file, as produced by `starknet-compile` (the ABI next to the `program`)
+/// or a bare ABI.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum LegacyArtifact {
+    /// The program is not deserialized, only the ABI and the entrypoints are used.
+    Full {
+        abi: Vec<RawLegacyAbiEntry>,
+        entry_points_by_type: RawLegacyEntryPoints,
+    },
+    Abi(Vec<RawLegacyAbiEntry>),
+}
+
+impl LegacyArtifact {
+    /// Returns the ABI of the artifact, warning when the entrypoints of a full artifact
+    /// don't match the functions of its ABI.
+    fn abi(&self, file_name: &str) -> &[RawLegacyAbiEntry] {
+        match self {
+            Self::Full {
+                abi,
+                entry_points_by_type,
+            } => {
+                let entry_points = entry_points_by_type.clone().into();
+
+                for mismatch in Self::selector_mismatches(abi, &entry_points) {
+                    tracing::warn!("Cairo 0 file {file_name}: {mismatch}");
+                }
+
+                abi
+            }
+            Self::Abi(abi) => abi,
+        }
+    }
+
+    /// Compares the selectors of the entrypoints with the ones of the functions,
+    /// L1 handlers and constructor of the ABI.
+    ///
+    /// The fallback entrypoints (`__default__` and `__l1_default__`) are not in the ABI.
+    fn selector_mismatches(
+        abi: &[RawLegacyAbiEntry],
+        entry_points: &LegacyEntryPointsByType,
+    ) -> Vec<String> {
+        let mut mismatches = vec![];
+
+        let names = |f: fn(&RawLegacyAbiEntry) -> Option<&str>| -> Vec<&str> {
+            abi.iter().filter_map(f).collect()
+        };
+
+        let kinds = [
+            (
+                "external",
+                names(|e| match e {
+                    RawLegacyAbiEntry::Function(f) => Some(&f.name),
+                    _ => None,
+                }),
+                &entry_points.external,
+                Some("__default__"),
+            ),
+            (
+                "L1 handler",
+                names(|e| match e {
+                    RawLegacyAbiEntry::L1Handler(h) => Some(&h.name),
+                    _ => None,
+                }),
+                &entry_points.l1_handler,
+                Some("__l1_default__"),
+            ),
+            (
+                "constructor",
+                names(|e| match e {
+                    RawLegacyAbiEntry::Constructor(c) => Some(&c.name),
+                    _ => None,
+                }),
+                &entry_points.constructor,
+                None,
+            ),
+        ];
+
+        for (kind, names, entry_points, fallback) in kinds {
+            let selectors: Vec<(Felt, &str)> = names
+                .into_iter()
+                .filter_map(|n| get_selector_from_name(n).ok().map(|s| (s, n)))
+                .collect();
+
+            for (selector, name) in &selectors {
+                if !entry_points.iter().any(|e| e.selector == *selector) {
+                    mismatches.push(format!(
+                        "{kind} `{name}` of the ABI has no entrypoint with the selector {selector:#x}"
+                    ));
+                }
+            }
+
+            let fallback = fallback.and_then(|f| get_selector_from_name(f).ok());
+
+            for e in entry_points {
+                if Some(e.selector) != fallback && !selectors.iter().any(|(s, _)| *s == e.selector)
+                {
+                    mismatches.push(format!(
+                        "{kind} entrypoint with the selector {:#x} is not in the ABI",
+                        e.selector
+                    ));
+                }
+            }
+        }
+
+        mismatches
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ContractData {
     /// Contract's name.
     pub name: String,
     /// Contract's origin.
     pub origin: ContractOrigin,
+    /// Metadata of the artifact the contract was parsed from.
+    pub artifact: ContractArtifact,
     /// Tokens parsed from the ABI.
     pub tokens: TokenizedAbi,
 }
@@ -39,9 +263,45 @@ pub struct ContractParserConfig {
     pub type_aliases: HashMap<String, String>,
     /// The contract aliases to be provided to the Cainome parser.
     pub contract_aliases: HashMap<String, String>,
+    /// Derives to be added only to the generated types matching a pattern (`*` as wildcard).
+    #[serde(default)]
+    pub type_derives: HashMap<String, Vec<String>>,
+    /// ABIs (or Sierra classes) providing the structs and enums referenced
+    /// but not defined in the parsed ABIs.
+    #[serde(default)]
+    pub extra_types: Vec<Utf8PathBuf>,
+    /// Max number of nested structs and enums in the ABIs, the default of the parser if not set.
+    #[serde(default)]
+    pub hydration_max_depth: Option<usize>,
+    /// Naming of the structs and enums which are not aliased, `last_segment` by default.
+    #[serde(default, with = "type_naming")]
+    pub type_naming: TypeNaming,
+    /// Constants of the contracts, by contract name, emitted in the `<contract>_consts` module
+    /// of their bindings and checked against their views.
+    #[serde(default)]
+    pub consts: HashMap<String, Vec<ContractConst>>,
+    /// Fixed tuples generated as named structs in the bindings of all the contracts.
+    #[serde(default)]
+    pub tuple_structs: Vec<TupleStruct>,
+    /// Enums mapped to `Result` in the bindings of all the contracts.
+    #[serde(default)]
+    pub result_enums: Vec<ResultEnum>,
 }
 
 impl ContractParserConfig {
+    /// Returns the options of the Cainome parser.
+    pub fn parser_options(&self) -> ParserOptions {
+        let mut options = ParserOptions::default();
+
+        if let Some(depth) = self.hydration_max_depth {
+            options.hydration_max_depth = depth;
+        }
+
+        options.type_naming = self.type_naming;
+
+        options
+    }
+
     pub fn from_json(path: &Utf8PathBuf) -> CainomeCliResult<Self> {
         Ok(serde_json::from_reader(std::io::BufReader::new(
             std::fs::File::open(path)?,
@@ -55,10 +315,36 @@ impl Default for ContractParserConfig {
             sierra_extension: ".contract_class.json".to_string(),
             type_aliases: HashMap::default(),
             contract_aliases: HashMap::default(),
+            type_derives: HashMap::default(),
+            extra_types: vec![],
+            hydration_max_depth: None,
+            type_naming: TypeNaming::default(),
+            consts: HashMap::new(),
+            tuple_structs: vec![],
+            result_enums: vec![],
         }
     }
 }
 
+/// (De)serializes the [`TypeNaming`] of the parser config as a string.
+mod type_naming {
+    use cainome_parser::TypeNaming;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(naming: &TypeNaming, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(match naming {
+            TypeNaming::LastSegment => "last_segment",
+            TypeNaming::ShortestUnique => "shortest_unique",
+        })
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<TypeNaming, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(D::Error::custom)
+    }
+}
+
 pub struct ContractParser {}
 
 impl ContractParser {
@@ -66,6 +352,7 @@ impl ContractParser {
         path: Utf8PathBuf,
         config: &ContractParserConfig,
     ) -> CainomeCliResult<Vec<ContractData>> {
+        let extra_types = Self::load_extra_types(&config.extra_types)?;
         let mut contracts = vec![];
 
         for entry in fs::read_dir(path)? {
@@ -78,35 +365,22 @@ impl ContractParser {
                         continue;
                     }
 
-                    let file_content = fs::read_to_string(&path)?;
-
-                    match AbiParser::tokens_from_abi_string(&file_content, &config.type_aliases) {
-                        Ok(tokens) => {
-                            let contract_name = {
-                                let n = file_name.trim_end_matches(&config.sierra_extension);
-                                if let Some(alias) = config.contract_aliases.get(n) {
-                                    tracing::trace!(
-                                        "Aliasing {file_name} contract name with {alias}"
-                                    );
-                                    alias
-                                } else {
-                                    n
-                                }
-                            };
-
-                            tracing::trace!(
-                                "Adding {contract_name} ({file_name}) to the list of contracts"
-                            );
-                            contracts.push(ContractData {
-                                name: contract_name.to_string(),
-                                origin: ContractOrigin::SierraClassFile(file_name.to_string()),
-                                tokens,
-                            });
+                    let contract_name = {
+                        let n = file_name.trim_end_matches(&config.sierra_extension);
+                        if let Some(alias) = config.contract_aliases.get(n) {
+                            tracing::trace!("Aliasing {file_name} contract name with {alias}");
+                            alias
+                        } else {
+                            n
                         }
-                        Err(e) => {
-                            tracing::warn!("Sierra file {file_name} could not be parsed {e:?}")
-                        }
-                    }
+                    };
+
+                    contracts.extend(Self::from_artifact_file(
+                        &path,
+                        contract_name,
+                        &extra_types,
+                        config,
+                    )?);
                 }
             }
         }
@@ -114,11 +388,125 @@ impl ContractParser {
         Ok(contracts)
     }
 
+    /// Parses the contracts built by Scarb for the packages of a workspace.
+    ///
+    /// The contracts are named after their Cairo module, the contract aliases of the config
+    /// applying to `package::Contract` or `Contract`.
+    ///
+    /// # Arguments
+    ///
+    /// * `manifest_path` - The path of the root `Scarb.toml` of the workspace.
+    /// * `profile` - The profile the contracts were built with.
+    /// * `config` - The parser config.
+    pub fn from_scarb_manifest(
+        manifest_path: &Utf8PathBuf,
+        profile: &str,
+        config: &ContractParserConfig,
+    ) -> CainomeCliResult<Vec<ContractData>> {
+        let workspace = ScarbWorkspace::from_manifest(manifest_path)?;
+        let extra_types = Self::load_extra_types(&config.extra_types)?;
+        let mut contracts: Vec<ContractData> = vec![];
+
+        for scarb_contract in workspace.contracts(profile)? {
+            let qualified_name = format!("{}::{}", scarb_contract.package, scarb_contract.name);
+
+            let contract_name = config
+                .contract_aliases
+                .get(&qualified_name)
+                .or_else(|| config.contract_aliases.get(&scarb_contract.name))
+                .unwrap_or(&scarb_contract.name);
+
+            if contracts.iter().any(|c| &c.name == contract_name) {
+                return Err(Error::Other(format!(
+                    "Several Scarb contracts are named {contract_name}. Use `contract_aliases` in the parser config to rename {qualified_name}."
+                )));
+            }
+
+            contracts.extend(Self::from_artifact_file(
+                scarb_contract.sierra_path.as_std_path(),
+                contract_name,
+                &extra_types,
+                config,
+            )?);
+        }
+
+        Ok(contracts)
+    }
+
+    /// Parses an artifact file, a Sierra class or a Cairo 0 artifact.
+    ///
+    /// Returns `None` if the artifact is skipped, because it can't be parsed
+    /// or would only produce useless bindings.
+    fn from_artifact_file(
+        path: &std::path::Path,
+        contract_name: &str,
+        extra_types: &[AbiEntry],
+        config: &ContractParserConfig,
+    ) -> CainomeCliResult<Option<ContractData>> {
+        let file_name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or_default();
+
+        let file_content = fs::read_to_string(path)?;
+
+        match AbiParser::parse_abi_string(&file_content) {
+            Ok(entries) => {
+                if let Some(reason) = Self::skip_reason(&entries) {
+                    tracing::warn!("Sierra file {file_name} skipped: {reason}");
+                    return Ok(None);
+                }
+
+                let tokens = Self::tokenize(contract_name, &entries, extra_types, config)?;
+
+                tracing::trace!("Adding {contract_name} ({file_name}) to the list of contracts");
+                let artifact = match serde_json::from_str::<SierraClass>(&file_content) {
+                    Ok(class) => ContractArtifact::from_sierra_class(&class),
+                    Err(_) => ContractArtifact::from_abi(&entries),
+                };
+
+                Ok(Some(ContractData {
+                    name: contract_name.to_string(),
+                    origin: ContractOrigin::SierraClassFile(file_name.to_string()),
+                    artifact,
+                    tokens,
+                }))
+            }
+            Err(e) => match serde_json::from_str::<LegacyArtifact>(&file_content) {
+                Ok(legacy) => {
+                    let entries = legacy.abi(file_name);
+
+                    if entries.is_empty() {
+                        tracing::warn!("Cairo 0 file {file_name} skipped: empty ABI");
+                        return Ok(None);
+                    }
+
+                    let tokens = AbiParserLegacy::collect_tokens(entries, &config.type_aliases)?;
+
+                    tracing::trace!(
+                        "Adding Cairo 0 {contract_name} ({file_name}) to the list of contracts"
+                    );
+
+                    Ok(Some(ContractData {
+                        name: contract_name.to_string(),
+                        origin: ContractOrigin::LegacyArtifactFile(file_name.to_string()),
+                        artifact: ContractArtifact::from_legacy_abi(entries),
+                        tokens,
+                    }))
+                }
+                Err(_) => {
+                    tracing::warn!("Sierra file {file_name} could not be parsed {e:?}");
+                    Ok(None)
+                }
+            },
+        }
+    }
+
     pub async fn from_chain(
         name: &str,
         address: Felt,
         rpc_url: Url,
-        type_aliases: &HashMap<String, String>,
+        config: &ContractParserConfig,
     ) -> CainomeCliResult<ContractData> {
         let provider = AnyProvider::JsonRpcHttp(JsonRpcClient::new(HttpTransport::new(rpc_url)));
 
@@ -128,21 +516,578 @@ impl ContractParser {
 
         match class {
             ContractClass::Sierra(sierra) => {
-                match AbiParser::tokens_from_abi_string(&sierra.abi, type_aliases) {
-                    Ok(tokens) => Ok(ContractData {
-                        name: name.to_string(),
-                        origin: ContractOrigin::FetchedFromChain(address),
-                        tokens,
-                    }),
-                    Err(e) => Err(Error::Other(format!(
+                let entries = AbiParser::parse_abi_string(&sierra.abi).map_err(|e| {
+                    Error::Other(format!(
                         "Error parsing ABI from address {:#x}: {:?}",
                         address, e
-                    ))),
-                }
+                    ))
+                })?;
+
+                let extra_types = Self::load_extra_types(&config.extra_types)?;
+
+                Ok(ContractData {
+                    name: name.to_string(),
+                    origin: ContractOrigin::FetchedFromChain(address),
+                    artifact: ContractArtifact::from_flattened_sierra_class(&sierra),
+                    tokens: Self::tokenize(name, &entries, &extra_types, config)?,
+                })
             }
-            ContractClass::Legacy(_) => Err(Error::Other(
-                "Legacy class is not supported yet".to_string(),
-            )),
+            ContractClass::Legacy(legacy) => Self::from_legacy_class(name, address, legacy, config),
         }
     }
+
+    /// Fetches concurrently the contracts listed in a manifest, returned in the order of the manifest.
+    ///
+    /// At most [`MAX_CONCURRENT_FETCHES`] contracts are fetched at a time, to not be
+    /// rate limited by the public RPC endpoints.
+    ///
+    /// # Arguments
+    ///
+    /// * `manifest_path` - The path of the manifest (JSON or TOML).
+    /// * `default_rpc_url` - The RPC url of the contracts without network.
+    /// * `config` - The parser config.
+    pub async fn from_contracts_manifest(
+        manifest_path: &Utf8PathBuf,
+        default_rpc_url: Option<&Url>,
+        config: &ContractParserConfig,
+    ) -> CainomeCliResult<Vec<ContractData>> {
+        let manifest = ContractsManifest::from_file(manifest_path)?;
+        let config = Arc::new(config.clone());
+        let semaphore = Arc::new(Semaphore::new(MAX_CONCURRENT_FETCHES));
+        let mut tasks: JoinSet<CainomeCliResult<(usize, ContractData)>> = JoinSet::new();
+
+        for (i, contract) in manifest.contracts.iter().enumerate() {
+            let rpc_url = manifest.rpc_url(contract, default_rpc_url)?;
+            let (name, address) = (contract.name.clone(), contract.address);
+            let config = config.clone();
+            let semaphore = semaphore.clone();
+
+            tasks.spawn(async move {
+                let _permit = semaphore
+                    .acquire_owned()
+                    .await
+                    .map_err(|e| Error::Other(format!("Fetching task failed: {e}")))?;
+
+                let data = Self::from_chain(&name, address, rpc_url, &config)
+                    .await
+                    .map_err(|e| Error::Other(format!("Contract {name} ({address:#x}): {e}")))?;
+
+                Ok((i, data))
+            });
+        }
+
+        let mut contracts = vec![];
+
+        while let Some(result) = tasks.join_next().await {
+            let result = result.map_err(|e| Error::Other(format!("Fetching task failed: {e}")))?;
+            contracts.push(result?);
+        }
+
+        contracts.sort_by_key(|(i, _)| *i);
+
+        Ok(contracts.into_iter().map(|(_, c)| c).collect())
+    }
+
+    /// Parses the ABI of a Cairo 0 class fetched from the chain.
+    ///
+    /// Like for the Cairo 0 artifacts, a warning is emitted when the entrypoints
+    /// of the class don't match the functions of its ABI.
+    fn from_legacy_class(
+        name: &str,
+        address: Felt,
+        class: CompressedLegacyContractClass,
+        config: &ContractParserConfig,
+    ) -> CainomeCliResult<ContractData> {
+        let entries: Vec<RawLegacyAbiEntry> = class
+            .abi
+            .unwrap_or_default()
+            .into_iter()
+            .map(RawLegacyAbiEntry::from)
+            .collect();
+
+        if entries.is_empty() {
+            return Err(Error::Other(format!(
+                "Cairo 0 class at address {:#x} has no ABI",
+                address
+            )));
+        }
+
+        for mismatch in LegacyArtifact::selector_mismatches(&entries, &class.entry_points_by_type) {
+            tracing::warn!("Cairo 0 class at address {:#x}: {mismatch}", address);
+        }
+
+        Ok(ContractData {
+            name: name.to_string(),
+            origin: ContractOrigin::FetchedFromChain(address),
+            artifact: ContractArtifact::from_legacy_abi(&entries),
+            tokens: AbiParserLegacy::collect_tokens(&entries, &config.type_aliases)?,
+        })
+    }
+
+    pub fn from_merged_abis(
+        name: &str,
+        paths: &[Utf8PathBuf],
+        config: &ContractParserConfig,
+    ) -> CainomeCliResult<ContractData> {
+        let mut abis = vec![];
+
+        for path in paths {
+            let file_content = fs::read_to_string(path)?;
+            let entries = AbiParser::parse_abi_string(&file_content)
+                .map_err(|e| Error::Other(format!("ABI file {path} could not be parsed: {e:?}")))?;
+
+            abis.push(entries);
+        }
+
+        let merged = AbiParser::merge_abis(&abis);
+
+        for conflict in &merged.conflicts {
+            tracing::warn!(
+                "Conflicting definitions of {} `{}`: keeping {}, ignoring {}",
+                conflict.kind,
+                conflict.name,
+                paths[conflict.kept],
+                paths[conflict.discarded],
+            );
+        }
+
+        let extra_types = Self::load_extra_types(&config.extra_types)?;
+        let tokens = Self::tokenize(name, &merged.entries, &extra_types, config)?;
+
+        tracing::trace!(
+            "Adding {name} to the list of contracts, merged from {} ABI files",
+            paths.len()
+        );
+
+        Ok(ContractData {
+            name: name.to_string(),
+            origin: ContractOrigin::MergedAbiFiles(paths.iter().map(|p| p.to_string()).collect()),
+            artifact: ContractArtifact::from_abi(&merged.entries),
+            tokens,
+        })
+    }
+
+    /// Returns why no bindings are generated for an ABI found in the artifacts, if so.
+    ///
+    /// The classes with an empty ABI, or only declaring interfaces and types without
+    /// exposing any entrypoint nor emitting any event (like the utility classes) would
+    /// only produce useless bindings.
+    fn skip_reason(entries: &[AbiEntry]) -> Option<&'static str> {
+        if entries.is_empty() {
+            return Some("empty ABI");
+        }
+
+        let is_used = entries.iter().any(|e| {
+            matches!(
+                e,
+                AbiEntry::Function(_)
+                    | AbiEntry::Impl(_)
+                    | AbiEntry::Constructor(_)
+                    | AbiEntry::L1Handler(_)
+                    | AbiEntry::Event(_)
+            )
+        });
+
+        if is_used {
+            None
+        } else {
+            Some("no entrypoint nor event in the ABI, only interfaces or types")
+        }
+    }
+
+    /// Loads the structs and enums defined in the given ABIs (or Sierra classes).
+    fn load_extra_types(paths: &[Utf8PathBuf]) -> CainomeCliResult<Vec<AbiEntry>> {
+        let mut abis = vec![];
+
+        for path in paths {
+            let file_content = fs::read_to_string(path)?;
+            let entries = AbiParser::parse_abi_string(&file_content).map_err(|e| {
+                Error::Other(format!(
+                    "Extra types file {path} could not be parsed: {e:?}"
+                ))
+            })?;
+
+            // Only the types are used, to not add the functions of the extra ABIs.
+            let types = entries
+                .into_iter()
+                .filter(|e| matches!(e, AbiEntry::Struct(_) | AbiEntry::Enum(_)))
+                .collect();
+
+            abis.push(types);
+        }
+
+        Ok(AbiParser::merge_abis(&abis).entries)
+    }
+
+    /// Tokenizes the ABI entries of a contract, completed by the extra types
+    /// for the types it doesn't define.
+    ///
+    /// Fails if some types are still unresolved, since the bindings couldn't be expanded.
+    fn tokenize(
+        name: &str,
+        entries: &[AbiEntry],
+        extra_types: &[AbiEntry],
+        config: &ContractParserConfig,
+    ) -> CainomeCliResult<TokenizedAbi> {
+        let type_aliases = &config.type_aliases;
+
+        // The types defined by the contract have precedence, conflicts are expected.
+        let entries = AbiParser::merge_abis(&[entries.to_vec(), extra_types.to_vec()]).entries;
+        let tokens = AbiParser::collect_tokens_with_options(
+            &entries,
+            type_aliases,
+            &config.parser_options(),
+        )?;
+
+        for (type_path, alias) in tokens.prelude_renames() {
+            if !type_aliases.contains_key(&type_path) {
+                tracing::warn!(
+                    "Contract {name}: type {type_path} collides with the Rust prelude and is renamed {alias}"
+                );
+            }
+        }
+
+        let unresolved = tokens.unresolved_types();
+        if !unresolved.is_empty() {
+            return Err(Error::Other(format!(
+                "Contract {name} references types not defined in its ABI: {}. Use `--extra-types` to provide ABIs defining them.",
+                unresolved.join(", ")
+            )));
+        }
+
+        let collisions = tokens.name_collisions();
+        if !collisions.is_empty() {
+            return Err(Error::Other(format!(
+                "Contract {name} has types generated with the same name: {}. Use `type_aliases` in the parser config to rename them, or `\"type_naming\": \"shortest_unique\"`.",
+                collisions
+                    .iter()
+                    .map(|c| c.to_string())
+                    .collect::<Vec<_>>()
+                    .join("; ")
+            )));
+        }
+
+        Ok(tokens)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_artifact_from_sierra_class() {
+        let content = fs::read_to_string("./crates/parser/test_data/cairo_ls_abi.json").unwrap();
+        let class: SierraClass = serde_json::from_str(&content).unwrap();
+
+        let artifact = ContractArtifact::from_sierra_class(&class);
+
+        assert_eq!(artifact.class_hash, class.class_hash().ok());
+        assert!(artifact.class_hash.is_some());
+        assert_eq!(
+            artifact.sierra_program_hash,
+            Some(Poseidon::hash_array(&class.sierra_program))
+        );
+        assert_eq!(artifact.sierra_version.as_deref(), Some("1.6.0"));
+        assert_eq!(artifact.compiler_version.as_deref(), Some("2.8.4"));
+        assert_eq!(artifact.contract_class_version.as_deref(), Some("0.1.0"));
+
+        let abi: Vec<AbiEntry> = serde_json::from_str(&artifact.abi).unwrap();
+        assert_eq!(abi.len(), class.abi.len());
+    }
+
+    #[test]
+    fn test_artifact_from_abi() {
+        let artifact = ContractArtifact::from_abi(&[]);
+
+        assert_eq!(artifact.abi, "[]");
+        assert!(artifact.class_hash.is_none());
+        assert!(artifact.compiler_version.is_none());
+    }
+
+    #[test]
+    fn test_artifacts_path_skips_useless_abis() {
+        let dir = crate::plugins::test_utils::temp_dir("useless-abis");
+
+        let interface = r#"{
+            "type": "interface",
+            "name": "pkg::IUtils",
+            "items": [
+                {
+                    "type": "function",
+                    "name": "get",
+                    "inputs": [],
+                    "outputs": [{ "type": "core::felt252" }],
+                    "state_mutability": "view"
+                }
+            ]
+        }"#;
+        let event = r#"{
+            "type": "event",
+            "name": "pkg::Event",
+            "kind": "enum",
+            "variants": []
+        }"#;
+
+        fs::write(dir.join("empty.contract_class.json"), "[]").unwrap();
+        fs::write(
+            dir.join("interfaces.contract_class.json"),
+            format!("[{interface}]"),
+        )
+        .unwrap();
+        fs::write(
+            dir.join("events.contract_class.json"),
+            format!("[{interface}, {event}]"),
+        )
+        .unwrap();
+        fs::copy(
+            "./crates/parser/test_data/cairo_ls_abi.json",
+            dir.join("ls.contract_class.json"),
+        )
+        .unwrap();
+
+        let mut names: Vec<String> =
+            ContractParser::from_artifacts_path(dir, &ContractParserConfig::default())
+                .unwrap()
+                .into_iter()
+                .map(|c| c.name)
+                .collect();
+        names.sort();
+
+        assert_eq!(names, vec!["events", "ls"]);
+    }
+
+    #[test]
+    fn test_parser_config_type_naming() {
+        let json = r#"{ "sierra_extension": ".json", "type_aliases": {}, "contract_aliases": {} }"#;
+        let config: ContractParserConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.type_naming, TypeNaming::LastSegment);
+
+        let json = r#"{
+            "sierra_extension": ".json",
+            "type_aliases": {},
+            "contract_aliases": {},
+            "type_naming": "shortest_unique"
+        }"#;
+        let config: ContractParserConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            config.parser_options().type_naming,
+            TypeNaming::ShortestUnique
+        );
+        assert!(serde_json::to_string(&config)
+            .unwrap()
+            .contains(r#""type_naming":"shortest_unique""#));
+
+        let json = r#"{
+            "sierra_extension": ".json",
+            "type_aliases": {},
+            "contract_aliases": {},
+            "type_naming": "short"
+        }"#;
+        assert!(serde_json::from_str::<ContractParserConfig>(json).is_err());
+    }
+
+    #[test]
+    fn test_skip_reason() {
+        let abi = |json: &str| AbiParser::parse_abi_string(json).unwrap();
+
+        assert_eq!(ContractParser::skip_reason(&[]), Some("empty ABI"));
+        assert!(ContractParser::skip_reason(&abi(
+            r#"[{ "type": "struct", "name": "pkg::S", "members": [] }]"#
+        ))
+        .is_some());
+        assert!(ContractParser::skip_reason(&abi(
+            r#"[{ "type": "constructor", "name": "constructor", "inputs": [] }]"#
+        ))
+        .is_none());
+    }
+
+    #[test]
+    fn test_artifacts_path_legacy() {
+        let dir = crate::plugins::test_utils::temp_dir("legacy-artifacts");
+
+        fs::copy(
+            "./contracts/cairo0/kkrt_account_cairo0.json",
+            dir.join("account.contract_class.json"),
+        )
+        .unwrap();
+        fs::copy(
+            "./contracts/cairo0/kkrt.abi.json",
+            dir.join("kkrt.contract_class.json"),
+        )
+        .unwrap();
+
+        let mut contracts =
+            ContractParser::from_artifacts_path(dir, &ContractParserConfig::default()).unwrap();
+        contracts.sort_by(|a, b| a.name.cmp(&b.name));
+
+        assert_eq!(contracts.len(), 2);
+        assert_eq!(contracts[0].name, "account");
+        assert!(matches!(
+            &contracts[0].origin,
+            ContractOrigin::LegacyArtifactFile(f) if f == "account.contract_class.json"
+        ));
+        assert!(contracts[0]
+            .tokens
+            .functions
+            .iter()
+            .any(|f| f.to_function().unwrap().name == "get_evm_address"));
+
+        // Only the ABI of the full artifact is kept.
+        let abi: Vec<RawLegacyAbiEntry> = serde_json::from_str(&contracts[0].artifact.abi).unwrap();
+        assert_eq!(abi.len(), 27);
+
+        assert_eq!(contracts[1].name, "kkrt");
+        assert!(!contracts[1].tokens.functions.is_empty());
+    }
+
+    #[test]
+    fn test_scarb_manifest() {
+        let root = crate::plugins::test_utils::temp_dir("scarb-manifest");
+        fs::create_dir_all(root.join("target/release")).unwrap();
+
+        fs::write(root.join("Scarb.toml"), "[package]\nname = \"ls\"\n").unwrap();
+        fs::write(
+            root.join("target/release/ls.starknet_artifacts.json"),
+            r#"{
+                "version": 1,
+                "contracts": [
+                    {
+                        "id": "a1",
+                        "package_name": "ls",
+                        "contract_name": "MyContract",
+                        "module_path": "ls::MyContract",
+                        "artifacts": { "sierra": "ls_MyContract.contract_class.json", "casm": null }
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+        fs::copy(
+            "./crates/parser/test_data/cairo_ls_abi.json",
+            root.join("target/release/ls_MyContract.contract_class.json"),
+        )
+        .unwrap();
+
+        let manifest_path = root.join("Scarb.toml");
+        let mut config = ContractParserConfig::default();
+
+        let contracts =
+            ContractParser::from_scarb_manifest(&manifest_path, "release", &config).unwrap();
+        assert_eq!(contracts.len(), 1);
+        assert_eq!(contracts[0].name, "MyContract");
+        assert!(matches!(
+            &contracts[0].origin,
+            ContractOrigin::SierraClassFile(f) if f == "ls_MyContract.contract_class.json"
+        ));
+
+        config
+            .contract_aliases
+            .insert("ls::MyContract".to_string(), "Ls".to_string());
+
+        let contracts =
+            ContractParser::from_scarb_manifest(&manifest_path, "release", &config).unwrap();
+        assert_eq!(contracts[0].name, "Ls");
+    }
+
+    #[test]
+    fn test_legacy_class_from_chain() {
+        let content = fs::read_to_string("./contracts/cairo0/kkrt_account_cairo0.json").unwrap();
+        let Ok(LegacyArtifact::Full {
+            abi,
+            entry_points_by_type,
+        }) = serde_json::from_str(&content)
+        else {
+            panic!("expected a full Cairo 0 artifact");
+        };
+
+        // The class as returned by the RPC, the program is not used.
+        let class = CompressedLegacyContractClass {
+            program: vec![],
+            entry_points_by_type: entry_points_by_type.into(),
+            abi: Some(abi.into_iter().map(Into::into).collect()),
+        };
+
+        let contract = ContractParser::from_legacy_class(
+            "Account",
+            Felt::ONE,
+            class.clone(),
+            &ContractParserConfig::default(),
+        )
+        .unwrap();
+
+        assert_eq!(contract.name, "Account");
+        assert!(matches!(
+            contract.origin,
+            ContractOrigin::FetchedFromChain(a) if a == Felt::ONE
+        ));
+        assert!(contract
+            .tokens
+            .functions
+            .iter()
+            .any(|f| f.to_function().unwrap().name == "get_evm_address"));
+
+        let no_abi = CompressedLegacyContractClass { abi: None, ..class };
+        assert!(ContractParser::from_legacy_class(
+            "Account",
+            Felt::ONE,
+            no_abi,
+            &ContractParserConfig::default()
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_legacy_selector_mismatches() {
+        let content = fs::read_to_string("./contracts/cairo0/kkrt_account_cairo0.json").unwrap();
+        let Ok(LegacyArtifact::Full {
+            abi,
+            entry_points_by_type,
+        }) = serde_json::from_str(&content)
+        else {
+            panic!("expected a full Cairo 0 artifact");
+        };
+
+        assert!(LegacyArtifact::selector_mismatches(&abi, &entry_points_by_type.into()).is_empty());
+
+        let selector = |name| format!("{:#x}", get_selector_from_name(name).unwrap());
+        let artifact = format!(
+            r#"{{
+                "abi": [{{ "type": "function", "name": "foo", "inputs": [], "outputs": [] }}],
+                "entry_points_by_type": {{
+                    "CONSTRUCTOR": [],
+                    "EXTERNAL": [
+                        {{ "offset": "0x0", "selector": "{}" }},
+                        {{ "offset": "0x1", "selector": "{}" }}
+                    ],
+                    "L1_HANDLER": []
+                }},
+                "program": {{}}
+            }}"#,
+            selector("bar"),
+            selector("__default__"),
+        );
+
+        let Ok(LegacyArtifact::Full {
+            abi,
+            entry_points_by_type,
+        }) = serde_json::from_str(&artifact)
+        else {
+            panic!("expected a full Cairo 0 artifact");
+        };
+
+        assert_eq!(
+            LegacyArtifact::selector_mismatches(&abi, &entry_points_by_type.into()),
+            vec![
+                format!(
+                    "external `foo` of the ABI has no entrypoint with the selector {}",
+                    selector("foo")
+                ),
+                format!(
+                    "external entrypoint with the selector {} is not in the ABI",
+                    selector("bar")
+                ),
+            ]
+        );
+    }
 }
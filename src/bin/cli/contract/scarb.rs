@@ -0,0 +1,269 @@
+//! Discovery of the contracts built by Scarb in a workspace.
+//!
+//! For each package with a `starknet-contract` target, Scarb writes into `target/<profile>`
+//! the Sierra class of each contract (`<package>_<Contract>.contract_class.json`), and a
+//! `<package>.starknet_artifacts.json` file listing them with the name of their Cairo module.
+use camino::{Utf8Path, Utf8PathBuf};
+use serde::Deserialize;
+use std::fs;
+
+use crate::error::{CainomeCliResult, Error};
+
+/// The profile of `scarb build`, when none is given.
+pub const DEFAULT_PROFILE: &str = "dev";
+
+/// The subset of a `Scarb.toml` manifest needed to find the packages.
+#[derive(Debug, Deserialize)]
+struct Manifest {
+    package: Option<ManifestPackage>,
+    workspace: Option<ManifestWorkspace>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ManifestPackage {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ManifestWorkspace {
+    #[serde(default)]
+    members: Vec<String>,
+}
+
+/// The `<package>.starknet_artifacts.json` file written by Scarb.
+#[derive(Debug, Deserialize)]
+struct StarknetArtifacts {
+    contracts: Vec<StarknetContract>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StarknetContract {
+    package_name: String,
+    contract_name: String,
+    artifacts: StarknetContractArtifacts,
+}
+
+#[derive(Debug, Deserialize)]
+struct StarknetContractArtifacts {
+    sierra: Option<String>,
+}
+
+/// A contract built by Scarb.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScarbContract {
+    /// The package defining the contract.
+    pub package: String,
+    /// The name of the Cairo module of the contract.
+    pub name: String,
+    /// The path of the Sierra class of the contract.
+    pub sierra_path: Utf8PathBuf,
+}
+
+/// A Scarb workspace, or a single package.
+#[derive(Debug, Clone)]
+pub struct ScarbWorkspace {
+    /// The directory of the root manifest.
+    pub root: Utf8PathBuf,
+    /// The names of the packages of the workspace.
+    pub packages: Vec<String>,
+}
+
+impl ScarbWorkspace {
+    /// Reads the root manifest of a workspace, and the manifests of its members.
+    ///
+    /// The members are paths relative to the root, or directories whose subdirectories
+    /// are all members (`crates/*`).
+    ///
+    /// # Arguments
+    ///
+    /// * `manifest_path` - The path of the root `Scarb.toml`.
+    pub fn from_manifest(manifest_path: &Utf8Path) -> CainomeCliResult<Self> {
+        let root = manifest_path
+            .parent()
+            .map(Utf8Path::to_path_buf)
+            .unwrap_or_default();
+
+        let manifest = read_manifest(manifest_path)?;
+        let mut packages = vec![];
+
+        if let Some(package) = &manifest.package {
+            packages.push(package.name.clone());
+        }
+
+        for pattern in manifest.workspace.iter().flat_map(|w| &w.members) {
+            for member in expand_member(&root, pattern)? {
+                let member_manifest = read_manifest(&member.join("Scarb.toml"))?;
+
+                match member_manifest.package {
+                    Some(package) if !packages.contains(&package.name) => {
+                        packages.push(package.name)
+                    }
+                    Some(_) => (),
+                    None => {
+                        return Err(Error::Other(format!(
+                            "Scarb workspace member {member} has no `[package]`"
+                        )))
+                    }
+                }
+            }
+        }
+
+        if packages.is_empty() {
+            return Err(Error::Other(format!(
+                "Scarb manifest {manifest_path} defines no package"
+            )));
+        }
+
+        Ok(Self { root, packages })
+    }
+
+    /// Returns the directory of the artifacts built with the profile,
+    /// `SCARB_TARGET_DIR` overriding the `target` directory of the workspace like for Scarb.
+    ///
+    /// # Arguments
+    ///
+    /// * `profile` - The profile of the build.
+    pub fn target_dir(&self, profile: &str) -> Utf8PathBuf {
+        let target = std::env::var("SCARB_TARGET_DIR")
+            .map(Utf8PathBuf::from)
+            .unwrap_or_else(|_| self.root.join("target"));
+
+        target.join(profile)
+    }
+
+    /// Returns the contracts built for the packages of the workspace.
+    ///
+    /// The packages without `starknet-contract` target have no artifacts, and are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `profile` - The profile of the build.
+    pub fn contracts(&self, profile: &str) -> CainomeCliResult<Vec<ScarbContract>> {
+        let target_dir = self.target_dir(profile);
+
+        if !target_dir.is_dir() {
+            return Err(Error::Other(format!(
+                "Scarb target directory {target_dir} not found, run `scarb build` first"
+            )));
+        }
+
+        let mut contracts = vec![];
+
+        for package in &self.packages {
+            let path = target_dir.join(format!("{package}.starknet_artifacts.json"));
+
+            if !path.is_file() {
+                tracing::debug!("Scarb package {package} skipped: no {path}");
+                continue;
+            }
+
+            let artifacts: StarknetArtifacts = serde_json::from_str(&fs::read_to_string(&path)?)?;
+
+            for contract in artifacts.contracts {
+                let Some(sierra) = contract.artifacts.sierra else {
+                    tracing::warn!(
+                        "Scarb contract {}::{} skipped: no Sierra class built",
+                        contract.package_name,
+                        contract.contract_name
+                    );
+                    continue;
+                };
+
+                contracts.push(ScarbContract {
+                    package: contract.package_name,
+                    name: contract.contract_name,
+                    sierra_path: target_dir.join(sierra),
+                });
+            }
+        }
+
+        Ok(contracts)
+    }
+}
+
+fn read_manifest(path: &Utf8Path) -> CainomeCliResult<Manifest> {
+    toml::from_str(&fs::read_to_string(path)?)
+        .map_err(|e| Error::Other(format!("Scarb manifest {path} could not be parsed: {e}")))
+}
+
+/// Returns the directories of a workspace member, sorted.
+fn expand_member(root: &Utf8Path, pattern: &str) -> CainomeCliResult<Vec<Utf8PathBuf>> {
+    let Some(parent) = pattern.strip_suffix("/*") else {
+        return Ok(vec![root.join(pattern)]);
+    };
+
+    let mut members = vec![];
+
+    for entry in fs::read_dir(root.join(parent))? {
+        let path = Utf8PathBuf::from_path_buf(entry?.path())
+            .map_err(|p| Error::Other(format!("Non UTF-8 path {}", p.display())))?;
+
+        if path.join("Scarb.toml").is_file() {
+            members.push(path);
+        }
+    }
+
+    members.sort();
+    Ok(members)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_workspace_contracts() {
+        let root = crate::plugins::test_utils::temp_dir("scarb-workspace");
+
+        let target = root.join("target/dev");
+        fs::create_dir_all(&target).unwrap();
+        fs::create_dir_all(root.join("crates/token")).unwrap();
+        fs::create_dir_all(root.join("crates/utils")).unwrap();
+
+        fs::write(
+            root.join("Scarb.toml"),
+            "[workspace]\nmembers = [\"crates/*\"]\n\n[package]\nname = \"game\"\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("crates/token/Scarb.toml"),
+            "[package]\nname = \"token\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("crates/utils/Scarb.toml"),
+            "[package]\nname = \"utils\"\n",
+        )
+        .unwrap();
+        fs::write(
+            target.join("token.starknet_artifacts.json"),
+            r#"{
+                "version": 1,
+                "contracts": [
+                    {
+                        "id": "a1",
+                        "package_name": "token",
+                        "contract_name": "Erc20",
+                        "module_path": "token::erc20::Erc20",
+                        "artifacts": { "sierra": "token_Erc20.contract_class.json", "casm": null }
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let workspace = ScarbWorkspace::from_manifest(&root.join("Scarb.toml")).unwrap();
+        assert_eq!(workspace.packages, vec!["game", "token", "utils"]);
+
+        assert_eq!(
+            workspace.contracts(DEFAULT_PROFILE).unwrap(),
+            vec![ScarbContract {
+                package: "token".to_string(),
+                name: "Erc20".to_string(),
+                sierra_path: target.join("token_Erc20.contract_class.json"),
+            }]
+        );
+
+        assert!(workspace.contracts("release").is_err());
+    }
+}
@@ -12,6 +12,8 @@ pub enum Error {
     Cainome(#[from] CainomeError),
     #[error(transparent)]
     Provider(#[from] ProviderError),
+    #[error("Plugin `{plugin}` panicked: {message}")]
+    PluginPanicked { plugin: String, message: String },
     #[error("An error occurred: {0}")]
     Other(String),
 }
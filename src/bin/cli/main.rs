@@ -2,9 +2,12 @@ use clap::Parser;
 use tracing_subscriber::{fmt, EnvFilter};
 
 mod args;
+mod codec;
+mod commands;
 mod contract;
 mod error;
 mod plugins;
+mod wallet;
 
 use args::CainomeArgs;
 use contract::{ContractParser, ContractParserConfig};
@@ -18,12 +21,24 @@ async fn main() -> CainomeCliResult<()> {
     let args = CainomeArgs::parse();
     tracing::trace!("args: {:?}", args);
 
-    let parser_config = if let Some(path) = args.parser_config {
+    if let Some(command) = args.command {
+        return command.run().await;
+    }
+
+    // Both are required by clap when no subcommand is given.
+    let (Some(output_dir), Some(execution_version)) = (args.output_dir, args.execution_version)
+    else {
+        return Err(Error::Other("Invalid arguments".to_string()));
+    };
+
+    let mut parser_config = if let Some(path) = args.parser_config {
         ContractParserConfig::from_json(&path)?
     } else {
         ContractParserConfig::default()
     };
 
+    parser_config.extra_types.extend(args.extra_types);
+
     let contracts = if let Some(path) = args.artifacts_path {
         let ret = ContractParser::from_artifacts_path(path.clone(), &parser_config)?;
 
@@ -37,23 +52,78 @@ async fn main() -> CainomeCliResult<()> {
             return Err(Error::Other("Invalid arguments".to_string()));
         }
 
+        ret
+    } else if let Some(manifest_path) = &args.scarb_manifest {
+        let profile = args
+            .scarb_profile
+            .as_deref()
+            .unwrap_or(contract::scarb::DEFAULT_PROFILE);
+        let ret = ContractParser::from_scarb_manifest(manifest_path, profile, &parser_config)?;
+
+        if ret.is_empty() {
+            tracing::error!("No contract built by Scarb found for '{}'", manifest_path);
+
+            return Err(Error::Other("Invalid arguments".to_string()));
+        }
+
+        ret
+    } else if let Some(manifest_path) = &args.contracts_manifest {
+        let ret = ContractParser::from_contracts_manifest(
+            manifest_path,
+            args.rpc_url.as_ref(),
+            &parser_config,
+        )
+        .await?;
+
+        if ret.is_empty() {
+            tracing::error!("No contract listed in '{}'", manifest_path);
+
+            return Err(Error::Other("Invalid arguments".to_string()));
+        }
+
         ret
     } else if let (Some(name), Some(address), Some(url)) =
-        (args.contract_name, args.contract_address, args.rpc_url)
+        (&args.contract_name, args.contract_address, args.rpc_url)
     {
-        vec![ContractParser::from_chain(&name, address, url, &parser_config.type_aliases).await?]
+        vec![ContractParser::from_chain(name, address, url, &parser_config).await?]
+    } else if let (Some(name), false) = (&args.contract_name, args.merge_abi.is_empty()) {
+        vec![ContractParser::from_merged_abis(
+            name,
+            &args.merge_abi,
+            &parser_config,
+        )?]
     } else {
-        panic!("Invalid arguments: no contracts to be parsed");
+        return Err(Error::Other(
+            "No contracts to be parsed: expected `--artifacts-path`, `--scarb-manifest`, `--contract-address`, `--contracts-manifest` or `--merge-abi`"
+                .to_string(),
+        ));
     };
 
     let pm = PluginManager::from(args.plugins);
 
     pm.generate(PluginInput {
-        output_dir: args.output_dir,
+        output_dir,
         contracts,
-        execution_version: args.execution_version,
+        execution_version,
         derives: args.derives.unwrap_or_default(),
         contract_derives: args.contract_derives.unwrap_or_default(),
+        type_derives: parser_config.type_derives,
+        blocking: args.blocking,
+        arbitrary: args.arbitrary,
+        params_struct_threshold: args.params_struct_threshold,
+        prelude: args.prelude,
+        roundtrip_tests: args.roundtrip_tests,
+        serde_radix: args.serde_radix,
+        serde_bytes: args.serde_bytes,
+        src5: args.src5,
+        paymaster: args.paymaster,
+        generation_mode: args.generation_mode,
+        consts: parser_config.consts,
+        tuple_structs: parser_config.tuple_structs,
+        result_enums: parser_config.result_enums,
+        emit_examples: args.emit_examples,
+        identical_types_conversions: args.identical_types_conversions,
+        shared_types: args.shared_types,
     })
     .await?;
 
@@ -63,11 +133,14 @@ async fn main() -> CainomeCliResult<()> {
 pub fn init_logging() -> CainomeCliResult<()> {
     const DEFAULT_LOG_FILTER: &str = "info,cainome=trace";
 
-    let builder = fmt::Subscriber::builder().with_env_filter(
-        EnvFilter::try_from_default_env()
-            .or(EnvFilter::try_new(DEFAULT_LOG_FILTER))
-            .map_err(|e| Error::Other(format!("Tracing error: {:?}", e)))?,
-    );
+    // Logs are written to stderr, to keep stdout for the commands output.
+    let builder = fmt::Subscriber::builder()
+        .with_writer(std::io::stderr)
+        .with_env_filter(
+            EnvFilter::try_from_default_env()
+                .or(EnvFilter::try_new(DEFAULT_LOG_FILTER))
+                .map_err(|e| Error::Other(format!("Tracing error: {:?}", e)))?,
+        );
 
     tracing::subscriber::set_global_default(builder.finish())
         .map_err(|e| Error::Other(format!("Tracing error: {:?}", e)))
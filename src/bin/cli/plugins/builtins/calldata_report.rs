@@ -0,0 +1,395 @@
+//! Report of the calldata sizes of the entrypoints, generated instead of bindings.
+//!
+//! For each entrypoint, the size (in felts) of its calldata is given as a formula:
+//! the static sizes of the inputs are summed, and the inputs whose size depends on
+//! their value (like the arrays) are dynamic terms. The report is written in markdown
+//! for the humans, and in JSON for the tools.
+use async_trait::async_trait;
+use cainome_parser::size::static_size;
+use cainome_parser::tokens::{Function, StateMutability, Token};
+use cainome_parser::TokenizedAbi;
+use camino::Utf8PathBuf;
+use convert_case::Case;
+use serde::Serialize;
+use starknet::core::utils::get_selector_from_name;
+
+use crate::error::{CainomeCliResult, Error};
+use crate::plugins::builtins::BuiltinPlugin;
+use crate::plugins::utils::contract_identifier;
+use crate::plugins::PluginInput;
+
+/// Definitions of the terms used in the formulas.
+const LEGEND: &str = "Sizes in felts. `len(x)` is the number of elements of the array `x`, \
+`bytes(x) / 31` the number of full 31 bytes words of the byte array `x`, and `size(x)` the size \
+of a value whose serialization depends on it (like an enum with variants of different sizes). \
+The payload of the L1 handlers doesn't contain their first input, the L1 sender.";
+
+pub struct CalldataReportPlugin;
+
+impl CalldataReportPlugin {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl BuiltinPlugin for CalldataReportPlugin {
+    fn name(&self) -> &'static str {
+        "calldata_report"
+    }
+
+    async fn generate_code(&self, input: &PluginInput) -> CainomeCliResult<Vec<Utf8PathBuf>> {
+        tracing::trace!("CalldataReport plugin requested");
+
+        let mut files = vec![];
+
+        for contract in &input.contracts {
+            let contract_name = contract_identifier(contract, Case::Snake);
+            let report = ContractReport::new(&contract.name, &contract.tokens)?;
+
+            let md_path = input
+                .output_dir
+                .join(format!("{}.calldata.md", contract_name));
+            let json_path = input
+                .output_dir
+                .join(format!("{}.calldata.json", contract_name));
+
+            tracing::trace!("CalldataReport writing files {} and {}", md_path, json_path);
+            std::fs::write(&md_path, report.to_markdown())?;
+            std::fs::write(&json_path, serde_json::to_string_pretty(&report)?)?;
+
+            files.push(md_path);
+            files.push(json_path);
+        }
+
+        Ok(files)
+    }
+}
+
+/// The calldata sizes of the entrypoints of a contract.
+#[derive(Debug, Serialize)]
+struct ContractReport {
+    contract: String,
+    legend: &'static str,
+    entrypoints: Vec<EntrypointReport>,
+}
+
+#[derive(Debug, Serialize)]
+struct EntrypointReport {
+    name: String,
+    /// `view`, `external` or `l1_handler`.
+    kind: &'static str,
+    /// The interface declaring the entrypoint, if any.
+    interface: Option<String>,
+    selector: String,
+    /// Sum of the static sizes, including the constant part of the dynamic terms.
+    static_size: usize,
+    /// The terms depending on the values of the inputs.
+    dynamic_terms: Vec<String>,
+    /// The whole formula of the calldata size.
+    calldata_size: String,
+    inputs: Vec<InputReport>,
+}
+
+#[derive(Debug, Serialize)]
+struct InputReport {
+    name: String,
+    #[serde(rename = "type")]
+    type_path: String,
+    size: String,
+}
+
+/// The size of a serialized input: a constant part, and a term depending on its value.
+#[derive(Debug, PartialEq)]
+struct SizeTerm {
+    constant: usize,
+    dynamic: Option<String>,
+}
+
+impl SizeTerm {
+    /// Returns the size of the serialized input `name`.
+    fn new(name: &str, token: &Token) -> Self {
+        if let Some(constant) = static_size(token) {
+            return Self {
+                constant,
+                dynamic: None,
+            };
+        }
+
+        let (constant, dynamic) = match token {
+            // The legacy arrays (Cairo 0) have their length given as a separate input.
+            Token::Array(array) => {
+                let prefix = if array.is_legacy { 0 } else { 1 };
+
+                match static_size(&array.inner) {
+                    Some(1) => (prefix, format!("len({name})")),
+                    Some(size) => (prefix, format!("{size} * len({name})")),
+                    None => (prefix, format!("sum(size({name}[i]))")),
+                }
+            }
+            // The full words, followed by the pending word and its length.
+            Token::Composite(c) if c.type_path_no_generic() == "core::byte_array::ByteArray" => {
+                (3, format!("bytes({name}) / 31"))
+            }
+            _ => (0, format!("size({name})")),
+        };
+
+        Self {
+            constant,
+            dynamic: Some(dynamic),
+        }
+    }
+}
+
+impl std::fmt::Display for SizeTerm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (&self.dynamic, self.constant) {
+            (None, constant) => write!(f, "{constant}"),
+            (Some(dynamic), 0) => write!(f, "{dynamic}"),
+            (Some(dynamic), constant) => write!(f, "{constant} + {dynamic}"),
+        }
+    }
+}
+
+impl ContractReport {
+    fn new(contract: &str, tokens: &TokenizedAbi) -> CainomeCliResult<Self> {
+        let mut entrypoints = vec![];
+
+        for f in &tokens.functions {
+            entrypoints.push(EntrypointReport::new(f.to_function()?, None, false)?);
+        }
+
+        let mut interfaces: Vec<_> = tokens
+            .interfaces
+            .iter()
+            .map(|(key, functions)| (tokens.interface_path(key), functions))
+            .collect();
+        interfaces.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        for (interface, functions) in interfaces {
+            for f in functions {
+                entrypoints.push(EntrypointReport::new(
+                    f.to_function()?,
+                    Some(interface),
+                    false,
+                )?);
+            }
+        }
+
+        for f in &tokens.l1_handlers {
+            entrypoints.push(EntrypointReport::new(f.to_function()?, None, true)?);
+        }
+
+        Ok(Self {
+            contract: contract.to_string(),
+            legend: LEGEND,
+            entrypoints,
+        })
+    }
+
+    fn to_markdown(&self) -> String {
+        let mut md = format!("# Calldata of the `{}` contract\n\n", self.contract);
+        md.push_str(self.legend);
+        md.push_str("\n\n");
+
+        if self.entrypoints.is_empty() {
+            md.push_str("The contract has no entrypoint.\n");
+            return md;
+        }
+
+        md.push_str("| Entrypoint | Interface | Kind | Selector | Calldata size | Inputs |\n");
+        md.push_str("| --- | --- | --- | --- | --- | --- |\n");
+
+        for e in &self.entrypoints {
+            let inputs: Vec<String> = e
+                .inputs
+                .iter()
+                .map(|i| format!("`{}`: `{}`", i.name, i.size))
+                .collect();
+
+            md.push_str(&format!(
+                "| `{}` | {} | {} | `{}` | `{}` | {} |\n",
+                e.name,
+                e.interface
+                    .as_ref()
+                    .map(|i| format!("`{i}`"))
+                    .unwrap_or_default(),
+                e.kind,
+                e.selector,
+                e.calldata_size,
+                inputs.join(", ")
+            ));
+        }
+
+        md
+    }
+}
+
+impl EntrypointReport {
+    fn new(
+        function: &Function,
+        interface: Option<&str>,
+        l1_handler: bool,
+    ) -> CainomeCliResult<Self> {
+        let kind = match (l1_handler, &function.state_mutability) {
+            (true, _) => "l1_handler",
+            (false, StateMutability::View) => "view",
+            (false, StateMutability::External) => "external",
+        };
+
+        let selector = get_selector_from_name(&function.name).map_err(|e| {
+            Error::Other(format!("Invalid entrypoint name `{}`: {e}", function.name))
+        })?;
+
+        // The first input of a L1 handler is the L1 sender, which is not in the payload.
+        let skipped = if l1_handler { 1 } else { 0 };
+
+        let mut static_size = 0;
+        let mut dynamic_terms = vec![];
+        let mut inputs = vec![];
+
+        for (name, token) in function.inputs.iter().skip(skipped) {
+            let term = SizeTerm::new(name, token);
+
+            static_size += term.constant;
+            dynamic_terms.extend(term.dynamic.clone());
+
+            inputs.push(InputReport {
+                name: name.clone(),
+                type_path: token.type_path(),
+                size: term.to_string(),
+            });
+        }
+
+        let calldata_size = if dynamic_terms.is_empty() {
+            static_size.to_string()
+        } else if static_size == 0 {
+            dynamic_terms.join(" + ")
+        } else {
+            format!("{} + {}", static_size, dynamic_terms.join(" + "))
+        };
+
+        Ok(Self {
+            name: function.name.clone(),
+            kind,
+            interface: interface.map(String::from),
+            selector: format!("{:#x}", selector),
+            static_size,
+            dynamic_terms,
+            calldata_size,
+            inputs,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugins::test_utils;
+    use cainome_parser::AbiParser;
+    use std::collections::HashMap;
+
+    const ABI: &str = r#"[
+        {
+            "type": "interface",
+            "name": "pkg::IVault",
+            "items": [
+                {
+                    "type": "function",
+                    "name": "deposit",
+                    "inputs": [
+                        { "name": "to", "type": "core::starknet::contract_address::ContractAddress" },
+                        { "name": "amount", "type": "core::integer::u256" },
+                        { "name": "memo", "type": "core::byte_array::ByteArray" },
+                        { "name": "proof", "type": "core::array::Span::<core::integer::u256>" }
+                    ],
+                    "outputs": [],
+                    "state_mutability": "external"
+                },
+                {
+                    "type": "function",
+                    "name": "total",
+                    "inputs": [],
+                    "outputs": [{ "type": "core::integer::u256" }],
+                    "state_mutability": "view"
+                }
+            ]
+        },
+        {
+            "type": "l1_handler",
+            "name": "on_deposit",
+            "inputs": [
+                { "name": "from_address", "type": "core::felt252" },
+                { "name": "data", "type": "core::array::Array::<core::felt252>" }
+            ],
+            "outputs": [],
+            "state_mutability": "external"
+        }
+    ]"#;
+
+    fn report() -> ContractReport {
+        let tokens = AbiParser::tokens_from_abi_string(ABI, &HashMap::new()).unwrap();
+        ContractReport::new("Vault", &tokens).unwrap()
+    }
+
+    #[test]
+    fn test_calldata_sizes() {
+        let report = report();
+        let sizes: Vec<(&str, &str, &str)> = report
+            .entrypoints
+            .iter()
+            .map(|e| (e.name.as_str(), e.kind, e.calldata_size.as_str()))
+            .collect();
+
+        assert_eq!(
+            sizes,
+            vec![
+                (
+                    "deposit",
+                    "external",
+                    "7 + bytes(memo) / 31 + 2 * len(proof)"
+                ),
+                ("total", "view", "0"),
+                ("on_deposit", "l1_handler", "1 + len(data)"),
+            ]
+        );
+
+        let deposit = &report.entrypoints[0];
+        assert_eq!(deposit.interface.as_deref(), Some("pkg::IVault"));
+        assert_eq!(deposit.static_size, 7);
+        assert_eq!(deposit.inputs[1].size, "2");
+        assert_eq!(deposit.inputs[3].size, "1 + 2 * len(proof)");
+        assert_eq!(
+            deposit.selector,
+            format!("{:#x}", get_selector_from_name("deposit").unwrap())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_generate_code() {
+        let mut input = test_utils::plugin_input("calldata_report", &["vault"]);
+        input.contracts[0].tokens =
+            AbiParser::tokens_from_abi_string(ABI, &HashMap::new()).unwrap();
+
+        let files = CalldataReportPlugin::new()
+            .generate_code(&input)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            files,
+            vec![
+                input.output_dir.join("vault.calldata.md"),
+                input.output_dir.join("vault.calldata.json"),
+            ]
+        );
+
+        let md = std::fs::read_to_string(&files[0]).unwrap();
+        assert!(md.contains("| `total` | `pkg::IVault` | view |"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&files[1]).unwrap()).unwrap();
+        assert_eq!(json["contract"], "vault");
+        assert_eq!(json["entrypoints"][2]["calldata_size"], "1 + len(data)");
+    }
+}
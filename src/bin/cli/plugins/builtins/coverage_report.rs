@@ -0,0 +1,117 @@
+//! Report of the coverage of the ABI entries by the rust bindings, generated instead of bindings.
+//!
+//! Each struct, enum, event, entrypoint and constructor of the ABI is listed with the name
+//! it is generated with, or the reason it is skipped with the options of the rust plugin
+//! (like the generation mode). The report is written in JSON for the tools auditing
+//! that the bindings are complete.
+use async_trait::async_trait;
+use cainome_rs::{CoverageReport, ExpandOptions};
+use camino::Utf8PathBuf;
+use convert_case::Case;
+
+use crate::error::CainomeCliResult;
+use crate::plugins::builtins::BuiltinPlugin;
+use crate::plugins::utils::contract_identifier;
+use crate::plugins::PluginInput;
+
+pub struct CoverageReportPlugin;
+
+impl CoverageReportPlugin {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl BuiltinPlugin for CoverageReportPlugin {
+    fn name(&self) -> &'static str {
+        "coverage_report"
+    }
+
+    async fn generate_code(&self, input: &PluginInput) -> CainomeCliResult<Vec<Utf8PathBuf>> {
+        tracing::trace!("CoverageReport plugin requested");
+
+        // Only the generation mode changes the entries generated by the rust plugin.
+        let options = ExpandOptions {
+            generation_mode: input.generation_mode,
+            ..Default::default()
+        };
+
+        let mut files = vec![];
+
+        for contract in &input.contracts {
+            let contract_name = contract_identifier(contract, Case::Snake);
+            let report = CoverageReport::new(&contract.name, &contract.tokens, &options);
+
+            for entry in report.skipped_entries() {
+                tracing::debug!(
+                    "Contract {}: {} is not generated ({:?})",
+                    contract.name,
+                    entry.name,
+                    entry.status
+                );
+            }
+
+            let out_path = input
+                .output_dir
+                .join(format!("{}.coverage.json", contract_name));
+
+            tracing::trace!("CoverageReport writing file {}", out_path);
+            std::fs::write(&out_path, serde_json::to_string_pretty(&report)?)?;
+
+            files.push(out_path);
+        }
+
+        Ok(files)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugins::test_utils;
+    use cainome_parser::AbiParser;
+    use cainome_rs::GenerationMode;
+    use std::collections::HashMap;
+
+    const ABI: &str = r#"[
+        {
+            "type": "function",
+            "name": "cancel",
+            "inputs": [{ "name": "id", "type": "core::felt252" }],
+            "outputs": [],
+            "state_mutability": "external"
+        },
+        {
+            "type": "function",
+            "name": "price",
+            "inputs": [],
+            "outputs": [{ "type": "core::integer::u256" }],
+            "state_mutability": "view"
+        }
+    ]"#;
+
+    #[tokio::test]
+    async fn test_generate_code() {
+        let mut input = test_utils::plugin_input("coverage_report", &["book"]);
+        input.generation_mode = GenerationMode::Encode;
+        input.contracts[0].tokens =
+            AbiParser::tokens_from_abi_string(ABI, &HashMap::new()).unwrap();
+
+        let files = CoverageReportPlugin::new()
+            .generate_code(&input)
+            .await
+            .unwrap();
+
+        assert_eq!(files, vec![input.output_dir.join("book.coverage.json")]);
+
+        let json: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&files[0]).unwrap()).unwrap();
+        assert_eq!(json["contract"], "book");
+        assert_eq!(json["generation_mode"], "encode");
+        assert_eq!(json["entries"][0]["name"], "cancel");
+        assert_eq!(json["entries"][0]["rust_name"], "cancel_getcall");
+        assert_eq!(json["entries"][1]["name"], "price");
+        assert_eq!(json["entries"][1]["reason"], "generation_mode");
+    }
+}
@@ -1,22 +1,43 @@
 use async_trait::async_trait;
+use camino::Utf8PathBuf;
 
 use crate::error::CainomeCliResult;
 use crate::plugins::PluginInput;
 
 mod rust;
 pub use rust::RustPlugin;
+mod calldata_report;
+pub use calldata_report::CalldataReportPlugin;
+mod session_policies;
+pub use session_policies::SessionPoliciesPlugin;
+mod coverage_report;
+pub use coverage_report::CoverageReportPlugin;
+// cainome:new-plugin:modules
 
 #[derive(Debug)]
 pub enum BuiltinPlugins {
     Rust,
+    CalldataReport,
+    SessionPolicies,
+    CoverageReport,
+    // cainome:new-plugin:variants
 }
 
 #[async_trait]
-pub trait BuiltinPlugin {
-    /// Generates code by executing the plugin.
+pub trait BuiltinPlugin: Send + Sync {
+    /// Name of the plugin, as used in the CLI options (e.g. `rust`).
+    fn name(&self) -> &'static str;
+
+    /// Command formatting the generated files, which are appended as arguments.
+    /// `None` if the plugin has no formatter by default.
+    fn formatter(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// Generates code by executing the plugin, returning the paths of the generated files.
     ///
     /// # Arguments
     ///
     /// * `data` - Contract data.
-    async fn generate_code(&self, input: &PluginInput) -> CainomeCliResult<()>;
+    async fn generate_code(&self, input: &PluginInput) -> CainomeCliResult<Vec<Utf8PathBuf>>;
 }
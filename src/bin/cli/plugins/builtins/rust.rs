@@ -1,11 +1,17 @@
 use async_trait::async_trait;
-use cainome_rs::{self};
-use convert_case::{Case, Casing};
+use cainome_rs::{self, ExpandOptions, SharedTypes};
+use camino::Utf8PathBuf;
+use convert_case::Case;
 
-use crate::error::CainomeCliResult;
+use crate::contract::ContractArtifact;
+use crate::error::{CainomeCliResult, Error};
 use crate::plugins::builtins::BuiltinPlugin;
+use crate::plugins::utils::{contract_identifier, sanitize_identifier};
 use crate::plugins::PluginInput;
 
+/// The module of the types shared by several contracts, with `--shared-types`.
+const SHARED_TYPES_MODULE: &str = "types";
+
 pub struct RustPlugin;
 
 impl RustPlugin {
@@ -16,41 +22,522 @@ impl RustPlugin {
 
 #[async_trait]
 impl BuiltinPlugin for RustPlugin {
-    async fn generate_code(&self, input: &PluginInput) -> CainomeCliResult<()> {
+    fn name(&self) -> &'static str {
+        "rust"
+    }
+
+    fn formatter(&self) -> Option<&'static str> {
+        Some("rustfmt --edition 2021")
+    }
+
+    async fn generate_code(&self, input: &PluginInput) -> CainomeCliResult<Vec<Utf8PathBuf>> {
         tracing::trace!("Rust plugin requested");
 
-        for contract in &input.contracts {
-            // The contract name contains the fully qualified path of the cairo module.
-            // For now, let's only take the latest part of this path.
-            // TODO: if a project has several contracts with the same name under different
-            // namespaces, we should provide a solution to solve those conflicts.
-            let contract_name = contract
-                .name
-                .split("::")
-                .last()
-                .unwrap_or(&contract.name)
-                .from_case(Case::Snake)
-                .to_case(Case::Pascal);
-
-            let expanded = cainome_rs::abi_to_tokenstream(
-                &contract_name,
-                &contract.tokens,
-                input.execution_version,
-                &input.derives,
-                &input.contract_derives,
-            );
-            let filename = format!(
-                "{}.rs",
-                contract_name.from_case(Case::Pascal).to_case(Case::Snake)
-            );
+        let options = ExpandOptions {
+            execution_version: input.execution_version,
+            derives: input.derives.clone(),
+            contract_derives: input.contract_derives.clone(),
+            type_derives: input.type_derives.clone(),
+            blocking: input.blocking,
+            arbitrary: input.arbitrary,
+            params_struct_threshold: input.params_struct_threshold,
+            prelude: input.prelude,
+            roundtrip_tests: input.roundtrip_tests,
+            serde_radix: input.serde_radix,
+            serde_bytes: input.serde_bytes,
+            src5: input.src5,
+            paymaster: input.paymaster,
+            consts: vec![],
+            tuple_structs: input.tuple_structs.clone(),
+            result_enums: input.result_enums.clone(),
+            generation_mode: input.generation_mode,
+            artifact: None,
+            shared_types: Default::default(),
+        };
+
+        if input.emit_examples && !input.generation_mode.has_contract() {
+            return Err(Error::Other(
+                "The examples interact with the contract, only generated in the `full` generation mode"
+                    .to_string(),
+            ));
+        }
+
+        if let Some(name) = input
+            .consts
+            .keys()
+            .find(|name| !input.contracts.iter().any(|c| &c.name == *name))
+        {
+            return Err(Error::Other(format!(
+                "Constants declared for the unknown contract `{name}`"
+            )));
+        }
+
+        let mut generated = vec![];
+
+        let contract_names: Vec<String> = input
+            .contracts
+            .iter()
+            .map(|contract| contract_identifier(contract, Case::Pascal))
+            .collect();
+        let module_names: Vec<String> = contract_names
+            .iter()
+            .map(|name| sanitize_identifier(name, Case::Pascal, Case::Snake))
+            .collect();
+
+        let shared = if input.shared_types {
+            if module_names.iter().any(|m| m == SHARED_TYPES_MODULE) {
+                return Err(Error::Other(format!(
+                    "The module `{SHARED_TYPES_MODULE}` of the shared types conflicts with the bindings of a contract"
+                )));
+            }
+
+            let tokens: Vec<_> = input.contracts.iter().map(|c| &c.tokens).collect();
+            Some(SharedTypes::new(&tokens))
+        } else {
+            None
+        };
+
+        for (i, contract) in input.contracts.iter().enumerate() {
+            let contract_name = &contract_names[i];
+
+            let consts = input
+                .consts
+                .get(&contract.name)
+                .cloned()
+                .unwrap_or_default();
+            let errors = cainome_rs::check_consts(&consts, &contract.tokens);
+            if !errors.is_empty() {
+                return Err(Error::Other(format!(
+                    "Invalid constants for the contract `{}`: {}",
+                    contract.name,
+                    errors.join(", ")
+                )));
+            }
+
+            let errors = cainome_rs::check_tuple_structs(&input.tuple_structs, &contract.tokens);
+            if !errors.is_empty() {
+                return Err(Error::Other(format!(
+                    "Invalid tuple structs for the contract `{}`: {}",
+                    contract.name,
+                    errors.join(", ")
+                )));
+            }
+
+            let errors = cainome_rs::check_result_enums(&input.result_enums, &contract.tokens);
+            if !errors.is_empty() {
+                return Err(Error::Other(format!(
+                    "Invalid result enums for the contract `{}`: {}",
+                    contract.name,
+                    errors.join(", ")
+                )));
+            }
+
+            let options = ExpandOptions {
+                consts,
+                shared_types: shared
+                    .as_ref()
+                    .map(|s| s.for_contract(i, &format!("super::{SHARED_TYPES_MODULE}")))
+                    .unwrap_or_default(),
+                ..options.clone()
+            };
+
+            let mut expanded =
+                cainome_rs::abi_to_tokenstream(contract_name, &contract.tokens, &options);
+
+            // The bindings of the contracts are expected to be sibling modules.
+            let mut sibling_modules = vec![];
+            if shared.is_some() {
+                sibling_modules.push(SHARED_TYPES_MODULE.to_string());
+            }
+
+            if input.identical_types_conversions {
+                for (j, other) in input.contracts.iter().enumerate().filter(|(j, _)| *j != i) {
+                    expanded.extend(cainome_rs::identical_types_conversions_tokenstream(
+                        &contract.tokens,
+                        &other.tokens,
+                        &format!("super::{}", module_names[j]),
+                    ));
+
+                    sibling_modules.push(module_names[j].clone());
+                }
+            }
+
+            let filename = format!("{}.rs", module_names[i]);
 
             let mut out_path = input.output_dir.clone();
-            out_path.push(filename);
+            out_path.push(&filename);
 
             tracing::trace!("Rust writing file {}", out_path);
-            std::fs::write(&out_path, expanded.to_string())?;
+            std::fs::write(
+                &out_path,
+                format!(
+                    "{}{}\n{}",
+                    artifact_header(&contract.artifact),
+                    cainome_rs::abi_to_module_doc(
+                        contract_name,
+                        &contract.tokens,
+                        input.generation_mode,
+                    ),
+                    expanded
+                ),
+            )?;
+
+            generated.push(out_path);
+
+            if input.emit_examples {
+                let example = cainome_rs::abi_to_example_tokenstream(
+                    contract_name,
+                    &contract.tokens,
+                    &format!("../{}", filename),
+                    &sibling_modules,
+                );
+
+                let examples_dir = input.output_dir.join("examples");
+                std::fs::create_dir_all(&examples_dir)?;
+
+                let example_path = examples_dir.join(&filename);
+
+                tracing::trace!("Rust writing example {}", example_path);
+                std::fs::write(
+                    &example_path,
+                    format!("{}{}", example_header(contract_name), example),
+                )?;
+
+                generated.push(example_path);
+            }
+        }
+
+        if let Some(shared) = &shared {
+            let types_path = input.output_dir.join(format!("{SHARED_TYPES_MODULE}.rs"));
+
+            tracing::trace!("Rust writing shared types {}", types_path);
+            std::fs::write(
+                &types_path,
+                format!(
+                    "{}{}",
+                    shared_types_header(&shared.tokens),
+                    cainome_rs::shared_types_to_tokenstream(shared, &options)
+                ),
+            )?;
+
+            generated.push(types_path);
+        }
+
+        if input.prelude {
+            let prelude_path = input.output_dir.join("prelude.rs");
+
+            tracing::trace!("Rust writing prelude {}", prelude_path);
+            std::fs::write(
+                &prelude_path,
+                prelude_content(&module_names, &contract_names),
+            )?;
+
+            generated.push(prelude_path);
+        }
+
+        Ok(generated)
+    }
+}
+
+/// Returns the content of the prelude file, re-exporting the preludes of the
+/// bindings of all the contracts, expected to be sibling modules.
+///
+/// # Arguments
+///
+/// * `module_names` - The modules of the bindings of the contracts.
+/// * `contract_names` - The names of the contracts, in the same order.
+fn prelude_content(module_names: &[String], contract_names: &[String]) -> String {
+    let mut content = String::from(
+        "// Flat re-exports of the bindings of all the contracts, generated by cainome.\n\
+         // The types defined by several contracts must be used from their own bindings.\n",
+    );
+
+    for (module_name, contract_name) in module_names.iter().zip(contract_names) {
+        content.push_str(&format!(
+            "pub use super::{}::{}::*;\n",
+            module_name,
+            cainome_rs::prelude_module_name(contract_name)
+        ));
+    }
+
+    content
+}
+
+/// Returns the inner doc comments of the module of the shared types.
+fn shared_types_header(tokens: &cainome_parser::TokenizedAbi) -> String {
+    format!(
+        "//! Types defined identically by several contracts, generated once by cainome.\n\
+         //! The bindings of the contracts re-export them ({} structs, {} enums).\n\n",
+        tokens.structs.len(),
+        tokens.enums.len()
+    )
+}
+
+/// Returns the comments introducing the example program of a contract.
+fn example_header(contract_name: &str) -> String {
+    format!(
+        "// Example of interactions with the `{}` contract, generated by cainome.\n\
+         // Replace the placeholder values with the ones of your deployment to run it.\n\n",
+        contract_name
+    )
+}
+
+/// Returns the comments describing the artifact the bindings are generated from,
+/// empty if nothing is known beside the ABI.
+fn artifact_header(artifact: &ContractArtifact) -> String {
+    let mut header = String::new();
+
+    if let Some(class_hash) = artifact.class_hash {
+        header.push_str(&format!("// Class hash: {:#x}\n", class_hash));
+    }
+
+    if let Some(hash) = artifact.sierra_program_hash {
+        header.push_str(&format!("// Sierra program hash: {:#x}\n", hash));
+    }
+
+    if let Some(version) = &artifact.sierra_version {
+        header.push_str(&format!("// Sierra version: {}\n", version));
+    }
+
+    if let Some(version) = &artifact.compiler_version {
+        header.push_str(&format!("// Compiler version: {}\n", version));
+    }
+
+    header
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugins::test_utils;
+    use cainome_rs::{ContractConst, GenerationMode, TupleStruct};
+    use std::collections::HashMap;
+
+    #[tokio::test]
+    async fn test_generate_code() {
+        let input = test_utils::plugin_input("rust", &["my_contract"]);
+
+        let generated = RustPlugin::new().generate_code(&input).await.unwrap();
+
+        assert_eq!(generated, vec![input.output_dir.join("my_contract.rs")]);
+
+        let bindings = std::fs::read_to_string(&generated[0]).unwrap();
+        assert!(bindings.contains("//! Bindings of the `MyContract` contract.\n"));
+    }
+
+    #[tokio::test]
+    async fn test_generate_code_with_examples() {
+        let mut input = test_utils::plugin_input("rust-examples", &["my_contract"]);
+        input.emit_examples = true;
+
+        let generated = RustPlugin::new().generate_code(&input).await.unwrap();
+
+        assert_eq!(
+            generated,
+            vec![
+                input.output_dir.join("my_contract.rs"),
+                input.output_dir.join("examples").join("my_contract.rs"),
+            ]
+        );
+
+        let example = std::fs::read_to_string(&generated[1]).unwrap();
+        assert!(example.starts_with("// Example of interactions with the `MyContract` contract"));
+        assert!(example.contains("MyContractReader :: new"));
+    }
+
+    #[tokio::test]
+    async fn test_generate_code_with_generation_mode() {
+        let mut input = test_utils::plugin_input("rust-decode", &["my_contract"]);
+        input.generation_mode = GenerationMode::Decode;
+
+        let generated = RustPlugin::new().generate_code(&input).await.unwrap();
+
+        let bindings = std::fs::read_to_string(&generated[0]).unwrap();
+        assert!(bindings.contains("//! - The types, and the decoding of the events.\n"));
+        assert!(!bindings.contains("pub struct MyContract"));
+
+        input.emit_examples = true;
+        assert!(RustPlugin::new().generate_code(&input).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_generate_code_with_identical_types_conversions() {
+        let mut input =
+            test_utils::plugin_input("rust-conversions", &["my_contract", "other_contract"]);
+        input.identical_types_conversions = true;
+
+        let abi = r#"[{
+            "type": "struct",
+            "name": "pkg::Order",
+            "members": [{ "name": "price", "type": "core::integer::u128" }]
+        }]"#;
+        for contract in &mut input.contracts {
+            contract.tokens =
+                cainome_parser::AbiParser::tokens_from_abi_string(abi, &Default::default())
+                    .unwrap();
+        }
+
+        let generated = RustPlugin::new().generate_code(&input).await.unwrap();
+
+        let bindings = std::fs::read_to_string(&generated[0]).unwrap();
+        assert!(bindings.contains("impl From < super :: other_contract :: Order > for Order"));
+
+        let bindings = std::fs::read_to_string(&generated[1]).unwrap();
+        assert!(bindings.contains("impl From < super :: my_contract :: Order > for Order"));
+    }
+
+    #[tokio::test]
+    async fn test_generate_code_with_shared_types() {
+        let mut input = test_utils::plugin_input("rust-shared-types", &["my_contract", "other"]);
+        input.shared_types = true;
+
+        let abi = r#"[{
+            "type": "struct",
+            "name": "pkg::Order",
+            "members": [{ "name": "price", "type": "core::felt252" }]
+        }]"#;
+        for contract in &mut input.contracts {
+            contract.tokens =
+                cainome_parser::AbiParser::tokens_from_abi_string(abi, &Default::default())
+                    .unwrap();
+        }
+
+        let generated = RustPlugin::new().generate_code(&input).await.unwrap();
+
+        assert_eq!(generated[2], input.output_dir.join("types.rs"));
+
+        for bindings in &generated[..2] {
+            let bindings = std::fs::read_to_string(bindings).unwrap();
+            assert!(bindings.contains("pub use super :: types :: Order ;"));
+            assert!(!bindings.contains("pub struct Order"));
         }
 
-        Ok(())
+        let types = std::fs::read_to_string(&generated[2]).unwrap();
+        assert!(types.starts_with("//! Types defined identically by several contracts"));
+        assert!(types.contains("pub struct Order"));
+
+        // The examples declare the module of the shared types used by the bindings.
+        input.emit_examples = true;
+        let generated = RustPlugin::new().generate_code(&input).await.unwrap();
+
+        let example = std::fs::read_to_string(&generated[1]).unwrap();
+        assert!(example.contains("# [path = \"../types.rs\"] # [allow (warnings)] mod types ;"));
+
+        let mut input = test_utils::plugin_input("rust-shared-types-conflict", &["types"]);
+        input.shared_types = true;
+        assert!(RustPlugin::new().generate_code(&input).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_generate_code_with_consts() {
+        let mut input = test_utils::plugin_input("rust-consts", &["my_contract"]);
+        input.contracts[0].tokens = cainome_parser::AbiParser::tokens_from_abi_string(
+            r#"[{
+                "type": "function",
+                "name": "fee_bps",
+                "inputs": [],
+                "outputs": [{ "type": "core::integer::u16" }],
+                "state_mutability": "view"
+            }]"#,
+            &Default::default(),
+        )
+        .unwrap();
+
+        let fee_bps: ContractConst =
+            serde_json::from_str(r#"{ "name": "FEE_BPS", "type": "u16", "value": "30" }"#).unwrap();
+        input
+            .consts
+            .insert("my_contract".to_string(), vec![fee_bps.clone()]);
+
+        let generated = RustPlugin::new().generate_code(&input).await.unwrap();
+
+        let bindings = std::fs::read_to_string(&generated[0]).unwrap();
+        assert!(bindings.contains("pub const FEE_BPS : u16 = 30 ;"));
+        assert!(bindings.contains("pub async fn check_consts ("));
+
+        let invalid = ContractConst {
+            view: Some("max_supply".to_string()),
+            ..fee_bps.clone()
+        };
+        input
+            .consts
+            .insert("my_contract".to_string(), vec![invalid]);
+        assert!(RustPlugin::new().generate_code(&input).await.is_err());
+
+        input.consts = HashMap::from([("unknown".to_string(), vec![fee_bps])]);
+        assert!(RustPlugin::new().generate_code(&input).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_generate_code_with_tuple_structs() {
+        let mut input = test_utils::plugin_input("rust-tuple-structs", &["my_contract"]);
+        input.contracts[0].tokens = cainome_parser::AbiParser::tokens_from_abi_string(
+            r#"[{
+                "type": "function",
+                "name": "position",
+                "inputs": [],
+                "outputs": [{ "type": "(core::felt252, core::felt252)" }],
+                "state_mutability": "view"
+            }]"#,
+            &Default::default(),
+        )
+        .unwrap();
+
+        let mut vec2: TupleStruct = serde_json::from_str(
+            r#"{ "tuple": "(core::felt252, core::felt252)", "name": "Vec2", "fields": ["x", "y"] }"#,
+        )
+        .unwrap();
+        input.tuple_structs = vec![vec2.clone()];
+
+        let generated = RustPlugin::new().generate_code(&input).await.unwrap();
+
+        let bindings = std::fs::read_to_string(&generated[0]).unwrap();
+        assert!(bindings.contains("pub struct Vec2 { pub x : starknet :: core :: types :: Felt , pub y : starknet :: core :: types :: Felt }"));
+        assert!(bindings.contains("FCall < P , Vec2 >"));
+
+        vec2.fields.pop();
+        input.tuple_structs = vec![vec2];
+        assert!(RustPlugin::new().generate_code(&input).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_generate_code_with_prelude() {
+        let mut input = test_utils::plugin_input("rust-prelude", &["my_contract", "other"]);
+        input.prelude = true;
+
+        let generated = RustPlugin::new().generate_code(&input).await.unwrap();
+
+        assert_eq!(generated[2], input.output_dir.join("prelude.rs"));
+
+        let bindings = std::fs::read_to_string(&generated[0]).unwrap();
+        assert!(bindings.contains("pub mod my_contract_prelude"));
+
+        assert_eq!(
+            std::fs::read_to_string(&generated[2]).unwrap(),
+            prelude_content(
+                &["my_contract".to_string(), "other".to_string()],
+                &["MyContract".to_string(), "Other".to_string()]
+            )
+        );
+        assert!(
+            prelude_content(&["my_contract".to_string()], &["MyContract".to_string()])
+                .ends_with("pub use super::my_contract::my_contract_prelude::*;\n")
+        );
+    }
+
+    #[test]
+    fn test_artifact_header() {
+        assert_eq!(artifact_header(&ContractArtifact::default()), "");
+
+        let artifact = ContractArtifact {
+            class_hash: Some(0x12.into()),
+            compiler_version: Some("2.8.4".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            artifact_header(&artifact),
+            "// Class hash: 0x12\n// Compiler version: 2.8.4\n"
+        );
     }
 }
@@ -0,0 +1,475 @@
+//! Manifest of the session policies of the externals, generated instead of bindings.
+//!
+//! The wallets with session keys (like the Cartridge controller) let a user pre-approve
+//! the entrypoints a session can invoke without a confirmation. The manifest lists the
+//! externals of a contract with their selector and the schema of their arguments, and
+//! suggests the limits a policy should set on them, from the types and names of the inputs.
+//!
+//! Each method is a superset of a method of the controller policies (`name` and `entrypoint`),
+//! so the `methods` can be used as the policies of the contract once deployed:
+//! `{ "contracts": { "<address>": { "methods": [...] } } }`.
+use async_trait::async_trait;
+use cainome_parser::tokens::{CompositeType, Function, StateMutability, Token};
+use cainome_parser::TokenizedAbi;
+use camino::Utf8PathBuf;
+use convert_case::{Case, Casing};
+use serde::Serialize;
+use serde_json::{json, Value};
+use starknet::core::utils::get_selector_from_name;
+
+use crate::contract::ContractOrigin;
+use crate::error::{CainomeCliResult, Error};
+use crate::plugins::builtins::BuiltinPlugin;
+use crate::plugins::utils::contract_identifier;
+use crate::plugins::PluginInput;
+
+/// Max depth of the nested types in the schemas, deeper types are only named.
+const MAX_SCHEMA_DEPTH: usize = 8;
+
+/// Parts of the entrypoint names administrating the contract, which should not be
+/// invoked by a session.
+const SENSITIVE_NAMES: [&str; 6] = [
+    "upgrade",
+    "owner",
+    "admin",
+    "role",
+    "pause",
+    "implementation",
+];
+
+/// Parts of the input names holding an amount, which a policy should bound.
+const AMOUNT_NAMES: [&str; 5] = ["amount", "value", "quantity", "price", "fee"];
+
+pub struct SessionPoliciesPlugin;
+
+impl SessionPoliciesPlugin {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl BuiltinPlugin for SessionPoliciesPlugin {
+    fn name(&self) -> &'static str {
+        "session_policies"
+    }
+
+    async fn generate_code(&self, input: &PluginInput) -> CainomeCliResult<Vec<Utf8PathBuf>> {
+        tracing::trace!("SessionPolicies plugin requested");
+
+        let mut files = vec![];
+
+        for contract in &input.contracts {
+            let contract_name = contract_identifier(contract, Case::Snake);
+
+            let address = match &contract.origin {
+                ContractOrigin::FetchedFromChain(address) => Some(format!("{:#x}", address)),
+                _ => None,
+            };
+            let manifest = PoliciesManifest::new(&contract.name, address, &contract.tokens)?;
+
+            let mut out_path = input.output_dir.clone();
+            out_path.push(format!("{}.policies.json", contract_name));
+
+            tracing::trace!("SessionPolicies writing file {}", out_path);
+            std::fs::write(&out_path, serde_json::to_string_pretty(&manifest)?)?;
+
+            files.push(out_path);
+        }
+
+        Ok(files)
+    }
+}
+
+/// The session policies of the externals of a contract.
+#[derive(Debug, Serialize)]
+struct PoliciesManifest {
+    contract: String,
+    /// Only known when the contract was fetched from the chain.
+    address: Option<String>,
+    methods: Vec<MethodPolicy>,
+}
+
+#[derive(Debug, Serialize)]
+struct MethodPolicy {
+    /// Human readable name of the method, displayed by the wallets.
+    name: String,
+    entrypoint: String,
+    selector: String,
+    /// The interface declaring the entrypoint, if any.
+    interface: Option<String>,
+    /// Whether the entrypoint administrates the contract, and should not be
+    /// approved for a session.
+    sensitive: bool,
+    inputs: Vec<InputPolicy>,
+    suggested_limits: Vec<SuggestedLimit>,
+}
+
+#[derive(Debug, Serialize)]
+struct InputPolicy {
+    name: String,
+    #[serde(rename = "type")]
+    type_path: String,
+    schema: Value,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+struct SuggestedLimit {
+    input: String,
+    /// `max_value`, `max_length`, `allowed_addresses` or `allowed_values`.
+    kind: &'static str,
+    /// The allowed values, for the `allowed_values` limits.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    values: Option<Vec<String>>,
+}
+
+impl PoliciesManifest {
+    fn new(
+        contract: &str,
+        address: Option<String>,
+        tokens: &TokenizedAbi,
+    ) -> CainomeCliResult<Self> {
+        let mut methods = vec![];
+
+        for f in &tokens.functions {
+            methods.extend(MethodPolicy::new(f.to_function()?, None)?);
+        }
+
+        let mut interfaces: Vec<_> = tokens
+            .interfaces
+            .iter()
+            .map(|(key, functions)| (tokens.interface_path(key), functions))
+            .collect();
+        interfaces.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        for (interface, functions) in interfaces {
+            for f in functions {
+                methods.extend(MethodPolicy::new(f.to_function()?, Some(interface))?);
+            }
+        }
+
+        Ok(Self {
+            contract: contract.to_string(),
+            address,
+            methods,
+        })
+    }
+}
+
+impl MethodPolicy {
+    /// Returns the policy of the function, `None` for the views which don't need one.
+    fn new(function: &Function, interface: Option<&str>) -> CainomeCliResult<Option<Self>> {
+        if function.state_mutability == StateMutability::View {
+            return Ok(None);
+        }
+
+        let selector = get_selector_from_name(&function.name).map_err(|e| {
+            Error::Other(format!("Invalid entrypoint name `{}`: {e}", function.name))
+        })?;
+
+        let lowercase_name = function.name.to_lowercase();
+        let sensitive = function.name.starts_with("__")
+            || SENSITIVE_NAMES.iter().any(|n| lowercase_name.contains(n));
+
+        let mut inputs = vec![];
+        let mut suggested_limits = vec![];
+
+        for (name, token) in &function.inputs {
+            inputs.push(InputPolicy {
+                name: name.clone(),
+                type_path: token.type_path(),
+                schema: schema(token, 0),
+            });
+
+            suggested_limits.extend(SuggestedLimit::new(name, token));
+        }
+
+        Ok(Some(Self {
+            name: function.name.to_case(Case::Title),
+            entrypoint: function.name.clone(),
+            selector: format!("{:#x}", selector),
+            interface: interface.map(String::from),
+            sensitive,
+            inputs,
+            suggested_limits,
+        }))
+    }
+}
+
+impl SuggestedLimit {
+    /// Returns the limit a policy should set on the input, if any.
+    fn new(name: &str, token: &Token) -> Option<Self> {
+        let lowercase_name = name.to_lowercase();
+        let limit = |kind, values| {
+            Some(Self {
+                input: name.to_string(),
+                kind,
+                values,
+            })
+        };
+
+        match token {
+            Token::CoreBasic(b) if b.type_name() == "ContractAddress" => {
+                limit("allowed_addresses", None)
+            }
+            Token::Array(_) => limit("max_length", None),
+            t if is_amount(t) && AMOUNT_NAMES.iter().any(|n| lowercase_name.contains(n)) => {
+                limit("max_value", None)
+            }
+            // The enums without data, like a direction, are bounded by their variants.
+            Token::Composite(c)
+                if c.r#type == CompositeType::Enum
+                    && !c.is_builtin()
+                    && c.inners.iter().all(|i| is_unit(&i.token)) =>
+            {
+                limit(
+                    "allowed_values",
+                    Some(c.inners.iter().map(|i| i.name.clone()).collect()),
+                )
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Returns whether the token is an unsigned integer, which can hold an amount.
+fn is_amount(token: &Token) -> bool {
+    match token {
+        Token::CoreBasic(b) => ["u64", "u128", "felt252"].contains(&b.type_name().as_str()),
+        Token::Composite(c) => c.type_path_no_generic() == "core::integer::u256",
+        _ => false,
+    }
+}
+
+fn is_unit(token: &Token) -> bool {
+    match token {
+        Token::Tuple(t) => t.inners.is_empty(),
+        Token::CoreBasic(b) => b.type_path == "()",
+        _ => false,
+    }
+}
+
+/// Returns the JSON schema of the type, describing how its value is given.
+fn schema(token: &Token, depth: usize) -> Value {
+    if depth > MAX_SCHEMA_DEPTH {
+        return json!({ "type": token.type_path() });
+    }
+
+    match token {
+        Token::CoreBasic(b) => json!({ "type": b.type_name() }),
+        Token::Array(a) => json!({ "type": "array", "items": schema(&a.inner, depth + 1) }),
+        Token::Tuple(t) if t.inners.is_empty() => json!({ "type": "unit" }),
+        Token::Tuple(t) => json!({
+            "type": "tuple",
+            "items": t.inners.iter().map(|i| schema(i, depth + 1)).collect::<Vec<_>>(),
+        }),
+        Token::Composite(c) if c.is_builtin() => {
+            // The Cairo name (`u256`), the type name being the one of the generated type.
+            let type_path = c.type_path_no_generic();
+            let name = type_path.rsplit("::").next().unwrap_or(&type_path);
+
+            let args: Vec<Value> = c
+                .generic_args
+                .iter()
+                .map(|(_, t)| schema(t, depth + 1))
+                .collect();
+
+            if args.is_empty() {
+                json!({ "type": name })
+            } else {
+                json!({ "type": name, "args": args })
+            }
+        }
+        Token::Composite(c) => {
+            let inners: Vec<Value> = c
+                .inners
+                .iter()
+                .map(|i| json!({ "name": i.name, "schema": schema(&i.token, depth + 1) }))
+                .collect();
+
+            match c.r#type {
+                CompositeType::Enum => {
+                    json!({ "type": "enum", "name": c.type_path_no_generic(), "variants": inners })
+                }
+                _ => {
+                    json!({ "type": "struct", "name": c.type_path_no_generic(), "members": inners })
+                }
+            }
+        }
+        Token::GenericArg(g) => json!({ "type": "generic", "name": g }),
+        Token::Function(f) => json!({ "type": "function", "name": f.name }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugins::test_utils;
+    use cainome_parser::AbiParser;
+    use std::collections::HashMap;
+
+    const ABI: &str = r#"[
+        {
+            "type": "enum",
+            "name": "pkg::Direction",
+            "variants": [
+                { "name": "Left", "type": "()" },
+                { "name": "Right", "type": "()" }
+            ]
+        },
+        {
+            "type": "struct",
+            "name": "pkg::Item",
+            "members": [
+                { "name": "id", "type": "core::integer::u32" },
+                { "name": "tags", "type": "core::array::Array::<core::felt252>" }
+            ]
+        },
+        {
+            "type": "interface",
+            "name": "pkg::IGame",
+            "items": [
+                {
+                    "type": "function",
+                    "name": "move_player",
+                    "inputs": [{ "name": "direction", "type": "pkg::Direction" }],
+                    "outputs": [],
+                    "state_mutability": "external"
+                },
+                {
+                    "type": "function",
+                    "name": "buy",
+                    "inputs": [
+                        { "name": "seller", "type": "core::starknet::contract_address::ContractAddress" },
+                        { "name": "item", "type": "pkg::Item" },
+                        { "name": "price", "type": "core::integer::u256" }
+                    ],
+                    "outputs": [],
+                    "state_mutability": "external"
+                },
+                {
+                    "type": "function",
+                    "name": "position",
+                    "inputs": [],
+                    "outputs": [{ "type": "core::integer::u32" }],
+                    "state_mutability": "view"
+                }
+            ]
+        },
+        {
+            "type": "function",
+            "name": "upgrade",
+            "inputs": [{ "name": "class_hash", "type": "core::starknet::class_hash::ClassHash" }],
+            "outputs": [],
+            "state_mutability": "external"
+        }
+    ]"#;
+
+    fn manifest() -> PoliciesManifest {
+        let tokens = AbiParser::tokens_from_abi_string(ABI, &HashMap::new()).unwrap();
+        PoliciesManifest::new("Game", None, &tokens).unwrap()
+    }
+
+    #[test]
+    fn test_methods() {
+        let manifest = manifest();
+        let methods: Vec<(&str, &str, bool)> = manifest
+            .methods
+            .iter()
+            .map(|m| (m.name.as_str(), m.entrypoint.as_str(), m.sensitive))
+            .collect();
+
+        assert_eq!(
+            methods,
+            vec![
+                ("Upgrade", "upgrade", true),
+                ("Move Player", "move_player", false),
+                ("Buy", "buy", false),
+            ]
+        );
+
+        assert_eq!(manifest.methods[1].interface.as_deref(), Some("pkg::IGame"));
+        assert_eq!(
+            manifest.methods[1].selector,
+            format!("{:#x}", get_selector_from_name("move_player").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_suggested_limits() {
+        let manifest = manifest();
+
+        assert_eq!(
+            manifest.methods[1].suggested_limits,
+            vec![SuggestedLimit {
+                input: "direction".to_string(),
+                kind: "allowed_values",
+                values: Some(vec!["Left".to_string(), "Right".to_string()]),
+            }]
+        );
+
+        let kinds: Vec<(&str, &str)> = manifest.methods[2]
+            .suggested_limits
+            .iter()
+            .map(|l| (l.input.as_str(), l.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![("seller", "allowed_addresses"), ("price", "max_value")]
+        );
+
+        assert!(manifest.methods[0].suggested_limits.is_empty());
+    }
+
+    #[test]
+    fn test_schema() {
+        let manifest = manifest();
+
+        assert_eq!(
+            manifest.methods[2].inputs[1].schema,
+            json!({
+                "type": "struct",
+                "name": "pkg::Item",
+                "members": [
+                    { "name": "id", "schema": { "type": "u32" } },
+                    { "name": "tags", "schema": { "type": "array", "items": { "type": "felt252" } } }
+                ]
+            })
+        );
+        assert_eq!(
+            manifest.methods[2].inputs[2].schema,
+            json!({ "type": "u256" })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_generate_code() {
+        let mut input = test_utils::plugin_input("session_policies", &["my_contract"]);
+        input.contracts[0].origin =
+            ContractOrigin::FetchedFromChain(starknet::core::types::Felt::ONE);
+        input.contracts[0].tokens =
+            AbiParser::tokens_from_abi_string(ABI, &HashMap::new()).unwrap();
+
+        let files = SessionPoliciesPlugin::new()
+            .generate_code(&input)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            files,
+            vec![input.output_dir.join("my_contract.policies.json")]
+        );
+
+        let json: Value =
+            serde_json::from_str(&std::fs::read_to_string(&files[0]).unwrap()).unwrap();
+        assert_eq!(json["contract"], "my_contract");
+        assert_eq!(json["address"], "0x1");
+        assert_eq!(json["methods"][1]["entrypoint"], "move_player");
+        assert!(json["methods"][1]["suggested_limits"][0]
+            .get("values")
+            .is_some());
+        assert!(json["methods"][2]["suggested_limits"][0]
+            .get("values")
+            .is_none());
+    }
+}
@@ -1,20 +1,59 @@
-use cainome_rs::ExecutionVersion;
+use cainome_rs::{
+    BytesEncoding, ContractConst, ExecutionVersion, GenerationMode, ResultEnum, SerdeRadix,
+    TupleStruct,
+};
 use camino::Utf8PathBuf;
+use std::any::Any;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
 
 pub mod builtins;
+pub mod utils;
 use builtins::BuiltinPlugins;
 
 use crate::contract::ContractData;
-use crate::error::CainomeCliResult;
-use crate::plugins::builtins::{BuiltinPlugin, RustPlugin};
+use crate::error::{CainomeCliResult, Error};
+use crate::plugins::builtins::{
+    BuiltinPlugin, CalldataReportPlugin, CoverageReportPlugin, RustPlugin, SessionPoliciesPlugin,
+};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct PluginInput {
     pub output_dir: Utf8PathBuf,
     pub contracts: Vec<ContractData>,
     pub execution_version: ExecutionVersion,
     pub derives: Vec<String>,
     pub contract_derives: Vec<String>,
+    pub type_derives: HashMap<String, Vec<String>>,
+    pub blocking: bool,
+    pub arbitrary: bool,
+    pub params_struct_threshold: Option<usize>,
+    /// Whether a `prelude` module re-exporting the generated items is generated.
+    pub prelude: bool,
+    /// Whether the serialization round-trip tests of the types are generated.
+    pub roundtrip_tests: bool,
+    /// The radix of the integers and felts in the serde implementations of the types.
+    pub serde_radix: SerdeRadix,
+    /// The encoding of the bytes in the serde implementations of the types.
+    pub serde_bytes: BytesEncoding,
+    /// Whether the SRC5 interface ids and the methods probing for them are generated.
+    pub src5: bool,
+    /// Whether the externals can be sponsored by a paymaster.
+    pub paymaster: bool,
+    /// The parts of the bindings generated by the rust plugin.
+    pub generation_mode: GenerationMode,
+    /// The constants declared for the contracts, by contract name.
+    pub consts: HashMap<String, Vec<ContractConst>>,
+    /// The fixed tuples generated as named structs.
+    pub tuple_structs: Vec<TupleStruct>,
+    /// The enums mapped to `Result`.
+    pub result_enums: Vec<ResultEnum>,
+    /// Whether an example program is generated along the bindings of each contract.
+    pub emit_examples: bool,
+    /// Whether `From` implementations are generated between the identical types of the contracts.
+    pub identical_types_conversions: bool,
+    /// Whether the types shared by several contracts are generated once in a `types` module.
+    pub shared_types: bool,
 }
 
 #[derive(Debug)]
@@ -23,22 +62,52 @@ pub struct PluginManager {
     pub builtin_plugins: Vec<BuiltinPlugins>,
     /// A list of custom plugins to invoke via stdin.
     pub plugins: Vec<String>,
+    /// Formatter commands by plugin name, overriding the default ones.
+    /// An empty command disables the formatting for the plugin.
+    pub formatters: HashMap<String, String>,
+    /// Output directories by plugin name, overriding the output directory of the input.
+    pub output_dirs: HashMap<String, Utf8PathBuf>,
+    /// Whether the generated files are formatted.
+    pub format: bool,
+}
+
+/// Report of the execution of a plugin.
+#[derive(Debug)]
+pub struct PluginReport {
+    /// Name of the plugin.
+    pub name: String,
+    /// Time spent by the plugin, formatting included.
+    pub duration: Duration,
+    /// Paths of the files written by the plugin.
+    pub files: Vec<Utf8PathBuf>,
 }
 
 impl PluginManager {
     /// Generates the bindings by calling all the configured Plugin.
-    pub async fn generate(&self, input: PluginInput) -> CainomeCliResult<()> {
+    ///
+    /// The plugins are isolated from each other: a plugin failing or panicking
+    /// doesn't prevent the others from running, its error is only returned
+    /// once all the plugins are done.
+    pub async fn generate(&self, input: PluginInput) -> CainomeCliResult<Vec<PluginReport>> {
         if self.builtin_plugins.is_empty() && self.plugins.is_empty() {
-            return Ok(());
+            return Ok(vec![]);
         }
 
-        for bp in &self.builtin_plugins {
-            let builder: Box<dyn BuiltinPlugin> = match bp {
-                BuiltinPlugins::Rust => Box::new(RustPlugin::new()),
-            };
+        let builders = self
+            .builtin_plugins
+            .iter()
+            .map(|bp| -> Box<dyn BuiltinPlugin> {
+                match bp {
+                    BuiltinPlugins::Rust => Box::new(RustPlugin::new()),
+                    BuiltinPlugins::CalldataReport => Box::new(CalldataReportPlugin::new()),
+                    BuiltinPlugins::SessionPolicies => Box::new(SessionPoliciesPlugin::new()),
+                    BuiltinPlugins::CoverageReport => Box::new(CoverageReportPlugin::new()),
+                    // cainome:new-plugin:builders
+                }
+            })
+            .collect();
 
-            builder.generate_code(&input).await?;
-        }
+        let reports = self.run_builtin_plugins(builders, input).await?;
 
         // TODO: add the plugins once stdin is supported.
         // To ensure that -> use JSON to send the list of contracts + the output dir
@@ -46,7 +115,375 @@ impl PluginManager {
         // + define a plugin output to know if it was a success of not + the list
         // of generated files.
 
-        Ok(())
+        Ok(reports)
+    }
+
+    /// Runs each builtin plugin in its own task, with its own copy of the input,
+    /// and reports the duration and the files written by each of them.
+    ///
+    /// The panics of the plugins are converted into errors. If several plugins fail,
+    /// the error of the first one (in the order of `builders`) is returned.
+    async fn run_builtin_plugins(
+        &self,
+        builders: Vec<Box<dyn BuiltinPlugin>>,
+        input: PluginInput,
+    ) -> CainomeCliResult<Vec<PluginReport>> {
+        let mut tasks = vec![];
+
+        for builder in builders {
+            let name = builder.name();
+
+            let mut input = input.clone();
+            input.output_dir = self.output_dir(name, &input.output_dir);
+
+            let formatter = if self.format {
+                match self.formatters.get(name) {
+                    Some(command) => Some(command.clone()),
+                    None => builder.formatter().map(String::from),
+                }
+            } else {
+                None
+            };
+
+            let task = tokio::spawn(async move {
+                let start = Instant::now();
+
+                std::fs::create_dir_all(&input.output_dir)?;
+                let files = builder.generate_code(&input).await?;
+
+                if let Some(command) = formatter.filter(|c| !c.trim().is_empty()) {
+                    format_files(&command, &files);
+                }
+
+                Ok::<_, Error>(PluginReport {
+                    name: name.to_string(),
+                    duration: start.elapsed(),
+                    files,
+                })
+            });
+
+            tasks.push((name, task));
+        }
+
+        let mut reports = vec![];
+        let mut first_error = None;
+
+        for (name, task) in tasks {
+            let result = match task.await {
+                Ok(result) => result,
+                Err(e) if e.is_panic() => Err(Error::PluginPanicked {
+                    plugin: name.to_string(),
+                    message: panic_message(e.into_panic()),
+                }),
+                Err(e) => Err(Error::Other(format!("Plugin `{name}` was cancelled: {e}"))),
+            };
+
+            match result {
+                Ok(report) => {
+                    tracing::info!(
+                        "Plugin `{}` wrote {} files in {:.2?}",
+                        report.name,
+                        report.files.len(),
+                        report.duration
+                    );
+                    reports.push(report);
+                }
+                Err(e) => {
+                    tracing::error!("Plugin `{name}` failed: {e}");
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+
+        match first_error {
+            Some(e) => Err(e),
+            None => Ok(reports),
+        }
+    }
+
+    /// Returns the directory where the files of the plugin `name` must be written.
+    fn output_dir(&self, name: &str, default: &Utf8PathBuf) -> Utf8PathBuf {
+        self.output_dirs
+            .get(name)
+            .cloned()
+            .unwrap_or_else(|| default.clone())
+    }
+}
+
+/// Returns the message of a panic, from its payload.
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic payload".to_string()
+    }
+}
+
+/// Runs the formatter `command` on the given files, appended as arguments.
+///
+/// A formatting failure (like a formatter not being installed) is not fatal,
+/// since the generated files are valid anyway: a warning is emitted instead.
+fn format_files(command: &str, files: &[Utf8PathBuf]) -> bool {
+    if files.is_empty() {
+        return true;
+    }
+
+    let mut args = command.split_whitespace();
+    let Some(program) = args.next() else {
+        return true;
+    };
+
+    tracing::trace!("Formatting {} files with `{command}`", files.len());
+
+    match std::process::Command::new(program)
+        .args(args)
+        .args(files)
+        .status()
+    {
+        Ok(status) if status.success() => true,
+        Ok(status) => {
+            tracing::warn!("Formatter `{command}` failed ({status}), files are left unformatted");
+            false
+        }
+        Err(e) => {
+            tracing::warn!(
+                "Formatter `{command}` could not be run ({e}), files are left unformatted"
+            );
+            false
+        }
+    }
+}
+
+/// Parses a formatter option of the form `<PLUGIN>=<COMMAND>`.
+pub fn parse_formatter(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((plugin, command)) if !plugin.is_empty() => {
+            Ok((plugin.to_string(), command.to_string()))
+        }
+        _ => Err(format!(
+            "Invalid formatter `{s}`, expected `<PLUGIN>=<COMMAND>`"
+        )),
+    }
+}
+
+/// Parses a plugin output directory option of the form `<PLUGIN>=<PATH>`.
+pub fn parse_plugin_out(s: &str) -> Result<(String, Utf8PathBuf), String> {
+    match s.split_once('=') {
+        Some((plugin, path)) if !plugin.is_empty() && !path.is_empty() => {
+            Ok((plugin.to_string(), Utf8PathBuf::from(path)))
+        }
+        _ => Err(format!(
+            "Invalid plugin output directory `{s}`, expected `<PLUGIN>=<PATH>`"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_formatter() {
+        assert_eq!(
+            parse_formatter("rust=rustfmt --edition 2021").unwrap(),
+            ("rust".to_string(), "rustfmt --edition 2021".to_string())
+        );
+        assert_eq!(
+            parse_formatter("rust=").unwrap(),
+            ("rust".to_string(), String::new())
+        );
+        assert!(parse_formatter("rustfmt").is_err());
+        assert!(parse_formatter("=rustfmt").is_err());
+    }
+
+    #[test]
+    fn test_parse_plugin_out() {
+        assert_eq!(
+            parse_plugin_out("rust=src/bindings").unwrap(),
+            ("rust".to_string(), Utf8PathBuf::from("src/bindings"))
+        );
+        assert!(parse_plugin_out("src/bindings").is_err());
+        assert!(parse_plugin_out("rust=").is_err());
+        assert!(parse_plugin_out("=src/bindings").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_generate_with_plugin_output_dir() {
+        let input = test_utils::plugin_input("plugin-out", &["my_contract"]);
+        let rust_dir = input.output_dir.join("rust");
+
+        let pm = PluginManager {
+            builtin_plugins: vec![BuiltinPlugins::Rust],
+            plugins: vec![],
+            formatters: HashMap::new(),
+            output_dirs: HashMap::from([("rust".to_string(), rust_dir.clone())]),
+            format: false,
+        };
+
+        assert_eq!(pm.output_dir("rust", &input.output_dir), rust_dir);
+        assert_eq!(pm.output_dir("other", &input.output_dir), input.output_dir);
+
+        pm.generate(input).await.unwrap();
+
+        assert!(rust_dir.join("my_contract.rs").exists());
+    }
+
+    struct PanickingPlugin;
+
+    #[async_trait::async_trait]
+    impl BuiltinPlugin for PanickingPlugin {
+        fn name(&self) -> &'static str {
+            "panicking"
+        }
+
+        async fn generate_code(&self, _input: &PluginInput) -> CainomeCliResult<Vec<Utf8PathBuf>> {
+            panic!("unsupported token");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_builtin_plugins_isolation() {
+        let input = test_utils::plugin_input("isolation", &["my_contract"]);
+        let output_dir = input.output_dir.clone();
+        let _ = std::fs::remove_file(output_dir.join("my_contract.rs"));
+
+        let pm = PluginManager {
+            builtin_plugins: vec![],
+            plugins: vec![],
+            formatters: HashMap::new(),
+            output_dirs: HashMap::new(),
+            format: false,
+        };
+
+        let err = pm
+            .run_builtin_plugins(
+                vec![Box::new(PanickingPlugin), Box::new(RustPlugin::new())],
+                input,
+            )
+            .await
+            .unwrap_err();
+
+        match err {
+            Error::PluginPanicked { plugin, message } => {
+                assert_eq!(plugin, "panicking");
+                assert_eq!(message, "unsupported token");
+            }
+            e => panic!("Unexpected error: {e}"),
+        }
+
+        // The other plugins still run.
+        assert!(output_dir.join("my_contract.rs").exists());
+    }
+
+    #[tokio::test]
+    async fn test_run_builtin_plugins_report() {
+        let input = test_utils::plugin_input("report", &["my_contract", "other"]);
+        let output_dir = input.output_dir.clone();
+
+        let pm = PluginManager {
+            builtin_plugins: vec![],
+            plugins: vec![],
+            formatters: HashMap::new(),
+            output_dirs: HashMap::new(),
+            format: false,
+        };
+
+        let reports = pm
+            .run_builtin_plugins(vec![Box::new(RustPlugin::new())], input)
+            .await
+            .unwrap();
+
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].name, "rust");
+        assert_eq!(
+            reports[0].files,
+            vec![
+                output_dir.join("my_contract.rs"),
+                output_dir.join("other.rs")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_format_files_failure_tolerance() {
+        let files = vec![Utf8PathBuf::from("my_contract.rs")];
+
+        assert!(format_files("true", &files));
+        assert!(!format_files("false", &files));
+        assert!(!format_files("cainome-unknown-formatter --check", &files));
+        // Nothing to run.
+        assert!(format_files("false", &[]));
+        assert!(format_files("  ", &files));
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test_utils {
+    use cainome_parser::TokenizedAbi;
+    use camino::Utf8PathBuf;
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::PluginInput;
+    use crate::contract::{ContractArtifact, ContractData, ContractOrigin};
+
+    /// Creates an empty temporary directory, unique to the test process and to the call.
+    pub fn temp_dir(name: &str) -> Utf8PathBuf {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        let dir = std::env::temp_dir().join(format!(
+            "cainome-{name}-{}-{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).expect("Failed to create temporary directory");
+
+        Utf8PathBuf::from_path_buf(dir).expect("Temporary directory must be UTF-8")
+    }
+
+    /// Builds a plugin input with empty contracts, writing into a
+    /// dedicated temporary directory (see [`temp_dir`]).
+    pub fn plugin_input(plugin_name: &str, contract_names: &[&str]) -> PluginInput {
+        let output_dir = temp_dir(plugin_name);
+
+        let contracts = contract_names
+            .iter()
+            .map(|name| ContractData {
+                name: name.to_string(),
+                origin: ContractOrigin::SierraClassFile(format!("{name}.contract_class.json")),
+                artifact: ContractArtifact::default(),
+                tokens: TokenizedAbi::default(),
+            })
+            .collect();
+
+        PluginInput {
+            output_dir,
+            contracts,
+            execution_version: Default::default(),
+            derives: vec![],
+            contract_derives: vec![],
+            type_derives: HashMap::new(),
+            blocking: false,
+            arbitrary: false,
+            params_struct_threshold: None,
+            prelude: false,
+            roundtrip_tests: false,
+            serde_radix: Default::default(),
+            serde_bytes: Default::default(),
+            src5: false,
+            paymaster: false,
+            generation_mode: Default::default(),
+            consts: HashMap::new(),
+            tuple_structs: vec![],
+            result_enums: vec![],
+            emit_examples: false,
+            identical_types_conversions: false,
+            shared_types: false,
+        }
     }
 }
 
@@ -0,0 +1,151 @@
+//! Utilities shared by the plugins.
+use convert_case::{Case, Casing};
+
+use crate::contract::ContractData;
+
+pub use cainome_rs::RUST_KEYWORDS;
+
+/// Name used when nothing is left of a name once sanitized.
+const FALLBACK_NAME: &str = "contract";
+
+/// Converts a name (like a contract name coming from an artifact filename) into
+/// a valid identifier in the `to` case.
+///
+/// * The ASCII letters and digits are kept, any other character (like `-`, `.`, an accented
+///   letter or an emoji) separates words: rustc rejects the non-ASCII identifiers of the
+///   modules and of the `#[path]` files (E0754).
+/// * An identifier starting with a digit is prefixed with `_`.
+/// * A reserved word is suffixed with `_`.
+///
+/// # Arguments
+///
+/// * `name` - The name to sanitize.
+/// * `from` - The case of the name, used to split it into words.
+/// * `to` - The case of the identifier.
+pub fn sanitize_identifier(name: &str, from: Case, to: Case) -> String {
+    let cleaned: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+
+    let words: Vec<&str> = cleaned.split('_').filter(|w| !w.is_empty()).collect();
+
+    let mut identifier = if words.is_empty() {
+        FALLBACK_NAME.to_case(to)
+    } else {
+        words.join("_").from_case(from).to_case(to)
+    };
+
+    if identifier.starts_with(|c: char| c.is_ascii_digit()) {
+        identifier.insert(0, '_');
+    }
+
+    if identifier == "Self" || RUST_KEYWORDS.contains(&identifier.as_str()) {
+        identifier.push('_');
+    }
+
+    identifier
+}
+
+/// Returns the name of the contract as an identifier in the `to` case.
+///
+/// The contract name contains the fully qualified path of the cairo module,
+/// only the last part of this path is used.
+///
+/// # Arguments
+///
+/// * `contract` - The contract.
+/// * `to` - The case of the identifier.
+pub fn contract_identifier(contract: &ContractData, to: Case) -> String {
+    // TODO: if a project has several contracts with the same name under different
+    // namespaces, we should provide a solution to solve those conflicts.
+    let name = contract.name.split("::").last().unwrap_or(&contract.name);
+
+    sanitize_identifier(name, Case::Snake, to)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_identifier() {
+        let pascal = |name| sanitize_identifier(name, Case::Snake, Case::Pascal);
+        let snake = |name| sanitize_identifier(name, Case::Pascal, Case::Snake);
+
+        assert_eq!(pascal("my_contract"), "MyContract");
+        assert_eq!(pascal("my-contract.v2"), "MyContractV2");
+        assert_eq!(snake("MyContract"), "my_contract");
+    }
+
+    #[test]
+    fn test_sanitize_identifier_unicode() {
+        let pascal = |name| sanitize_identifier(name, Case::Snake, Case::Pascal);
+        let snake = |name| sanitize_identifier(name, Case::Snake, Case::Snake);
+
+        assert_eq!(pascal("café_token"), "CafToken");
+        assert_eq!(snake("café_token"), "caf_token");
+        assert_eq!(pascal("🚀rocket_🌕moon"), "RocketMoon");
+        assert_eq!(pascal("日本_token"), "Token");
+        assert_eq!(pascal("日本"), "Contract");
+        assert_eq!(pascal("🚀"), "Contract");
+        assert_eq!(pascal(""), "Contract");
+    }
+
+    #[test]
+    fn test_sanitize_identifier_leading_digits() {
+        assert_eq!(
+            sanitize_identifier("1inch_router", Case::Snake, Case::Pascal),
+            "_1inchRouter"
+        );
+        assert_eq!(
+            sanitize_identifier("_1inchRouter", Case::Pascal, Case::Snake),
+            "_1_inch_router"
+        );
+        assert_eq!(
+            sanitize_identifier("🚀2moon", Case::Snake, Case::Snake),
+            "_2moon"
+        );
+    }
+
+    #[test]
+    fn test_sanitize_identifier_reserved_words() {
+        assert_eq!(
+            sanitize_identifier("type", Case::Snake, Case::Snake),
+            "type_"
+        );
+        assert_eq!(
+            sanitize_identifier("self", Case::Snake, Case::Pascal),
+            "Self_"
+        );
+        assert_eq!(
+            sanitize_identifier("Self_", Case::Pascal, Case::Snake),
+            "self_"
+        );
+        assert_eq!(
+            sanitize_identifier("types", Case::Snake, Case::Snake),
+            "types"
+        );
+    }
+
+    #[test]
+    fn test_contract_identifier() {
+        let contract = ContractData {
+            name: "pkg::contracts::my-token".to_string(),
+            origin: crate::contract::ContractOrigin::SierraClassFile("a.json".to_string()),
+            artifact: Default::default(),
+            tokens: Default::default(),
+        };
+
+        assert_eq!(contract_identifier(&contract, Case::Pascal), "MyToken");
+        assert_eq!(contract_identifier(&contract, Case::Snake), "my_token");
+
+        let contract = ContractData {
+            name: "pkg::contracts::crème_brûlée".to_string(),
+            ..contract
+        };
+
+        assert_eq!(contract_identifier(&contract, Case::Pascal), "CrMeBrLE");
+        assert_eq!(contract_identifier(&contract, Case::Snake), "cr_me_br_l_e");
+    }
+}
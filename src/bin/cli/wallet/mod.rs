@@ -0,0 +1,252 @@
+//! Credentials of the accounts sending transactions.
+//!
+//! The signing key of an account is given by one of:
+//!
+//! * `--private-key <KEY>`: the private key itself, or `env:<VAR>` to read it from the
+//!   environment variable `VAR`. `STARKNET_PRIVATE_KEY` is read if no credential source is given.
+//! * `--keystore <PATH>`: an encrypted JSON keystore, compatible with the ones of `starkli`,
+//!   decrypted with `--keystore-password` (or `STARKNET_KEYSTORE_PASSWORD`).
+//!
+//! The secrets are never part of the `Debug` output of the arguments, which are logged.
+use camino::Utf8PathBuf;
+use clap::Args;
+use starknet::core::types::Felt;
+use starknet::signers::{LocalWallet, SigningKey};
+use std::str::FromStr;
+
+use crate::error::{CainomeCliResult, Error};
+
+#[derive(Args)]
+pub struct WalletArgs {
+    #[arg(long)]
+    #[arg(value_name = "KEY")]
+    #[arg(conflicts_with = "keystore")]
+    #[arg(
+        help = "Private key of the account, or `env:<VAR>` to read it from an environment variable."
+    )]
+    pub private_key: Option<PrivateKeySource>,
+
+    #[arg(long)]
+    #[arg(value_name = "PATH")]
+    #[arg(
+        help = "Path of the encrypted JSON keystore (like the starkli ones) holding the private key of the account."
+    )]
+    pub keystore: Option<Utf8PathBuf>,
+
+    #[arg(long)]
+    #[arg(value_name = "PASSWORD")]
+    #[arg(env = "STARKNET_KEYSTORE_PASSWORD", hide_env_values = true)]
+    #[arg(requires = "keystore")]
+    #[arg(help = "Password of the keystore.")]
+    pub keystore_password: Option<String>,
+}
+
+impl WalletArgs {
+    /// Returns the signer of the account, from the credential source given.
+    pub fn signer(&self) -> CainomeCliResult<LocalWallet> {
+        Ok(LocalWallet::from(self.signing_key()?))
+    }
+
+    fn signing_key(&self) -> CainomeCliResult<SigningKey> {
+        if let Some(source) = &self.private_key {
+            return Ok(SigningKey::from_secret_scalar(source.resolve()?));
+        }
+
+        let Some(keystore) = &self.keystore else {
+            return PrivateKeySource::Env(PRIVATE_KEY_ENV.to_string())
+                .resolve()
+                .map(SigningKey::from_secret_scalar)
+                .map_err(|_| {
+                    Error::Other(format!(
+                        "A private key or a keystore is required to sign the transactions \
+                         (`--private-key`, `--keystore` or `{PRIVATE_KEY_ENV}`)"
+                    ))
+                });
+        };
+
+        let Some(password) = &self.keystore_password else {
+            return Err(Error::Other(format!(
+                "The password of the keystore {keystore} is required \
+                 (`--keystore-password` or `STARKNET_KEYSTORE_PASSWORD`)"
+            )));
+        };
+
+        SigningKey::from_keystore(keystore, password)
+            .map_err(|e| Error::Other(format!("Keystore {keystore} could not be decrypted: {e}")))
+    }
+}
+
+impl std::fmt::Debug for WalletArgs {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WalletArgs")
+            .field("private_key", &self.private_key)
+            .field("keystore", &self.keystore)
+            .field(
+                "keystore_password",
+                &self.keystore_password.as_ref().map(|_| REDACTED),
+            )
+            .finish()
+    }
+}
+
+const REDACTED: &str = "<redacted>";
+
+/// Environment variable read for the private key when no credential source is given.
+const PRIVATE_KEY_ENV: &str = "STARKNET_PRIVATE_KEY";
+
+/// Source of a private key given on the command line.
+#[derive(Clone, PartialEq)]
+pub enum PrivateKeySource {
+    /// The private key itself.
+    Key(Felt),
+    /// The name of the environment variable holding the private key.
+    Env(String),
+}
+
+impl PrivateKeySource {
+    /// Returns the private key, reading the environment if needed.
+    pub fn resolve(&self) -> CainomeCliResult<Felt> {
+        match self {
+            Self::Key(key) => Ok(*key),
+            Self::Env(var) => {
+                let value = std::env::var(var).map_err(|_| {
+                    Error::Other(format!("Environment variable `{var}` is not set"))
+                })?;
+
+                Felt::from_str(value.trim()).map_err(|_| {
+                    Error::Other(format!(
+                        "Environment variable `{var}` doesn't hold a valid private key"
+                    ))
+                })
+            }
+        }
+    }
+}
+
+impl FromStr for PrivateKeySource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(var) = s.strip_prefix("env:") {
+            if var.is_empty() {
+                return Err("Missing environment variable name, expected `env:<VAR>`".to_string());
+            }
+
+            return Ok(Self::Env(var.to_string()));
+        }
+
+        // The value is not part of the error, since it is a secret.
+        Felt::from_str(s)
+            .map(Self::Key)
+            .map_err(|_| "Invalid private key, expected a felt or `env:<VAR>`".to_string())
+    }
+}
+
+impl std::fmt::Debug for PrivateKeySource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Key(_) => f.debug_tuple("Key").field(&REDACTED).finish(),
+            Self::Env(var) => f.debug_tuple("Env").field(var).finish(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[derive(Parser)]
+    struct Cli {
+        #[command(flatten)]
+        wallet: WalletArgs,
+    }
+
+    #[test]
+    fn test_private_key_source_from_str() {
+        assert_eq!(
+            "0x1234".parse::<PrivateKeySource>().unwrap(),
+            PrivateKeySource::Key(Felt::from(0x1234))
+        );
+        assert_eq!(
+            "env:MY_KEY".parse::<PrivateKeySource>().unwrap(),
+            PrivateKeySource::Env("MY_KEY".to_string())
+        );
+        assert!("env:".parse::<PrivateKeySource>().is_err());
+        assert!("my key".parse::<PrivateKeySource>().is_err());
+    }
+
+    #[test]
+    fn test_private_key_source_resolve() {
+        std::env::set_var("CAINOME_TEST_WALLET_KEY", "0xabcd\n");
+
+        assert_eq!(
+            PrivateKeySource::Env("CAINOME_TEST_WALLET_KEY".to_string())
+                .resolve()
+                .unwrap(),
+            Felt::from(0xabcd)
+        );
+        assert!(
+            PrivateKeySource::Env("CAINOME_TEST_WALLET_UNSET".to_string())
+                .resolve()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_debug_redacts_secrets() {
+        let cli = Cli::parse_from([
+            "wallet",
+            "--keystore",
+            "keystore.json",
+            "--keystore-password",
+            "hunter2",
+        ]);
+        let debug = format!("{:?}", cli.wallet);
+        assert!(debug.contains("keystore.json"));
+        assert!(!debug.contains("hunter2"));
+
+        let cli = Cli::parse_from(["wallet", "--private-key", "0x1234abcd"]);
+        let debug = format!("{:?}", cli.wallet);
+        assert!(debug.contains(REDACTED));
+        assert!(!debug.contains("1234abcd"));
+    }
+
+    #[test]
+    fn test_keystore_signer() {
+        let path = crate::plugins::test_utils::temp_dir("wallet").join("keystore.json");
+
+        let key = SigningKey::from_secret_scalar(Felt::from(0x1234));
+        key.save_as_keystore(&path, "password").unwrap();
+
+        let wallet = |password: Option<&str>| WalletArgs {
+            private_key: None,
+            keystore: Some(path.clone()),
+            keystore_password: password.map(String::from),
+        };
+
+        assert_eq!(
+            wallet(Some("password"))
+                .signing_key()
+                .unwrap()
+                .secret_scalar(),
+            Felt::from(0x1234)
+        );
+        assert!(wallet(Some("wrong")).signing_key().is_err());
+        assert!(wallet(None).signing_key().is_err());
+
+        // An explicit keystore takes precedence over the environment.
+        std::env::set_var(PRIVATE_KEY_ENV, "0x1");
+        let cli = Cli::parse_from([
+            "wallet",
+            "--keystore",
+            path.as_str(),
+            "--keystore-password",
+            "password",
+        ]);
+        assert_eq!(
+            cli.wallet.signing_key().unwrap().secret_scalar(),
+            Felt::from(0x1234)
+        );
+    }
+}
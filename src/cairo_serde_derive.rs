@@ -63,6 +63,9 @@ mod tests {
         let deserialized = ExampleTuple::cairo_deserialize(&serialized, 0).unwrap();
 
         assert_eq!(deserialized, tuple);
+
+        let (_, size) = ExampleTuple::cairo_deserialize_with_size(&serialized, 0).unwrap();
+        assert_eq!(size, serialized.len());
     }
 
     #[test]
@@ -109,6 +112,50 @@ mod tests {
         let deserialized = ExampleEnum::cairo_deserialize(&serialized, 0).unwrap();
 
         assert_eq!(deserialized, enum_);
+
+        // Serialized at the end of an existing buffer.
+        let mut buffer = vec![felt!("9")];
+        ExampleEnum::cairo_serialize_to(&enum_, &mut buffer);
+
+        assert_eq!(buffer[0], felt!("9"));
+        assert_eq!(buffer[1..], serialized);
+
+        let (_, size) = ExampleEnum::cairo_deserialize_with_size(&buffer, 1).unwrap();
+        assert_eq!(size, serialized.len());
+    }
+
+    #[test]
+    fn test_derive_error_path() {
+        let tuple = ExampleTuple(
+            ExampleNested {
+                x: Felt::from(1),
+                y: ExampleSimple {
+                    x: vec![Felt::from(2), Felt::from(3)],
+                    y: Felt::from(4),
+                },
+            },
+            vec![Felt::from(1)],
+        );
+        let serialized = ExampleTuple::cairo_serialize(&tuple);
+
+        // The buffer ends in the middle of the nested array.
+        let err = ExampleTuple::cairo_deserialize(&serialized[..3], 0).unwrap_err();
+        assert_eq!(err.path().unwrap(), "ExampleTuple.0.y.x");
+
+        let enum_ = ExampleEnum::Tuple(
+            ExampleSimple {
+                x: vec![],
+                y: Felt::from(1),
+            },
+            ExampleSimple {
+                x: vec![Felt::from(2)],
+                y: Felt::from(3),
+            },
+        );
+        let serialized = ExampleEnum::cairo_serialize(&enum_);
+
+        let err = ExampleEnum::cairo_deserialize(&serialized[..5], 0).unwrap_err();
+        assert_eq!(err.path().unwrap(), "ExampleEnum::Tuple.1.y");
     }
 
     #[derive(Debug, CairoSerde, PartialEq)]
@@ -172,5 +219,326 @@ mod tests {
             CountEnum::cairo_deserialize(&[felt!("5")], 0).unwrap(),
             CountEnum::Five
         );
+        assert_eq!(
+            CountEnum::cairo_deserialize_with_size(&[felt!("5")], 0).unwrap(),
+            (CountEnum::Five, 1)
+        );
+    }
+
+    #[derive(Debug, CairoSerde, PartialEq)]
+    struct ExampleAdapter {
+        x: Felt,
+        #[cairo_serde(with = cainome_cairo_serde::SerdeAdapter<(u64, u64), std::time::Duration>)]
+        y: std::time::Duration,
+    }
+
+    #[derive(Debug, CairoSerde, PartialEq)]
+    enum ExampleAdapterEnum {
+        Secs(
+            #[cairo_serde(with = cainome_cairo_serde::SerdeAdapter<u64, std::time::Duration>)]
+            std::time::Duration,
+        ),
+    }
+
+    #[test]
+    fn test_derive_with_adapter() {
+        let example = ExampleAdapter {
+            x: Felt::ONE,
+            y: std::time::Duration::new(2, 3),
+        };
+
+        let serialized = ExampleAdapter::cairo_serialize(&example);
+        assert_eq!(serialized, vec![felt!("1"), felt!("2"), felt!("3")]);
+        assert_eq!(
+            ExampleAdapter::cairo_deserialize(&serialized, 0).unwrap(),
+            example
+        );
+
+        let example = ExampleAdapterEnum::Secs(std::time::Duration::from_secs(4));
+        let serialized = ExampleAdapterEnum::cairo_serialize(&example);
+        assert_eq!(serialized, vec![felt!("0"), felt!("4")]);
+        assert_eq!(
+            ExampleAdapterEnum::cairo_deserialize(&serialized, 0).unwrap(),
+            example
+        );
+    }
+
+    #[cfg(feature = "secret")]
+    mod secret {
+        use cainome_cairo_serde::{CairoSerde, Choice, ConstantTimeEq, Zeroize};
+        use cainome_cairo_serde_derive::CairoSerde;
+        use starknet::macros::felt;
+        use starknet_types_core::felt::Felt;
+
+        #[derive(Debug, PartialEq)]
+        struct SessionKey(Felt);
+
+        impl cainome_cairo_serde::Adapter<Felt> for SessionKey {
+            fn to_cairo(&self) -> Felt {
+                self.0
+            }
+
+            fn from_cairo(cairo: Felt) -> cainome_cairo_serde::Result<Self> {
+                Ok(Self(cairo))
+            }
+        }
+
+        impl Zeroize for SessionKey {
+            fn zeroize(&mut self) {
+                self.0.zeroize();
+            }
+        }
+
+        impl ConstantTimeEq for SessionKey {
+            fn ct_eq(&self, other: &Self) -> Choice {
+                self.0.ct_eq(&other.0)
+            }
+        }
+
+        #[derive(Debug, CairoSerde, PartialEq)]
+        struct ExampleSecret {
+            owner: Felt,
+            token: cainome_cairo_serde::Secret<Felt>,
+            #[cairo_serde(with = cainome_cairo_serde::Secret<cainome_cairo_serde::SerdeAdapter<Felt, SessionKey>>)]
+            key: cainome_cairo_serde::Secret<SessionKey>,
+        }
+
+        #[test]
+        fn test_derive_with_secret() {
+            use cainome_cairo_serde::Secret;
+
+            let example = ExampleSecret {
+                owner: Felt::ONE,
+                token: Secret::new(Felt::TWO),
+                key: Secret::new(SessionKey(Felt::THREE)),
+            };
+
+            let serialized = ExampleSecret::cairo_serialize(&example);
+            assert_eq!(serialized, vec![felt!("1"), felt!("2"), felt!("3")]);
+            assert_eq!(
+                ExampleSecret::cairo_deserialize(&serialized, 0).unwrap(),
+                example
+            );
+            assert!(!format!("{:?}", example).contains("0x3"));
+        }
+    }
+
+    #[derive(Debug, CairoSerde, PartialEq)]
+    struct OrderId(Felt);
+
+    #[derive(Debug, CairoSerde, PartialEq)]
+    #[repr(transparent)]
+    struct Amount {
+        value: cainome_cairo_serde::U256,
+    }
+
+    #[derive(Debug, CairoSerde, PartialEq)]
+    struct Tags(Vec<Felt>);
+
+    #[derive(Debug, CairoSerde, PartialEq)]
+    struct Order {
+        id: OrderId,
+        amount: Amount,
+    }
+
+    #[test]
+    fn test_derive_transparent() {
+        assert_eq!(OrderId::SERIALIZED_SIZE, Some(1));
+        assert_eq!(Amount::SERIALIZED_SIZE, Some(2));
+        assert_eq!(Tags::SERIALIZED_SIZE, None);
+
+        let id = OrderId(felt!("0x123"));
+        assert_eq!(OrderId::cairo_serialize(&id), vec![felt!("0x123")]);
+        assert_eq!(
+            OrderId::cairo_deserialize(&[felt!("0x123")], 0).unwrap(),
+            id
+        );
+
+        let tags = Tags(vec![felt!("1"), felt!("2")]);
+        let serialized = Tags::cairo_serialize(&tags);
+        assert_eq!(serialized, Vec::<Felt>::cairo_serialize(&tags.0));
+        assert_eq!(Tags::cairo_serialized_size(&tags), 3);
+        assert_eq!(Tags::cairo_deserialize(&serialized, 0).unwrap(), tags);
+
+        // The newtypes interop with the data of their inner types.
+        let order = Order {
+            id,
+            amount: Amount {
+                value: cainome_cairo_serde::U256 { low: 5, high: 0 },
+            },
+        };
+        let serialized = Order::cairo_serialize(&order);
+        assert_eq!(serialized, vec![felt!("0x123"), felt!("5"), felt!("0")]);
+        assert_eq!(Order::cairo_deserialize(&serialized, 0).unwrap(), order);
+
+        let err = Order::cairo_deserialize(&serialized[..2], 0).unwrap_err();
+        // The transparent structs are not part of the path.
+        assert_eq!(err.path().unwrap(), "Order.amount");
+    }
+
+    #[derive(Debug, CairoSerde, PartialEq)]
+    struct Pair<T> {
+        a: T,
+        b: T,
+    }
+
+    #[derive(Debug, CairoSerde, PartialEq)]
+    struct Tagged<K: Copy, V>
+    where
+        K: PartialEq,
+    {
+        key: K,
+        values: Vec<V>,
+        count: u32,
+    }
+
+    #[derive(Debug, CairoSerde, PartialEq)]
+    struct Wrapper<T>(T);
+
+    #[derive(Debug, CairoSerde, PartialEq)]
+    enum Either<L, R> {
+        Left(L),
+        Right { value: R },
+    }
+
+    #[test]
+    fn test_derive_generics() {
+        let pair = Pair {
+            a: cainome_cairo_serde::U256 { low: 1, high: 0 },
+            b: cainome_cairo_serde::U256 { low: 2, high: 0 },
+        };
+        let serialized = Pair::<cainome_cairo_serde::U256>::cairo_serialize(&pair);
+        assert_eq!(
+            serialized,
+            vec![felt!("1"), felt!("0"), felt!("2"), felt!("0")]
+        );
+        assert_eq!(
+            Pair::<cainome_cairo_serde::U256>::cairo_deserialize(&serialized, 0).unwrap(),
+            pair
+        );
+
+        // The `RustType` of the parameters is mapped, the one of an adapter is the adapted type.
+        let tagged = Tagged {
+            key: 7_u8,
+            values: vec![std::time::Duration::from_secs(3)],
+            count: 2,
+        };
+        type CairoTagged = Tagged<u8, cainome_cairo_serde::SerdeAdapter<u64, std::time::Duration>>;
+        let serialized = CairoTagged::cairo_serialize(&tagged);
+        assert_eq!(
+            serialized,
+            vec![felt!("7"), felt!("1"), felt!("3"), felt!("2")]
+        );
+        assert_eq!(
+            CairoTagged::cairo_deserialize(&serialized, 0).unwrap(),
+            tagged
+        );
+
+        assert_eq!(Wrapper::<u32>::SERIALIZED_SIZE, Some(1));
+        assert_eq!(
+            Wrapper::<Pair<Felt>>::cairo_serialize(&Wrapper(Pair {
+                a: felt!("1"),
+                b: felt!("2")
+            })),
+            vec![felt!("1"), felt!("2")]
+        );
+
+        let either = Either::<u8, Felt>::Right { value: felt!("3") };
+        let serialized = Either::<u8, Felt>::cairo_serialize(&either);
+        assert_eq!(serialized, vec![felt!("1"), felt!("3")]);
+        assert_eq!(
+            Either::<u8, Felt>::cairo_deserialize(&serialized, 0).unwrap(),
+            either
+        );
+    }
+
+    #[derive(Debug, CairoSerde, PartialEq)]
+    struct Position {
+        #[cairo_serde(rename = "positionId")]
+        id: Felt,
+        #[cairo_serde(skip)]
+        cached_value: Option<u64>,
+        size: u128,
+    }
+
+    #[derive(Debug, CairoSerde, PartialEq)]
+    #[repr(transparent)]
+    struct Typed<T> {
+        value: Felt,
+        #[cairo_serde(skip)]
+        marker: std::marker::PhantomData<T>,
+    }
+
+    #[derive(Debug, CairoSerde, PartialEq)]
+    enum Action {
+        Open(#[cairo_serde(skip)] u8, Felt),
+        Close {
+            #[cairo_serde(rename = "closeId")]
+            id: Felt,
+            #[cairo_serde(skip)]
+            reason: String,
+        },
+    }
+
+    #[test]
+    fn test_derive_skip_and_rename() {
+        let position = Position {
+            id: felt!("1"),
+            cached_value: Some(3),
+            size: 2,
+        };
+
+        let serialized = Position::cairo_serialize(&position);
+        assert_eq!(serialized, vec![felt!("1"), felt!("2")]);
+        assert_eq!(Position::cairo_serialized_size(&position), 2);
+
+        let deserialized = Position::cairo_deserialize(&serialized, 0).unwrap();
+        assert_eq!(
+            deserialized,
+            Position {
+                cached_value: None,
+                ..position
+            }
+        );
+
+        let err = Position::cairo_deserialize(&[], 0).unwrap_err();
+        assert_eq!(err.path().unwrap(), "Position.positionId");
+
+        // The skipped fields don't prevent a struct from being transparent.
+        assert_eq!(Typed::<u8>::SERIALIZED_SIZE, Some(1));
+        let typed = Typed::<u8> {
+            value: felt!("4"),
+            marker: std::marker::PhantomData,
+        };
+        assert_eq!(Typed::<u8>::cairo_serialize(&typed), vec![felt!("4")]);
+        assert_eq!(
+            Typed::<u8>::cairo_deserialize(&[felt!("4")], 0).unwrap(),
+            typed
+        );
+
+        let open = Action::Open(7, felt!("5"));
+        let serialized = Action::cairo_serialize(&open);
+        assert_eq!(serialized, vec![felt!("0"), felt!("5")]);
+        assert_eq!(
+            Action::cairo_deserialize(&serialized, 0).unwrap(),
+            Action::Open(0, felt!("5"))
+        );
+
+        let close = Action::Close {
+            id: felt!("6"),
+            reason: "expired".to_string(),
+        };
+        let serialized = Action::cairo_serialize(&close);
+        assert_eq!(serialized, vec![felt!("1"), felt!("6")]);
+        assert_eq!(
+            Action::cairo_deserialize(&serialized, 0).unwrap(),
+            Action::Close {
+                id: felt!("6"),
+                reason: String::new(),
+            }
+        );
+
+        let err = Action::cairo_deserialize(&[felt!("1")], 0).unwrap_err();
+        assert_eq!(err.path().unwrap(), "Action::Close.closeId");
     }
 }
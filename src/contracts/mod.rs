@@ -0,0 +1,143 @@
+//! Prebuilt bindings of the OpenZeppelin standard interfaces.
+//!
+//! The bindings are generated with `abigen!` from the ABIs vendored in `src/contracts/abi`,
+//! which only contain the standard interfaces and events of the OpenZeppelin presets.
+//! Any contract implementing those interfaces can be used with them:
+//!
+//! ```ignore
+//! use cainome::contracts::erc20::Erc20Reader;
+//!
+//! let token = Erc20Reader::new(token_address, &provider);
+//! let balance = token.balance_of(&account).call().await?;
+//! ```
+
+/// Bindings of the ERC20 tokens (`IERC20` and `IERC20Metadata`).
+pub mod erc20 {
+    crate::rs::abigen!(
+        Erc20,
+        "src/contracts/abi/erc20.json",
+        type_aliases {
+            openzeppelin_token::erc20::erc20::ERC20Component::Event as Erc20Event;
+        },
+        derives(Debug, Clone, PartialEq)
+    );
+}
+
+/// Bindings of the ERC721 tokens (`IERC721`, `IERC721Metadata` and `ISRC5`).
+pub mod erc721 {
+    crate::rs::abigen!(
+        Erc721,
+        "src/contracts/abi/erc721.json",
+        type_aliases {
+            openzeppelin_token::erc721::erc721::ERC721Component::Event as Erc721Event;
+        },
+        derives(Debug, Clone, PartialEq),
+        src5
+    );
+}
+
+/// Bindings of the ERC1155 tokens (`IERC1155`, `IERC1155MetadataURI` and `ISRC5`).
+pub mod erc1155 {
+    crate::rs::abigen!(
+        Erc1155,
+        "src/contracts/abi/erc1155.json",
+        type_aliases {
+            openzeppelin_token::erc1155::erc1155::ERC1155Component::Event as Erc1155Event;
+        },
+        derives(Debug, Clone, PartialEq),
+        src5
+    );
+}
+
+/// Bindings of the accounts (`ISRC6`, `IDeclarer`, `IDeployable`, `IPublicKey` and `ISRC5`).
+pub mod account {
+    crate::rs::abigen!(
+        Account,
+        "src/contracts/abi/account.json",
+        type_aliases {
+            openzeppelin_account::account::AccountComponent::Event as AccountEvent;
+        },
+        derives(Debug, Clone, PartialEq),
+        src5
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use starknet::core::types::{EmittedEvent, Felt};
+    use starknet::core::utils::get_selector_from_name;
+    use starknet::providers::{jsonrpc::HttpTransport, JsonRpcClient, Url};
+
+    use crate::cairo_serde::{ContractAddress, U256};
+
+    fn provider() -> JsonRpcClient<HttpTransport> {
+        JsonRpcClient::new(HttpTransport::new(
+            Url::parse("http://localhost:5050").unwrap(),
+        ))
+    }
+
+    #[test]
+    fn test_erc20_calls() {
+        use super::erc20::*;
+
+        let provider = provider();
+        let token = Erc20Reader::new(Felt::ONE, &provider);
+
+        let call = token.balance_of(&ContractAddress(Felt::TWO));
+        assert_eq!(
+            call.call_raw.entry_point_selector,
+            get_selector_from_name("balance_of").unwrap()
+        );
+        assert_eq!(call.call_raw.calldata, vec![Felt::TWO]);
+    }
+
+    #[test]
+    fn test_erc20_transfer_event() {
+        use super::erc20::*;
+
+        let emitted = EmittedEvent {
+            from_address: Felt::ONE,
+            keys: vec![
+                get_selector_from_name("Transfer").unwrap(),
+                Felt::TWO,
+                Felt::THREE,
+            ],
+            data: vec![Felt::from(10), Felt::ZERO],
+            block_hash: None,
+            block_number: None,
+            transaction_hash: Felt::ZERO,
+        };
+
+        assert_eq!(
+            Event::try_from(&emitted).unwrap(),
+            Event::ERC20Event(Erc20Event::Transfer(Transfer {
+                from: ContractAddress(Felt::TWO),
+                to: ContractAddress(Felt::THREE),
+                value: U256 { low: 10, high: 0 },
+            }))
+        );
+    }
+
+    #[test]
+    fn test_interface_ids() {
+        // The ids published by OpenZeppelin.
+        assert_eq!(
+            super::erc721::ERC721_IERC721_INTERFACE_ID,
+            Felt::from_hex_unchecked(
+                "0x33eb2f84c309543403fd69f0d0f363781ef06ef6faeb0131ff16ea3175bd943"
+            )
+        );
+        assert_eq!(
+            super::erc1155::ERC1155_IERC1155_INTERFACE_ID,
+            Felt::from_hex_unchecked(
+                "0x6114a8f75559e1b39fcba08ce02961a1aa082d9256a158dd3e64964e4b1b52"
+            )
+        );
+        assert_eq!(
+            super::account::ACCOUNT_ISRC6_INTERFACE_ID,
+            Felt::from_hex_unchecked(
+                "0x2ceccef7f994940b3962a6c67e0ba4fcd37df7d131417c604f91e03caecc1cd"
+            )
+        );
+    }
+}
@@ -1,17 +1,27 @@
 //! Cainome crate.
 
+// The prebuilt bindings refer to the crate by its name, like the user crates.
+#[cfg(feature = "contracts")]
+extern crate self as cainome;
+
 pub mod cairo_serde {
     pub use cainome_cairo_serde::*;
 }
 
+#[cfg(feature = "derive")]
 pub mod cairo_serde_derive;
 
+#[cfg(feature = "parser")]
 pub mod parser {
     pub use cainome_parser::*;
 }
 
-#[cfg(feature = "abigen-rs")]
+#[cfg(feature = "rs")]
 pub mod rs {
     pub use cainome_rs::*;
+    #[cfg(feature = "abigen-rs")]
     pub use cainome_rs_macro::*;
 }
+
+#[cfg(feature = "contracts")]
+pub mod contracts;